@@ -0,0 +1,77 @@
+//! CPU vs GPU color conversion benchmarks
+//!
+//! Run with: cargo bench --features gpu --bench convert_benchmarks
+//!
+//! Compare the `cpu` and `gpu` timings per resolution to find the frame size
+//! at which the GPU path starts winning on a given machine, and adjust
+//! `GPU_CONVERT_MIN_PIXELS` accordingly.
+
+use crabcamera::platform::convert::{to_rgb8, GpuConverter};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::time::Duration;
+
+const RESOLUTIONS: [(u32, u32, &str); 4] = [
+    (640, 480, "480p"),
+    (1280, 720, "720p"),
+    (1920, 1080, "1080p"),
+    (3840, 2160, "4k"),
+];
+
+/// Generate a YUYV or NV12 buffer with a gradient pattern
+fn generate_test_yuv(width: u32, height: u32, format: &str) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    match format {
+        "YUYV" => (0..w * h * 2)
+            .map(|i| if i % 2 == 0 { (i / 2 % 256) as u8 } else { 128 })
+            .collect(),
+        _ => {
+            let mut data: Vec<u8> = (0..w * h).map(|i| (i % 256) as u8).collect();
+            data.extend((0..w * h / 2).map(|i| (i % 64 + 96) as u8));
+            data
+        }
+    }
+}
+
+fn bench_yuv_to_rgb8(c: &mut Criterion) {
+    let Ok(mut gpu) = GpuConverter::new() else {
+        eprintln!("No GPU adapter available; benchmarking the CPU path only");
+        return bench_cpu_only(c);
+    };
+
+    for format in ["YUYV", "NV12"] {
+        let mut group = c.benchmark_group(format!("{format}_to_rgb8"));
+        group.measurement_time(Duration::from_secs(5));
+
+        for (width, height, name) in RESOLUTIONS {
+            let data = generate_test_yuv(width, height, format);
+            group.throughput(Throughput::Elements(u64::from(width) * u64::from(height)));
+
+            group.bench_with_input(BenchmarkId::new("cpu", name), &data, |b, data| {
+                b.iter(|| to_rgb8(black_box(data), width, height, format).unwrap());
+            });
+            group.bench_with_input(BenchmarkId::new("gpu", name), &data, |b, data| {
+                b.iter(|| gpu.convert(black_box(data), width, height, format).unwrap());
+            });
+        }
+
+        group.finish();
+    }
+}
+
+fn bench_cpu_only(c: &mut Criterion) {
+    for format in ["YUYV", "NV12"] {
+        let mut group = c.benchmark_group(format!("{format}_to_rgb8"));
+        for (width, height, name) in RESOLUTIONS {
+            let data = generate_test_yuv(width, height, format);
+            group.throughput(Throughput::Elements(u64::from(width) * u64::from(height)));
+            group.bench_with_input(BenchmarkId::new("cpu", name), &data, |b, data| {
+                b.iter(|| to_rgb8(black_box(data), width, height, format).unwrap());
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_yuv_to_rgb8);
+
+criterion_main!(benches);
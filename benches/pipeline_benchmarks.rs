@@ -0,0 +1,41 @@
+//! End-to-end capture → convert → encode → mux throughput
+//!
+//! Run with: cargo bench --features bench --bench pipeline_benchmarks
+//!
+//! Prints fps, per-stage latency and allocations per frame for a YUYV
+//! camera at each resolution. Add `gpu` to the features to see the effect
+//! of GPU conversion on the convert stage.
+
+use crabcamera::bench::{run_pipeline_bench, CountingAllocator, PipelineBenchConfig};
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+const RESOLUTIONS: [(u32, u32, &str); 3] = [
+    (640, 480, "480p"),
+    (1280, 720, "720p"),
+    (1920, 1080, "1080p"),
+];
+
+fn main() {
+    println!(
+        "{:<6} {:>7} {:>11} {:>11} {:>11} {:>11} {:>12}",
+        "res", "fps", "capture ms", "convert ms", "encode ms", "mux ms", "allocs/frame"
+    );
+    for (width, height, label) in RESOLUTIONS {
+        let config = PipelineBenchConfig::new(width, height);
+        match run_pipeline_bench(&config) {
+            Ok(report) => println!(
+                "{:<6} {:>7.1} {:>11.3} {:>11.3} {:>11.3} {:>11.3} {:>12.1}",
+                label,
+                report.fps,
+                report.capture.mean_ms,
+                report.convert.map_or(0.0, |s| s.mean_ms),
+                report.encode.mean_ms,
+                report.mux.mean_ms,
+                report.allocations.map_or(0.0, |a| a.per_frame),
+            ),
+            Err(e) => eprintln!("{label}: {e}"),
+        }
+    }
+}
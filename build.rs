@@ -2,37 +2,88 @@
 const COMMANDS: &[&str] = &[
     "initialize_camera_system",
     "get_available_cameras",
+    "refresh_cameras",
     "get_platform_info",
     "test_camera_system",
     "get_current_platform",
+    "get_enabled_features",
+    "require_feature",
     "check_camera_availability",
+    "check_camera_in_use",
     "get_camera_formats",
     "get_recommended_format",
+    "get_format_presets",
+    "get_format_tradeoff",
+    "get_max_resolution_format",
     "get_optimal_settings",
     "get_system_diagnostics",
     "request_camera_permission",
     "check_camera_permission_status",
     "get_permission_status_string",
+    "get_camera_permission_guidance",
+    "open_camera_privacy_settings",
+    "request_microphone_permission",
+    "check_microphone_permission_status",
     "capture_single_photo",
     "capture_photo_sequence",
+    "capture_photo_sequence_streaming",
+    "capture_gif",
+    "arm_capture",
+    "fire_capture",
+    "disarm_capture",
+    "cancel_operation",
     "capture_with_quality_retry",
     "start_camera_preview",
+    "start_camera_preview_with_format_index",
+    "capture_single_photo_with_format_index",
+    "capture_max_resolution_photo",
     "stop_camera_preview",
+    "set_camera_format",
     "release_camera",
     "get_capture_stats",
+    "get_session_metrics",
+    "pause_camera",
+    "resume_camera",
+    "reset_session_metrics",
     "save_frame_to_disk",
     "save_frame_compressed",
+    "save_frame_auto",
+    "cleanup_temp_captures",
+    "start_timelapse",
+    "stop_timelapse",
+    "get_timelapse_status",
+    "clear_timelapse_status",
     "set_frame_callback",
+    "get_queued_frames",
+    "set_frame_orientation",
+    "capture_depth_frame",
     "set_camera_controls",
     "get_camera_controls",
     "capture_burst_sequence",
     "set_manual_focus",
     "set_manual_exposure",
+    "set_exposure_mode",
+    "set_exposure",
+    "set_focus_mode",
+    "get_exposure_settings",
     "set_white_balance",
+    "set_white_balance_kelvin",
+    "set_white_balance_preset",
+    "preset_settings",
+    "apply_preset",
+    "trigger_autofocus",
+    "set_focus_point",
     "capture_hdr_sequence",
     "capture_focus_stack_legacy",
     "get_camera_performance",
     "test_camera_capabilities",
+    "get_camera_capabilities_cached",
+    "patch_camera_controls",
+    "begin_controls",
+    "stage_controls",
+    "commit_controls",
+    "discard_controls",
+    "capture_autoframe",
     "validate_frame_quality",
     "validate_provided_frame",
     "analyze_frame_blur",
@@ -41,8 +92,13 @@ const COMMANDS: &[&str] = &[
     "get_quality_config",
     "capture_best_quality_frame",
     "auto_capture_with_quality",
+    "auto_capture_with_enhancement",
+    "capture_denoised",
     "analyze_quality_trends",
     "get_config",
+    "get_config_schema",
+    "validate_config",
+    "clear_device_config",
     "update_config",
     "reset_config",
     "get_camera_config",
@@ -53,6 +109,8 @@ const COMMANDS: &[&str] = &[
     "update_full_quality_config",
     "update_storage_config",
     "update_advanced_config",
+    "save_camera_profile",
+    "load_camera_profile",
     "start_device_monitoring",
     "stop_device_monitoring",
     "poll_device_event",
@@ -61,6 +119,22 @@ const COMMANDS: &[&str] = &[
     "capture_focus_brackets_command",
     "get_default_focus_config",
     "validate_focus_config",
+    "capture_focus_stack_streaming",
+    "capture_and_pick_sharpest",
+    "start_recording",
+    "record_frame",
+    "stop_recording",
+    "get_recording_status",
+    "list_recording_sessions",
+    "start_camera_recording",
+    "stop_camera_recording",
+    "start_mjpeg_server",
+    "stop_mjpeg_server",
+    "start_rtsp_server",
+    "stop_rtsp_server",
+    "list_audio_devices",
+    "list_loopback_devices",
+    "get_default_audio_device",
 ];
 
 fn main() {
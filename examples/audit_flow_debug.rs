@@ -18,7 +18,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Step 1: Start preview (this creates camera in registry)
     println!("[1] start_camera_preview({}, None)...", device_id);
-    match start_camera_preview(device_id.clone(), None).await {
+    match start_camera_preview(device_id.clone(), None, None).await {
         Ok(msg) => println!("    OK: {}", msg),
         Err(e) => println!("    ERROR: {}", e),
     }
@@ -36,7 +36,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Now capture - this reuses the registry camera
     println!("\n[4] capture_single_photo({}, None)...", device_id);
-    match capture_single_photo(Some(device_id.clone()), None).await {
+    match capture_single_photo(Some(device_id.clone()), None, None).await {
         Ok(frame) => {
             println!(
                 "    OK: {}x{}, {} bytes",
@@ -56,7 +56,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Step 4: Start camera preview
     println!("\n▶️  Starting camera preview...");
     let format = CameraFormat::standard(); // 1280x720 @ 30fps
-    match start_camera_preview(device_id.clone(), Some(format)).await {
+    match start_camera_preview(device_id.clone(), Some(format), None).await {
         Ok(message) => println!("✅ {}", message),
         Err(e) => {
             eprintln!("❌ Failed to start preview: {}", e);
@@ -71,7 +71,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Step 5: Capture some frames while preview is running
     println!("\n📸 Capturing frames from active preview stream...");
     for i in 1..=5 {
-        match capture_single_photo(Some(device_id.clone()), None).await {
+        match capture_single_photo(Some(device_id.clone()), None, None).await {
             Ok(frame) => {
                 println!(
                     "  Frame {}: {}x{} pixels ({} bytes) at {}",
@@ -398,7 +398,7 @@ async fn main() {
 
     // Test: start_camera_preview
     print!("  [5.1] start_camera_preview({}) ... ", device_id);
-    match start_camera_preview(device_id.clone(), None).await {
+    match start_camera_preview(device_id.clone(), None, None).await {
         Ok(msg) => {
             println!("✅ {}", msg);
             results.push(TestResult::pass("start_camera_preview"));
@@ -448,7 +448,7 @@ async fn main() {
 
     // Test: capture_single_photo
     print!("  [6.1] capture_single_photo({}) ... ", device_id);
-    let captured_frame = match capture_single_photo(Some(device_id.clone()), None).await {
+    let captured_frame = match capture_single_photo(Some(device_id.clone()), None, None).await {
         Ok(frame) => {
             println!(
                 "✅ {}x{}, {} bytes",
@@ -496,7 +496,7 @@ async fn main() {
 
     // Test: capture_photo_sequence
     print!("  [6.4] capture_photo_sequence (3 photos) ... ");
-    match capture_photo_sequence(device_id.clone(), 3, 200, None).await {
+    match capture_photo_sequence(device_id.clone(), 3, 200, None, None).await {
         Ok(frames) => {
             println!("✅ Captured {} frames", frames.len());
             results.push(TestResult::pass("capture_photo_sequence"));
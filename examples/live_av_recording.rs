@@ -76,7 +76,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("-----------------------");
 
         let format = CameraFormat::standard(); // 1280x720 @ 30fps
-        start_camera_preview(device_id.clone(), Some(format.clone())).await?;
+        start_camera_preview(device_id.clone(), Some(format.clone()), None).await?;
         println!(
             "   ✅ Camera preview started at {}x{}",
             format.width, format.height
@@ -86,7 +86,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         sleep(tokio::time::Duration::from_millis(500)).await;
 
         // Get a test frame to confirm it works
-        let test_frame = capture_single_photo(Some(device_id.clone()), None).await?;
+        let test_frame = capture_single_photo(Some(device_id.clone()), None, None).await?;
         println!(
             "   ✅ Test frame captured: {}x{}",
             test_frame.width, test_frame.height
@@ -109,9 +109,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 );
                 config = config.with_audio(AudioConfig {
                     device_id: Some(device.id.clone()),
+                    device_kind: crabcamera::audio::DeviceKind::Input,
                     sample_rate: device.sample_rate,
                     channels: device.channels,
                     bitrate: 128_000,
+                    auto_fallback: false,
                 });
             }
         } else {
@@ -121,6 +123,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("   Output: {:?}", output_path);
 
         let mut recorder = Recorder::new(&output_path, config)?;
+        // Wait for the microphone so both tracks start together
+        recorder.start();
         println!("   ✅ Recorder initialized");
 
         // Step 6: Record for 5 seconds
@@ -135,8 +139,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let mut frame_count = 0u64;
 
         while start.elapsed() < duration {
-            // Capture frame from camera (no sleep - grab as fast as possible)
-            match capture_single_photo(Some(device_id.clone()), None).await {
+            // Capture frame from camera (no sleep or warmup - grab as fast as possible)
+            match capture_single_photo(Some(device_id.clone()), None, Some(0)).await {
                 Ok(frame) => {
                     // Write to recorder
                     recorder.write_frame(&frame)?;
@@ -157,7 +157,7 @@ async fn main() {
         format.width, format.height, format.fps
     );
 
-    match start_camera_preview(device_id.clone(), Some(format.clone())).await {
+    match start_camera_preview(device_id.clone(), Some(format.clone()), None).await {
         Ok(msg) => println!("   ✅ {}", msg),
         Err(e) => {
             println!("   ❌ Failed to start stream: {}", e);
@@ -173,7 +173,7 @@ async fn main() {
     println!("📋 STEP 7: Capture Test Photo");
     println!("─────────────────────────────────────");
 
-    match capture_single_photo(Some(device_id.clone()), Some(format)).await {
+    match capture_single_photo(Some(device_id.clone()), Some(format), None).await {
         Ok(frame) => {
             println!("   ✅ Captured frame!");
             println!("      Size: {}x{} pixels", frame.width, frame.height);
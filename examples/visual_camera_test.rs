@@ -52,8 +52,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             height: 720,
             fps: 30.0,
             format_type: "MJPEG".to_string(), // Request MJPEG
+            bit_depth: 8,
         },
         controls: Default::default(),
+        open_policy: Default::default(),
+        timestamp_source: Default::default(),
+        pixel_format: Default::default(),
+        backend: None,
     };
 
     // Initialize camera directly
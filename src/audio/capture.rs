@@ -10,19 +10,30 @@
 //! - Start/stop operations are idempotent
 //! - Properly joins capture thread on stop
 //! - Non-blocking callback design
-
-use std::sync::atomic::{AtomicBool, Ordering};
+//! - Device audio is resampled to the requested rate (48 kHz for Opus)
+//! - A disconnected device is detected via the stream error callback and
+//!   reported by [`AudioCapture::poll_device`], optionally switching to the
+//!   new default input
+//! - System audio can be captured from loopback devices
+//!   ([`AudioCapture::with_kind`]) through the same frames
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Stream, StreamConfig};
-
-use super::device::find_audio_device;
-use crate::constants::{
-    AUDIO_BUFFER_FRAMES, AUDIO_DEVICE_DEFAULT, AUDIO_SAMPLE_RATE_44K, AUDIO_SAMPLE_RATE_48K,
-};
+use serde::{Deserialize, Serialize};
+
+use super::device::{find_audio_device, DeviceKind};
+use super::loopback::find_loopback_device;
+#[cfg(target_os = "linux")]
+use super::loopback::MonitorProcess;
+use super::resample::Resampler;
+use crate::constants::{AUDIO_BUFFER_FRAMES, AUDIO_DEVICE_DEFAULT, AUDIO_SAMPLE_RATE_48K};
 use crate::errors::CameraError;
+use crate::logging::{log_event, LogLevel};
+use crate::permissions::{check_microphone_permission_detailed, PermissionStatus};
 use crate::timing::PTSClock;
 
 /// Maximum number of audio frames to buffer before dropping oldest.
@@ -44,14 +55,80 @@ pub struct AudioFrame {
     pub timestamp: f64,
 }
 
-/// Audio capture stream from microphone
+/// Switch to another input device after the capturing one was lost,
+/// returned by [`AudioCapture::poll_device`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioDeviceSwitch {
+    /// Name of the device that disappeared
+    pub from: String,
+    /// Name of the device capture continues on
+    pub to: String,
+    /// PTS at which the loss was handled, in seconds
+    pub lost_at: f64,
+    /// Seconds of audio missing between the last frame from `from` and the
+    /// start of `to`; frame timestamps jump by this much
+    pub gap_secs: f64,
+}
+
+/// State shared between an [`AudioCapture`] and its stream callbacks
+#[derive(Clone)]
+struct StreamShared {
+    sender: crossbeam_channel::Sender<AudioFrame>,
+    is_running: Arc<AtomicBool>,
+    /// Set by the error callback when the backend reports the device gone
+    device_lost: Arc<AtomicBool>,
+    /// End PTS of the last delivered frame, as `f64` bits
+    last_frame_end: Arc<AtomicU64>,
+    clock: PTSClock,
+}
+
+/// Where captured samples come from
+enum Source {
+    /// cpal stream on an input device (or a WASAPI output in loopback)
+    Stream(Stream),
+    /// `parec` recording a PulseAudio/PipeWire monitor source
+    #[cfg(target_os = "linux")]
+    Monitor(MonitorProcess),
+}
+
+impl Source {
+    fn play(&self) -> Result<(), String> {
+        match self {
+            Source::Stream(stream) => stream.play().map_err(|e| e.to_string()),
+            // Always recording; frames are discarded while stopped
+            #[cfg(target_os = "linux")]
+            Source::Monitor(_) => Ok(()),
+        }
+    }
+
+    fn pause(&self) -> Result<(), String> {
+        match self {
+            Source::Stream(stream) => stream.pause().map_err(|e| e.to_string()),
+            #[cfg(target_os = "linux")]
+            Source::Monitor(_) => Ok(()),
+        }
+    }
+}
+
+/// A source opened by [`open_source`]
+struct OpenedSource {
+    source: Source,
+    name: String,
+    device_rate: u32,
+    channels: u16,
+}
+
+/// Audio capture stream from microphone or system audio
 pub struct AudioCapture {
-    stream: Option<Stream>,
+    source: Option<Source>,
     receiver: crossbeam_channel::Receiver<AudioFrame>,
-    is_running: Arc<AtomicBool>,
+    shared: StreamShared,
+    kind: DeviceKind,
+    device_name: String,
+    auto_fallback: bool,
     sample_rate: u32,
+    device_rate: u32,
     channels: u16,
-    clock: PTSClock,
 }
 
 impl AudioCapture {
@@ -60,8 +137,14 @@ impl AudioCapture {
     /// If `device_id` is `None` or empty, uses the system default input.
     /// The `clock` should be shared with the video recorder for sync.
     ///
+    /// The device runs at its native rate; frames are resampled to
+    /// `sample_rate` (48 kHz when 0) before delivery, so every
+    /// [`AudioFrame`] reports the requested rate.
+    ///
     /// # Errors
     ///
+    /// Returns `CameraError::PermissionDenied` if the OS blocks microphone access.
+    ///
     /// Returns `CameraError::AudioError` if:
     /// - The device cannot be found
     /// - The device configuration is unsupported
@@ -72,104 +155,103 @@ impl AudioCapture {
         channels: u16,
         clock: PTSClock,
     ) -> Result<Self, CameraError> {
-        let device_id_str = device_id.unwrap_or(AUDIO_DEVICE_DEFAULT);
-        let device_info = find_audio_device(device_id_str)?;
-
-        let host = cpal::default_host();
-        let device = if device_id_str.is_empty() || device_id_str == AUDIO_DEVICE_DEFAULT {
-            host.default_input_device()
-                .ok_or_else(|| CameraError::AudioError("No default audio device".to_string()))?
-        } else {
-            host.input_devices()
-                .map_err(|e| CameraError::AudioError(format!("Failed to enumerate devices: {e}")))?
-                .find(|d| d.name().ok().as_ref() == Some(&device_info.name))
-                .ok_or_else(|| {
-                    CameraError::AudioError(format!("Device not found: {device_id_str}"))
-                })?
-        };
-
-        // Use requested sample rate, falling back to device default
-        let supported_config = device
-            .default_input_config()
-            .map_err(|e| CameraError::AudioError(format!("No supported config: {e}")))?;
+        Self::with_kind(device_id, DeviceKind::Input, sample_rate, channels, clock)
+    }
 
-        let actual_sample_rate =
-            if sample_rate == AUDIO_SAMPLE_RATE_48K || sample_rate == AUDIO_SAMPLE_RATE_44K {
-                sample_rate
-            } else {
-                supported_config.sample_rate().0
-            };
+    /// Create an audio capture for an input or loopback device
+    ///
+    /// With [`DeviceKind::Loopback`], `device_id` refers to a device from
+    /// [`list_loopback_devices`](super::list_loopback_devices) (`None` for
+    /// the default output's loopback) and system audio is captured; see
+    /// [`loopback`](super::loopback) for what each platform supports.
+    /// Otherwise this is [`new`](Self::new).
+    ///
+    /// # Errors
+    ///
+    /// As [`new`](Self::new); loopback capture additionally fails with
+    /// `CameraError::AudioError` when the platform offers no loopback source.
+    pub fn with_kind(
+        device_id: Option<&str>,
+        kind: DeviceKind,
+        sample_rate: u32,
+        channels: u16,
+        clock: PTSClock,
+    ) -> Result<Self, CameraError> {
+        // Report OS-level denial explicitly; otherwise it surfaces later as an
+        // opaque "no device" or stream build error. Loopback only goes through
+        // the microphone permission where it is a virtual input (macOS).
+        if kind == DeviceKind::Input || cfg!(target_os = "macos") {
+            let permission = check_microphone_permission_detailed();
+            if matches!(
+                permission.status,
+                PermissionStatus::Denied | PermissionStatus::Restricted
+            ) {
+                return Err(CameraError::PermissionDenied(permission.message));
+            }
+        }
 
-        let actual_channels = if channels == 1 || channels == 2 {
-            channels
+        let output_rate = if sample_rate == 0 {
+            AUDIO_SAMPLE_RATE_48K
         } else {
-            supported_config.channels()
-        };
-
-        let config = StreamConfig {
-            channels: actual_channels,
-            sample_rate: cpal::SampleRate(actual_sample_rate),
-            buffer_size: cpal::BufferSize::Default,
+            sample_rate
         };
 
         // Bounded channel to prevent unbounded memory growth
         let (sender, receiver) = crossbeam_channel::bounded(MAX_BUFFER_FRAMES);
-        let is_running = Arc::new(AtomicBool::new(false));
-        let is_running_clone = is_running.clone();
-        let clock_clone = clock.clone();
-        let config_sample_rate = config.sample_rate.0;
-        let config_channels = config.channels;
-
-        let stream = device
-            .build_input_stream(
-                &config,
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    if !is_running_clone.load(Ordering::Relaxed) {
-                        return;
-                    }
-
-                    let frame = AudioFrame {
-                        samples: data.to_vec(),
-                        sample_rate: config_sample_rate,
-                        channels: config_channels,
-                        timestamp: clock_clone.pts(),
-                    };
-
-                    // Non-blocking send - drops oldest if buffer full
-                    let _ = sender.try_send(frame);
-                },
-                move |err| {
-                    log::error!("Audio capture error: {err}");
-                },
-                None,
-            )
-            .map_err(|e| CameraError::AudioError(format!("Failed to build stream: {e}")))?;
+        let shared = StreamShared {
+            sender,
+            is_running: Arc::new(AtomicBool::new(false)),
+            device_lost: Arc::new(AtomicBool::new(false)),
+            last_frame_end: Arc::new(AtomicU64::new(0.0_f64.to_bits())),
+            clock,
+        };
+
+        let opened = open_source(
+            kind,
+            device_id.unwrap_or(AUDIO_DEVICE_DEFAULT),
+            channels,
+            output_rate,
+            &shared,
+        )?;
 
         Ok(Self {
-            stream: Some(stream),
+            source: Some(opened.source),
             receiver,
-            is_running,
-            sample_rate: config.sample_rate.0,
-            channels: config.channels,
-            clock,
+            shared,
+            kind,
+            device_name: opened.name,
+            auto_fallback: false,
+            sample_rate: output_rate,
+            device_rate: opened.device_rate,
+            channels: opened.channels,
         })
     }
 
+    /// Switch to the system default input (or default loopback) when the
+    /// device is lost, instead of failing (see [`poll_device`](Self::poll_device))
+    #[must_use]
+    pub fn with_auto_fallback(mut self, enabled: bool) -> Self {
+        self.auto_fallback = enabled;
+        self
+    }
+
     /// Start capturing audio (idempotent)
     ///
     /// # Errors
-    /// Returns `CameraError::AudioError` if the underlying stream fails to play.
+    /// Returns `CameraError::AudioError` if the underlying stream fails to play,
+    /// or `CameraError::AudioDeviceLost` if the device was lost earlier.
     pub fn start(&mut self) -> Result<(), CameraError> {
-        if self.is_running.load(Ordering::Relaxed) {
+        if self.shared.is_running.load(Ordering::Relaxed) {
             return Ok(()); // Already running
         }
 
-        if let Some(ref stream) = self.stream {
-            stream
-                .play()
-                .map_err(|e| CameraError::AudioError(format!("Failed to start stream: {e}")))?;
-            self.is_running.store(true, Ordering::Relaxed);
-        }
+        let Some(ref source) = self.source else {
+            return Err(CameraError::AudioDeviceLost(self.device_name.clone()));
+        };
+        source
+            .play()
+            .map_err(|e| CameraError::AudioError(format!("Failed to start stream: {e}")))?;
+        self.shared.is_running.store(true, Ordering::Relaxed);
 
         Ok(())
     }
@@ -179,20 +261,106 @@ impl AudioCapture {
     /// # Errors
     /// Returns `CameraError::AudioError` if the underlying stream fails to pause.
     pub fn stop(&mut self) -> Result<(), CameraError> {
-        if !self.is_running.load(Ordering::Relaxed) {
+        if !self.shared.is_running.load(Ordering::Relaxed) {
             return Ok(()); // Already stopped
         }
 
-        if let Some(ref stream) = self.stream {
-            stream
+        if let Some(ref source) = self.source {
+            source
                 .pause()
                 .map_err(|e| CameraError::AudioError(format!("Failed to stop stream: {e}")))?;
-            self.is_running.store(false, Ordering::Relaxed);
+            self.shared.is_running.store(false, Ordering::Relaxed);
         }
 
         Ok(())
     }
 
+    /// Handle a lost input device, if the audio backend reported one
+    ///
+    /// Call this periodically from the thread that owns the capture (the
+    /// stream cannot move between threads). Returns `Ok(None)` while the
+    /// device is healthy. After a loss with auto fallback enabled, capture
+    /// resumes on the current default input and the switch is returned and
+    /// logged as a [`LogEvent`](crate::logging::LogEvent); frames keep the
+    /// same rate and channel count, with a timestamp gap of
+    /// [`AudioDeviceSwitch::gap_secs`].
+    ///
+    /// # Errors
+    /// Returns `CameraError::AudioDeviceLost` if the device is gone and auto
+    /// fallback is off or no replacement input could be opened. Capture is
+    /// stopped and frames already buffered can still be read.
+    pub fn poll_device(&mut self) -> Result<Option<AudioDeviceSwitch>, CameraError> {
+        if !self.shared.device_lost.swap(false, Ordering::SeqCst) {
+            return Ok(None);
+        }
+
+        let lost_at = self.shared.clock.pts();
+        let was_running = self.shared.is_running.swap(false, Ordering::SeqCst);
+        // The dead stream cannot be paused reliably; dropping it releases it
+        self.source = None;
+
+        let replacement = if self.auto_fallback {
+            self.reopen_default_input(was_running)
+        } else {
+            Err(CameraError::AudioDeviceLost(self.device_name.clone()))
+        };
+        let to = match replacement {
+            Ok(name) => name,
+            Err(e) => {
+                log_event(
+                    LogLevel::Error,
+                    module_path!(),
+                    Some(&self.device_name),
+                    e.to_string(),
+                );
+                return Err(e);
+            }
+        };
+
+        let last_end = f64::from_bits(self.shared.last_frame_end.load(Ordering::SeqCst));
+        let switch = AudioDeviceSwitch {
+            from: std::mem::replace(&mut self.device_name, to.clone()),
+            to,
+            lost_at,
+            gap_secs: gap_secs(last_end, lost_at, self.shared.clock.pts()),
+        };
+        log_event(
+            LogLevel::Warn,
+            module_path!(),
+            Some(&switch.to),
+            format!(
+                "Audio input '{}' lost; switched to '{}' ({:.3}s gap)",
+                switch.from, switch.to, switch.gap_secs
+            ),
+        );
+        Ok(Some(switch))
+    }
+
+    /// Open the default device of the same kind with the current output
+    /// format and return its name
+    fn reopen_default_input(&mut self, play: bool) -> Result<String, CameraError> {
+        let lost =
+            |detail: &str| CameraError::AudioDeviceLost(format!("{} ({detail})", self.device_name));
+        let opened = open_source(
+            self.kind,
+            AUDIO_DEVICE_DEFAULT,
+            self.channels,
+            self.sample_rate,
+            &self.shared,
+        )
+        .map_err(|e| lost(&e.to_string()))?;
+        if play {
+            opened
+                .source
+                .play()
+                .map_err(|e| lost(&format!("failed to start fallback: {e}")))?;
+            self.shared.is_running.store(true, Ordering::SeqCst);
+        }
+        self.source = Some(opened.source);
+        self.device_rate = opened.device_rate;
+        Ok(opened.name)
+    }
+
     /// Try to read an audio frame without blocking
     ///
     /// Returns `None` if no frame is available.
@@ -224,14 +392,30 @@ impl AudioCapture {
 
     /// Check if capture is currently running
     pub fn is_running(&self) -> bool {
-        self.is_running.load(Ordering::Relaxed)
+        self.shared.is_running.load(Ordering::Relaxed)
     }
 
-    /// Get the configured sample rate
+    /// Get the name of the device currently capturing
+    pub fn device_name(&self) -> &str {
+        &self.device_name
+    }
+
+    /// Get whether this captures an input or a loopback device
+    pub fn kind(&self) -> DeviceKind {
+        self.kind
+    }
+
+    /// Get the sample rate of delivered frames
     pub fn sample_rate(&self) -> u32 {
         self.sample_rate
     }
 
+    /// Get the native sample rate the device captures at, before resampling
+    /// to [`sample_rate`](Self::sample_rate)
+    pub fn actual_device_rate(&self) -> u32 {
+        self.device_rate
+    }
+
     /// Get the configured channel count
     pub fn channels(&self) -> u16 {
         self.channels
@@ -239,7 +423,7 @@ impl AudioCapture {
 
     /// Get the shared PTS clock
     pub fn clock(&self) -> &PTSClock {
-        &self.clock
+        &self.shared.clock
     }
 }
 
@@ -247,11 +431,216 @@ impl Drop for AudioCapture {
     fn drop(&mut self) {
         // Ensure stream is stopped before drop
         let _ = self.stop();
-        // Stream is dropped here, which joins any internal threads
-        self.stream = None;
+        // Source is dropped here, which joins any internal threads
+        self.source = None;
+    }
+}
+
+/// Find and open the device `device_id` of `kind`
+fn open_source(
+    kind: DeviceKind,
+    device_id: &str,
+    channels: u16,
+    output_rate: u32,
+    shared: &StreamShared,
+) -> Result<OpenedSource, CameraError> {
+    let is_default = device_id.is_empty() || device_id == AUDIO_DEVICE_DEFAULT;
+    let host = cpal::default_host();
+
+    if kind == DeviceKind::Input {
+        let device_info = find_audio_device(device_id)?;
+        let device = if is_default {
+            host.default_input_device()
+                .ok_or_else(|| CameraError::AudioError("No default audio device".to_string()))?
+        } else {
+            host.input_devices()
+                .map_err(|e| CameraError::AudioError(format!("Failed to enumerate devices: {e}")))?
+                .find(|d| d.name().ok().as_ref() == Some(&device_info.name))
+                .ok_or_else(|| CameraError::AudioError(format!("Device not found: {device_id}")))?
+        };
+        let name = device.name().unwrap_or(device_info.name);
+        return build_stream(&device, false, channels, output_rate, shared, name);
+    }
+
+    let device_info = find_loopback_device(device_id)?;
+
+    // Linux monitors come from the sound server, not cpal
+    #[cfg(target_os = "linux")]
+    {
+        let channels = if channels == 1 || channels == 2 {
+            channels
+        } else {
+            device_info.channels
+        };
+        let source = MonitorProcess::spawn(
+            &device_info.name,
+            output_rate,
+            channels,
+            frame_sender(shared.clone(), None, channels, output_rate),
+            shared.device_lost.clone(),
+        )?;
+        Ok(OpenedSource {
+            source: Source::Monitor(source),
+            name: device_info.name,
+            device_rate: device_info.sample_rate,
+            channels,
+        })
+    }
+
+    // WASAPI loopback: an input stream on the output device
+    #[cfg(target_os = "windows")]
+    {
+        let device = host
+            .output_devices()
+            .map_err(|e| CameraError::AudioError(format!("Failed to enumerate devices: {e}")))?
+            .find(|d| d.name().ok().as_ref() == Some(&device_info.name))
+            .ok_or_else(|| CameraError::AudioError(format!("Device not found: {device_id}")))?;
+        build_stream(
+            &device,
+            true,
+            channels,
+            output_rate,
+            shared,
+            device_info.name,
+        )
+    }
+
+    // Virtual loopback drivers are ordinary inputs
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    {
+        let device = host
+            .input_devices()
+            .map_err(|e| CameraError::AudioError(format!("Failed to enumerate devices: {e}")))?
+            .find(|d| d.name().ok().as_ref() == Some(&device_info.name))
+            .ok_or_else(|| CameraError::AudioError(format!("Device not found: {device_id}")))?;
+        build_stream(
+            &device,
+            false,
+            channels,
+            output_rate,
+            shared,
+            device_info.name,
+        )
+    }
+}
+
+/// Build an input stream on `device` at its native rate, resampled to
+/// `output_rate`; `loopback` captures what an output device plays
+fn build_stream(
+    device: &cpal::Device,
+    loopback: bool,
+    channels: u16,
+    output_rate: u32,
+    shared: &StreamShared,
+    name: String,
+) -> Result<OpenedSource, CameraError> {
+    // Capture at the device's native rate and resample to the requested one
+    let supported_config = if loopback {
+        device.default_output_config()
+    } else {
+        device.default_input_config()
+    }
+    .map_err(|e| CameraError::AudioError(format!("No supported config: {e}")))?;
+
+    let device_rate = supported_config.sample_rate().0;
+    let actual_channels = if channels == 1 || channels == 2 {
+        channels
+    } else {
+        supported_config.channels()
+    };
+
+    let config = StreamConfig {
+        channels: actual_channels,
+        sample_rate: cpal::SampleRate(device_rate),
+        buffer_size: cpal::BufferSize::Default,
+    };
+
+    let resampler = Resampler::new(device_rate, output_rate, actual_channels);
+    if resampler.is_active() {
+        log::info!("Resampling audio from {device_rate} Hz to {output_rate} Hz");
+    }
+
+    let mut on_samples = frame_sender(
+        shared.clone(),
+        Some(resampler),
+        actual_channels,
+        output_rate,
+    );
+    let lost_flag = shared.device_lost.clone();
+    let stream = device
+        .build_input_stream(
+            &config,
+            move |data: &[f32], _: &cpal::InputCallbackInfo| on_samples(data),
+            move |err| {
+                if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+                    log::warn!("Audio input device disconnected");
+                    lost_flag.store(true, Ordering::SeqCst);
+                } else {
+                    log::error!("Audio capture error: {err}");
+                }
+            },
+            None,
+        )
+        .map_err(|e| CameraError::AudioError(format!("Failed to build stream: {e}")))?;
+
+    Ok(OpenedSource {
+        source: Source::Stream(stream),
+        name,
+        device_rate,
+        channels: actual_channels,
+    })
+}
+
+/// Callback turning captured samples into timestamped [`AudioFrame`]s,
+/// resampled first when a resampler is given
+fn frame_sender(
+    shared: StreamShared,
+    mut resampler: Option<Resampler>,
+    channels: u16,
+    output_rate: u32,
+) -> impl FnMut(&[f32]) + Send + 'static {
+    move |data: &[f32]| {
+        if !shared.is_running.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let samples = match resampler {
+            Some(ref mut resampler) => resampler.process(data),
+            None => data.to_vec(),
+        };
+        if samples.is_empty() {
+            return;
+        }
+        let timestamp = shared.clock.pts();
+        #[allow(clippy::cast_precision_loss)]
+        // Callback chunks hold far fewer than 2^52 samples
+        let duration = samples.len() as f64 / f64::from(channels) / f64::from(output_rate);
+        shared
+            .last_frame_end
+            .store((timestamp + duration).to_bits(), Ordering::Relaxed);
+        let frame = AudioFrame {
+            samples,
+            sample_rate: output_rate,
+            channels,
+            timestamp,
+        };
+
+        // Non-blocking send - drops oldest if buffer full
+        let _ = shared.sender.try_send(frame);
     }
 }
 
+/// Timestamp gap left by a device switch: from the end of the last frame
+/// (or the loss itself if no frame arrived) to when the new device resumed
+fn gap_secs(last_frame_end: f64, lost_at: f64, resumed_at: f64) -> f64 {
+    let gap_start = if last_frame_end > 0.0 {
+        last_frame_end.min(lost_at)
+    } else {
+        lost_at
+    };
+    (resumed_at - gap_start).max(0.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -269,6 +658,30 @@ mod tests {
         assert_eq!(frame.channels, 2);
     }
 
+    #[test]
+    fn test_switch_gap_measured_from_last_frame() {
+        // Last frame ended at 2.0s, loss handled at 2.3s, new device at 2.5s
+        assert!((gap_secs(2.0, 2.3, 2.5) - 0.5).abs() < 1e-9);
+        // No frame yet: the gap starts at the loss
+        assert!((gap_secs(0.0, 1.0, 1.25) - 0.25).abs() < 1e-9);
+        // Clock reads never make the gap negative
+        assert!(gap_secs(3.0, 3.0, 2.9).abs() < 1e-9);
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Opens a real audio device via cpal/WASAPI; COM stream setup can hard-abort (STATUS_ACCESS_VIOLATION) on headless CI runners - run manually"
+    )]
+    fn test_poll_device_is_quiet_while_healthy() {
+        let clock = PTSClock::new();
+        if let Ok(capture) = AudioCapture::new(None, 48000, 2, clock) {
+            let mut capture = capture.with_auto_fallback(true);
+            assert!(!capture.device_name().is_empty());
+            assert_eq!(capture.poll_device().unwrap(), None);
+        }
+    }
+
     #[test]
     #[cfg_attr(
         target_os = "windows",
@@ -287,4 +700,23 @@ mod tests {
             assert!(capture.stop().is_ok());
         }
     }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Opens a real audio device via cpal/WASAPI; COM stream setup can hard-abort (STATUS_ACCESS_VIOLATION) on headless CI runners - run manually"
+    )]
+    fn test_frames_delivered_at_requested_rate() {
+        // Only meaningful where an input device exists
+        let clock = PTSClock::new();
+        if let Ok(mut capture) = AudioCapture::new(None, 48000, 2, clock) {
+            assert_eq!(capture.sample_rate(), 48000);
+            if capture.start().is_ok() {
+                if let Ok(frame) = capture.recv_timeout(Duration::from_millis(500)) {
+                    assert_eq!(frame.sample_rate, 48000);
+                }
+                let _ = capture.stop();
+            }
+        }
+    }
 }
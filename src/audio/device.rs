@@ -6,7 +6,7 @@
 //! ## Features
 //!
 //! - `system_inputs -> Vec<AudioDevice>`
-//! - includes(id, name, `sample_rate`, channels, `is_default`)
+//! - includes(id, name, `sample_rate`, channels, `is_default`, kind)
 //! - `input_devices_only`
 //! - `deterministic_ordering`
 //! - no `starting_audio_capture`
@@ -17,6 +17,17 @@ use serde::{Deserialize, Serialize};
 
 use crate::errors::CameraError;
 
+/// What an audio device captures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DeviceKind {
+    /// Microphone or line input
+    #[default]
+    Input,
+    /// System audio output (loopback/monitor), see
+    /// [`list_loopback_devices`](super::list_loopback_devices)
+    Loopback,
+}
+
 /// Audio input device information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioDevice {
@@ -30,6 +41,9 @@ pub struct AudioDevice {
     pub channels: u16,
     /// Whether this is the system default input device
     pub is_default: bool,
+    /// Input or loopback source
+    #[serde(default)]
+    pub kind: DeviceKind,
 }
 
 /// List all available audio input devices
@@ -50,36 +64,41 @@ pub fn list_audio_devices() -> Result<Vec<AudioDevice>, CameraError> {
             let name = device.name().ok()?;
             let config = device.default_input_config().ok()?;
 
-            // Generate synthetic ID: cpal doesn't expose unique device IDs on all platforms,
-            // so we combine index with name hash to create a stable-ish identifier.
-            // Format: "audio_{index}_{hash}" where hash is first 8 chars of name hash
-            let name_hash = {
-                use std::collections::hash_map::DefaultHasher;
-                use std::hash::{Hash, Hasher};
-                let mut hasher = DefaultHasher::new();
-                name.hash(&mut hasher);
-                format!("{:08x}", hasher.finish() & 0xFFFF_FFFF)
-            };
-            let id = format!("audio_{index}_{name_hash}");
-
             Some(AudioDevice {
-                id,
+                id: synthetic_id("audio", index, &name),
                 name: name.clone(),
                 sample_rate: config.sample_rate().0,
                 channels: config.channels(),
                 is_default: default_device_name.as_ref() == Some(&name),
+                kind: DeviceKind::Input,
             })
         })
         .collect();
 
-    // Deterministic ordering: default first, then alphabetically
+    sort_devices(&mut devices);
+    Ok(devices)
+}
+
+/// Synthetic device ID
+///
+/// cpal doesn't expose unique device IDs on all platforms, so we combine
+/// index with name hash to create a stable-ish identifier.
+/// Format: "{prefix}_{index}_{hash}" where hash is first 8 chars of name hash
+pub(super) fn synthetic_id(prefix: &str, index: usize, name: &str) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    format!("{prefix}_{index}_{:08x}", hasher.finish() & 0xFFFF_FFFF)
+}
+
+/// Deterministic ordering: default first, then alphabetically
+pub(super) fn sort_devices(devices: &mut [AudioDevice]) {
     devices.sort_by(|a, b| match (a.is_default, b.is_default) {
         (true, false) => std::cmp::Ordering::Less,
         (false, true) => std::cmp::Ordering::Greater,
         _ => a.name.cmp(&b.name),
     });
-
-    Ok(devices)
 }
 
 /// Get the default audio input device
@@ -102,21 +121,13 @@ pub fn get_default_audio_device() -> Result<AudioDevice, CameraError> {
         .map_err(|e| CameraError::AudioError(format!("Failed to get device config: {e}")))?;
 
     // Generate synthetic ID for default device (index 0)
-    let name_hash = {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut hasher = DefaultHasher::new();
-        name.hash(&mut hasher);
-        format!("{:08x}", hasher.finish() & 0xFFFF_FFFF)
-    };
-    let id = format!("audio_0_{name_hash}");
-
     Ok(AudioDevice {
-        id,
+        id: synthetic_id("audio", 0, &name),
         name,
         sample_rate: config.sample_rate().0,
         channels: config.channels(),
         is_default: true,
+        kind: DeviceKind::Input,
     })
 }
 
@@ -139,6 +150,15 @@ pub fn find_audio_device(device_id: &str) -> Result<AudioDevice, CameraError> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_synthetic_id_is_stable() {
+        let id = synthetic_id("audio", 2, "USB Mic");
+        assert!(id.starts_with("audio_2_"));
+        assert_eq!(id.len(), "audio_2_".len() + 8);
+        assert_eq!(id, synthetic_id("audio", 2, "USB Mic"));
+        assert_ne!(id, synthetic_id("loopback", 2, "USB Mic"));
+    }
+
     #[test]
     #[cfg_attr(
         target_os = "windows",
@@ -0,0 +1,333 @@
+//! System audio (loopback) sources
+//!
+//! Loopback devices capture what the computer plays instead of a
+//! microphone, e.g. application audio for screen-recording tutorials. They
+//! are opened through [`AudioCapture::with_kind`](super::AudioCapture::with_kind)
+//! with [`DeviceKind::Loopback`] and deliver the same [`AudioFrame`](super::AudioFrame)s.
+//!
+//! ## Platform support
+//!
+//! - **Windows**: every output endpoint, captured with WASAPI loopback
+//!   (cpal input stream on the output device). Silence produces no frames
+//!   on some drivers.
+//! - **Linux**: the `.monitor` source of each PulseAudio or PipeWire
+//!   (`pipewire-pulse`) sink, recorded with `parec` from pulseaudio-utils.
+//!   Plain ALSA systems have no monitor sources, so the list is empty.
+//! - **macOS**: no system loopback without ScreenCaptureKit, which needs
+//!   app entitlements this plugin cannot grant. Virtual drivers such as
+//!   BlackHole or Soundflower are listed and captured as inputs, which
+//!   needs microphone permission and a multi-output device routing
+//!   playback to them.
+//! - Other platforms: no loopback devices.
+
+#[cfg(target_os = "linux")]
+use std::io::Read;
+#[cfg(target_os = "linux")]
+use std::process::{Child, Command, Stdio};
+#[cfg(target_os = "linux")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(target_os = "linux")]
+use std::sync::Arc;
+#[cfg(target_os = "linux")]
+use std::thread::JoinHandle;
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use cpal::traits::{DeviceTrait, HostTrait};
+
+use super::device::{sort_devices, synthetic_id, AudioDevice, DeviceKind};
+#[cfg(target_os = "macos")]
+use crate::constants::AUDIO_MACOS_LOOPBACK_DRIVERS;
+use crate::constants::{
+    AUDIO_CHANNELS, AUDIO_DEVICE_DEFAULT, AUDIO_LOOPBACK_ID_PREFIX, AUDIO_SAMPLE_RATE,
+};
+#[cfg(target_os = "linux")]
+use crate::constants::{AUDIO_MONITOR_CHUNK_MS, AUDIO_PACTL, AUDIO_PAREC};
+use crate::errors::CameraError;
+
+/// List system-audio sources that can be captured as loopback devices
+///
+/// Returns devices in the same order as [`list_audio_devices`](super::list_audio_devices)
+/// (default first, then alphabetically), each with
+/// [`DeviceKind::Loopback`]. See the module docs for what each platform
+/// offers; an empty list means none are available.
+///
+/// # Errors
+/// Returns error if the audio host cannot enumerate output devices.
+pub fn list_loopback_devices() -> Result<Vec<AudioDevice>, CameraError> {
+    let mut devices = platform_loopback_devices()?;
+    sort_devices(&mut devices);
+    Ok(devices)
+}
+
+/// Find a loopback device by ID or name
+///
+/// If `device_id` is "default" or empty, returns the default loopback
+/// device (the monitor of the default output), or the first one.
+pub(super) fn find_loopback_device(device_id: &str) -> Result<AudioDevice, CameraError> {
+    let devices = list_loopback_devices()?;
+    let found = if device_id.is_empty() || device_id == AUDIO_DEVICE_DEFAULT {
+        devices.into_iter().next()
+    } else {
+        devices
+            .into_iter()
+            .find(|d| d.id == device_id || d.name == device_id)
+    };
+    found.ok_or_else(|| CameraError::AudioError(format!("Loopback device not found: {device_id}")))
+}
+
+#[cfg(target_os = "windows")]
+fn platform_loopback_devices() -> Result<Vec<AudioDevice>, CameraError> {
+    let host = cpal::default_host();
+    let default_name = host.default_output_device().and_then(|d| d.name().ok());
+
+    Ok(host
+        .output_devices()
+        .map_err(|e| CameraError::AudioError(format!("Failed to enumerate output devices: {e}")))?
+        .enumerate()
+        .filter_map(|(index, device)| {
+            let name = device.name().ok()?;
+            let config = device.default_output_config().ok()?;
+            Some(AudioDevice {
+                id: synthetic_id(AUDIO_LOOPBACK_ID_PREFIX, index, &name),
+                is_default: default_name.as_ref() == Some(&name),
+                name,
+                sample_rate: config.sample_rate().0,
+                channels: config.channels(),
+                kind: DeviceKind::Loopback,
+            })
+        })
+        .collect())
+}
+
+#[cfg(target_os = "macos")]
+fn platform_loopback_devices() -> Result<Vec<AudioDevice>, CameraError> {
+    let host = cpal::default_host();
+
+    Ok(host
+        .input_devices()
+        .map_err(|e| CameraError::AudioError(format!("Failed to enumerate audio devices: {e}")))?
+        .enumerate()
+        .filter_map(|(index, device)| {
+            let name = device.name().ok()?;
+            if !AUDIO_MACOS_LOOPBACK_DRIVERS
+                .iter()
+                .any(|driver| name.contains(driver))
+            {
+                return None;
+            }
+            let config = device.default_input_config().ok()?;
+            Some(AudioDevice {
+                id: synthetic_id(AUDIO_LOOPBACK_ID_PREFIX, index, &name),
+                name,
+                sample_rate: config.sample_rate().0,
+                channels: config.channels(),
+                is_default: false,
+                kind: DeviceKind::Loopback,
+            })
+        })
+        .collect())
+}
+
+#[cfg(target_os = "linux")]
+// Fallible on the other platforms
+#[allow(clippy::unnecessary_wraps)]
+fn platform_loopback_devices() -> Result<Vec<AudioDevice>, CameraError> {
+    // No PulseAudio/PipeWire (or no pactl) means no monitor sources
+    let Some(listing) = pactl(&["list", "short", "sources"]) else {
+        return Ok(Vec::new());
+    };
+    let default_sink = pactl(&["get-default-sink"]);
+    Ok(parse_monitor_sources(
+        &listing,
+        default_sink.as_deref().map(str::trim),
+    ))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+// Fallible on the other platforms
+#[allow(clippy::unnecessary_wraps)]
+fn platform_loopback_devices() -> Result<Vec<AudioDevice>, CameraError> {
+    Ok(Vec::new())
+}
+
+/// Run `pactl` and return its stdout, if it succeeded
+#[cfg(target_os = "linux")]
+fn pactl(args: &[&str]) -> Option<String> {
+    let output = Command::new(AUDIO_PACTL)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse `pactl list short sources` output into monitor-source devices
+///
+/// Lines look like
+/// `58\talsa_output.pci.analog-stereo.monitor\tPipeWire\ts32le 2ch 48000Hz\tSUSPENDED`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_monitor_sources(listing: &str, default_sink: Option<&str>) -> Vec<AudioDevice> {
+    listing
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let name = fields.nth(1)?;
+            let sink = name.strip_suffix(".monitor")?;
+            let spec = fields.nth(1).unwrap_or_default();
+            let channels = spec
+                .split_whitespace()
+                .find_map(|token| token.strip_suffix("ch")?.parse().ok())
+                .unwrap_or(AUDIO_CHANNELS);
+            let sample_rate = spec
+                .split_whitespace()
+                .find_map(|token| token.strip_suffix("Hz")?.parse().ok())
+                .unwrap_or(AUDIO_SAMPLE_RATE);
+            Some((name, sink, channels, sample_rate))
+        })
+        .enumerate()
+        .map(|(index, (name, sink, channels, sample_rate))| AudioDevice {
+            id: synthetic_id(AUDIO_LOOPBACK_ID_PREFIX, index, name),
+            name: name.to_string(),
+            sample_rate,
+            channels,
+            is_default: default_sink == Some(sink),
+            kind: DeviceKind::Loopback,
+        })
+        .collect()
+}
+
+/// `parec` recording a PulseAudio/PipeWire monitor source
+///
+/// Samples are read on a helper thread in
+/// [`AUDIO_MONITOR_CHUNK_MS`] chunks and handed to the capture callback.
+/// The process ending on its own (server restart, sink removed) sets the
+/// device-lost flag, like a cpal stream error.
+#[cfg(target_os = "linux")]
+pub(super) struct MonitorProcess {
+    child: Child,
+    reader: Option<JoinHandle<()>>,
+    stopping: Arc<AtomicBool>,
+}
+
+#[cfg(target_os = "linux")]
+impl MonitorProcess {
+    /// Start recording `source` as interleaved f32 at `rate`/`channels`
+    /// (the sound server converts from the sink format)
+    pub(super) fn spawn(
+        source: &str,
+        rate: u32,
+        channels: u16,
+        mut on_samples: impl FnMut(&[f32]) + Send + 'static,
+        device_lost: Arc<AtomicBool>,
+    ) -> Result<Self, CameraError> {
+        let mut child = Command::new(AUDIO_PAREC)
+            .arg(format!("--device={source}"))
+            .arg("--format=float32le")
+            .arg(format!("--rate={rate}"))
+            .arg(format!("--channels={channels}"))
+            .arg(format!("--latency-msec={AUDIO_MONITOR_CHUNK_MS}"))
+            .arg("--raw")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| CameraError::AudioError(format!("Failed to start {AUDIO_PAREC}: {e}")))?;
+        let mut stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| CameraError::AudioError(format!("{AUDIO_PAREC} has no stdout")))?;
+
+        let stopping = Arc::new(AtomicBool::new(false));
+        let stopping_clone = stopping.clone();
+        let frames_per_chunk =
+            usize::try_from(rate * AUDIO_MONITOR_CHUNK_MS / 1000).map_or(480, |n| n.max(1));
+        let chunk_bytes = frames_per_chunk * usize::from(channels) * 4;
+        let source = source.to_string();
+        let reader = std::thread::spawn(move || {
+            let mut buffer = vec![0u8; chunk_bytes];
+            while stdout.read_exact(&mut buffer).is_ok() {
+                let samples: Vec<f32> = buffer
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect();
+                on_samples(&samples);
+            }
+            if !stopping_clone.load(Ordering::SeqCst) {
+                log::warn!("Loopback source {source} ended");
+                device_lost.store(true, Ordering::SeqCst);
+            }
+        });
+
+        Ok(Self {
+            child,
+            reader: Some(reader),
+            stopping,
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for MonitorProcess {
+    fn drop(&mut self) {
+        self.stopping.store(true, Ordering::SeqCst);
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_monitor_sources() {
+        let listing = "\
+57\talsa_output.usb-headset.analog-stereo.monitor\tPipeWire\ts16le 2ch 44100Hz\tSUSPENDED
+58\talsa_input.usb-headset.mono-fallback\tPipeWire\ts16le 1ch 16000Hz\tRUNNING
+59\talsa_output.pci.hdmi-surround.monitor\tPipeWire\tfloat32le 6ch 48000Hz\tIDLE
+";
+        let devices = parse_monitor_sources(listing, Some("alsa_output.pci.hdmi-surround"));
+
+        // Microphones are not monitors
+        assert_eq!(devices.len(), 2);
+        assert!(devices.iter().all(|d| d.kind == DeviceKind::Loopback));
+        assert!(devices.iter().all(|d| d.id.starts_with("loopback_")));
+
+        assert_eq!(
+            devices[0].name,
+            "alsa_output.usb-headset.analog-stereo.monitor"
+        );
+        assert_eq!(devices[0].sample_rate, 44100);
+        assert_eq!(devices[0].channels, 2);
+        assert!(!devices[0].is_default);
+
+        assert_eq!(devices[1].channels, 6);
+        assert!(devices[1].is_default);
+    }
+
+    #[test]
+    fn test_parse_tolerates_missing_format() {
+        let devices = parse_monitor_sources("1\tnull.monitor\n", None);
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].sample_rate, AUDIO_SAMPLE_RATE);
+        assert_eq!(devices[0].channels, AUDIO_CHANNELS);
+    }
+
+    #[test]
+    #[cfg_attr(
+        target_os = "windows",
+        ignore = "Enumerates real audio endpoints via cpal/WASAPI; COM enumeration can hard-abort (STATUS_ACCESS_VIOLATION) on headless CI runners - run manually"
+    )]
+    fn test_list_loopback_devices_no_panic() {
+        if let Ok(devices) = list_loopback_devices() {
+            assert!(devices.iter().all(|d| d.kind == DeviceKind::Loopback));
+        }
+    }
+}
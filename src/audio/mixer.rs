@@ -0,0 +1,483 @@
+//! Mixing several audio sources into one track
+//!
+//! [`AudioMixer`] combines captures that share a [`PTSClock`](super::PTSClock),
+//! e.g. a microphone and a loopback device for tutorial recordings, into a
+//! single stream of [`AudioFrame`]s that can be fed to the
+//! [`OpusEncoder`](super::OpusEncoder).
+//!
+//! ## Properties
+//!
+//! - Sources are aligned by frame timestamp; gaps become silence and
+//!   late samples are dropped so sources never drift apart
+//! - Each source has its own gain
+//! - Sources at other rates or channel counts are converted to the output
+//!   format first
+//! - The sum is soft-limited, so it never exceeds full scale
+//! - A source that delivers nothing (e.g. a silent loopback that sends no
+//!   frames) is treated as silence once it lags by
+//!   [`AUDIO_MIXER_MAX_LAG_SECS`], so it cannot stall the others
+
+use std::collections::VecDeque;
+
+use super::capture::{AudioCapture, AudioFrame};
+use super::resample::Resampler;
+use crate::constants::{
+    AUDIO_MIXER_BLOCK_MS, AUDIO_MIXER_GAP_TOLERANCE_SECS, AUDIO_MIXER_MAX_LAG_SECS,
+};
+use crate::errors::CameraError;
+
+/// Level above which the soft limiter starts compressing
+const LIMITER_KNEE: f32 = 0.9;
+
+/// One input of the mixer
+struct MixerSource {
+    capture: Option<AudioCapture>,
+    gain: f32,
+    /// Interleaved samples in the output format, starting at the mixer's
+    /// next output PTS
+    buffer: VecDeque<f32>,
+    resampler: Option<Resampler>,
+}
+
+/// Mixes timestamped audio from several sources into one stream
+pub struct AudioMixer {
+    sources: Vec<MixerSource>,
+    sample_rate: u32,
+    channels: u16,
+    /// PTS of the next output sample; the timeline starts at the first
+    /// frame from any source
+    next_pts: Option<f64>,
+    /// End PTS of the newest frame received from any source
+    latest_pts: f64,
+}
+
+impl AudioMixer {
+    /// Create a mixer producing frames at `sample_rate` with `channels`
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        Self {
+            sources: Vec::new(),
+            sample_rate: sample_rate.max(1),
+            channels: channels.max(1),
+            next_pts: None,
+            latest_pts: 0.0,
+        }
+    }
+
+    /// Add a capture as a source, returning its index
+    ///
+    /// The capture must use the same [`PTSClock`](super::PTSClock) as the
+    /// other sources; [`poll`](Self::poll) drains it.
+    pub fn add_capture(&mut self, capture: AudioCapture, gain: f32) -> usize {
+        self.add(Some(capture), gain)
+    }
+
+    /// Add a source fed manually with [`push`](Self::push), returning its
+    /// index
+    pub fn add_source(&mut self, gain: f32) -> usize {
+        self.add(None, gain)
+    }
+
+    fn add(&mut self, capture: Option<AudioCapture>, gain: f32) -> usize {
+        self.sources.push(MixerSource {
+            capture,
+            gain,
+            buffer: VecDeque::new(),
+            resampler: None,
+        });
+        self.sources.len() - 1
+    }
+
+    /// Change the gain of a source (1.0 = unchanged)
+    ///
+    /// # Errors
+    /// Returns `CameraError::ConfigError` if `source` is not a valid index.
+    pub fn set_gain(&mut self, source: usize, gain: f32) -> Result<(), CameraError> {
+        self.source_mut(source)?.gain = gain;
+        Ok(())
+    }
+
+    /// Start every capture source
+    ///
+    /// # Errors
+    /// Returns the first error of [`AudioCapture::start`].
+    pub fn start(&mut self) -> Result<(), CameraError> {
+        for capture in self.sources.iter_mut().filter_map(|s| s.capture.as_mut()) {
+            capture.start()?;
+        }
+        Ok(())
+    }
+
+    /// Stop every capture source
+    ///
+    /// # Errors
+    /// Returns the first error of [`AudioCapture::stop`].
+    pub fn stop(&mut self) -> Result<(), CameraError> {
+        for capture in self.sources.iter_mut().filter_map(|s| s.capture.as_mut()) {
+            capture.stop()?;
+        }
+        Ok(())
+    }
+
+    /// Feed a frame to a manual source
+    ///
+    /// # Errors
+    /// Returns `CameraError::ConfigError` if `source` is not a valid index.
+    pub fn push(&mut self, source: usize, frame: &AudioFrame) -> Result<(), CameraError> {
+        self.source_mut(source)?;
+        self.ingest(source, frame);
+        Ok(())
+    }
+
+    /// Drain the capture sources and return the mixed frames that are
+    /// complete
+    pub fn poll(&mut self) -> Vec<AudioFrame> {
+        for index in 0..self.sources.len() {
+            let frames = self.sources[index]
+                .capture
+                .as_ref()
+                .map(AudioCapture::drain)
+                .unwrap_or_default();
+            for frame in &frames {
+                self.ingest(index, frame);
+            }
+        }
+        self.mix(false)
+    }
+
+    /// Mix everything still buffered, padding shorter sources with silence
+    pub fn flush(&mut self) -> Vec<AudioFrame> {
+        let mut frames = self.poll();
+        frames.extend(self.mix(true));
+        frames
+    }
+
+    /// Sample rate of mixed frames
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Channel count of mixed frames
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Number of sources
+    pub fn source_count(&self) -> usize {
+        self.sources.len()
+    }
+
+    fn source_mut(&mut self, source: usize) -> Result<&mut MixerSource, CameraError> {
+        self.sources
+            .get_mut(source)
+            .ok_or_else(|| CameraError::ConfigError(format!("No mixer source {source}")))
+    }
+
+    fn frames_to_secs(&self, frames: usize) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        // Buffered frame counts stay far below 2^52
+        let frames = frames as f64;
+        frames / f64::from(self.sample_rate)
+    }
+
+    // Gap lengths are short, non-negative durations
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn secs_to_frames(&self, secs: f64) -> usize {
+        (secs * f64::from(self.sample_rate)).round().max(0.0) as usize
+    }
+
+    /// PTS just after the last buffered sample of `index`
+    fn end_pts(&self, index: usize) -> f64 {
+        let buffered = self.sources[index].buffer.len() / usize::from(self.channels);
+        self.next_pts.unwrap_or_default() + self.frames_to_secs(buffered)
+    }
+
+    /// Convert a frame to the output format and append it to its source at
+    /// its timestamp
+    fn ingest(&mut self, index: usize, frame: &AudioFrame) {
+        let (sample_rate, out_channels) = (self.sample_rate, self.channels);
+        let channels = usize::from(out_channels);
+        let samples = convert_channels(&frame.samples, frame.channels, out_channels);
+        let samples = if frame.sample_rate == sample_rate {
+            samples
+        } else {
+            self.sources[index]
+                .resampler
+                .get_or_insert_with(|| Resampler::new(frame.sample_rate, sample_rate, out_channels))
+                .process(&samples)
+        };
+        let frame_count = samples.len() / channels;
+        if self.next_pts.is_none() {
+            self.next_pts = Some(frame.timestamp);
+        }
+
+        let mut samples = samples.as_slice();
+        let drift = frame.timestamp - self.end_pts(index);
+        if drift > AUDIO_MIXER_GAP_TOLERANCE_SECS {
+            // Missing audio (or a source starting late): keep the timeline
+            // with silence
+            let silence = self.secs_to_frames(drift) * channels;
+            self.sources[index]
+                .buffer
+                .extend(std::iter::repeat(0.0).take(silence));
+        } else if drift < -AUDIO_MIXER_GAP_TOLERANCE_SECS {
+            // Arrived after its slot was mixed or padded
+            let late = self.secs_to_frames(-drift).min(frame_count);
+            samples = &samples[late * channels..];
+        }
+        self.sources[index].buffer.extend(samples.iter().copied());
+
+        let frame_end = frame.timestamp + self.frames_to_secs(frame_count);
+        self.latest_pts = self.latest_pts.max(frame_end);
+    }
+
+    /// Emit complete blocks; `force` pads every source to the longest and
+    /// also emits the partial remainder
+    fn mix(&mut self, force: bool) -> Vec<AudioFrame> {
+        let mut out = Vec::new();
+        let Some(mut pts) = self.next_pts else {
+            return out;
+        };
+        let channels = usize::from(self.channels);
+        let block = self
+            .secs_to_frames(f64::from(AUDIO_MIXER_BLOCK_MS) / 1000.0)
+            .max(1);
+
+        // Sources far behind the newest audio count as silent up to the lag
+        let target = if force {
+            (0..self.sources.len())
+                .map(|index| self.end_pts(index))
+                .fold(pts, f64::max)
+        } else {
+            self.latest_pts - AUDIO_MIXER_MAX_LAG_SECS
+        };
+        for index in 0..self.sources.len() {
+            let missing = self.secs_to_frames(target - self.end_pts(index));
+            self.sources[index]
+                .buffer
+                .extend(std::iter::repeat(0.0).take(missing * channels));
+        }
+
+        let mut available = self
+            .sources
+            .iter()
+            .map(|s| s.buffer.len() / channels)
+            .min()
+            .unwrap_or(0);
+        while available >= block || (force && available > 0) {
+            let frames = block.min(available);
+            let mut samples = vec![0.0_f32; frames * channels];
+            for source in &mut self.sources {
+                let gain = source.gain;
+                for (mixed, sample) in samples
+                    .iter_mut()
+                    .zip(source.buffer.drain(..frames * channels))
+                {
+                    *mixed += sample * gain;
+                }
+            }
+            for sample in &mut samples {
+                *sample = soft_limit(*sample);
+            }
+            out.push(AudioFrame {
+                samples,
+                sample_rate: self.sample_rate,
+                channels: self.channels,
+                timestamp: pts,
+            });
+            pts += self.frames_to_secs(frames);
+            available -= frames;
+        }
+        self.next_pts = Some(pts);
+        out
+    }
+}
+
+/// Up- or downmix interleaved samples between channel counts
+fn convert_channels(samples: &[f32], from: u16, to: u16) -> Vec<f32> {
+    let (from, to) = (usize::from(from.max(1)), usize::from(to.max(1)));
+    if from == to {
+        return samples.to_vec();
+    }
+    samples
+        .chunks_exact(from)
+        .flat_map(|frame| {
+            #[allow(clippy::cast_precision_loss)]
+            // Channel counts are tiny
+            let mono = frame.iter().sum::<f32>() / from as f32;
+            (0..to).map(move |channel| {
+                if from == 1 || to == 1 {
+                    mono
+                } else {
+                    frame.get(channel).copied().unwrap_or(mono)
+                }
+            })
+        })
+        .collect()
+}
+
+/// Pass levels below the knee unchanged and compress the rest smoothly so
+/// the result stays within full scale
+fn soft_limit(sample: f32) -> f32 {
+    let magnitude = sample.abs();
+    if magnitude <= LIMITER_KNEE {
+        sample
+    } else {
+        let headroom = 1.0 - LIMITER_KNEE;
+        let compressed = LIMITER_KNEE + headroom * ((magnitude - LIMITER_KNEE) / headroom).tanh();
+        compressed.copysign(sample)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    /// Frames of `freq` Hz split into 10 ms chunks starting at `start` PTS
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn sine_frames(rate: u32, freq: f64, amplitude: f64, secs: f64, start: f64) -> Vec<AudioFrame> {
+        let chunk = usize::try_from(rate / 100).unwrap();
+        let total = (secs * f64::from(rate)) as usize;
+        (0..total)
+            .step_by(chunk)
+            .map(|offset| AudioFrame {
+                samples: (offset..(offset + chunk).min(total))
+                    .map(|i| {
+                        (amplitude * (2.0 * PI * freq * i as f64 / f64::from(rate)).sin()) as f32
+                    })
+                    .collect(),
+                sample_rate: rate,
+                channels: 1,
+                timestamp: start + offset as f64 / f64::from(rate),
+            })
+            .collect()
+    }
+
+    /// Relative power of `freq` in `samples` (Goertzel)
+    #[allow(clippy::cast_precision_loss)]
+    fn tone_power(samples: &[f32], rate: u32, freq: f64) -> f64 {
+        let coeff = 2.0 * (2.0 * PI * freq / f64::from(rate)).cos();
+        let (mut s1, mut s2) = (0.0, 0.0);
+        for &x in samples {
+            let s0 = f64::from(x) + coeff * s1 - s2;
+            s2 = s1;
+            s1 = s0;
+        }
+        (s1 * s1 + s2 * s2 - coeff * s1 * s2) / (samples.len() as f64).powi(2)
+    }
+
+    /// Feed the sources in timestamp order, as live captures would arrive
+    fn mix_all(mixer: &mut AudioMixer, sources: &[(usize, Vec<AudioFrame>)]) -> Vec<AudioFrame> {
+        let mut arrivals: Vec<(usize, &AudioFrame)> = sources
+            .iter()
+            .flat_map(|(source, frames)| frames.iter().map(move |frame| (*source, frame)))
+            .collect();
+        arrivals.sort_by(|a, b| a.1.timestamp.total_cmp(&b.1.timestamp));
+
+        let mut out = Vec::new();
+        for (source, frame) in arrivals {
+            mixer.push(source, frame).unwrap();
+            out.extend(mixer.poll());
+        }
+        out.extend(mixer.flush());
+        out
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn test_mix_contains_both_frequencies() {
+        let mut mixer = AudioMixer::new(48_000, 1);
+        let mic = mixer.add_source(1.0);
+        let system = mixer.add_source(1.0);
+        let out = mix_all(
+            &mut mixer,
+            &[
+                (mic, sine_frames(48_000, 440.0, 0.4, 1.0, 0.0)),
+                (system, sine_frames(48_000, 1_000.0, 0.4, 1.0, 0.0)),
+            ],
+        );
+
+        let samples: Vec<f32> = out.iter().flat_map(|f| f.samples.iter().copied()).collect();
+        assert!(samples.len().abs_diff(48_000) <= 1, "{}", samples.len());
+        let p440 = tone_power(&samples, 48_000, 440.0);
+        let p1000 = tone_power(&samples, 48_000, 1_000.0);
+        let p700 = tone_power(&samples, 48_000, 700.0);
+        assert!(p440 > 100.0 * p700, "440 Hz missing: {p440} vs {p700}");
+        assert!(p1000 > 100.0 * p700, "1 kHz missing: {p1000} vs {p700}");
+
+        // Output timestamps advance contiguously from the first source
+        assert!(out[0].timestamp.abs() < 1e-9);
+        for pair in out.windows(2) {
+            let expected = pair[0].timestamp + pair[0].samples.len() as f64 / 48_000.0;
+            assert!((pair[1].timestamp - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_mix_resamples_and_aligns_late_source() {
+        let mut mixer = AudioMixer::new(48_000, 2);
+        let mic = mixer.add_source(1.0);
+        let system = mixer.add_source(0.5);
+        // System audio at 44.1 kHz starts 250 ms after the mic
+        let out = mix_all(
+            &mut mixer,
+            &[
+                (mic, sine_frames(48_000, 440.0, 0.3, 1.0, 0.0)),
+                (system, sine_frames(44_100, 1_000.0, 0.6, 0.75, 0.25)),
+            ],
+        );
+        assert!(out
+            .iter()
+            .all(|f| f.sample_rate == 48_000 && f.channels == 2));
+
+        let left: Vec<f32> = out
+            .iter()
+            .flat_map(|f| f.samples.iter().step_by(2).copied())
+            .collect();
+        let (head, tail) = left.split_at(10_000);
+        // Before 250 ms only the mic plays
+        assert!(tone_power(head, 48_000, 1_000.0) < 1e-4 * tone_power(head, 48_000, 440.0));
+        let tail = &tail[3_000..];
+        assert!(tone_power(tail, 48_000, 1_000.0) > 0.1 * tone_power(tail, 48_000, 440.0));
+    }
+
+    #[test]
+    fn test_sum_is_soft_limited() {
+        let mut mixer = AudioMixer::new(48_000, 1);
+        let a = mixer.add_source(1.0);
+        let b = mixer.add_source(1.0);
+        let out = mix_all(
+            &mut mixer,
+            &[
+                (a, sine_frames(48_000, 440.0, 1.0, 0.2, 0.0)),
+                (b, sine_frames(48_000, 440.0, 1.0, 0.2, 0.0)),
+            ],
+        );
+        let peak = out
+            .iter()
+            .flat_map(|f| f.samples.iter())
+            .fold(0.0_f32, |peak, s| peak.max(s.abs()));
+        assert!(peak <= 1.0 && peak > LIMITER_KNEE, "{peak}");
+        assert!((soft_limit(0.5) - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_silent_source_does_not_stall_mix() {
+        let mut mixer = AudioMixer::new(48_000, 1);
+        let mic = mixer.add_source(1.0);
+        let _loopback = mixer.add_source(1.0);
+        let mut out = Vec::new();
+        for frame in sine_frames(48_000, 440.0, 0.5, 0.5, 0.0) {
+            mixer.push(mic, &frame).unwrap();
+            out.extend(mixer.poll());
+        }
+        let mixed: usize = out.iter().map(|f| f.samples.len()).sum();
+        // Everything but the lag window is mixed before flushing
+        assert!(mixed >= 48_000 / 2 - 48_000 / 5 - 960, "{mixed}");
+        assert!(mixer.push(7, &out[0]).is_err());
+    }
+}
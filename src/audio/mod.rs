@@ -6,8 +6,11 @@
 //!
 //! Submodules:
 //! - `device`: Audio device enumeration
+//! - `loopback`: System-audio (loopback) sources and platform limits
 //! - `capture`: PCM audio capture with bounded buffering
 //! - `encoder`: Opus audio encoding
+//! - `mixer`: Timestamp-aligned mixing of several sources into one track
+//! - `resample`: Sample-rate conversion to the requested output rate
 //! - `clock`: PTS (Presentation Timestamp) synchronization
 
 /// Standard audio sample rate for Opus encoding (48kHz)
@@ -19,8 +22,14 @@ pub const AUDIO_CHANNELS: u16 = 2;
 mod capture;
 mod device;
 mod encoder;
+pub mod loopback;
+mod mixer;
+mod resample;
 
 pub use crate::timing::PTSClock;
-pub use capture::{AudioCapture, AudioFrame};
-pub use device::{get_default_audio_device, list_audio_devices, AudioDevice};
+pub use capture::{AudioCapture, AudioDeviceSwitch, AudioFrame};
+pub use device::{get_default_audio_device, list_audio_devices, AudioDevice, DeviceKind};
 pub use encoder::{EncodedAudio, OpusEncoder};
+pub use loopback::list_loopback_devices;
+pub use mixer::AudioMixer;
+pub use resample::Resampler;
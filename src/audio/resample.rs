@@ -0,0 +1,193 @@
+//! Streaming sample-rate conversion for captured audio
+//!
+//! Microphones often run at 44.1 kHz (or 16/96 kHz) while Opus needs
+//! 48 kHz. [`Resampler`] converts interleaved f32 PCM between any two
+//! rates with a windowed-sinc interpolator, keeping a few input frames of
+//! history between calls so chunk boundaries are seamless.
+//!
+//! When downsampling, the filter cutoff drops to the output Nyquist
+//! frequency so content above it is attenuated instead of aliasing. The
+//! filter delays the signal by `HALF_TAPS` input frames (under 0.2 ms at
+//! 44.1 kHz).
+
+use std::f64::consts::PI;
+
+/// Input frames on each side of the interpolation point
+const HALF_TAPS: usize = 8;
+
+/// Converts interleaved PCM from one sample rate to another
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    input_rate: u32,
+    output_rate: u32,
+    channels: usize,
+    /// Input frames per output frame
+    step: f64,
+    /// Normalized cutoff (1.0 = input Nyquist)
+    cutoff: f64,
+    /// Position of the next output frame, in input frames from the start
+    /// of `history`
+    position: f64,
+    /// Interleaved input frames still needed by upcoming output frames
+    history: Vec<f32>,
+}
+
+impl Resampler {
+    /// Create a resampler for `channels` interleaved channels
+    pub fn new(input_rate: u32, output_rate: u32, channels: u16) -> Self {
+        let step = f64::from(input_rate) / f64::from(output_rate.max(1));
+        Self {
+            input_rate,
+            output_rate,
+            channels: usize::from(channels.max(1)),
+            step,
+            cutoff: (1.0 / step).min(1.0) * 0.95,
+            position: 0.0,
+            history: Vec::new(),
+        }
+    }
+
+    /// Whether the rates differ, i.e. [`process`](Self::process) does work
+    pub fn is_active(&self) -> bool {
+        self.input_rate != self.output_rate
+    }
+
+    /// Sample rate of the input
+    pub fn input_rate(&self) -> u32 {
+        self.input_rate
+    }
+
+    /// Sample rate of the output
+    pub fn output_rate(&self) -> u32 {
+        self.output_rate
+    }
+
+    /// Convert a chunk of interleaved input, returning the output frames it
+    /// completes (possibly none for very small chunks)
+    // Frame counts and positions stay far below 2^52, and positions are >= 0
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    pub fn process(&mut self, input: &[f32]) -> Vec<f32> {
+        if !self.is_active() {
+            return input.to_vec();
+        }
+        self.history.extend_from_slice(input);
+        let frames = self.history.len() / self.channels;
+
+        let mut out = Vec::with_capacity(((input.len() as f64) / self.step) as usize + 4);
+        while self.position + (HALF_TAPS as f64) < frames as f64 {
+            let base = self.position.floor() as isize;
+            let frac = self.position - base as f64;
+            for channel in 0..self.channels {
+                out.push(self.interpolate(base, frac, channel));
+            }
+            self.position += self.step;
+        }
+
+        // Keep the frames the next output frame still reaches back to
+        let consumed = (self.position.floor() as usize)
+            .saturating_sub(HALF_TAPS - 1)
+            .min(frames);
+        self.history.drain(..consumed * self.channels);
+        self.position -= consumed as f64;
+        out
+    }
+
+    // Tap offsets are tiny; the result is narrowed back to an f32 sample
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_wrap,
+        clippy::cast_possible_truncation
+    )]
+    fn interpolate(&self, base: isize, frac: f64, channel: usize) -> f32 {
+        let mut acc = 0.0;
+        let mut weight_sum = 0.0;
+        let half = HALF_TAPS as isize;
+        for k in (1 - half)..=half {
+            let Ok(index) = usize::try_from(base + k) else {
+                continue;
+            };
+            let x = k as f64 - frac;
+            let weight = self.cutoff * sinc(self.cutoff * x) * hann(x / (HALF_TAPS as f64));
+            acc += f64::from(self.history[index * self.channels + channel]) * weight;
+            weight_sum += weight;
+        }
+        if weight_sum.abs() > f64::EPSILON {
+            (acc / weight_sum) as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+/// Hann window over `-1..=1`
+fn hann(x: f64) -> f64 {
+    if x.abs() >= 1.0 {
+        0.0
+    } else {
+        0.5 * (1.0 + (PI * x).cos())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(rate: u32, freq: f64, frames: usize, channels: usize) -> Vec<f32> {
+        (0..frames)
+            .flat_map(|i| {
+                #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+                let v = (2.0 * PI * freq * i as f64 / f64::from(rate)).sin() as f32;
+                std::iter::repeat(v).take(channels)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_44k_to_48k_keeps_ratio_and_pitch() {
+        let input = sine(44_100, 440.0, 44_100, 2);
+        let mut resampler = Resampler::new(44_100, 48_000, 2);
+        let mut output = Vec::new();
+        for chunk in input.chunks(441 * 2) {
+            output.extend(resampler.process(chunk));
+        }
+        let frames = output.len() / 2;
+        assert!(frames.abs_diff(48_000) <= HALF_TAPS + 1, "{frames} frames");
+
+        // Rising zero crossings of the left channel count the cycles
+        let left: Vec<f32> = output.iter().step_by(2).copied().collect();
+        let cycles = left
+            .windows(2)
+            .filter(|w| w[0] < 0.0 && w[1] >= 0.0)
+            .count();
+        assert!((439..=441).contains(&cycles), "{cycles} cycles");
+        assert!(left.iter().all(|s| s.abs() <= 1.05));
+    }
+
+    #[test]
+    fn test_equal_rates_pass_through() {
+        let mut resampler = Resampler::new(48_000, 48_000, 1);
+        assert!(!resampler.is_active());
+        assert_eq!(resampler.process(&[0.1, 0.2, 0.3]), [0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn test_downsampling_halves_sample_count() {
+        let input = sine(96_000, 1_000.0, 9_600, 1);
+        let mut resampler = Resampler::new(96_000, 48_000, 1);
+        let output = resampler.process(&input);
+        assert!(output.len().abs_diff(4_800) <= HALF_TAPS);
+    }
+}
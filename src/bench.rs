@@ -0,0 +1,445 @@
+//! Capture → convert → encode → mux throughput benchmark
+//!
+//! [`run_pipeline_bench`] pushes synthetic camera frames through the same
+//! stages as a recording: delivery of the raw buffer, conversion to RGB8
+//! ([`to_rgb8_auto`], so the GPU path is used when enabled), openh264
+//! encoding, and MP4 muxing into a discarding writer. The report gives
+//! end-to-end fps and per-stage latency, so a change to one stage can be
+//! compared before and after, and users can see what their hardware
+//! sustains at a given resolution.
+//!
+//! Allocation counts need [`CountingAllocator`] installed as the global
+//! allocator of the binary running the benchmark; otherwise
+//! [`BenchReport::allocations`] is `None`:
+//!
+//! ```rust,ignore
+//! #[global_allocator]
+//! static ALLOC: crabcamera::bench::CountingAllocator = crabcamera::bench::CountingAllocator;
+//! ```
+//!
+//! `cargo bench --features bench --bench pipeline_benchmarks` runs it at
+//! the common resolutions.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Instant;
+
+use muxide::api::{MuxerBuilder, VideoCodec};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    BENCH_DEFAULT_FRAMES, BENCH_SOURCE_FRAMES, BENCH_WARMUP_FRAMES, VIDEO_BITRATE_HD,
+};
+use crate::errors::CameraError;
+use crate::platform::convert::to_rgb8_auto;
+use crate::recording::H264Encoder;
+use crate::testing::synthetic_video_frame;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static COUNTING: AtomicBool = AtomicBool::new(false);
+
+/// Global allocator wrapper counting allocations for [`BenchReport`]
+///
+/// Forwards to the system allocator; reallocations count as allocations.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record(size: usize) {
+        COUNTING.store(true, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+/// Allocation count and bytes so far, if [`CountingAllocator`] is installed
+fn allocation_snapshot() -> Option<(u64, u64)> {
+    COUNTING.load(Ordering::Relaxed).then(|| {
+        (
+            ALLOCATIONS.load(Ordering::Relaxed),
+            ALLOCATED_BYTES.load(Ordering::Relaxed),
+        )
+    })
+}
+
+/// Parameters of one [`run_pipeline_bench`] run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineBenchConfig {
+    /// Frame width in pixels
+    pub width: u32,
+    /// Frame height in pixels
+    pub height: u32,
+    /// Timed frames
+    pub frames: u32,
+    /// Untimed frames pushed through first (encoder and allocator warm-up)
+    pub warmup_frames: u32,
+    /// Nominal frame rate, used for rate control and timestamps
+    pub fps: f64,
+    /// Encoder bitrate in bits per second
+    pub bitrate: u32,
+    /// Raw format delivered by the synthetic camera: `YUYV`, `NV12`,
+    /// `MJPEG` or `RGB24` (which skips the conversion stage)
+    pub source_format: String,
+}
+
+impl PipelineBenchConfig {
+    /// YUYV source at `width`x`height`, 30 fps, default frame counts
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            frames: BENCH_DEFAULT_FRAMES,
+            warmup_frames: BENCH_WARMUP_FRAMES,
+            fps: 30.0,
+            bitrate: VIDEO_BITRATE_HD,
+            source_format: "YUYV".to_string(),
+        }
+    }
+
+    /// Set the number of timed frames
+    pub fn with_frames(mut self, frames: u32) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    /// Set the number of warm-up frames
+    pub fn with_warmup_frames(mut self, frames: u32) -> Self {
+        self.warmup_frames = frames;
+        self
+    }
+
+    /// Set the raw source format
+    pub fn with_source_format(mut self, format: impl Into<String>) -> Self {
+        self.source_format = format.into();
+        self
+    }
+
+    /// Set the encoder bitrate
+    pub fn with_bitrate(mut self, bitrate: u32) -> Self {
+        self.bitrate = bitrate;
+        self
+    }
+}
+
+/// Latency distribution of one pipeline stage, in milliseconds
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StageLatency {
+    /// Mean per frame
+    pub mean_ms: f64,
+    /// Median per frame
+    pub p50_ms: f64,
+    /// 95th percentile per frame
+    pub p95_ms: f64,
+    /// Slowest frame
+    pub max_ms: f64,
+}
+
+impl StageLatency {
+    // Sample counts are far below 2^52
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn from_samples(mut samples: Vec<f64>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_by(f64::total_cmp);
+        let at = |q: f64| samples[((samples.len() - 1) as f64 * q).round() as usize];
+        Self {
+            mean_ms: samples.iter().sum::<f64>() / samples.len() as f64,
+            p50_ms: at(0.5),
+            p95_ms: at(0.95),
+            max_ms: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Allocations made during the timed frames
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllocationStats {
+    /// Number of allocations (including reallocations)
+    pub count: u64,
+    /// Bytes requested
+    pub bytes: u64,
+    /// Allocations per timed frame
+    pub per_frame: f64,
+}
+
+/// Result of [`run_pipeline_bench`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    /// Frame width in pixels
+    pub width: u32,
+    /// Frame height in pixels
+    pub height: u32,
+    /// Raw source format
+    pub source_format: String,
+    /// Timed frames
+    pub frames: u32,
+    /// Wall time of the timed frames
+    pub elapsed_secs: f64,
+    /// End-to-end frames per second
+    pub fps: f64,
+    /// Delivery of the raw buffer (copy out of the camera's buffer)
+    pub capture: StageLatency,
+    /// Conversion to RGB8; `None` for RGB sources
+    pub convert: Option<StageLatency>,
+    /// H.264 encoding
+    pub encode: StageLatency,
+    /// MP4 muxing
+    pub mux: StageLatency,
+    /// Bytes of the finished MP4
+    pub bytes_written: u64,
+    /// `None` unless [`CountingAllocator`] is the global allocator
+    pub allocations: Option<AllocationStats>,
+}
+
+/// Run the synthetic capture → convert → encode → mux pipeline once
+///
+/// # Errors
+/// Returns [`CameraError::UnsupportedFormat`] for an unknown source format,
+/// [`CameraError::InitializationError`] for zero dimensions or frame rate,
+/// and the encoder, conversion or muxing error if a stage fails.
+// Frame counts and durations stay far below 2^52
+#[allow(clippy::cast_precision_loss)]
+pub fn run_pipeline_bench(config: &PipelineBenchConfig) -> Result<BenchReport, CameraError> {
+    if config.width == 0 || config.height == 0 || config.fps <= 0.0 {
+        return Err(CameraError::InitializationError(format!(
+            "Invalid benchmark parameters: {}x{} at {} fps",
+            config.width, config.height, config.fps
+        )));
+    }
+    let (width, height) = (config.width, config.height);
+    let converts = !is_rgb(&config.source_format);
+    let sources = (0..BENCH_SOURCE_FRAMES as u64)
+        .map(|n| synthetic_source(&config.source_format, width, height, n))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut encoder = H264Encoder::new(width, height, config.fps, config.bitrate)?;
+    let mut muxer = MuxerBuilder::new(std::io::sink())
+        .video(VideoCodec::H264, width, height, config.fps)
+        .with_fast_start(false)
+        .build()
+        .map_err(|e| CameraError::MuxingError(format!("Failed to create muxer: {e}")))?;
+
+    let total = config.warmup_frames + config.frames;
+    let timed = config.frames as usize;
+    let (mut capture, mut convert, mut encode, mut mux) = (
+        Vec::with_capacity(timed),
+        Vec::with_capacity(timed),
+        Vec::with_capacity(timed),
+        Vec::with_capacity(timed),
+    );
+    let mut started = Instant::now();
+    let mut allocations_before = None;
+
+    for n in 0..total {
+        if n == config.warmup_frames {
+            allocations_before = allocation_snapshot();
+            started = Instant::now();
+        }
+        let measured = n >= config.warmup_frames;
+
+        let t = Instant::now();
+        let raw = sources[n as usize % sources.len()].clone();
+        let capture_ms = elapsed_ms(t);
+
+        let t = Instant::now();
+        let rgb = if converts {
+            to_rgb8_auto(&raw, width, height, &config.source_format)?
+        } else {
+            raw
+        };
+        let convert_ms = elapsed_ms(t);
+
+        let t = Instant::now();
+        let encoded = encoder.encode_rgb(&rgb)?;
+        let encode_ms = elapsed_ms(t);
+
+        let t = Instant::now();
+        if !encoded.data.is_empty() {
+            let pts = f64::from(n) / config.fps;
+            muxer
+                .write_video(pts, &encoded.data, encoded.is_keyframe)
+                .map_err(|e| CameraError::MuxingError(format!("Failed to write frame: {e}")))?;
+        }
+        let mux_ms = elapsed_ms(t);
+
+        if measured {
+            capture.push(capture_ms);
+            convert.push(convert_ms);
+            encode.push(encode_ms);
+            mux.push(mux_ms);
+        }
+    }
+
+    let elapsed_secs = started.elapsed().as_secs_f64();
+    let allocations = allocations_before.zip(allocation_snapshot()).map(
+        |((count, bytes), (count_after, bytes_after))| {
+            let count = count_after - count;
+            AllocationStats {
+                count,
+                bytes: bytes_after - bytes,
+                per_frame: count as f64 / f64::from(config.frames.max(1)),
+            }
+        },
+    );
+    let stats = muxer
+        .finish_with_stats()
+        .map_err(|e| CameraError::MuxingError(format!("Failed to finalize: {e}")))?;
+
+    Ok(BenchReport {
+        width,
+        height,
+        source_format: config.source_format.clone(),
+        frames: config.frames,
+        elapsed_secs,
+        fps: if elapsed_secs > 0.0 {
+            f64::from(config.frames) / elapsed_secs
+        } else {
+            0.0
+        },
+        capture: StageLatency::from_samples(capture),
+        convert: converts.then(|| StageLatency::from_samples(convert)),
+        encode: StageLatency::from_samples(encode),
+        mux: StageLatency::from_samples(mux),
+        bytes_written: stats.bytes_written,
+        allocations,
+    })
+}
+
+fn elapsed_ms(since: Instant) -> f64 {
+    since.elapsed().as_secs_f64() * 1000.0
+}
+
+fn is_rgb(format: &str) -> bool {
+    matches!(
+        format.to_ascii_uppercase().as_str(),
+        "RGB24" | "RGB8" | "RAWRGB"
+    )
+}
+
+/// Raw buffer in `format` showing the moving synthetic test pattern
+fn synthetic_source(
+    format: &str,
+    width: u32,
+    height: u32,
+    frame_number: u64,
+) -> Result<Vec<u8>, CameraError> {
+    let rgb = synthetic_video_frame(frame_number, width, height).data;
+    let (w, h) = (width as usize, height as usize);
+    match format.to_ascii_uppercase().as_str() {
+        f if is_rgb(f) => Ok(rgb),
+        "YUYV" | "YUY2" => Ok(rgb
+            .chunks_exact(6)
+            .flat_map(|pair| {
+                let [y0, u, v] = rgb_to_yuv(&pair[..3]);
+                let [y1, ..] = rgb_to_yuv(&pair[3..]);
+                [y0, u, y1, v]
+            })
+            .collect()),
+        "NV12" => {
+            let mut out: Vec<u8> = rgb.chunks_exact(3).map(|p| rgb_to_yuv(p)[0]).collect();
+            for y in (0..h).step_by(2) {
+                for x in (0..w).step_by(2) {
+                    let i = (y * w + x) * 3;
+                    let [_, u, v] = rgb_to_yuv(&rgb[i..i + 3]);
+                    out.extend([u, v]);
+                }
+            }
+            Ok(out)
+        }
+        "MJPEG" | "MJPG" => {
+            let mut jpeg = Vec::new();
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, 85)
+                .encode(&rgb, width, height, image::ExtendedColorType::Rgb8)
+                .map_err(|e| CameraError::CaptureError(format!("JPEG encode failed: {e}")))?;
+            Ok(jpeg)
+        }
+        _ => Err(CameraError::UnsupportedFormat(format.to_string())),
+    }
+}
+
+/// BT.601 limited-range Y, U, V of one RGB pixel
+// Results are clamped to 0..=255 before narrowing
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn rgb_to_yuv(pixel: &[u8]) -> [u8; 3] {
+    let (r, g, b) = (
+        i32::from(pixel[0]),
+        i32::from(pixel[1]),
+        i32::from(pixel[2]),
+    );
+    let y = ((66 * r + 129 * g + 25 * b + 128) >> 8) + 16;
+    let u = ((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128;
+    let v = ((112 * r - 94 * g - 18 * b + 128) >> 8) + 128;
+    [y, u, v].map(|c| c.clamp(0, 255) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_latency_percentiles() {
+        let stats = StageLatency::from_samples((1..=100).map(f64::from).collect());
+        assert!((stats.mean_ms - 50.5).abs() < 1e-9);
+        assert!((stats.p50_ms - 51.0).abs() < 1e-9);
+        assert!((stats.p95_ms - 95.0).abs() < 1e-9);
+        assert!((stats.max_ms - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_synthetic_sources_have_format_sizes() {
+        assert_eq!(
+            synthetic_source("YUYV", 64, 48, 0).unwrap().len(),
+            64 * 48 * 2
+        );
+        assert_eq!(
+            synthetic_source("NV12", 64, 48, 0).unwrap().len(),
+            64 * 48 * 3 / 2
+        );
+        assert_eq!(
+            synthetic_source("RGB24", 64, 48, 0).unwrap().len(),
+            64 * 48 * 3
+        );
+        assert!(synthetic_source("H265", 64, 48, 0).is_err());
+    }
+
+    #[test]
+    fn test_pipeline_bench_reports_all_stages() {
+        let config = PipelineBenchConfig::new(160, 120)
+            .with_frames(5)
+            .with_warmup_frames(1);
+        let report = run_pipeline_bench(&config).unwrap();
+        assert_eq!(report.frames, 5);
+        assert!(report.fps > 0.0);
+        assert!(report.convert.is_some());
+        assert!(report.bytes_written > 0);
+
+        let rgb = run_pipeline_bench(&config.with_source_format("RGB24")).unwrap();
+        assert!(rgb.convert.is_none());
+    }
+}
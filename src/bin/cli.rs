@@ -187,6 +187,7 @@ fn cmd_list_controls(args: &[String]) -> Result<(), Box<dyn std::error::Error>>
             height: 480,
             fps: 30.0,
             format_type: "MJPEG".to_string(),
+            bit_depth: 8,
         }, // dummy
         buffer_policy: BufferPolicy::DropOldest { capacity: 2 },
         audio_mode: AudioMode::Disabled,
@@ -236,6 +237,7 @@ fn cmd_set_control(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
             height: 480,
             fps: 30.0,
             format_type: "MJPEG".to_string(),
+            bit_depth: 8,
         }, // dummy
         buffer_policy: BufferPolicy::DropOldest { capacity: 2 },
         audio_mode: AudioMode::Disabled,
@@ -311,10 +313,5 @@ fn parse_format(s: &str) -> Result<CameraFormat, Box<dyn std::error::Error>> {
     }
     let width: u32 = size_parts[0].parse()?;
     let height: u32 = size_parts[1].parse()?;
-    Ok(CameraFormat {
-        width,
-        height,
-        fps: fps as f32,
-        format_type: format_type.to_string(),
-    })
+    Ok(CameraFormat::new(width, height, fps as f32).with_format_type(format_type.to_string()))
 }
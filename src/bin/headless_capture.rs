@@ -93,6 +93,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             height: format.height,
             fps: format.fps,
             format_type: format.format_type.clone(),
+            bit_depth: format.bit_depth,
         },
         buffer_policy: BufferPolicy::DropOldest {
             capacity: HEADLESS_BUFFER_CAPACITY,
@@ -1,13 +1,31 @@
-use crate::commands::capture::get_or_create_camera;
-use crate::constants::{MAX_ISO, MIN_ISO};
-use crate::platform::PlatformCamera;
+use crate::commands::capture::{get_or_create_camera, CaptureStage, Operation};
+use crate::constants::{
+    AUTOFOCUS_LOCK_TIMEOUT_MS, AUTOFOCUS_REGION_FRACTION, AUTOFOCUS_SETTLE_FRAMES,
+    AUTOFOCUS_SETTLE_TOLERANCE, AUTOFOCUS_SWEEP_STEPS, DEFAULT_EXPOSURE_RANGE, MAX_ISO, MIN_ISO,
+};
+use crate::errors::CommandError;
+use crate::platform::{get_existing_camera, get_last_known_metrics, PlatformCamera};
+use crate::quality::{AutoFramer, BlurDetector};
 use crate::types::{
-    BurstConfig, CameraControls, CameraFrame, ControlApplicationResult, WhiteBalance,
+    BurstConfig, CameraCapabilities, CameraControls, CameraControlsPatch, CameraFrame,
+    CapturePreset, CapturePresetResult, ControlApplicationResult, ExposureMode, ExposureModeResult,
+    ExposureReading, ExposureSettings, ExposureValue, FocusLock, FocusMethod, FocusMode,
+    FocusModeResult, FocusModeSupport, FocusPointResult, GainReading, GainValue, Rect,
+    WhiteBalance, WhiteBalanceCorrection, WhiteBalancePreset, WhiteBalanceResult,
 };
-use std::sync::{Arc, Mutex as StdMutex};
-use std::time::Instant;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex as StdMutex};
+use std::time::{Duration, Instant};
 use tauri::command;
 
+// Control batches started with `begin_controls`, by device id
+static CONTROL_SESSIONS: LazyLock<StdMutex<HashMap<String, ControlSession>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+// Per-device auto-framing state, so successive captures pan smoothly
+static AUTOFRAMERS: LazyLock<StdMutex<HashMap<String, AutoFramer>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
 /// Apply advanced camera controls
 ///
 /// # Errors
@@ -18,7 +36,7 @@ use tauri::command;
 pub async fn set_camera_controls(
     device_id: String,
     controls: CameraControls,
-) -> Result<ControlApplicationResult, String> {
+) -> Result<ControlApplicationResult, CommandError> {
     log::info!("Setting camera controls for device: {device_id}");
 
     let camera_arc =
@@ -28,11 +46,11 @@ pub async fn set_camera_controls(
     tokio::task::spawn_blocking(move || {
         let mut camera = camera_arc
             .lock()
-            .map_err(|_| "Mutex poisoned".to_string())?;
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
 
         let result = camera.apply_controls(&controls).map_err(|e| {
             log::error!("Failed to apply camera controls: {e}");
-            format!("Failed to apply controls: {e}")
+            CommandError::new(e.error_code(), format!("Failed to apply controls: {e}"))
         })?;
 
         log::info!(
@@ -45,7 +63,260 @@ pub async fn set_camera_controls(
         Ok(result)
     })
     .await
-    .map_err(|e| format!("Task join error: {e}"))?
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// Apply only the controls set in `patch`, leaving all others untouched
+///
+/// Returns the effective controls reported by the camera afterwards.
+///
+/// # Errors
+/// Returns an `Err` if the camera cannot be created or retrieved, if the
+/// camera mutex is poisoned, if the blocking task fails to join, or if
+/// applying or reading back the controls fails.
+#[command]
+pub async fn patch_camera_controls(
+    device_id: String,
+    patch: CameraControlsPatch,
+) -> Result<CameraControls, CommandError> {
+    log::info!("Patching camera controls for device: {device_id}");
+
+    let camera_arc =
+        get_or_create_camera(device_id.clone(), crate::types::CameraFormat::standard()).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut camera = camera_arc
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+
+        if !patch.is_empty() {
+            let result = camera
+                .apply_controls(&CameraControls::from(patch))
+                .map_err(|e| {
+                    log::error!("Failed to patch camera controls: {e}");
+                    CommandError::new(e.error_code(), format!("Failed to apply controls: {e}"))
+                })?;
+
+            log::info!(
+                "Camera controls patched for device {} (applied={}, rejected={})",
+                device_id,
+                result.applied.len(),
+                result.rejected.len()
+            );
+        }
+
+        camera
+            .get_controls()
+            .map_err(|e| CommandError::new(e.error_code(), format!("Failed to read controls: {e}")))
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// Control changes collected for one camera and applied together
+///
+/// Setting focus, exposure and white balance one command at a time makes
+/// the picture flicker through each intermediate state and costs a device
+/// round-trip per control. A session collects the changes, later ones
+/// replacing earlier ones for the same control, and [`commit`](Self::commit)
+/// hands them to the camera in a single `apply_controls` call.
+///
+/// No backend can set several controls atomically, so a batch is applied in
+/// a fixed order: focus, exposure mode, exposure time and ISO, white balance,
+/// then image adjustments (brightness, contrast, ...). Exposure settles
+/// before white balance is measured against it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ControlSession {
+    /// Camera the changes are for
+    pub device_id: String,
+    /// Changes collected so far
+    pub patch: CameraControlsPatch,
+}
+
+impl ControlSession {
+    /// Start an empty session for `device_id`
+    pub fn new(device_id: String) -> Self {
+        Self {
+            device_id,
+            patch: CameraControlsPatch::default(),
+        }
+    }
+
+    /// Fix focus at `distance` (0.0 = infinity, 1.0 = closest), turning
+    /// auto-focus off
+    #[must_use]
+    pub fn focus(self, distance: f32) -> Self {
+        self.patch(CameraControlsPatch {
+            auto_focus: Some(false),
+            focus_distance: Some(distance),
+            ..CameraControlsPatch::default()
+        })
+    }
+
+    /// Fix the exposure time in seconds, turning auto-exposure off
+    #[must_use]
+    pub fn exposure(self, exposure_time: f32) -> Self {
+        self.patch(CameraControlsPatch {
+            auto_exposure: Some(false),
+            exposure_time: Some(exposure_time),
+            ..CameraControlsPatch::default()
+        })
+    }
+
+    /// Fix the ISO sensitivity, turning auto-exposure off
+    #[must_use]
+    pub fn iso(self, iso_sensitivity: u32) -> Self {
+        self.patch(CameraControlsPatch {
+            auto_exposure: Some(false),
+            iso_sensitivity: Some(iso_sensitivity),
+            ..CameraControlsPatch::default()
+        })
+    }
+
+    /// Set the white balance
+    #[must_use]
+    pub fn white_balance(self, white_balance: WhiteBalance) -> Self {
+        self.patch(CameraControlsPatch {
+            white_balance: Some(white_balance),
+            ..CameraControlsPatch::default()
+        })
+    }
+
+    /// Add every control set in `patch`
+    #[must_use]
+    pub fn patch(mut self, patch: CameraControlsPatch) -> Self {
+        self.patch.merge(patch);
+        self
+    }
+
+    /// Check the collected values against the limits of the single-control
+    /// commands
+    ///
+    /// # Errors
+    /// Returns an `INVALID_ARGUMENT` error if the focus distance is outside
+    /// `[0.0, 1.0]`, the exposure time outside `(0.0, 10.0]`, or the ISO
+    /// outside the supported range.
+    pub fn validate(&self) -> Result<(), CommandError> {
+        if let Some(distance) = self.patch.focus_distance {
+            if !(0.0..=1.0).contains(&distance) {
+                return Err(CommandError::invalid_argument(
+                    "Focus distance must be between 0.0 (infinity) and 1.0 (closest)",
+                ));
+            }
+        }
+        if let Some(exposure_time) = self.patch.exposure_time {
+            if exposure_time <= 0.0 || exposure_time > 10.0 {
+                return Err(CommandError::invalid_argument(
+                    "Exposure time must be between 0.0 and 10.0 seconds",
+                ));
+            }
+        }
+        if let Some(iso) = self.patch.iso_sensitivity {
+            if !(MIN_ISO..=MAX_ISO).contains(&iso) {
+                return Err(CommandError::invalid_argument(format!(
+                    "ISO sensitivity must be between {MIN_ISO} and {MAX_ISO}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the collected changes in one call
+    ///
+    /// An empty session succeeds without touching the camera.
+    ///
+    /// # Errors
+    /// Returns an `Err` if [`validate`](Self::validate) fails, otherwise
+    /// propagates any error from [`set_camera_controls`].
+    pub async fn commit(self) -> Result<ControlApplicationResult, CommandError> {
+        self.validate()?;
+        if self.patch.is_empty() {
+            return Ok(ControlApplicationResult {
+                applied: vec![],
+                rejected: vec![],
+            });
+        }
+        if self.patch.white_balance.is_some() {
+            crate::quality::white_balance::set_software_white_balance(&self.device_id, None);
+        }
+        set_camera_controls(self.device_id, CameraControls::from(self.patch)).await
+    }
+}
+
+/// Start collecting control changes for a camera
+///
+/// Changes passed to [`stage_controls`] are held until [`commit_controls`]
+/// applies them together (see [`ControlSession`]). Beginning again discards
+/// changes not yet committed.
+///
+/// # Errors
+/// This function always succeeds and never returns an `Err`.
+#[command]
+pub async fn begin_controls(device_id: String) -> Result<(), CommandError> {
+    if let Ok(mut sessions) = CONTROL_SESSIONS.lock() {
+        sessions.insert(device_id.clone(), ControlSession::new(device_id));
+    }
+    Ok(())
+}
+
+/// Add changes to the camera's pending control session
+///
+/// Returns every change collected so far.
+///
+/// # Errors
+/// Returns a `NOT_FOUND` error if [`begin_controls`] was not called for the
+/// camera, or an `INVALID_ARGUMENT` error if a value is out of range (the
+/// session keeps its previous changes).
+#[command]
+pub async fn stage_controls(
+    device_id: String,
+    patch: CameraControlsPatch,
+) -> Result<CameraControlsPatch, CommandError> {
+    let mut sessions = CONTROL_SESSIONS
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?;
+    let session = sessions.get_mut(&device_id).ok_or_else(|| {
+        CommandError::not_found(format!("No control session for device {device_id}"))
+    })?;
+    let staged = session.clone().patch(patch);
+    staged.validate()?;
+    *session = staged;
+    Ok(session.patch.clone())
+}
+
+/// Apply the camera's pending control changes in one call and end the
+/// session
+///
+/// # Errors
+/// Returns a `NOT_FOUND` error if no session was begun for the camera,
+/// otherwise propagates any error from [`ControlSession::commit`].
+#[command]
+pub async fn commit_controls(device_id: String) -> Result<ControlApplicationResult, CommandError> {
+    let session = CONTROL_SESSIONS
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?
+        .remove(&device_id)
+        .ok_or_else(|| {
+            CommandError::not_found(format!("No control session for device {device_id}"))
+        })?;
+    log::info!(
+        "Committing control session for device {device_id}: {:?}",
+        session.patch
+    );
+    session.commit().await
+}
+
+/// Drop the camera's pending control changes without applying them
+///
+/// Returns whether a session was pending.
+///
+/// # Errors
+/// This function always succeeds and never returns an `Err`.
+#[command]
+pub async fn discard_controls(device_id: String) -> Result<bool, CommandError> {
+    Ok(CONTROL_SESSIONS
+        .lock()
+        .is_ok_and(|mut sessions| sessions.remove(&device_id).is_some()))
 }
 
 /// Get current camera controls
@@ -55,7 +326,7 @@ pub async fn set_camera_controls(
 /// is poisoned, if the blocking task fails to join, or if reading the
 /// controls from the camera fails.
 #[command]
-pub async fn get_camera_controls(device_id: String) -> Result<CameraControls, String> {
+pub async fn get_camera_controls(device_id: String) -> Result<CameraControls, CommandError> {
     log::info!("Getting camera controls for device: {device_id}");
 
     let camera_arc =
@@ -65,7 +336,7 @@ pub async fn get_camera_controls(device_id: String) -> Result<CameraControls, St
     tokio::task::spawn_blocking(move || {
         let camera = camera_arc
             .lock()
-            .map_err(|_| "Mutex poisoned".to_string())?;
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
 
         match camera.get_controls() {
             Ok(controls) => {
@@ -74,12 +345,15 @@ pub async fn get_camera_controls(device_id: String) -> Result<CameraControls, St
             }
             Err(e) => {
                 log::error!("Failed to get camera controls: {e}");
-                Err(format!("Failed to get controls: {e}"))
+                Err(CommandError::new(
+                    e.error_code(),
+                    format!("Failed to get controls: {e}"),
+                ))
             }
         }
     })
     .await
-    .map_err(|e| format!("Task join error: {e}"))?
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
 }
 
 /// Capture burst sequence with advanced controls
@@ -95,7 +369,17 @@ pub async fn get_camera_controls(device_id: String) -> Result<CameraControls, St
 pub async fn capture_burst_sequence(
     device_id: String,
     config: BurstConfig,
-) -> Result<Vec<CameraFrame>, String> {
+) -> Result<Vec<CameraFrame>, CommandError> {
+    burst_sequence(device_id, config, &Operation::register(None)?).await
+}
+
+/// Capture a burst, reporting each frame as progress of `operation` and
+/// failing with `CANCELLED` once it is cancelled
+async fn burst_sequence(
+    device_id: String,
+    config: BurstConfig,
+    operation: &Operation,
+) -> Result<Vec<CameraFrame>, CommandError> {
     log::info!(
         "Starting burst capture: {} frames from device {}",
         config.count,
@@ -113,17 +397,32 @@ pub async fn capture_burst_sequence(
     let start_time = Instant::now();
 
     for i in 0..config.count {
+        if operation.token().is_cancelled() {
+            return Err(CommandError::cancelled(format!(
+                "Burst cancelled after {i} of {} frames",
+                config.count
+            )));
+        }
         log::debug!("Capturing burst frame {} of {}", i + 1, config.count);
 
         let frame = capture_burst_frame(camera_arc.clone(), config.clone(), i).await?;
+        operation.report(
+            &device_id,
+            i + 1,
+            Some(config.count),
+            CaptureStage::Capturing,
+            Some(&frame),
+        );
         frames.push(frame);
 
         // Wait between captures (except for the last one)
         if i < config.count - 1 {
-            tokio::time::sleep(tokio::time::Duration::from_millis(u64::from(
-                config.interval_ms,
-            )))
-            .await;
+            tokio::select! {
+                () = operation.token().cancelled() => {}
+                () = tokio::time::sleep(tokio::time::Duration::from_millis(u64::from(
+                    config.interval_ms,
+                ))) => {}
+            }
         }
     }
 
@@ -149,21 +448,29 @@ pub async fn capture_burst_sequence(
 }
 
 /// Validate a [`BurstConfig`] prior to starting a burst capture.
-fn validate_burst_config(config: &BurstConfig) -> Result<(), String> {
+fn validate_burst_config(config: &BurstConfig) -> Result<(), CommandError> {
     if config.count == 0 || config.count > 50 {
-        return Err("Invalid burst count (must be 1-50)".to_string());
+        return Err(CommandError::invalid_argument(
+            "Invalid burst count (must be 1-50)",
+        ));
     }
 
     if config.focus_stacking && config.count < 2 {
-        return Err("Focus stacking requires at least 2 frames (count >= 2)".to_string());
+        return Err(CommandError::invalid_argument(
+            "Focus stacking requires at least 2 frames (count >= 2)",
+        ));
     }
 
     if let Some(ref bracketing) = config.bracketing {
         if bracketing.stops.is_empty() {
-            return Err("Exposure bracketing requires at least one stop value".to_string());
+            return Err(CommandError::invalid_argument(
+                "Exposure bracketing requires at least one stop value",
+            ));
         }
         if bracketing.base_exposure <= 0.0 {
-            return Err("Exposure bracketing base_exposure must be greater than zero".to_string());
+            return Err(CommandError::invalid_argument(
+                "Exposure bracketing base_exposure must be greater than zero",
+            ));
         }
     }
 
@@ -171,7 +478,7 @@ fn validate_burst_config(config: &BurstConfig) -> Result<(), String> {
 }
 
 /// Start the camera stream on a blocking task, logging (not erroring) on failure.
-async fn start_burst_stream(camera_arc: Arc<StdMutex<PlatformCamera>>) -> Result<(), String> {
+async fn start_burst_stream(camera_arc: Arc<StdMutex<PlatformCamera>>) -> Result<(), CommandError> {
     tokio::task::spawn_blocking(move || {
         if let Ok(mut camera) = camera_arc.lock() {
             if let Err(e) = camera.start_stream() {
@@ -180,7 +487,7 @@ async fn start_burst_stream(camera_arc: Arc<StdMutex<PlatformCamera>>) -> Result
         }
     })
     .await
-    .map_err(|e| format!("Task join error: {e}"))
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))
 }
 
 /// Capture a single burst frame, applying exposure bracketing and focus stacking
@@ -189,11 +496,11 @@ async fn capture_burst_frame(
     camera_arc: Arc<StdMutex<PlatformCamera>>,
     config: BurstConfig,
     index: u32,
-) -> Result<CameraFrame, String> {
+) -> Result<CameraFrame, CommandError> {
     tokio::task::spawn_blocking(move || {
         let mut camera = camera_arc
             .lock()
-            .map_err(|_| "Mutex poisoned".to_string())?;
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
 
         // Apply exposure bracketing if configured
         if let Some(ref bracketing) = config.bracketing {
@@ -247,16 +554,15 @@ async fn capture_burst_frame(
             }
             Err(e) => {
                 log::error!("Failed to capture burst frame {}: {}", index + 1, e);
-                Err(format!(
-                    "Failed to capture burst frame {}: {}",
-                    index + 1,
-                    e
+                Err(CommandError::new(
+                    e.error_code(),
+                    format!("Failed to capture burst frame {}: {}", index + 1, e),
                 ))
             }
         }
     })
     .await
-    .map_err(|e| format!("Task join error: {e}"))?
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
 }
 
 /// Batch camera settings to apply in a single call
@@ -266,7 +572,7 @@ pub struct CameraSettingsInput {
     pub device_id: String,
     /// Manual focus distance (0.0 = infinity, 1.0 = closest)
     pub focus_distance: Option<f32>,
-    /// Exposure time in seconds (0.0 < t <= 10.0)
+    /// Exposure time in seconds (0.0 < t <= 10.0), not a normalized position
     pub exposure_time: Option<f32>,
     /// ISO sensitivity (50-12800)
     pub iso_sensitivity: Option<u32>,
@@ -293,12 +599,14 @@ pub struct CameraSettingsInput {
 #[command]
 pub async fn apply_camera_settings(
     settings: CameraSettingsInput,
-) -> Result<ControlApplicationResult, String> {
+) -> Result<ControlApplicationResult, CommandError> {
     let mut combined = CameraControls::default();
 
     if let Some(focus_distance) = settings.focus_distance {
         if !(0.0..=1.0).contains(&focus_distance) {
-            return Err("Focus distance must be between 0.0 and 1.0".to_string());
+            return Err(CommandError::invalid_argument(
+                "Focus distance must be between 0.0 and 1.0",
+            ));
         }
         combined.auto_focus = Some(false);
         combined.focus_distance = Some(focus_distance);
@@ -306,7 +614,9 @@ pub async fn apply_camera_settings(
 
     if let Some(exposure_time) = settings.exposure_time {
         if exposure_time <= 0.0 || exposure_time > 10.0 {
-            return Err("Exposure time must be between 0.0 and 10.0 seconds".to_string());
+            return Err(CommandError::invalid_argument(
+                "Exposure time must be between 0.0 and 10.0 seconds",
+            ));
         }
         combined.auto_exposure = Some(false);
         combined.exposure_time = Some(exposure_time);
@@ -314,9 +624,9 @@ pub async fn apply_camera_settings(
 
     if let Some(iso_sensitivity) = settings.iso_sensitivity {
         if !(MIN_ISO..=MAX_ISO).contains(&iso_sensitivity) {
-            return Err(format!(
+            return Err(CommandError::invalid_argument(format!(
                 "ISO sensitivity must be between {MIN_ISO} and {MAX_ISO}"
-            ));
+            )));
         }
         combined.auto_exposure = Some(false);
         combined.iso_sensitivity = Some(iso_sensitivity);
@@ -363,9 +673,11 @@ pub async fn apply_camera_settings(
 pub async fn set_manual_focus(
     device_id: String,
     focus_distance: f32,
-) -> Result<ControlApplicationResult, String> {
+) -> Result<ControlApplicationResult, CommandError> {
     if !(0.0..=1.0).contains(&focus_distance) {
-        return Err("Focus distance must be between 0.0 (infinity) and 1.0 (closest)".to_string());
+        return Err(CommandError::invalid_argument(
+            "Focus distance must be between 0.0 (infinity) and 1.0 (closest)",
+        ));
     }
 
     let controls = CameraControls {
@@ -392,15 +704,17 @@ pub async fn set_manual_exposure(
     device_id: String,
     exposure_time: f32,
     iso_sensitivity: u32,
-) -> Result<ControlApplicationResult, String> {
+) -> Result<ControlApplicationResult, CommandError> {
     if exposure_time <= 0.0 || exposure_time > 10.0 {
-        return Err("Exposure time must be between 0.0 and 10.0 seconds".to_string());
+        return Err(CommandError::invalid_argument(
+            "Exposure time must be between 0.0 and 10.0 seconds",
+        ));
     }
 
     if !(MIN_ISO..=MAX_ISO).contains(&iso_sensitivity) {
-        return Err(format!(
+        return Err(CommandError::invalid_argument(format!(
             "ISO sensitivity must be between {MIN_ISO} and {MAX_ISO}"
-        ));
+        )));
     }
 
     let controls = CameraControls {
@@ -413,6 +727,164 @@ pub async fn set_manual_exposure(
     set_camera_controls(device_id, controls).await
 }
 
+/// Switch between auto, shutter-priority and manual exposure
+///
+/// `Auto` turns auto-exposure on. `ShutterPriority` turns it off and fixes
+/// the shutter, leaving ISO/gain to the camera. `Manual` fixes both. The
+/// shutter and ISO are checked against the camera's reported
+/// `exposure_range` and `iso_range` (or the default limits when it reports
+/// none). Only the exposure controls are touched; the result reports which
+/// aspects of the mode the camera honored.
+///
+/// # Errors
+/// Returns an `Err` if the shutter or ISO is out of range, if the camera
+/// cannot be obtained, the mutex is poisoned, the blocking task fails to
+/// join, or reading capabilities or applying the controls fails.
+#[command]
+pub async fn set_exposure_mode(
+    device_id: String,
+    mode: ExposureMode,
+) -> Result<ExposureModeResult, CommandError> {
+    log::info!("Setting exposure mode for device {device_id}: {mode:?}");
+
+    let camera_arc =
+        get_or_create_camera(device_id.clone(), crate::types::CameraFormat::standard()).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut camera = camera_arc
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+
+        let capabilities = camera.test_capabilities().map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to read capabilities: {e}"))
+        })?;
+        validate_exposure_mode(mode, &capabilities)?;
+
+        let applied = camera
+            .apply_controls(&CameraControls::from(mode.to_patch()))
+            .map_err(|e| {
+                log::error!("Failed to set exposure mode: {e}");
+                CommandError::new(e.error_code(), format!("Failed to apply controls: {e}"))
+            })?;
+
+        let result = ExposureModeResult::from_application(mode, applied);
+        if !result.fully_honored() {
+            log::warn!(
+                "Exposure mode only partly honored by device {device_id}: rejected {:?}",
+                result.controls.rejected
+            );
+        }
+        Ok(result)
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// Read the exposure time and gain in every unit, with the camera's limits
+///
+/// Limits come from the camera's reported `exposure_range` and `iso_range`,
+/// or the default limits when it reports none.
+///
+/// # Errors
+/// Returns an `Err` if the camera cannot be obtained, the mutex is
+/// poisoned, the blocking task fails to join, or reading the controls or
+/// capabilities fails.
+#[command]
+pub async fn get_exposure_settings(device_id: String) -> Result<ExposureSettings, CommandError> {
+    let camera_arc =
+        get_or_create_camera(device_id.clone(), crate::types::CameraFormat::standard()).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let camera = camera_arc
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+        read_exposure_settings(&camera)
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// Set a manual exposure time and/or gain in any unit
+///
+/// Turns auto-exposure off and converts `exposure` to seconds and `gain`
+/// to ISO using the camera's limits (see [`get_exposure_settings`]); at
+/// least one must be given. Returns the settings the camera reports
+/// afterwards, so a value the driver rounded or rejected shows up there.
+///
+/// # Errors
+/// Returns an `Err` if neither value is given, if a value converts to
+/// something outside the camera's limits, if the camera cannot be
+/// obtained, the mutex is poisoned, the blocking task fails to join, or
+/// applying or reading back the controls fails.
+#[command]
+pub async fn set_exposure(
+    device_id: String,
+    exposure: Option<ExposureValue>,
+    gain: Option<GainValue>,
+) -> Result<ExposureSettings, CommandError> {
+    if exposure.is_none() && gain.is_none() {
+        return Err(CommandError::invalid_argument(
+            "Give an exposure time, a gain, or both",
+        ));
+    }
+    log::info!("Setting exposure for device {device_id}: {exposure:?}, gain {gain:?}");
+
+    let camera_arc =
+        get_or_create_camera(device_id.clone(), crate::types::CameraFormat::standard()).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut camera = camera_arc
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+
+        let capabilities = camera.test_capabilities().map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to read capabilities: {e}"))
+        })?;
+        let (exposure_range, iso_range) = exposure_limits(&capabilities);
+
+        let mut patch = CameraControlsPatch {
+            auto_exposure: Some(false),
+            ..CameraControlsPatch::default()
+        };
+        if let Some(exposure) = exposure {
+            let seconds = exposure.to_seconds(exposure_range);
+            let (min, max) = exposure_range;
+            if !seconds.is_finite() || !(min..=max).contains(&seconds) {
+                return Err(CommandError::invalid_argument(format!(
+                    "Exposure time must be between {min} and {max} seconds, got {seconds}"
+                )));
+            }
+            patch.exposure_time = Some(seconds);
+        }
+        if let Some(gain) = gain {
+            let iso = gain.to_iso(iso_range);
+            let (min, max) = iso_range;
+            if !(min..=max).contains(&iso) {
+                return Err(CommandError::invalid_argument(format!(
+                    "ISO sensitivity must be between {min} and {max}, got {iso}"
+                )));
+            }
+            patch.iso_sensitivity = Some(iso);
+        }
+
+        let result = camera
+            .apply_controls(&CameraControls::from(patch))
+            .map_err(|e| {
+                log::error!("Failed to set exposure: {e}");
+                CommandError::new(e.error_code(), format!("Failed to apply controls: {e}"))
+            })?;
+        if !result.rejected.is_empty() {
+            log::warn!(
+                "Device {device_id} rejected exposure controls {:?}",
+                result.rejected
+            );
+        }
+        read_exposure_settings(&camera)
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
 /// Set white balance mode
 ///
 /// ## Deprecation
@@ -425,26 +897,541 @@ pub async fn set_manual_exposure(
 pub async fn set_white_balance(
     device_id: String,
     white_balance: WhiteBalance,
-) -> Result<ControlApplicationResult, String> {
+) -> Result<ControlApplicationResult, CommandError> {
     let controls = CameraControls {
         white_balance: Some(white_balance),
         ..CameraControls::default()
     };
 
+    crate::quality::white_balance::set_software_white_balance(&device_id, None);
     set_camera_controls(device_id, controls).await
 }
 
+/// Set white balance to a color temperature in Kelvin
+///
+/// `kelvin` is clamped to `WHITE_BALANCE_MIN_KELVIN..=WHITE_BALANCE_MAX_KELVIN`
+/// (2500K-10000K). Only the white balance control is touched. If the camera
+/// rejects the control, captured RGB frames are corrected in software instead;
+/// the result reports which path is in effect.
+///
+/// # Errors
+/// Propagates any error from [`set_camera_controls`].
+#[command]
+pub async fn set_white_balance_kelvin(
+    device_id: String,
+    kelvin: u32,
+) -> Result<WhiteBalanceResult, CommandError> {
+    let clamped = crate::quality::white_balance::clamp_kelvin(kelvin);
+    if clamped != kelvin {
+        log::warn!("White balance {kelvin}K out of range, clamped to {clamped}K");
+    }
+
+    let patch = CameraControlsPatch {
+        white_balance: Some(WhiteBalance::Custom(clamped)),
+        ..CameraControlsPatch::default()
+    };
+    let applied = set_camera_controls(device_id.clone(), CameraControls::from(patch)).await?;
+
+    let correction = white_balance_correction(&applied);
+    let software = (correction == WhiteBalanceCorrection::Software).then_some(clamped);
+    crate::quality::white_balance::set_software_white_balance(&device_id, software);
+    log::info!("White balance for {device_id} set to {clamped}K ({correction:?})");
+
+    Ok(WhiteBalanceResult {
+        requested_kelvin: kelvin,
+        kelvin: clamped,
+        correction,
+    })
+}
+
+/// Set white balance to a named color-temperature preset
+///
+/// See [`set_white_balance_kelvin`] for how the preset is applied.
+///
+/// # Errors
+/// Propagates any error from [`set_white_balance_kelvin`].
+#[command]
+pub async fn set_white_balance_preset(
+    device_id: String,
+    preset: WhiteBalancePreset,
+) -> Result<WhiteBalanceResult, CommandError> {
+    set_white_balance_kelvin(device_id, preset.kelvin()).await
+}
+
+/// Start an auto-focus cycle and wait until focus settles
+///
+/// Auto-focus is switched on, then frames are captured until both their
+/// sharpness (Laplacian variance, see [`BlurDetector`]) and the focus
+/// distance the driver reports stop changing for
+/// `AUTOFOCUS_SETTLE_FRAMES` frames. Drivers rarely expose a lock state, so
+/// settled sharpness stands in for it. `timeout_ms` defaults to
+/// `AUTOFOCUS_LOCK_TIMEOUT_MS`; on timeout the result has `locked: false`.
+///
+/// # Errors
+/// Returns an `Err` if the camera cannot be created or retrieved, if the
+/// camera mutex is poisoned, if the blocking task fails to join, if the
+/// camera rejects auto-focus, or if a frame capture fails.
+#[command]
+pub async fn trigger_autofocus(
+    device_id: String,
+    timeout_ms: Option<u64>,
+) -> Result<FocusLock, CommandError> {
+    log::info!("Triggering auto-focus on device: {device_id}");
+
+    let camera_arc =
+        get_or_create_camera(device_id.clone(), crate::types::CameraFormat::standard()).await?;
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(AUTOFOCUS_LOCK_TIMEOUT_MS));
+
+    tokio::task::spawn_blocking(move || {
+        let mut camera = camera_arc
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+
+        let enable = CameraControls::from(CameraControlsPatch {
+            auto_focus: Some(true),
+            ..CameraControlsPatch::default()
+        });
+        let result = camera.apply_controls(&enable).map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to start auto-focus: {e}"))
+        })?;
+        if !result.fully_applied() {
+            return Err(CommandError::new(
+                "UNSUPPORTED_OPERATION",
+                format!("Device {device_id} does not support auto-focus"),
+            ));
+        }
+
+        let lock = wait_for_focus(&mut camera, timeout)?;
+        if lock.locked {
+            log::info!(
+                "Focus locked on device {device_id} after {} frames ({} ms)",
+                lock.frames,
+                lock.elapsed_ms
+            );
+        } else {
+            log::warn!(
+                "Focus did not settle on device {device_id} within {} ms",
+                timeout.as_millis()
+            );
+        }
+        Ok(lock)
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// Switch between autofocus and fixed focus modes
+///
+/// `Continuous` leaves the camera's autofocus running. `Manual`,
+/// `Infinity` and `Macro` switch autofocus off and fix the lens at the
+/// given distance, the far extreme (0.0) or the closest (1.0). `Auto`
+/// focuses once and holds: cameras only offer continuous autofocus, so it
+/// is emulated by waiting for focus to settle (see [`trigger_autofocus`])
+/// and then switching autofocus off at the distance it found.
+///
+/// The result says whether the mode was set in hardware, emulated, or is
+/// unsupported. A camera that rejects a fixed distance has autofocus
+/// switched back on rather than being left half-configured.
+///
+/// # Errors
+/// Returns an `INVALID_ARGUMENT` error if a `Manual` distance is outside
+/// 0.0-1.0. Also returns an `Err` if the camera cannot be created or
+/// retrieved, if the camera mutex is poisoned, if the blocking task fails
+/// to join, or if a control or capture fails.
+#[command]
+pub async fn set_focus_mode(
+    device_id: String,
+    mode: FocusMode,
+) -> Result<FocusModeResult, CommandError> {
+    if let FocusMode::Manual { distance } = mode {
+        if !(0.0..=1.0).contains(&distance) {
+            return Err(CommandError::invalid_argument(format!(
+                "Focus distance must be between 0.0 (infinity) and 1.0 (closest), got {distance}"
+            )));
+        }
+    }
+    log::info!("Setting focus mode for device {device_id}: {mode:?}");
+
+    let camera_arc =
+        get_or_create_camera(device_id.clone(), crate::types::CameraFormat::standard()).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut camera = camera_arc
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+
+        let mut controls = camera
+            .apply_controls(&CameraControls::from(mode.to_patch()))
+            .map_err(|e| {
+                CommandError::new(e.error_code(), format!("Failed to apply controls: {e}"))
+            })?;
+        let mut result = FocusModeResult::from_application(mode, controls.clone());
+        if mode == FocusMode::Auto && result.support == FocusModeSupport::Hardware {
+            let lock = wait_for_focus(
+                &mut camera,
+                Duration::from_millis(AUTOFOCUS_LOCK_TIMEOUT_MS),
+            )?;
+            let hold = CameraControlsPatch {
+                auto_focus: Some(false),
+                focus_distance: lock.focus_distance,
+                ..CameraControlsPatch::default()
+            };
+            let held = camera
+                .apply_controls(&CameraControls::from(hold))
+                .map_err(|e| {
+                    CommandError::new(e.error_code(), format!("Failed to hold focus: {e}"))
+                })?;
+            controls.applied.extend(held.applied);
+            controls.rejected.extend(held.rejected);
+            result = FocusModeResult {
+                support: FocusModeSupport::Emulated,
+                focus_distance: lock.focus_distance,
+                controls,
+                ..result
+            };
+        } else if result.support == FocusModeSupport::Unsupported && mode.fixes_distance() {
+            let restore = CameraControlsPatch {
+                auto_focus: Some(true),
+                ..CameraControlsPatch::default()
+            };
+            let _ = camera.apply_controls(&CameraControls::from(restore));
+        }
+
+        if result.support == FocusModeSupport::Unsupported {
+            log::warn!(
+                "Focus mode {mode:?} unsupported by device {device_id}: rejected {:?}",
+                result.controls.rejected
+            );
+        }
+        Ok(result)
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// Focus on a point of the image (tap-to-focus)
+///
+/// `x` and `y` are normalized to 0.0-1.0 with the origin at the top left.
+/// Cameras with region autofocus (`AVFoundation`'s focus point of interest
+/// on macOS) focus on the point themselves. Elsewhere the lens is swept
+/// through `AUTOFOCUS_SWEEP_STEPS` manual focus distances, then more finely
+/// around the best one, scoring a region around the point with
+/// [`BlurDetector::region_variance`]; the sharpest distance is kept, with
+/// auto-focus off. The result reports which method was used.
+///
+/// # Errors
+/// Returns an `INVALID_ARGUMENT` error if `x` or `y` is outside 0.0-1.0,
+/// and an `UNSUPPORTED_OPERATION` error if the camera has neither region
+/// autofocus nor manual focus. Also returns an `Err` if the camera cannot
+/// be created or retrieved, if the camera mutex is poisoned, if the
+/// blocking task fails to join, or if a control or capture fails.
+#[command]
+pub async fn set_focus_point(
+    device_id: String,
+    x: f32,
+    y: f32,
+) -> Result<FocusPointResult, CommandError> {
+    if !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y) {
+        return Err(CommandError::invalid_argument(format!(
+            "Focus point ({x}, {y}) must be within 0.0-1.0"
+        )));
+    }
+    log::info!("Focusing device {device_id} on ({x:.2}, {y:.2})");
+
+    let camera_arc =
+        get_or_create_camera(device_id.clone(), crate::types::CameraFormat::standard()).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut camera = camera_arc
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+
+        let hardware = camera.set_focus_point(x, y).map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to set focus point: {e}"))
+        })?;
+        if hardware {
+            return Ok(FocusPointResult {
+                x,
+                y,
+                method: FocusMethod::Hardware,
+                focus_distance: None,
+                sharpness: None,
+            });
+        }
+
+        let (focus_distance, sharpness) = sweep_focus(&mut camera, x, y)?;
+        log::info!(
+            "Software focus on ({x:.2}, {y:.2}) chose distance {focus_distance:.2} (variance {sharpness:.1})"
+        );
+        Ok(FocusPointResult {
+            x,
+            y,
+            method: FocusMethod::Software,
+            focus_distance: Some(focus_distance),
+            sharpness: Some(sharpness),
+        })
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// Capture frames until auto-focus settles or `timeout` passes
+fn wait_for_focus(
+    camera: &mut PlatformCamera,
+    timeout: Duration,
+) -> Result<FocusLock, CommandError> {
+    let started = Instant::now();
+    let mut settle = FocusSettle::default();
+    let mut lock = FocusLock {
+        locked: false,
+        focus_distance: None,
+        sharpness: 0.0,
+        frames: 0,
+        elapsed_ms: 0,
+    };
+    while !lock.locked && started.elapsed() < timeout {
+        let frame = camera.capture_frame().map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to capture frame: {e}"))
+        })?;
+        lock.sharpness = BlurDetector::laplacian_variance(&frame);
+        lock.focus_distance = camera
+            .get_controls()
+            .ok()
+            .and_then(|controls| controls.focus_distance);
+        lock.frames += 1;
+        lock.locked = settle.observe(lock.sharpness, lock.focus_distance);
+    }
+    // Elapsed waits are far below u64::MAX milliseconds
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        lock.elapsed_ms = started.elapsed().as_millis() as u64;
+    }
+    Ok(lock)
+}
+
+/// Contrast-detect focus: try manual focus distances and keep the one where
+/// the region around (`x`, `y`) is sharpest
+fn sweep_focus(camera: &mut PlatformCamera, x: f32, y: f32) -> Result<(f32, f64), CommandError> {
+    let mut score = |distance: f32| -> Result<f64, CommandError> {
+        let controls = CameraControls::from(CameraControlsPatch {
+            auto_focus: Some(false),
+            focus_distance: Some(distance),
+            ..CameraControlsPatch::default()
+        });
+        let result = camera
+            .apply_controls(&controls)
+            .map_err(|e| CommandError::new(e.error_code(), format!("Failed to set focus: {e}")))?;
+        if result
+            .rejected
+            .iter()
+            .any(|control| control == "focus_distance")
+        {
+            // Leave the camera focusing by itself rather than stuck
+            let restore = CameraControls::from(CameraControlsPatch {
+                auto_focus: Some(true),
+                ..CameraControlsPatch::default()
+            });
+            let _ = camera.apply_controls(&restore);
+            return Err(CommandError::new(
+                "UNSUPPORTED_OPERATION",
+                "Camera supports neither region autofocus nor manual focus",
+            ));
+        }
+        // The first frame may have been exposed while the lens moved
+        let mut frame = None;
+        for _ in 0..2 {
+            frame = Some(camera.capture_frame().map_err(|e| {
+                CommandError::new(e.error_code(), format!("Failed to capture frame: {e}"))
+            })?);
+        }
+        let frame = frame.ok_or_else(|| CommandError::system("No frame captured"))?;
+        let region = focus_region(frame.width, frame.height, x, y);
+        Ok(BlurDetector::region_variance(&frame, region))
+    };
+
+    // Sweep steps are small constants, exact as f32
+    #[allow(clippy::cast_precision_loss)]
+    let step = 1.0 / (AUTOFOCUS_SWEEP_STEPS - 1) as f32;
+    #[allow(clippy::cast_precision_loss)]
+    let coarse: Vec<f32> = (0..AUTOFOCUS_SWEEP_STEPS)
+        .map(|i| i as f32 * step)
+        .collect();
+    let mut best = (0.0, f64::MIN);
+    let mut last = 0.0;
+    for distance in coarse {
+        let sharpness = score(distance)?;
+        last = distance;
+        if sharpness > best.1 {
+            best = (distance, sharpness);
+        }
+    }
+    // Refine between the neighbouring coarse steps
+    let center = best.0;
+    for offset in [-0.5, -0.25, 0.25, 0.5] {
+        let distance = (center + offset * step).clamp(0.0, 1.0);
+        let sharpness = score(distance)?;
+        last = distance;
+        if sharpness > best.1 {
+            best = (distance, sharpness);
+        }
+    }
+    if (last - best.0).abs() > f32::EPSILON {
+        score(best.0)?;
+    }
+    Ok(best)
+}
+
+/// Region scored by the software focus sweep: `AUTOFOCUS_REGION_FRACTION`
+/// of each dimension, centered on the normalized point and kept inside the
+/// frame
+// Frame dimensions fit in f32 exactly enough for a region; clamped values
+// are non-negative and within the frame
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn focus_region(width: u32, height: u32, x: f32, y: f32) -> Rect {
+    let side = |extent: u32| {
+        ((extent as f32 * AUTOFOCUS_REGION_FRACTION) as u32)
+            .max(3)
+            .min(extent)
+    };
+    let origin = |center: f32, extent: u32, size: u32| {
+        let start = center * extent as f32 - size as f32 / 2.0;
+        (start.max(0.0) as u32).min(extent - size)
+    };
+    let (region_width, region_height) = (side(width), side(height));
+    Rect::new(
+        origin(x, width, region_width),
+        origin(y, height, region_height),
+        region_width,
+        region_height,
+    )
+}
+
+/// Tracks whether successive frames show focus has stopped moving
+#[derive(Debug, Default)]
+struct FocusSettle {
+    last: Option<(f64, Option<f32>)>,
+    settled_frames: u32,
+}
+
+impl FocusSettle {
+    /// Record a frame's sharpness and reported focus distance; returns
+    /// whether focus has now been steady for `AUTOFOCUS_SETTLE_FRAMES`
+    fn observe(&mut self, sharpness: f64, focus_distance: Option<f32>) -> bool {
+        let steady = self.last.is_some_and(|(last_sharpness, last_distance)| {
+            let change = (sharpness - last_sharpness).abs() / last_sharpness.max(f64::EPSILON);
+            let distance_steady = match (last_distance, focus_distance) {
+                (Some(last), Some(current)) => (last - current).abs() < 0.01,
+                _ => true,
+            };
+            change <= AUTOFOCUS_SETTLE_TOLERANCE && distance_steady
+        });
+        self.settled_frames = if steady { self.settled_frames + 1 } else { 0 };
+        self.last = Some((sharpness, focus_distance));
+        self.settled_frames >= AUTOFOCUS_SETTLE_FRAMES
+    }
+}
+
+/// Controls a capture preset would apply, so a UI can preview it
+///
+/// # Errors
+/// This function always succeeds and never returns an `Err`.
+#[command]
+pub async fn preset_settings(preset: CapturePreset) -> Result<CameraControls, CommandError> {
+    Ok(preset.controls())
+}
+
+/// Apply a capture preset (portrait, document, low light, action)
+///
+/// The preset's controls go through the same path as
+/// [`set_camera_controls`], so controls the camera lacks are skipped and
+/// listed in `controls.rejected`. When a manual exposure or focus value is
+/// rejected after its automatic mode was turned off, the automatic mode is
+/// switched back on and reported in `restored_auto`.
+///
+/// # Errors
+/// Returns an `Err` if the camera cannot be created or retrieved, if the
+/// camera mutex is poisoned, if the blocking task fails to join, or if
+/// applying the controls fails.
+#[command]
+pub async fn apply_preset(
+    device_id: String,
+    preset: CapturePreset,
+) -> Result<CapturePresetResult, CommandError> {
+    log::info!("Applying {preset:?} preset to device: {device_id}");
+
+    let camera_arc =
+        get_or_create_camera(device_id.clone(), crate::types::CameraFormat::standard()).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut camera = camera_arc
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+
+        let apply = |camera: &mut PlatformCamera, controls: &CameraControls| {
+            camera.apply_controls(controls).map_err(|e| {
+                log::error!("Failed to apply {preset:?} preset: {e}");
+                CommandError::new(e.error_code(), format!("Failed to apply controls: {e}"))
+            })
+        };
+
+        let controls = apply(&mut camera, &preset.controls())?;
+        let fallback = auto_fallback(&controls);
+        let mut restored_auto = Vec::new();
+        if !fallback.is_empty() {
+            restored_auto = apply(&mut camera, &CameraControls::from(fallback))?.applied;
+        }
+
+        log::info!(
+            "{preset:?} preset applied to device {device_id} (applied={}, rejected={}, restored_auto={restored_auto:?})",
+            controls.applied.len(),
+            controls.rejected.len()
+        );
+        Ok(CapturePresetResult {
+            preset,
+            controls,
+            restored_auto,
+        })
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// Automatic modes to switch back on because their manual value was
+/// rejected after the automatic mode itself was turned off
+fn auto_fallback(result: &ControlApplicationResult) -> CameraControlsPatch {
+    let applied = |name: &str| result.applied.iter().any(|control| control == name);
+    let rejected = |name: &str| result.rejected.iter().any(|control| control == name);
+    CameraControlsPatch {
+        auto_exposure: (applied("auto_exposure") && rejected("exposure_time")).then_some(true),
+        auto_focus: (applied("auto_focus") && rejected("focus_distance")).then_some(true),
+        ..CameraControlsPatch::default()
+    }
+}
+
 /// Enable HDR mode with automatic exposure bracketing
 ///
+/// Each bracket is reported as capture progress. With an `operation_id`,
+/// [`cancel_operation`](crate::commands::capture::cancel_operation) stops
+/// the sequence and the command fails with a `CANCELLED` error, since
+/// partial brackets cannot be merged.
+///
 /// # Errors
 /// Propagates any error from [`capture_burst_sequence`] (including invalid
-/// burst configuration) or from obtaining the camera.
+/// burst configuration) or from obtaining the camera. Also returns an `Err`
+/// if `operation_id` is already in use or the sequence is cancelled.
 #[command]
-pub async fn capture_hdr_sequence(device_id: String) -> Result<Vec<CameraFrame>, String> {
+pub async fn capture_hdr_sequence(
+    device_id: String,
+    operation_id: Option<String>,
+) -> Result<Vec<CameraFrame>, CommandError> {
     log::info!("Capturing HDR sequence from device: {device_id}");
 
     let config = BurstConfig::hdr_burst();
-    capture_burst_sequence(device_id, config).await
+    burst_sequence(device_id, config, &Operation::register(operation_id)?).await
 }
 
 /// Capture focus stacked sequence for macro photography (legacy - use `focus_stack` module)
@@ -457,11 +1444,13 @@ pub async fn capture_hdr_sequence(device_id: String) -> Result<Vec<CameraFrame>,
 pub async fn capture_focus_stack_legacy(
     device_id: String,
     stack_count: u32,
-) -> Result<Vec<CameraFrame>, String> {
+) -> Result<Vec<CameraFrame>, CommandError> {
     log::info!("Capturing focus stack (legacy): {stack_count} frames from device {device_id}");
 
     if !(3..=20).contains(&stack_count) {
-        return Err("Focus stack count must be between 3 and 20".to_string());
+        return Err(CommandError::invalid_argument(
+            "Focus stack count must be between 3 and 20",
+        ));
     }
 
     let config = BurstConfig {
@@ -478,6 +1467,10 @@ pub async fn capture_focus_stack_legacy(
 
 /// Get camera performance metrics
 ///
+/// Active cameras report live rolling values. A camera that has been released
+/// reports the last values observed before release; a camera never opened is
+/// opened to take a measurement.
+///
 /// # Errors
 /// Returns an `Err` if the camera cannot be obtained, if the camera mutex
 /// is poisoned, if the blocking task fails to join, or if reading the
@@ -485,15 +1478,20 @@ pub async fn capture_focus_stack_legacy(
 #[command]
 pub async fn get_camera_performance(
     device_id: String,
-) -> Result<crate::types::CameraPerformanceMetrics, String> {
-    let camera_arc =
-        get_or_create_camera(device_id.clone(), crate::types::CameraFormat::standard()).await?;
+) -> Result<crate::types::CameraPerformanceMetrics, CommandError> {
+    let camera_arc = if let Some(camera) = get_existing_camera(&device_id).await {
+        camera
+    } else if let Some(metrics) = get_last_known_metrics(&device_id).await {
+        return Ok(metrics);
+    } else {
+        get_or_create_camera(device_id.clone(), crate::types::CameraFormat::standard()).await?
+    };
 
     let device_id_clone = device_id.clone();
     tokio::task::spawn_blocking(move || {
         let camera = camera_arc
             .lock()
-            .map_err(|_| "Mutex poisoned".to_string())?;
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
 
         match camera.get_performance_metrics() {
             Ok(metrics) => {
@@ -507,16 +1505,112 @@ pub async fn get_camera_performance(
             }
             Err(e) => {
                 log::error!("Failed to get performance metrics: {e}");
-                Err(format!("Failed to get performance metrics: {e}"))
+                Err(CommandError::new(
+                    e.error_code(),
+                    format!("Failed to get performance metrics: {e}"),
+                ))
             }
         }
     })
     .await
-    .map_err(|e| format!("Task join error: {e}"))?
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// Get camera capabilities from enumeration metadata without opening the device
+///
+/// Works while the camera is busy; see
+/// [`CameraSystem::get_capabilities_cached`](crate::platform::CameraSystem::get_capabilities_cached)
+/// for which fields are reported versus assumed.
+///
+/// # Errors
+/// Returns an `Err` if enumeration fails, the device is unknown, or the
+/// blocking task fails to join.
+#[command]
+pub async fn get_camera_capabilities_cached(
+    device_id: String,
+) -> Result<crate::types::CameraCapabilities, CommandError> {
+    tokio::task::spawn_blocking(move || {
+        crate::platform::CameraSystem::get_capabilities_cached(&device_id).map_err(|e| {
+            CommandError::new(
+                e.error_code(),
+                format!("Failed to get cached capabilities: {e}"),
+            )
+        })
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// Capture a frame cropped and scaled to keep the main subject centered
+///
+/// The subject is the largest detected face (requires the `face-detect`
+/// feature); without one the frame is center-cropped. Successive calls for the
+/// same device move the crop smoothly rather than jumping. `target_aspect` is
+/// width / height and `padding` the margin around the subject as a fraction
+/// of its size. The crop used is reported in `metadata.crop`.
+///
+/// # Errors
+/// Returns an `Err` if `target_aspect` is not a positive number, if the camera
+/// cannot be obtained, the mutex is poisoned, the blocking task fails to join,
+/// the capture fails, or the frame cannot be cropped.
+#[command]
+pub async fn capture_autoframe(
+    device_id: String,
+    target_aspect: f32,
+    padding: f32,
+) -> Result<CameraFrame, CommandError> {
+    if !(target_aspect.is_finite() && target_aspect > 0.0) {
+        return Err(CommandError::invalid_argument(format!(
+            "Invalid target aspect ratio: {target_aspect}"
+        )));
+    }
+    log::info!("Auto-framing capture for device: {device_id} (aspect {target_aspect})");
+
+    let camera_arc =
+        get_or_create_camera(device_id.clone(), crate::types::CameraFormat::standard()).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let frame = {
+            let mut camera = camera_arc
+                .lock()
+                .map_err(|_| CommandError::system("Mutex poisoned"))?;
+            camera.capture_frame().map_err(|e| {
+                CommandError::new(e.error_code(), format!("Failed to capture frame: {e}"))
+            })?
+        };
+
+        #[cfg(feature = "face-detect")]
+        let frame = if frame.metadata.faces.is_none() {
+            let mut frame = frame;
+            frame.metadata.faces = Some(crate::quality::detect::detect_faces(&frame));
+            frame
+        } else {
+            frame
+        };
+
+        let mut framers = AUTOFRAMERS
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+        let framer = framers
+            .entry(device_id)
+            .or_insert_with(|| AutoFramer::new(target_aspect, padding));
+        if !framer.is_configured_for(target_aspect, padding) {
+            *framer = AutoFramer::new(target_aspect, padding);
+        }
+
+        framer
+            .frame(&frame)
+            .map_err(|e| CommandError::new(e.error_code(), format!("Failed to auto-frame: {e}")))
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
 }
 
 /// Test camera capabilities and return supported features
 ///
+/// The device is probed on the first call; later calls return the cached
+/// result until it is unplugged or `refresh_cameras` is called.
+///
 /// # Errors
 /// Returns an `Err` if the camera cannot be obtained, if the camera mutex
 /// is poisoned, if the blocking task fails to join, or if querying the
@@ -524,7 +1618,7 @@ pub async fn get_camera_performance(
 #[command]
 pub async fn test_camera_capabilities(
     device_id: String,
-) -> Result<crate::types::CameraCapabilities, String> {
+) -> Result<crate::types::CameraCapabilities, CommandError> {
     log::info!("Testing camera capabilities for device: {device_id}");
 
     let camera_arc =
@@ -534,9 +1628,9 @@ pub async fn test_camera_capabilities(
     tokio::task::spawn_blocking(move || {
         let camera = camera_arc
             .lock()
-            .map_err(|_| "Mutex poisoned".to_string())?;
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
 
-        match camera.test_capabilities() {
+        match crate::platform::CameraSystem::capabilities(&device_id_clone, &camera) {
             Ok(capabilities) => {
                 log::info!(
                     "Camera {} capabilities: manual_focus={}, manual_exposure={}, max_res={}x{}",
@@ -550,23 +1644,95 @@ pub async fn test_camera_capabilities(
             }
             Err(e) => {
                 log::error!("Failed to test camera capabilities: {e}");
-                Err(format!("Failed to test capabilities: {e}"))
+                Err(CommandError::new(
+                    e.error_code(),
+                    format!("Failed to test capabilities: {e}"),
+                ))
             }
         }
     })
     .await
-    .map_err(|e| format!("Task join error: {e}"))?
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
 }
 
 // Helper functions
 
+/// Check an exposure mode's shutter and ISO against the camera's ranges
+fn validate_exposure_mode(
+    mode: ExposureMode,
+    capabilities: &CameraCapabilities,
+) -> Result<(), CommandError> {
+    let (shutter, iso) = match mode {
+        ExposureMode::Auto => return Ok(()),
+        ExposureMode::ShutterPriority { shutter } => (shutter, None),
+        ExposureMode::Manual { shutter, iso } => (shutter, Some(iso)),
+    };
+
+    let (min_shutter, max_shutter) = capabilities.exposure_range.unwrap_or((0.0, 10.0));
+    if !shutter.is_finite() || shutter <= 0.0 || !(min_shutter..=max_shutter).contains(&shutter) {
+        return Err(CommandError::invalid_argument(format!(
+            "Shutter time must be between {min_shutter} and {max_shutter} seconds, got {shutter}"
+        )));
+    }
+
+    let (min_iso, max_iso) = capabilities.iso_range.unwrap_or((MIN_ISO, MAX_ISO));
+    if let Some(iso) = iso.filter(|iso| !(min_iso..=max_iso).contains(iso)) {
+        return Err(CommandError::invalid_argument(format!(
+            "ISO sensitivity must be between {min_iso} and {max_iso}, got {iso}"
+        )));
+    }
+    Ok(())
+}
+
+/// Exposure (seconds) and ISO limits of a camera, or the defaults where it
+/// reports none
+fn exposure_limits(capabilities: &CameraCapabilities) -> ((f32, f32), (u32, u32)) {
+    (
+        capabilities
+            .exposure_range
+            .unwrap_or(DEFAULT_EXPOSURE_RANGE),
+        capabilities.iso_range.unwrap_or((MIN_ISO, MAX_ISO)),
+    )
+}
+
+fn read_exposure_settings(camera: &PlatformCamera) -> Result<ExposureSettings, CommandError> {
+    let controls = camera
+        .get_controls()
+        .map_err(|e| CommandError::new(e.error_code(), format!("Failed to read controls: {e}")))?;
+    let capabilities = camera.test_capabilities().map_err(|e| {
+        CommandError::new(e.error_code(), format!("Failed to read capabilities: {e}"))
+    })?;
+    let (exposure_range, iso_range) = exposure_limits(&capabilities);
+    Ok(ExposureSettings {
+        auto_exposure: controls.auto_exposure,
+        exposure: controls
+            .exposure_time
+            .map(|seconds| ExposureReading::new(seconds, exposure_range)),
+        gain: controls
+            .iso_sensitivity
+            .map(|iso| GainReading::new(iso, iso_range)),
+    })
+}
+
+/// Hardware if the camera accepted the white balance control, otherwise software
+fn white_balance_correction(result: &ControlApplicationResult) -> WhiteBalanceCorrection {
+    if result.applied.iter().any(|name| name == "white_balance") {
+        WhiteBalanceCorrection::Hardware
+    } else {
+        WhiteBalanceCorrection::Software
+    }
+}
+
 /// Save burst sequence to disk
-async fn save_burst_sequence(frames: &[CameraFrame], save_dir: &str) -> Result<(), String> {
+async fn save_burst_sequence(frames: &[CameraFrame], save_dir: &str) -> Result<(), CommandError> {
     log::info!("Saving {} frames to directory: {}", frames.len(), save_dir);
 
     // Create directory if it doesn't exist
     if let Err(e) = tokio::fs::create_dir_all(save_dir).await {
-        return Err(format!("Failed to create directory {save_dir}: {e}"));
+        return Err(CommandError::new(
+            "IO_ERROR",
+            format!("Failed to create directory {save_dir}: {e}"),
+        ));
     }
 
     let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
@@ -577,7 +1743,9 @@ async fn save_burst_sequence(frames: &[CameraFrame], save_dir: &str) -> Result<(
 
         // Convert to JPEG for smaller file size
         let img = image::RgbImage::from_vec(frame.width, frame.height, frame.data.clone())
-            .ok_or_else(|| "Failed to create image from frame data".to_string())?;
+            .ok_or_else(|| {
+                CommandError::invalid_argument("Failed to create image from frame data")
+            })?;
 
         let dynamic_img = image::DynamicImage::ImageRgb8(img);
 
@@ -593,11 +1761,17 @@ async fn save_burst_sequence(frames: &[CameraFrame], save_dir: &str) -> Result<(
             }
             Ok(Err(e)) => {
                 log::error!("Failed to save frame {}: {}", i + 1, e);
-                return Err(format!("Failed to save frame {}: {}", i + 1, e));
+                return Err(CommandError::new(
+                    "IO_ERROR",
+                    format!("Failed to save frame {}: {}", i + 1, e),
+                ));
             }
             Err(e) => {
                 log::error!("Task join error for frame {}: {}", i + 1, e);
-                return Err(format!("Failed to save frame {}: task error", i + 1));
+                return Err(CommandError::system(format!(
+                    "Failed to save frame {}: task error",
+                    i + 1
+                )));
             }
         }
     }
@@ -615,12 +1789,282 @@ mod tests {
         std::env::set_var("CRABCAMERA_USE_MOCK", "1");
     }
 
+    #[test]
+    fn test_white_balance_correction_from_application_result() {
+        let accepted = ControlApplicationResult {
+            applied: vec!["white_balance".to_string()],
+            rejected: vec![],
+        };
+        let rejected = ControlApplicationResult {
+            applied: vec![],
+            rejected: vec!["white_balance".to_string()],
+        };
+        assert_eq!(
+            white_balance_correction(&accepted),
+            WhiteBalanceCorrection::Hardware
+        );
+        assert_eq!(
+            white_balance_correction(&rejected),
+            WhiteBalanceCorrection::Software
+        );
+    }
+
+    #[tokio::test]
+    async fn test_control_session_batches_changes() {
+        enable_mock_camera();
+        let device = "control-session".to_string();
+        assert_eq!(
+            commit_controls(device.clone()).await.unwrap_err().code,
+            CommandError::NOT_FOUND
+        );
+
+        begin_controls(device.clone()).await.unwrap();
+        let focus = CameraControlsPatch {
+            auto_focus: Some(false),
+            focus_distance: Some(0.4),
+            ..CameraControlsPatch::default()
+        };
+        stage_controls(device.clone(), focus).await.unwrap();
+        let invalid = CameraControlsPatch {
+            exposure_time: Some(-1.0),
+            ..CameraControlsPatch::default()
+        };
+        assert_eq!(
+            stage_controls(device.clone(), invalid)
+                .await
+                .unwrap_err()
+                .code,
+            CommandError::INVALID_ARGUMENT
+        );
+        let wb = CameraControlsPatch {
+            white_balance: Some(WhiteBalance::Fluorescent),
+            ..CameraControlsPatch::default()
+        };
+        let staged = stage_controls(device.clone(), wb).await.unwrap();
+        assert_eq!(staged.focus_distance, Some(0.4));
+        assert!(staged.exposure_time.is_none(), "rejected change not kept");
+
+        let result = commit_controls(device.clone()).await.unwrap();
+        assert!(result.applied.contains(&"focus_distance".to_string()));
+        assert!(result.applied.contains(&"white_balance".to_string()));
+        let controls = get_camera_controls(device.clone()).await.unwrap();
+        assert_eq!(controls.focus_distance, Some(0.4));
+        assert_eq!(controls.white_balance, Some(WhiteBalance::Fluorescent));
+        assert!(!discard_controls(device).await.unwrap(), "commit ends it");
+    }
+
+    #[test]
+    fn test_control_session_builder() {
+        let session = ControlSession::new("0".to_string())
+            .exposure(0.01)
+            .iso(400)
+            .white_balance(WhiteBalance::Daylight)
+            .focus(0.5);
+        assert_eq!(session.patch.auto_exposure, Some(false));
+        assert_eq!(session.patch.iso_sensitivity, Some(400));
+        assert_eq!(session.patch.focus_distance, Some(0.5));
+        assert!(session.validate().is_ok());
+        assert!(session.focus(2.0).validate().is_err());
+    }
+
+    #[test]
+    fn test_focus_region_is_centered_and_clamped() {
+        let center = focus_region(1000, 500, 0.5, 0.5);
+        assert_eq!(center, Rect::new(400, 200, 200, 100));
+        let corner = focus_region(1000, 500, 1.0, 0.0);
+        assert_eq!(corner, Rect::new(800, 0, 200, 100));
+    }
+
+    #[tokio::test]
+    async fn test_set_focus_point_falls_back_to_software_sweep() {
+        enable_mock_camera();
+        assert!(set_focus_point("tap-focus".to_string(), 1.5, 0.5)
+            .await
+            .is_err());
+
+        let result = set_focus_point("tap-focus".to_string(), 0.25, 0.75)
+            .await
+            .expect("mock supports manual focus");
+        assert_eq!(result.method, FocusMethod::Software);
+        let distance = result.focus_distance.expect("software sweep distance");
+        let controls = get_camera_controls("tap-focus".to_string())
+            .await
+            .expect("controls");
+        assert_eq!(controls.focus_distance, Some(distance));
+        assert_eq!(controls.auto_focus, Some(false));
+    }
+
+    #[test]
+    fn test_focus_settle_waits_for_steady_frames() {
+        let mut settle = FocusSettle::default();
+        // Hunting: sharpness still climbing
+        assert!(!settle.observe(100.0, Some(0.2)));
+        assert!(!settle.observe(200.0, Some(0.4)));
+        assert!(!settle.observe(400.0, Some(0.5)));
+        // Settled
+        assert!(!settle.observe(402.0, Some(0.5)));
+        assert!(!settle.observe(399.0, Some(0.5)));
+        assert!(settle.observe(401.0, Some(0.5)));
+
+        // A moving lens resets the count even at steady sharpness
+        let mut settle = FocusSettle::default();
+        for distance in [0.1, 0.2, 0.3, 0.4] {
+            assert!(!settle.observe(500.0, Some(distance)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trigger_autofocus_locks_on_steady_mock() {
+        enable_mock_camera();
+        let lock = trigger_autofocus("af-lock".to_string(), Some(5000))
+            .await
+            .expect("mock supports auto-focus");
+        assert!(lock.locked);
+        assert!(lock.frames > AUTOFOCUS_SETTLE_FRAMES);
+    }
+
+    #[tokio::test]
+    async fn test_set_focus_mode_infinity_and_auto_with_mock() {
+        enable_mock_camera();
+        let device = "focus-mode".to_string();
+
+        let infinity = set_focus_mode(device.clone(), FocusMode::Infinity)
+            .await
+            .expect("mock has manual focus");
+        assert_eq!(infinity.support, FocusModeSupport::Hardware);
+        assert_eq!(infinity.focus_distance, Some(0.0));
+        let controls = get_camera_controls(device.clone()).await.unwrap();
+        assert_eq!(controls.auto_focus, Some(false));
+        assert_eq!(controls.focus_distance, Some(0.0));
+
+        let auto = set_focus_mode(device.clone(), FocusMode::Auto)
+            .await
+            .expect("mock has auto-focus");
+        assert_eq!(auto.support, FocusModeSupport::Emulated);
+        let controls = get_camera_controls(device.clone()).await.unwrap();
+        assert_eq!(
+            controls.auto_focus,
+            Some(false),
+            "focus held after settling"
+        );
+
+        let invalid = set_focus_mode(device, FocusMode::Manual { distance: 1.5 })
+            .await
+            .expect_err("distance out of range");
+        assert_eq!(invalid.code, CommandError::INVALID_ARGUMENT);
+    }
+
+    #[test]
+    fn test_auto_fallback_restores_modes_with_rejected_values() {
+        let result = ControlApplicationResult {
+            applied: vec!["auto_exposure".to_string(), "auto_focus".to_string()],
+            rejected: vec!["exposure_time".to_string()],
+        };
+        let fallback = auto_fallback(&result);
+        assert_eq!(fallback.auto_exposure, Some(true));
+        assert_eq!(fallback.auto_focus, None);
+
+        let all_applied = ControlApplicationResult {
+            applied: vec!["auto_exposure".to_string(), "exposure_time".to_string()],
+            rejected: vec![],
+        };
+        assert!(auto_fallback(&all_applied).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_apply_preset_sets_preset_controls() {
+        enable_mock_camera();
+        let preview = preset_settings(CapturePreset::Document)
+            .await
+            .expect("preset settings");
+        assert_eq!(preview.auto_focus, Some(false));
+        assert_eq!(preview.exposure_time, None);
+
+        let result = apply_preset("preset-cam".to_string(), CapturePreset::Document)
+            .await
+            .expect("mock accepts preset");
+        assert_eq!(result.preset, CapturePreset::Document);
+        assert!(result.controls.fully_applied());
+        assert!(result.restored_auto.is_empty());
+
+        let controls = get_camera_controls("preset-cam".to_string())
+            .await
+            .expect("controls");
+        assert_eq!(controls.sharpness, preview.sharpness);
+        assert_eq!(controls.focus_distance, preview.focus_distance);
+    }
+
+    #[tokio::test]
+    async fn test_set_white_balance_kelvin_clamps_and_uses_hardware() {
+        enable_mock_camera();
+        let result = set_white_balance_kelvin("wb-kelvin".to_string(), 20_000)
+            .await
+            .expect("mock accepts white balance");
+        assert_eq!(result.requested_kelvin, 20_000);
+        assert_eq!(result.kelvin, crate::constants::WHITE_BALANCE_MAX_KELVIN);
+        assert_eq!(result.correction, WhiteBalanceCorrection::Hardware);
+
+        let preset =
+            set_white_balance_preset("wb-kelvin".to_string(), WhiteBalancePreset::Tungsten)
+                .await
+                .expect("preset");
+        assert_eq!(preset.kelvin, 3200);
+
+        let controls = get_camera_controls("wb-kelvin".to_string())
+            .await
+            .expect("controls");
+        assert_eq!(controls.white_balance, Some(WhiteBalance::Custom(3200)));
+    }
+
+    #[tokio::test]
+    async fn test_set_exposure_mode_validates_and_reports_honored_aspects() {
+        enable_mock_camera();
+
+        let result = set_exposure_mode(
+            "exposure-mode".to_string(),
+            ExposureMode::ShutterPriority {
+                shutter: 1.0 / 250.0,
+            },
+        )
+        .await
+        .expect("mock accepts shutter priority");
+        assert!(result.fully_honored());
+        assert_eq!(result.shutter_honored, Some(true));
+        assert_eq!(result.iso_honored, None);
+
+        let controls = get_camera_controls("exposure-mode".to_string())
+            .await
+            .expect("controls");
+        assert_eq!(controls.auto_exposure, Some(false));
+        assert_eq!(controls.exposure_time, Some(1.0 / 250.0));
+
+        let err = set_exposure_mode(
+            "exposure-mode".to_string(),
+            ExposureMode::Manual {
+                shutter: 0.01,
+                iso: MAX_ISO + 1,
+            },
+        )
+        .await
+        .expect_err("ISO above the camera's range");
+        assert_eq!(err.code, CommandError::INVALID_ARGUMENT);
+
+        let err = set_exposure_mode(
+            "exposure-mode".to_string(),
+            ExposureMode::ShutterPriority { shutter: 30.0 },
+        )
+        .await
+        .expect_err("shutter above the camera's range");
+        assert!(err.message.contains("Shutter time must be between"));
+    }
+
     #[tokio::test]
     async fn test_set_manual_focus_rejects_out_of_range_value() {
         let result = set_manual_focus("0".to_string(), 1.5).await;
         assert!(result.is_err());
         assert!(result
             .err()
+            .map(|e| e.message)
             .unwrap_or_default()
             .contains("Focus distance must be between 0.0"));
     }
@@ -631,16 +2075,52 @@ mod tests {
         assert!(result.is_err());
         assert!(result
             .err()
+            .map(|e| e.message)
             .unwrap_or_default()
             .contains("Exposure time must be between 0.0 and 10.0 seconds"));
     }
 
+    #[tokio::test]
+    async fn test_set_exposure_converts_units() {
+        enable_mock_camera();
+        let device = "exposure-units".to_string();
+
+        let settings = set_exposure(
+            device.clone(),
+            Some(ExposureValue::Ev(-8.0)),
+            Some(GainValue::Db(12.0)),
+        )
+        .await
+        .expect("mock accepts exposure");
+        assert_eq!(settings.auto_exposure, Some(false));
+        let exposure = settings.exposure.expect("exposure read back");
+        assert!((exposure.seconds - 1.0 / 256.0).abs() < 1e-6);
+        assert!((exposure.ev + 8.0).abs() < 1e-4);
+        assert!((exposure.min_seconds - 0.001).abs() < f32::EPSILON);
+        let gain = settings.gain.expect("gain read back");
+        assert_eq!(gain.iso, 398);
+        assert_eq!((gain.min_iso, gain.max_iso), (MIN_ISO, MAX_ISO));
+
+        let read = get_exposure_settings(device.clone()).await.unwrap();
+        assert_eq!(read, settings);
+
+        let too_long = set_exposure(device.clone(), Some(ExposureValue::Seconds(30.0)), None)
+            .await
+            .expect_err("beyond the mock's 10 s limit");
+        assert_eq!(too_long.code, CommandError::INVALID_ARGUMENT);
+        let neither = set_exposure(device, None, None).await.unwrap_err();
+        assert_eq!(neither.code, CommandError::INVALID_ARGUMENT);
+
+        std::env::remove_var("CRABCAMERA_USE_MOCK");
+    }
+
     #[tokio::test]
     async fn test_set_manual_exposure_rejects_invalid_iso() {
         let result = set_manual_exposure("0".to_string(), 0.01, MIN_ISO.saturating_sub(1)).await;
         assert!(result.is_err());
         assert!(result
             .err()
+            .map(|e| e.message)
             .unwrap_or_default()
             .contains("ISO sensitivity must be between"));
     }
@@ -659,6 +2139,7 @@ mod tests {
         assert!(result.is_err());
         assert!(result
             .expect_err("focus distance error expected")
+            .message
             .contains("Focus distance must be between 0.0"));
 
         let result = apply_camera_settings(CameraSettingsInput {
@@ -673,6 +2154,7 @@ mod tests {
         assert!(result.is_err());
         assert!(result
             .expect_err("exposure time error expected")
+            .message
             .contains("Exposure time must be between 0.0 and 10.0 seconds"));
 
         let result = apply_camera_settings(CameraSettingsInput {
@@ -687,6 +2169,7 @@ mod tests {
         assert!(result.is_err());
         assert!(result
             .expect_err("ISO sensitivity error expected")
+            .message
             .contains("ISO sensitivity must be between"));
     }
 
@@ -705,6 +2188,7 @@ mod tests {
         assert!(result.is_err());
         assert!(result
             .err()
+            .map(|e| e.message)
             .unwrap_or_default()
             .contains("Invalid burst count"));
     }
@@ -724,6 +2208,7 @@ mod tests {
         assert!(result.is_err());
         assert!(result
             .err()
+            .map(|e| e.message)
             .unwrap_or_default()
             .contains("Focus stacking requires at least 2 frames"));
     }
@@ -746,6 +2231,7 @@ mod tests {
         assert!(result.is_err());
         assert!(result
             .err()
+            .map(|e| e.message)
             .unwrap_or_default()
             .contains("Exposure bracketing requires at least one stop value"));
     }
@@ -768,6 +2254,7 @@ mod tests {
         assert!(result.is_err());
         assert!(result
             .err()
+            .map(|e| e.message)
             .unwrap_or_default()
             .contains("Exposure bracketing base_exposure must be greater than zero"));
     }
@@ -778,6 +2265,7 @@ mod tests {
         assert!(result.is_err());
         assert!(result
             .err()
+            .map(|e| e.message)
             .unwrap_or_default()
             .contains("Focus stack count must be between 3 and 20"));
     }
@@ -787,10 +2275,10 @@ mod tests {
         let invalid_frame = CameraFrame::new(vec![1, 2, 3], 16, 16, "0".to_string());
         let result = save_burst_sequence(&[invalid_frame], "test_outputs/invalid_burst").await;
 
-        assert!(result.is_err());
-        assert!(result
-            .err()
-            .unwrap_or_default()
+        let err = result.expect_err("invalid frame should be rejected");
+        assert_eq!(err.code, CommandError::INVALID_ARGUMENT);
+        assert!(err
+            .message
             .contains("Failed to create image from frame data"));
     }
 
@@ -864,7 +2352,7 @@ mod tests {
             .expect("set_white_balance should succeed with mock");
         assert!(!wb.applied.is_empty());
 
-        let hdr = capture_hdr_sequence("0".to_string())
+        let hdr = capture_hdr_sequence("0".to_string(), None)
             .await
             .expect("hdr wrapper should succeed with mock");
         assert!(!hdr.is_empty());
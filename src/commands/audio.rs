@@ -6,6 +6,7 @@
 //! ## Commands
 //!
 //! - `list_audio_devices`: Get all available audio input devices
+//! - `list_loopback_devices`: Get system-audio (loopback) sources
 //! - `start_recording`: Accepts optional audio device configuration
 //! - Error strings are user-friendly (never expose internal types)
 //! - All operations are async-safe
@@ -13,7 +14,11 @@
 use serde::{Deserialize, Serialize};
 use tauri::command;
 
-use crate::audio::{list_audio_devices as enumerate_audio_devices, AudioDevice};
+use crate::audio::{
+    list_audio_devices as enumerate_audio_devices,
+    list_loopback_devices as enumerate_loopback_devices, AudioDevice, DeviceKind,
+};
+use crate::errors::CommandError;
 
 /// Audio device information exposed to Tauri frontend
 ///
@@ -31,6 +36,9 @@ pub struct AudioDeviceInfo {
     pub channels: u16,
     /// Whether this is the system default input device
     pub is_default: bool,
+    /// Input or loopback source
+    #[serde(default)]
+    pub kind: DeviceKind,
 }
 
 impl From<AudioDevice> for AudioDeviceInfo {
@@ -41,6 +49,7 @@ impl From<AudioDevice> for AudioDeviceInfo {
             sample_rate: device.sample_rate,
             channels: device.channels,
             is_default: device.is_default,
+            kind: device.kind,
         }
     }
 }
@@ -58,12 +67,36 @@ impl From<AudioDevice> for AudioDeviceInfo {
 /// # Errors
 /// Returns an `Err` if the audio devices cannot be enumerated.
 #[command]
-pub fn list_audio_devices() -> Result<Vec<AudioDeviceInfo>, String> {
+pub fn list_audio_devices() -> Result<Vec<AudioDeviceInfo>, CommandError> {
     enumerate_audio_devices()
         .map(|devices| devices.into_iter().map(AudioDeviceInfo::from).collect())
         .map_err(|e| {
             log::error!("Failed to enumerate audio devices: {e:?}");
-            "Unable to list audio devices. Please check that your audio drivers are installed correctly.".to_string()
+            CommandError::new(
+                e.error_code(),
+                "Unable to list audio devices. Please check that your audio drivers are installed correctly.",
+            )
+        })
+}
+
+/// List system-audio sources that can be recorded alongside the camera
+///
+/// Pass a returned `id` as the audio device with
+/// [`DeviceKind::Loopback`] to capture what the computer plays. The list is
+/// empty where the platform offers no loopback (see [`crate::audio::loopback`]).
+///
+/// # Errors
+/// Returns an `Err` if the output devices cannot be enumerated.
+#[command]
+pub fn list_loopback_devices() -> Result<Vec<AudioDeviceInfo>, CommandError> {
+    enumerate_loopback_devices()
+        .map(|devices| devices.into_iter().map(AudioDeviceInfo::from).collect())
+        .map_err(|e| {
+            log::error!("Failed to enumerate loopback devices: {e:?}");
+            CommandError::new(
+                e.error_code(),
+                "Unable to list system audio sources. Please check that your audio drivers are installed correctly.",
+            )
         })
 }
 
@@ -75,12 +108,14 @@ pub fn list_audio_devices() -> Result<Vec<AudioDeviceInfo>, String> {
 /// # Errors
 /// Returns an `Err` if no default audio input device is available.
 #[command]
-pub fn get_default_audio_device() -> Result<AudioDeviceInfo, String> {
+pub fn get_default_audio_device() -> Result<AudioDeviceInfo, CommandError> {
     crate::audio::get_default_audio_device()
         .map(AudioDeviceInfo::from)
         .map_err(|e| {
             log::error!("Failed to get default audio device: {e:?}");
-            "No default audio input device available. Please connect a microphone.".to_string()
+            CommandError::not_found(
+                "No default audio input device available. Please connect a microphone.",
+            )
         })
 }
 
@@ -96,6 +131,7 @@ mod tests {
             sample_rate: 48000,
             channels: 2,
             is_default: true,
+            kind: DeviceKind::Input,
         };
 
         let json = serde_json::to_string(&device).expect("serialize audio device");
@@ -115,6 +151,7 @@ mod tests {
             sample_rate: 44100,
             channels: 1,
             is_default: false,
+            kind: DeviceKind::Loopback,
         };
 
         let info = AudioDeviceInfo::from(internal);
@@ -123,5 +160,6 @@ mod tests {
         assert_eq!(info.sample_rate, 44100);
         assert_eq!(info.channels, 1);
         assert!(!info.is_default);
+        assert_eq!(info.kind, DeviceKind::Loopback);
     }
 }
@@ -1,11 +1,23 @@
+use crate::constants::{
+    CAPTURE_RETRY_COUNT, CAPTURE_SEQUENCE_MAX_COUNT, CAPTURE_WARMUP_DELAY_MS,
+    CAPTURE_WARMUP_FRAMES, CAPTURE_WARMUP_MAX_FRAMES, FILE_NAME_SEQ_WIDTH, GIF_MAX_FRAMES,
+    PROGRESS_THUMBNAIL_MAX_DIM, TEMP_CAPTURE_PREFIX, TIMELAPSE_KEEP_WARM_SECS,
+};
+use crate::errors::CommandError;
 pub use crate::platform::{
-    capture_with_reconnect, get_existing_camera, get_or_create_camera, reconnect_camera,
-    PlatformCamera,
+    capture_with_reconnect, capture_with_reconnect_report, capture_with_warmup,
+    get_existing_camera, get_or_create_camera, reconnect_camera, FrameQueue, PlatformCamera,
+    ReorderBuffer,
 };
 use crate::quality::QualityValidator;
-use crate::types::{CameraFormat, CameraFrame};
+use crate::types::{CameraFormat, CameraFrame, FlipAxis, FrameTransform, SessionMetricsReport};
+use std::collections::HashMap;
 use std::fs::File;
-use tauri::command;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex as StdMutex, RwLock};
+use std::time::Duration;
+use tauri::{command, Emitter, Runtime};
+use tokio_util::sync::CancellationToken;
 
 /// Capture mode for the consolidated [`capture`] command
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -37,6 +49,25 @@ pub struct CaptureOptions {
     pub format: Option<CameraFormat>,
     /// Capture mode (single, sequence, or quality retry)
     pub mode: CaptureMode,
+    /// Frames discarded before a single capture (None = default, 0 = off)
+    #[serde(default)]
+    pub warmup_frames: Option<u32>,
+    /// Burn each frame's capture timestamp into its top-left corner
+    #[serde(default)]
+    pub timestamp_overlay: bool,
+    /// Rotate frames upright using the device's reported sensor orientation
+    /// (no-op when the device reports none)
+    #[serde(default)]
+    pub auto_orient: bool,
+    /// Wait for auto-focus to settle before capturing (see
+    /// [`trigger_autofocus`](crate::commands::advanced::trigger_autofocus));
+    /// ignored by cameras without auto-focus
+    #[serde(default)]
+    pub autofocus: bool,
+    /// Id under which a sequence capture can be stopped early with
+    /// [`cancel_operation`]
+    #[serde(default)]
+    pub operation_id: Option<String>,
 }
 
 /// Result from the consolidated [`capture`] command
@@ -63,10 +94,59 @@ pub struct CaptureResult {
 /// ([`capture_single_photo`], [`capture_photo_sequence`], or
 /// [`capture_with_quality_retry`]).
 #[command]
-pub async fn capture(options: CaptureOptions) -> Result<CaptureResult, String> {
+pub async fn capture(options: CaptureOptions) -> Result<CaptureResult, CommandError> {
+    let overlay = options.timestamp_overlay;
+    let auto_orient = options.auto_orient;
+    let device_id = options.device_id.clone().unwrap_or_else(|| "0".to_string());
+    if options.autofocus {
+        match crate::commands::advanced::trigger_autofocus(device_id.clone(), None).await {
+            Ok(lock) if !lock.locked => log::warn!("Capturing before focus settled"),
+            Ok(_) => {}
+            Err(e) if e.code == "UNSUPPORTED_OPERATION" => {
+                log::debug!("Skipping auto-focus: {}", e.message);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    let mut result = capture_by_mode(options).await?;
+    if auto_orient {
+        let rotation = sensor_rotation(&device_id).await?;
+        for frame in &mut result.frames {
+            crate::platform::orientation::auto_orient(frame, rotation);
+        }
+    }
+    if overlay {
+        result.frames = result
+            .frames
+            .into_iter()
+            .map(crate::quality::timestamp_overlay)
+            .collect();
+    }
+    Ok(result)
+}
+
+/// Sensor rotation reported by an already-open camera
+async fn sensor_rotation(device_id: &str) -> Result<Option<u16>, CommandError> {
+    let Some(camera) = get_existing_camera(device_id).await else {
+        return Ok(None);
+    };
+    let rotation = tokio::task::spawn_blocking(move || {
+        let camera = camera
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+        Ok::<_, CommandError>(camera.sensor_rotation())
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
+    Ok(rotation)
+}
+
+async fn capture_by_mode(options: CaptureOptions) -> Result<CaptureResult, CommandError> {
     match options.mode {
         CaptureMode::Single => {
-            let frame = capture_single_photo(options.device_id, options.format).await?;
+            let frame =
+                capture_single_photo(options.device_id, options.format, options.warmup_frames)
+                    .await?;
             Ok(CaptureResult {
                 frames: vec![frame],
                 mode: "single".to_string(),
@@ -75,8 +155,14 @@ pub async fn capture(options: CaptureOptions) -> Result<CaptureResult, String> {
         }
         CaptureMode::Sequence { count, interval_ms } => {
             let device_id = options.device_id.unwrap_or_else(|| "0".to_string());
-            let frames =
-                capture_photo_sequence(device_id, count, interval_ms, options.format).await?;
+            let frames = capture_photo_sequence(
+                device_id,
+                count,
+                interval_ms,
+                options.format,
+                options.operation_id,
+            )
+            .await?;
             Ok(CaptureResult {
                 frames,
                 mode: "sequence".to_string(),
@@ -105,6 +191,10 @@ pub async fn capture(options: CaptureOptions) -> Result<CaptureResult, String> {
 
 /// Capture a single photo from the specified camera with automatic reconnection
 ///
+/// `warmup_frames` frames are captured and discarded first so auto-exposure
+/// can settle; `None` uses `CAPTURE_WARMUP_FRAMES` (5) and `Some(0)` disables
+/// warmup. Requests are capped at `CAPTURE_WARMUP_MAX_FRAMES`.
+///
 /// ## Deprecation
 /// Prefer the consolidated [`capture`] command with `CaptureMode::Single`.
 ///
@@ -115,15 +205,19 @@ pub async fn capture(options: CaptureOptions) -> Result<CaptureResult, String> {
 pub async fn capture_single_photo(
     device_id: Option<String>,
     format: Option<CameraFormat>,
-) -> Result<CameraFrame, String> {
+    warmup_frames: Option<u32>,
+) -> Result<CameraFrame, CommandError> {
     log::info!("Capturing single photo from camera: {device_id:?}");
 
     // Use default camera if none specified
     let camera_id = device_id.unwrap_or_else(|| "0".to_string());
     let capture_format = format.unwrap_or_else(CameraFormat::standard);
+    let warmup = warmup_frames
+        .unwrap_or(CAPTURE_WARMUP_FRAMES)
+        .min(CAPTURE_WARMUP_MAX_FRAMES);
 
-    // Use capture_with_reconnect for automatic recovery
-    match capture_with_reconnect(camera_id, capture_format, 3).await {
+    // Use capture_with_warmup for automatic recovery
+    match capture_with_warmup(camera_id, capture_format, 3, warmup).await {
         Ok(frame) => {
             log::info!(
                 "Successfully captured frame: {}x{} ({} bytes)",
@@ -135,38 +229,252 @@ pub async fn capture_single_photo(
         }
         Err(e) => {
             log::error!("Failed to capture frame: {e}");
-            Err(format!("Failed to capture frame: {e}"))
+            Err(CommandError::new(
+                e.error_code(),
+                format!("Failed to capture frame: {e}"),
+            ))
         }
     }
 }
 
 /// Capture multiple photos in sequence
 ///
+/// With an `operation_id`, [`cancel_operation`] stops the sequence early;
+/// the frames captured until then are returned.
+///
 /// ## Deprecation
 /// Prefer the consolidated [`capture`] command with `CaptureMode::Sequence`.
 ///
 /// # Errors
-/// Returns an `Err` if `count` is `0` or greater than `20`. Also returns an
-/// `Err` if the camera cannot be obtained, the mutex is poisoned, the blocking
-/// task fails to join, or a frame capture fails.
+/// Returns an `Err` if `count` is `0` or greater than `20`, or if
+/// `operation_id` is already in use. Also returns an `Err` if the camera
+/// cannot be obtained, the mutex is poisoned, the blocking task fails to
+/// join, or a frame capture fails.
 #[command]
 pub async fn capture_photo_sequence(
     device_id: String,
     count: u32,
     interval_ms: u32,
     format: Option<CameraFormat>,
-) -> Result<Vec<CameraFrame>, String> {
+    operation_id: Option<String>,
+) -> Result<Vec<CameraFrame>, CommandError> {
     log::info!("Capturing {count} photos from camera {device_id} with {interval_ms}ms interval");
 
-    if count == 0 || count > 20 {
-        return Err("Invalid photo count (must be 1-20)".to_string());
+    let operation = Operation::register(operation_id)?;
+    let mut frames = Vec::new();
+    capture_sequence_with(
+        device_id,
+        count,
+        CAPTURE_SEQUENCE_MAX_COUNT,
+        interval_ms,
+        format,
+        &operation,
+        |_, frame| {
+            frames.push(frame);
+        },
+    )
+    .await?;
+
+    log::info!("Successfully captured {} photos", frames.len());
+    Ok(frames)
+}
+
+/// One frame of a streamed sequence, emitted as `crabcamera://sequence-frame`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SequenceFrameEvent {
+    /// Device the sequence is captured from.
+    pub device_id: String,
+    /// Zero-based position of this frame in the sequence.
+    pub index: u32,
+    /// Number of frames requested.
+    pub total: u32,
+    /// The captured frame.
+    pub frame: CameraFrame,
+}
+
+/// Result of [`capture_photo_sequence_streaming`]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SequenceSummary {
+    /// Device the sequence was captured from.
+    pub device_id: String,
+    /// Frames captured and emitted.
+    pub frames_captured: u32,
+    /// Number of frames requested.
+    pub total: u32,
+    /// Whether [`cancel_operation`] stopped the sequence early.
+    #[serde(default)]
+    pub cancelled: bool,
+    /// Wall-clock time for the whole sequence in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Capture multiple photos in sequence, emitting each one as it is captured
+///
+/// Each frame is sent as a [`SequenceFrameEvent`] on
+/// `crabcamera://sequence-frame` so a progress UI can update live; the
+/// command itself resolves with a [`SequenceSummary`] once the sequence is
+/// done. Accepts the same `count` and `interval_ms` as
+/// [`capture_photo_sequence`]. If a capture fails part-way, frames already
+/// emitted stay delivered and the command returns the error. With an
+/// `operation_id`, [`cancel_operation`] ends the sequence early and the
+/// summary reports `cancelled`.
+///
+/// # Errors
+/// Returns an `Err` if `count` is `0` or greater than `20`, or if
+/// `operation_id` is already in use. Also returns an `Err` if the camera
+/// cannot be obtained, the mutex is poisoned, the blocking task fails to
+/// join, or a frame capture fails.
+#[command]
+pub async fn capture_photo_sequence_streaming<R: Runtime>(
+    device_id: String,
+    count: u32,
+    interval_ms: u32,
+    format: Option<CameraFormat>,
+    operation_id: Option<String>,
+    app: tauri::AppHandle<R>,
+) -> Result<SequenceSummary, CommandError> {
+    stream_photo_sequence(
+        device_id,
+        count,
+        interval_ms,
+        format,
+        operation_id,
+        move |event| {
+            let _ = app.emit("crabcamera://sequence-frame", event);
+        },
+    )
+    .await
+}
+
+/// Capture a sequence, reporting each frame to `on_frame`
+async fn stream_photo_sequence<F>(
+    device_id: String,
+    count: u32,
+    interval_ms: u32,
+    format: Option<CameraFormat>,
+    operation_id: Option<String>,
+    mut on_frame: F,
+) -> Result<SequenceSummary, CommandError>
+where
+    F: FnMut(&SequenceFrameEvent) + Send,
+{
+    log::info!("Streaming {count} photos from camera {device_id} with {interval_ms}ms interval");
+
+    let operation = Operation::register(operation_id)?;
+    let start = std::time::Instant::now();
+    let mut frames_captured = 0;
+    capture_sequence_with(
+        device_id.clone(),
+        count,
+        CAPTURE_SEQUENCE_MAX_COUNT,
+        interval_ms,
+        format,
+        &operation,
+        |index, frame| {
+            frames_captured += 1;
+            on_frame(&SequenceFrameEvent {
+                device_id: device_id.clone(),
+                index,
+                total: count,
+                frame,
+            });
+        },
+    )
+    .await?;
+
+    let duration_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+    log::info!("Streamed {frames_captured} photos in {duration_ms}ms");
+    Ok(SequenceSummary {
+        device_id,
+        frames_captured,
+        total: count,
+        cancelled: frames_captured < count,
+        duration_ms,
+    })
+}
+
+/// Capture `frame_count` frames `delay_ms` apart and encode them as an
+/// animated GIF
+///
+/// Frames are downscaled so their longest side is at most `max_dim`, and
+/// each is quantized to its own 256-color palette. They play back
+/// `delay_ms` apart, so the clip runs at about the speed it was captured
+/// (GIF delays have 10 ms resolution). `loop_forever` defaults to `true`;
+/// `false` plays the clip once. Progress is reported like a sequence.
+///
+/// # Errors
+/// Returns an `Err` if `frame_count` is `0` or greater than `100`, if
+/// `max_dim` is `0`, if the camera cannot be obtained, the mutex is
+/// poisoned, the blocking task fails to join, a frame capture fails, or
+/// GIF encoding fails.
+#[command]
+pub async fn capture_gif(
+    device_id: String,
+    frame_count: u32,
+    delay_ms: u32,
+    max_dim: u32,
+    format: Option<CameraFormat>,
+    loop_forever: Option<bool>,
+) -> Result<Vec<u8>, CommandError> {
+    if max_dim == 0 {
+        return Err(CommandError::invalid_argument("max_dim must be at least 1"));
+    }
+    log::info!("Capturing {frame_count}-frame GIF from camera {device_id}");
+
+    let operation = Operation::register(None)?;
+    let mut frames = Vec::new();
+    capture_sequence_with(
+        device_id,
+        frame_count,
+        GIF_MAX_FRAMES,
+        delay_ms,
+        format,
+        &operation,
+        |_, frame| {
+            frames.push(
+                crate::preview::encode::downscale_to_max_dim(&frame, max_dim).unwrap_or(frame),
+            );
+        },
+    )
+    .await?;
+
+    let looping = loop_forever.unwrap_or(true);
+    let gif = tokio::task::spawn_blocking(move || {
+        crate::preview::encode::encode_gif(&frames, delay_ms, looping)
+            .map_err(|e| CommandError::new("ENCODING_FAILED", e))
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
+    log::info!("Encoded {frame_count}-frame GIF ({} bytes)", gif.len());
+    Ok(gif)
+}
+
+/// Capture `count` frames `interval_ms` apart, handing each to `on_frame`
+/// with its zero-based index as soon as it is captured
+///
+/// `count` must be between 1 and `max_count`. Reports progress for
+/// `operation` and stops early, without an error, once it is cancelled.
+async fn capture_sequence_with<F>(
+    device_id: String,
+    count: u32,
+    max_count: u32,
+    interval_ms: u32,
+    format: Option<CameraFormat>,
+    operation: &Operation,
+    mut on_frame: F,
+) -> Result<(), CommandError>
+where
+    F: FnMut(u32, CameraFrame) + Send,
+{
+    if count == 0 || count > max_count {
+        return Err(CommandError::invalid_argument(format!(
+            "Invalid photo count (must be 1-{max_count})"
+        )));
     }
 
     let capture_format = format.unwrap_or_else(CameraFormat::standard);
-    let camera = match get_or_create_camera(device_id.clone(), capture_format).await {
-        Ok(cam) => cam,
-        Err(e) => return Err(e.to_string()),
-    };
+    let cancel = operation.token();
+    let camera = get_or_create_camera(device_id.clone(), capture_format).await?;
 
     // Start stream once
     {
@@ -179,36 +487,213 @@ pub async fn capture_photo_sequence(
             }
         })
         .await
-        .map_err(|e| format!("Task join error: {e}"))?;
+        .map_err(|e| CommandError::system(format!("Task join error: {e}")))?;
     }
 
-    let mut frames = Vec::new();
-
     for i in 0..count {
+        if cancel.is_cancelled() {
+            log::info!("Sequence cancelled after {i} of {count} photos");
+            break;
+        }
         log::debug!("Capturing photo {} of {}", i + 1, count);
 
         let camera_clone = camera.clone();
         let frame = tokio::task::spawn_blocking(move || {
             let mut camera_guard = camera_clone
                 .lock()
-                .map_err(|_| "Mutex poisoned".to_string())?;
-            camera_guard
-                .capture_frame()
-                .map_err(|e| format!("Failed to capture frame: {e}"))
+                .map_err(|_| CommandError::system("Mutex poisoned"))?;
+            camera_guard.capture_frame().map_err(|e| {
+                CommandError::new(e.error_code(), format!("Failed to capture frame: {e}"))
+            })
         })
         .await
-        .map_err(|e| format!("Task join error: {e}"))??;
-
-        frames.push(frame);
+        .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
+
+        operation.report(
+            &device_id,
+            i + 1,
+            Some(count),
+            CaptureStage::Capturing,
+            Some(&frame),
+        );
+        on_frame(i, frame);
 
         // Wait between captures (except for the last one)
         if i < count - 1 {
-            tokio::time::sleep(tokio::time::Duration::from_millis(u64::from(interval_ms))).await;
+            tokio::select! {
+                () = cancel.cancelled() => {}
+                () = tokio::time::sleep(Duration::from_millis(u64::from(interval_ms))) => {}
+            }
         }
     }
 
-    log::info!("Successfully captured {} photos", frames.len());
-    Ok(frames)
+    Ok(())
+}
+
+// Cancellation tokens of running multi-step captures, by operation id
+static OPERATIONS: LazyLock<StdMutex<HashMap<String, CancellationToken>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+/// A multi-step capture that [`cancel_operation`] can stop
+///
+/// Registered under its operation id until dropped; without an id it cannot
+/// be cancelled.
+pub(crate) struct Operation {
+    id: Option<String>,
+    token: CancellationToken,
+}
+
+impl Operation {
+    /// Register an operation under `operation_id`
+    ///
+    /// # Errors
+    /// Returns an `INVALID_ARGUMENT` error if an operation with the same id
+    /// is still running.
+    pub(crate) fn register(operation_id: Option<String>) -> Result<Self, CommandError> {
+        let token = CancellationToken::new();
+        if let Some(id) = &operation_id {
+            let mut operations = OPERATIONS
+                .lock()
+                .map_err(|_| CommandError::system("Mutex poisoned"))?;
+            if operations.contains_key(id) {
+                return Err(CommandError::invalid_argument(format!(
+                    "Operation already running: {id}"
+                )));
+            }
+            operations.insert(id.clone(), token.clone());
+        }
+        Ok(Self {
+            id: operation_id,
+            token,
+        })
+    }
+
+    /// Token cancelled by [`cancel_operation`]
+    pub(crate) fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Report this operation's progress to the progress listener
+    pub(crate) fn report(
+        &self,
+        device_id: &str,
+        current: u32,
+        total: Option<u32>,
+        stage: CaptureStage,
+        frame: Option<&CameraFrame>,
+    ) {
+        report_progress(self.id.as_deref(), device_id, current, total, stage, frame);
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        if let Some(id) = &self.id {
+            if let Ok(mut operations) = OPERATIONS.lock() {
+                operations.remove(id);
+            }
+        }
+    }
+}
+
+/// Stop a running multi-step capture started with `operation_id`
+///
+/// Sequences stop after the current frame and return the frames captured so
+/// far; focus stacks fail with a `CANCELLED` error. The camera stays open.
+/// Returns whether an operation with that id was running. Time-lapses are
+/// stopped with [`stop_timelapse`].
+///
+/// # Errors
+/// Returns an `Err` if the operation registry mutex is poisoned.
+#[command]
+pub async fn cancel_operation(operation_id: String) -> Result<bool, CommandError> {
+    let token = OPERATIONS
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?
+        .get(&operation_id)
+        .cloned();
+    if let Some(token) = &token {
+        log::info!("Cancelling operation {operation_id}");
+        token.cancel();
+    }
+    Ok(token.is_some())
+}
+
+/// Step of a multi-frame capture reported by [`CaptureProgress`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureStage {
+    /// Frames are being captured
+    Capturing,
+    /// Captured frames are being aligned (focus stacks)
+    Aligning,
+    /// Frames are being merged into the result (focus stacks)
+    Merging,
+}
+
+/// Progress of a multi-frame capture, emitted as
+/// `crabcamera://capture-progress`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CaptureProgress {
+    /// Operation id passed to the command, for correlating with
+    /// [`cancel_operation`]; `None` when the caller gave none.
+    pub operation_id: Option<String>,
+    /// Device being captured.
+    pub device_id: String,
+    /// Steps of the current stage completed so far.
+    pub current: u32,
+    /// Steps in the current stage, `None` when open-ended.
+    pub total: Option<u32>,
+    /// Current stage.
+    pub stage: CaptureStage,
+    /// Thumbnail of the latest frame, at most `PROGRESS_THUMBNAIL_MAX_DIM`
+    /// pixels on its longest side.
+    pub frame: Option<CameraFrame>,
+}
+
+type ProgressListener = Box<dyn Fn(&CaptureProgress) + Send + Sync>;
+
+// Receives progress of sequences, focus stacks, HDR brackets and time-lapses
+static PROGRESS_LISTENER: RwLock<Option<ProgressListener>> = RwLock::new(None);
+
+/// Report the progress of multi-frame captures to `listener`, replacing any
+/// previous one
+///
+/// The plugin installs a listener that emits `crabcamera://capture-progress`.
+pub fn set_progress_listener(listener: ProgressListener) {
+    if let Ok(mut current) = PROGRESS_LISTENER.write() {
+        *current = Some(listener);
+    }
+}
+
+/// Hand a progress update to the listener, thumbnailing `frame` only when
+/// a listener is installed
+pub(crate) fn report_progress(
+    operation_id: Option<&str>,
+    device_id: &str,
+    current: u32,
+    total: Option<u32>,
+    stage: CaptureStage,
+    frame: Option<&CameraFrame>,
+) {
+    let Ok(listener) = PROGRESS_LISTENER.read() else {
+        return;
+    };
+    let Some(listener) = listener.as_ref() else {
+        return;
+    };
+    let thumbnail = frame.map(|frame| {
+        crate::preview::encode::downscale_to_max_dim(frame, PROGRESS_THUMBNAIL_MAX_DIM)
+            .unwrap_or_else(|| frame.clone())
+    });
+    listener(&CaptureProgress {
+        operation_id: operation_id.map(str::to_string),
+        device_id: device_id.to_string(),
+        current,
+        total,
+        stage,
+        frame: thumbnail,
+    });
 }
 
 /// Capture a photo with quality retry - automatically retries until quality threshold is met
@@ -227,7 +712,7 @@ pub async fn capture_with_quality_retry(
     max_attempts: Option<u32>,
     min_quality_score: Option<f32>,
     format: Option<CameraFormat>,
-) -> Result<CameraFrame, String> {
+) -> Result<CameraFrame, CommandError> {
     let camera_id = device_id.unwrap_or_else(|| "0".to_string());
     let attempts = max_attempts.unwrap_or(10).min(50); // Cap at 50 attempts
     let quality_threshold = min_quality_score.unwrap_or(0.7).clamp(0.0, 1.0);
@@ -239,7 +724,7 @@ pub async fn capture_with_quality_retry(
 
     let camera = match get_or_create_camera(camera_id.clone(), capture_format).await {
         Ok(cam) => cam,
-        Err(e) => return Err(e.to_string()),
+        Err(e) => return Err(e.into()),
     };
 
     // Start stream once
@@ -253,7 +738,7 @@ pub async fn capture_with_quality_retry(
             }
         })
         .await
-        .map_err(|e| format!("Task join error: {e}"))?;
+        .map_err(|e| CommandError::system(format!("Task join error: {e}")))?;
     }
 
     let validator = QualityValidator::default();
@@ -266,11 +751,11 @@ pub async fn capture_with_quality_retry(
             tokio::task::spawn_blocking(move || {
                 let mut camera_guard = camera_clone
                     .lock()
-                    .map_err(|_| "Mutex poisoned".to_string())?;
-                camera_guard.capture_frame().map_err(|e| e.to_string())
+                    .map_err(|_| CommandError::system("Mutex poisoned"))?;
+                camera_guard.capture_frame().map_err(CommandError::from)
             })
             .await
-            .map_err(|e| format!("Task join error: {e}"))??
+            .map_err(|e| CommandError::system(format!("Task join error: {e}")))??
         };
 
         // Validate quality
@@ -311,8 +796,9 @@ pub async fn capture_with_quality_retry(
         );
         Ok(frame)
     } else {
-        Err(format!(
-            "Failed to capture any valid frames after {attempts} attempts"
+        Err(CommandError::new(
+            "CAPTURE_FAILED",
+            format!("Failed to capture any valid frames after {attempts} attempts"),
         ))
     }
 }
@@ -322,14 +808,132 @@ pub async fn capture_with_quality_retry(
 /// # Errors
 /// Propagates any error from [`crate::platform::release_camera`].
 #[command]
-pub async fn release_camera(device_id: String) -> Result<String, String> {
+pub async fn release_camera(device_id: String) -> Result<String, CommandError> {
+    if let Ok(mut queues) = FRAME_QUEUES.lock() {
+        queues.remove(&device_id);
+    }
+    if let Ok(mut buffers) = REORDER_BUFFERS.lock() {
+        buffers.remove(&device_id);
+    }
     crate::platform::release_camera(&device_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
+}
+
+// Frames delivered by each camera's frame callback, awaiting `get_queued_frames`
+static FRAME_QUEUES: LazyLock<StdMutex<HashMap<String, FrameQueue>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+// Frames held back per device to restore timestamp order before queueing
+static REORDER_BUFFERS: LazyLock<StdMutex<HashMap<String, ReorderBuffer>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+// Capture and preview resolution of the last frame queued per device
+static PREVIEW_RESOLUTIONS: LazyLock<StdMutex<HashMap<String, ((u32, u32), (u32, u32))>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+/// Queue depth and drop count for a device's frame queue
+fn frame_queue_stats(device_id: &str) -> (usize, u64) {
+    FRAME_QUEUES
+        .lock()
+        .ok()
+        .and_then(|queues| queues.get(device_id).map(|q| (q.len(), q.dropped())))
+        .unwrap_or((0, 0))
+}
+
+/// Reordered and late-dropped frame counts for a device's reorder buffer
+fn reorder_stats(device_id: &str) -> (u64, u64) {
+    REORDER_BUFFERS
+        .lock()
+        .ok()
+        .and_then(|buffers| buffers.get(device_id).map(|b| (b.reordered(), b.late())))
+        .unwrap_or((0, 0))
+}
+
+/// Pass a frame through the device's reorder buffer, if any, returning the
+/// frames ready to queue
+fn resequence(device_id: &str, frame: CameraFrame) -> Vec<CameraFrame> {
+    let Ok(mut buffers) = REORDER_BUFFERS.lock() else {
+        return vec![frame];
+    };
+    match buffers.get_mut(device_id) {
+        Some(buffer) => buffer.push(frame),
+        None => vec![frame],
+    }
+}
+
+/// Resize every camera's frame queue after a config change, to the
+/// capacity `capacity_for` returns for its device id
+pub(crate) fn apply_frame_queue_capacity(capacity_for: impl Fn(&str) -> usize) {
+    // Capacities may enumerate devices; don't hold the queue lock meanwhile
+    let device_ids: Vec<String> = match FRAME_QUEUES.lock() {
+        Ok(queues) => queues.keys().cloned().collect(),
+        Err(_) => return,
+    };
+    let capacities: Vec<(String, usize)> = device_ids
+        .into_iter()
+        .map(|device_id| {
+            let capacity = capacity_for(&device_id);
+            (device_id, capacity)
+        })
+        .collect();
+    if let Ok(mut queues) = FRAME_QUEUES.lock() {
+        for (device_id, capacity) in capacities {
+            if let Some(queue) = queues.get_mut(&device_id) {
+                queue.set_capacity(capacity);
+            }
+        }
+    }
+}
+
+/// Capture and preview resolution last seen by a device's frame callback
+fn preview_resolutions(device_id: &str) -> (Option<(u32, u32)>, Option<(u32, u32)>) {
+    PREVIEW_RESOLUTIONS
+        .lock()
+        .ok()
+        .and_then(|resolutions| resolutions.get(device_id).copied())
+        .map_or((None, None), |(capture, preview)| {
+            (Some(capture), Some(preview))
+        })
+}
+
+/// Shrink a frame for the preview queue when `max_dim` is set, recording
+/// both resolutions for [`get_capture_stats`]
+fn scale_for_preview(device_id: &str, frame: CameraFrame, max_dim: Option<u32>) -> CameraFrame {
+    let capture = (frame.width, frame.height);
+    let frame = max_dim
+        .and_then(|max_dim| crate::preview::encode::downscale_to_max_dim(&frame, max_dim))
+        .unwrap_or(frame);
+    if let Ok(mut resolutions) = PREVIEW_RESOLUTIONS.lock() {
+        resolutions.insert(
+            device_id.to_string(),
+            (capture, (frame.width, frame.height)),
+        );
+    }
+    frame
 }
 
 /// Set a callback for real-time frame processing
 ///
+/// Every frame the camera delivers from then on is buffered for
+/// [`get_queued_frames`]. The buffer holds at most
+/// `AdvancedConfig::frame_queue_capacity` frames; when the consumer falls
+/// behind, the oldest frame is dropped (see `dropped_frames` in
+/// [`get_capture_stats`]) so memory stays bounded. Calling this again resets
+/// the queue and its counters.
+///
+/// With `AdvancedConfig::preview_max_dim` set, buffered frames are
+/// downscaled to fit it, which keeps the IPC payload small; captures such as
+/// [`capture_single_photo`] still return full-resolution frames. Changes to
+/// the capacity and preview size apply to running callbacks; the reorder
+/// window applies from the next call. All three honour the camera's
+/// configuration overrides.
+///
+/// With `AdvancedConfig::reorder_window` above 0, frames are held back in a
+/// [`ReorderBuffer`] and queued in hardware timestamp order, at the cost of
+/// that many frame intervals of latency; frames too late to be placed are
+/// dropped. Both counts are reported by [`get_capture_stats`].
+///
 /// # Errors
 /// Returns an `Err` if the camera cannot be obtained, the mutex is poisoned,
 /// the blocking task fails to join, or the callback cannot be registered.
@@ -337,15 +941,38 @@ pub async fn release_camera(device_id: String) -> Result<String, String> {
 pub async fn set_frame_callback(
     device_id: String,
     format: Option<CameraFormat>,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     log::info!("Setting frame callback for device: {device_id}");
 
     let capture_format = format.unwrap_or_else(CameraFormat::standard);
     let camera = match get_or_create_camera(device_id.clone(), capture_format).await {
         Ok(cam) => cam,
-        Err(e) => return Err(e.to_string()),
+        Err(e) => return Err(e.into()),
     };
 
+    let stable_id = crate::platform::CameraSystem::stable_device_id(&device_id);
+    let advanced = crate::commands::config::effective_config(Some(&stable_id)).advanced;
+    FRAME_QUEUES
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?
+        .insert(
+            device_id.clone(),
+            FrameQueue::new(advanced.frame_queue_capacity),
+        );
+    if let Ok(mut resolutions) = PREVIEW_RESOLUTIONS.lock() {
+        resolutions.remove(&device_id);
+    }
+    if let Ok(mut buffers) = REORDER_BUFFERS.lock() {
+        if advanced.reorder_window > 0 {
+            buffers.insert(
+                device_id.clone(),
+                ReorderBuffer::new(advanced.reorder_window),
+            );
+        } else {
+            buffers.remove(&device_id);
+        }
+    }
+
     let device_id_clone = device_id.clone();
     let callback = move |frame: CameraFrame| {
         log::debug!(
@@ -355,7 +982,23 @@ pub async fn set_frame_callback(
             frame.height,
             frame.size_bytes
         );
-        // Frame available for frontend comsumption via events
+        let frame = crate::platform::orientation::orient_frame(frame);
+        let preview_max_dim = crate::commands::config::current_preview_max_dim(&stable_id);
+        let frame = scale_for_preview(&device_id_clone, frame, preview_max_dim);
+        let ready = resequence(&device_id_clone, frame);
+        let Ok(mut queues) = FRAME_QUEUES.lock() else {
+            return;
+        };
+        if let Some(queue) = queues.get_mut(&device_id_clone) {
+            for frame in ready {
+                if queue.push(frame) {
+                    log::debug!(
+                        "Frame queue for {device_id_clone} full; dropped oldest ({} total)",
+                        queue.dropped()
+                    );
+                }
+            }
+        }
     };
 
     let camera_clone = camera.clone();
@@ -363,20 +1006,49 @@ pub async fn set_frame_callback(
     tokio::task::spawn_blocking(move || {
         let mut camera_guard = camera_clone
             .lock()
-            .map_err(|_| "Mutex poisoned".to_string())?;
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
 
-        camera_guard
-            .frame_callback(callback)
-            .map_err(|e| format!("Failed to set frame callback for device {device_id_clone}: {e}"))
+        camera_guard.frame_callback(callback).map_err(|e| {
+            CommandError::new(
+                e.error_code(),
+                format!("Failed to set frame callback for device {device_id_clone}: {e}"),
+            )
+        })
     })
     .await
-    .map_err(|e| format!("Task join error: {e}"))??;
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
 
     Ok(format!("Frame callback set for device: {device_id}"))
 }
 
+/// Take frames buffered since the last call, oldest first
+///
+/// Returns at most `max_frames` frames (all of them when `None`); frames are
+/// only buffered after [`set_frame_callback`] was called for `device_id`.
+///
+/// # Errors
+/// Returns an `Err` if the frame queue mutex is poisoned.
+#[command]
+pub async fn get_queued_frames(
+    device_id: String,
+    max_frames: Option<u32>,
+) -> Result<Vec<CameraFrame>, CommandError> {
+    let max = max_frames.map_or(usize::MAX, |n| n as usize);
+    let mut queues = FRAME_QUEUES
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?;
+    Ok(queues
+        .get_mut(&device_id)
+        .map(|queue| queue.drain(max))
+        .unwrap_or_default())
+}
+
 /// Start continuous capture from a camera (for live preview)
 ///
+/// Discards `warmup_frames` frames once the stream starts so the first
+/// preview frames are not dark; `None` uses `CAPTURE_WARMUP_FRAMES` (5) and
+/// `Some(0)` disables warmup.
+///
 /// # Errors
 /// Returns an `Err` if the camera cannot be obtained, the mutex is poisoned,
 /// the blocking task fails to join, or starting the camera stream fails.
@@ -384,13 +1056,17 @@ pub async fn set_frame_callback(
 pub async fn start_camera_preview(
     device_id: String,
     format: Option<CameraFormat>,
-) -> Result<String, String> {
+    warmup_frames: Option<u32>,
+) -> Result<String, CommandError> {
     log::info!("Starting camera preview for device: {device_id}");
 
     let capture_format = format.unwrap_or_else(CameraFormat::standard);
+    let warmup = warmup_frames
+        .unwrap_or(CAPTURE_WARMUP_FRAMES)
+        .min(CAPTURE_WARMUP_MAX_FRAMES);
     let camera = match get_or_create_camera(device_id.clone(), capture_format).await {
         Ok(cam) => cam,
-        Err(e) => return Err(e.to_string()),
+        Err(e) => return Err(e.into()),
     };
 
     let camera_clone = camera.clone();
@@ -398,20 +1074,28 @@ pub async fn start_camera_preview(
     tokio::task::spawn_blocking(move || {
         let mut camera_guard = camera_clone
             .lock()
-            .map_err(|_| "Mutex poisoned".to_string())?;
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
         match camera_guard.start_stream() {
             Ok(()) => {
+                crate::platform::manager::discard_warmup_frames(
+                    &mut camera_guard,
+                    warmup,
+                    CAPTURE_WARMUP_DELAY_MS,
+                );
                 log::info!("Camera preview started for device: {device_id_clone}");
                 Ok(format!("Preview started for camera {device_id_clone}"))
             }
             Err(e) => {
                 log::error!("Failed to start camera preview: {e}");
-                Err(format!("Failed to start camera preview: {e}"))
+                Err(CommandError::new(
+                    e.error_code(),
+                    format!("Failed to start camera preview: {e}"),
+                ))
             }
         }
     })
     .await
-    .map_err(|e| format!("Task join error: {e}"))?
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
 }
 
 /// Stop camera preview
@@ -421,7 +1105,7 @@ pub async fn start_camera_preview(
 /// is poisoned, if the blocking task fails to join, or if stopping the camera
 /// stream fails.
 #[command]
-pub async fn stop_camera_preview(device_id: String) -> Result<String, String> {
+pub async fn stop_camera_preview(device_id: String) -> Result<String, CommandError> {
     log::info!("Stopping camera preview for device: {device_id}");
 
     if let Some(camera) = get_existing_camera(&device_id).await {
@@ -430,7 +1114,7 @@ pub async fn stop_camera_preview(device_id: String) -> Result<String, String> {
         tokio::task::spawn_blocking(move || {
             let mut camera_guard = camera_clone
                 .lock()
-                .map_err(|_| "Mutex poisoned".to_string())?;
+                .map_err(|_| CommandError::system("Mutex poisoned"))?;
             match camera_guard.stop_stream() {
                 Ok(()) => {
                     log::info!("Camera preview stopped for device: {device_id_clone}");
@@ -438,149 +1122,1264 @@ pub async fn stop_camera_preview(device_id: String) -> Result<String, String> {
                 }
                 Err(e) => {
                     log::error!("Failed to stop camera preview: {e}");
-                    Err(format!("Failed to stop camera preview: {e}"))
+                    Err(CommandError::new(
+                        e.error_code(),
+                        format!("Failed to stop camera preview: {e}"),
+                    ))
                 }
             }
         })
         .await
-        .map_err(|e| format!("Task join error: {e}"))?
+        .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
     } else {
         let msg = format!("No active camera found with ID: {device_id}");
         log::warn!("{msg}");
-        Err(msg)
+        Err(CommandError::not_found(msg))
     }
 }
 
-/// Get capture statistics for a camera
+/// Pause frame delivery from an open camera without releasing it
+///
+/// Unlike [`stop_camera_preview`], the device keeps streaming, so exposure
+/// and white balance stay settled and [`resume_camera`] is immediate. No
+/// frames reach the frame callback, previews or recordings while paused;
+/// captures fail with `STREAM_FAILED`. The sensor stays powered, so a paused
+/// camera draws about as much power and USB bandwidth as a running one and
+/// its activity light stays on; release it for long pauses.
 ///
 /// # Errors
-/// Returns an `Err` if the camera mutex is poisoned or the blocking task fails
-/// to join (only when an active camera exists for `device_id`).
+/// Returns an `Err` if no camera is open for `device_id`.
 #[command]
-pub async fn get_capture_stats(device_id: String) -> Result<CaptureStats, String> {
-    if let Some(camera) = get_existing_camera(&device_id).await {
-        let camera_clone = camera.clone();
-        let device_id_clone = device_id.clone();
-        let stats = tokio::task::spawn_blocking(move || {
-            let camera_guard = camera_clone
-                .lock()
-                .map_err(|_| "Mutex poisoned".to_string())?;
-            let is_active = camera_guard.is_available();
-            let device_id_opt = camera_guard.get_device_id();
-
-            Ok::<CaptureStats, String>(CaptureStats {
-                device_id: device_id_clone,
-                is_active,
-                device_info: device_id_opt.map(std::string::ToString::to_string),
-            })
-        })
-        .await
-        .map_err(|e| format!("Task join error: {e}"))??;
-        Ok(stats)
+pub async fn pause_camera(device_id: String) -> Result<String, CommandError> {
+    if crate::platform::pause_camera(&device_id).await? {
+        Ok(format!("Camera {device_id} paused"))
     } else {
-        Ok(CaptureStats {
-            device_id: device_id.clone(),
-            is_active: false,
-            device_info: None,
-        })
+        Ok(format!("Camera {device_id} already paused"))
     }
 }
 
-/// Save captured frame to disk as a proper image file
-/// Supports PNG (lossless) based on file extension
+/// Resume frame delivery after [`pause_camera`]
 ///
 /// # Errors
-/// Returns an `Err` if the frame data cannot be converted into an image or if
-/// writing the image file fails (including a blocking task join failure).
+/// Returns an `Err` if no camera is open for `device_id`.
 #[command]
-pub async fn save_frame_to_disk(frame: CameraFrame, file_path: String) -> Result<String, String> {
-    log::info!("Saving frame {} to disk: {}", frame.id, file_path);
+pub async fn resume_camera(device_id: String) -> Result<String, CommandError> {
+    if crate::platform::resume_camera(&device_id).await? {
+        Ok(format!("Camera {device_id} resumed"))
+    } else {
+        Ok(format!("Camera {device_id} was not paused"))
+    }
+}
 
-    // Convert frame data to proper image format
-    let img = image::RgbImage::from_vec(frame.width, frame.height, frame.data)
-        .ok_or_else(|| "Failed to create image from frame data".to_string())?;
+/// Frame grabbed by [`fire_capture`]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TriggeredFrame {
+    /// The captured frame.
+    pub frame: CameraFrame,
+    /// Time from the trigger reaching [`fire_capture`] to the frame being
+    /// returned, in milliseconds.
+    pub latency_ms: f64,
+    /// Frame interval of the armed format in milliseconds, the latency to
+    /// expect from a warm stream.
+    pub frame_interval_ms: f64,
+}
 
-    let dynamic_img = image::DynamicImage::ImageRgb8(img);
+struct ArmedCapture {
+    device_id: String,
+    frame_interval_ms: f64,
+}
 
-    // Determine format from extension, default to PNG
-    let format = if file_path.to_lowercase().ends_with(".jpg")
-        || file_path.to_lowercase().ends_with(".jpeg")
-    {
-        image::ImageFormat::Jpeg
-    } else {
-        image::ImageFormat::Png
-    };
+// Cameras armed with `arm_capture`, by session id
+static ARMED_CAPTURES: LazyLock<StdMutex<HashMap<String, ArmedCapture>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
 
-    // Save in spawn_blocking to avoid blocking async runtime
-    let file_path_clone = file_path.clone();
-    match tokio::task::spawn_blocking(move || {
-        dynamic_img.save_with_format(&file_path_clone, format)
+/// Open a camera and keep its stream running so [`fire_capture`] can grab
+/// a frame as soon as an external trigger fires
+///
+/// Warmup frames are discarded here, so a trigger costs about one frame
+/// interval instead of the hundreds of milliseconds needed to open a
+/// camera. Returns the session id to pass to [`fire_capture`] and
+/// [`disarm_capture`]. Several sessions may arm the same camera.
+///
+/// # Errors
+/// Returns an `Err` if the camera cannot be obtained, the mutex is poisoned,
+/// the blocking task fails to join, or starting the stream fails.
+#[command]
+pub async fn arm_capture(
+    device_id: String,
+    format: Option<CameraFormat>,
+) -> Result<String, CommandError> {
+    let format = format.unwrap_or_else(CameraFormat::standard);
+    log::info!("Arming capture on camera {device_id} at {format:?}");
+
+    let camera = get_or_create_camera(device_id.clone(), format.clone()).await?;
+    tokio::task::spawn_blocking(move || {
+        let mut camera = camera
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+        camera.start_stream().map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to start stream: {e}"))
+        })?;
+        crate::platform::manager::discard_warmup_frames(
+            &mut camera,
+            CAPTURE_WARMUP_FRAMES,
+            CAPTURE_WARMUP_DELAY_MS,
+        );
+        Ok::<_, CommandError>(())
     })
     .await
-    {
-        Ok(Ok(())) => {
-            log::info!("Frame saved successfully to: {file_path}");
-            Ok(format!("Frame saved to {file_path}"))
-        }
-        Ok(Err(e)) => {
-            log::error!("Failed to save frame: {e}");
-            Err(format!("Failed to save frame: {e}"))
-        }
-        Err(e) => {
-            log::error!("Task join error: {e}");
-            Err("Failed to execute save task".to_string())
-        }
-    }
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
+
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let frame_interval_ms = if format.fps > 0.0 {
+        1000.0 / f64::from(format.fps)
+    } else {
+        0.0
+    };
+    ARMED_CAPTURES
+        .lock()
+        .map_err(|_| CommandError::system("Armed capture registry poisoned"))?
+        .insert(
+            session_id.clone(),
+            ArmedCapture {
+                device_id,
+                frame_interval_ms,
+            },
+        );
+    Ok(session_id)
 }
 
-/// Save frame with compression for smaller file sizes
+/// Grab the next frame from a camera armed with [`arm_capture`]
+///
+/// Call this from the trigger handler (GPIO, key press, network message);
+/// the reported latency runs from this call to the frame being returned.
+/// The session stays armed for further triggers.
 ///
 /// # Errors
-/// Returns an `Err` if the frame data cannot be converted into an image, if the
+/// Returns a `NOT_FOUND` error if `session_id` is not armed or its camera
+/// was released. Also returns an `Err` if the mutex is poisoned, the
+/// blocking task fails to join, or the capture fails.
+#[command]
+pub async fn fire_capture(session_id: String) -> Result<TriggeredFrame, CommandError> {
+    let fired = std::time::Instant::now();
+    let (device_id, frame_interval_ms) = ARMED_CAPTURES
+        .lock()
+        .map_err(|_| CommandError::system("Armed capture registry poisoned"))?
+        .get(&session_id)
+        .map(|armed| (armed.device_id.clone(), armed.frame_interval_ms))
+        .ok_or_else(|| CommandError::not_found(format!("No armed capture with id {session_id}")))?;
+    let camera = get_existing_camera(&device_id).await.ok_or_else(|| {
+        CommandError::not_found(format!("Camera {device_id} was released while armed"))
+    })?;
+
+    let frame = tokio::task::spawn_blocking(move || {
+        let mut camera = camera
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+        camera
+            .capture_frame()
+            .map_err(|e| CommandError::new(e.error_code(), format!("Failed to capture frame: {e}")))
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
+
+    let latency_ms = fired.elapsed().as_secs_f64() * 1000.0;
+    log::info!("Triggered capture on camera {device_id} took {latency_ms:.1} ms");
+    Ok(TriggeredFrame {
+        frame,
+        latency_ms,
+        frame_interval_ms,
+    })
+}
+
+/// End an armed capture session
+///
+/// Stops the camera's stream unless another session still has it armed;
+/// the camera stays open until released. Returns `false` if `session_id`
+/// was not armed.
+///
+/// # Errors
+/// Returns an `Err` if the registry or camera mutex is poisoned, the
+/// blocking task fails to join, or stopping the stream fails.
+#[command]
+pub async fn disarm_capture(session_id: String) -> Result<bool, CommandError> {
+    let (armed, still_armed) = {
+        let mut sessions = ARMED_CAPTURES
+            .lock()
+            .map_err(|_| CommandError::system("Armed capture registry poisoned"))?;
+        let Some(armed) = sessions.remove(&session_id) else {
+            return Ok(false);
+        };
+        let still_armed = sessions.values().any(|s| s.device_id == armed.device_id);
+        (armed, still_armed)
+    };
+    log::info!(
+        "Disarmed capture session {session_id} on camera {}",
+        armed.device_id
+    );
+
+    if !still_armed {
+        if let Some(camera) = get_existing_camera(&armed.device_id).await {
+            tokio::task::spawn_blocking(move || {
+                let mut camera = camera
+                    .lock()
+                    .map_err(|_| CommandError::system("Mutex poisoned"))?;
+                camera.stop_stream().map_err(|e| {
+                    CommandError::new(e.error_code(), format!("Failed to stop stream: {e}"))
+                })
+            })
+            .await
+            .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
+        }
+    }
+    Ok(true)
+}
+
+/// Switch a camera to a new format without releasing it
+///
+/// An open camera keeps its registry entry and device handle: the stream is
+/// paused, the closest supported mode is negotiated and previously applied
+/// controls are restored. A camera that is not open yet is opened with
+/// `format`.
+///
+/// # Errors
+/// Returns an `Err` if the camera cannot be opened, the mutex is poisoned,
+/// the blocking task fails to join, or the driver rejects the format.
+#[command]
+pub async fn set_camera_format(
+    device_id: String,
+    format: CameraFormat,
+) -> Result<String, CommandError> {
+    let summary = format!("{}x{} @ {}fps", format.width, format.height, format.fps);
+    log::info!("Setting format {summary} for camera {device_id}");
+
+    let Some(camera) = get_existing_camera(&device_id).await else {
+        get_or_create_camera(device_id.clone(), format).await?;
+        return Ok(format!("Camera {device_id} opened at {summary}"));
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let mut camera_guard = camera
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+        camera_guard.reconfigure(format).map_err(|e| {
+            log::error!("Failed to change camera format: {e}");
+            format!("Failed to change camera format: {e}")
+        })
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
+
+    Ok(format!("Camera {device_id} format set to {summary}"))
+}
+
+/// A frame captured in an exact format, with that format
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FormatCapture {
+    /// The captured frame.
+    pub frame: CameraFrame,
+    /// Format the camera captured in.
+    pub format: CameraFormat,
+}
+
+/// Capture a photo in the `format_index`th format of
+/// [`get_camera_formats`](crate::commands::init::get_camera_formats)
+///
+/// Unlike [`capture_single_photo`], the listed format is used verbatim, so
+/// entries sharing a resolution but differing in pixel format or frame rate
+/// can be told apart. The camera keeps the format afterwards. Warmup frames
+/// are discarded first as for [`capture_single_photo`]; there is no
+/// automatic reconnection, since reopening would renegotiate the format.
+///
+/// # Errors
+/// Returns an `INVALID_ARGUMENT` error if `format_index` is out of range,
+/// or an `Err` if the camera cannot be found or opened, the driver does not
+/// accept the format exactly, or the capture fails.
+#[command]
+pub async fn capture_single_photo_with_format_index(
+    device_id: String,
+    format_index: usize,
+    warmup_frames: Option<u32>,
+) -> Result<FormatCapture, CommandError> {
+    log::info!("Capturing photo from camera {device_id} in format #{format_index}");
+
+    let warmup = warmup_frames
+        .unwrap_or(CAPTURE_WARMUP_FRAMES)
+        .min(CAPTURE_WARMUP_MAX_FRAMES);
+    let (camera, format) = open_with_format_index(&device_id, format_index).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut camera_guard = camera
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+        camera_guard.start_stream().map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to start stream: {e}"))
+        })?;
+        crate::platform::manager::discard_warmup_frames(
+            &mut camera_guard,
+            warmup,
+            CAPTURE_WARMUP_DELAY_MS,
+        );
+        let frame = camera_guard.capture_frame().map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to capture frame: {e}"))
+        })?;
+        Ok(FormatCapture { frame, format })
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// Capture a photo in the camera's largest format
+///
+/// The format is chosen as by
+/// [`get_max_resolution_format`](crate::commands::init::get_max_resolution_format)
+/// and used verbatim, as by [`capture_single_photo_with_format_index`],
+/// which also describes `warmup_frames`. The camera keeps the format
+/// afterwards.
+///
+/// # Errors
+/// Returns an `Err` if the camera cannot be found or reports no usable
+/// formats, or if opening it in that format or the capture fails.
+#[command]
+pub async fn capture_max_resolution_photo(
+    device_id: String,
+    warmup_frames: Option<u32>,
+) -> Result<FormatCapture, CommandError> {
+    let formats = crate::commands::init::get_camera_formats(device_id.clone()).await?;
+    let index = crate::platform::max_resolution_format(&formats)
+        .and_then(|chosen| formats.iter().position(|f| *f == chosen))
+        .ok_or_else(|| {
+            CommandError::not_found(format!("Camera '{device_id}' reports no usable formats"))
+        })?;
+    capture_single_photo_with_format_index(device_id, index, warmup_frames).await
+}
+
+/// Start the preview in the `format_index`th format of
+/// [`get_camera_formats`](crate::commands::init::get_camera_formats)
+///
+/// Like [`start_camera_preview`], but the listed format is used verbatim
+/// instead of the closest match. Returns the format in use.
+///
+/// # Errors
+/// Returns an `INVALID_ARGUMENT` error if `format_index` is out of range,
+/// or an `Err` if the camera cannot be found or opened, the driver does not
+/// accept the format exactly, or the stream cannot be started.
+#[command]
+pub async fn start_camera_preview_with_format_index(
+    device_id: String,
+    format_index: usize,
+    warmup_frames: Option<u32>,
+) -> Result<CameraFormat, CommandError> {
+    let (_, format) = open_with_format_index(&device_id, format_index).await?;
+    start_camera_preview(device_id, Some(format.clone()), warmup_frames).await?;
+    Ok(format)
+}
+
+/// Open `device_id` if needed and switch it verbatim to its
+/// `format_index`th listed format
+async fn open_with_format_index(
+    device_id: &str,
+    format_index: usize,
+) -> Result<(Arc<StdMutex<PlatformCamera>>, CameraFormat), CommandError> {
+    let formats = crate::commands::init::get_camera_formats(device_id.to_string()).await?;
+    let format = format_at(formats, format_index)?;
+    let camera = get_or_create_camera(device_id.to_string(), format.clone()).await?;
+
+    let camera_clone = camera.clone();
+    let format = tokio::task::spawn_blocking(move || {
+        let mut camera_guard = camera_clone
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+        camera_guard.reconfigure_exact(format).map_err(|e| {
+            CommandError::new(
+                e.error_code(),
+                format!("Failed to select format #{format_index}: {e}"),
+            )
+        })
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
+    Ok((camera, format))
+}
+
+/// Entry `index` of a camera's format list
+fn format_at(mut formats: Vec<CameraFormat>, index: usize) -> Result<CameraFormat, CommandError> {
+    if index >= formats.len() {
+        return Err(CommandError::invalid_argument(format!(
+            "Format index {index} out of range: camera lists {} formats",
+            formats.len()
+        )));
+    }
+    Ok(formats.swap_remove(index))
+}
+
+/// Mirror and/or rotate every frame from a camera
+///
+/// The flip is applied first, then the clockwise rotation, to frames from
+/// captures, previews and the frame callback; each transformed frame records
+/// it in `metadata.orientation`. `flip: None` with `rotation_degrees: 0`
+/// turns orientation off. Raw captures in the camera's native format are not
+/// transformed.
+///
+/// # Errors
+/// Returns an `Err` if `rotation_degrees` is not 0, 90, 180 or 270.
+#[command]
+pub async fn set_frame_orientation(
+    device_id: String,
+    flip: Option<FlipAxis>,
+    rotation_degrees: u16,
+) -> Result<FrameTransform, CommandError> {
+    let transform = FrameTransform {
+        flip,
+        rotation_degrees,
+    };
+    transform
+        .validate()
+        .map_err(|e| CommandError::invalid_argument(e.to_string()))?;
+
+    crate::platform::orientation::set_orientation(&device_id, transform);
+    log::info!("Frame orientation for {device_id} set to {transform:?}");
+    Ok(transform)
+}
+
+/// Capture one depth frame (Z16) from a depth camera node
+///
+/// The returned frame has format `"Z16"` with 16-bit little-endian samples
+/// and `metadata.depth_units` in meters per unit; it saves as a 16-bit
+/// grayscale PNG. Depth nodes are opened directly rather than through the
+/// shared camera registry. Currently Linux (V4L2) only.
+///
+/// # Errors
+/// Returns an `Err` if a dimension is zero, the device is not a Z16 depth
+/// node, the platform has no depth support, or the capture fails.
+#[command]
+pub async fn capture_depth_frame(
+    device_id: String,
+    width: u32,
+    height: u32,
+) -> Result<CameraFrame, CommandError> {
+    if width == 0 || height == 0 {
+        return Err(CommandError::invalid_argument(format!(
+            "Depth frame size must be non-zero, got {width}x{height}"
+        )));
+    }
+
+    let frame = tokio::task::spawn_blocking(move || {
+        crate::platform::CameraSystem::capture_depth_frame(&device_id, width, height)
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
+    Ok(frame)
+}
+
+/// Get capture statistics for a camera
+///
+/// # Errors
+/// Returns an `Err` if the camera mutex is poisoned or the blocking task fails
+/// to join (only when an active camera exists for `device_id`).
+#[command]
+pub async fn get_capture_stats(device_id: String) -> Result<CaptureStats, CommandError> {
+    let (queued_frames, dropped_frames) = frame_queue_stats(&device_id);
+    let (capture_resolution, preview_resolution) = preview_resolutions(&device_id);
+    let (reordered_frames, late_frames) = reorder_stats(&device_id);
+    if let Some(camera) = get_existing_camera(&device_id).await {
+        let camera_clone = camera.clone();
+        let device_id_clone = device_id.clone();
+        let stats = tokio::task::spawn_blocking(move || {
+            let camera_guard = camera_clone
+                .lock()
+                .map_err(|_| CommandError::system("Mutex poisoned"))?;
+            let is_active = camera_guard.is_available();
+            let device_id_opt = camera_guard.get_device_id();
+            let paused = device_id_opt.is_some_and(crate::platform::is_camera_paused);
+
+            Ok::<CaptureStats, CommandError>(CaptureStats {
+                device_id: device_id_clone,
+                is_active,
+                device_info: device_id_opt.map(std::string::ToString::to_string),
+                paused,
+                queued_frames,
+                dropped_frames,
+                capture_resolution,
+                preview_resolution,
+                reordered_frames,
+                late_frames,
+            })
+        })
+        .await
+        .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
+        Ok(stats)
+    } else {
+        Ok(CaptureStats {
+            device_id: device_id.clone(),
+            is_active: false,
+            device_info: None,
+            paused: false,
+            queued_frames,
+            dropped_frames,
+            capture_resolution,
+            preview_resolution,
+            reordered_frames,
+            late_frames,
+        })
+    }
+}
+
+/// Get cumulative capture statistics for a camera since it was opened
+///
+/// Unlike [`get_capture_stats`], which describes the moment, this adds up
+/// frames, bytes, capture latency and errors by code over the whole
+/// session. A released camera's totals stay readable, with `is_open` false,
+/// until it is opened again.
+///
+/// # Errors
+/// Returns a `NOT_FOUND` error if the camera has not been opened.
+#[command]
+pub async fn get_session_metrics(device_id: String) -> Result<SessionMetricsReport, CommandError> {
+    let key = crate::platform::CameraSystem::resolve_device_id(&device_id);
+    crate::platform::session_metrics::session_report(&key).ok_or_else(|| {
+        CommandError::not_found(format!("No capture session for device {device_id}"))
+    })
+}
+
+/// Reset a camera's session statistics, returning the totals up to now
+///
+/// An open camera's counters and uptime restart from zero; a released
+/// camera's totals are discarded.
+///
+/// # Errors
+/// Returns a `NOT_FOUND` error if the camera has not been opened.
+#[command]
+pub async fn reset_session_metrics(
+    device_id: String,
+) -> Result<SessionMetricsReport, CommandError> {
+    let key = crate::platform::CameraSystem::resolve_device_id(&device_id);
+    crate::platform::session_metrics::reset_session(&key).ok_or_else(|| {
+        CommandError::not_found(format!("No capture session for device {device_id}"))
+    })
+}
+
+/// Save captured frame to disk as a proper image file
+/// Supports PNG (lossless) based on file extension
+///
+/// # Errors
+/// Returns an `Err` if the frame data cannot be converted into an image or if
+/// writing the image file fails (including a blocking task join failure).
+#[command]
+pub async fn save_frame_to_disk(
+    frame: CameraFrame,
+    file_path: String,
+) -> Result<String, CommandError> {
+    log::info!("Saving frame {} to disk: {}", frame.id, file_path);
+
+    // Determine format from extension, default to PNG
+    let format = if file_path.to_lowercase().ends_with(".jpg")
+        || file_path.to_lowercase().ends_with(".jpeg")
+    {
+        image::ImageFormat::Jpeg
+    } else {
+        image::ImageFormat::Png
+    };
+
+    // Convert frame data to proper image format
+    let dynamic_img = frame_to_image(frame, format)?;
+
+    // Save in spawn_blocking to avoid blocking async runtime
+    let file_path_clone = file_path.clone();
+    match tokio::task::spawn_blocking(move || {
+        dynamic_img.save_with_format(&file_path_clone, format)
+    })
+    .await
+    {
+        Ok(Ok(())) => {
+            log::info!("Frame saved successfully to: {file_path}");
+            Ok(format!("Frame saved to {file_path}"))
+        }
+        Ok(Err(e)) => {
+            log::error!("Failed to save frame: {e}");
+            Err(CommandError::new(
+                "IO_ERROR",
+                format!("Failed to save frame: {e}"),
+            ))
+        }
+        Err(e) => {
+            log::error!("Task join error: {e}");
+            Err(CommandError::system("Failed to execute save task"))
+        }
+    }
+}
+
+/// Save frame with compression for smaller file sizes
+///
+/// # Errors
+/// Returns an `Err` if the frame data cannot be converted into an image, if the
 /// output file cannot be created, or if encoding/writing the compressed image
 /// fails (including a blocking task join failure).
 #[command]
-pub async fn save_frame_compressed(
-    frame: CameraFrame,
-    file_path: String,
-    quality: Option<u8>,
-) -> Result<String, String> {
-    log::info!(
-        "Saving compressed frame {} to disk: {}",
-        frame.id,
-        file_path
-    );
+pub async fn save_frame_compressed(
+    frame: CameraFrame,
+    file_path: String,
+    quality: Option<u8>,
+) -> Result<String, CommandError> {
+    log::info!(
+        "Saving compressed frame {} to disk: {}",
+        frame.id,
+        file_path
+    );
+
+    let quality = quality.unwrap_or(85); // Default JPEG quality
+
+    // Convert frame to image and compress
+    let dynamic_img = frame_to_image(frame, image::ImageFormat::Jpeg)?;
+
+    // Save with compression in a spawn_blocking task
+    let file_path_clone = file_path.clone();
+    match tokio::task::spawn_blocking(move || {
+        let mut file = File::create(&file_path_clone)?;
+        let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut file, quality);
+        dynamic_img.write_with_encoder(encoder)
+    })
+    .await
+    {
+        Ok(Ok(())) => {
+            log::info!("Compressed frame saved to: {file_path}");
+            Ok(format!("Compressed frame saved to {file_path}"))
+        }
+        Ok(Err(e)) => {
+            log::error!("Failed to save compressed frame: {e}");
+            Err(CommandError::new(
+                "IO_ERROR",
+                format!("Failed to save compressed frame: {e}"),
+            ))
+        }
+        Err(e) => {
+            log::error!("Task join error: {e}");
+            Err(CommandError::system("Failed to execute save task"))
+        }
+    }
+}
+
+/// Save a frame under an automatically generated name
+///
+/// `template` may contain `{timestamp}`, `{date}`, `{device}` and `{seq}`
+/// placeholders (and `/` for subdirectories); the extension is added from
+/// the storage config's `default_format`. `dir` and `template` default to the
+/// storage config's `output_directory` and `file_name_template`, taking the
+/// frame's camera overrides into account. The
+/// directory is created if needed. `{seq}` is zero-padded and increases
+/// monotonically per directory, skipping names that already exist.
+///
+/// Returns the path the frame was written to.
+///
+/// # Errors
+/// Returns an `Err` if the frame data cannot be converted into an image, if
+/// the directory cannot be created, or if encoding/writing the image fails
+/// (including a blocking task join failure).
+#[command]
+pub async fn save_frame_auto(
+    mut frame: CameraFrame,
+    dir: Option<String>,
+    template: Option<String>,
+) -> Result<String, CommandError> {
+    let storage = crate::commands::config::effective_config(Some(&frame.device_id)).storage;
+    let dir = PathBuf::from(dir.unwrap_or(storage.output_directory));
+    let template = template.unwrap_or(storage.file_name_template);
+    let (format, extension) = match storage.default_format.to_lowercase().as_str() {
+        "png" => (image::ImageFormat::Png, "png"),
+        "bmp" => (image::ImageFormat::Bmp, "bmp"),
+        _ => (image::ImageFormat::Jpeg, "jpg"),
+    };
+    let quality = storage.jpeg_quality;
+
+    let image_frame = CameraFrame {
+        data: std::mem::take(&mut frame.data),
+        ..frame.clone()
+    };
+    let dynamic_img = frame_to_image(image_frame, format)?;
+
+    let path = tokio::task::spawn_blocking(move || {
+        let path = next_auto_path(&dir, &template, &frame, extension)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                CommandError::new(
+                    "IO_ERROR",
+                    format!("Failed to create output directory: {e}"),
+                )
+            })?;
+        }
+
+        let written = if format == image::ImageFormat::Jpeg {
+            File::create(&path)
+                .map_err(image::ImageError::IoError)
+                .and_then(|mut file| {
+                    let encoder =
+                        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut file, quality);
+                    dynamic_img.write_with_encoder(encoder)
+                })
+        } else {
+            dynamic_img.save_with_format(&path, format)
+        };
+        written.map_err(|e| CommandError::new("IO_ERROR", format!("Failed to save frame: {e}")))?;
+        Ok::<_, CommandError>(path)
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+    .inspect_err(|e| log::error!("{e}"))?;
+
+    let path = path.to_string_lossy().into_owned();
+    log::info!("Frame saved to: {path}");
+    Ok(path)
+}
+
+/// Build an encodable image from a frame
+///
+/// RGBA8 frames keep their alpha channel for formats that support it (PNG,
+/// BMP); JPEG has no alpha, so it is stripped there. High bit depth frames
+/// (P010, RGB16) are saved as 16-bit PNGs and rounded to 8 bits otherwise;
+/// depth (Z16) frames become 16-bit grayscale PNGs holding the raw samples.
+fn frame_to_image(
+    frame: CameraFrame,
+    format: image::ImageFormat,
+) -> Result<image::DynamicImage, CommandError> {
+    if frame.is_depth() && format == image::ImageFormat::Png {
+        let pixels = frame.width as usize * frame.height as usize;
+        let samples = frame
+            .data
+            .get(..pixels * 2)
+            .ok_or_else(|| CommandError::invalid_argument("Depth frame buffer is too small"))?
+            .chunks_exact(2)
+            .map(|sample| u16::from_le_bytes([sample[0], sample[1]]))
+            .collect();
+        let img =
+            image::ImageBuffer::<image::Luma<u16>, _>::from_vec(frame.width, frame.height, samples)
+                .ok_or_else(|| {
+                    CommandError::invalid_argument("Failed to create image from frame data")
+                })?;
+        return Ok(image::DynamicImage::ImageLuma16(img));
+    }
+
+    if frame.is_high_bit_depth() {
+        if format == image::ImageFormat::Png {
+            let samples = frame.to_rgb16()?;
+            let img = image::ImageBuffer::<image::Rgb<u16>, _>::from_vec(
+                frame.width,
+                frame.height,
+                samples,
+            )
+            .ok_or_else(|| {
+                CommandError::invalid_argument("Failed to create image from frame data")
+            })?;
+            return Ok(image::DynamicImage::ImageRgb16(img));
+        }
+        let rgb = frame.to_rgb8()?;
+        let img = image::RgbImage::from_vec(frame.width, frame.height, rgb).ok_or_else(|| {
+            CommandError::invalid_argument("Failed to create image from frame data")
+        })?;
+        return Ok(image::DynamicImage::ImageRgb8(img));
+    }
+
+    if frame.is_rgba() {
+        let img =
+            image::RgbaImage::from_vec(frame.width, frame.height, frame.data).ok_or_else(|| {
+                CommandError::invalid_argument("Failed to create image from frame data")
+            })?;
+        let img = image::DynamicImage::ImageRgba8(img);
+        return Ok(if format == image::ImageFormat::Jpeg {
+            image::DynamicImage::ImageRgb8(img.to_rgb8())
+        } else {
+            img
+        });
+    }
+
+    let img = image::RgbImage::from_vec(frame.width, frame.height, frame.data)
+        .ok_or_else(|| CommandError::invalid_argument("Failed to create image from frame data"))?;
+    Ok(image::DynamicImage::ImageRgb8(img))
+}
+
+// Last `{seq}` handed out per output directory
+static AUTO_NAME_SEQUENCE: LazyLock<StdMutex<HashMap<PathBuf, u64>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+/// Reserve the next free path for `template` in `dir`
+fn next_auto_path(
+    dir: &Path,
+    template: &str,
+    frame: &CameraFrame,
+    extension: &str,
+) -> Result<PathBuf, CommandError> {
+    let mut sequences = AUTO_NAME_SEQUENCE
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?;
+    let seq = sequences.entry(dir.to_path_buf()).or_insert(0);
+
+    loop {
+        *seq += 1;
+        let path = dir.join(format!(
+            "{}.{extension}",
+            render_file_name(template, frame, *seq)
+        ));
+        // Without `{seq}` every attempt renders the same name; overwrite it
+        if !path.exists() || !template.contains("{seq}") {
+            return Ok(path);
+        }
+    }
+}
+
+/// Expand the naming placeholders in `template` for `frame`
+fn render_file_name(template: &str, frame: &CameraFrame, seq: u64) -> String {
+    // Stable ids can contain characters that are invalid in file names
+    let device: String = frame
+        .device_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    template
+        .replace(
+            "{timestamp}",
+            &frame.timestamp.format("%Y%m%dT%H%M%S%.3fZ").to_string(),
+        )
+        .replace("{date}", &frame.timestamp.format("%Y-%m-%d").to_string())
+        .replace("{device}", &device)
+        .replace(
+            "{seq}",
+            &format!("{seq:0width$}", width = FILE_NAME_SEQ_WIDTH),
+        )
+}
+
+/// Directory temporary captures are written to
+///
+/// The storage config's `temp_dir`, or the system temp directory when unset.
+pub fn temp_capture_dir() -> PathBuf {
+    crate::commands::config::current_storage_config()
+        .temp_dir
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Reserve a unique path for a temporary capture with `extension`
+///
+/// The file name starts with [`TEMP_CAPTURE_PREFIX`] so
+/// [`cleanup_temp_captures`] can recognise it. The directory is created if
+/// needed.
+///
+/// # Errors
+/// Returns an `IO_ERROR` if the temp directory cannot be created. There is
+/// no fallback to the system temp directory: a configured `temp_dir` that is
+/// unwritable is reported rather than silently bypassed.
+pub fn temp_capture_path(extension: &str) -> Result<PathBuf, CommandError> {
+    let dir = temp_capture_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| {
+        CommandError::new(
+            "IO_ERROR",
+            format!("Failed to create temp directory {}: {e}", dir.display()),
+        )
+    })?;
+    Ok(dir.join(format!(
+        "{TEMP_CAPTURE_PREFIX}{}.{extension}",
+        uuid::Uuid::new_v4()
+    )))
+}
+
+/// Outcome of [`cleanup_temp_captures`]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TempCleanupResult {
+    /// Directory that was scanned.
+    pub directory: String,
+    /// Temporary captures deleted.
+    pub removed: u32,
+    /// Stale temporary captures that could not be deleted.
+    pub failed: u32,
+}
+
+/// Delete temporary captures older than `max_age_secs`
+///
+/// Only regular files directly in [`temp_capture_dir`] whose names start with
+/// [`TEMP_CAPTURE_PREFIX`] are considered; age is taken from the modification
+/// time. `max_age_secs` defaults to the storage config's `temp_max_age_secs`.
+/// A missing temp directory counts as already clean. Files that cannot be
+/// deleted are logged and counted in `failed`.
+///
+/// # Errors
+/// Returns an `IO_ERROR` if the temp directory exists but cannot be read,
+/// or an `Err` on a blocking task join failure.
+#[command]
+pub async fn cleanup_temp_captures(
+    max_age_secs: Option<u64>,
+) -> Result<TempCleanupResult, CommandError> {
+    let storage = crate::commands::config::current_storage_config();
+    let max_age = Duration::from_secs(max_age_secs.unwrap_or(storage.temp_max_age_secs));
+    let dir = storage.temp_dir.unwrap_or_else(std::env::temp_dir);
+
+    let directory = dir.to_string_lossy().into_owned();
+
+    let (removed, failed) =
+        tokio::task::spawn_blocking(move || remove_stale_temp_captures(&dir, max_age))
+            .await
+            .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
+    if removed > 0 || failed > 0 {
+        log::info!("Temp cleanup in {directory}: removed {removed}, failed {failed}");
+    }
+
+    Ok(TempCleanupResult {
+        directory,
+        removed,
+        failed,
+    })
+}
+
+/// Delete prefixed files in `dir` last modified more than `max_age` ago
+///
+/// Returns the number of files removed and the number that failed.
+fn remove_stale_temp_captures(dir: &Path, max_age: Duration) -> Result<(u32, u32), CommandError> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((0, 0)),
+        Err(e) => {
+            return Err(CommandError::new(
+                "IO_ERROR",
+                format!("Failed to read temp directory {}: {e}", dir.display()),
+            ))
+        }
+    };
+
+    let now = std::time::SystemTime::now();
+    let (mut removed, mut failed) = (0, 0);
+    for entry in entries.flatten() {
+        if !entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(TEMP_CAPTURE_PREFIX)
+        {
+            continue;
+        }
+        // symlink_metadata so a prefixed symlink is never followed
+        let Ok(metadata) = std::fs::symlink_metadata(entry.path()) else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .unwrap_or_default();
+        if age < max_age {
+            continue;
+        }
+        match std::fs::remove_file(entry.path()) {
+            Ok(()) => removed += 1,
+            Err(e) => {
+                log::warn!("Failed to remove {}: {e}", entry.path().display());
+                failed += 1;
+            }
+        }
+    }
+    Ok((removed, failed))
+}
+
+/// Progress of a time-lapse, emitted as `crabcamera://timelapse-progress`
+/// after every shot
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TimelapseStatus {
+    /// Device being captured.
+    pub device_id: String,
+    /// Frames captured and saved so far.
+    pub captured: u32,
+    /// Shots that failed to capture or save.
+    pub failures: u32,
+    /// Total shots requested, `None` to run until stopped.
+    pub count: Option<u32>,
+    /// Path of the most recently saved frame.
+    pub last_path: Option<String>,
+    /// Error from the most recent failed shot.
+    pub last_error: Option<String>,
+    /// When the next shot is scheduled, `None` once finished.
+    pub next_capture_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Whether the time-lapse is still running.
+    pub running: bool,
+}
+
+struct TimelapseHandle {
+    cancel: CancellationToken,
+    status: Arc<StdMutex<TimelapseStatus>>,
+}
+
+impl TimelapseHandle {
+    /// Whether shots are still scheduled
+    fn is_running(&self) -> bool {
+        self.status.lock().is_ok_and(|status| status.running)
+    }
+}
+
+// Time-lapses by device id; finished ones stay until the next start or
+// `clear_timelapse_status`
+static TIMELAPSES: LazyLock<StdMutex<HashMap<String, TimelapseHandle>>> =
+    LazyLock::new(|| StdMutex::new(HashMap::new()));
+
+/// Start capturing a frame every `interval_secs` in the background
+///
+/// Each frame is saved with [`save_frame_auto`] using `dir` and `template`.
+/// Runs `count` shots, or until [`stop_timelapse`] when `count` is `None`.
+/// For intervals under 30 seconds the camera stays open between shots;
+/// longer intervals release it after each shot to save power. A camera that
+/// was already open when the time-lapse started is never released. Failed
+/// shots are counted and the schedule continues. Each successful shot is also
+/// reported as a [`CaptureProgress`] without an operation id.
+///
+/// # Errors
+/// Returns an `Err` if `interval_secs` is not a positive number, if `count`
+/// is `0`, or if a time-lapse is already running for the device.
+#[command]
+pub async fn start_timelapse<R: Runtime>(
+    device_id: String,
+    interval_secs: f64,
+    count: Option<u32>,
+    dir: Option<String>,
+    template: Option<String>,
+    app: tauri::AppHandle<R>,
+) -> Result<String, CommandError> {
+    spawn_timelapse(
+        device_id,
+        interval_secs,
+        count,
+        dir,
+        template,
+        move |status| {
+            let _ = app.emit("crabcamera://timelapse-progress", status);
+        },
+    )
+}
+
+/// Stop a running time-lapse and return its final progress
+///
+/// The final progress stays available from [`get_timelapse_status`].
+///
+/// # Errors
+/// Returns a `NOT_FOUND` error if no time-lapse is running for the device,
+/// including one that already finished.
+#[command]
+pub async fn stop_timelapse(device_id: String) -> Result<TimelapseStatus, CommandError> {
+    let (cancel, status) = TIMELAPSES
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?
+        .get(&device_id)
+        .filter(|handle| handle.is_running())
+        .map(|handle| (handle.cancel.clone(), handle.status.clone()))
+        .ok_or_else(|| {
+            CommandError::not_found(format!("No time-lapse running for device: {device_id}"))
+        })?;
+
+    cancel.cancel();
+    let mut status = status
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?;
+    status.running = false;
+    status.next_capture_at = None;
+    log::info!("Time-lapse stopped for device {device_id}");
+    Ok(status.clone())
+}
 
-    let quality = quality.unwrap_or(85); // Default JPEG quality
+/// Get the progress of a time-lapse
+///
+/// After the time-lapse finishes or is stopped, its final progress (with
+/// `running` false) is returned until the next [`start_timelapse`] for the
+/// device or [`clear_timelapse_status`].
+///
+/// # Errors
+/// Returns a `NOT_FOUND` error if no time-lapse was started for the device
+/// since its status was last cleared.
+#[command]
+pub async fn get_timelapse_status(device_id: String) -> Result<TimelapseStatus, CommandError> {
+    let timelapses = TIMELAPSES
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?;
+    let handle = timelapses
+        .get(&device_id)
+        .ok_or_else(|| CommandError::not_found(format!("No time-lapse for device: {device_id}")))?;
+    let status = handle
+        .status
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?
+        .clone();
+    Ok(status)
+}
 
-    // Convert frame to image and compress
-    let img = image::RgbImage::from_vec(frame.width, frame.height, frame.data)
-        .ok_or_else(|| "Failed to create image from frame data".to_string())?;
+/// Forget the final progress of a finished time-lapse and return it
+///
+/// # Errors
+/// Returns a `NOT_FOUND` error if there is no time-lapse status for the
+/// device, or an `INVALID_ARGUMENT` error if the time-lapse is still running.
+#[command]
+pub async fn clear_timelapse_status(device_id: String) -> Result<TimelapseStatus, CommandError> {
+    let mut timelapses = TIMELAPSES
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?;
+    let handle = timelapses
+        .get(&device_id)
+        .ok_or_else(|| CommandError::not_found(format!("No time-lapse for device: {device_id}")))?;
+    if handle.is_running() {
+        return Err(CommandError::invalid_argument(format!(
+            "Time-lapse still running for device: {device_id}"
+        )));
+    }
+    let status = handle
+        .status
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?
+        .clone();
+    timelapses.remove(&device_id);
+    Ok(status)
+}
+
+/// Spawn the time-lapse task, reporting progress to `on_progress`
+fn spawn_timelapse<F>(
+    device_id: String,
+    interval_secs: f64,
+    count: Option<u32>,
+    dir: Option<String>,
+    template: Option<String>,
+    on_progress: F,
+) -> Result<String, CommandError>
+where
+    F: Fn(&TimelapseStatus) + Send + 'static,
+{
+    if !interval_secs.is_finite() || interval_secs <= 0.0 {
+        return Err(CommandError::invalid_argument(format!(
+            "Invalid time-lapse interval: {interval_secs}s"
+        )));
+    }
+    if count == Some(0) {
+        return Err(CommandError::invalid_argument(
+            "Time-lapse count must be at least 1",
+        ));
+    }
 
-    let dynamic_img = image::DynamicImage::ImageRgb8(img);
+    let interval = Duration::from_secs_f64(interval_secs);
+    let keep_warm = interval_secs < TIMELAPSE_KEEP_WARM_SECS;
+    let cancel = CancellationToken::new();
+    let status = Arc::new(StdMutex::new(TimelapseStatus {
+        device_id: device_id.clone(),
+        captured: 0,
+        failures: 0,
+        count,
+        last_path: None,
+        last_error: None,
+        next_capture_at: Some(chrono::Utc::now()),
+        running: true,
+    }));
 
-    // Save with compression in a spawn_blocking task
-    let file_path_clone = file_path.clone();
-    match tokio::task::spawn_blocking(move || {
-        let mut file = File::create(&file_path_clone)?;
-        let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut file, quality);
-        dynamic_img.write_with_encoder(encoder)
-    })
-    .await
     {
-        Ok(Ok(())) => {
-            log::info!("Compressed frame saved to: {file_path}");
-            Ok(format!("Compressed frame saved to {file_path}"))
+        let mut timelapses = TIMELAPSES
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+        if timelapses
+            .get(&device_id)
+            .is_some_and(TimelapseHandle::is_running)
+        {
+            return Err(CommandError::invalid_argument(format!(
+                "Time-lapse already running for device: {device_id}"
+            )));
         }
-        Ok(Err(e)) => {
-            log::error!("Failed to save compressed frame: {e}");
-            Err(format!("Failed to save compressed frame: {e}"))
+        // Replaces the final status of a finished one
+        timelapses.insert(
+            device_id.clone(),
+            TimelapseHandle {
+                cancel: cancel.clone(),
+                status: status.clone(),
+            },
+        );
+    }
+
+    log::info!(
+        "Time-lapse started for device {device_id}: every {interval_secs}s, count {count:?}, keep warm {keep_warm}"
+    );
+
+    let task_device_id = device_id.clone();
+    tokio::spawn(async move {
+        let device_id = task_device_id;
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut shots = 0u32;
+        // Leave a camera opened by someone else (e.g. a preview) open
+        let owns_camera = get_existing_camera(&device_id).await.is_none();
+
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+
+            let result = match capture_with_reconnect(
+                device_id.clone(),
+                CameraFormat::standard(),
+                CAPTURE_RETRY_COUNT,
+            )
+            .await
+            {
+                Ok(frame) => {
+                    report_progress(
+                        None,
+                        &device_id,
+                        shots + 1,
+                        count,
+                        CaptureStage::Capturing,
+                        Some(&frame),
+                    );
+                    save_frame_auto(frame, dir.clone(), template.clone())
+                        .await
+                        .map_err(|e| e.message)
+                }
+                Err(e) => Err(format!("Failed to capture frame: {e}")),
+            };
+            if owns_camera && !keep_warm {
+                let _ = crate::platform::release_camera(&device_id).await;
+            }
+            shots += 1;
+
+            let finished = count.is_some_and(|count| shots >= count) || cancel.is_cancelled();
+            let snapshot = {
+                let Ok(mut status) = status.lock() else {
+                    break;
+                };
+                match result {
+                    Ok(path) => {
+                        status.captured += 1;
+                        status.last_path = Some(path);
+                    }
+                    Err(e) => {
+                        log::warn!("Time-lapse shot {shots} failed for device {device_id}: {e}");
+                        status.failures += 1;
+                        status.last_error = Some(e);
+                    }
+                }
+                status.running = !finished;
+                status.next_capture_at = (!finished).then(|| {
+                    chrono::Utc::now() + chrono::Duration::from_std(interval).unwrap_or_default()
+                });
+                status.clone()
+            };
+            on_progress(&snapshot);
+
+            if finished {
+                break;
+            }
         }
-        Err(e) => {
-            log::error!("Task join error: {e}");
-            Err("Failed to execute save task".to_string())
+
+        // The entry stays, so the final status can still be read
+        if owns_camera && keep_warm {
+            let _ = crate::platform::release_camera(&device_id).await;
         }
-    }
+        log::info!("Time-lapse finished for device {device_id}");
+    });
+
+    Ok(format!("Time-lapse started for device {device_id}"))
 }
 
 // Helper functions (moved to platform::manager)
@@ -592,8 +2391,30 @@ pub struct CaptureStats {
     pub device_id: String,
     /// Whether the device is currently streaming.
     pub is_active: bool,
+    /// Whether frame delivery is paused by [`pause_camera`].
+    #[serde(default)]
+    pub paused: bool,
     /// Detailed device description (name, format, etc.).
     pub device_info: Option<String>,
+    /// Frames buffered for [`get_queued_frames`] and not yet taken.
+    pub queued_frames: usize,
+    /// Frames discarded because the frame queue was full.
+    pub dropped_frames: u64,
+    /// Resolution the camera delivers, from the last frame seen by the
+    /// frame callback.
+    #[serde(default)]
+    pub capture_resolution: Option<(u32, u32)>,
+    /// Resolution of frames buffered for [`get_queued_frames`] after
+    /// `advanced.preview_max_dim` downscaling.
+    #[serde(default)]
+    pub preview_resolution: Option<(u32, u32)>,
+    /// Frames moved back into timestamp order by the reorder buffer.
+    #[serde(default)]
+    pub reordered_frames: u64,
+    /// Frames dropped by the reorder buffer because they arrived after a
+    /// later-stamped frame had already been queued.
+    #[serde(default)]
+    pub late_frames: u64,
 }
 
 #[cfg(test)]
@@ -627,12 +2448,12 @@ mod tests {
     async fn test_capture_single_photo_and_sequence_with_mock() {
         enable_mock_camera();
 
-        let single = capture_single_photo(Some("0".to_string()), None)
+        let single = capture_single_photo(Some("0".to_string()), None, None)
             .await
             .expect("single capture should work with mock");
         assert_eq!(single.device_id, "0");
 
-        let seq = capture_photo_sequence("0".to_string(), 2, 0, None)
+        let seq = capture_photo_sequence("0".to_string(), 2, 0, None, None)
             .await
             .expect("sequence capture should work with mock");
         assert_eq!(seq.len(), 2);
@@ -640,6 +2461,184 @@ mod tests {
         std::env::remove_var("CRABCAMERA_USE_MOCK");
     }
 
+    #[tokio::test]
+    async fn test_stream_photo_sequence_emits_each_frame() {
+        enable_mock_camera();
+
+        let mut events = Vec::new();
+        let summary = stream_photo_sequence("0".to_string(), 3, 0, None, None, |event| {
+            events.push((event.index, event.total, event.frame.device_id.clone()));
+        })
+        .await
+        .expect("streamed sequence should work with mock");
+
+        assert_eq!(summary.frames_captured, 3);
+        assert!(!summary.cancelled);
+        assert_eq!(summary.total, 3);
+        assert_eq!(
+            events,
+            (0..3).map(|i| (i, 3, "0".to_string())).collect::<Vec<_>>()
+        );
+
+        let invalid = stream_photo_sequence("0".to_string(), 0, 0, None, None, |_| {
+            panic!("no frame expected for an invalid count");
+        })
+        .await
+        .expect_err("count 0 is rejected");
+        assert_eq!(invalid.code, CommandError::INVALID_ARGUMENT);
+
+        std::env::remove_var("CRABCAMERA_USE_MOCK");
+    }
+
+    #[tokio::test]
+    async fn test_armed_capture_fires_until_disarmed() {
+        enable_mock_camera();
+
+        let session = arm_capture("armed-test".to_string(), None)
+            .await
+            .expect("mock arms");
+        for _ in 0..2 {
+            let shot = fire_capture(session.clone()).await.expect("trigger fires");
+            assert_eq!(shot.frame.device_id, "armed-test");
+            assert!(shot.latency_ms >= 0.0);
+            assert!((shot.frame_interval_ms - 1000.0 / 30.0).abs() < 1e-6);
+        }
+
+        assert!(disarm_capture(session.clone()).await.unwrap());
+        assert!(!disarm_capture(session.clone()).await.unwrap());
+        let err = fire_capture(session).await.expect_err("disarmed");
+        assert_eq!(err.code, CommandError::NOT_FOUND);
+
+        std::env::remove_var("CRABCAMERA_USE_MOCK");
+    }
+
+    #[tokio::test]
+    async fn test_capture_gif_with_mock() {
+        use image::AnimationDecoder;
+
+        enable_mock_camera();
+
+        let gif = capture_gif("gif-test".to_string(), 3, 0, 64, None, None)
+            .await
+            .expect("GIF capture should work with mock");
+        let decoder = image::codecs::gif::GifDecoder::new(std::io::Cursor::new(gif)).unwrap();
+        let frames = decoder.into_frames().collect_frames().unwrap();
+        assert_eq!(frames.len(), 3);
+        let (width, height) = frames[0].buffer().dimensions();
+        assert!(width.max(height) <= 64);
+
+        let invalid = capture_gif("gif-test".to_string(), 3, 0, 0, None, Some(false))
+            .await
+            .expect_err("max_dim 0 is rejected");
+        assert_eq!(invalid.code, CommandError::INVALID_ARGUMENT);
+        let too_long = capture_gif(
+            "gif-test".to_string(),
+            GIF_MAX_FRAMES + 1,
+            0,
+            64,
+            None,
+            None,
+        )
+        .await
+        .expect_err("too many frames");
+        assert_eq!(too_long.code, CommandError::INVALID_ARGUMENT);
+
+        std::env::remove_var("CRABCAMERA_USE_MOCK");
+    }
+
+    #[tokio::test]
+    async fn test_cancel_operation_stops_sequence_midway() {
+        enable_mock_camera();
+
+        let sequence = tokio::spawn(capture_photo_sequence(
+            "0".to_string(),
+            10,
+            100,
+            None,
+            Some("cancel-test".to_string()),
+        ));
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert!(cancel_operation("cancel-test".to_string()).await.unwrap());
+
+        let frames = sequence
+            .await
+            .unwrap()
+            .expect("cancelled sequence returns partial frames");
+        assert!(!frames.is_empty());
+        assert!(frames.len() < 10);
+
+        // Finished operations are unregistered
+        assert!(!cancel_operation("cancel-test".to_string()).await.unwrap());
+
+        std::env::remove_var("CRABCAMERA_USE_MOCK");
+    }
+
+    #[tokio::test]
+    async fn test_sequence_reports_progress_with_thumbnails() {
+        enable_mock_camera();
+
+        let events = Arc::new(StdMutex::new(Vec::new()));
+        let sink = events.clone();
+        set_progress_listener(Box::new(move |progress| {
+            if progress.operation_id.as_deref() == Some("progress-test") {
+                sink.lock().unwrap().push(progress.clone());
+            }
+        }));
+
+        let frames = capture_photo_sequence(
+            "0".to_string(),
+            3,
+            0,
+            None,
+            Some("progress-test".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(frames.len(), 3);
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            events.iter().map(|p| p.current).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        for progress in events.iter() {
+            assert_eq!(progress.total, Some(3));
+            assert_eq!(progress.stage, CaptureStage::Capturing);
+            let thumbnail = progress.frame.as_ref().unwrap();
+            assert!(thumbnail.width.max(thumbnail.height) <= PROGRESS_THUMBNAIL_MAX_DIM);
+        }
+
+        std::env::remove_var("CRABCAMERA_USE_MOCK");
+    }
+
+    #[test]
+    fn test_format_at_validates_index() {
+        let formats = vec![
+            CameraFormat::new(1280, 720, 30.0).with_format_type("MJPG".to_string()),
+            CameraFormat::new(1280, 720, 30.0).with_format_type("YUYV".to_string()),
+            CameraFormat::new(640, 480, 60.0),
+        ];
+        let format = format_at(formats.clone(), 1).unwrap();
+        assert_eq!(format.format_type, "YUYV");
+        assert_eq!(format.width, 1280);
+
+        let err = format_at(formats, 3).unwrap_err();
+        assert_eq!(err.code, CommandError::INVALID_ARGUMENT);
+        assert!(err.message.contains("3 formats"));
+    }
+
+    #[test]
+    fn test_operation_ids_are_unique_while_running() {
+        let first = Operation::register(Some("dup-test".to_string())).unwrap();
+        let err = Operation::register(Some("dup-test".to_string()))
+            .err()
+            .expect("duplicate id is rejected");
+        assert_eq!(err.code, CommandError::INVALID_ARGUMENT);
+        drop(first);
+        assert!(Operation::register(Some("dup-test".to_string())).is_ok());
+        assert!(Operation::register(None).is_ok());
+    }
+
     #[tokio::test]
     async fn test_consolidated_capture_routes_to_correct_mode() {
         enable_mock_camera();
@@ -648,11 +2647,20 @@ mod tests {
             device_id: Some("0".to_string()),
             format: None,
             mode: CaptureMode::Single,
+            warmup_frames: Some(0),
+            timestamp_overlay: true,
+            auto_orient: true,
+            autofocus: true,
+            operation_id: None,
         })
         .await
         .expect("consolidated single capture should work");
         assert_eq!(single.frames.len(), 1);
         assert_eq!(single.mode, "single");
+        assert_eq!(
+            single.frames[0].metadata.applied_rotation, None,
+            "mock reports no sensor rotation, so auto_orient leaves frames alone"
+        );
 
         let seq = capture(CaptureOptions {
             device_id: Some("0".to_string()),
@@ -661,6 +2669,11 @@ mod tests {
                 count: 3,
                 interval_ms: 0,
             },
+            warmup_frames: None,
+            timestamp_overlay: false,
+            auto_orient: false,
+            autofocus: false,
+            operation_id: None,
         })
         .await
         .expect("consolidated sequence capture should work");
@@ -670,11 +2683,19 @@ mod tests {
         std::env::remove_var("CRABCAMERA_USE_MOCK");
     }
 
+    #[tokio::test]
+    async fn test_capture_depth_frame_rejects_empty_size() {
+        let err = capture_depth_frame("0".to_string(), 0, 480)
+            .await
+            .expect_err("zero width is rejected");
+        assert_eq!(err.code, CommandError::INVALID_ARGUMENT);
+    }
+
     #[tokio::test]
     async fn test_capture_sequence_validation_and_preview_controls() {
         enable_mock_camera();
 
-        let invalid = capture_photo_sequence("0".to_string(), 0, 0, None).await;
+        let invalid = capture_photo_sequence("0".to_string(), 0, 0, None, None).await;
         assert!(invalid.is_err());
 
         let msg = set_frame_callback("0".to_string(), None)
@@ -682,11 +2703,16 @@ mod tests {
             .expect("set callback should work");
         assert!(msg.contains("Frame callback set"));
 
-        let started = start_camera_preview("0".to_string(), None)
+        let started = start_camera_preview("0".to_string(), None, None)
             .await
             .expect("start preview should work");
         assert!(started.contains("Preview started"));
 
+        let switched = set_camera_format("0".to_string(), CameraFormat::new(640, 480, 30.0))
+            .await
+            .expect("format switch should work");
+        assert!(switched.contains("640x480"));
+
         let stats = get_capture_stats("0".to_string())
             .await
             .expect("stats should be available for active camera");
@@ -722,6 +2748,265 @@ mod tests {
         assert!(missing_stats.is_err() || missing_stats.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_pause_stops_delivery_until_resumed() {
+        enable_mock_camera();
+        let device_id = "pause-resume".to_string();
+        assert_eq!(
+            pause_camera("pause-never-opened".to_string())
+                .await
+                .unwrap_err()
+                .code,
+            "INITIALIZATION_FAILED"
+        );
+
+        let camera = get_or_create_camera(device_id.clone(), CameraFormat::low())
+            .await
+            .expect("mock camera");
+        let paused = pause_camera(device_id.clone()).await.expect("pause");
+        assert!(paused.contains("paused"));
+        assert!(get_capture_stats(device_id.clone()).await.unwrap().paused);
+        let err = camera.lock().unwrap().capture_frame().unwrap_err();
+        assert!(matches!(err, crate::errors::CameraError::StreamError(_)));
+
+        resume_camera(device_id.clone()).await.expect("resume");
+        assert!(!get_capture_stats(device_id.clone()).await.unwrap().paused);
+        camera
+            .lock()
+            .unwrap()
+            .capture_frame()
+            .expect("delivers again");
+
+        release_camera(device_id).await.expect("release");
+        std::env::remove_var("CRABCAMERA_USE_MOCK");
+    }
+
+    #[tokio::test]
+    async fn test_session_metrics_accumulate_until_reset() {
+        enable_mock_camera();
+        let device_id = "session-metrics".to_string();
+        let _ = release_camera(device_id.clone()).await;
+
+        for _ in 0..3 {
+            capture_single_photo(Some(device_id.clone()), None, Some(0))
+                .await
+                .expect("mock capture");
+        }
+        let metrics = get_session_metrics(device_id.clone())
+            .await
+            .expect("session metrics");
+        assert!(metrics.is_open);
+        assert!(metrics.frames_captured >= 3);
+        assert!(metrics.bytes_captured > 0);
+        assert!(metrics.latency_p99_ms >= metrics.latency_p50_ms);
+
+        let before = reset_session_metrics(device_id.clone())
+            .await
+            .expect("reset");
+        assert_eq!(before.frames_captured, metrics.frames_captured);
+        let after = get_session_metrics(device_id.clone()).await.expect("reset");
+        assert_eq!(after.frames_captured, 0);
+
+        release_camera(device_id.clone()).await.expect("release");
+        let released = get_session_metrics(device_id.clone())
+            .await
+            .expect("kept after release");
+        assert!(!released.is_open);
+
+        let missing = get_session_metrics("session-metrics-never-opened".to_string()).await;
+        assert_eq!(missing.unwrap_err().code, CommandError::NOT_FOUND);
+
+        std::env::remove_var("CRABCAMERA_USE_MOCK");
+    }
+
+    #[tokio::test]
+    async fn test_frame_queue_bounds_slow_consumer() {
+        enable_mock_camera();
+        let device_id = "queue-slow".to_string();
+        let capacity = crate::commands::config::current_advanced_config().frame_queue_capacity;
+
+        set_frame_callback(device_id.clone(), Some(CameraFormat::low()))
+            .await
+            .expect("set callback should work");
+        let camera = get_or_create_camera(device_id.clone(), CameraFormat::low())
+            .await
+            .expect("mock camera");
+
+        // Producer outpaces a consumer that takes one frame per three captured.
+        let mut consumed = 0;
+        for i in 0..capacity * 3 {
+            camera
+                .lock()
+                .expect("camera lock")
+                .capture_frame()
+                .expect("mock capture");
+            if i % 3 == 0 {
+                consumed += get_queued_frames(device_id.clone(), Some(1))
+                    .await
+                    .expect("take frame")
+                    .len();
+            }
+        }
+
+        let stats = get_capture_stats(device_id.clone()).await.expect("stats");
+        assert_eq!(stats.queued_frames, capacity);
+        assert_eq!(
+            stats.dropped_frames,
+            (capacity * 3 - consumed - capacity) as u64
+        );
+
+        let rest = get_queued_frames(device_id.clone(), None)
+            .await
+            .expect("drain queue");
+        assert_eq!(rest.len(), capacity);
+        assert!(rest.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+
+        let _ = release_camera(device_id).await;
+        std::env::remove_var("CRABCAMERA_USE_MOCK");
+    }
+
+    #[test]
+    fn test_render_file_name_placeholders() {
+        let mut frame = CameraFrame::new(vec![0; 12], 2, 2, "usb-046d:0825-ABC".to_string());
+        frame.timestamp = chrono::DateTime::parse_from_rfc3339("2026-01-02T03:04:05.678Z")
+            .expect("valid timestamp")
+            .with_timezone(&chrono::Utc);
+
+        assert_eq!(
+            render_file_name("{date}/{device}_{timestamp}_{seq}", &frame, 7),
+            "2026-01-02/usb-046d_0825-ABC_20260102T030405.678Z_000007"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_frame_auto_creates_dir_and_increments_seq() {
+        let dir = std::env::temp_dir().join(format!("crabcamera_auto_{}", uuid::Uuid::new_v4()));
+        let dir_str = dir.to_string_lossy().into_owned();
+        let frame = CameraFrame::new(vec![128; 4 * 4 * 3], 4, 4, "0".to_string());
+
+        let first = save_frame_auto(
+            frame.clone(),
+            Some(dir_str.clone()),
+            Some("shot_{seq}".to_string()),
+        )
+        .await
+        .expect("first save should succeed");
+        let second = save_frame_auto(frame, Some(dir_str), Some("shot_{seq}".to_string()))
+            .await
+            .expect("second save should succeed");
+
+        assert!(Path::new(&first).exists());
+        assert!(Path::new(&second).exists());
+        assert!(first.contains("shot_000001."));
+        assert!(second.contains("shot_000002."));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_timelapse_runs_count_shots_with_mock() {
+        enable_mock_camera();
+
+        let device_id = "timelapse-mock".to_string();
+        let dir = std::env::temp_dir().join(format!("crabcamera_tl_{}", uuid::Uuid::new_v4()));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        spawn_timelapse(
+            device_id.clone(),
+            0.05,
+            Some(3),
+            Some(dir.to_string_lossy().into_owned()),
+            Some("tl_{seq}".to_string()),
+            move |status| {
+                let _ = tx.send(status.clone());
+            },
+        )
+        .expect("time-lapse should start");
+        assert!(spawn_timelapse(device_id.clone(), 0.05, Some(1), None, None, |_| {}).is_err());
+
+        let mut last = None;
+        while let Some(status) = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+            .await
+            .expect("progress")
+        {
+            let done = !status.running;
+            last = Some(status);
+            if done {
+                break;
+            }
+        }
+
+        let last = last.expect("at least one progress event");
+        assert_eq!(last.captured, 3);
+        assert_eq!(last.failures, 0);
+        assert!(last.next_capture_at.is_none());
+        assert!(Path::new(last.last_path.as_deref().expect("saved path")).exists());
+        assert_eq!(
+            stop_timelapse(device_id.clone()).await.unwrap_err().code,
+            CommandError::NOT_FOUND
+        );
+
+        // The final status outlives the run until cleared
+        let kept = get_timelapse_status(device_id.clone())
+            .await
+            .expect("final status is kept");
+        assert!(!kept.running);
+        assert_eq!(kept.captured, 3);
+        assert_eq!(
+            clear_timelapse_status(device_id.clone())
+                .await
+                .expect("clear finished status")
+                .captured,
+            3
+        );
+        assert!(get_timelapse_status(device_id).await.is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+        std::env::remove_var("CRABCAMERA_USE_MOCK");
+    }
+
+    #[tokio::test]
+    async fn test_timelapse_keeps_camera_it_did_not_open() {
+        enable_mock_camera();
+
+        let device_id = "timelapse-shared".to_string();
+        let dir = std::env::temp_dir().join(format!("crabcamera_tl_{}", uuid::Uuid::new_v4()));
+        get_or_create_camera(device_id.clone(), CameraFormat::low())
+            .await
+            .expect("open camera");
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        // A long interval releases the camera after each shot it owns
+        spawn_timelapse(
+            device_id.clone(),
+            60.0,
+            Some(1),
+            Some(dir.to_string_lossy().into_owned()),
+            None,
+            move |status| {
+                let _ = tx.send(status.clone());
+            },
+        )
+        .expect("time-lapse should start");
+        let status = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+            .await
+            .expect("progress")
+            .expect("status");
+        // The per-shot release happens before progress is reported
+        assert!(!status.running);
+        assert!(get_existing_camera(&device_id).await.is_some());
+        let _ = crate::platform::release_camera(&device_id).await;
+        let _ = std::fs::remove_dir_all(&dir);
+        std::env::remove_var("CRABCAMERA_USE_MOCK");
+    }
+
+    #[tokio::test]
+    async fn test_timelapse_rejects_bad_arguments() {
+        assert!(spawn_timelapse("tl-bad".to_string(), 0.0, None, None, None, |_| {}).is_err());
+        assert!(spawn_timelapse("tl-bad".to_string(), 1.0, Some(0), None, None, |_| {}).is_err());
+        assert!(get_timelapse_status("tl-bad".to_string()).await.is_err());
+    }
+
     #[test]
     fn test_quality_threshold_clamping() {
         // Verify quality threshold is properly clamped
@@ -762,4 +3047,79 @@ mod tests {
         // Equal score: strictly-greater comparison → should NOT replace
         assert!(!best.as_ref().is_none_or(|b| score_a > b.1));
     }
+
+    #[tokio::test]
+    async fn test_set_frame_orientation_applies_to_captures() {
+        enable_mock_camera();
+        let err = set_frame_orientation("orient-cmd".to_string(), None, 45)
+            .await
+            .expect_err("45 degrees is rejected");
+        assert_eq!(err.code, CommandError::INVALID_ARGUMENT);
+
+        let upright = capture_single_photo(Some("orient-cmd".to_string()), None, Some(0))
+            .await
+            .expect("mock capture");
+        set_frame_orientation("orient-cmd".to_string(), Some(FlipAxis::Horizontal), 90)
+            .await
+            .expect("valid orientation");
+        let rotated = capture_single_photo(Some("orient-cmd".to_string()), None, Some(0))
+            .await
+            .expect("mock capture");
+        set_frame_orientation("orient-cmd".to_string(), None, 0)
+            .await
+            .expect("reset orientation");
+
+        assert_eq!(
+            (rotated.width, rotated.height),
+            (upright.height, upright.width)
+        );
+        assert_eq!(
+            rotated.metadata.orientation.map(|t| t.rotation_degrees),
+            Some(90)
+        );
+    }
+
+    #[test]
+    fn test_scale_for_preview_records_both_resolutions() {
+        let frame = CameraFrame::new(vec![10; 640 * 480 * 3], 640, 480, "scaled".to_string());
+
+        let preview = scale_for_preview("scale-preview", frame.clone(), Some(320));
+        assert_eq!((preview.width, preview.height), (320, 240));
+        assert_eq!(
+            preview_resolutions("scale-preview"),
+            (Some((640, 480)), Some((320, 240)))
+        );
+
+        let full = scale_for_preview("scale-full", frame, None);
+        assert_eq!((full.width, full.height), (640, 480));
+        assert_eq!(preview_resolutions("scale-full").1, Some((640, 480)));
+        assert_eq!(preview_resolutions("scale-unknown"), (None, None));
+    }
+
+    #[test]
+    fn test_remove_stale_temp_captures_only_touches_prefixed_files() {
+        let dir = std::env::temp_dir().join(format!("crabcamera-cleanup-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let temp = dir.join(format!("{TEMP_CAPTURE_PREFIX}frame.jpg"));
+        let unrelated = dir.join("photo.jpg");
+        std::fs::write(&temp, b"temp").unwrap();
+        std::fs::write(&unrelated, b"keep").unwrap();
+
+        let day = Duration::from_secs(24 * 60 * 60);
+        assert_eq!(remove_stale_temp_captures(&dir, day).unwrap(), (0, 0));
+        assert!(temp.exists());
+
+        assert_eq!(
+            remove_stale_temp_captures(&dir, Duration::ZERO).unwrap(),
+            (1, 0)
+        );
+        assert!(!temp.exists());
+        assert!(unrelated.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            remove_stale_temp_captures(&dir, Duration::ZERO).unwrap(),
+            (0, 0)
+        );
+    }
 }
@@ -1,41 +1,194 @@
-use crate::config::CrabCameraConfig;
+use crate::commands::capture::get_or_create_camera;
+use crate::config::{
+    overlay, profiles, set_thread_affinity, CameraProfile, ConfigChangedEvent, ConfigIssue,
+    ConfigSection, ConfigValidationReport, CrabCameraConfig,
+};
+use crate::errors::CommandError;
+use crate::platform::CameraSystem;
+use crate::types::{CameraFormat, ControlApplicationResult};
 use std::sync::{Arc, LazyLock, RwLock};
 use tauri::command;
 
-static GLOBAL_CONFIG: LazyLock<Arc<RwLock<CrabCameraConfig>>> =
-    LazyLock::new(|| Arc::new(RwLock::new(CrabCameraConfig::load_or_default())));
+static GLOBAL_CONFIG: LazyLock<Arc<RwLock<CrabCameraConfig>>> = LazyLock::new(|| {
+    let config = CrabCameraConfig::load_or_default();
+    profiles::set_auto_apply(config.camera.auto_apply_saved_profile);
+    set_thread_affinity(config.advanced.thread_affinity.clone());
+    Arc::new(RwLock::new(config))
+});
+
+type ConfigListener = Box<dyn Fn(&ConfigChangedEvent) + Send + Sync>;
+
+// Receives every change made through the config commands
+static CONFIG_LISTENER: RwLock<Option<ConfigListener>> = RwLock::new(None);
+
+/// Deliver configuration changes made through the config commands to
+/// `listener`, replacing any previous one
+///
+/// The plugin installs a listener that emits `crabcamera://config-changed`.
+pub fn set_config_listener(listener: ConfigListener) {
+    if let Ok(mut current) = CONFIG_LISTENER.write() {
+        *current = Some(listener);
+    }
+}
+
+/// Load the global configuration and apply its profile auto-apply and
+/// thread affinity settings
+pub(crate) fn sync_runtime_settings() {
+    if let Ok(config) = GLOBAL_CONFIG.read() {
+        apply_runtime_settings(&config);
+    }
+}
+
+/// Push the settings that take effect without reopening cameras
+fn apply_runtime_settings(config: &CrabCameraConfig) {
+    profiles::set_auto_apply(config.camera.auto_apply_saved_profile);
+    set_thread_affinity(config.advanced.thread_affinity.clone());
+    crate::commands::capture::apply_frame_queue_capacity(|device_id| {
+        if config.devices.is_empty() {
+            config.advanced.frame_queue_capacity
+        } else {
+            config
+                .for_device(&CameraSystem::stable_device_id(device_id))
+                .advanced
+                .frame_queue_capacity
+        }
+    });
+}
+
+/// Apply `update` to a copy of the global configuration and, if the result
+/// validates, save and install it, apply its live settings and notify the
+/// config listener of the sections that changed
+fn commit_config(
+    update: impl FnOnce(&mut CrabCameraConfig),
+) -> Result<CrabCameraConfig, CommandError> {
+    let (previous, updated) = {
+        let mut config = GLOBAL_CONFIG
+            .write()
+            .map_err(|_| CommandError::system("Configuration lock poisoned"))?;
+        let mut updated = config.clone();
+        update(&mut updated);
+        updated
+            .validate()
+            .map_err(|e| CommandError::new("INVALID_CONFIG", e))?;
+        updated.save_to_file(CrabCameraConfig::default_path())?;
+        (std::mem::replace(&mut *config, updated.clone()), updated)
+    };
+    apply_runtime_settings(&updated);
+
+    let sections = previous.changed_sections(&updated);
+    if !sections.is_empty() {
+        log::info!("Configuration changed: {sections:?}");
+        if let Ok(listener) = CONFIG_LISTENER.read() {
+            if let Some(listener) = listener.as_ref() {
+                listener(&ConfigChangedEvent {
+                    sections,
+                    config: updated.clone(),
+                });
+            }
+        }
+    }
+    Ok(updated)
+}
+
+/// Current preview size limit of the camera with stable id `stable_id`,
+/// read per frame so changes apply live
+pub(crate) fn current_preview_max_dim(stable_id: &str) -> Option<u32> {
+    let config = GLOBAL_CONFIG.read().ok()?;
+    if config.devices.contains_key(stable_id) {
+        config.for_device(stable_id).advanced.preview_max_dim
+    } else {
+        config.advanced.preview_max_dim
+    }
+}
+
+/// Effective configuration of a camera, with its overrides applied
+///
+/// `device_id` may be numeric or stable. Without any overrides this is the
+/// global configuration and no devices are enumerated.
+pub(crate) fn effective_config(device_id: Option<&str>) -> CrabCameraConfig {
+    let config = GLOBAL_CONFIG
+        .read()
+        .map_or_else(|_| CrabCameraConfig::default(), |config| config.clone());
+    match device_id {
+        Some(device_id) if !config.devices.is_empty() => {
+            config.for_device(&CameraSystem::stable_device_id(device_id))
+        }
+        _ => config,
+    }
+}
+
+/// Current storage settings, for commands that fall back to them
+pub(crate) fn current_storage_config() -> crate::config::StorageConfig {
+    GLOBAL_CONFIG.read().map_or_else(
+        |_| CrabCameraConfig::default().storage,
+        |config| config.storage.clone(),
+    )
+}
+
+/// Current advanced settings, for commands that fall back to them
+pub(crate) fn current_advanced_config() -> crate::config::AdvancedConfig {
+    GLOBAL_CONFIG.read().map_or_else(
+        |_| CrabCameraConfig::default().advanced,
+        |config| config.advanced.clone(),
+    )
+}
+
+/// Configuration for a getter: the global one, or with a `device_id` that
+/// camera's effective configuration
+fn config_for(device_id: Option<&str>) -> Result<CrabCameraConfig, CommandError> {
+    if GLOBAL_CONFIG.is_poisoned() {
+        return Err(CommandError::system("Configuration lock poisoned"));
+    }
+    Ok(effective_config(device_id))
+}
+
+/// Store `values` as a section of the global configuration or, with a
+/// `device_id`, as that camera's override of it
+fn commit_section<T: serde::Serialize>(
+    device_id: Option<&str>,
+    section: ConfigSection,
+    values: T,
+    assign: impl FnOnce(&mut CrabCameraConfig, T),
+) -> Result<(), CommandError> {
+    match device_id {
+        Some(device_id) => {
+            let stable_id = CameraSystem::stable_device_id(device_id);
+            let values = serde_json::to_value(values)
+                .map_err(|e| CommandError::system(format!("Failed to serialize section: {e}")))?;
+            commit_config(|config| config.set_device_section(&stable_id, section, &values))?;
+        }
+        None => {
+            commit_config(|config| assign(config, values))?;
+        }
+    }
+    Ok(())
+}
 
 /// Get the current configuration
 ///
+/// With a `device_id` (numeric or stable), returns that camera's effective
+/// configuration: its overrides layered over the global settings, without
+/// the `devices` table.
+///
 /// # Errors
 /// Returns an `Err` if the global configuration lock is poisoned.
 #[command]
-pub async fn get_config() -> Result<CrabCameraConfig, String> {
-    let config = GLOBAL_CONFIG.read().map_err(|e| e.to_string())?;
-    Ok(config.clone())
+pub async fn get_config(device_id: Option<String>) -> Result<CrabCameraConfig, CommandError> {
+    config_for(device_id.as_deref())
 }
 
 /// Update configuration
 ///
+/// Emits `crabcamera://config-changed` with the sections that changed, as do
+/// [`reset_config`] and the per-section updates.
+///
 /// # Errors
 /// Returns an `Err` if the new configuration fails validation, if the global
 /// configuration lock is poisoned, or if the configuration cannot be saved to
 /// disk.
 #[command]
-pub async fn update_config(new_config: CrabCameraConfig) -> Result<(), String> {
-    // Validate first
-    new_config.validate().map_err(|e| e.clone())?;
-
-    {
-        let mut config = GLOBAL_CONFIG.write().map_err(|e| e.to_string())?;
-        *config = new_config.clone();
-    }
-
-    // Save to file
-    new_config
-        .save_to_file(CrabCameraConfig::default_path())
-        .map_err(|e| e.to_string())?;
-
+pub async fn update_config(new_config: CrabCameraConfig) -> Result<(), CommandError> {
+    commit_config(|config| *config = new_config)?;
     Ok(())
 }
 
@@ -45,83 +198,167 @@ pub async fn update_config(new_config: CrabCameraConfig) -> Result<(), String> {
 /// Returns an `Err` if the global configuration lock is poisoned or if the
 /// default configuration cannot be saved to disk.
 #[command]
-pub async fn reset_config() -> Result<CrabCameraConfig, String> {
-    let default_config = CrabCameraConfig::default();
+pub async fn reset_config() -> Result<CrabCameraConfig, CommandError> {
+    commit_config(|config| *config = CrabCameraConfig::default())
+}
 
-    {
-        let mut config = GLOBAL_CONFIG
-            .write()
-            .map_err(|e| format!("Failed to write config: {e}"))?;
-        *config = default_config.clone();
+/// Check a configuration change without applying or saving it
+///
+/// `partial` holds any subset of the config (e.g.
+/// `{ "storage": { "jpeg_quality": 0 } }`) and is merged over the current
+/// configuration before checking, so a settings form can validate one
+/// section or field as the user edits it. Errors carry the dotted `field`
+/// path they refer to; `valid` is what `update_config` would decide. With a
+/// `device_id`, the default resolution and frame rate are also checked
+/// against the formats that camera lists.
+///
+/// # Errors
+/// Returns an `Err` if the global configuration lock is poisoned. Invalid
+/// values are reported in the result, not as an `Err`.
+#[command]
+pub async fn validate_config(
+    partial: serde_json::Value,
+    device_id: Option<String>,
+) -> Result<ConfigValidationReport, CommandError> {
+    let mut merged = {
+        let config = GLOBAL_CONFIG
+            .read()
+            .map_err(|_| CommandError::system("Configuration lock poisoned"))?;
+        serde_json::to_value(&*config)
+            .map_err(|e| CommandError::system(format!("Failed to serialize configuration: {e}")))?
+    };
+    overlay(&mut merged, partial);
+
+    let config: CrabCameraConfig = match serde_json::from_value(merged) {
+        Ok(config) => config,
+        Err(e) => {
+            return Ok(ConfigValidationReport {
+                valid: false,
+                errors: vec![ConfigIssue::new("", format!("Invalid config value: {e}"))],
+                warnings: Vec::new(),
+            })
+        }
+    };
+    let mut report = config.check();
+
+    if let Some(device_id) = device_id {
+        let [width, height] = config.camera.default_resolution;
+        let fps = config.camera.default_fps;
+        // Validated frame rates are at most 240
+        #[allow(clippy::cast_precision_loss)]
+        let target_fps = fps as f32;
+        let cameras = crate::platform::CameraSystem::list_cameras().unwrap_or_default();
+        match cameras.iter().find(|camera| camera.matches_id(&device_id)) {
+            Some(camera) => {
+                let supported = camera.supports_formats.iter().any(|format| {
+                    format.width == width
+                        && format.height == height
+                        && (format.fps - target_fps).abs() < 0.5
+                });
+                if !supported && !camera.supports_formats.is_empty() {
+                    report.warnings.push(ConfigIssue::new(
+                        "camera.default_fps",
+                        format!("{device_id} lists no {width}x{height} format at {fps} fps"),
+                    ));
+                }
+            }
+            None => report.warnings.push(ConfigIssue::new(
+                "camera.default_resolution",
+                format!("Camera {device_id} not found; formats not checked"),
+            )),
+        }
     }
+    Ok(report)
+}
 
-    // Save defaults to file
-    default_config
-        .save_to_file(CrabCameraConfig::default_path())
-        .map_err(|e| e.to_string())?;
-
-    Ok(default_config)
+/// Describe every config field with its type, default and valid range
+///
+/// The result is JSON-schema-like (see [`crate::config::config_schema`]),
+/// so a settings UI can render and validate input generically.
+///
+/// # Errors
+/// This function always succeeds and never returns an `Err`.
+#[command]
+pub async fn get_config_schema() -> Result<serde_json::Value, CommandError> {
+    Ok(crate::config::config_schema())
 }
 
 /// Get camera configuration
 ///
+/// With a `device_id`, the camera's effective settings (see [`get_config`]).
+///
 /// # Errors
 /// Returns an `Err` if the global configuration lock is poisoned.
 #[command]
-pub async fn get_camera_config() -> Result<crate::config::CameraConfig, String> {
-    let config = GLOBAL_CONFIG.read().map_err(|e| e.to_string())?;
-    Ok(config.camera.clone())
+pub async fn get_camera_config(
+    device_id: Option<String>,
+) -> Result<crate::config::CameraConfig, CommandError> {
+    Ok(config_for(device_id.as_deref())?.camera)
 }
 
 /// Get quality configuration (full config object)
 ///
+/// With a `device_id`, the camera's effective settings (see [`get_config`]).
+///
 /// # Errors
 /// Returns an `Err` if the global configuration lock is poisoned.
 #[command]
-pub async fn get_full_quality_config() -> Result<crate::config::QualityConfig, String> {
-    let config = GLOBAL_CONFIG.read().map_err(|e| e.to_string())?;
-    Ok(config.quality.clone())
+pub async fn get_full_quality_config(
+    device_id: Option<String>,
+) -> Result<crate::config::QualityConfig, CommandError> {
+    Ok(config_for(device_id.as_deref())?.quality)
 }
 
 /// Get storage configuration
 ///
+/// With a `device_id`, the camera's effective settings (see [`get_config`]).
+///
 /// # Errors
 /// Returns an `Err` if the global configuration lock is poisoned.
 #[command]
-pub async fn get_storage_config() -> Result<crate::config::StorageConfig, String> {
-    let config = GLOBAL_CONFIG.read().map_err(|e| e.to_string())?;
-    Ok(config.storage.clone())
+pub async fn get_storage_config(
+    device_id: Option<String>,
+) -> Result<crate::config::StorageConfig, CommandError> {
+    Ok(config_for(device_id.as_deref())?.storage)
 }
 
 /// Get advanced configuration
 ///
+/// With a `device_id`, the camera's effective settings (see [`get_config`]).
+///
 /// # Errors
 /// Returns an `Err` if the global configuration lock is poisoned.
 #[command]
-pub async fn get_advanced_config() -> Result<crate::config::AdvancedConfig, String> {
-    let config = GLOBAL_CONFIG.read().map_err(|e| e.to_string())?;
-    Ok(config.advanced.clone())
+pub async fn get_advanced_config(
+    device_id: Option<String>,
+) -> Result<crate::config::AdvancedConfig, CommandError> {
+    Ok(config_for(device_id.as_deref())?.advanced)
 }
 
 /// Update camera configuration
 ///
+/// With a `device_id` (numeric or stable), `camera_config` becomes that
+/// camera's override instead: only the fields that differ from the global
+/// settings are stored, keyed by the camera's stable id, and the other
+/// fields keep following the global configuration. The same applies to the
+/// other section updates.
+///
 /// # Errors
 /// Returns an `Err` if the global configuration lock is poisoned, if the
 /// resulting configuration fails validation, or if it cannot be saved to disk.
 #[command]
 pub async fn update_camera_config(
     camera_config: crate::config::CameraConfig,
-) -> Result<(), String> {
-    let mut config = GLOBAL_CONFIG.write().map_err(|e| e.to_string())?;
-    config.camera = camera_config;
-
-    config.validate().map_err(|e| e.clone())?;
-
-    config
-        .save_to_file(CrabCameraConfig::default_path())
-        .map_err(|e| e.to_string())?;
-
-    Ok(())
+    device_id: Option<String>,
+) -> Result<(), CommandError> {
+    commit_section(
+        device_id.as_deref(),
+        ConfigSection::Camera,
+        camera_config,
+        |config, values| {
+            config.camera = values;
+        },
+    )
 }
 
 /// Update quality configuration (full config object)
@@ -132,17 +369,16 @@ pub async fn update_camera_config(
 #[command]
 pub async fn update_full_quality_config(
     quality_config: crate::config::QualityConfig,
-) -> Result<(), String> {
-    let mut config = GLOBAL_CONFIG.write().map_err(|e| e.to_string())?;
-    config.quality = quality_config;
-
-    config.validate().map_err(|e| e.clone())?;
-
-    config
-        .save_to_file(CrabCameraConfig::default_path())
-        .map_err(|e| e.to_string())?;
-
-    Ok(())
+    device_id: Option<String>,
+) -> Result<(), CommandError> {
+    commit_section(
+        device_id.as_deref(),
+        ConfigSection::Quality,
+        quality_config,
+        |config, values| {
+            config.quality = values;
+        },
+    )
 }
 
 /// Update storage configuration
@@ -153,17 +389,16 @@ pub async fn update_full_quality_config(
 #[command]
 pub async fn update_storage_config(
     storage_config: crate::config::StorageConfig,
-) -> Result<(), String> {
-    let mut config = GLOBAL_CONFIG.write().map_err(|e| e.to_string())?;
-    config.storage = storage_config;
-
-    config.validate().map_err(|e| e.clone())?;
-
-    config
-        .save_to_file(CrabCameraConfig::default_path())
-        .map_err(|e| e.to_string())?;
-
-    Ok(())
+    device_id: Option<String>,
+) -> Result<(), CommandError> {
+    commit_section(
+        device_id.as_deref(),
+        ConfigSection::Storage,
+        storage_config,
+        |config, values| {
+            config.storage = values;
+        },
+    )
 }
 
 /// Update advanced configuration
@@ -174,26 +409,113 @@ pub async fn update_storage_config(
 #[command]
 pub async fn update_advanced_config(
     advanced_config: crate::config::AdvancedConfig,
-) -> Result<(), String> {
-    let mut config = GLOBAL_CONFIG.write().map_err(|e| e.to_string())?;
-    config.advanced = advanced_config;
+    device_id: Option<String>,
+) -> Result<(), CommandError> {
+    commit_section(
+        device_id.as_deref(),
+        ConfigSection::Advanced,
+        advanced_config,
+        |config, values| {
+            config.advanced = values;
+        },
+    )
+}
 
-    config.validate().map_err(|e| e.clone())?;
+/// Remove every configuration override of a camera
+///
+/// Returns whether the camera had any.
+///
+/// # Errors
+/// Returns an `Err` if the global configuration lock is poisoned or if the
+/// configuration cannot be saved to disk.
+#[command]
+pub async fn clear_device_config(device_id: String) -> Result<bool, CommandError> {
+    let stable_id = CameraSystem::stable_device_id(&device_id);
+    let mut removed = false;
+    commit_config(|config| removed = config.devices.remove(&stable_id).is_some())?;
+    Ok(removed)
+}
 
-    config
-        .save_to_file(CrabCameraConfig::default_path())
-        .map_err(|e| e.to_string())?;
+/// Save the camera's current controls as its persistent profile
+///
+/// The profile is keyed by the device's persistent id, so it is found again
+/// after reconnects even if the numeric `device_id` changes.
+///
+/// # Errors
+/// Returns an `Err` if the camera cannot be created or retrieved, if the
+/// camera mutex is poisoned, if the blocking task fails to join, or if the
+/// controls cannot be read or the profile file cannot be written.
+#[command]
+pub async fn save_camera_profile(device_id: String) -> Result<CameraProfile, CommandError> {
+    log::info!("Saving camera profile for device: {device_id}");
+
+    let camera_arc = get_or_create_camera(device_id.clone(), CameraFormat::standard()).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let camera = camera_arc
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+
+        profiles::save_profile(&device_id, &camera).map_err(|e| {
+            log::error!("Failed to save camera profile: {e}");
+            CommandError::new(
+                e.error_code(),
+                format!("Failed to save camera profile: {e}"),
+            )
+        })
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
 
-    Ok(())
+/// Apply the camera's saved profile
+///
+/// Saved controls the camera no longer supports are skipped and reported in
+/// the result's `rejected` list.
+///
+/// # Errors
+/// Returns an `Err` if no profile is saved for the device, if the camera
+/// cannot be created or retrieved, if the camera mutex is poisoned, if the
+/// blocking task fails to join, or if the profile cannot be loaded or applied.
+#[command]
+pub async fn load_camera_profile(
+    device_id: String,
+) -> Result<ControlApplicationResult, CommandError> {
+    log::info!("Loading camera profile for device: {device_id}");
+
+    let camera_arc = get_or_create_camera(device_id.clone(), CameraFormat::standard()).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut camera = camera_arc
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+
+        profiles::apply_profile(&device_id, &mut camera)
+            .map_err(|e| {
+                log::error!("Failed to load camera profile: {e}");
+                CommandError::new(
+                    e.error_code(),
+                    format!("Failed to load camera profile: {e}"),
+                )
+            })?
+            .ok_or_else(|| {
+                CommandError::not_found(format!("No saved camera profile for device: {device_id}"))
+            })
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // Serializes tests that replace the shared global configuration
+    static CONFIG_TEST_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
     #[tokio::test]
     async fn test_get_config() {
-        let result = get_config().await;
+        let result = get_config(None).await;
         assert!(result.is_ok());
 
         let config = result.expect("config should load");
@@ -202,6 +524,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_reset_config() {
+        let _guard = CONFIG_TEST_LOCK.lock().await;
         let result = reset_config().await;
         assert!(result.is_ok());
 
@@ -211,7 +534,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_camera_config() {
-        let result = get_camera_config().await;
+        let result = get_camera_config(None).await;
         assert!(result.is_ok());
 
         let camera_config = result.expect("camera config expected");
@@ -220,29 +543,123 @@ mod tests {
 
     #[tokio::test]
     async fn test_other_getters_return_values() {
-        assert!(get_full_quality_config().await.is_ok());
-        assert!(get_storage_config().await.is_ok());
-        assert!(get_advanced_config().await.is_ok());
+        assert!(get_full_quality_config(None).await.is_ok());
+        assert!(get_storage_config(None).await.is_ok());
+        assert!(get_advanced_config(None).await.is_ok());
     }
 
     #[tokio::test]
     async fn test_update_config_and_subconfigs() {
+        let _guard = CONFIG_TEST_LOCK.lock().await;
         let base = CrabCameraConfig::default();
         update_config(base.clone())
             .await
             .expect("update_config should succeed for default config");
 
-        update_camera_config(base.camera.clone())
+        update_camera_config(base.camera.clone(), None)
             .await
             .expect("update_camera_config should succeed");
-        update_full_quality_config(base.quality.clone())
+        update_full_quality_config(base.quality.clone(), None)
             .await
             .expect("update_full_quality_config should succeed");
-        update_storage_config(base.storage.clone())
+        update_storage_config(base.storage.clone(), None)
             .await
             .expect("update_storage_config should succeed");
-        update_advanced_config(base.advanced.clone())
+        update_advanced_config(base.advanced.clone(), None)
             .await
             .expect("update_advanced_config should succeed");
     }
+
+    #[tokio::test]
+    async fn test_update_notifies_listener_and_rejects_invalid() {
+        let _guard = CONFIG_TEST_LOCK.lock().await;
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        set_config_listener(Box::new(move |event| {
+            sink.lock().expect("events lock").push(event.clone());
+        }));
+
+        let mut storage = CrabCameraConfig::default().storage;
+        storage.jpeg_quality = 77;
+        update_storage_config(storage, None)
+            .await
+            .expect("valid storage update");
+        assert!(events.lock().expect("events lock").iter().any(|event| {
+            event
+                .sections
+                .contains(&crate::config::ConfigSection::Storage)
+                && event.config.storage.jpeg_quality == 77
+        }));
+
+        let mut advanced = CrabCameraConfig::default().advanced;
+        advanced.frame_queue_capacity = 0;
+        assert!(update_advanced_config(advanced, None).await.is_err());
+        let current = get_advanced_config(None).await.expect("advanced config");
+        assert_ne!(current.frame_queue_capacity, 0);
+
+        reset_config().await.expect("reset");
+    }
+
+    #[tokio::test]
+    async fn test_device_override_layers_over_global() {
+        let _guard = CONFIG_TEST_LOCK.lock().await;
+        let device_id = "override-test-cam".to_string();
+        let mut storage = CrabCameraConfig::default().storage;
+        storage.jpeg_quality = 61;
+        update_storage_config(storage, Some(device_id.clone()))
+            .await
+            .expect("device storage update");
+
+        let device = get_storage_config(Some(device_id.clone()))
+            .await
+            .expect("device storage");
+        assert_eq!(device.jpeg_quality, 61);
+        let global = get_storage_config(None).await.expect("global storage");
+        assert_ne!(global.jpeg_quality, 61);
+        assert!(get_config(Some(device_id.clone()))
+            .await
+            .expect("device config")
+            .devices
+            .is_empty());
+
+        assert!(clear_device_config(device_id.clone())
+            .await
+            .expect("clear overrides"));
+        assert!(!clear_device_config(device_id).await.expect("already clear"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_config_reports_without_saving() {
+        let before = get_config(None).await.expect("config");
+        let report = validate_config(
+            serde_json::json!({ "storage": { "jpeg_quality": 0 }, "advanced": { "hdr_brackets": 50 } }),
+            None,
+        )
+        .await
+        .expect("report");
+        assert!(!report.valid);
+        let fields: Vec<&str> = report.errors.iter().map(|e| e.field.as_str()).collect();
+        assert!(fields.contains(&"storage.jpeg_quality"));
+        assert!(fields.contains(&"advanced.hdr_brackets"));
+        assert_eq!(
+            get_config(None).await.expect("config").storage.jpeg_quality,
+            before.storage.jpeg_quality
+        );
+
+        let wrong_type = validate_config(
+            serde_json::json!({ "camera": { "default_fps": "fast" } }),
+            None,
+        )
+        .await
+        .expect("report");
+        assert!(!wrong_type.valid);
+
+        let ok = validate_config(
+            serde_json::json!({ "storage": { "default_format": "png" } }),
+            None,
+        )
+        .await
+        .expect("report");
+        assert!(ok.valid);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::errors::CommandError;
 use crate::platform::{DeviceEvent, DeviceMonitor};
 use std::sync::{Arc, LazyLock};
 use tauri::command;
@@ -11,15 +12,14 @@ static GLOBAL_MONITOR: LazyLock<Arc<RwLock<Option<DeviceMonitor>>>> =
 /// # Errors
 /// Returns an `Err` if the underlying device monitor fails to start.
 #[command]
-pub async fn start_device_monitoring() -> Result<String, String> {
+pub async fn start_device_monitoring() -> Result<String, CommandError> {
     let mut monitor_guard = GLOBAL_MONITOR.write().await;
 
     if monitor_guard.is_none() {
         let monitor = DeviceMonitor::new();
-        monitor
-            .start_monitoring()
-            .await
-            .map_err(|e| format!("Failed to start monitoring: {e}"))?;
+        monitor.start_monitoring().await.map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to start monitoring: {e}"))
+        })?;
         *monitor_guard = Some(monitor);
         Ok("Device monitoring started".to_string())
     } else {
@@ -32,14 +32,13 @@ pub async fn start_device_monitoring() -> Result<String, String> {
 /// # Errors
 /// Returns an `Err` if the underlying device monitor fails to stop.
 #[command]
-pub async fn stop_device_monitoring() -> Result<String, String> {
+pub async fn stop_device_monitoring() -> Result<String, CommandError> {
     let mut monitor_guard = GLOBAL_MONITOR.write().await;
 
     if let Some(monitor) = monitor_guard.as_ref() {
-        monitor
-            .stop_monitoring()
-            .await
-            .map_err(|e| format!("Failed to stop monitoring: {e}"))?;
+        monitor.stop_monitoring().await.map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to stop monitoring: {e}"))
+        })?;
         *monitor_guard = None;
         Ok("Device monitoring stopped".to_string())
     } else {
@@ -50,9 +49,9 @@ pub async fn stop_device_monitoring() -> Result<String, String> {
 /// Poll for device events (non-blocking)
 ///
 /// # Errors
-/// Returns an `Err` if device monitoring has not been started.
+/// Returns a `NOT_FOUND` error if device monitoring has not been started.
 #[command]
-pub async fn poll_device_event() -> Result<Option<DeviceEventInfo>, String> {
+pub async fn poll_device_event() -> Result<Option<DeviceEventInfo>, CommandError> {
     let monitor_guard = GLOBAL_MONITOR.read().await;
 
     if let Some(monitor) = monitor_guard.as_ref() {
@@ -62,22 +61,22 @@ pub async fn poll_device_event() -> Result<Option<DeviceEventInfo>, String> {
             Ok(None)
         }
     } else {
-        Err("Device monitoring not started".to_string())
+        Err(CommandError::not_found("Device monitoring not started"))
     }
 }
 
 /// Get list of currently active devices
 ///
 /// # Errors
-/// Returns an `Err` if device monitoring has not been started.
+/// Returns a `NOT_FOUND` error if device monitoring has not been started.
 #[command]
-pub async fn get_monitored_devices() -> Result<Vec<crate::types::CameraDeviceInfo>, String> {
+pub async fn get_monitored_devices() -> Result<Vec<crate::types::CameraDeviceInfo>, CommandError> {
     let monitor_guard = GLOBAL_MONITOR.read().await;
 
     if let Some(monitor) = monitor_guard.as_ref() {
         Ok(monitor.get_active_devices().await)
     } else {
-        Err("Device monitoring not started".to_string())
+        Err(CommandError::not_found("Device monitoring not started"))
     }
 }
 
@@ -86,7 +85,7 @@ pub async fn get_monitored_devices() -> Result<Vec<crate::types::CameraDeviceInf
 pub struct DeviceEventInfo {
     /// The type of event ("connected" or "disconnected").
     pub event_type: String,
-    /// The ID of the affected device.
+    /// The stable ID of the affected device.
     pub device_id: String,
 }
 
@@ -1,10 +1,13 @@
-use crate::constants::{
-    FOCUS_STACK_MAX_DIST, FOCUS_STACK_MAX_STEPS, FOCUS_STACK_MIN_DIST, FOCUS_STACK_MIN_STEPS,
-};
-use crate::focus_stack::align::align_frames;
-use crate::focus_stack::capture::{capture_focus_brackets, capture_focus_sequence};
+use crate::commands::capture::{CaptureStage, Operation};
+use crate::errors::CommandError;
+use crate::focus_stack::align::{align_frames, align_frames_with};
+use crate::focus_stack::capture::{capture_focus_brackets, capture_focus_sequence_cancellable};
 use crate::focus_stack::merge::merge_frames;
-use crate::focus_stack::{FocusStackConfig, FocusStackResult};
+use crate::focus_stack::select::sharpest_index;
+use crate::focus_stack::streaming;
+use crate::focus_stack::{
+    AlignmentMethod, FocusConfigReport, FocusStackConfig, FocusStackResult, SharpestFrame,
+};
 use crate::types::CameraFormat;
 use std::time::Instant;
 /// Focus stacking Tauri commands
@@ -14,16 +17,22 @@ use tauri::command;
 
 /// Capture and merge a focus stack
 ///
+/// With an `operation_id`,
+/// [`cancel_operation`](crate::commands::capture::cancel_operation) stops the
+/// capture and the command fails with a `CANCELLED` error. Progress is
+/// reported per captured frame, then as the stack is aligned and merged.
+///
 /// # Errors
-/// Returns an `Err` if capturing the focus sequence fails, if frame alignment
-/// fails (when enabled) or applying an alignment transform fails, or if merging
-/// the frames fails.
+/// Returns an `Err` if capturing the focus sequence fails or is cancelled, if
+/// `operation_id` is already in use, if frame alignment fails (when enabled)
+/// or applying an alignment transform fails, or if merging the frames fails.
 #[command]
 pub async fn capture_focus_stack(
     device_id: String,
     config: FocusStackConfig,
     format: Option<CameraFormat>,
-) -> Result<FocusStackResult, String> {
+    operation_id: Option<String>,
+) -> Result<FocusStackResult, CommandError> {
     log::info!(
         "Starting focus stack capture: device={}, steps={}",
         device_id,
@@ -31,46 +40,78 @@ pub async fn capture_focus_stack(
     );
 
     let start_time = Instant::now();
+    let operation = Operation::register(operation_id)?;
+    let steps = config.num_steps;
 
     // Capture sequence
-    let frames = capture_focus_sequence(device_id, config.clone(), format)
-        .await
-        .map_err(|e| e.to_string())?;
+    let mut captured = 0;
+    let frames = capture_focus_sequence_cancellable(
+        device_id.clone(),
+        config.clone(),
+        format,
+        operation.token(),
+        |frame| {
+            captured += 1;
+            operation.report(
+                &device_id,
+                captured,
+                Some(steps),
+                CaptureStage::Capturing,
+                Some(frame),
+            );
+        },
+    )
+    .await?;
 
     log::info!("Captured {} frames, starting alignment", frames.len());
 
     // Align frames if enabled
-    let (aligned_frames, avg_alignment_error) = if config.enable_alignment {
-        let alignments = align_frames(&frames).map_err(|e| e.to_string())?;
+    let (aligned_frames, avg_alignment_error, avg_residual) = if config.enable_alignment {
+        check_cancelled(&operation)?;
+        operation.report(&device_id, 0, Some(captured), CaptureStage::Aligning, None);
+
+        let alignments = align_frames_with(&frames, config.alignment_method)?;
 
         #[allow(clippy::cast_precision_loss)]
         // usize→f32: alignment count is small, no precision loss
-        let avg_error = alignments.iter().map(|a| a.error).sum::<f32>() / alignments.len() as f32;
+        let count = alignments.len() as f32;
+        let avg_error = alignments.iter().map(|a| a.error).sum::<f32>() / count;
+        let avg_residual = alignments.iter().map(|a| a.residual).sum::<f32>() / count;
 
-        log::info!("Alignment complete, avg error: {avg_error:.3} pixels");
+        log::info!(
+            "Alignment complete ({:?}), avg error: {avg_error:.3} pixels, residual: {avg_residual:.2}",
+            config.alignment_method
+        );
 
         // Apply alignment transforms to frames
         let mut aligned = Vec::with_capacity(frames.len());
         for (frame, alignment) in frames.iter().zip(alignments.iter()) {
-            let aligned_frame = crate::focus_stack::align::apply_alignment(frame, alignment)
-                .map_err(|e| e.to_string())?;
+            let aligned_frame = crate::focus_stack::align::apply_alignment(frame, alignment)?;
             aligned.push(aligned_frame);
         }
 
-        (aligned, avg_error)
+        (aligned, avg_error, avg_residual)
     } else {
-        (frames, 0.0)
+        (frames, 0.0, 0.0)
     };
 
     log::info!("Starting merge with {} blend levels", config.blend_levels);
+    check_cancelled(&operation)?;
+    operation.report(&device_id, 0, Some(captured), CaptureStage::Merging, None);
 
     // Merge frames
     let merged_frame = merge_frames(
         &aligned_frames,
         config.sharpness_threshold,
         config.blend_levels,
-    )
-    .map_err(|e| e.to_string())?;
+    )?;
+    operation.report(
+        &device_id,
+        captured,
+        Some(captured),
+        CaptureStage::Merging,
+        Some(&merged_frame),
+    );
 
     let processing_time_ms = u64::try_from(start_time.elapsed().as_millis()).unwrap_or(u64::MAX);
 
@@ -80,10 +121,126 @@ pub async fn capture_focus_stack(
         merged_frame,
         num_sources: aligned_frames.len(),
         alignment_error: avg_alignment_error,
+        alignment_method: config.alignment_method,
+        alignment_residual: avg_residual,
         processing_time_ms,
     })
 }
 
+/// Capture a focus stack, merging each frame as it is captured
+///
+/// Keeps about two frames in memory regardless of `num_steps`, at the cost of
+/// sharpest-pixel selection instead of pyramid blending (`blend_levels` is
+/// ignored). Prefer this over [`capture_focus_stack`] for long, high-resolution
+/// stacks.
+///
+/// Cancellable through `operation_id` like [`capture_focus_stack`].
+///
+/// # Errors
+/// Returns an `Err` if the config is invalid, `operation_id` is already in
+/// use, a capture fails or is cancelled, or a frame cannot be aligned or
+/// merged.
+#[command]
+pub async fn capture_focus_stack_streaming(
+    device_id: String,
+    config: FocusStackConfig,
+    format: Option<CameraFormat>,
+    operation_id: Option<String>,
+) -> Result<FocusStackResult, CommandError> {
+    log::info!(
+        "Starting streaming focus stack: device={}, steps={}",
+        device_id,
+        config.num_steps
+    );
+
+    let operation = Operation::register(operation_id)?;
+    let steps = config.num_steps;
+    let mut merged = 0;
+    streaming::capture_focus_stack_streaming(
+        device_id.clone(),
+        config,
+        format,
+        operation.token(),
+        |frame| {
+            merged += 1;
+            operation.report(
+                &device_id,
+                merged,
+                Some(steps),
+                CaptureStage::Capturing,
+                Some(frame),
+            );
+        },
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+/// Fail with `CANCELLED` once the operation was cancelled
+fn check_cancelled(operation: &Operation) -> Result<(), CommandError> {
+    if operation.token().is_cancelled() {
+        return Err(CommandError::cancelled("Focus stack cancelled"));
+    }
+    Ok(())
+}
+
+/// Capture a focus sequence and return only its sharpest frame
+///
+/// A lighter alternative to [`capture_focus_stack`] for scenes with a single
+/// focal plane: no alignment or merging, just the frame with the highest
+/// Laplacian variance. Other settings come from [`FocusStackConfig::default`].
+/// Cancellable through `operation_id` like [`capture_focus_stack`].
+///
+/// # Errors
+/// Returns an `Err` if `num_steps` is out of range, `operation_id` is already
+/// in use, or a capture fails or is cancelled.
+#[command]
+pub async fn capture_and_pick_sharpest(
+    device_id: String,
+    num_steps: u32,
+    format: Option<CameraFormat>,
+    operation_id: Option<String>,
+) -> Result<SharpestFrame, CommandError> {
+    log::info!("Capturing {num_steps} focus steps to pick the sharpest: device={device_id}");
+
+    let config = FocusStackConfig {
+        num_steps,
+        ..FocusStackConfig::default()
+    };
+    let operation = Operation::register(operation_id)?;
+    let mut captured = 0;
+    let mut frames = capture_focus_sequence_cancellable(
+        device_id.clone(),
+        config,
+        format,
+        operation.token(),
+        |frame| {
+            captured += 1;
+            operation.report(
+                &device_id,
+                captured,
+                Some(num_steps),
+                CaptureStage::Capturing,
+                Some(frame),
+            );
+        },
+    )
+    .await?;
+    drop(operation);
+
+    let num_sources = frames.len();
+    let (index, sharpness) =
+        sharpest_index(&frames).ok_or_else(|| CommandError::not_found("No frames captured"))?;
+    log::info!("Frame {index} of {num_sources} is sharpest (variance {sharpness:.1})");
+
+    Ok(SharpestFrame {
+        frame: frames.swap_remove(index),
+        index,
+        sharpness,
+        num_sources,
+    })
+}
+
 /// Capture focus brackets (multiple overlapping focus ranges)
 ///
 /// ## Deprecation
@@ -101,27 +258,26 @@ pub async fn capture_focus_brackets_command(
     sharpness_threshold: f32,
     blend_levels: u32,
     format: Option<CameraFormat>,
-) -> Result<FocusStackResult, String> {
+) -> Result<FocusStackResult, CommandError> {
     log::info!("Starting focus bracket capture: {brackets} brackets x {shots_per_bracket} shots");
 
     let start_time = Instant::now();
 
     // Capture all brackets
-    let frames = capture_focus_brackets(device_id, brackets, shots_per_bracket, format)
-        .await
-        .map_err(|e| e.to_string())?;
+    let frames = capture_focus_brackets(device_id, brackets, shots_per_bracket, format).await?;
 
     log::info!("Captured {} total frames from brackets", frames.len());
 
     // Align and merge
-    let alignments = align_frames(&frames).map_err(|e| e.to_string())?;
+    let alignments = align_frames(&frames)?;
 
     #[allow(clippy::cast_precision_loss)]
     // usize→f32: alignment count is small, no precision loss
-    let avg_error = alignments.iter().map(|a| a.error).sum::<f32>() / alignments.len() as f32;
+    let count = alignments.len() as f32;
+    let avg_error = alignments.iter().map(|a| a.error).sum::<f32>() / count;
+    let avg_residual = alignments.iter().map(|a| a.residual).sum::<f32>() / count;
 
-    let merged_frame =
-        merge_frames(&frames, sharpness_threshold, blend_levels).map_err(|e| e.to_string())?;
+    let merged_frame = merge_frames(&frames, sharpness_threshold, blend_levels)?;
 
     let processing_time_ms = u64::try_from(start_time.elapsed().as_millis()).unwrap_or(u64::MAX);
 
@@ -131,6 +287,8 @@ pub async fn capture_focus_brackets_command(
         merged_frame,
         num_sources: frames.len(),
         alignment_error: avg_error,
+        alignment_method: AlignmentMethod::TranslationOnly,
+        alignment_residual: avg_residual,
         processing_time_ms,
     })
 }
@@ -143,9 +301,10 @@ pub fn get_default_focus_config() -> FocusStackConfig {
 
 /// Validate focus stack configuration
 ///
-/// # Errors
-/// Returns an `Err` if `num_steps`, `focus_start`, `focus_end`,
-/// `sharpness_threshold`, or `blend_levels` fall outside their allowed ranges.
+/// Returns a [`FocusConfigReport`] listing errors and warnings (e.g. a long
+/// capture time or a reversed focus range) plus a corrected `suggested`
+/// config; `valid` is the overall pass/fail. Pass the capture `format` to
+/// also check `blend_levels` against the resolution.
 // Owned `FocusStackConfig` is REQUIRED: this is a Tauri `#[command]` and the
 // invoke bridge only deserializes arguments by value. `needless_pass_by_value`
 // is a false positive here — there is no sound `&T` form (Tauri's `CommandArg`
@@ -153,38 +312,11 @@ pub fn get_default_focus_config() -> FocusStackConfig {
 // exception pending a decision on framework-forced lints.
 #[allow(clippy::needless_pass_by_value)]
 #[command]
-pub fn validate_focus_config(config: FocusStackConfig) -> Result<String, String> {
-    if config.num_steps < FOCUS_STACK_MIN_STEPS {
-        return Err(format!(
-            "num_steps must be at least {FOCUS_STACK_MIN_STEPS}"
-        ));
-    }
-
-    if config.num_steps > FOCUS_STACK_MAX_STEPS {
-        return Err(format!("num_steps must be at most {FOCUS_STACK_MAX_STEPS}"));
-    }
-
-    if config.focus_start < FOCUS_STACK_MIN_DIST || config.focus_start > FOCUS_STACK_MAX_DIST {
-        return Err(format!(
-            "focus_start must be between {FOCUS_STACK_MIN_DIST:.1} and {FOCUS_STACK_MAX_DIST:.1}"
-        ));
-    }
-
-    if config.focus_end < FOCUS_STACK_MIN_DIST || config.focus_end > FOCUS_STACK_MAX_DIST {
-        return Err(format!(
-            "focus_end must be between {FOCUS_STACK_MIN_DIST:.1} and {FOCUS_STACK_MAX_DIST:.1}"
-        ));
-    }
-
-    if config.sharpness_threshold < 0.0 || config.sharpness_threshold > 1.0 {
-        return Err("sharpness_threshold must be between 0.0 and 1.0".to_string());
-    }
-
-    if config.blend_levels < 3 || config.blend_levels > 10 {
-        return Err("blend_levels must be between 3 and 10".to_string());
-    }
-
-    Ok("Configuration valid".to_string())
+pub fn validate_focus_config(
+    config: FocusStackConfig,
+    format: Option<CameraFormat>,
+) -> FocusConfigReport {
+    config.diagnose(format.as_ref())
 }
 
 #[cfg(test)]
@@ -201,8 +333,10 @@ mod tests {
     #[test]
     fn test_config_validation_valid() {
         let config = FocusStackConfig::default();
-        let result = validate_focus_config(config);
-        assert!(result.is_ok());
+        let report = validate_focus_config(config, None);
+        assert!(report.valid);
+        assert!(report.errors.is_empty());
+        assert!(report.warnings.is_empty());
     }
 
     #[test]
@@ -211,11 +345,10 @@ mod tests {
             num_steps: 1,
             ..Default::default()
         };
-        let result = validate_focus_config(config);
-        assert!(result.is_err());
-        assert!(result
-            .expect_err("steps error expected")
-            .contains("at least 2"));
+        let report = validate_focus_config(config, None);
+        assert!(!report.valid);
+        assert!(report.errors[0].contains("between 2 and 100"));
+        assert_eq!(report.suggested.num_steps, 2);
     }
 
     #[test]
@@ -224,8 +357,9 @@ mod tests {
             focus_start: -0.5,
             ..Default::default()
         };
-        let result = validate_focus_config(config);
-        assert!(result.is_err());
+        let report = validate_focus_config(config, None);
+        assert!(!report.valid);
+        assert!(report.suggested.diagnose(None).valid);
     }
 
     #[test]
@@ -234,8 +368,9 @@ mod tests {
             sharpness_threshold: 1.5,
             ..Default::default()
         };
-        let result = validate_focus_config(config);
-        assert!(result.is_err());
+        let report = validate_focus_config(config, None);
+        assert!(!report.valid);
+        assert!(report.suggested.diagnose(None).valid);
     }
 
     #[test]
@@ -244,8 +379,30 @@ mod tests {
             blend_levels: 15,
             ..Default::default()
         };
-        let result = validate_focus_config(config);
-        assert!(result.is_err());
+        let report = validate_focus_config(config, None);
+        assert!(!report.valid);
+        assert!(report.suggested.diagnose(None).valid);
+    }
+
+    #[test]
+    fn test_config_validation_warns_and_suggests_fixes() {
+        let config = FocusStackConfig {
+            num_steps: 25,
+            step_delay_ms: 200,
+            focus_start: 0.8,
+            focus_end: 0.2,
+            blend_levels: 10,
+            ..Default::default()
+        };
+        let report = validate_focus_config(config, Some(CameraFormat::new(64, 48, 30.0)));
+
+        assert!(report.valid, "warnings alone keep the config valid");
+        assert_eq!(report.warnings.len(), 3, "{:?}", report.warnings);
+        assert!(report.warnings.iter().any(|w| w.contains("5.0s")));
+        assert!((report.suggested.focus_start - 0.2).abs() < 1e-6);
+        assert!((report.suggested.focus_end - 0.8).abs() < 1e-6);
+        assert_eq!(report.suggested.blend_levels, 5);
+        assert_eq!(report.suggested.num_steps, 25);
     }
 
     #[tokio::test]
@@ -255,7 +412,24 @@ mod tests {
             ..Default::default()
         };
 
-        let result = capture_focus_stack("0".to_string(), config, None).await;
+        let result = capture_focus_stack("0".to_string(), config, None, None).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_capture_focus_stack_streaming_rejects_invalid_config_early() {
+        let config = FocusStackConfig {
+            num_steps: 1,
+            ..Default::default()
+        };
+
+        let result = capture_focus_stack_streaming("0".to_string(), config, None, None).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_capture_and_pick_sharpest_rejects_invalid_steps_early() {
+        let result = capture_and_pick_sharpest("0".to_string(), 1, None, None).await;
         assert!(result.is_err());
     }
 
@@ -1,5 +1,12 @@
-use crate::platform::{CameraSystem, PlatformInfo, SystemTestResult};
-use crate::types::{CameraDeviceInfo, CameraFormat, Platform};
+use crate::errors::{CameraError, CommandError};
+use crate::platform::{
+    max_resolution_format, negotiate_format, CameraSystem, FormatNegotiation, PlatformInfo,
+    SystemTestResult,
+};
+use crate::types::{
+    CameraBackend, CameraDeviceInfo, CameraFormat, FormatPreset, FormatPresetInfo,
+    PixelFormatPreference, Platform,
+};
 use tauri::command;
 
 use crate::registry::{FeatureManifest, SystemRegistry};
@@ -15,7 +22,11 @@ pub async fn get_system_manifest() -> Vec<FeatureManifest> {
 /// # Errors
 /// Returns an `Err` if the camera system fails to initialize.
 #[command]
-pub async fn initialize_camera_system() -> Result<String, String> {
+pub async fn initialize_camera_system() -> Result<String, CommandError> {
+    // Saved profiles are applied on first open when the config asks for it,
+    // and new capture/encode threads pick up the configured affinity
+    crate::commands::config::sync_runtime_settings();
+
     match CameraSystem::initialize() {
         Ok(message) => {
             log::info!("Camera system initialized: {message}");
@@ -23,7 +34,10 @@ pub async fn initialize_camera_system() -> Result<String, String> {
         }
         Err(e) => {
             log::error!("Failed to initialize camera system: {e}");
-            Err(format!("Failed to initialize camera system: {e}"))
+            Err(CommandError::new(
+                e.error_code(),
+                format!("Failed to initialize camera system: {e}"),
+            ))
         }
     }
 }
@@ -33,7 +47,7 @@ pub async fn initialize_camera_system() -> Result<String, String> {
 /// # Errors
 /// Returns an `Err` if the camera system fails to enumerate cameras.
 #[command]
-pub async fn get_available_cameras() -> Result<Vec<CameraDeviceInfo>, String> {
+pub async fn get_available_cameras() -> Result<Vec<CameraDeviceInfo>, CommandError> {
     match CameraSystem::list_cameras() {
         Ok(cameras) => {
             log::info!("Found {} cameras", cameras.len());
@@ -49,17 +63,56 @@ pub async fn get_available_cameras() -> Result<Vec<CameraDeviceInfo>, String> {
         }
         Err(e) => {
             log::error!("Failed to list cameras: {e}");
-            Err(format!("Failed to list cameras: {e}"))
+            Err(CommandError::new(
+                e.error_code(),
+                format!("Failed to list cameras: {e}"),
+            ))
         }
     }
 }
 
+/// Drop cached devices and capabilities and list cameras again
+///
+/// [`get_available_cameras`] reuses its last result until a running device
+/// monitor reports a change; call this to pick up cameras plugged in while
+/// monitoring is off.
+///
+/// # Errors
+/// Returns an `Err` if the camera system fails to enumerate cameras.
+#[command]
+pub async fn refresh_cameras() -> Result<Vec<CameraDeviceInfo>, CommandError> {
+    tokio::task::spawn_blocking(|| {
+        CameraSystem::refresh_devices().map_err(|e| {
+            log::error!("Failed to refresh cameras: {e}");
+            CommandError::new(e.error_code(), format!("Failed to refresh cameras: {e}"))
+        })
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// List cameras followed by the displays that can be captured
+///
+/// Screens have `transport: "Screen"` and ids like `screen:0`, which every
+/// capture, preview and recording command accepts.
+///
+/// # Errors
+/// Returns an `Err` if the camera system fails to enumerate cameras.
+#[cfg(feature = "screen-capture")]
+#[command]
+pub async fn get_available_sources() -> Result<Vec<CameraDeviceInfo>, CommandError> {
+    CameraSystem::list_sources(true).map_err(|e| {
+        log::error!("Failed to list sources: {e}");
+        CommandError::new(e.error_code(), format!("Failed to list sources: {e}"))
+    })
+}
+
 /// Get platform-specific information
 ///
 /// # Errors
 /// Returns an `Err` if the platform information cannot be retrieved.
 #[command]
-pub async fn get_platform_info() -> Result<PlatformInfo, String> {
+pub async fn get_platform_info() -> Result<PlatformInfo, CommandError> {
     match CameraSystem::get_platform_info() {
         Ok(info) => {
             log::info!(
@@ -71,7 +124,10 @@ pub async fn get_platform_info() -> Result<PlatformInfo, String> {
         }
         Err(e) => {
             log::error!("Failed to get platform info: {e}");
-            Err(format!("Failed to get platform info: {e}"))
+            Err(CommandError::new(
+                e.error_code(),
+                format!("Failed to get platform info: {e}"),
+            ))
         }
     }
 }
@@ -81,7 +137,7 @@ pub async fn get_platform_info() -> Result<PlatformInfo, String> {
 /// # Errors
 /// Returns an `Err` if the camera system test fails to run.
 #[command]
-pub async fn test_camera_system() -> Result<SystemTestResult, String> {
+pub async fn test_camera_system() -> Result<SystemTestResult, CommandError> {
     log::info!("Running camera system test...");
 
     match CameraSystem::test_system() {
@@ -113,7 +169,10 @@ pub async fn test_camera_system() -> Result<SystemTestResult, String> {
         }
         Err(e) => {
             log::error!("Camera system test failed: {e}");
-            Err(format!("Camera system test failed: {e}"))
+            Err(CommandError::new(
+                e.error_code(),
+                format!("Camera system test failed: {e}"),
+            ))
         }
     }
 }
@@ -123,7 +182,7 @@ pub async fn test_camera_system() -> Result<SystemTestResult, String> {
 /// # Errors
 /// This function always succeeds and never returns an `Err`.
 #[command]
-pub async fn get_current_platform() -> Result<String, String> {
+pub async fn get_current_platform() -> Result<String, CommandError> {
     let platform = Platform::current();
     Ok(platform.as_str().to_string())
 }
@@ -133,12 +192,12 @@ pub async fn get_current_platform() -> Result<String, String> {
 /// # Errors
 /// Returns an `Err` if the camera system fails to enumerate cameras.
 #[command]
-pub async fn check_camera_availability(device_id: String) -> Result<bool, String> {
+pub async fn check_camera_availability(device_id: String) -> Result<bool, CommandError> {
     match CameraSystem::list_cameras() {
         Ok(cameras) => {
             let is_available = cameras
                 .iter()
-                .find(|camera| camera.id == device_id)
+                .find(|camera| camera.matches_id(&device_id))
                 .is_some_and(|camera| camera.is_available);
 
             log::debug!("Camera {device_id} availability: {is_available}");
@@ -146,21 +205,40 @@ pub async fn check_camera_availability(device_id: String) -> Result<bool, String
         }
         Err(e) => {
             log::error!("Failed to check camera availability: {e}");
-            Err(format!("Failed to check camera availability: {e}"))
+            Err(CommandError::new(
+                e.error_code(),
+                format!("Failed to check camera availability: {e}"),
+            ))
         }
     }
 }
 
+/// Check if a camera is held by another process
+///
+/// Lets the UI tell a busy device apart from a missing one before opening
+/// it. Only Linux can detect this; other platforms always report `false`.
+///
+/// # Errors
+/// Returns a `SYSTEM_ERROR` if the check cannot run.
+#[command]
+pub async fn check_camera_in_use(device_id: String) -> Result<bool, CommandError> {
+    // Scans the open file descriptors of every process
+    let in_use = tokio::task::spawn_blocking(move || CameraSystem::is_device_in_use(&device_id))
+        .await
+        .map_err(|e| CommandError::system(format!("Task join error: {e}")))?;
+    Ok(in_use)
+}
+
 /// Get supported formats for a specific camera
 ///
 /// # Errors
 /// Returns an `Err` if the camera system fails to enumerate cameras, or if no
 /// camera with the given `device_id` is found.
 #[command]
-pub async fn get_camera_formats(device_id: String) -> Result<Vec<CameraFormat>, String> {
+pub async fn get_camera_formats(device_id: String) -> Result<Vec<CameraFormat>, CommandError> {
     match CameraSystem::list_cameras() {
         Ok(cameras) => {
-            if let Some(camera) = cameras.iter().find(|c| c.id == device_id) {
+            if let Some(camera) = cameras.iter().find(|c| c.matches_id(&device_id)) {
                 log::debug!(
                     "Camera {} supports {} formats",
                     device_id,
@@ -170,22 +248,62 @@ pub async fn get_camera_formats(device_id: String) -> Result<Vec<CameraFormat>,
             } else {
                 let msg = format!("Camera with ID '{device_id}' not found");
                 log::warn!("{msg}");
-                Err(msg)
+                Err(CommandError::not_found(msg))
             }
         }
         Err(e) => {
             log::error!("Failed to get camera formats: {e}");
-            Err(format!("Failed to get camera formats: {e}"))
+            Err(CommandError::new(
+                e.error_code(),
+                format!("Failed to get camera formats: {e}"),
+            ))
         }
     }
 }
 
+/// Show which mode a device would use for `format`, and the compressed and
+/// uncompressed modes it was chosen between
+///
+/// `preference` defaults to [`PixelFormatPreference::Auto`]. To override the
+/// choice, pass the alternative's `format_type` with the format, or set
+/// [`CameraInitParams::pixel_format`](crate::types::CameraInitParams::pixel_format).
+///
+/// # Errors
+/// Returns an `Err` if the camera is not found or reports no usable formats.
+#[command]
+pub async fn get_format_tradeoff(
+    device_id: String,
+    format: CameraFormat,
+    preference: Option<PixelFormatPreference>,
+) -> Result<FormatNegotiation, CommandError> {
+    let supported = get_camera_formats(device_id.clone()).await?;
+    negotiate_format(&supported, &format, preference.unwrap_or_default()).ok_or_else(|| {
+        CommandError::not_found(format!("Camera '{device_id}' reports no usable formats"))
+    })
+}
+
+/// Get the largest format a camera offers, for full-resolution stills
+///
+/// The listed modes are not in any guaranteed order; this picks the one
+/// with the most pixels, preferring modes of at least
+/// [`MAX_RESOLUTION_MIN_FPS`](crate::constants::MAX_RESOLUTION_MIN_FPS).
+///
+/// # Errors
+/// Returns an `Err` if the camera is not found or reports no usable formats.
+#[command]
+pub async fn get_max_resolution_format(device_id: String) -> Result<CameraFormat, CommandError> {
+    let supported = get_camera_formats(device_id.clone()).await?;
+    max_resolution_format(&supported).ok_or_else(|| {
+        CommandError::not_found(format!("Camera '{device_id}' reports no usable formats"))
+    })
+}
+
 /// Get recommended format for high-quality photography
 ///
 /// # Errors
 /// This function always succeeds and never returns an `Err`.
 #[command]
-pub async fn get_recommended_format() -> Result<CameraFormat, String> {
+pub async fn get_recommended_format() -> Result<CameraFormat, CommandError> {
     let format = crate::platform::optimizations::get_photography_format();
     log::info!(
         "Recommended photography format: {}x{} @ {}fps ({})",
@@ -197,12 +315,24 @@ pub async fn get_recommended_format() -> Result<CameraFormat, String> {
     Ok(format)
 }
 
+/// List the standard format presets with display labels, smallest first
+///
+/// # Errors
+/// This function always succeeds and never returns an `Err`.
+#[command]
+pub async fn get_format_presets() -> Result<Vec<FormatPresetInfo>, CommandError> {
+    Ok(FormatPreset::ALL
+        .into_iter()
+        .map(FormatPresetInfo::from)
+        .collect())
+}
+
 /// Get optimal camera settings for high-quality capture
 ///
 /// # Errors
 /// This function always succeeds and never returns an `Err`.
 #[command]
-pub async fn get_optimal_settings() -> Result<crate::types::CameraInitParams, String> {
+pub async fn get_optimal_settings() -> Result<crate::types::CameraInitParams, CommandError> {
     let params = crate::platform::optimizations::get_optimal_settings();
     log::info!(
         "Optimal settings: Device {} with {}x{} @ {}fps",
@@ -223,7 +353,7 @@ pub async fn get_optimal_settings() -> Result<crate::types::CameraInitParams, St
 /// This function always returns a diagnostics report and never returns an `Err`;
 /// individual subsystem failures are captured in the report's error fields.
 #[command]
-pub async fn get_system_diagnostics() -> Result<SystemDiagnostics, String> {
+pub async fn get_system_diagnostics() -> Result<SystemDiagnostics, CommandError> {
     log::info!("Running system diagnostics...");
 
     let platform = Platform::current();
@@ -257,10 +387,14 @@ pub async fn get_system_diagnostics() -> Result<SystemDiagnostics, String> {
     };
     let camera_count = cameras.len();
 
-    // Build camera summaries
-    let camera_summaries: Vec<CameraSummary> = cameras
-        .iter()
-        .map(|c| CameraSummary {
+    // Build camera summaries, with the backend of cameras already open
+    let mut camera_summaries = Vec::with_capacity(cameras.len());
+    for c in &cameras {
+        let backend = match crate::platform::get_existing_camera(&c.id).await {
+            Some(camera) => camera.lock().ok().and_then(|camera| camera.backend()),
+            None => None,
+        };
+        camera_summaries.push(CameraSummary {
             id: c.id.clone(),
             name: c.name.clone(),
             is_available: c.is_available,
@@ -270,16 +404,25 @@ pub async fn get_system_diagnostics() -> Result<SystemDiagnostics, String> {
                 .iter()
                 .map(|f| (f.width, f.height))
                 .max_by_key(|(w, h)| w * h),
-        })
-        .collect();
+            backend,
+        });
+    }
 
     // Check permission status — preserve error
     let (permission_status, permission_error) =
         match crate::commands::permissions::check_camera_permission_status().await {
             Ok(p) => (p.status.to_string(), None),
-            Err(e) => ("unknown".to_string(), Some(e)),
+            Err(e) => ("unknown".to_string(), Some(e.message)),
         };
 
+    // Probing encoders test-encodes a frame and may run ffmpeg
+    let (h264_encoders, encoder_error) = tokio::task::spawn_blocking(h264_encoder_status)
+        .await
+        .unwrap_or_else(|e| (Vec::new(), Some(format!("Encoder probe failed: {e}"))));
+    if let Some(error) = &encoder_error {
+        log::warn!("Software H.264 encoding unavailable: {error}");
+    }
+
     let diagnostics = SystemDiagnostics {
         crate_version,
         platform: platform.as_str().to_string(),
@@ -289,7 +432,9 @@ pub async fn get_system_diagnostics() -> Result<SystemDiagnostics, String> {
         camera_count,
         cameras: camera_summaries,
         permission_status,
-        features_enabled: get_enabled_features(),
+        features_enabled: enabled_features(),
+        h264_encoders,
+        encoder_error,
         timestamp: chrono::Utc::now().to_rfc3339(),
         platform_info_error,
         camera_enumeration_error,
@@ -323,6 +468,13 @@ pub struct SystemDiagnostics {
     pub permission_status: String,
     /// List of enabled cargo features compiled into this build.
     pub features_enabled: Vec<String>,
+    /// H.264 encoders usable for recording, preferred first (e.g.
+    /// `["VaApi", "OpenH264"]`); empty without the `recording` feature.
+    #[serde(default)]
+    pub h264_encoders: Vec<String>,
+    /// Why openh264 cannot be used on this machine, if it cannot.
+    #[serde(default)]
+    pub encoder_error: Option<String>,
     /// ISO 8601 timestamp of the diagnostics report.
     pub timestamp: String,
     /// Error from platform info query, if any.
@@ -333,6 +485,29 @@ pub struct SystemDiagnostics {
     pub permission_error: Option<String>,
 }
 
+/// Usable H.264 encoders, preferred first, and why openh264 is missing
+/// from them
+fn h264_encoder_status() -> (Vec<String>, Option<String>) {
+    #[cfg(feature = "recording")]
+    {
+        use crate::recording::{EncoderBackend, H264Encoder};
+
+        #[allow(unused_mut)] // only extended with the hwenc feature
+        let mut encoders = Vec::new();
+        #[cfg(feature = "hwenc")]
+        encoders.extend(crate::recording::probe_hardware_encoder().map(|b| format!("{b:?}")));
+        let error = H264Encoder::unavailable_reason().map(|reason| format!("openh264: {reason}"));
+        if error.is_none() {
+            encoders.push(format!("{:?}", EncoderBackend::OpenH264));
+        }
+        (encoders, error)
+    }
+    #[cfg(not(feature = "recording"))]
+    {
+        (Vec::new(), None)
+    }
+}
+
 /// Summary of a camera device
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CameraSummary {
@@ -346,21 +521,64 @@ pub struct CameraSummary {
     pub format_count: usize,
     /// Maximum supported resolution (width, height), if any.
     pub max_resolution: Option<(u32, u32)>,
+    /// Capture API the camera is open with; `None` if it is not open.
+    pub backend: Option<CameraBackend>,
 }
 
+/// Optional Cargo features and whether this build has them
+const CARGO_FEATURES: &[(&str, bool)] = &[
+    ("tauri", cfg!(feature = "tauri")),
+    ("recording", cfg!(feature = "recording")),
+    ("audio", cfg!(feature = "audio")),
+    ("headless", cfg!(feature = "headless")),
+    ("contextlite", cfg!(feature = "contextlite")),
+    ("face-detect", cfg!(feature = "face-detect")),
+    ("http-stream", cfg!(feature = "http-stream")),
+    ("rtsp", cfg!(feature = "rtsp")),
+    ("gpu", cfg!(feature = "gpu")),
+    ("hwenc", cfg!(feature = "hwenc")),
+    ("screen-capture", cfg!(feature = "screen-capture")),
+    ("sha256", cfg!(feature = "sha256")),
+    ("bench", cfg!(feature = "bench")),
+    // Removed in 0.7.0; kept so older frontends get FEATURE_DISABLED
+    ("webrtc", false),
+];
+
 /// Get list of Cargo features compiled into this build.
-fn get_enabled_features() -> Vec<String> {
-    [
-        ("tauri", cfg!(feature = "tauri")),
-        ("recording", cfg!(feature = "recording")),
-        ("audio", cfg!(feature = "audio")),
-        ("headless", cfg!(feature = "headless")),
-        ("contextlite", cfg!(feature = "contextlite")),
-    ]
-    .into_iter()
-    .filter(|(_, enabled)| *enabled)
-    .map(|(name, _)| name.to_string())
-    .collect()
+fn enabled_features() -> Vec<String> {
+    CARGO_FEATURES
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| (*name).to_string())
+        .collect()
+}
+
+/// List the optional Cargo features this build was compiled with
+///
+/// Commands of a disabled feature fail with `FEATURE_DISABLED` (or, for
+/// features without registered stand-ins, Tauri's generic "command not
+/// found"). Frontends can call this once at startup and hide the UI of
+/// features missing from the list.
+#[command]
+pub async fn get_enabled_features() -> Vec<String> {
+    enabled_features()
+}
+
+/// Check that `feature` is compiled in before using its commands
+///
+/// # Errors
+/// Returns a `FEATURE_DISABLED` error naming the feature if this build was
+/// compiled without it, or `INVALID_ARGUMENT` for a name that is not an
+/// optional feature of crabcamera.
+#[command]
+pub async fn require_feature(feature: String) -> Result<(), CommandError> {
+    match CARGO_FEATURES.iter().find(|(name, _)| *name == feature) {
+        Some((_, true)) => Ok(()),
+        Some((_, false)) => Err(CameraError::FeatureDisabled(feature).into()),
+        None => Err(CommandError::invalid_argument(format!(
+            "Unknown feature: {feature}"
+        ))),
+    }
 }
 
 #[cfg(test)]
@@ -378,6 +596,14 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_check_camera_in_use_unknown_camera_is_free() {
+        let in_use = check_camera_in_use("/dev/no-such-camera-911".to_string())
+            .await
+            .expect("in-use check should run");
+        assert!(!in_use);
+    }
+
     #[tokio::test]
     async fn test_get_recommended_format_has_valid_shape() {
         let format = get_recommended_format()
@@ -389,6 +615,12 @@ mod tests {
         assert!(!format.format_type.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_get_max_resolution_format_unknown_camera() {
+        let result = get_max_resolution_format("no-such-camera-911".to_string()).await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_get_optimal_settings_has_valid_shape() {
         let params = get_optimal_settings()
@@ -402,7 +634,7 @@ mod tests {
 
     #[test]
     fn test_get_enabled_features_contains_recording_when_enabled() {
-        let features = get_enabled_features();
+        let features = enabled_features();
 
         #[cfg(feature = "recording")]
         assert!(features.iter().any(|f| f == "recording"));
@@ -411,6 +643,20 @@ mod tests {
         assert!(!features.iter().any(|f| f == "recording"));
     }
 
+    #[tokio::test]
+    async fn test_require_feature_reports_disabled_features() {
+        let err = require_feature("webrtc".to_string()).await.unwrap_err();
+        assert_eq!(err.code, "FEATURE_DISABLED");
+        assert!(err.message.contains("`webrtc`"));
+
+        let tauri = require_feature("tauri".to_string()).await;
+        assert_eq!(tauri.is_ok(), cfg!(feature = "tauri"));
+        assert_eq!(get_enabled_features().await, enabled_features());
+
+        let err = require_feature("teleport".to_string()).await.unwrap_err();
+        assert_eq!(err.code, CommandError::INVALID_ARGUMENT);
+    }
+
     #[tokio::test]
     async fn test_system_diagnostics_shape() {
         let diagnostics = get_system_diagnostics()
@@ -430,5 +676,11 @@ mod tests {
             assert!(!cam.id.is_empty());
             assert!(!cam.name.is_empty());
         }
+
+        #[cfg(feature = "recording")]
+        assert!(diagnostics.h264_encoders.iter().any(|e| e == "OpenH264"));
+        #[cfg(not(feature = "recording"))]
+        assert!(diagnostics.h264_encoders.is_empty());
+        assert!(diagnostics.encoder_error.is_none());
     }
 }
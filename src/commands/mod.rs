@@ -21,5 +21,21 @@ pub mod quality;
 #[cfg(feature = "recording")]
 pub mod recording;
 
+/// MJPEG-over-HTTP and RTSP stream servers.
+#[cfg(any(feature = "http-stream", feature = "rtsp"))]
+pub mod stream;
+
 #[cfg(feature = "audio")]
 pub mod audio;
+
+/// `FEATURE_DISABLED` stand-ins for commands of compiled-out features.
+#[cfg(all(
+    feature = "tauri",
+    not(all(
+        feature = "recording",
+        feature = "http-stream",
+        feature = "rtsp",
+        feature = "audio"
+    ))
+))]
+pub mod unavailable;
@@ -1,8 +1,13 @@
 #[cfg(target_os = "macos")]
-use crate::constants::AV_MEDIA_TYPE_VIDEO;
-#[cfg(target_os = "macos")]
 use crate::constants::PERMISSION_REQUEST_TIMEOUT_SECS;
-use crate::permissions::{check_permission_detailed, PermissionInfo, PermissionStatus};
+#[cfg(target_os = "macos")]
+use crate::constants::{AV_MEDIA_TYPE_AUDIO, AV_MEDIA_TYPE_VIDEO};
+use crate::errors::CommandError;
+use crate::permissions::{
+    check_microphone_permission_detailed, check_permission_detailed, get_permission_guidance,
+    open_privacy_settings, PermissionGuidance, PermissionInfo, PermissionStatus,
+};
+use crate::types::Platform;
 use tauri::command;
 
 /// Request camera permission (platform-specific)
@@ -11,7 +16,7 @@ use tauri::command;
 /// Returns an `Err` if the current platform is not supported, or, on macOS,
 /// if `AVFoundation` is unavailable or the permission request times out.
 #[command]
-pub async fn request_camera_permission() -> Result<PermissionInfo, String> {
+pub async fn request_camera_permission() -> Result<PermissionInfo, CommandError> {
     log::info!("Requesting camera permission");
 
     let current_status = check_permission_detailed();
@@ -29,7 +34,7 @@ pub async fn request_camera_permission() -> Result<PermissionInfo, String> {
     // Platform-specific permission request
     #[cfg(target_os = "macos")]
     {
-        request_permission_macos().await
+        request_permission_macos(AV_MEDIA_TYPE_VIDEO, "Camera").await
     }
 
     #[cfg(target_os = "windows")]
@@ -57,13 +62,20 @@ pub async fn request_camera_permission() -> Result<PermissionInfo, String> {
 
     #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
     {
-        Err("Platform not supported".to_string())
+        Err(CommandError::new(
+            "UNSUPPORTED_OPERATION",
+            "Platform not supported",
+        ))
     }
 }
 
+/// Show the `AVFoundation` consent prompt for a media type (`vide` or `soun`)
 #[cfg(target_os = "macos")]
 #[allow(clippy::unused_async)]
-async fn request_permission_macos() -> Result<PermissionInfo, String> {
+async fn request_permission_macos(
+    media_type_code: &str,
+    device: &str,
+) -> Result<PermissionInfo, CommandError> {
     use block::ConcreteBlock;
     use objc::runtime::{Class, Object};
     use objc::{msg_send, sel, sel_impl};
@@ -71,20 +83,23 @@ async fn request_permission_macos() -> Result<PermissionInfo, String> {
     use std::sync::mpsc;
     use std::time::Duration;
 
-    log::info!("Requesting macOS camera permission");
+    log::info!("Requesting macOS {device} permission");
 
     unsafe {
-        let av_capture_device_class =
-            Class::get("AVCaptureDevice").ok_or("AVFoundation not available")?;
+        let av_capture_device_class = Class::get("AVCaptureDevice").ok_or_else(|| {
+            CommandError::new("UNSUPPORTED_OPERATION", "AVFoundation not available")
+        })?;
 
         // Build an NSString for the video media type (AVMediaTypeVideo == @"vide").
         // AVCaptureDevice has no `mediaTypeForString:` selector; sending it raises
         // an unrecognized-selector NSException which aborts the process.
-        let ns_string_class = Class::get("NSString").ok_or("Foundation not available")?;
-        let av_media_type_video =
-            CString::new(AV_MEDIA_TYPE_VIDEO).map_err(|_| "Invalid media type string")?;
+        let ns_string_class = Class::get("NSString").ok_or_else(|| {
+            CommandError::new("UNSUPPORTED_OPERATION", "Foundation not available")
+        })?;
+        let av_media_type = CString::new(media_type_code)
+            .map_err(|_| CommandError::system("Invalid media type string"))?;
         let media_type: *mut Object =
-            msg_send![ns_string_class, stringWithUTF8String: av_media_type_video.as_ptr()];
+            msg_send![ns_string_class, stringWithUTF8String: av_media_type.as_ptr()];
 
         let (tx, rx) = mpsc::channel();
 
@@ -101,24 +116,27 @@ async fn request_permission_macos() -> Result<PermissionInfo, String> {
         let _: () = msg_send![av_capture_device_class, requestAccessForMediaType:media_type completionHandler:&*handler]; // Wait for user response (with timeout)
         match rx.recv_timeout(Duration::from_secs(PERMISSION_REQUEST_TIMEOUT_SECS)) {
             Ok(granted) if granted => {
-                log::info!("Camera permission granted");
+                log::info!("{device} permission granted");
                 Ok(PermissionInfo {
                     status: PermissionStatus::Granted,
-                    message: "Camera access authorized".to_string(),
+                    message: format!("{device} access authorized"),
                     can_request: false,
                 })
             }
             Ok(_) => {
-                log::warn!("Camera permission denied");
+                log::warn!("{device} permission denied");
                 Ok(PermissionInfo {
                     status: PermissionStatus::Denied,
-                    message: "Camera access denied by user".to_string(),
+                    message: format!("{device} access denied by user"),
                     can_request: false,
                 })
             }
             Err(_) => {
                 log::error!("Permission request timed out");
-                Err("Permission request timed out".to_string())
+                Err(CommandError::new(
+                    "PERMISSION_DENIED",
+                    "Permission request timed out",
+                ))
             }
         }
     }
@@ -129,11 +147,62 @@ async fn request_permission_macos() -> Result<PermissionInfo, String> {
 /// # Errors
 /// This function always succeeds and never returns an `Err`.
 #[command]
-pub async fn check_camera_permission_status() -> Result<PermissionInfo, String> {
+pub async fn check_camera_permission_status() -> Result<PermissionInfo, CommandError> {
     log::debug!("Checking camera permission status");
     Ok(check_permission_detailed())
 }
 
+/// Request microphone permission (platform-specific)
+///
+/// # Errors
+/// Returns an `Err` if the current platform is not supported, or, on macOS,
+/// if `AVFoundation` is unavailable or the permission request times out.
+#[command]
+pub async fn request_microphone_permission() -> Result<PermissionInfo, CommandError> {
+    log::info!("Requesting microphone permission");
+
+    let current_status = check_microphone_permission_detailed();
+
+    if current_status.status == PermissionStatus::Granted {
+        log::info!("Microphone permission already granted");
+        return Ok(current_status);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        if !current_status.can_request {
+            log::warn!("Cannot request permission: {}", current_status.message);
+            return Ok(current_status);
+        }
+        request_permission_macos(AV_MEDIA_TYPE_AUDIO, "Microphone").await
+    }
+
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    {
+        // No programmatic prompt: Windows consent is changed in Settings >
+        // Privacy & security > Microphone, and Linux does not gate access.
+        Ok(current_status)
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    {
+        Err(CommandError::new(
+            "UNSUPPORTED_OPERATION",
+            "Platform not supported",
+        ))
+    }
+}
+
+/// Check microphone permission status
+///
+/// # Errors
+/// This function always succeeds and never returns an `Err`.
+#[command]
+pub async fn check_microphone_permission_status() -> Result<PermissionInfo, CommandError> {
+    log::debug!("Checking microphone permission status");
+    Ok(check_microphone_permission_detailed())
+}
+
 /// Get human-readable permission status string (legacy compatibility)
 #[command]
 pub fn get_permission_status_string() -> String {
@@ -141,6 +210,22 @@ pub fn get_permission_status_string() -> String {
     format!("{:?}", info.status)
 }
 
+/// Get instructions for granting camera access on the current platform
+#[command]
+pub fn get_camera_permission_guidance() -> PermissionGuidance {
+    get_permission_guidance(Platform::current())
+}
+
+/// Open the OS camera privacy settings pane
+///
+/// # Errors
+/// Returns an `Err` if the platform has no settings deep link or the settings
+/// app cannot be launched.
+#[command]
+pub async fn open_camera_privacy_settings() -> Result<(), CommandError> {
+    open_privacy_settings().map_err(CommandError::from)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,6 +245,21 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_check_microphone_permission_status_shape() {
+        let info = check_microphone_permission_status()
+            .await
+            .expect("microphone status should return info");
+        assert!(!info.message.is_empty());
+    }
+
+    #[test]
+    fn test_permission_guidance_matches_current_platform() {
+        let guidance = get_camera_permission_guidance();
+        assert_eq!(guidance.platform, Platform::current());
+        assert!(!guidance.settings_path.is_empty());
+    }
+
     #[test]
     fn test_permission_status_string_is_known_debug_variant() {
         let status = get_permission_status_string();
@@ -2,6 +2,7 @@ use std::sync::Arc;
 use tauri::command;
 use tauri::Runtime;
 
+use crate::errors::CommandError;
 use crate::preview::{PreviewConfig, PreviewStream};
 
 static PREVIEW_HANDLE: tokio::sync::RwLock<Option<Arc<PreviewStream>>> =
@@ -19,7 +20,7 @@ pub async fn start_preview_stream<R: Runtime>(
     downscale: f32,
     jpeg_quality: u8,
     app: tauri::AppHandle<R>,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     let config = PreviewConfig {
         fps_target,
         downscale,
@@ -34,7 +35,7 @@ pub async fn start_preview_stream<R: Runtime>(
         crate::types::CameraFormat::standard(),
     )
     .await
-    .map_err(|e| format!("Failed to get camera: {e}"))?;
+    .map_err(|e| CommandError::new(e.error_code(), format!("Failed to get camera: {e}")))?;
 
     stream.start(
         camera.clone(),
@@ -56,13 +57,13 @@ pub async fn start_preview_stream<R: Runtime>(
 /// # Errors
 /// Returns an `Err` if there is no active preview stream.
 #[command]
-pub async fn stop_preview_stream() -> Result<String, String> {
+pub async fn stop_preview_stream() -> Result<String, CommandError> {
     let mut guard = PREVIEW_HANDLE.write().await;
     if let Some(ref stream) = *guard {
         stream.stop();
         *guard = None;
         Ok("preview_stopped".to_string())
     } else {
-        Err("No active preview stream".to_string())
+        Err(CommandError::not_found("No active preview stream"))
     }
 }
@@ -1,7 +1,10 @@
-use crate::commands::capture::capture_single_photo;
+use crate::commands::capture::{capture_photo_sequence, capture_single_photo};
 #[cfg(test)]
 use crate::constants::*;
-use crate::quality::{BlurDetector, BlurMetrics, ExposureAnalyzer, ExposureMetrics};
+use crate::errors::CommandError;
+use crate::quality::{
+    auto_enhance, temporal_denoise, BlurDetector, BlurMetrics, ExposureAnalyzer, ExposureMetrics,
+};
 use crate::quality::{QualityReport, QualityValidator, ValidationConfig};
 use crate::types::CameraFrame;
 use std::sync::{Arc, LazyLock};
@@ -21,11 +24,11 @@ static QUALITY_VALIDATOR: LazyLock<Arc<RwLock<QualityValidator>>> =
 pub async fn validate_frame_quality(
     device_id: Option<String>,
     capture_format: Option<crate::types::CameraFormat>,
-) -> Result<QualityReport, String> {
+) -> Result<QualityReport, CommandError> {
     log::info!("Validating frame quality for device: {device_id:?}");
 
     // Capture a frame first
-    let frame = capture_single_photo(device_id, capture_format).await?;
+    let frame = capture_single_photo(device_id, capture_format, None).await?;
 
     // Validate quality
     let validator = QUALITY_VALIDATOR.read().await;
@@ -39,7 +42,7 @@ pub async fn validate_frame_quality(
 /// # Errors
 /// This function always succeeds and never returns an `Err`.
 #[command]
-pub async fn validate_provided_frame(frame: CameraFrame) -> Result<QualityReport, String> {
+pub async fn validate_provided_frame(frame: CameraFrame) -> Result<QualityReport, CommandError> {
     log::info!(
         "Validating provided frame: {}x{}",
         frame.width,
@@ -61,11 +64,11 @@ pub async fn validate_provided_frame(frame: CameraFrame) -> Result<QualityReport
 pub async fn analyze_frame_blur(
     device_id: Option<String>,
     capture_format: Option<crate::types::CameraFormat>,
-) -> Result<BlurMetrics, String> {
+) -> Result<BlurMetrics, CommandError> {
     log::info!("Analyzing frame blur for device: {device_id:?}");
 
     // Capture a frame
-    let frame = capture_single_photo(device_id, capture_format).await?;
+    let frame = capture_single_photo(device_id, capture_format, None).await?;
 
     // Analyze blur
     let blur_detector = BlurDetector::default();
@@ -83,11 +86,11 @@ pub async fn analyze_frame_blur(
 pub async fn analyze_frame_exposure(
     device_id: Option<String>,
     capture_format: Option<crate::types::CameraFormat>,
-) -> Result<ExposureMetrics, String> {
+) -> Result<ExposureMetrics, CommandError> {
     log::info!("Analyzing frame exposure for device: {device_id:?}");
 
     // Capture a frame
-    let frame = capture_single_photo(device_id, capture_format).await?;
+    let frame = capture_single_photo(device_id, capture_format, None).await?;
 
     // Analyze exposure
     let exposure_analyzer = ExposureAnalyzer::default();
@@ -96,12 +99,56 @@ pub async fn analyze_frame_exposure(
     Ok(metrics)
 }
 
+/// Detect faces in a captured frame (best-effort)
+///
+/// Returns bounding boxes with the detector's confidence. Requires a face model
+/// configured via [`crate::quality::detect::set_face_model_path`] or the
+/// `CRABCAMERA_FACE_MODEL` environment variable.
+///
+/// # Errors
+/// Returns an `Err` if the frame cannot be captured, no face model is
+/// configured or loadable, or the blocking task fails to join.
+#[cfg(feature = "face-detect")]
+#[command]
+pub async fn detect_faces_in_frame(
+    device_id: Option<String>,
+    capture_format: Option<crate::types::CameraFormat>,
+) -> Result<Vec<crate::quality::detect::DetectedFace>, CommandError> {
+    log::info!("Detecting faces for device: {device_id:?}");
+
+    let frame = capture_single_photo(device_id, capture_format, None).await?;
+
+    tokio::task::spawn_blocking(move || {
+        crate::quality::detect::detect_faces_with_confidence(&frame)
+            .map_err(|e| CommandError::new(e.error_code(), format!("Face detection failed: {e}")))
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+}
+
+/// Enable or disable automatic face detection on frames captured from a device
+///
+/// While enabled, captured frames carry detected faces in `metadata.faces`.
+///
+/// # Errors
+/// This function always succeeds and never returns an `Err`.
+#[cfg(feature = "face-detect")]
+#[command]
+pub async fn set_face_detection_enabled(
+    device_id: String,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    log::info!("Automatic face detection for {device_id}: {enabled}");
+    crate::quality::detect::set_auto_detect(&device_id, enabled);
+    Ok(())
+}
+
 /// Update quality validation configuration
 ///
 /// # Errors
 /// This function always succeeds and never returns an `Err`.
 #[command]
-pub async fn update_quality_config(config: ValidationConfigDto) -> Result<String, String> {
+pub async fn update_quality_config(config: ValidationConfigDto) -> Result<String, CommandError> {
     log::info!("Updating quality validation configuration");
 
     let validation_config = ValidationConfig {
@@ -124,7 +171,7 @@ pub async fn update_quality_config(config: ValidationConfigDto) -> Result<String
 /// # Errors
 /// This function always succeeds and never returns an `Err`.
 #[command]
-pub async fn get_quality_config() -> Result<ValidationConfigDto, String> {
+pub async fn get_quality_config() -> Result<ValidationConfigDto, CommandError> {
     let validator = QUALITY_VALIDATOR.read().await;
     let config = validator.config();
 
@@ -147,7 +194,7 @@ pub async fn capture_best_quality_frame(
     device_id: Option<String>,
     capture_format: Option<crate::types::CameraFormat>,
     num_attempts: Option<u32>,
-) -> Result<CaptureQualityResult, String> {
+) -> Result<CaptureQualityResult, CommandError> {
     let attempts = num_attempts.unwrap_or(5).min(10); // Max 10 attempts
     log::info!("Capturing best quality frame with {attempts} attempts");
 
@@ -160,7 +207,7 @@ pub async fn capture_best_quality_frame(
         log::debug!("Quality capture attempt {attempt} of {attempts}");
 
         // Capture frame
-        match capture_single_photo(device_id.clone(), capture_format.clone()).await {
+        match capture_single_photo(device_id.clone(), capture_format.clone(), None).await {
             Ok(frame) => {
                 // Validate quality
                 let report = validator.validate_frame(&frame);
@@ -195,7 +242,10 @@ pub async fn capture_best_quality_frame(
             quality_report: report,
             attempts_used: attempts,
         }),
-        _ => Err("Failed to capture any valid frames".to_string()),
+        _ => Err(CommandError::new(
+            "CAPTURE_FAILED",
+            "Failed to capture any valid frames",
+        )),
     }
 }
 
@@ -211,7 +261,7 @@ pub async fn auto_capture_with_quality(
     min_quality_threshold: Option<f32>,
     max_attempts: Option<u32>,
     timeout_seconds: Option<u32>,
-) -> Result<CaptureQualityResult, String> {
+) -> Result<CaptureQualityResult, CommandError> {
     let quality_threshold = min_quality_threshold.unwrap_or(0.7);
     let max_tries = max_attempts.unwrap_or(20).min(50); // Max 50 attempts
     let timeout = timeout_seconds.unwrap_or(30); // 30 second timeout
@@ -226,13 +276,16 @@ pub async fn auto_capture_with_quality(
     for attempt in 1..=max_tries {
         // Check timeout
         if start_time.elapsed().as_secs() >= u64::from(timeout) {
-            return Err(format!("Auto-capture timeout after {timeout} seconds"));
+            return Err(CommandError::new(
+                "CAPTURE_FAILED",
+                format!("Auto-capture timeout after {timeout} seconds"),
+            ));
         }
 
         log::debug!("Auto-capture attempt {attempt} of {max_tries}");
 
         // Capture frame
-        match capture_single_photo(device_id.clone(), capture_format.clone()).await {
+        match capture_single_photo(device_id.clone(), capture_format.clone(), None).await {
             Ok(frame) => {
                 // Validate quality
                 let report = validator.validate_frame(&frame);
@@ -265,11 +318,67 @@ pub async fn auto_capture_with_quality(
         tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
     }
 
-    Err(format!(
-        "Failed to capture frame meeting quality threshold {quality_threshold} after {max_tries} attempts"
+    Err(CommandError::new(
+        "CAPTURE_FAILED",
+        format!(
+            "Failed to capture frame meeting quality threshold {quality_threshold} after {max_tries} attempts"
+        ),
     ))
 }
 
+/// Capture a frame and brighten it if it is under-exposed
+///
+/// `strength` (0.0-1.0) defaults to `ENHANCE_DEFAULT_STRENGTH`. The
+/// enhancement is perceptual, not a physically accurate exposure correction;
+/// see [`crate::quality::enhance`].
+///
+/// # Errors
+/// Returns an `Err` if the frame cannot be captured or the enhancement task
+/// fails to complete.
+#[command]
+pub async fn auto_capture_with_enhancement(
+    device_id: Option<String>,
+    capture_format: Option<crate::types::CameraFormat>,
+    strength: Option<f32>,
+) -> Result<CameraFrame, CommandError> {
+    let strength = strength.unwrap_or(crate::constants::ENHANCE_DEFAULT_STRENGTH);
+    let frame = capture_single_photo(device_id, capture_format, None).await?;
+
+    tokio::task::spawn_blocking(move || auto_enhance(frame, strength))
+        .await
+        .map_err(|e| CommandError::system(format!("Task join error: {e}")))
+}
+
+/// Capture `n_frames` frames of a static scene and average them to reduce noise
+///
+/// Intended for low-light stills of a stationary subject; regions that move
+/// between frames keep the first frame's pixels. See
+/// [`crate::quality::denoise`].
+///
+/// # Errors
+/// Returns an `Err` if `n_frames` is outside `2..=CAPTURE_SEQUENCE_MAX_COUNT`,
+/// if the sequence cannot be captured, or if the frames cannot be merged.
+#[command]
+pub async fn capture_denoised(
+    device_id: String,
+    n_frames: u32,
+    format: Option<crate::types::CameraFormat>,
+) -> Result<CameraFrame, CommandError> {
+    let max = crate::constants::CAPTURE_SEQUENCE_MAX_COUNT;
+    if !(2..=max).contains(&n_frames) {
+        return Err(CommandError::invalid_argument(format!(
+            "Invalid frame count (must be 2-{max})"
+        )));
+    }
+
+    let frames = capture_photo_sequence(device_id, n_frames, 0, format, None).await?;
+
+    tokio::task::spawn_blocking(move || temporal_denoise(&frames))
+        .await
+        .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+        .map_err(CommandError::from)
+}
+
 /// Analyze quality trends over multiple captures
 ///
 /// # Errors
@@ -280,7 +389,7 @@ pub async fn analyze_quality_trends(
     device_id: Option<String>,
     capture_format: Option<crate::types::CameraFormat>,
     num_samples: Option<u32>,
-) -> Result<QualityTrendAnalysis, String> {
+) -> Result<QualityTrendAnalysis, CommandError> {
     let samples = num_samples.unwrap_or(10).min(20); // Max 20 samples
     log::info!("Analyzing quality trends over {samples} samples");
 
@@ -290,7 +399,7 @@ pub async fn analyze_quality_trends(
     for i in 1..=samples {
         log::debug!("Quality trend sample {i} of {samples}");
 
-        match capture_single_photo(device_id.clone(), capture_format.clone()).await {
+        match capture_single_photo(device_id.clone(), capture_format.clone(), None).await {
             Ok(frame) => {
                 let report = validator.validate_frame(&frame);
                 reports.push(report);
@@ -306,7 +415,10 @@ pub async fn analyze_quality_trends(
     }
 
     if reports.is_empty() {
-        return Err("No valid samples captured for trend analysis".to_string());
+        return Err(CommandError::new(
+            "CAPTURE_FAILED",
+            "No valid samples captured for trend analysis",
+        ));
     }
 
     // Calculate trend statistics
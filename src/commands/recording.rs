@@ -1,19 +1,25 @@
 //! Tauri commands for video recording
 //!
 //! These commands provide an interface for recording video from cameras.
+//! [`start_camera_recording`] does everything in the background; the
+//! session commands ([`start_recording`], [`record_frame`],
+//! [`stop_recording`]) leave frame pacing to the caller.
 
 use std::collections::HashMap;
 use std::sync::{Arc, LazyLock, Mutex as SyncMutex};
 use tauri::command;
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 
 #[cfg(feature = "audio")]
 use crate::constants::{AUDIO_BITRATE, AUDIO_CHANNELS, AUDIO_DEVICE_DEFAULT, AUDIO_SAMPLE_RATE};
 use crate::constants::{
-    DEFAULT_CAMERA_ID, RECORDING_QUALITY_PRESET_1080P, RECORDING_QUALITY_PRESET_4K,
-    RECORDING_QUALITY_PRESET_720P, RECORDING_QUALITY_PRESET_HIGH, RECORDING_QUALITY_PRESET_LOW,
-    RECORDING_QUALITY_PRESET_MEDIUM, RECORDING_SESSION_PREFIX,
+    CAMERA_RECORDING_MAX_FAILURES, CAMERA_RECORDING_PAUSE_POLL_MS, DEFAULT_CAMERA_ID,
+    RECORDING_QUALITY_PRESET_1080P, RECORDING_QUALITY_PRESET_4K, RECORDING_QUALITY_PRESET_720P,
+    RECORDING_QUALITY_PRESET_HIGH, RECORDING_QUALITY_PRESET_LOW, RECORDING_QUALITY_PRESET_MEDIUM,
+    RECORDING_SESSION_PREFIX,
 };
+use crate::errors::{CameraError, CommandError};
 use crate::platform::PlatformCamera;
 use crate::recording::{Recorder, RecordingConfig, RecordingQuality, RecordingStats};
 use crate::types::CameraFormat;
@@ -31,11 +37,23 @@ struct RecordingSession {
     is_running: bool,
 }
 
+/// Background recording started by [`start_camera_recording`]
+struct CameraRecording {
+    cancel: CancellationToken,
+    task: tokio::task::JoinHandle<Result<RecordingStats, CameraError>>,
+}
+
+// Background recordings by device id; `None` reserves a device while
+// `start_camera_recording` is still opening it
+static CAMERA_RECORDINGS: LazyLock<SyncMutex<HashMap<String, Option<CameraRecording>>>> =
+    LazyLock::new(|| SyncMutex::new(HashMap::new()));
+
 /// Options for [`start_recording`].
 ///
 /// Grouped into a single struct so the Tauri command takes one argument
 /// (satisfying clippy's `too_many_arguments` limit); the JS `invoke` call
 /// passes a single options object.
+#[derive(Debug, Clone, serde::Deserialize)]
 pub struct RecordingStartOptions {
     /// Camera device ID (or `None` for the default camera).
     pub device_id: Option<String>,
@@ -69,7 +87,7 @@ pub struct RecordingStartOptions {
 /// be started, if the camera mutex is poisoned, or if the [`Recorder`] cannot
 /// be created.
 #[command]
-pub async fn start_recording(options: RecordingStartOptions) -> Result<String, String> {
+pub async fn start_recording(options: RecordingStartOptions) -> Result<String, CommandError> {
     let RecordingStartOptions {
         device_id,
         output_path,
@@ -100,22 +118,8 @@ pub async fn start_recording(options: RecordingStartOptions) -> Result<String, S
         output_path
     );
 
-    // Parse quality preset
-    let recording_quality = match quality.as_deref() {
-        Some(q) if q == RECORDING_QUALITY_PRESET_LOW || q == RECORDING_QUALITY_PRESET_720P => {
-            Some(RecordingQuality::Low)
-        }
-        Some(q) if q == RECORDING_QUALITY_PRESET_MEDIUM || q == RECORDING_QUALITY_PRESET_1080P => {
-            Some(RecordingQuality::Medium)
-        }
-        Some(q) if q == RECORDING_QUALITY_PRESET_HIGH || q == RECORDING_QUALITY_PRESET_4K => {
-            Some(RecordingQuality::High)
-        }
-        _ => None,
-    };
-
     // Build recording config
-    let mut config = if let Some(q) = recording_quality {
+    let mut config = if let Some(q) = parse_quality(quality.as_deref()) {
         RecordingConfig::from_quality_with_fps(q, fps)
     } else {
         RecordingConfig::new(width, height, fps)
@@ -135,9 +139,11 @@ pub async fn start_recording(options: RecordingStartOptions) -> Result<String, S
             } else {
                 Some(audio_id)
             },
+            device_kind: crate::audio::DeviceKind::Input,
             sample_rate: AUDIO_SAMPLE_RATE,
             channels: AUDIO_CHANNELS,
             bitrate: AUDIO_BITRATE,
+            auto_fallback: false,
         });
     }
 
@@ -150,20 +156,37 @@ pub async fn start_recording(options: RecordingStartOptions) -> Result<String, S
         CameraFormat::new(config.width, config.height, fps_f32),
     )
     .await
-    .map_err(|e| format!("Failed to initialize camera: {e}"))?;
+    .map_err(|e| CommandError::new(e.error_code(), format!("Failed to initialize camera: {e}")))?;
 
     // Start camera stream
     {
         let mut cam = camera
             .lock()
-            .map_err(|_| "Camera mutex poisoned".to_string())?;
-        cam.start_stream()
-            .map_err(|e| format!("Failed to start camera stream: {e}"))?;
+            .map_err(|_| CommandError::system("Camera mutex poisoned"))?;
+        cam.start_stream().map_err(|e| {
+            CommandError::new(
+                e.error_code(),
+                format!("Failed to start camera stream: {e}"),
+            )
+        })?;
     }
 
     // Create recorder
-    let recorder = Recorder::new(&output_path, config)
-        .map_err(|e| format!("Failed to create recorder: {e}"))?;
+    let mut recorder = Recorder::new(&output_path, config).map_err(|e| {
+        CommandError::new(e.error_code(), format!("Failed to create recorder: {e}"))
+    })?;
+
+    // Pre-roll: wait until the camera delivers, then until audio does, so the
+    // first recorded frame is t=0 of both tracks
+    {
+        let mut cam = camera
+            .lock()
+            .map_err(|_| CommandError::system("Camera mutex poisoned"))?;
+        cam.capture_frame().map_err(|e| {
+            CommandError::new(e.error_code(), format!("Camera produced no frames: {e}"))
+        })?;
+    }
+    recorder.start();
 
     // Generate session ID
     let session_id = format!(
@@ -198,21 +221,22 @@ pub async fn start_recording(options: RecordingStartOptions) -> Result<String, S
 /// camera mutex is poisoned, if recording is not running, if the camera frame
 /// capture fails, if no recorder is available, or if writing the frame fails.
 #[command]
-pub async fn record_frame(session_id: String) -> Result<u64, String> {
+pub async fn record_frame(session_id: String) -> Result<u64, CommandError> {
     let session_arc = {
         let registry = RECORDER_REGISTRY.read().await;
-        registry
-            .get(&session_id)
-            .cloned()
-            .ok_or_else(|| format!("Recording session not found: {session_id}"))?
+        registry.get(&session_id).cloned().ok_or_else(|| {
+            CommandError::not_found(format!("Recording session not found: {session_id}"))
+        })?
     };
 
     let mut session = session_arc
         .lock()
-        .map_err(|_| "Mutex poisoned".to_string())?;
+        .map_err(|_| CommandError::system("Mutex poisoned"))?;
 
     if !session.is_running {
-        return Err("Recording is not running".to_string());
+        return Err(CommandError::invalid_argument(format!(
+            "Recording is not running: {session_id}"
+        )));
     }
 
     // Capture frame from camera
@@ -220,20 +244,20 @@ pub async fn record_frame(session_id: String) -> Result<u64, String> {
         let mut camera = session
             .camera
             .lock()
-            .map_err(|_| "Mutex poisoned".to_string())?;
-        camera
-            .capture_frame()
-            .map_err(|e| format!("Failed to capture frame: {e}"))?
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+        camera.capture_frame().map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to capture frame: {e}"))
+        })?
     };
 
     // Write to recorder
     let recorder = session
         .recorder
         .as_mut()
-        .ok_or_else(|| "Recorder not available".to_string())?;
+        .ok_or_else(|| CommandError::system("Recorder not available"))?;
     recorder
         .write_frame(&frame)
-        .map_err(|e| format!("Failed to write frame: {e}"))?;
+        .map_err(|e| CommandError::new(e.error_code(), format!("Failed to write frame: {e}")))?;
 
     Ok(recorder.frame_count())
 }
@@ -248,26 +272,26 @@ pub async fn record_frame(session_id: String) -> Result<u64, String> {
 /// camera mutex is poisoned, if the recorder has already been taken, or if
 /// finalizing the recording fails.
 #[command]
-pub async fn stop_recording(session_id: String) -> Result<RecordingStats, String> {
+pub async fn stop_recording(session_id: String) -> Result<RecordingStats, CommandError> {
     // Remove session from registry
     let session_arc = {
         let mut registry = RECORDER_REGISTRY.write().await;
-        registry
-            .remove(&session_id)
-            .ok_or_else(|| format!("Recording session not found: {session_id}"))?
+        registry.remove(&session_id).ok_or_else(|| {
+            CommandError::not_found(format!("Recording session not found: {session_id}"))
+        })?
     };
 
     // Get exclusive access and stop
     let mut session = session_arc
         .lock()
-        .map_err(|_| "Mutex poisoned".to_string())?;
+        .map_err(|_| CommandError::system("Mutex poisoned"))?;
 
     // Stop camera stream
     {
         let mut camera = session
             .camera
             .lock()
-            .map_err(|_| "Camera mutex poisoned".to_string())?;
+            .map_err(|_| CommandError::system("Camera mutex poisoned"))?;
         let _ = camera.stop_stream();
     }
 
@@ -275,9 +299,11 @@ pub async fn stop_recording(session_id: String) -> Result<RecordingStats, String
     let stats = session
         .recorder
         .take()
-        .ok_or_else(|| "Recorder already taken".to_string())?
+        .ok_or_else(|| CommandError::system("Recorder already taken"))?
         .finish()
-        .map_err(|e| format!("Failed to finalize recording: {e}"))?;
+        .map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to finalize recording: {e}"))
+        })?;
 
     log::info!(
         "Recording stopped: {} frames, {:.2}s, {} bytes",
@@ -295,23 +321,22 @@ pub async fn stop_recording(session_id: String) -> Result<RecordingStats, String
 /// Returns an `Err` if the recording session is not found, or if the session
 /// or camera mutex is poisoned, or if no recorder is available.
 #[command]
-pub async fn get_recording_status(session_id: String) -> Result<RecordingStatus, String> {
+pub async fn get_recording_status(session_id: String) -> Result<RecordingStatus, CommandError> {
     let session_arc = {
         let registry = RECORDER_REGISTRY.read().await;
-        registry
-            .get(&session_id)
-            .cloned()
-            .ok_or_else(|| format!("Recording session not found: {session_id}"))?
+        registry.get(&session_id).cloned().ok_or_else(|| {
+            CommandError::not_found(format!("Recording session not found: {session_id}"))
+        })?
     };
 
     let session = session_arc
         .lock()
-        .map_err(|_| "Mutex poisoned".to_string())?;
+        .map_err(|_| CommandError::system("Mutex poisoned"))?;
 
     let recorder = session
         .recorder
         .as_ref()
-        .ok_or_else(|| "Recorder not available".to_string())?;
+        .ok_or_else(|| CommandError::system("Recorder not available"))?;
 
     // Build audio status if audio feature enabled
     #[cfg(feature = "audio")]
@@ -340,11 +365,242 @@ pub async fn get_recording_status(session_id: String) -> Result<RecordingStatus,
 /// # Errors
 /// This function always succeeds and never returns an `Err`.
 #[command]
-pub async fn list_recording_sessions() -> Result<Vec<String>, String> {
+pub async fn list_recording_sessions() -> Result<Vec<String>, CommandError> {
     let registry = RECORDER_REGISTRY.read().await;
     Ok(registry.keys().cloned().collect())
 }
 
+/// Record a camera to a file in the background until
+/// [`stop_camera_recording`]
+///
+/// Opens the camera in `format` (the `quality` preset's resolution and
+/// frame rate, or the standard format, when `None`) and feeds every frame
+/// it delivers to a [`Recorder`] on a blocking task. `quality` is a preset
+/// name as for [`start_recording`] and sets the bitrate. The video takes
+/// the size of the frames the camera actually delivers, and frames are
+/// placed by their timestamps at the format's frame rate (see
+/// [`RecordingConfig::with_constant_framerate`]), so a camera faster or
+/// slower than `format.fps` still records at real speed. With the `audio`
+/// feature the default microphone is recorded too unless `audio` is
+/// `false`; if it fails, video carries on alone.
+///
+/// After [`CAMERA_RECORDING_MAX_FAILURES`] consecutive failed captures the
+/// file is finished early; [`stop_camera_recording`] still returns its
+/// statistics. While the camera is paused no frames are written and the
+/// last frame stays on screen.
+///
+/// # Errors
+/// Returns an `Err` if the device is already recording or starting to, the
+/// camera cannot be opened or delivers no frame, or the [`Recorder`] cannot
+/// be created.
+#[command]
+pub async fn start_camera_recording(
+    device_id: String,
+    format: Option<CameraFormat>,
+    output_path: String,
+    quality: Option<String>,
+    audio: Option<bool>,
+) -> Result<String, CommandError> {
+    let quality = parse_quality(quality.as_deref());
+    let format = format.unwrap_or_else(|| {
+        quality.map_or_else(CameraFormat::standard, |q| {
+            let (width, height) = q.resolution();
+            #[allow(clippy::cast_possible_truncation)] // preset rates are 30 or 60
+            CameraFormat::new(width, height, q.fps() as f32)
+        })
+    });
+    // Reserve the device before the recorder truncates `output_path`
+    {
+        let mut recordings = CAMERA_RECORDINGS
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+        if recordings.contains_key(&device_id) {
+            return Err(CommandError::invalid_argument(format!(
+                "Device {device_id} is already recording"
+            )));
+        }
+        recordings.insert(device_id.clone(), None);
+    }
+
+    let started =
+        spawn_camera_recording(device_id.clone(), format, output_path, quality, audio).await;
+    let mut recordings = CAMERA_RECORDINGS
+        .lock()
+        .map_err(|_| CommandError::system("Mutex poisoned"))?;
+    match started {
+        Ok(recording) => {
+            recordings.insert(device_id.clone(), Some(recording));
+            log::info!("Background recording started for device {device_id}");
+            Ok(format!("Recording started for device {device_id}"))
+        }
+        Err(e) => {
+            recordings.remove(&device_id);
+            Err(e)
+        }
+    }
+}
+
+/// Open the camera and recorder for [`start_camera_recording`] and start
+/// feeding frames on a blocking task
+async fn spawn_camera_recording(
+    device_id: String,
+    format: CameraFormat,
+    output_path: String,
+    quality: Option<RecordingQuality>,
+    audio: Option<bool>,
+) -> Result<CameraRecording, CommandError> {
+    let camera = super::capture::get_or_create_camera(device_id.clone(), format.clone()).await?;
+    let task_camera = camera.clone();
+    let recorder = tokio::task::spawn_blocking(move || {
+        let mut cam = task_camera
+            .lock()
+            .map_err(|_| CommandError::system("Camera mutex poisoned"))?;
+        cam.start_stream().map_err(|e| {
+            CommandError::new(
+                e.error_code(),
+                format!("Failed to start camera stream: {e}"),
+            )
+        })?;
+        // Size the video from a real frame, which may differ from the request
+        let first = cam.capture_frame().map_err(|e| {
+            CommandError::new(e.error_code(), format!("Camera produced no frames: {e}"))
+        })?;
+
+        let mut config = RecordingConfig::new(first.width, first.height, f64::from(format.fps))
+            .with_constant_framerate(true);
+        if let Some(q) = quality {
+            config = config.with_bitrate(q.bitrate());
+        }
+        #[cfg(feature = "audio")]
+        if audio.unwrap_or(true) {
+            config = config.with_default_audio();
+        }
+        #[cfg(not(feature = "audio"))]
+        let _ = audio;
+
+        let mut recorder = Recorder::new(&output_path, config).map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to create recorder: {e}"))
+        })?;
+        recorder.start();
+        Ok::<Recorder, CommandError>(recorder)
+    })
+    .await
+    .map_err(|e| CommandError::system(format!("Task join error: {e}")))??;
+
+    let cancel = CancellationToken::new();
+    let task_cancel = cancel.clone();
+    let task = tokio::task::spawn_blocking(move || {
+        feed_recorder(&device_id, &camera, recorder, &task_cancel)
+    });
+    Ok(CameraRecording { cancel, task })
+}
+
+/// Stop a recording started with [`start_camera_recording`] and finalize
+/// its file
+///
+/// # Errors
+/// Returns a `NOT_FOUND` error if the device is not recording (or is still
+/// starting), or an `Err` if a frame could not be written or the file cannot
+/// be finalized.
+#[command]
+pub async fn stop_camera_recording(device_id: String) -> Result<RecordingStats, CommandError> {
+    let recording = {
+        let mut recordings = CAMERA_RECORDINGS
+            .lock()
+            .map_err(|_| CommandError::system("Mutex poisoned"))?;
+        match recordings.get(&device_id) {
+            Some(Some(_)) => recordings.remove(&device_id).flatten(),
+            _ => None,
+        }
+    }
+    .ok_or_else(|| {
+        CommandError::not_found(format!("No recording running for device: {device_id}"))
+    })?;
+
+    recording.cancel.cancel();
+    let stats = recording
+        .task
+        .await
+        .map_err(|e| CommandError::system(format!("Task join error: {e}")))?
+        .map_err(CommandError::from)?;
+    log::info!(
+        "Background recording stopped for device {device_id}: {} frames, {:.2}s",
+        stats.video_frames,
+        stats.duration_secs
+    );
+    Ok(stats)
+}
+
+/// Capture frames into `recorder` until cancelled, then finish it
+fn feed_recorder(
+    device_id: &str,
+    camera: &Arc<SyncMutex<PlatformCamera>>,
+    mut recorder: Recorder,
+    cancel: &CancellationToken,
+) -> Result<RecordingStats, CameraError> {
+    let key = camera
+        .lock()
+        .map_err(|_| CameraError::SystemError("Camera mutex poisoned".to_string()))?
+        .get_device_id()
+        .map(str::to_string);
+    let mut failures = 0;
+    while !cancel.is_cancelled() {
+        if key
+            .as_deref()
+            .is_some_and(crate::platform::is_camera_paused)
+        {
+            std::thread::sleep(std::time::Duration::from_millis(
+                CAMERA_RECORDING_PAUSE_POLL_MS,
+            ));
+            continue;
+        }
+        let frame = camera
+            .lock()
+            .map_err(|_| CameraError::SystemError("Camera mutex poisoned".to_string()))?
+            .capture_frame();
+        match frame {
+            Ok(frame) => {
+                failures = 0;
+                if let Err(e) = recorder.write_frame(&frame) {
+                    log::error!("Recording of device {device_id} failed: {e}");
+                    return Err(e);
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                log::warn!("Recording capture failed for device {device_id}: {e}");
+                if failures >= CAMERA_RECORDING_MAX_FAILURES {
+                    log::error!(
+                        "Recording of device {device_id} ended after {failures} failed captures"
+                    );
+                    break;
+                }
+            }
+        }
+    }
+
+    if let Ok(mut cam) = camera.lock() {
+        let _ = cam.stop_stream();
+    }
+    recorder.finish()
+}
+
+/// Quality preset for a preset name, `None` for unknown names
+fn parse_quality(quality: Option<&str>) -> Option<RecordingQuality> {
+    match quality {
+        Some(q) if q == RECORDING_QUALITY_PRESET_LOW || q == RECORDING_QUALITY_PRESET_720P => {
+            Some(RecordingQuality::Low)
+        }
+        Some(q) if q == RECORDING_QUALITY_PRESET_MEDIUM || q == RECORDING_QUALITY_PRESET_1080P => {
+            Some(RecordingQuality::Medium)
+        }
+        Some(q) if q == RECORDING_QUALITY_PRESET_HIGH || q == RECORDING_QUALITY_PRESET_4K => {
+            Some(RecordingQuality::High)
+        }
+        _ => None,
+    }
+}
+
 /// Recording status information
 /// Per #`AudioErrorRecovery`: ! `session_status_reflects_audio_state`
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -410,10 +666,11 @@ mod tests {
     async fn test_write_frame_to_missing_session_returns_error() {
         let result = record_frame("nonexistent_session_xyz".to_string()).await;
         assert!(result.is_err());
-        let msg = result.expect_err("missing session error expected");
+        let err = result.expect_err("missing session error expected");
+        assert_eq!(err.code, CommandError::NOT_FOUND);
         assert!(
-            msg.contains("nonexistent_session_xyz"),
-            "error should identify the missing session, got: {msg}"
+            err.message.contains("nonexistent_session_xyz"),
+            "error should identify the missing session, got: {err}"
         );
     }
 
@@ -421,21 +678,63 @@ mod tests {
     async fn test_get_recording_status_missing_session_returns_error() {
         let result = get_recording_status("no_such_session_abc".to_string()).await;
         assert!(result.is_err());
-        let msg = result.expect_err("missing session error expected");
+        let err = result.expect_err("missing session error expected");
+        assert_eq!(err.code, CommandError::NOT_FOUND);
         assert!(
-            msg.contains("no_such_session_abc"),
-            "error should identify the missing session, got: {msg}"
+            err.message.contains("no_such_session_abc"),
+            "error should identify the missing session, got: {err}"
         );
     }
 
+    #[tokio::test]
+    async fn test_camera_recording_runs_in_background() {
+        std::env::set_var("CRABCAMERA_USE_MOCK", "1");
+        let device_id = "background-recording".to_string();
+        let output = std::env::temp_dir().join("crabcamera_background_recording.mp4");
+        let output_path = output.to_string_lossy().to_string();
+
+        start_camera_recording(
+            device_id.clone(),
+            Some(CameraFormat::new(320, 240, 15.0)),
+            output_path.clone(),
+            Some("low".to_string()),
+            Some(false),
+        )
+        .await
+        .expect("start background recording");
+        let again = start_camera_recording(device_id.clone(), None, output_path, None, None).await;
+        assert_eq!(again.unwrap_err().code, CommandError::INVALID_ARGUMENT);
+
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        let stats = stop_camera_recording(device_id.clone())
+            .await
+            .expect("stop background recording");
+        assert!(stats.video_frames > 0);
+        assert!(output.exists());
+        let _ = std::fs::remove_file(&output);
+
+        let missing = stop_camera_recording(device_id).await;
+        assert_eq!(missing.unwrap_err().code, CommandError::NOT_FOUND);
+        std::env::remove_var("CRABCAMERA_USE_MOCK");
+    }
+
+    #[test]
+    fn test_parse_quality_presets() {
+        assert_eq!(parse_quality(Some("720p")), Some(RecordingQuality::Low));
+        assert_eq!(parse_quality(Some("4k")), Some(RecordingQuality::High));
+        assert_eq!(parse_quality(Some("cinematic")), None);
+        assert_eq!(parse_quality(None), None);
+    }
+
     #[tokio::test]
     async fn test_stop_recording_missing_session_returns_error() {
         let result = stop_recording("ghost_session_999".to_string()).await;
         assert!(result.is_err());
-        let msg = result.expect_err("missing session error expected");
+        let err = result.expect_err("missing session error expected");
+        assert_eq!(err.code, CommandError::NOT_FOUND);
         assert!(
-            msg.contains("ghost_session_999"),
-            "error should identify the missing session, got: {msg}"
+            err.message.contains("ghost_session_999"),
+            "error should identify the missing session, got: {err}"
         );
     }
 }
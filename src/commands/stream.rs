@@ -0,0 +1,168 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use tauri::command;
+
+use crate::errors::CommandError;
+#[cfg(feature = "http-stream")]
+use crate::preview::MjpegServer;
+#[cfg(feature = "rtsp")]
+use crate::streaming::RtspServer;
+use crate::types::CameraFormat;
+
+#[cfg(feature = "http-stream")]
+static MJPEG_SERVER: tokio::sync::RwLock<Option<MjpegServer>> =
+    tokio::sync::RwLock::const_new(None);
+
+/// Serve a camera as an MJPEG stream over HTTP and return its URL
+///
+/// The server listens on loopback only (`http://127.0.0.1:<port>/`); pass
+/// port `0` to pick a free one. Any number of viewers can connect. Starting a
+/// new server replaces the running one. For access from other machines, use
+/// [`MjpegServer::start`] with an explicit bind address.
+///
+/// # Errors
+/// Returns an `Err` if the camera cannot be obtained or the port cannot be
+/// bound.
+#[cfg(feature = "http-stream")]
+#[command]
+pub async fn start_mjpeg_server(
+    device_id: String,
+    format: CameraFormat,
+    port: u16,
+) -> Result<String, CommandError> {
+    log::info!("Starting MJPEG server for device {device_id} on port {port}");
+
+    let fps = format.fps;
+    let camera = crate::platform::get_or_create_camera(device_id, format)
+        .await
+        .map_err(|e| CommandError::new(e.error_code(), format!("Failed to get camera: {e}")))?;
+
+    let mut guard = MJPEG_SERVER.write().await;
+    if let Some(previous) = guard.take() {
+        previous.stop();
+    }
+
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let server = MjpegServer::start(camera, addr, fps).await.map_err(|e| {
+        CommandError::new(e.error_code(), format!("Failed to start MJPEG server: {e}"))
+    })?;
+    let url = server.url();
+    *guard = Some(server);
+
+    Ok(url)
+}
+
+/// Stop the running MJPEG server and disconnect all viewers
+///
+/// # Errors
+/// Returns an `Err` if no MJPEG server is running.
+#[cfg(feature = "http-stream")]
+#[command]
+pub async fn stop_mjpeg_server() -> Result<String, CommandError> {
+    let mut guard = MJPEG_SERVER.write().await;
+    if let Some(server) = guard.take() {
+        server.stop();
+        Ok("mjpeg_server_stopped".to_string())
+    } else {
+        Err(CommandError::not_found("No active MJPEG server"))
+    }
+}
+
+#[cfg(feature = "rtsp")]
+static RTSP_SERVER: tokio::sync::RwLock<Option<RtspServer>> = tokio::sync::RwLock::const_new(None);
+
+/// Publish a camera as an H.264 RTSP stream and return its `rtsp://` URL
+///
+/// Like the MJPEG server this listens on loopback only unless `bind` names
+/// another address. RTSP consumers (NVRs, Home Assistant) usually run on
+/// another machine; pass `0.0.0.0` to reach them, in which case the returned
+/// URL still uses `127.0.0.1`. There is no authentication, so only bind
+/// other interfaces on trusted networks. Pass port `0` to pick a free one.
+/// Starting a new server replaces the running one.
+///
+/// # Errors
+/// Returns an `Err` if the camera cannot be obtained or the port cannot be
+/// bound.
+#[cfg(feature = "rtsp")]
+#[command]
+pub async fn start_rtsp_server(
+    device_id: String,
+    format: CameraFormat,
+    port: u16,
+    path: String,
+    bind: Option<IpAddr>,
+) -> Result<String, CommandError> {
+    let ip = bind.unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    log::info!("Starting RTSP server for device {device_id} on {ip} port {port}");
+
+    let fps = format.fps;
+    let camera = crate::platform::get_or_create_camera(device_id, format)
+        .await
+        .map_err(|e| CommandError::new(e.error_code(), format!("Failed to get camera: {e}")))?;
+
+    let mut guard = RTSP_SERVER.write().await;
+    if let Some(previous) = guard.take() {
+        previous.stop();
+    }
+
+    let addr = SocketAddr::new(ip, port);
+    let server = RtspServer::start(camera, addr, &path, fps)
+        .await
+        .map_err(|e| {
+            CommandError::new(e.error_code(), format!("Failed to start RTSP server: {e}"))
+        })?;
+    let url = server.url();
+    *guard = Some(server);
+
+    Ok(url)
+}
+
+/// Stop the running RTSP server and end all client sessions
+///
+/// # Errors
+/// Returns an `Err` if no RTSP server is running.
+#[cfg(feature = "rtsp")]
+#[command]
+pub async fn stop_rtsp_server() -> Result<String, CommandError> {
+    let mut guard = RTSP_SERVER.write().await;
+    if let Some(server) = guard.take() {
+        server.stop();
+        Ok("rtsp_server_stopped".to_string())
+    } else {
+        Err(CommandError::not_found("No active RTSP server"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "http-stream")]
+    #[tokio::test]
+    async fn test_start_and_stop_mjpeg_server() {
+        let url = start_mjpeg_server("mjpeg-cmd".to_string(), CameraFormat::low(), 0)
+            .await
+            .expect("server starts");
+        assert!(url.starts_with("http://127.0.0.1:"));
+
+        assert!(stop_mjpeg_server().await.is_ok());
+        assert!(stop_mjpeg_server().await.is_err());
+    }
+
+    #[cfg(feature = "rtsp")]
+    #[tokio::test]
+    async fn test_start_and_stop_rtsp_server() {
+        let url = start_rtsp_server(
+            "rtsp-cmd".to_string(),
+            CameraFormat::low(),
+            0,
+            "/cam/".to_string(),
+            None,
+        )
+        .await
+        .expect("server starts");
+        assert!(url.starts_with("rtsp://127.0.0.1:") && url.ends_with("/cam"));
+
+        assert!(stop_rtsp_server().await.is_ok());
+        assert!(stop_rtsp_server().await.is_err());
+    }
+}
@@ -0,0 +1,170 @@
+//! Stand-ins for commands of features this build was compiled without
+//!
+//! Tauri answers an unregistered command with a generic "command not found".
+//! [`crate::init`] registers these under the real commands' names when their
+//! feature is off, so a frontend calling one gets a `FEATURE_DISABLED` error
+//! naming the Cargo feature to enable. They take no arguments; whatever the
+//! frontend passes is ignored.
+
+use tauri::command;
+
+use crate::errors::{CameraError, CommandError};
+
+/// `start_recording` in a build without the `recording` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "recording"))]
+#[command]
+pub fn start_recording() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("recording".to_string()).into())
+}
+
+/// `record_frame` in a build without the `recording` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "recording"))]
+#[command]
+pub fn record_frame() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("recording".to_string()).into())
+}
+
+/// `stop_recording` in a build without the `recording` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "recording"))]
+#[command]
+pub fn stop_recording() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("recording".to_string()).into())
+}
+
+/// `get_recording_status` in a build without the `recording` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "recording"))]
+#[command]
+pub fn get_recording_status() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("recording".to_string()).into())
+}
+
+/// `list_recording_sessions` in a build without the `recording` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "recording"))]
+#[command]
+pub fn list_recording_sessions() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("recording".to_string()).into())
+}
+
+/// `start_camera_recording` in a build without the `recording` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "recording"))]
+#[command]
+pub fn start_camera_recording() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("recording".to_string()).into())
+}
+
+/// `stop_camera_recording` in a build without the `recording` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "recording"))]
+#[command]
+pub fn stop_camera_recording() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("recording".to_string()).into())
+}
+
+/// `start_mjpeg_server` in a build without the `http-stream` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "http-stream"))]
+#[command]
+pub fn start_mjpeg_server() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("http-stream".to_string()).into())
+}
+
+/// `stop_mjpeg_server` in a build without the `http-stream` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "http-stream"))]
+#[command]
+pub fn stop_mjpeg_server() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("http-stream".to_string()).into())
+}
+
+/// `start_rtsp_server` in a build without the `rtsp` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "rtsp"))]
+#[command]
+pub fn start_rtsp_server() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("rtsp".to_string()).into())
+}
+
+/// `stop_rtsp_server` in a build without the `rtsp` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "rtsp"))]
+#[command]
+pub fn stop_rtsp_server() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("rtsp".to_string()).into())
+}
+
+/// `list_audio_devices` in a build without the `audio` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "audio"))]
+#[command]
+pub fn list_audio_devices() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("audio".to_string()).into())
+}
+
+/// `list_loopback_devices` in a build without the `audio` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "audio"))]
+#[command]
+pub fn list_loopback_devices() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("audio".to_string()).into())
+}
+
+/// `get_default_audio_device` in a build without the `audio` feature
+///
+/// # Errors
+/// Always fails with `FEATURE_DISABLED`.
+#[cfg(not(feature = "audio"))]
+#[command]
+pub fn get_default_audio_device() -> Result<(), CommandError> {
+    Err(CameraError::FeatureDisabled("audio".to_string()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "recording"))]
+    #[test]
+    fn test_recording_stub_reports_feature() {
+        let err = super::start_camera_recording().unwrap_err();
+        assert_eq!(err.code, "FEATURE_DISABLED");
+        assert!(err.message.contains("`recording`"));
+    }
+
+    #[cfg(not(feature = "audio"))]
+    #[test]
+    fn test_audio_stub_reports_feature() {
+        let err = super::list_audio_devices().unwrap_err();
+        assert_eq!(err.code, "FEATURE_DISABLED");
+        assert!(err.message.contains("`audio`"));
+    }
+}
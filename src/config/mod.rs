@@ -0,0 +1,1052 @@
+//! Configuration management for CrabCamera
+//!
+//! Provides configuration loading, saving, and management for camera settings,
+//! quality thresholds, storage preferences, and other runtime options.
+
+use crate::constants::{
+    CONFIG_VERSION, DEFAULT_BLUR_THRESHOLD, DEFAULT_DATE_FORMAT, DEFAULT_EXPOSURE_THRESHOLD,
+    DEFAULT_FILE_NAME_TEMPLATE, DEFAULT_FOCUS_STACK_STEPS, DEFAULT_FPS,
+    DEFAULT_FRAME_QUEUE_CAPACITY, DEFAULT_HDR_BRACKETS, DEFAULT_IMAGE_FORMAT, DEFAULT_JPEG_QUALITY,
+    DEFAULT_MAX_RETRY_ATTEMPTS, DEFAULT_OUTPUT_DIRECTORY, DEFAULT_OVERALL_THRESHOLD,
+    DEFAULT_RECONNECT_ATTEMPTS, DEFAULT_RECONNECT_DELAY_MS, DEFAULT_RESOLUTION_HEIGHT,
+    DEFAULT_RESOLUTION_WIDTH, DEFAULT_RETRY_DELAY_MS, DEFAULT_TEMP_CAPTURE_MAX_AGE_SECS,
+    MAX_FRAME_QUEUE_CAPACITY, MAX_REORDER_WINDOW,
+};
+use crate::errors::CameraError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Per-device saved control profiles.
+pub mod profiles;
+pub use profiles::{CameraProfile, CameraProfileStore};
+
+/// Schema of the configuration for settings UIs.
+pub mod schema;
+pub use schema::config_schema;
+
+/// Core affinity and priority of internal threads.
+pub mod threads;
+pub use threads::{
+    set_thread_affinity, thread_affinity, tune_current_thread, ThreadAffinityConfig,
+    ThreadPriorityLevel, ThreadRole, ThreadTuning,
+};
+
+/// Root configuration structure
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CrabCameraConfig {
+    /// Schema version of the file; older files are migrated on load.
+    #[serde(default)]
+    pub version: u32,
+    /// Camera hardware preferences and defaults.
+    pub camera: CameraConfig,
+    /// Image quality analysis thresholds.
+    pub quality: QualityConfig,
+    /// File storage paths and naming conventions.
+    pub storage: StorageConfig,
+    /// Experimental and advanced features.
+    pub advanced: AdvancedConfig,
+    /// Per-camera overrides keyed by stable device id: the fields that
+    /// differ from the global config, e.g. `devices."usb-...".storage`.
+    /// See [`for_device`](Self::for_device).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub devices: BTreeMap<String, serde_json::Map<String, serde_json::Value>>,
+}
+
+/// Camera-specific configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CameraConfig {
+    /// Default camera resolution [width, height]
+    pub default_resolution: [u32; 2],
+    /// Default frames per second
+    pub default_fps: u32,
+    /// Auto-reconnect on device disconnect
+    pub auto_reconnect: bool,
+    /// Reconnect retry attempts
+    pub reconnect_attempts: u32,
+    /// Reconnect delay in milliseconds
+    pub reconnect_delay_ms: u64,
+    /// Apply the saved per-device control profile when a camera is first opened
+    #[serde(default)]
+    pub auto_apply_saved_profile: bool,
+}
+
+/// Quality validation configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QualityConfig {
+    /// Enable automatic quality-based retry
+    pub auto_retry_enabled: bool,
+    /// Maximum retry attempts for quality capture
+    pub max_retry_attempts: u32,
+    /// Minimum acceptable blur threshold (0.0-1.0)
+    pub min_blur_threshold: f32,
+    /// Minimum acceptable exposure score (0.0-1.0)
+    pub min_exposure_score: f32,
+    /// Minimum overall quality score (0.0-1.0)
+    pub min_overall_score: f32,
+    /// Retry delay between attempts in milliseconds
+    pub retry_delay_ms: u64,
+}
+
+/// Storage and file management configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StorageConfig {
+    /// Default output directory for captures
+    pub output_directory: String,
+    /// Auto-organize files by date
+    pub auto_organize_by_date: bool,
+    /// Date format for organization (e.g., "YYYY-MM-DD")
+    pub date_format: String,
+    /// Default image format (jpeg, png, bmp)
+    pub default_format: String,
+    /// JPEG quality (0-100)
+    pub jpeg_quality: u8,
+    /// Auto-delete low quality captures
+    pub auto_delete_low_quality: bool,
+    /// File name template for automatically named captures; supports
+    /// `{timestamp}`, `{date}`, `{device}` and `{seq}`
+    #[serde(default = "default_file_name_template")]
+    pub file_name_template: String,
+    /// Directory for temporary captures (`None` = the system temp dir)
+    #[serde(default)]
+    pub temp_dir: Option<PathBuf>,
+    /// Age in seconds after which temporary captures are cleaned up
+    #[serde(default = "default_temp_max_age_secs")]
+    pub temp_max_age_secs: u64,
+}
+
+fn default_file_name_template() -> String {
+    DEFAULT_FILE_NAME_TEMPLATE.to_string()
+}
+
+fn default_temp_max_age_secs() -> u64 {
+    DEFAULT_TEMP_CAPTURE_MAX_AGE_SECS
+}
+
+/// Advanced features configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdvancedConfig {
+    /// Enable focus stacking
+    pub focus_stacking_enabled: bool,
+    /// Number of focus steps for stacking
+    pub focus_stack_steps: u32,
+    /// Enable HDR capture
+    pub hdr_enabled: bool,
+    /// Number of exposure brackets for HDR
+    pub hdr_brackets: u32,
+    /// Frames buffered per camera for polling consumers; the oldest frame is
+    /// dropped when a slow consumer lets the queue fill up
+    #[serde(default = "default_frame_queue_capacity")]
+    pub frame_queue_capacity: usize,
+    /// Longest side in pixels of frames buffered for polling consumers;
+    /// larger frames are downscaled to cut IPC payload (`None` = full size)
+    #[serde(default)]
+    pub preview_max_dim: Option<u32>,
+    /// Frames held back per camera to restore hardware timestamp order
+    /// before they are buffered for polling consumers; adds this many frame
+    /// intervals of latency (0 = off)
+    #[serde(default)]
+    pub reorder_window: usize,
+    /// Core pinning and priority of the capture and encode threads
+    /// (default: OS scheduling)
+    #[serde(default)]
+    pub thread_affinity: ThreadAffinityConfig,
+}
+
+fn default_frame_queue_capacity() -> usize {
+    DEFAULT_FRAME_QUEUE_CAPACITY
+}
+
+/// Replace `path` with `contents` so that readers, and the file after a
+/// crash, see either the old or the new contents, never a partial write
+///
+/// The data goes to a uniquely named temporary file in the same directory,
+/// so concurrent writers do not share one, is synced to disk, and is renamed
+/// over `path`. The temporary file is removed if any step fails.
+pub(crate) fn write_atomically(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(contents)?;
+    file.as_file().sync_all()?;
+    file.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// Overlay `patch` onto `base`, recursing into objects
+pub(crate) fn overlay(base: &mut serde_json::Value, patch: serde_json::Value) {
+    match (base, patch) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                match base.get_mut(&key) {
+                    Some(existing) => overlay(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
+/// Parts of `target` that differ from `base`, without nulls (which the
+/// config file cannot store)
+fn difference(base: &serde_json::Value, target: &serde_json::Value) -> Option<serde_json::Value> {
+    match (base, target) {
+        (_, serde_json::Value::Null) => None,
+        (serde_json::Value::Object(base), serde_json::Value::Object(target)) => {
+            let diff: serde_json::Map<_, _> = target
+                .iter()
+                .filter_map(|(key, value)| {
+                    let changed = match base.get(key) {
+                        Some(existing) => difference(existing, value),
+                        None => difference(&serde_json::Value::Null, value),
+                    };
+                    changed.map(|changed| (key.clone(), changed))
+                })
+                .collect();
+            (!diff.is_empty()).then_some(serde_json::Value::Object(diff))
+        }
+        (base, target) => (base != target).then(|| target.clone()),
+    }
+}
+
+/// Move a config file that cannot be parsed aside, keeping it for the user
+/// to recover
+fn back_up_unreadable(path: &Path) {
+    if !path.exists() {
+        return;
+    }
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let mut backup_name = path.file_name().unwrap_or_default().to_os_string();
+    backup_name.push(format!(".corrupt-{stamp}"));
+    let backup = path.with_file_name(backup_name);
+    match fs::rename(path, &backup) {
+        Ok(()) => log::warn!("Moved unreadable config file to {}", backup.display()),
+        Err(e) => log::error!("Failed to back up unreadable config file: {e}"),
+    }
+}
+
+/// Top-level section of [`CrabCameraConfig`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSection {
+    /// [`CameraConfig`]
+    Camera,
+    /// [`QualityConfig`]
+    Quality,
+    /// [`StorageConfig`]
+    Storage,
+    /// [`AdvancedConfig`]
+    Advanced,
+    /// Per-camera overrides
+    Devices,
+}
+
+impl ConfigSection {
+    /// Key of the section in the config file
+    pub fn key(self) -> &'static str {
+        match self {
+            ConfigSection::Camera => "camera",
+            ConfigSection::Quality => "quality",
+            ConfigSection::Storage => "storage",
+            ConfigSection::Advanced => "advanced",
+            ConfigSection::Devices => "devices",
+        }
+    }
+}
+
+/// A configuration update, emitted as `crabcamera://config-changed`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChangedEvent {
+    /// Sections whose values changed
+    pub sections: Vec<ConfigSection>,
+    /// Configuration now in effect
+    pub config: CrabCameraConfig,
+}
+
+impl Default for CrabCameraConfig {
+    fn default() -> Self {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        // f32→u32: DEFAULT_FPS is a known positive constant (30.0)
+        let default_fps_val = DEFAULT_FPS as u32;
+        Self {
+            version: CONFIG_VERSION,
+            camera: CameraConfig {
+                default_resolution: [DEFAULT_RESOLUTION_WIDTH, DEFAULT_RESOLUTION_HEIGHT],
+                default_fps: default_fps_val,
+                auto_reconnect: true,
+                reconnect_attempts: DEFAULT_RECONNECT_ATTEMPTS,
+                reconnect_delay_ms: DEFAULT_RECONNECT_DELAY_MS,
+                auto_apply_saved_profile: false,
+            },
+            quality: QualityConfig {
+                auto_retry_enabled: true,
+                max_retry_attempts: DEFAULT_MAX_RETRY_ATTEMPTS,
+                min_blur_threshold: DEFAULT_BLUR_THRESHOLD,
+                min_exposure_score: DEFAULT_EXPOSURE_THRESHOLD,
+                min_overall_score: DEFAULT_OVERALL_THRESHOLD,
+                retry_delay_ms: DEFAULT_RETRY_DELAY_MS,
+            },
+            storage: StorageConfig {
+                output_directory: DEFAULT_OUTPUT_DIRECTORY.to_string(),
+                auto_organize_by_date: true,
+                date_format: DEFAULT_DATE_FORMAT.to_string(),
+                default_format: DEFAULT_IMAGE_FORMAT.to_string(),
+                jpeg_quality: DEFAULT_JPEG_QUALITY,
+                auto_delete_low_quality: false,
+                file_name_template: default_file_name_template(),
+                temp_dir: None,
+                temp_max_age_secs: default_temp_max_age_secs(),
+            },
+            advanced: AdvancedConfig {
+                focus_stacking_enabled: false,
+                focus_stack_steps: DEFAULT_FOCUS_STACK_STEPS,
+                hdr_enabled: false,
+                hdr_brackets: DEFAULT_HDR_BRACKETS,
+                frame_queue_capacity: default_frame_queue_capacity(),
+                preview_max_dim: None,
+                reorder_window: 0,
+                thread_affinity: ThreadAffinityConfig::default(),
+            },
+            devices: BTreeMap::new(),
+        }
+    }
+}
+
+impl CrabCameraConfig {
+    /// Load configuration from TOML file
+    ///
+    /// Files written by older versions are migrated to the current schema
+    /// (see [`migrate`](Self::migrate)).
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::InitializationError`] if the config file
+    /// cannot be read, if its contents cannot be parsed as TOML, or if they
+    /// do not describe a configuration.
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, CameraError> {
+        let path = path.as_ref();
+
+        if !path.exists() {
+            log::info!(
+                "Config file not found at {}, using defaults",
+                path.display()
+            );
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path).map_err(|e| {
+            CameraError::InitializationError(format!("Failed to read config file: {e}"))
+        })?;
+
+        let config = Self::parse(&contents)?;
+        log::info!("Loaded configuration from {}", path.display());
+        Ok(config)
+    }
+
+    /// Parse and [`migrate`](Self::migrate) the contents of a config file
+    fn parse(contents: &str) -> Result<Self, CameraError> {
+        let table: toml::Table = toml::from_str(contents).map_err(|e| {
+            CameraError::InitializationError(format!("Failed to parse config file: {e}"))
+        })?;
+        Self::migrate(table).try_into().map_err(|e| {
+            CameraError::InitializationError(format!("Failed to parse config file: {e}"))
+        })
+    }
+
+    /// Upgrade a parsed config file to [`CONFIG_VERSION`]
+    ///
+    /// Files without a `version` are version 0, from before versioning;
+    /// every field added since has a default, so migrating them only stamps
+    /// the version. Future schema changes add a step here. Files from a
+    /// newer version are left as they are and their unknown fields ignored.
+    pub fn migrate(mut table: toml::Table) -> toml::Table {
+        let version = table
+            .get("version")
+            .and_then(toml::Value::as_integer)
+            .and_then(|v| u32::try_from(v).ok())
+            .unwrap_or(0);
+
+        if version > CONFIG_VERSION {
+            log::warn!(
+                "Config file is version {version}, newer than supported version \
+                 {CONFIG_VERSION}; unknown settings are ignored"
+            );
+            return table;
+        }
+        if version < CONFIG_VERSION {
+            // v0 -> v1: `version` introduced, no field changes
+            table.insert(
+                "version".to_string(),
+                toml::Value::Integer(i64::from(CONFIG_VERSION)),
+            );
+            log::info!("Migrated config file from version {version} to {CONFIG_VERSION}");
+        }
+        table
+    }
+
+    /// Save configuration to TOML file
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::InitializationError`] if the parent directory
+    /// cannot be created, if the config cannot be serialized to TOML, or if the
+    /// file cannot be written.
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), CameraError> {
+        let path = path.as_ref();
+
+        // Create parent directories if needed
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                CameraError::InitializationError(format!("Failed to create config directory: {e}"))
+            })?;
+        }
+
+        let toml_string = toml::to_string_pretty(self).map_err(|e| {
+            CameraError::InitializationError(format!("Failed to serialize config: {e}"))
+        })?;
+
+        write_atomically(path, toml_string.as_bytes()).map_err(|e| {
+            CameraError::InitializationError(format!("Failed to write config file: {e}"))
+        })?;
+
+        log::info!("Saved configuration to {}", path.display());
+        Ok(())
+    }
+
+    /// Sections that differ between `self` and `other`
+    pub fn changed_sections(&self, other: &Self) -> Vec<ConfigSection> {
+        [
+            (ConfigSection::Camera, self.camera != other.camera),
+            (ConfigSection::Quality, self.quality != other.quality),
+            (ConfigSection::Storage, self.storage != other.storage),
+            (ConfigSection::Advanced, self.advanced != other.advanced),
+            (ConfigSection::Devices, self.devices != other.devices),
+        ]
+        .into_iter()
+        .filter_map(|(section, changed)| changed.then_some(section))
+        .collect()
+    }
+
+    /// Effective configuration of the camera with stable id `stable_id`
+    ///
+    /// Precedence is device override > global config > defaults: the
+    /// device's overridden fields replace the global ones, everything else
+    /// is inherited. The result has no `devices` entries.
+    pub fn for_device(&self, stable_id: &str) -> Self {
+        let mut effective = self.clone();
+        effective.devices.clear();
+        let Some(overrides) = self.devices.get(stable_id) else {
+            return effective;
+        };
+
+        let mut merged = serde_json::to_value(&effective).unwrap_or_default();
+        overlay(&mut merged, serde_json::Value::Object(overrides.clone()));
+        serde_json::from_value(merged).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid config override for {stable_id}: {e}");
+            effective
+        })
+    }
+
+    /// Make `values` the `section` settings of camera `stable_id`
+    ///
+    /// Only the fields that differ from the global config are stored, so
+    /// later global changes still reach the others. `None` values cannot
+    /// override a global value and are inherited.
+    pub fn set_device_section(
+        &mut self,
+        stable_id: &str,
+        section: ConfigSection,
+        values: &serde_json::Value,
+    ) {
+        let global = serde_json::to_value(&*self)
+            .ok()
+            .and_then(|config| config.get(section.key()).cloned())
+            .unwrap_or_default();
+        let overrides = self.devices.entry(stable_id.to_string()).or_default();
+        match difference(&global, values) {
+            Some(diff) => {
+                overrides.insert(section.key().to_string(), diff);
+            }
+            None => {
+                overrides.remove(section.key());
+            }
+        }
+        if overrides.is_empty() {
+            self.devices.remove(stable_id);
+        }
+    }
+
+    /// Get default config file path
+    pub fn default_path() -> PathBuf {
+        PathBuf::from("crabcamera.toml")
+    }
+
+    /// Load from default location or create with defaults
+    ///
+    /// A file that cannot be parsed or migrated is renamed to
+    /// `<name>.corrupt-<unix time>` so the next save does not overwrite the
+    /// user's settings, and the defaults are used. A file that cannot be
+    /// read (e.g. no permission, an I/O error) is left where it is.
+    pub fn load_or_default() -> Self {
+        Self::load_or_default_from(&Self::default_path())
+    }
+
+    /// [`load_or_default`](Self::load_or_default) for the file at `path`
+    fn load_or_default_from(path: &Path) -> Self {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                log::info!(
+                    "Config file not found at {}, using defaults",
+                    path.display()
+                );
+                return Self::default();
+            }
+            Err(e) => {
+                log::warn!("Failed to read config file, using defaults: {e}");
+                return Self::default();
+            }
+        };
+        match Self::parse(&contents) {
+            Ok(config) => {
+                log::info!("Loaded configuration from {}", path.display());
+                config
+            }
+            Err(e) => {
+                log::warn!("Failed to load config, using defaults: {e}");
+                back_up_unreadable(path);
+                Self::default()
+            }
+        }
+    }
+
+    /// Validate configuration values
+    ///
+    /// # Errors
+    /// Returns an `Err` describing the first invalid value if any resolution,
+    /// FPS, quality threshold, JPEG quality, focus-stack step count, or HDR
+    /// bracket count is out of its allowed range. [`check`](Self::check)
+    /// reports every problem instead.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.check().errors.into_iter().next() {
+            Some(issue) => Err(issue.message),
+            None => Ok(()),
+        }
+    }
+
+    /// Check every value, collecting errors (which make [`validate`](Self::validate)
+    /// fail) and warnings (settings that work but are probably unintended)
+    pub fn check(&self) -> ConfigValidationReport {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut error =
+            |field: &str, message: String| errors.push(ConfigIssue::new(field, message));
+
+        // Camera config
+        if self.camera.default_resolution[0] == 0 || self.camera.default_resolution[1] == 0 {
+            error(
+                "camera.default_resolution",
+                "Invalid default resolution".to_string(),
+            );
+        }
+        if self.camera.default_fps == 0 || self.camera.default_fps > 240 {
+            error(
+                "camera.default_fps",
+                "Invalid default FPS (must be 1-240)".to_string(),
+            );
+        }
+
+        // Quality config
+        if !(0.0..=1.0).contains(&self.quality.min_blur_threshold) {
+            error(
+                "quality.min_blur_threshold",
+                "Blur threshold must be between 0.0 and 1.0".to_string(),
+            );
+        }
+        if !(0.0..=1.0).contains(&self.quality.min_exposure_score) {
+            error(
+                "quality.min_exposure_score",
+                "Exposure score must be between 0.0 and 1.0".to_string(),
+            );
+        }
+        if !(0.0..=1.0).contains(&self.quality.min_overall_score) {
+            error(
+                "quality.min_overall_score",
+                "Overall score must be between 0.0 and 1.0".to_string(),
+            );
+        }
+
+        // Storage config
+        if self.storage.jpeg_quality == 0 || self.storage.jpeg_quality > 100 {
+            error(
+                "storage.jpeg_quality",
+                "JPEG quality must be between 1 and 100".to_string(),
+            );
+        }
+        if self.storage.file_name_template.trim().is_empty() {
+            error(
+                "storage.file_name_template",
+                "File name template must not be empty".to_string(),
+            );
+        }
+
+        // Advanced config
+        if self.advanced.focus_stack_steps == 0 || self.advanced.focus_stack_steps > 100 {
+            error(
+                "advanced.focus_stack_steps",
+                "Focus stack steps must be between 1 and 100".to_string(),
+            );
+        }
+        if self.advanced.hdr_brackets == 0 || self.advanced.hdr_brackets > 10 {
+            error(
+                "advanced.hdr_brackets",
+                "HDR brackets must be between 1 and 10".to_string(),
+            );
+        }
+        if self.advanced.frame_queue_capacity == 0
+            || self.advanced.frame_queue_capacity > MAX_FRAME_QUEUE_CAPACITY
+        {
+            error(
+                "advanced.frame_queue_capacity",
+                format!("Frame queue capacity must be between 1 and {MAX_FRAME_QUEUE_CAPACITY}"),
+            );
+        }
+        if self.advanced.preview_max_dim == Some(0) {
+            error(
+                "advanced.preview_max_dim",
+                "Preview max dimension must be at least 1".to_string(),
+            );
+        }
+        if self.advanced.reorder_window > MAX_REORDER_WINDOW {
+            error(
+                "advanced.reorder_window",
+                format!("Reorder window must be at most {MAX_REORDER_WINDOW} frames"),
+            );
+        }
+        if let Err(message) = self.advanced.thread_affinity.validate() {
+            error("advanced.thread_affinity", message);
+        }
+        for stable_id in self.devices.keys() {
+            for issue in self.for_device(stable_id).check().errors {
+                error(
+                    &format!("devices.{stable_id}.{}", issue.field),
+                    issue.message,
+                );
+            }
+        }
+
+        // Cross-field checks that do not block saving
+        let mut warn = |field: &str, message: &str| warnings.push(ConfigIssue::new(field, message));
+        if self.quality.auto_retry_enabled && self.quality.max_retry_attempts == 0 {
+            warn(
+                "quality.max_retry_attempts",
+                "Quality retry is enabled but allows 0 attempts",
+            );
+        }
+        if self.camera.auto_reconnect && self.camera.reconnect_attempts == 0 {
+            warn(
+                "camera.reconnect_attempts",
+                "Auto-reconnect is enabled but allows 0 attempts",
+            );
+        }
+        let format = self.storage.default_format.to_lowercase();
+        if !["jpeg", "jpg", "png", "bmp"].contains(&format.as_str()) {
+            warn(
+                "storage.default_format",
+                "Unknown image format; captures are saved as JPEG",
+            );
+        } else if format != "jpeg"
+            && format != "jpg"
+            && self.storage.jpeg_quality != DEFAULT_JPEG_QUALITY
+        {
+            warn(
+                "storage.jpeg_quality",
+                "JPEG quality has no effect on PNG or BMP captures",
+            );
+        }
+        if self.storage.output_directory.trim().is_empty() {
+            warn(
+                "storage.output_directory",
+                "No output directory; captures are saved to the working directory",
+            );
+        }
+
+        ConfigValidationReport {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+        }
+    }
+}
+
+/// One problem found by [`CrabCameraConfig::check`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigIssue {
+    /// Dotted path of the field (e.g. `storage.jpeg_quality`)
+    pub field: String,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl ConfigIssue {
+    /// Issue for `field`
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Result of checking a configuration without applying it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigValidationReport {
+    /// Whether the config would be accepted (no errors)
+    pub valid: bool,
+    /// Invalid values that must be fixed
+    pub errors: Vec<ConfigIssue>,
+    /// Settings that are accepted but probably unintended
+    pub warnings: Vec<ConfigIssue>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = CrabCameraConfig::default();
+        assert_eq!(config.camera.default_resolution, [1920, 1080]);
+        assert_eq!(config.camera.default_fps, 30);
+        assert!(config.quality.auto_retry_enabled);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let config = CrabCameraConfig::default();
+        assert!(config.validate().is_ok());
+
+        let mut bad_config = config.clone();
+        bad_config.camera.default_resolution = [0, 0];
+        assert!(bad_config.validate().is_err());
+
+        let mut bad_quality = CrabCameraConfig::default();
+        bad_quality.quality.min_blur_threshold = 1.5;
+        assert!(bad_quality.validate().is_err());
+    }
+
+    #[test]
+    fn test_changed_sections() {
+        let config = CrabCameraConfig::default();
+        assert!(config.changed_sections(&config.clone()).is_empty());
+
+        let mut changed = config.clone();
+        changed.storage.jpeg_quality = 50;
+        changed.advanced.preview_max_dim = Some(640);
+        assert_eq!(
+            config.changed_sections(&changed),
+            [ConfigSection::Storage, ConfigSection::Advanced]
+        );
+    }
+
+    #[test]
+    fn test_migrates_unversioned_file() {
+        let mut config = CrabCameraConfig::default();
+        config.storage.jpeg_quality = 70;
+        let mut table = toml::Table::try_from(&config).expect("config to table");
+        table.remove("version");
+
+        let migrated: CrabCameraConfig = CrabCameraConfig::migrate(table)
+            .try_into()
+            .expect("migrated config");
+        assert_eq!(migrated.version, CONFIG_VERSION);
+        assert_eq!(migrated.storage.jpeg_quality, 70);
+
+        let mut future = toml::Table::try_from(&config).expect("config to table");
+        future.insert("version".to_string(), toml::Value::Integer(99));
+        future.insert("new_section".to_string(), toml::Value::Boolean(true));
+        let loaded: CrabCameraConfig = CrabCameraConfig::migrate(future)
+            .try_into()
+            .expect("newer config loads");
+        assert_eq!(loaded.version, 99);
+    }
+
+    #[test]
+    fn test_atomic_write_and_corrupt_backup() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let dir = temp.path();
+        let path = dir.join("crabcamera.toml");
+
+        write_atomically(&path, b"not = [valid").expect("atomic write");
+        // Only the target is left behind
+        assert_eq!(fs::read_dir(dir).expect("read dir").count(), 1);
+        assert!(CrabCameraConfig::load_from_file(&path).is_err());
+
+        let loaded = CrabCameraConfig::load_or_default_from(&path);
+        assert_eq!(loaded.version, CrabCameraConfig::default().version);
+        assert!(!path.exists());
+        let backups: Vec<_> = fs::read_dir(dir)
+            .expect("read dir")
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().contains(".corrupt-"))
+            .collect();
+        assert_eq!(backups.len(), 1);
+    }
+
+    #[test]
+    fn test_unreadable_config_is_not_moved() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        // Reading a directory fails without the contents being corrupt
+        let path = temp.path().join("crabcamera.toml");
+        fs::create_dir(&path).expect("create dir");
+
+        let loaded = CrabCameraConfig::load_or_default_from(&path);
+        assert_eq!(loaded.version, CrabCameraConfig::default().version);
+        assert!(path.is_dir());
+        assert_eq!(fs::read_dir(temp.path()).expect("read dir").count(), 1);
+    }
+
+    #[test]
+    fn test_device_overrides_layer_over_global() {
+        let mut config = CrabCameraConfig::default();
+        let mut storage = config.storage.clone();
+        storage.jpeg_quality = 70;
+        storage.default_format = "png".to_string();
+        config.set_device_section(
+            "usb-doc-cam",
+            ConfigSection::Storage,
+            &serde_json::to_value(&storage).unwrap(),
+        );
+
+        // Only the differing fields are stored
+        let stored = &config.devices["usb-doc-cam"]["storage"];
+        assert_eq!(stored.as_object().map(serde_json::Map::len), Some(2));
+
+        // Later global changes reach fields the device does not override
+        config.storage.output_directory = "/captures".to_string();
+        let effective = config.for_device("usb-doc-cam");
+        assert_eq!(effective.storage.jpeg_quality, 70);
+        assert_eq!(effective.storage.output_directory, "/captures");
+        assert!(effective.devices.is_empty());
+        assert_eq!(config.for_device("usb-face-cam").storage.jpeg_quality, 95);
+
+        // Overrides survive a save/load round trip and are validated
+        let toml_string = toml::to_string_pretty(&config).expect("serialize");
+        let loaded: CrabCameraConfig = toml::from_str(&toml_string).expect("parse");
+        assert_eq!(loaded.for_device("usb-doc-cam").storage.jpeg_quality, 70);
+
+        storage.jpeg_quality = 0;
+        config.set_device_section(
+            "usb-doc-cam",
+            ConfigSection::Storage,
+            &serde_json::to_value(&storage).unwrap(),
+        );
+        let report = config.check();
+        assert_eq!(
+            report.errors[0].field,
+            "devices.usb-doc-cam.storage.jpeg_quality"
+        );
+
+        // Matching the global values removes the override
+        config.set_device_section(
+            "usb-doc-cam",
+            ConfigSection::Storage,
+            &serde_json::to_value(&config.storage).unwrap(),
+        );
+        assert!(config.devices.is_empty());
+    }
+
+    #[test]
+    fn test_config_save_and_load() {
+        let temp_dir = std::env::temp_dir();
+        let config_path = temp_dir.join("test_crabcamera.toml");
+
+        // Clean up any existing test file
+        let _ = fs::remove_file(&config_path);
+
+        let config = CrabCameraConfig::default();
+        assert!(config.save_to_file(&config_path).is_ok());
+
+        let loaded = CrabCameraConfig::load_from_file(&config_path).expect("load saved config");
+        assert_eq!(loaded.camera.default_fps, config.camera.default_fps);
+        assert_eq!(
+            loaded.quality.max_retry_attempts,
+            config.quality.max_retry_attempts
+        );
+
+        // Clean up
+        let _ = fs::remove_file(&config_path);
+    }
+
+    #[test]
+    fn test_config_toml_format() {
+        let config = CrabCameraConfig::default();
+        let toml_string = toml::to_string_pretty(&config).expect("serialize config to TOML");
+
+        // Verify TOML contains expected sections
+        assert!(toml_string.contains("[camera]"));
+        assert!(toml_string.contains("[quality]"));
+        assert!(toml_string.contains("[storage]"));
+        assert!(toml_string.contains("[advanced]"));
+        assert!(toml_string.contains("default_resolution"));
+        assert!(toml_string.contains("auto_retry_enabled"));
+    }
+
+    #[test]
+    fn test_load_nonexistent_file() {
+        let result = CrabCameraConfig::load_from_file("nonexistent_file.toml");
+        assert!(result.is_ok()); // Should return default
+        assert_eq!(result.expect("load default config").camera.default_fps, 30);
+    }
+
+    #[test]
+    fn test_validate_all_remaining_error_branches() {
+        let mut cfg = CrabCameraConfig::default();
+
+        cfg.camera.default_fps = 0;
+        assert_eq!(
+            cfg.validate().expect_err("fps=0 should fail"),
+            "Invalid default FPS (must be 1-240)"
+        );
+
+        cfg = CrabCameraConfig::default();
+        cfg.camera.default_fps = 241;
+        assert_eq!(
+            cfg.validate().expect_err("fps>240 should fail"),
+            "Invalid default FPS (must be 1-240)"
+        );
+
+        cfg = CrabCameraConfig::default();
+        cfg.quality.min_exposure_score = 1.2;
+        assert_eq!(
+            cfg.validate().expect_err("exposure>1 should fail"),
+            "Exposure score must be between 0.0 and 1.0"
+        );
+
+        cfg = CrabCameraConfig::default();
+        cfg.quality.min_overall_score = -0.1;
+        assert_eq!(
+            cfg.validate().expect_err("overall<0 should fail"),
+            "Overall score must be between 0.0 and 1.0"
+        );
+
+        cfg = CrabCameraConfig::default();
+        cfg.storage.jpeg_quality = 0;
+        assert_eq!(
+            cfg.validate().expect_err("jpeg quality 0 should fail"),
+            "JPEG quality must be between 1 and 100"
+        );
+
+        cfg = CrabCameraConfig::default();
+        cfg.storage.jpeg_quality = 101;
+        assert_eq!(
+            cfg.validate().expect_err("jpeg quality >100 should fail"),
+            "JPEG quality must be between 1 and 100"
+        );
+
+        cfg = CrabCameraConfig::default();
+        cfg.storage.file_name_template = " ".to_string();
+        assert_eq!(
+            cfg.validate().expect_err("blank template should fail"),
+            "File name template must not be empty"
+        );
+
+        cfg = CrabCameraConfig::default();
+        cfg.advanced.focus_stack_steps = 0;
+        assert_eq!(
+            cfg.validate().expect_err("focus stack 0 should fail"),
+            "Focus stack steps must be between 1 and 100"
+        );
+
+        cfg = CrabCameraConfig::default();
+        cfg.advanced.focus_stack_steps = 101;
+        assert_eq!(
+            cfg.validate().expect_err("focus stack >100 should fail"),
+            "Focus stack steps must be between 1 and 100"
+        );
+
+        cfg = CrabCameraConfig::default();
+        cfg.advanced.hdr_brackets = 0;
+        assert_eq!(
+            cfg.validate().expect_err("hdr 0 should fail"),
+            "HDR brackets must be between 1 and 10"
+        );
+
+        cfg = CrabCameraConfig::default();
+        cfg.advanced.hdr_brackets = 11;
+        assert_eq!(
+            cfg.validate().expect_err("hdr >10 should fail"),
+            "HDR brackets must be between 1 and 10"
+        );
+
+        cfg = CrabCameraConfig::default();
+        cfg.advanced.frame_queue_capacity = 0;
+        assert!(cfg.validate().is_err(), "empty frame queue should fail");
+
+        cfg = CrabCameraConfig::default();
+        cfg.advanced.preview_max_dim = Some(0);
+        assert!(cfg.validate().is_err(), "zero preview size should fail");
+
+        cfg = CrabCameraConfig::default();
+        cfg.advanced.reorder_window = MAX_REORDER_WINDOW + 1;
+        assert!(
+            cfg.validate().is_err(),
+            "oversized reorder window should fail"
+        );
+    }
+
+    #[test]
+    fn test_default_path_and_load_or_default() {
+        assert_eq!(
+            CrabCameraConfig::default_path(),
+            PathBuf::from("crabcamera.toml")
+        );
+
+        // Ensure missing default file path still returns a usable default.
+        let loaded = CrabCameraConfig::load_or_default();
+        assert_eq!(
+            loaded.camera.default_fps,
+            CrabCameraConfig::default().camera.default_fps
+        );
+    }
+
+    #[test]
+    fn test_load_from_file_parse_error() {
+        let temp_dir = std::env::temp_dir();
+        let bad_path = temp_dir.join("test_crabcamera_invalid.toml");
+
+        let _ = fs::remove_file(&bad_path);
+        fs::write(&bad_path, "this-is-not-valid-toml = = =").expect("write invalid toml");
+
+        let result = CrabCameraConfig::load_from_file(&bad_path);
+        assert!(result.is_err());
+        let msg = result.expect_err("invalid toml should error").to_string();
+        assert!(msg.contains("Failed to parse config file"));
+
+        let _ = fs::remove_file(&bad_path);
+    }
+
+    #[test]
+    fn test_save_to_file_create_parent_directory() {
+        let base = std::env::temp_dir().join("crabcamera_config_nested_test");
+        let nested = base.join("deep").join("crabcamera.toml");
+        let _ = fs::remove_dir_all(&base);
+
+        let cfg = CrabCameraConfig::default();
+        cfg.save_to_file(&nested)
+            .expect("save should create parent dirs");
+        assert!(nested.exists());
+
+        let loaded = CrabCameraConfig::load_from_file(&nested).expect("load saved config");
+        assert_eq!(loaded.storage.default_format, cfg.storage.default_format);
+
+        let _ = fs::remove_dir_all(&base);
+    }
+}
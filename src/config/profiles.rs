@@ -0,0 +1,256 @@
+//! Per-device camera control profiles
+//!
+//! Stores a [`CameraControls`] snapshot for each camera as JSON in the
+//! application config directory, keyed by the device's persistent id (see
+//! [`CameraSystem::stable_device_id`]) so a profile follows the physical
+//! camera rather than its enumeration index.
+
+use crate::constants::{APP_CONFIG_DIR_NAME, CAMERA_PROFILES_FILE_NAME, CONFIG_DIR_ENV_VAR};
+use crate::errors::CameraError;
+use crate::platform::{CameraSystem, PlatformCamera};
+use crate::types::{CameraControls, ControlApplicationResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Whether saved profiles are applied when a camera is first opened
+static AUTO_APPLY: AtomicBool = AtomicBool::new(false);
+
+/// Saved control settings for one camera
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CameraProfile {
+    /// Control values to restore.
+    pub controls: CameraControls,
+    /// When the profile was last saved.
+    pub saved_at: DateTime<Utc>,
+}
+
+impl CameraProfile {
+    /// Create a profile from the given controls, stamped with the current time
+    pub fn new(controls: CameraControls) -> Self {
+        Self {
+            controls,
+            saved_at: Utc::now(),
+        }
+    }
+}
+
+/// All saved camera profiles, keyed by persistent device id
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CameraProfileStore {
+    /// Profiles by device key.
+    pub profiles: HashMap<String, CameraProfile>,
+}
+
+impl CameraProfileStore {
+    /// Load profiles from a JSON file
+    ///
+    /// A missing file yields an empty store.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::InitializationError`] if the file cannot be
+    /// read or its contents cannot be parsed as JSON.
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, CameraError> {
+        let path = path.as_ref();
+
+        if !path.exists() {
+            log::debug!("No camera profiles at {}", path.display());
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path).map_err(|e| {
+            CameraError::InitializationError(format!("Failed to read camera profiles: {e}"))
+        })?;
+
+        serde_json::from_str(&contents).map_err(|e| {
+            CameraError::InitializationError(format!("Failed to parse camera profiles: {e}"))
+        })
+    }
+
+    /// Save profiles to a JSON file
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::InitializationError`] if the parent directory
+    /// cannot be created, if the profiles cannot be serialized, or if the file
+    /// cannot be written.
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), CameraError> {
+        let path = path.as_ref();
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                CameraError::InitializationError(format!("Failed to create config directory: {e}"))
+            })?;
+        }
+
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            CameraError::InitializationError(format!("Failed to serialize camera profiles: {e}"))
+        })?;
+
+        super::write_atomically(path, json.as_bytes()).map_err(|e| {
+            CameraError::InitializationError(format!("Failed to write camera profiles: {e}"))
+        })?;
+
+        log::info!("Saved camera profiles to {}", path.display());
+        Ok(())
+    }
+
+    /// Default profile file path inside [`config_dir`]
+    pub fn default_path() -> PathBuf {
+        config_dir().join(CAMERA_PROFILES_FILE_NAME)
+    }
+
+    /// Get the profile stored under `key`
+    pub fn get(&self, key: &str) -> Option<&CameraProfile> {
+        self.profiles.get(key)
+    }
+
+    /// Store `profile` under `key`, replacing any previous one
+    pub fn insert(&mut self, key: String, profile: CameraProfile) {
+        self.profiles.insert(key, profile);
+    }
+}
+
+/// Application config directory
+///
+/// Uses `CRABCAMERA_CONFIG_DIR` when set, otherwise the platform's per-user
+/// config location (`%APPDATA%`, `~/Library/Application Support` or
+/// `$XDG_CONFIG_HOME`/`~/.config`) with a `crabcamera` subdirectory.
+pub fn config_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(CONFIG_DIR_ENV_VAR) {
+        return PathBuf::from(dir);
+    }
+
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home.map(|h| h.join("Library").join("Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home.map(|h| h.join(".config")))
+    };
+
+    base.unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_CONFIG_DIR_NAME)
+}
+
+/// Enable or disable applying saved profiles when a camera is first opened
+pub fn set_auto_apply(enabled: bool) {
+    AUTO_APPLY.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether saved profiles are applied when a camera is first opened
+pub fn auto_apply_enabled() -> bool {
+    AUTO_APPLY.load(Ordering::Relaxed)
+}
+
+/// Save `camera`'s current controls as the profile for `device_id`
+///
+/// # Errors
+/// Returns a [`CameraError`] if the controls cannot be read from the camera
+/// or the profile file cannot be loaded or written.
+pub fn save_profile(
+    device_id: &str,
+    camera: &PlatformCamera,
+) -> Result<CameraProfile, CameraError> {
+    let key = CameraSystem::stable_device_id(device_id);
+    let profile = CameraProfile::new(camera.get_controls()?);
+
+    let path = CameraProfileStore::default_path();
+    let mut store = CameraProfileStore::load_from_file(&path)?;
+    store.insert(key.clone(), profile.clone());
+    store.save_to_file(&path)?;
+
+    log::info!("Saved camera profile for device {device_id} ({key})");
+    Ok(profile)
+}
+
+/// Apply the saved profile for `device_id` to `camera`
+///
+/// Controls the camera no longer supports are skipped and listed in the
+/// result's `rejected` field. Returns `Ok(None)` if no profile is saved.
+///
+/// # Errors
+/// Returns a [`CameraError`] if the profile file cannot be loaded or the
+/// camera fails to apply the controls.
+pub fn apply_profile(
+    device_id: &str,
+    camera: &mut PlatformCamera,
+) -> Result<Option<ControlApplicationResult>, CameraError> {
+    let key = CameraSystem::stable_device_id(device_id);
+    let store = CameraProfileStore::load_from_file(CameraProfileStore::default_path())?;
+    let Some(profile) = store.get(&key) else {
+        log::debug!("No saved camera profile for device {device_id} ({key})");
+        return Ok(None);
+    };
+
+    let result = camera.apply_controls(&profile.controls)?;
+    if !result.rejected.is_empty() {
+        log::warn!(
+            "Skipped unsupported controls from saved profile for device {device_id}: {}",
+            result.rejected.join(", ")
+        );
+    }
+    log::info!(
+        "Applied saved camera profile for device {device_id} (applied={}, rejected={})",
+        result.applied.len(),
+        result.rejected.len()
+    );
+    Ok(Some(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_store_save_and_load() {
+        let path = std::env::temp_dir()
+            .join("crabcamera_profiles_test")
+            .join(CAMERA_PROFILES_FILE_NAME);
+        let _ = fs::remove_file(&path);
+
+        let mut store = CameraProfileStore::default();
+        store.insert(
+            "usb-046d:0825-ABC123".to_string(),
+            CameraProfile::new(CameraControls::professional()),
+        );
+        store.save_to_file(&path).expect("save profiles");
+
+        let loaded = CameraProfileStore::load_from_file(&path).expect("load profiles");
+        assert_eq!(
+            loaded.get("usb-046d:0825-ABC123"),
+            store.get("usb-046d:0825-ABC123")
+        );
+        assert!(loaded.get("0").is_none());
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_profile_store_missing_and_invalid_files() {
+        let missing = CameraProfileStore::load_from_file("nonexistent_profiles.json")
+            .expect("missing file is an empty store");
+        assert!(missing.profiles.is_empty());
+
+        let bad_path = std::env::temp_dir().join("crabcamera_profiles_invalid.json");
+        fs::write(&bad_path, "{ not json").expect("write invalid json");
+        let msg = CameraProfileStore::load_from_file(&bad_path)
+            .expect_err("invalid json should error")
+            .to_string();
+        assert!(msg.contains("Failed to parse camera profiles"));
+
+        let _ = fs::remove_file(&bad_path);
+    }
+
+    #[test]
+    fn test_default_path_is_in_config_dir() {
+        let path = CameraProfileStore::default_path();
+        assert!(path.ends_with(CAMERA_PROFILES_FILE_NAME));
+        assert_eq!(path.parent(), Some(config_dir().as_path()));
+    }
+}
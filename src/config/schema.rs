@@ -0,0 +1,277 @@
+//! JSON-schema-like description of [`CrabCameraConfig`] for settings UIs
+//!
+//! Field names, types and defaults come from serializing the default
+//! config, so new fields show up without touching this module. Ranges,
+//! allowed values and nullable types, which serialization cannot reveal,
+//! come from `FIELD_RULES`; the tests check each rule against
+//! [`CrabCameraConfig::validate`].
+
+use serde_json::{json, Map, Value};
+
+use super::CrabCameraConfig;
+use crate::constants::{MAX_FRAME_QUEUE_CAPACITY, MAX_REORDER_WINDOW};
+
+/// Constraints of one field, addressed by its dotted path
+struct FieldRule {
+    path: &'static str,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    /// JSON type of `Option` fields, whose default (`null`) hides it
+    nullable: Option<&'static str>,
+    allowed: &'static [&'static str],
+}
+
+impl FieldRule {
+    const fn new(path: &'static str) -> Self {
+        Self {
+            path,
+            minimum: None,
+            maximum: None,
+            nullable: None,
+            allowed: &[],
+        }
+    }
+
+    const fn range(mut self, minimum: f64, maximum: f64) -> Self {
+        self.minimum = Some(minimum);
+        self.maximum = Some(maximum);
+        self
+    }
+
+    const fn min(mut self, minimum: f64) -> Self {
+        self.minimum = Some(minimum);
+        self
+    }
+
+    const fn nullable(mut self, json_type: &'static str) -> Self {
+        self.nullable = Some(json_type);
+        self
+    }
+
+    const fn one_of(mut self, allowed: &'static [&'static str]) -> Self {
+        self.allowed = allowed;
+        self
+    }
+}
+
+// Queue and window limits are small constants, exact as f64
+#[allow(clippy::cast_precision_loss)]
+const FIELD_RULES: &[FieldRule] = &[
+    FieldRule::new("camera.default_resolution").min(1.0),
+    FieldRule::new("camera.default_fps").range(1.0, 240.0),
+    FieldRule::new("quality.min_blur_threshold").range(0.0, 1.0),
+    FieldRule::new("quality.min_exposure_score").range(0.0, 1.0),
+    FieldRule::new("quality.min_overall_score").range(0.0, 1.0),
+    FieldRule::new("storage.jpeg_quality").range(1.0, 100.0),
+    FieldRule::new("storage.temp_dir").nullable("string"),
+    FieldRule::new("advanced.focus_stack_steps").range(1.0, 100.0),
+    FieldRule::new("advanced.hdr_brackets").range(1.0, 10.0),
+    FieldRule::new("advanced.frame_queue_capacity").range(1.0, MAX_FRAME_QUEUE_CAPACITY as f64),
+    FieldRule::new("advanced.preview_max_dim")
+        .min(1.0)
+        .nullable("integer"),
+    FieldRule::new("advanced.reorder_window").range(0.0, MAX_REORDER_WINDOW as f64),
+    FieldRule::new("advanced.thread_affinity.capture.core")
+        .min(0.0)
+        .nullable("integer"),
+    FieldRule::new("advanced.thread_affinity.capture.priority").one_of(PRIORITIES),
+    FieldRule::new("advanced.thread_affinity.encode.core")
+        .min(0.0)
+        .nullable("integer"),
+    FieldRule::new("advanced.thread_affinity.encode.priority").one_of(PRIORITIES),
+];
+
+const PRIORITIES: &[&str] = &["normal", "high", "max"];
+
+/// Schema of the whole configuration
+///
+/// Each object has `type: "object"` and `properties`; each leaf has `type`
+/// (`["integer", "null"]` for optional fields), `default`, and where
+/// applicable `minimum`/`maximum` (for arrays: of every item), `enum`, and
+/// `minLength` for strings that must not be empty. Pinned thread cores are
+/// additionally bounded by this machine's core count.
+pub fn config_schema() -> Value {
+    let defaults = serde_json::to_value(CrabCameraConfig::default()).unwrap_or(Value::Null);
+    let mut schema = describe(&defaults);
+    for rule in FIELD_RULES {
+        if let Some(field) = field_mut(&mut schema, rule.path) {
+            apply_rule(field, rule);
+        }
+    }
+    let cores = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+    for role in ["capture", "encode"] {
+        let path = format!("advanced.thread_affinity.{role}.core");
+        if let Some(Value::Object(field)) = field_mut(&mut schema, &path) {
+            field.insert("maximum".to_string(), json!(cores - 1));
+        }
+    }
+    if let Some(Value::Object(field)) = field_mut(&mut schema, "storage.file_name_template") {
+        field.insert("minLength".to_string(), json!(1));
+    }
+    schema
+}
+
+/// Schema node describing `value`, with `value` as the default
+fn describe(value: &Value) -> Value {
+    match value {
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(name, field)| (name.clone(), describe(field)))
+                .collect();
+            json!({ "type": "object", "properties": properties })
+        }
+        Value::Array(items) => {
+            let mut node = json!({
+                "type": "array",
+                "default": value,
+                "minItems": items.len(),
+                "maxItems": items.len(),
+            });
+            if let Some(first) = items.first() {
+                node["items"] = json!({ "type": json_type(first) });
+            }
+            node
+        }
+        _ => json!({ "type": json_type(value), "default": value }),
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Leaf node at a dotted config path
+fn field_mut<'a>(schema: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    path.split('.').try_fold(schema, |node, name| {
+        node.get_mut("properties")?.get_mut(name)
+    })
+}
+
+fn apply_rule(field: &mut Value, rule: &FieldRule) {
+    if let Some(json_type) = rule.nullable {
+        field["type"] = json!([json_type, "null"]);
+    }
+    // Array bounds apply to every item
+    let target = if field["type"] == "array" {
+        &mut field["items"]
+    } else {
+        field
+    };
+    if let Some(minimum) = rule.minimum {
+        target["minimum"] = number(minimum);
+    }
+    if let Some(maximum) = rule.maximum {
+        target["maximum"] = number(maximum);
+    }
+    if !rule.allowed.is_empty() {
+        target["enum"] = json!(rule.allowed);
+    }
+}
+
+/// Whole bounds as JSON integers, so integer fields get integer limits
+// Bounds are small and checked to be whole before narrowing
+#[allow(clippy::cast_possible_truncation)]
+fn number(bound: f64) -> Value {
+    if bound.fract().abs() < f64::EPSILON {
+        json!(bound as i64)
+    } else {
+        json!(bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_value(path: &str, value: Value) -> Result<(), String> {
+        let mut config = serde_json::to_value(CrabCameraConfig::default()).unwrap();
+        let field = path
+            .split('.')
+            .try_fold(&mut config, |node, name| node.get_mut(name))
+            .unwrap();
+        *field = match field {
+            Value::Array(items) => Value::Array(vec![value; items.len()]),
+            _ => value,
+        };
+        serde_json::from_value::<CrabCameraConfig>(config)
+            .map_err(|e| e.to_string())?
+            .validate()
+    }
+
+    #[test]
+    fn test_schema_lists_every_field_with_defaults() {
+        let schema = config_schema();
+        let fps = &schema["properties"]["camera"]["properties"]["default_fps"];
+        assert_eq!(fps["type"], "integer");
+        assert_eq!(fps["default"], 30);
+        assert_eq!(fps["maximum"], 240);
+        let blur = &schema["properties"]["quality"]["properties"]["min_blur_threshold"];
+        assert_eq!(blur["type"], "number");
+        let resolution = &schema["properties"]["camera"]["properties"]["default_resolution"];
+        assert_eq!(resolution["items"]["minimum"], 1);
+        let preview = &schema["properties"]["advanced"]["properties"]["preview_max_dim"];
+        assert_eq!(preview["type"], json!(["integer", "null"]));
+    }
+
+    #[test]
+    fn test_rules_match_validation() {
+        let schema = config_schema();
+        for rule in FIELD_RULES {
+            let mut probe = schema.clone();
+            assert!(field_mut(&mut probe, rule.path).is_some(), "{}", rule.path);
+            if rule.path.contains(".core") {
+                continue; // bounded by the machine, covered in threads.rs
+            }
+            if let Some(minimum) = rule.minimum {
+                assert!(
+                    with_value(rule.path, number(minimum)).is_ok(),
+                    "{}",
+                    rule.path
+                );
+                let below = if minimum >= 1.0 {
+                    minimum - 1.0
+                } else {
+                    minimum - 0.01
+                };
+                assert!(
+                    with_value(rule.path, number(below)).is_err(),
+                    "{}",
+                    rule.path
+                );
+            }
+            if let Some(maximum) = rule.maximum {
+                assert!(
+                    with_value(rule.path, number(maximum)).is_ok(),
+                    "{}",
+                    rule.path
+                );
+                let above = if maximum > 1.0 {
+                    maximum + 1.0
+                } else {
+                    maximum + 0.01
+                };
+                assert!(
+                    with_value(rule.path, number(above)).is_err(),
+                    "{}",
+                    rule.path
+                );
+            }
+            for allowed in rule.allowed {
+                assert!(
+                    with_value(rule.path, json!(allowed)).is_ok(),
+                    "{}",
+                    rule.path
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Core affinity and scheduling priority for internal threads
+//!
+//! [`ThreadAffinityConfig`] pins crabcamera's capture and encode threads to
+//! a core and/or raises their priority, to keep them clear of the
+//! application's own threads during real-time recording. The default leaves
+//! every thread untouched.
+//!
+//! Tuned threads:
+//! - capture: the [`FrameStream`](crate::platform::FrameStream) and headless
+//!   capture threads, which run the camera backend and frame callbacks, and
+//!   the headless audio capture thread
+//! - encode: the recorder's audio capture/Opus thread
+//!
+//! H.264 encoding runs on the thread calling `Recorder::write_frame`; call
+//! [`tune_current_thread`] with [`ThreadRole::Encode`] from that thread to
+//! apply the same settings to it.
+//!
+//! Platform support:
+//!
+//! | | Core pinning | Priority |
+//! |---|---|---|
+//! | Linux | yes | `High`/`Max` need `CAP_SYS_NICE` or a raised `RLIMIT_NICE` |
+//! | Windows | yes | yes |
+//! | macOS | affinity hint only, ignored on Apple Silicon | yes |
+//!
+//! Settings that cannot be applied are logged as warnings; the thread keeps
+//! running with the OS defaults.
+
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+// Applied by threads as they start; set from the global config
+static THREAD_AFFINITY: RwLock<ThreadAffinityConfig> = RwLock::new(ThreadAffinityConfig {
+    capture: ThreadTuning {
+        core: None,
+        priority: ThreadPriorityLevel::Normal,
+    },
+    encode: ThreadTuning {
+        core: None,
+        priority: ThreadPriorityLevel::Normal,
+    },
+});
+
+/// Scheduling priority for an internal thread
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreadPriorityLevel {
+    /// Leave the OS default
+    #[default]
+    Normal,
+    /// Above normal
+    High,
+    /// Highest priority available to the process
+    Max,
+}
+
+/// Affinity and priority for one kind of internal thread
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadTuning {
+    /// Core index to pin the thread to (`None` = let the OS schedule it)
+    #[serde(default)]
+    pub core: Option<usize>,
+    /// Scheduling priority
+    #[serde(default)]
+    pub priority: ThreadPriorityLevel,
+}
+
+impl ThreadTuning {
+    /// Whether this leaves the thread untouched
+    pub fn is_default(&self) -> bool {
+        self.core.is_none() && self.priority == ThreadPriorityLevel::Normal
+    }
+}
+
+/// Affinity and priority of crabcamera's capture and encode threads
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadAffinityConfig {
+    /// Camera capture and frame callback threads
+    #[serde(default)]
+    pub capture: ThreadTuning,
+    /// Encoding threads
+    #[serde(default)]
+    pub encode: ThreadTuning,
+}
+
+impl ThreadAffinityConfig {
+    /// Check that pinned cores exist on this machine
+    ///
+    /// # Errors
+    /// Returns an `Err` naming the first core index beyond the available
+    /// parallelism.
+    pub fn validate(&self) -> Result<(), String> {
+        let cores = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+        for (role, tuning) in [("capture", &self.capture), ("encode", &self.encode)] {
+            if let Some(core) = tuning.core.filter(|&core| core >= cores) {
+                return Err(format!(
+                    "{role} thread core {core} does not exist ({cores} cores available)"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Kind of internal thread, selecting its [`ThreadTuning`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadRole {
+    /// Camera capture and frame callbacks
+    Capture,
+    /// Video or audio encoding
+    Encode,
+}
+
+/// Set the affinity and priority applied to threads started from now on
+pub fn set_thread_affinity(config: ThreadAffinityConfig) {
+    if let Ok(mut current) = THREAD_AFFINITY.write() {
+        *current = config;
+    }
+}
+
+/// Affinity and priority currently applied to new threads
+pub fn thread_affinity() -> ThreadAffinityConfig {
+    THREAD_AFFINITY
+        .read()
+        .map(|config| config.clone())
+        .unwrap_or_default()
+}
+
+/// Apply the configured tuning for `role` to the calling thread
+///
+/// Does nothing with the default settings. Failures are logged, not
+/// returned, since the thread works either way.
+pub fn tune_current_thread(role: ThreadRole) {
+    let config = thread_affinity();
+    let tuning = match role {
+        ThreadRole::Capture => config.capture,
+        ThreadRole::Encode => config.encode,
+    };
+    if tuning.is_default() {
+        return;
+    }
+
+    if let Some(core) = tuning.core {
+        let pinned = core_affinity::get_core_ids()
+            .and_then(|ids| ids.into_iter().find(|id| id.id == core))
+            .is_some_and(core_affinity::set_for_current);
+        if !pinned {
+            log::warn!("Could not pin {role:?} thread to core {core}");
+        }
+    }
+
+    let priority = match tuning.priority {
+        ThreadPriorityLevel::Normal => None,
+        ThreadPriorityLevel::High => thread_priority::ThreadPriorityValue::try_from(75u8)
+            .ok()
+            .map(thread_priority::ThreadPriority::Crossplatform),
+        ThreadPriorityLevel::Max => Some(thread_priority::ThreadPriority::Max),
+    };
+    if let Some(priority) = priority {
+        if let Err(e) = thread_priority::set_current_thread_priority(priority) {
+            log::warn!(
+                "Could not set {role:?} thread priority to {:?}: {e:?}",
+                tuning.priority
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_noop() {
+        let config = ThreadAffinityConfig::default();
+        assert!(config.capture.is_default());
+        assert!(config.encode.is_default());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_missing_core() {
+        let config = ThreadAffinityConfig {
+            encode: ThreadTuning {
+                core: Some(usize::MAX),
+                priority: ThreadPriorityLevel::Normal,
+            },
+            ..Default::default()
+        };
+        assert!(config.validate().unwrap_err().contains("encode"));
+    }
+
+    #[test]
+    fn test_deserializes_partial_toml() {
+        let config: ThreadAffinityConfig =
+            toml::from_str("[capture]\ncore = 0\npriority = \"max\"\n").unwrap();
+        assert_eq!(config.capture.core, Some(0));
+        assert_eq!(config.capture.priority, ThreadPriorityLevel::Max);
+        assert!(config.encode.is_default());
+    }
+}
@@ -33,9 +33,19 @@ pub const DEFAULT_FPS: f32 = 30.0;
 /// High frame rate
 pub const HIGH_FPS: f32 = 60.0;
 
+/// Shortfall below a requested frame rate still counted as meeting it
+/// during format negotiation (covers 29.97 vs 30)
+pub const FORMAT_FPS_TOLERANCE: f32 = 0.5;
+/// Lowest frame rate preferred when picking a camera's largest mode; slower
+/// modes are used only if nothing else reaches it
+pub const MAX_RESOLUTION_MIN_FPS: f32 = 5.0;
+
 /// Linux video device prefix
 pub const LINUX_VIDEO_DEVICE_PREFIX: &str = "/dev/video";
 
+/// Poll interval while waiting for a busy device under `OpenPolicy::RetryFor` (ms)
+pub const DEVICE_BUSY_POLL_INTERVAL_MS: u64 = 250;
+
 /// Default ISO sensitivity
 pub const DEFAULT_ISO: u32 = 400;
 
@@ -45,12 +55,62 @@ pub const MIN_ISO: u32 = 50;
 /// Maximum ISO sensitivity
 pub const MAX_ISO: u32 = 12800;
 
+/// ISO sensitivity corresponding to 0 dB of sensor gain
+pub const ISO_AT_UNITY_GAIN: u32 = 100;
+
+/// Exposure range assumed when a camera reports none (seconds)
+pub const DEFAULT_EXPOSURE_RANGE: (f32, f32) = (0.0001, 10.0);
+
+/// Shortest exposure time used for log-scale conversions (seconds), in
+/// place of a reported minimum of zero
+pub const MIN_EXPOSURE_SECONDS: f32 = 0.000_001;
+
+/// Unit of the V4L2 absolute exposure control (seconds)
+pub const V4L2_EXPOSURE_UNIT_SECONDS: f32 = 0.0001;
+
+/// Autofocus Lock - Default Wait Before Giving Up (ms)
+pub const AUTOFOCUS_LOCK_TIMEOUT_MS: u64 = 2000;
+
+/// Autofocus Lock - Relative Sharpness Change Below Which a Frame Counts as Settled
+pub const AUTOFOCUS_SETTLE_TOLERANCE: f64 = 0.05;
+
+/// Autofocus Lock - Consecutive Settled Frames That Count as a Lock
+pub const AUTOFOCUS_SETTLE_FRAMES: u32 = 3;
+
+/// Tap-to-Focus - Side of the Scored Region as a Fraction of the Frame
+pub const AUTOFOCUS_REGION_FRACTION: f32 = 0.2;
+
+/// Tap-to-Focus - Focus Distances Tried per Software Sweep Pass
+pub const AUTOFOCUS_SWEEP_STEPS: u32 = 9;
+
 /// Default video format type
 pub const DEFAULT_FORMAT_TYPE: &str = "YUYV";
 
 /// RGB format type
 pub const FORMAT_RGB: &str = "RGB8";
 
+/// RGBA format type (RGB8 plus an alpha channel)
+pub const FORMAT_RGBA: &str = "RGBA8";
+
+/// 16-bit-per-channel RGB format type (little-endian samples)
+pub const FORMAT_RGB16: &str = "RGB16";
+
+/// 16-bit depth format type (little-endian distance samples)
+pub const FORMAT_DEPTH16: &str = "Z16";
+
+/// Meters per raw depth unit when the device does not report one
+/// (1 mm, the Intel RealSense default)
+pub const DEFAULT_DEPTH_UNITS: f32 = 0.001;
+
+/// Bits per color sample of 8-bit formats
+pub const DEFAULT_BIT_DEPTH: u8 = 8;
+
+/// HDR tone mapping - luminance mapped to SDR white (nits, ITU-R BT.2408)
+pub const HDR_SDR_WHITE_NITS: f32 = 203.0;
+
+/// HDR tone mapping - luminance mapped to full white (nits, typical HDR10 mastering peak)
+pub const HDR_PEAK_NITS: f32 = 1000.0;
+
 /// MJPEG format type
 pub const FORMAT_MJPEG: &str = "MJPEG";
 
@@ -60,6 +120,13 @@ pub const DEFAULT_POOL_SIZE: usize = 10;
 /// Default bytes per pixel (RGB8)
 pub const BYTES_PER_PIXEL_RGB: u32 = 3;
 
+/// Sliding window used for delivered-FPS measurement (seconds)
+pub const PERF_FPS_WINDOW_SECS: f64 = 2.0;
+
+/// Capture latencies up to this many milliseconds are histogrammed in
+/// 1 ms buckets for session percentiles; slower captures share one bucket
+pub const SESSION_LATENCY_HISTOGRAM_MS: usize = 1000;
+
 /// Default Reconnect Attempts
 pub const DEFAULT_RECONNECT_ATTEMPTS: u32 = 3;
 
@@ -87,6 +154,30 @@ pub const DEFAULT_OUTPUT_DIRECTORY: &str = "./captures";
 /// Default Date Format
 pub const DEFAULT_DATE_FORMAT: &str = "YYYY-MM-DD";
 
+/// Default File Name Template for Automatically Named Captures
+pub const DEFAULT_FILE_NAME_TEMPLATE: &str = "{device}_{timestamp}_{seq}";
+
+/// Automatic Naming - Zero-Padded Width of `{seq}`
+pub const FILE_NAME_SEQ_WIDTH: usize = 6;
+
+/// Temporary Captures - File Name Prefix (cleanup only touches these files)
+pub const TEMP_CAPTURE_PREFIX: &str = "crabcamera-tmp-";
+
+/// Temporary Captures - Default Age Before Cleanup Removes Them (seconds)
+pub const DEFAULT_TEMP_CAPTURE_MAX_AGE_SECS: u64 = 24 * 60 * 60;
+
+/// Config Directory - Environment Variable Override
+pub const CONFIG_DIR_ENV_VAR: &str = "CRABCAMERA_CONFIG_DIR";
+
+/// Config Directory - Application Subdirectory Name
+pub const APP_CONFIG_DIR_NAME: &str = "crabcamera";
+
+/// Config File - Current Schema Version Written to `version`
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Camera Profiles - File Name Inside the Config Directory
+pub const CAMERA_PROFILES_FILE_NAME: &str = "camera_profiles.json";
+
 /// Default Image Format
 pub const DEFAULT_IMAGE_FORMAT: &str = "jpeg";
 
@@ -99,6 +190,16 @@ pub const DEFAULT_FOCUS_STACK_STEPS: u32 = 10;
 /// Default HDR Brackets
 pub const DEFAULT_HDR_BRACKETS: u32 = 3;
 
+/// Default Frame Queue Capacity
+/// Frames buffered per camera for `get_queued_frames` before the oldest is dropped
+pub const DEFAULT_FRAME_QUEUE_CAPACITY: usize = 8;
+
+/// Maximum Frame Queue Capacity
+pub const MAX_FRAME_QUEUE_CAPACITY: usize = 256;
+
+/// Maximum frames held back to restore timestamp order
+pub const MAX_REORDER_WINDOW: usize = 30;
+
 /// Audio sample rate (Standard Opus requirement)
 pub const AUDIO_SAMPLE_RATE: u32 = 48000;
 
@@ -118,6 +219,22 @@ pub const AUDIO_BUFFER_FRAMES: usize = 256;
 pub const AUDIO_DEVICE_DEFAULT: &str = "default";
 /// Audio Capture - Default Bitrate (128kbps)
 pub const AUDIO_DEFAULT_BITRATE: u32 = 128_000;
+/// Audio Capture - ID Prefix for Loopback Devices
+pub const AUDIO_LOOPBACK_ID_PREFIX: &str = "loopback";
+/// Audio Capture - PulseAudio/PipeWire Control Tool (Linux loopback listing)
+pub const AUDIO_PACTL: &str = "pactl";
+/// Audio Capture - PulseAudio/PipeWire Recorder (Linux monitor capture)
+pub const AUDIO_PAREC: &str = "parec";
+/// Audio Capture - Monitor Read Chunk (ms)
+pub const AUDIO_MONITOR_CHUNK_MS: u32 = 10;
+/// Audio Mixer - Output Block Duration (ms)
+pub const AUDIO_MIXER_BLOCK_MS: u32 = 20;
+/// Audio Mixer - Timestamp Drift Tolerated Before Padding or Dropping (s)
+pub const AUDIO_MIXER_GAP_TOLERANCE_SECS: f64 = 0.04;
+/// Audio Mixer - Lag After Which a Silent Source Is Padded (s)
+pub const AUDIO_MIXER_MAX_LAG_SECS: f64 = 0.2;
+/// Audio Capture - Virtual Loopback Drivers Recognized on macOS
+pub const AUDIO_MACOS_LOOPBACK_DRIVERS: &[&str] = &["BlackHole", "Soundflower", "Loopback Audio"];
 
 /// CLI Defaults
 /// Default timeout for capture operations in ms
@@ -221,6 +338,16 @@ pub const ALIGNMENT_SIGNIFICANT_ROTATION: f32 = 0.01;
 pub const ALIGNMENT_SIGNIFICANT_SCALE: f32 = 0.01;
 /// Sampling step for alignment
 pub const ALIGNMENT_SAMPLING_STEP: usize = 4;
+/// Side of the square working image for phase correlation (power of two)
+pub const ALIGNMENT_PHASE_SIZE: usize = 128;
+/// Largest rotation searched, in radians (10°)
+pub const ALIGNMENT_MAX_ROTATION: f32 = 0.174_532_93;
+/// Coarse rotation search step, in radians (1°)
+pub const ALIGNMENT_ROTATION_STEP: f32 = 0.017_453_292;
+/// Largest scale deviation from 1.0 searched by affine alignment
+pub const ALIGNMENT_MAX_SCALE_DELTA: f32 = 0.1;
+/// Scale search step for affine alignment
+pub const ALIGNMENT_SCALE_STEP: f32 = 0.02;
 
 /// Focus Stacking - Bracket Limits
 /// Minimum number of brackets (2)
@@ -251,6 +378,8 @@ pub const FOCUS_STACK_MAX_DIST: f32 = 1.0;
 pub const CAPTURE_RETRY_COUNT: u32 = 3;
 /// Number of warmup frames to discard
 pub const CAPTURE_WARMUP_FRAMES: u32 = 5;
+/// Upper bound on caller-requested warmup frames
+pub const CAPTURE_WARMUP_MAX_FRAMES: u32 = 120;
 /// Delay between warmup frames in ms
 pub const CAPTURE_WARMUP_DELAY_MS: u64 = 30;
 /// Warmup frames after reconnection
@@ -259,6 +388,14 @@ pub const CAPTURE_RECONNECT_WARMUP_FRAMES: u32 = 10;
 pub const CAPTURE_RECONNECT_WARMUP_DELAY_MS: u64 = 50;
 /// Maximum number of frames in a sequence
 pub const CAPTURE_SEQUENCE_MAX_COUNT: u32 = 20;
+/// Maximum number of frames in a captured GIF
+pub const GIF_MAX_FRAMES: u32 = 100;
+/// `NeuQuant` sampling speed for GIF palettes (1 = best quality, 30 = fastest)
+pub const GIF_QUANTIZE_SPEED: i32 = 10;
+/// Longest side of the thumbnails attached to capture progress events
+pub const PROGRESS_THUMBNAIL_MAX_DIM: u32 = 160;
+/// Time-lapse intervals shorter than this keep the camera open between shots
+pub const TIMELAPSE_KEEP_WARM_SECS: f64 = 30.0;
 /// Maximum number of frames in a burst
 pub const BURST_MAX_COUNT: u32 = 50;
 
@@ -289,8 +426,16 @@ pub const MOCK_SLOW_CAPTURE_DELAY_MS: u64 = 100;
 /// Platform - Windows Metadata
 /// MJPEG Header Signature
 pub const MJPEG_SIGNATURE: [u8; 3] = [0xFF, 0xD8, 0xFF];
+/// JPEG Start-of-Image Marker
+pub const JPEG_SOI: [u8; 2] = [0xFF, 0xD8];
+/// JPEG End-of-Image Marker
+pub const JPEG_EOI: [u8; 2] = [0xFF, 0xD9];
+/// Extra captures attempted when a frame fails integrity validation
+pub const FRAME_VALIDATION_RETRIES: u32 = 2;
 /// Percentage of non-zero bytes required to consider a frame valid
 pub const VALID_FRAME_NONZERO_PERCENT: f64 = 1.0;
+/// Only every Nth pixel is compared when checking frames for duplicates
+pub const FRAME_DUPLICATE_SAMPLE_STRIDE: usize = 4;
 
 /// Focus Stacking - Defaults
 /// Default delay between focus steps in ms
@@ -301,6 +446,12 @@ pub const FOCUS_STACK_DEFAULT_SHARPNESS: f32 = 0.5;
 pub const FOCUS_STACK_DEFAULT_BLEND_LEVELS: u32 = 5;
 /// Default bracket overlap factor
 pub const FOCUS_STACK_BRACKET_OVERLAP: f32 = 1.2;
+/// Minimum pyramid blend levels
+pub const FOCUS_STACK_MIN_BLEND_LEVELS: u32 = 3;
+/// Maximum pyramid blend levels
+pub const FOCUS_STACK_MAX_BLEND_LEVELS: u32 = 10;
+/// Estimated capture time (ms) above which config validation warns
+pub const FOCUS_STACK_SLOW_CAPTURE_MS: u64 = 4000;
 
 /// Exposure Analysis - Brightness Thresholds
 /// Threshold for low brightness
@@ -339,6 +490,12 @@ pub const RECORDING_AUDIO_CHANNEL_CAPACITY: usize = 256;
 /// Recording - Audio Thread Sleep Duration (ms)
 pub const RECORDING_AUDIO_SLEEP_MS: u64 = 1;
 
+/// Recording - Longest wait for the first audio before video starts (ms)
+pub const RECORDING_AV_START_TIMEOUT_MS: u64 = 1000;
+
+/// Recording - Audio frames held while waiting for the first video frame
+pub const RECORDING_AUDIO_PREROLL_FRAMES: usize = 256;
+
 /// Defaults
 /// Default camera ID
 pub const DEFAULT_CAMERA_ID: &str = "0";
@@ -358,13 +515,33 @@ pub const RECORDING_QUALITY_PRESET_HIGH: &str = "high";
 pub const RECORDING_QUALITY_PRESET_4K: &str = "4k";
 /// Recording session ID prefix
 pub const RECORDING_SESSION_PREFIX: &str = "rec_";
+/// Consecutive failed captures after which a background camera recording
+/// finishes its file and stops
+pub const CAMERA_RECORDING_MAX_FAILURES: u32 = 30;
+/// How often a background recording checks whether its paused camera has
+/// resumed
+pub const CAMERA_RECORDING_PAUSE_POLL_MS: u64 = 20;
+/// Longest timestamp gap a constant frame rate recording fills by repeating
+/// the previous frame (seconds); longer gaps are cut to this
+pub const CFR_MAX_GAP_SECS: f64 = 1.0;
 
 /// Permissions
 /// Permission request timeout
 pub const PERMISSION_REQUEST_TIMEOUT_SECS: u64 = 60;
+/// macOS deep link to System Settings > Privacy & Security > Camera
+pub const MACOS_CAMERA_PRIVACY_URI: &str =
+    "x-apple.systempreferences:com.apple.preference.security?Privacy_Camera";
+/// Windows deep link to Settings > Privacy & security > Camera
+pub const WINDOWS_CAMERA_PRIVACY_URI: &str = "ms-settings:privacy-webcam";
 #[cfg(target_os = "macos")]
 /// macOS `AVMediaTypeVideo`
 pub const AV_MEDIA_TYPE_VIDEO: &str = "vide";
+#[cfg(target_os = "macos")]
+/// macOS `AVMediaTypeAudio`
+pub const AV_MEDIA_TYPE_AUDIO: &str = "soun";
+#[cfg(target_os = "windows")]
+/// Windows consent store key for microphone access
+pub const WINDOWS_MICROPHONE_CONSENT_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone";
 
 /// Recording - Frame Drop Log Interval
 pub const RECORDING_DROP_LOG_INTERVAL: u64 = 10;
@@ -378,3 +555,167 @@ pub const VIDEO_BITRATE_4K: u32 = 10_000_000;
 
 /// Video bitrate (Low quality/720p)
 pub const VIDEO_BITRATE_SD: u32 = 2_500_000;
+
+/// Frame Stream - Channel Capacity
+/// Frames buffered between the capture thread and a `FrameStream` consumer
+pub const FRAME_STREAM_CHANNEL_CAPACITY: usize = 4;
+
+/// White Balance - Minimum Color Temperature (Kelvin)
+/// Kelvin requests are clamped to `WHITE_BALANCE_MIN_KELVIN..=WHITE_BALANCE_MAX_KELVIN`
+pub const WHITE_BALANCE_MIN_KELVIN: u32 = 2500;
+
+/// White Balance - Maximum Color Temperature (Kelvin)
+pub const WHITE_BALANCE_MAX_KELVIN: u32 = 10_000;
+
+/// Auto-Framing - Crop Smoothing Factor
+/// Fraction of the distance to the new crop target covered per frame
+pub const AUTOFRAME_SMOOTHING: f64 = 0.3;
+
+/// Auto-Framing - Minimum Crop Size
+/// Smallest crop as a fraction of the full frame, limiting digital zoom
+pub const AUTOFRAME_MIN_CROP_FRACTION: f64 = 0.25;
+
+/// Low-Light Enhancement - Default Strength (0.0-1.0)
+pub const ENHANCE_DEFAULT_STRENGTH: f32 = 0.8;
+
+/// Low-Light Enhancement - Target Mean Brightness (0.0-1.0)
+/// The adaptive gamma curve maps the frame's mean brightness onto this
+pub const ENHANCE_TARGET_BRIGHTNESS: f32 = 0.45;
+
+/// Low-Light Enhancement - Strongest Gamma
+/// Lower bound on the adaptive gamma, limiting how far shadows are lifted
+pub const ENHANCE_MIN_GAMMA: f32 = 0.4;
+
+/// Low-Light Enhancement - Maximum Per-Pixel Gain
+/// Caps brightening so sensor noise in near-black areas is not amplified
+pub const ENHANCE_MAX_GAIN: f32 = 4.0;
+
+/// Low-Light Enhancement - Local Contrast Tiles per Axis
+pub const ENHANCE_TILE_GRID: usize = 8;
+
+/// Low-Light Enhancement - Histogram Clip Limit
+/// Maximum bin height as a multiple of a uniform histogram's bin height
+pub const ENHANCE_CLIP_LIMIT: f32 = 3.0;
+
+/// Temporal Denoise - Motion Rejection Threshold
+/// Mean per-channel difference from the reference frame above which a sample
+/// is treated as motion and excluded from the average
+pub const DENOISE_MOTION_THRESHOLD: u32 = 30;
+
+/// Timestamp Overlay - `strftime` Format of the Burned-In Timestamp
+pub const TIMESTAMP_OVERLAY_FORMAT: &str = "%Y-%m-%d %H:%M:%S UTC";
+
+/// Timestamp Overlay - Distance From the Top-Left Corner (pixels)
+pub const TIMESTAMP_OVERLAY_MARGIN: u32 = 8;
+
+/// Timestamp Overlay - Frame Height per Unit of Text Scale (pixels)
+/// A 1080-line frame gets 3x text, a 480-line frame 1x
+pub const TIMESTAMP_OVERLAY_LINES_PER_SCALE: u32 = 360;
+
+/// Face Detection - Model Path Environment Variable
+/// Path to the `SeetaFace` frontal model used when no path was set in code
+#[cfg(feature = "face-detect")]
+pub const FACE_MODEL_ENV_VAR: &str = "CRABCAMERA_FACE_MODEL";
+
+/// Face Detection - Minimum Face Size (pixels)
+#[cfg(feature = "face-detect")]
+pub const FACE_DETECT_MIN_FACE_SIZE: u32 = 40;
+
+/// Face Detection - Score Threshold
+/// Detector scores below this are discarded
+#[cfg(feature = "face-detect")]
+pub const FACE_DETECT_SCORE_THRESHOLD: f64 = 2.0;
+
+/// Face Detection - Image Pyramid Scale Factor
+#[cfg(feature = "face-detect")]
+pub const FACE_DETECT_PYRAMID_SCALE: f32 = 0.8;
+
+/// Face Detection - Sliding Window Step (pixels)
+#[cfg(feature = "face-detect")]
+pub const FACE_DETECT_WINDOW_STEP: u32 = 4;
+
+/// MJPEG Server - JPEG Quality (1-100)
+#[cfg(feature = "http-stream")]
+pub const MJPEG_SERVER_JPEG_QUALITY: u8 = 80;
+
+/// MJPEG Server - Multipart Boundary
+#[cfg(feature = "http-stream")]
+pub const MJPEG_SERVER_BOUNDARY: &str = "crabcamera-frame";
+
+/// MJPEG Server - Fan-out Channel Capacity
+/// Encoded frames buffered per viewer before it starts skipping
+#[cfg(feature = "http-stream")]
+pub const MJPEG_SERVER_CHANNEL_CAPACITY: usize = 4;
+
+/// MJPEG Server - Maximum Request Head Size (bytes)
+#[cfg(feature = "http-stream")]
+pub const MJPEG_SERVER_MAX_REQUEST_BYTES: usize = 8192;
+
+/// RTSP Server - Fan-out Channel Capacity
+/// Encoded access units buffered per client before it starts skipping
+#[cfg(feature = "rtsp")]
+pub const RTSP_CHANNEL_CAPACITY: usize = 8;
+
+/// RTSP Server - Maximum Request Line or Header Line Length (bytes)
+#[cfg(feature = "rtsp")]
+pub const RTSP_MAX_LINE_BYTES: usize = 8192;
+
+/// RTSP Server - Maximum Request Body Size (bytes)
+/// Larger bodies are answered with `413` and the connection is closed
+#[cfg(feature = "rtsp")]
+pub const RTSP_MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// RTSP Server - Session Timeout Advertised to Clients (seconds)
+#[cfg(feature = "rtsp")]
+pub const RTSP_SESSION_TIMEOUT_SECS: u32 = 60;
+
+/// RTP - Dynamic Payload Type Used for H.264
+#[cfg(feature = "rtsp")]
+pub const RTP_PAYLOAD_TYPE_H264: u8 = 96;
+
+/// RTP - Maximum Payload per Packet (bytes)
+/// Keeps UDP datagrams under a typical 1500-byte MTU
+#[cfg(feature = "rtsp")]
+pub const RTP_MAX_PAYLOAD: usize = 1400;
+
+/// GPU Conversion - Minimum Frame Size Routed to the GPU (pixels)
+/// Default crossover below which upload/readback costs more than the CPU
+/// path saves; tune per machine with `cargo bench --features gpu --bench convert_benchmarks`
+#[cfg(feature = "gpu")]
+pub const GPU_CONVERT_MIN_PIXELS: u64 = 1920 * 1080;
+
+/// ffmpeg - Executable Path Environment Variable
+/// Path to the ffmpeg used when no path was set in code; never looked up on `PATH`
+#[cfg(any(feature = "hwenc", feature = "screen-capture"))]
+pub const FFMPEG_PATH_ENV_VAR: &str = "CRABCAMERA_FFMPEG";
+/// Hardware Encoding - DRM render node used for VA-API
+#[cfg(feature = "hwenc")]
+pub const HWENC_VAAPI_DEVICE: &str = "/dev/dri/renderD128";
+
+/// Screen Capture - Device id prefix of capturable displays
+#[cfg(feature = "screen-capture")]
+pub const SCREEN_ID_PREFIX: &str = "screen:";
+/// Screen Capture - Tool listing X11 monitors on Linux
+#[cfg(feature = "screen-capture")]
+pub const SCREEN_XRANDR: &str = "xrandr";
+/// Screen Capture - Frame rate listed for displays
+#[cfg(feature = "screen-capture")]
+pub const SCREEN_DEFAULT_FPS: f32 = 30.0;
+/// Screen Capture - Longest wait for the next frame (ms)
+#[cfg(feature = "screen-capture")]
+pub const SCREEN_FRAME_TIMEOUT_MS: u64 = 2000;
+
+/// Pipeline Benchmark - Timed frames per run
+#[cfg(feature = "bench")]
+pub const BENCH_DEFAULT_FRAMES: u32 = 120;
+/// Pipeline Benchmark - Untimed frames encoded before measuring
+#[cfg(feature = "bench")]
+pub const BENCH_WARMUP_FRAMES: u32 = 10;
+/// Pipeline Benchmark - Distinct synthetic source frames cycled through
+#[cfg(feature = "bench")]
+pub const BENCH_SOURCE_FRAMES: usize = 8;
+
+/// System Test - Longest wait for one camera to open and capture (ms)
+pub const SYSTEM_TEST_DEVICE_TIMEOUT_MS: u64 = 5000;
+/// System Test - Cameras tested at the same time
+pub const SYSTEM_TEST_CONCURRENCY: usize = 4;
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// The top-level error type for camera operations.
@@ -7,6 +8,8 @@ pub enum CameraError {
     InitializationError(String),
     /// Permission denied by OS or user.
     PermissionDenied(String),
+    /// The device is held by another process.
+    DeviceBusy(String),
     /// Failed to capture a frame.
     CaptureError(String),
     /// Failed to set a camera control.
@@ -15,6 +18,8 @@ pub enum CameraError {
     StreamError(String),
     /// Operation not supported by the current hardware or platform.
     UnsupportedOperation(String),
+    /// Pixel format (fourcc) with no known conversion.
+    UnsupportedFormat(String),
     #[cfg(feature = "recording")]
     /// Video encoding initialization or processing error.
     EncodingError(String),
@@ -24,9 +29,15 @@ pub enum CameraError {
     #[cfg(feature = "recording")]
     /// File system I/O error during recording.
     IoError(String),
+    #[cfg(feature = "recording")]
+    /// The named video encoder cannot be loaded on this machine.
+    EncoderUnavailable(String),
     #[cfg(feature = "audio")]
     /// Audio device or capture error.
     AudioError(String),
+    #[cfg(feature = "audio")]
+    /// The audio input device disappeared during capture (e.g. unplugged).
+    AudioDeviceLost(String),
     /// System resource or access error.
     AccessError(String),
     /// Connection implementation error.
@@ -35,6 +46,8 @@ pub enum CameraError {
     SystemError(String),
     /// Invalid configuration.
     ConfigError(String),
+    /// The Cargo feature providing the operation is not compiled in.
+    FeatureDisabled(String),
 }
 
 impl fmt::Display for CameraError {
@@ -44,22 +57,70 @@ impl fmt::Display for CameraError {
                 write!(f, "Camera initialization error: {msg}")
             }
             CameraError::PermissionDenied(msg) => write!(f, "Permission denied error: {msg}"),
+            CameraError::DeviceBusy(msg) => write!(f, "Device busy: {msg}"),
             CameraError::CaptureError(msg) => write!(f, "Capture error: {msg}"),
             CameraError::ControlError(msg) => write!(f, "Camera control error: {msg}"),
             CameraError::StreamError(msg) => write!(f, "Stream error: {msg}"),
             CameraError::UnsupportedOperation(msg) => write!(f, "Unsupported operation: {msg}"),
+            CameraError::UnsupportedFormat(fourcc) => write!(f, "Unsupported format: {fourcc}"),
             #[cfg(feature = "recording")]
             CameraError::EncodingError(msg) => write!(f, "Encoding error: {msg}"),
             #[cfg(feature = "recording")]
             CameraError::MuxingError(msg) => write!(f, "Muxing error: {msg}"),
             #[cfg(feature = "recording")]
             CameraError::IoError(msg) => write!(f, "IO error: {msg}"),
+            #[cfg(feature = "recording")]
+            CameraError::EncoderUnavailable(encoder) => {
+                write!(f, "Encoder unavailable: {encoder}")
+            }
             #[cfg(feature = "audio")]
             CameraError::AudioError(msg) => write!(f, "Audio error: {msg}"),
+            #[cfg(feature = "audio")]
+            CameraError::AudioDeviceLost(msg) => write!(f, "Audio device lost: {msg}"),
             CameraError::AccessError(msg) => write!(f, "Access error: {msg}"),
             CameraError::ConnectionError(msg) => write!(f, "Connection error: {msg}"),
             CameraError::SystemError(msg) => write!(f, "System error: {msg}"),
             CameraError::ConfigError(msg) => write!(f, "Configuration error: {msg}"),
+            CameraError::FeatureDisabled(feature) => write!(
+                f,
+                "Feature disabled: this build of crabcamera was compiled without the `{feature}` feature"
+            ),
+        }
+    }
+}
+
+impl CameraError {
+    /// Stable, machine-readable code for this error (e.g. `"CAMERA_BUSY"`)
+    ///
+    /// Codes never change between releases, unlike the `Display` text, so
+    /// frontends can branch on them.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            CameraError::InitializationError(_) => "INITIALIZATION_FAILED",
+            CameraError::PermissionDenied(_) => "PERMISSION_DENIED",
+            CameraError::DeviceBusy(_) => "CAMERA_BUSY",
+            CameraError::CaptureError(_) => "CAPTURE_FAILED",
+            CameraError::ControlError(_) => "CONTROL_FAILED",
+            CameraError::StreamError(_) => "STREAM_FAILED",
+            CameraError::UnsupportedOperation(_) => "UNSUPPORTED_OPERATION",
+            CameraError::UnsupportedFormat(_) => "UNSUPPORTED_FORMAT",
+            #[cfg(feature = "recording")]
+            CameraError::EncodingError(_) => "ENCODING_FAILED",
+            #[cfg(feature = "recording")]
+            CameraError::MuxingError(_) => "MUXING_FAILED",
+            #[cfg(feature = "recording")]
+            CameraError::IoError(_) => "IO_ERROR",
+            #[cfg(feature = "recording")]
+            CameraError::EncoderUnavailable(_) => "ENCODER_UNAVAILABLE",
+            #[cfg(feature = "audio")]
+            CameraError::AudioError(_) => "AUDIO_FAILED",
+            #[cfg(feature = "audio")]
+            CameraError::AudioDeviceLost(_) => "AUDIO_DEVICE_LOST",
+            CameraError::AccessError(_) => "ACCESS_DENIED",
+            CameraError::ConnectionError(_) => "CONNECTION_FAILED",
+            CameraError::SystemError(_) => "SYSTEM_ERROR",
+            CameraError::ConfigError(_) => "INVALID_CONFIG",
+            CameraError::FeatureDisabled(_) => "FEATURE_DISABLED",
         }
     }
 }
@@ -72,9 +133,137 @@ impl From<CameraError> for String {
 
 impl std::error::Error for CameraError {}
 
+/// `Display` prefixes of [`CameraError`] messages and their codes, used to
+/// recover the code from errors that were already turned into strings
+const MESSAGE_PREFIX_CODES: &[(&str, &str)] = &[
+    ("Camera initialization error:", "INITIALIZATION_FAILED"),
+    ("Permission denied error:", "PERMISSION_DENIED"),
+    ("Device busy:", "CAMERA_BUSY"),
+    ("Capture error:", "CAPTURE_FAILED"),
+    ("Camera control error:", "CONTROL_FAILED"),
+    ("Stream error:", "STREAM_FAILED"),
+    ("Unsupported operation:", "UNSUPPORTED_OPERATION"),
+    ("Unsupported format:", "UNSUPPORTED_FORMAT"),
+    ("Encoding error:", "ENCODING_FAILED"),
+    ("Muxing error:", "MUXING_FAILED"),
+    ("IO error:", "IO_ERROR"),
+    ("Encoder unavailable:", "ENCODER_UNAVAILABLE"),
+    ("Audio error:", "AUDIO_FAILED"),
+    ("Audio device lost:", "AUDIO_DEVICE_LOST"),
+    ("Access error:", "ACCESS_DENIED"),
+    ("Connection error:", "CONNECTION_FAILED"),
+    ("System error:", "SYSTEM_ERROR"),
+    ("Configuration error:", "INVALID_CONFIG"),
+    ("Feature disabled:", "FEATURE_DISABLED"),
+];
+
+/// Error returned by every Tauri command
+///
+/// Serializes as `{ "code": "...", "message": "..." }`. `code` is stable and
+/// meant for branching or localization in the frontend; `message` (also the
+/// `Display` output) is the same human-readable text commands returned
+/// before codes existed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandError {
+    /// Machine-readable error code, e.g. `"CAMERA_BUSY"` or `"NOT_FOUND"`
+    pub code: String,
+    /// Human-readable description of the error
+    pub message: String,
+}
+
+impl CommandError {
+    /// Code for errors that match no more specific category
+    pub const UNKNOWN: &'static str = "UNKNOWN";
+    /// Code for a missing camera, session or other resource
+    pub const NOT_FOUND: &'static str = "NOT_FOUND";
+    /// Code for rejected command arguments
+    pub const INVALID_ARGUMENT: &'static str = "INVALID_ARGUMENT";
+    /// Code for an operation stopped by [`cancel_operation`](crate::commands::capture::cancel_operation)
+    pub const CANCELLED: &'static str = "CANCELLED";
+    /// Code for an internal failure, such as a worker task that panicked
+    pub const SYSTEM_ERROR: &'static str = "SYSTEM_ERROR";
+
+    /// Create an error with an explicit code
+    #[must_use]
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Create a [`NOT_FOUND`](Self::NOT_FOUND) error
+    #[must_use]
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(Self::NOT_FOUND, message)
+    }
+
+    /// Create an [`INVALID_ARGUMENT`](Self::INVALID_ARGUMENT) error
+    #[must_use]
+    pub fn invalid_argument(message: impl Into<String>) -> Self {
+        Self::new(Self::INVALID_ARGUMENT, message)
+    }
+
+    /// Create a [`CANCELLED`](Self::CANCELLED) error
+    #[must_use]
+    pub fn cancelled(message: impl Into<String>) -> Self {
+        Self::new(Self::CANCELLED, message)
+    }
+
+    /// Create a [`SYSTEM_ERROR`](Self::SYSTEM_ERROR) error
+    #[must_use]
+    pub fn system(message: impl Into<String>) -> Self {
+        Self::new(Self::SYSTEM_ERROR, message)
+    }
+
+    /// Code of a stringified [`CameraError`], [`UNKNOWN`](Self::UNKNOWN) for
+    /// any other message
+    ///
+    /// Commands give their errors an explicit code; this only recovers the
+    /// code of a [`CameraError`] that was already turned into a string.
+    pub fn code_for_message(message: &str) -> &'static str {
+        MESSAGE_PREFIX_CODES
+            .iter()
+            .find(|(prefix, _)| message.starts_with(prefix))
+            .map_or(Self::UNKNOWN, |&(_, code)| code)
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<CameraError> for CommandError {
+    fn from(err: CameraError) -> Self {
+        Self::new(err.error_code(), err.to_string())
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        Self::new(Self::code_for_message(&message), message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+impl From<CommandError> for String {
+    fn from(err: CommandError) -> Self {
+        err.message
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::CameraError;
+    use super::{CameraError, CommandError};
 
     #[test]
     fn test_display_messages_for_all_core_variants() {
@@ -87,6 +276,10 @@ mod tests {
                 CameraError::PermissionDenied("perm".to_string()),
                 "Permission denied error: perm",
             ),
+            (
+                CameraError::DeviceBusy("busy".to_string()),
+                "Device busy: busy",
+            ),
             (
                 CameraError::CaptureError("capture".to_string()),
                 "Capture error: capture",
@@ -103,6 +296,10 @@ mod tests {
                 CameraError::UnsupportedOperation("unsupported".to_string()),
                 "Unsupported operation: unsupported",
             ),
+            (
+                CameraError::UnsupportedFormat("H264".to_string()),
+                "Unsupported format: H264",
+            ),
             (
                 CameraError::AccessError("access".to_string()),
                 "Access error: access",
@@ -119,6 +316,10 @@ mod tests {
                 CameraError::ConfigError("config".to_string()),
                 "Configuration error: config",
             ),
+            (
+                CameraError::FeatureDisabled("rtsp".to_string()),
+                "Feature disabled: this build of crabcamera was compiled without the `rtsp` feature",
+            ),
         ];
 
         for (error, expected) in cases {
@@ -139,6 +340,10 @@ mod tests {
                 "Muxing error: mux",
             ),
             (CameraError::IoError("io".to_string()), "IO error: io"),
+            (
+                CameraError::EncoderUnavailable("openh264".to_string()),
+                "Encoder unavailable: openh264",
+            ),
         ];
 
         for (error, expected) in cases {
@@ -151,6 +356,14 @@ mod tests {
     fn test_display_message_for_audio_variant() {
         let error = CameraError::AudioError("audio".to_string());
         assert_eq!(error.to_string(), "Audio error: audio");
+
+        let lost = CameraError::AudioDeviceLost("USB Mic".to_string());
+        assert_eq!(lost.to_string(), "Audio device lost: USB Mic");
+        assert_eq!(lost.error_code(), "AUDIO_DEVICE_LOST");
+        assert_eq!(
+            CommandError::from(lost.to_string()).code,
+            "AUDIO_DEVICE_LOST"
+        );
     }
 
     #[test]
@@ -162,4 +375,55 @@ mod tests {
         let err_obj: &dyn std::error::Error = &CameraError::SystemError("x".to_string());
         assert!(err_obj.source().is_none());
     }
+
+    #[test]
+    fn test_error_codes_survive_stringification() {
+        let errors = [
+            CameraError::InitializationError("x".to_string()),
+            CameraError::PermissionDenied("x".to_string()),
+            CameraError::DeviceBusy("x".to_string()),
+            CameraError::CaptureError("x".to_string()),
+            CameraError::ControlError("x".to_string()),
+            CameraError::StreamError("x".to_string()),
+            CameraError::UnsupportedOperation("x".to_string()),
+            CameraError::UnsupportedFormat("x".to_string()),
+            CameraError::AccessError("x".to_string()),
+            CameraError::ConnectionError("x".to_string()),
+            CameraError::SystemError("x".to_string()),
+            CameraError::ConfigError("x".to_string()),
+            CameraError::FeatureDisabled("x".to_string()),
+        ];
+
+        for error in errors {
+            let code = error.error_code();
+            let message = error.to_string();
+            assert_eq!(CommandError::from(message.clone()).code, code, "{message}");
+
+            let structured = CommandError::from(error);
+            assert_eq!(structured.code, code);
+            assert_eq!(
+                structured.to_string(),
+                message,
+                "Display keeps the old text"
+            );
+        }
+    }
+
+    #[test]
+    fn test_command_error_serializes_code_and_message() {
+        let error = CommandError::from(CameraError::DeviceBusy("held by zoom".to_string()));
+        let json = serde_json::to_value(&error).expect("serialize");
+        assert_eq!(json["code"], "CAMERA_BUSY");
+        assert_eq!(json["message"], "Device busy: held by zoom");
+
+        // Message wording is not interpreted
+        let legacy = CommandError::from("Camera with ID '7' not found");
+        assert_eq!(legacy.code, CommandError::UNKNOWN);
+        assert_eq!(CommandError::from("mystery").code, CommandError::UNKNOWN);
+        assert_eq!(
+            CommandError::system("Task join error: panicked").code,
+            CameraError::SystemError(String::new()).error_code()
+        );
+        assert_eq!(String::from(legacy), "Camera with ID '7' not found");
+    }
 }
@@ -0,0 +1,57 @@
+//! Location of the ffmpeg executable used by hardware encoding and screen
+//! capture
+//!
+//! The `hwenc` feature drives the platform encoder, and `screen-capture` the
+//! platform's screen grabber, through an `ffmpeg` child process. The
+//! executable is never looked up on `PATH`, where another program could
+//! stand in for it: point at it with [`set_ffmpeg_path`] or the
+//! `CRABCAMERA_FFMPEG` environment variable. Until one of them is set,
+//! hardware encoding reports itself unavailable and no screens are listed.
+
+use crate::constants::FFMPEG_PATH_ENV_VAR;
+use crate::errors::CameraError;
+use std::path::PathBuf;
+use std::sync::{LazyLock, RwLock};
+
+static FFMPEG_PATH: LazyLock<RwLock<Option<PathBuf>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Set the ffmpeg executable to run
+///
+/// Takes precedence over the `CRABCAMERA_FFMPEG` environment variable.
+pub fn set_ffmpeg_path(path: impl Into<PathBuf>) {
+    if let Ok(mut ffmpeg) = FFMPEG_PATH.write() {
+        *ffmpeg = Some(path.into());
+    }
+}
+
+/// The configured ffmpeg executable
+///
+/// # Errors
+/// Returns a [`CameraError::UnsupportedOperation`] if neither
+/// [`set_ffmpeg_path`] nor `CRABCAMERA_FFMPEG` names one.
+pub fn ffmpeg_path() -> Result<PathBuf, CameraError> {
+    FFMPEG_PATH
+        .read()
+        .ok()
+        .and_then(|p| p.clone())
+        .or_else(|| std::env::var_os(FFMPEG_PATH_ENV_VAR).map(PathBuf::from))
+        .ok_or_else(|| {
+            CameraError::UnsupportedOperation(format!(
+                "No ffmpeg configured; call set_ffmpeg_path or set {FFMPEG_PATH_ENV_VAR}"
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_ffmpeg_path_is_used() {
+        set_ffmpeg_path("/nonexistent/ffmpeg");
+        assert_eq!(
+            ffmpeg_path().expect("configured path"),
+            PathBuf::from("/nonexistent/ffmpeg")
+        );
+    }
+}
@@ -1,13 +1,28 @@
 use super::FocusStackError;
 use crate::constants::{
-    ALIGNMENT_SAMPLING_STEP, ALIGNMENT_SIGNIFICANT_ROTATION, ALIGNMENT_SIGNIFICANT_SCALE, LUMA_B,
-    LUMA_G, LUMA_R,
+    ALIGNMENT_MAX_ROTATION, ALIGNMENT_MAX_SCALE_DELTA, ALIGNMENT_PHASE_SIZE,
+    ALIGNMENT_ROTATION_STEP, ALIGNMENT_SAMPLING_STEP, ALIGNMENT_SCALE_STEP,
+    ALIGNMENT_SIGNIFICANT_ROTATION, ALIGNMENT_SIGNIFICANT_SCALE, LUMA_B, LUMA_G, LUMA_R,
 };
 /// Image alignment module for focus stacking
 ///
 /// Aligns images to compensate for camera movement between captures.
 /// Uses feature detection and homography estimation.
 use crate::types::CameraFrame;
+use serde::{Deserialize, Serialize};
+
+/// How [`align_frames_with`] estimates the transform between frames
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlignmentMethod {
+    /// Brightness center-of-mass shift (fast; the original behavior)
+    #[default]
+    TranslationOnly,
+    /// Phase-correlation translation plus a coarse rotation search
+    TranslationRotation,
+    /// Translation, rotation and uniform scale (a similarity transform;
+    /// shear is not estimated)
+    Affine,
+}
 
 /// Alignment result containing transform and error metrics
 #[derive(Debug, Clone)]
@@ -23,6 +38,10 @@ pub struct AlignmentResult {
 
     /// Alignment error (RMS pixel distance)
     pub error: f32,
+
+    /// Mean absolute luminance difference (0-255) from the reference after
+    /// applying the transform, measured on a downsampled image
+    pub residual: f32,
 }
 
 impl Default for AlignmentResult {
@@ -32,10 +51,20 @@ impl Default for AlignmentResult {
             rotation: 0.0,
             scale: 1.0,
             error: 0.0,
+            residual: 0.0,
         }
     }
 }
 
+/// Align a sequence of frames to the first frame using
+/// [`AlignmentMethod::TranslationOnly`]
+///
+/// # Errors
+/// See [`align_frames_with`].
+pub fn align_frames(frames: &[CameraFrame]) -> Result<Vec<AlignmentResult>, FocusStackError> {
+    align_frames_with(frames, AlignmentMethod::TranslationOnly)
+}
+
 /// Align a sequence of frames to the first frame
 ///
 /// Returns alignment transforms for each frame relative to reference.
@@ -46,7 +75,10 @@ impl Default for AlignmentResult {
 /// Returns a [`FocusStackError::InsufficientImages`] if fewer than two frames
 /// are provided, or a [`FocusStackError::DimensionMismatch`] if any frame does
 /// not match the reference frame's dimensions.
-pub fn align_frames(frames: &[CameraFrame]) -> Result<Vec<AlignmentResult>, FocusStackError> {
+pub fn align_frames_with(
+    frames: &[CameraFrame],
+    method: AlignmentMethod,
+) -> Result<Vec<AlignmentResult>, FocusStackError> {
     if frames.len() < 2 {
         return Err(FocusStackError::InsufficientImages {
             required: 2,
@@ -54,9 +86,11 @@ pub fn align_frames(frames: &[CameraFrame]) -> Result<Vec<AlignmentResult>, Focu
         });
     }
 
-    log::info!("Aligning {} frames", frames.len());
+    log::info!("Aligning {} frames ({method:?})", frames.len());
 
     let reference = &frames[0];
+    let factor = work_factor(reference);
+    let reference_gray = WorkImage::sample(reference, factor);
     let mut results = Vec::with_capacity(frames.len());
 
     // First frame is reference (no transform)
@@ -74,16 +108,25 @@ pub fn align_frames(frames: &[CameraFrame]) -> Result<Vec<AlignmentResult>, Focu
             });
         }
 
-        // Compute alignment using center-of-mass
-        // This is a simplified approach - production would use feature matching
-        let alignment = compute_alignment_simple(reference, frame);
+        let frame_gray = WorkImage::sample(frame, factor);
+        let mut alignment = match method {
+            // Center-of-mass: a simplified approach - production would use feature matching
+            AlignmentMethod::TranslationOnly => compute_alignment_simple(reference, frame),
+            AlignmentMethod::TranslationRotation => {
+                search_alignment(&reference_gray, &frame_gray, factor, false)
+            }
+            AlignmentMethod::Affine => search_alignment(&reference_gray, &frame_gray, factor, true),
+        };
+        alignment.residual = reference_gray.residual(&frame_gray, &alignment, factor);
 
         log::debug!(
-            "Frame {} alignment: translation=({:.2}, {:.2}), error={:.3}",
+            "Frame {} alignment: translation=({:.2}, {:.2}), rotation={:.4}, scale={:.3}, residual={:.2}",
             idx,
             alignment.translation.0,
             alignment.translation.1,
-            alignment.error
+            alignment.rotation,
+            alignment.scale,
+            alignment.residual
         );
 
         results.push(alignment);
@@ -165,6 +208,7 @@ fn compute_alignment_simple(reference: &CameraFrame, frame: &CameraFrame) -> Ali
         rotation: 0.0,
         scale: 1.0,
         error,
+        residual: 0.0,
     }
 }
 
@@ -333,6 +377,342 @@ fn apply_scale(frame: &mut CameraFrame, scale: f32) {
     frame.data = new_data;
 }
 
+/// Downsampling factor that fits the frame's longer side in
+/// [`ALIGNMENT_PHASE_SIZE`]
+fn work_factor(frame: &CameraFrame) -> usize {
+    let longest = frame.width.max(frame.height) as usize;
+    longest.div_ceil(ALIGNMENT_PHASE_SIZE).max(1)
+}
+
+#[allow(clippy::cast_precision_loss)] // working-image coordinates are tiny
+fn as_f32(value: usize) -> f32 {
+    value as f32
+}
+
+/// Downsampled luminance image used by the search-based alignment methods
+struct WorkImage {
+    data: Vec<f32>,
+    width: usize,
+    height: usize,
+    /// Rotation center of the full frame, in working-image pixels
+    center: (f32, f32),
+}
+
+impl WorkImage {
+    /// Box-average `factor`×`factor` blocks of the frame's luminance
+    fn sample(frame: &CameraFrame, factor: usize) -> Self {
+        let full_width = frame.width as usize;
+        let full_height = frame.height as usize;
+        let width = (full_width / factor).max(1);
+        let height = (full_height / factor).max(1);
+        let mut data = vec![0.0; width * height];
+
+        for (cell, value) in data.iter_mut().enumerate() {
+            let (bx, by) = (cell % width * factor, cell / width * factor);
+            let mut sum = 0.0;
+            let mut count = 0usize;
+            for y in by..(by + factor).min(full_height) {
+                for x in bx..(bx + factor).min(full_width) {
+                    let idx = (y * full_width + x) * 3;
+                    if let Some(px) = frame.data.get(idx..idx + 3) {
+                        sum += LUMA_R * f32::from(px[0])
+                            + LUMA_G * f32::from(px[1])
+                            + LUMA_B * f32::from(px[2]);
+                        count += 1;
+                    }
+                }
+            }
+            if count > 0 {
+                *value = sum / as_f32(count);
+            }
+        }
+
+        let factor = as_f32(factor);
+        Self {
+            data,
+            width,
+            height,
+            center: (
+                as_f32(full_width) / 2.0 / factor,
+                as_f32(full_height) / 2.0 / factor,
+            ),
+        }
+    }
+
+    /// Nearest-neighbor sample, `None` outside the image
+    fn at(&self, x: f32, y: f32) -> Option<f32> {
+        let (x, y) = (x.round(), y.round());
+        if x < 0.0 || y < 0.0 || x >= as_f32(self.width) || y >= as_f32(self.height) {
+            return None;
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // bounds checked above
+        let idx = y as usize * self.width + x as usize;
+        self.data.get(idx).copied()
+    }
+
+    /// Where pixel `(x, y)` of the aligned image is read from in this image
+    ///
+    /// Mirrors [`apply_alignment`]: translate, then rotate about the center,
+    /// then scale about the origin, i.e. `src = R(p / s - c) + c - t`.
+    fn source(
+        &self,
+        x: f32,
+        y: f32,
+        translation: (f32, f32),
+        rotation: f32,
+        scale: f32,
+    ) -> (f32, f32) {
+        let (sin, cos) = rotation.sin_cos();
+        let (dx, dy) = (x / scale - self.center.0, y / scale - self.center.1);
+        (
+            dx * cos - dy * sin + self.center.0 - translation.0,
+            dx * sin + dy * cos + self.center.1 - translation.1,
+        )
+    }
+
+    /// This image rotated and scaled (no translation), with uncovered pixels
+    /// set to the mean so they do not add edges to the correlation
+    fn warped(&self, rotation: f32, scale: f32) -> Self {
+        let mean = self.data.iter().sum::<f32>() / as_f32(self.data.len());
+        let mut data = vec![mean; self.data.len()];
+        for (cell, value) in data.iter_mut().enumerate() {
+            let (x, y) = (as_f32(cell % self.width), as_f32(cell / self.width));
+            let (sx, sy) = self.source(x, y, (0.0, 0.0), rotation, scale);
+            if let Some(v) = self.at(sx, sy) {
+                *value = v;
+            }
+        }
+        Self {
+            data,
+            width: self.width,
+            height: self.height,
+            center: self.center,
+        }
+    }
+
+    /// Mean absolute difference between this (reference) image and `frame`
+    /// after applying `alignment`, over the pixels both cover
+    fn residual(&self, frame: &Self, alignment: &AlignmentResult, factor: usize) -> f32 {
+        let factor = as_f32(factor);
+        let translation = (
+            alignment.translation.0 / factor,
+            alignment.translation.1 / factor,
+        );
+        let mut sum = 0.0;
+        let mut count = 0usize;
+        for (cell, &reference) in self.data.iter().enumerate() {
+            let (x, y) = (as_f32(cell % self.width), as_f32(cell / self.width));
+            let (sx, sy) = frame.source(x, y, translation, alignment.rotation, alignment.scale);
+            if let Some(v) = frame.at(sx, sy) {
+                sum += (reference - v).abs();
+                count += 1;
+            }
+        }
+        if count == 0 {
+            255.0
+        } else {
+            sum / as_f32(count)
+        }
+    }
+
+    /// Mean-removed, Hann-windowed spectrum zero-padded to
+    /// [`ALIGNMENT_PHASE_SIZE`] squared
+    fn spectrum(&self) -> (Vec<f32>, Vec<f32>) {
+        let n = ALIGNMENT_PHASE_SIZE;
+        let mean = self.data.iter().sum::<f32>() / as_f32(self.data.len());
+        let hann = |i: usize, len: usize| {
+            if len < 2 {
+                1.0
+            } else {
+                0.5 - 0.5 * (std::f32::consts::TAU * as_f32(i) / as_f32(len - 1)).cos()
+            }
+        };
+
+        let mut re = vec![0.0; n * n];
+        for y in 0..self.height.min(n) {
+            let wy = hann(y, self.height);
+            for x in 0..self.width.min(n) {
+                re[y * n + x] = (self.data[y * self.width + x] - mean) * wy * hann(x, self.width);
+            }
+        }
+        let mut im = vec![0.0; n * n];
+        fft_2d(&mut re, &mut im, n, false);
+        (re, im)
+    }
+}
+
+/// Phase correlation: the shift `t` (in working pixels) for which
+/// `frame(p - t)` best matches `reference(p)`
+fn phase_correlate(reference: &WorkImage, frame: &WorkImage) -> (f32, f32) {
+    let n = ALIGNMENT_PHASE_SIZE;
+    let (ref_re, ref_im) = reference.spectrum();
+    let (frame_re, frame_im) = frame.spectrum();
+
+    // Normalized cross-power spectrum R · conj(F) / |R · conj(F)|
+    let mut re = vec![0.0; n * n];
+    let mut im = vec![0.0; n * n];
+    for i in 0..n * n {
+        let cr = ref_re[i] * frame_re[i] + ref_im[i] * frame_im[i];
+        let ci = ref_im[i] * frame_re[i] - ref_re[i] * frame_im[i];
+        let magnitude = cr.hypot(ci);
+        if magnitude > f32::EPSILON {
+            re[i] = cr / magnitude;
+            im[i] = ci / magnitude;
+        }
+    }
+    fft_2d(&mut re, &mut im, n, true);
+
+    let peak = re
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map_or(0, |(i, _)| i);
+    let (px, py) = (peak % n, peak / n);
+
+    // Parabolic sub-pixel refinement, then wrap to a signed shift
+    let refine = |left: f32, center: f32, right: f32| {
+        let curvature = left - 2.0 * center + right;
+        if curvature.abs() > f32::EPSILON {
+            (0.5 * (left - right) / curvature).clamp(-0.5, 0.5)
+        } else {
+            0.0
+        }
+    };
+    let dx = refine(
+        re[py * n + (px + n - 1) % n],
+        re[peak],
+        re[py * n + (px + 1) % n],
+    );
+    let dy = refine(
+        re[(py + n - 1) % n * n + px],
+        re[peak],
+        re[(py + 1) % n * n + px],
+    );
+    let wrap = |p: usize| {
+        if p > n / 2 {
+            as_f32(p) - as_f32(n)
+        } else {
+            as_f32(p)
+        }
+    };
+    (wrap(px) + dx, wrap(py) + dy)
+}
+
+/// Estimate translation and rotation (and, with `with_scale`, uniform scale)
+/// by scoring candidate rotations/scales with phase correlation
+fn search_alignment(
+    reference: &WorkImage,
+    frame: &WorkImage,
+    factor: usize,
+    with_scale: bool,
+) -> AlignmentResult {
+    let evaluate = |rotation: f32, scale: f32| {
+        // Correlating the rotated/scaled frame gives t' = s·R⁻¹·t
+        let (tx, ty) = phase_correlate(reference, &frame.warped(rotation, scale));
+        let (sin, cos) = rotation.sin_cos();
+        let to_full = as_f32(factor) / scale;
+        let translation = (
+            (tx * cos - ty * sin) * to_full,
+            (tx * sin + ty * cos) * to_full,
+        );
+        let mut result = AlignmentResult {
+            translation,
+            rotation,
+            scale,
+            error: translation.0.hypot(translation.1),
+            residual: 0.0,
+        };
+        result.residual = reference.residual(frame, &result, factor);
+        result
+    };
+    let best_of = |candidates: &mut dyn Iterator<Item = (f32, f32)>| {
+        candidates
+            .map(|(rotation, scale)| evaluate(rotation, scale))
+            .min_by(|a, b| a.residual.total_cmp(&b.residual))
+            .unwrap_or_default()
+    };
+
+    #[allow(clippy::cast_possible_truncation)] // a handful of search steps
+    let steps = (ALIGNMENT_MAX_ROTATION / ALIGNMENT_ROTATION_STEP).round() as i32;
+    #[allow(clippy::cast_precision_loss)] // small step counts
+    let angle = |base: f32, step: f32, i: i32| base + i as f32 * step;
+
+    let mut best =
+        best_of(&mut (-steps..=steps).map(|i| (angle(0.0, ALIGNMENT_ROTATION_STEP, i), 1.0)));
+    if with_scale {
+        #[allow(clippy::cast_possible_truncation)] // a handful of search steps
+        let scale_steps = (ALIGNMENT_MAX_SCALE_DELTA / ALIGNMENT_SCALE_STEP).round() as i32;
+        let rotation = best.rotation;
+        best = best_of(
+            &mut (-scale_steps..=scale_steps)
+                .map(|i| (rotation, angle(1.0, ALIGNMENT_SCALE_STEP, i))),
+        );
+    }
+
+    // Refine the rotation between the coarse steps
+    let (rotation, scale) = (best.rotation, best.scale);
+    let fine = ALIGNMENT_ROTATION_STEP / 4.0;
+    best_of(&mut (-3..=3).map(|i| (angle(rotation, fine, i), scale)))
+}
+
+/// In-place iterative radix-2 FFT; `re.len()` must be a power of two
+fn fft(re: &mut [f32], im: &mut [f32], inverse: bool) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let theta = sign * std::f32::consts::TAU / as_f32(len);
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (w_im, w_re) = (theta * as_f32(k)).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// 2D FFT of an `n`×`n` row-major buffer (rows, then columns)
+fn fft_2d(re: &mut [f32], im: &mut [f32], n: usize, inverse: bool) {
+    for row in 0..n {
+        let span = row * n..(row + 1) * n;
+        fft(&mut re[span.clone()], &mut im[span], inverse);
+    }
+    let mut col_re = vec![0.0; n];
+    let mut col_im = vec![0.0; n];
+    for col in 0..n {
+        for row in 0..n {
+            col_re[row] = re[row * n + col];
+            col_im[row] = im[row * n + col];
+        }
+        fft(&mut col_re, &mut col_im, inverse);
+        for row in 0..n {
+            re[row * n + col] = col_re[row];
+            im[row * n + col] = col_im[row];
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -448,6 +828,7 @@ mod tests {
             rotation: 0.01,
             scale: 1.02,
             error: 0.5,
+            residual: 0.0,
         };
 
         let aligned = apply_alignment(&frame, &transform).expect("non-identity should succeed");
@@ -472,4 +853,110 @@ mod tests {
         apply_scale(&mut frame_scale, 1.2);
         assert_eq!(frame_scale.data.len(), 10 * 10 * 3);
     }
+
+    /// 96x80 frame with a non-repeating texture for the search methods
+    fn textured_frame() -> CameraFrame {
+        let mut data = Vec::with_capacity(96 * 80 * 3);
+        for y in 0..80u16 {
+            for x in 0..96u16 {
+                let (fx, fy) = (f32::from(x), f32::from(y));
+                let wave =
+                    60.0 * (fx * 0.13).sin() * (fy * 0.07).cos() + 50.0 * ((fx - fy) * 0.05).sin();
+                let block = if (x / 17 + y / 23) % 3 == 0 {
+                    40.0
+                } else {
+                    0.0
+                };
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                // clamped to u8 range
+                let v = (128.0 + wave + block).clamp(0.0, 255.0) as u8;
+                data.extend([v; 3]);
+            }
+        }
+        CameraFrame::new(data, 96, 80, "test_device".to_string())
+    }
+
+    /// Mean absolute difference away from the borders uncovered by a warp
+    fn interior_difference(a: &CameraFrame, b: &CameraFrame) -> f32 {
+        let mut sum = 0u32;
+        let mut count = 0u32;
+        for y in 16..64 {
+            for x in 16..80 {
+                let idx = (y * 96 + x) * 3;
+                sum += u32::from(a.data[idx].abs_diff(b.data[idx]));
+                count += 1;
+            }
+        }
+        #[allow(clippy::cast_precision_loss)] // small test sums
+        let mean = sum as f32 / count as f32;
+        mean
+    }
+
+    #[test]
+    fn test_alignment_method_default_is_translation_only() {
+        assert_eq!(AlignmentMethod::default(), AlignmentMethod::TranslationOnly);
+        let method: AlignmentMethod =
+            serde_json::from_str("\"TranslationRotation\"").expect("deserialize method");
+        assert_eq!(method, AlignmentMethod::TranslationRotation);
+    }
+
+    #[test]
+    fn test_phase_correlation_recovers_shift() {
+        let reference = textured_frame();
+        let shift = AlignmentResult {
+            translation: (5.0, -3.0),
+            ..AlignmentResult::default()
+        };
+        let moved = apply_alignment(&reference, &shift).expect("shift frame");
+
+        let alignments = align_frames_with(
+            &[reference.clone(), moved.clone()],
+            AlignmentMethod::TranslationRotation,
+        )
+        .expect("alignment should succeed");
+        let found = &alignments[1];
+        assert!((found.translation.0 + 5.0).abs() < 0.5, "{found:?}");
+        assert!((found.translation.1 - 3.0).abs() < 0.5, "{found:?}");
+        assert!(found.residual < 1.0, "{found:?}");
+
+        let restored = apply_alignment(&moved, found).expect("apply alignment");
+        assert!(interior_difference(&reference, &restored) < 1.0);
+    }
+
+    #[test]
+    fn test_rotation_search_finds_small_rotation() {
+        let reference = textured_frame();
+        let rotate = AlignmentResult {
+            translation: (4.0, 2.0),
+            rotation: 0.05,
+            ..AlignmentResult::default()
+        };
+        let moved = apply_alignment(&reference, &rotate).expect("rotate frame");
+
+        let pair = [reference.clone(), moved.clone()];
+        let translation_only = &align_frames(&pair).expect("translation alignment")[1];
+        let rotation = &align_frames_with(&pair, AlignmentMethod::TranslationRotation)
+            .expect("rotation alignment")[1];
+        assert!((rotation.rotation + 0.05).abs() < 0.01, "{rotation:?}");
+        assert!(rotation.residual < translation_only.residual);
+
+        let restored = apply_alignment(&moved, rotation).expect("apply alignment");
+        assert!(interior_difference(&reference, &restored) < 3.0);
+    }
+
+    #[test]
+    fn test_affine_alignment_estimates_scale() {
+        let reference = textured_frame();
+        let zoom = AlignmentResult {
+            rotation: -0.03,
+            scale: 1.04,
+            ..AlignmentResult::default()
+        };
+        let moved = apply_alignment(&reference, &zoom).expect("zoom frame");
+
+        let found = &align_frames_with(&[reference, moved], AlignmentMethod::Affine)
+            .expect("affine alignment")[1];
+        assert!((found.scale - 0.96).abs() < 0.015, "{found:?}");
+        assert!(found.residual < 3.0, "{found:?}");
+    }
 }
@@ -1,4 +1,4 @@
-use super::{FocusStackConfig, FocusStackError};
+use super::{AlignmentMethod, FocusStackConfig, FocusStackError};
 use crate::constants::{
     CAPTURE_RETRY_COUNT, FOCUS_STACK_MAX_BRACKETS, FOCUS_STACK_MAX_DIST, FOCUS_STACK_MAX_SHOTS,
     FOCUS_STACK_MIN_BRACKETS, FOCUS_STACK_MIN_DIST, FOCUS_STACK_MIN_SHOTS, FOCUS_STACK_MIN_STEPS,
@@ -9,6 +9,7 @@ use crate::platform::capture_with_reconnect;
 /// Handles capturing multiple images at different focus distances
 /// for focus stacking. Requires camera with manual focus control.
 use crate::types::{CameraFormat, CameraFrame};
+use tokio_util::sync::CancellationToken;
 
 /// Capture a sequence of images at different focus distances
 ///
@@ -25,6 +26,72 @@ pub async fn capture_focus_sequence(
     config: FocusStackConfig,
     format: Option<CameraFormat>,
 ) -> Result<Vec<CameraFrame>, FocusStackError> {
+    capture_focus_sequence_cancellable(device_id, config, format, &CancellationToken::new(), |_| {})
+        .await
+}
+
+/// [`capture_focus_sequence`] that stops when `cancel` is cancelled and
+/// shows each frame to `on_frame` as it is captured
+///
+/// # Errors
+/// Returns a [`FocusStackError::Cancelled`] once `cancel` is cancelled, or
+/// any error of [`capture_focus_sequence`].
+pub async fn capture_focus_sequence_cancellable<F>(
+    device_id: String,
+    config: FocusStackConfig,
+    format: Option<CameraFormat>,
+    cancel: &CancellationToken,
+    mut on_frame: F,
+) -> Result<Vec<CameraFrame>, FocusStackError>
+where
+    F: FnMut(&CameraFrame),
+{
+    let mut frames = Vec::new();
+    capture_focus_steps(device_id, &config, format, cancel, |frame| {
+        on_frame(&frame);
+        frames.push(frame);
+        Ok(())
+    })
+    .await?;
+
+    log::info!("Captured {} frames for focus stack", frames.len());
+
+    // Validate all frames have same dimensions
+    if let Some(first_frame) = frames.first() {
+        let expected_dims = (first_frame.width, first_frame.height);
+
+        for (_i, frame) in frames.iter().enumerate().skip(1) {
+            let dims = (frame.width, frame.height);
+            if dims != expected_dims {
+                return Err(FocusStackError::DimensionMismatch {
+                    expected: expected_dims,
+                    got: dims,
+                });
+            }
+        }
+    }
+
+    Ok(frames)
+}
+
+/// Capture each focus step and hand the frame to `on_frame` as soon as it
+/// arrives, so callers decide what to keep in memory
+///
+/// # Errors
+/// Returns a [`FocusStackError::InvalidConfig`] if `num_steps` or the focus
+/// range is invalid, a [`FocusStackError::MergeFailed`] if a capture fails,
+/// a [`FocusStackError::Cancelled`] once `cancel` is cancelled, or any error
+/// returned by `on_frame`.
+pub(crate) async fn capture_focus_steps<F>(
+    device_id: String,
+    config: &FocusStackConfig,
+    format: Option<CameraFormat>,
+    cancel: &CancellationToken,
+    mut on_frame: F,
+) -> Result<(), FocusStackError>
+where
+    F: FnMut(CameraFrame) -> Result<(), FocusStackError>,
+{
     // Validate config
     if config.num_steps < FOCUS_STACK_MIN_STEPS {
         return Err(FocusStackError::InvalidConfig(format!(
@@ -51,7 +118,6 @@ pub async fn capture_focus_sequence(
     );
 
     let capture_format = format.unwrap_or_else(CameraFormat::standard);
-    let mut frames = Vec::with_capacity(config.num_steps as usize);
 
     // Calculate focus step size
     let focus_range = config.focus_end - config.focus_start;
@@ -64,6 +130,14 @@ pub async fn capture_focus_sequence(
 
     // Capture each step
     for step in 0..config.num_steps {
+        if cancel.is_cancelled() {
+            log::info!(
+                "Focus stack cancelled after {step} of {} steps",
+                config.num_steps
+            );
+            return Err(FocusStackError::Cancelled);
+        }
+
         #[allow(clippy::cast_precision_loss)]
         let focus_distance = config.focus_start + (step as f32 * focus_step);
 
@@ -96,7 +170,7 @@ pub async fn capture_focus_sequence(
                     frame.height,
                     frame.size_bytes
                 );
-                frames.push(frame);
+                on_frame(frame)?;
             }
             Err(e) => {
                 log::error!("Failed to capture frame at step {}: {}", step + 1, e);
@@ -110,31 +184,16 @@ pub async fn capture_focus_sequence(
 
         // Delay before next capture (except for last frame)
         if step < config.num_steps - 1 {
-            tokio::time::sleep(tokio::time::Duration::from_millis(u64::from(
-                config.step_delay_ms,
-            )))
-            .await;
-        }
-    }
-
-    log::info!("Captured {} frames for focus stack", frames.len());
-
-    // Validate all frames have same dimensions
-    if let Some(first_frame) = frames.first() {
-        let expected_dims = (first_frame.width, first_frame.height);
-
-        for (_i, frame) in frames.iter().enumerate().skip(1) {
-            let dims = (frame.width, frame.height);
-            if dims != expected_dims {
-                return Err(FocusStackError::DimensionMismatch {
-                    expected: expected_dims,
-                    got: dims,
-                });
+            tokio::select! {
+                () = cancel.cancelled() => {}
+                () = tokio::time::sleep(tokio::time::Duration::from_millis(u64::from(
+                    config.step_delay_ms,
+                ))) => {}
             }
         }
     }
 
-    Ok(frames)
+    Ok(())
 }
 
 /// Capture focus brackets for advanced focus stacking
@@ -181,6 +240,7 @@ pub async fn capture_focus_brackets(
             focus_start: focus_start.min(1.0),
             focus_end: focus_end.min(1.0),
             enable_alignment: true,
+            alignment_method: AlignmentMethod::default(),
             sharpness_threshold: 0.5,
             blend_levels: 5,
         };
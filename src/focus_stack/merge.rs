@@ -182,7 +182,7 @@ fn merge_with_pyramid_blending(
 }
 
 /// Compute sharpness map using Laplacian edge detection
-fn compute_sharpness_map(frame: &CameraFrame) -> SharpnessMap {
+pub(crate) fn compute_sharpness_map(frame: &CameraFrame) -> SharpnessMap {
     let width = frame.width as usize;
     let height = frame.height as usize;
     let expected_size = width * height * 3;
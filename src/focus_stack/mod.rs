@@ -12,8 +12,20 @@ pub mod align;
 pub mod capture;
 /// Image merging and stacking algorithms.
 pub mod merge;
+/// Sharpest-frame selection without merging.
+pub mod select;
+/// Incremental focus stacking that merges frames as they are captured.
+pub mod streaming;
 
-use crate::types::CameraFrame;
+pub use align::AlignmentMethod;
+pub use select::{pick_sharpest, SharpestFrame};
+
+use crate::constants::{
+    FOCUS_STACK_MAX_BLEND_LEVELS, FOCUS_STACK_MAX_DIST, FOCUS_STACK_MAX_SHARPNESS,
+    FOCUS_STACK_MAX_STEPS, FOCUS_STACK_MIN_BLEND_LEVELS, FOCUS_STACK_MIN_DIST,
+    FOCUS_STACK_MIN_SHARPNESS, FOCUS_STACK_MIN_STEPS, FOCUS_STACK_SLOW_CAPTURE_MS,
+};
+use crate::types::{CameraFormat, CameraFrame};
 
 /// Focus stack configuration
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -33,6 +45,10 @@ pub struct FocusStackConfig {
     /// Enable alignment compensation
     pub enable_alignment: bool,
 
+    /// Transform estimated when `enable_alignment` is set
+    #[serde(default)]
+    pub alignment_method: AlignmentMethod,
+
     /// Sharpness threshold for region detection (0.0-1.0)
     pub sharpness_threshold: f32,
 
@@ -48,12 +64,138 @@ impl Default for FocusStackConfig {
             focus_start: 0.0,
             focus_end: 1.0,
             enable_alignment: true,
+            alignment_method: AlignmentMethod::default(),
             sharpness_threshold: 0.5,
             blend_levels: 5,
         }
     }
 }
 
+/// Outcome of checking a [`FocusStackConfig`]
+///
+/// `errors` make the config unusable; `warnings` flag settings that work but
+/// are probably unintended. `suggested` is the config with every error fixed
+/// and every correctable warning applied, ready for the UI to offer.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FocusConfigReport {
+    /// Whether the config can be used as-is (no errors)
+    pub valid: bool,
+    /// Out-of-range settings that must be fixed
+    pub errors: Vec<String>,
+    /// Settings that work but are likely unintended
+    pub warnings: Vec<String>,
+    /// Corrected version of the config
+    pub suggested: FocusStackConfig,
+}
+
+impl FocusStackConfig {
+    /// Check the config and build a corrected suggestion
+    ///
+    /// With a `format`, `blend_levels` is also checked against the number of
+    /// pyramid levels the resolution supports (merging clamps it anyway).
+    pub fn diagnose(&self, format: Option<&CameraFormat>) -> FocusConfigReport {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut suggested = self.clone();
+
+        if !(FOCUS_STACK_MIN_STEPS..=FOCUS_STACK_MAX_STEPS).contains(&self.num_steps) {
+            errors.push(format!(
+                "num_steps must be between {FOCUS_STACK_MIN_STEPS} and {FOCUS_STACK_MAX_STEPS}, got {}",
+                self.num_steps
+            ));
+            suggested.num_steps = self
+                .num_steps
+                .clamp(FOCUS_STACK_MIN_STEPS, FOCUS_STACK_MAX_STEPS);
+        }
+
+        for (name, value, fixed) in [
+            ("focus_start", self.focus_start, &mut suggested.focus_start),
+            ("focus_end", self.focus_end, &mut suggested.focus_end),
+        ] {
+            if !(FOCUS_STACK_MIN_DIST..=FOCUS_STACK_MAX_DIST).contains(&value) {
+                errors.push(format!(
+                    "{name} must be between {FOCUS_STACK_MIN_DIST:.1} and {FOCUS_STACK_MAX_DIST:.1}, got {value}"
+                ));
+                *fixed = if value.is_nan() {
+                    FOCUS_STACK_MIN_DIST
+                } else {
+                    value.clamp(FOCUS_STACK_MIN_DIST, FOCUS_STACK_MAX_DIST)
+                };
+            }
+        }
+        if suggested.focus_start > suggested.focus_end {
+            warnings.push(format!(
+                "focus_start ({}) is beyond focus_end ({}); they will be swapped to sweep near to far",
+                suggested.focus_start, suggested.focus_end
+            ));
+            std::mem::swap(&mut suggested.focus_start, &mut suggested.focus_end);
+        } else if (suggested.focus_end - suggested.focus_start).abs() < f32::EPSILON {
+            warnings.push(
+                "focus_start equals focus_end; every frame will have the same focus".to_string(),
+            );
+        }
+
+        if !(FOCUS_STACK_MIN_SHARPNESS..=FOCUS_STACK_MAX_SHARPNESS)
+            .contains(&self.sharpness_threshold)
+        {
+            errors.push(format!(
+                "sharpness_threshold must be between {FOCUS_STACK_MIN_SHARPNESS:.1} and {FOCUS_STACK_MAX_SHARPNESS:.1}, got {}",
+                self.sharpness_threshold
+            ));
+            suggested.sharpness_threshold = if self.sharpness_threshold.is_nan() {
+                Self::default().sharpness_threshold
+            } else {
+                self.sharpness_threshold
+                    .clamp(FOCUS_STACK_MIN_SHARPNESS, FOCUS_STACK_MAX_SHARPNESS)
+            };
+        }
+
+        if !(FOCUS_STACK_MIN_BLEND_LEVELS..=FOCUS_STACK_MAX_BLEND_LEVELS)
+            .contains(&self.blend_levels)
+        {
+            errors.push(format!(
+                "blend_levels must be between {FOCUS_STACK_MIN_BLEND_LEVELS} and {FOCUS_STACK_MAX_BLEND_LEVELS}, got {}",
+                self.blend_levels
+            ));
+            suggested.blend_levels = self
+                .blend_levels
+                .clamp(FOCUS_STACK_MIN_BLEND_LEVELS, FOCUS_STACK_MAX_BLEND_LEVELS);
+        }
+        if let Some(format) = format {
+            let supported = max_blend_levels(format.width, format.height);
+            if suggested.blend_levels > supported {
+                warnings.push(format!(
+                    "blend_levels {} exceeds the {supported} levels {}x{} supports and will be clamped",
+                    suggested.blend_levels, format.width, format.height
+                ));
+                suggested.blend_levels = supported.max(FOCUS_STACK_MIN_BLEND_LEVELS);
+            }
+        }
+
+        let capture_ms = u64::from(suggested.num_steps) * u64::from(suggested.step_delay_ms);
+        if capture_ms >= FOCUS_STACK_SLOW_CAPTURE_MS {
+            #[allow(clippy::cast_precision_loss)] // display only
+            let secs = capture_ms as f64 / 1000.0;
+            warnings.push(format!(
+                "{} steps with {}ms delay take at least {secs:.1}s; the subject and camera must stay still throughout",
+                suggested.num_steps, suggested.step_delay_ms
+            ));
+        }
+
+        FocusConfigReport {
+            valid: errors.is_empty(),
+            errors,
+            warnings,
+            suggested,
+        }
+    }
+}
+
+/// Pyramid levels whose smallest level is still at least 2 pixels on each side
+fn max_blend_levels(width: u32, height: u32) -> u32 {
+    width.min(height).max(1).ilog2()
+}
+
 /// Focus stack result containing the merged image and metadata
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct FocusStackResult {
@@ -66,6 +208,15 @@ pub struct FocusStackResult {
     /// Average alignment error (pixels)
     pub alignment_error: f32,
 
+    /// Alignment method used
+    #[serde(default)]
+    pub alignment_method: AlignmentMethod,
+
+    /// Average luminance difference (0-255) left after alignment; 0.0 when
+    /// alignment is disabled
+    #[serde(default)]
+    pub alignment_residual: f32,
+
     /// Processing time (ms)
     pub processing_time_ms: u64,
 }
@@ -105,6 +256,9 @@ pub enum FocusStackError {
 
     /// Invalid configuration
     InvalidConfig(String),
+
+    /// Capture stopped by `cancel_operation`
+    Cancelled,
 }
 
 impl std::fmt::Display for FocusStackError {
@@ -132,12 +286,28 @@ impl std::fmt::Display for FocusStackError {
             Self::AlignmentFailed(msg) => write!(f, "Alignment failed: {msg}"),
             Self::MergeFailed(msg) => write!(f, "Merge failed: {msg}"),
             Self::InvalidConfig(msg) => write!(f, "Invalid config: {msg}"),
+            Self::Cancelled => write!(f, "Focus stack capture cancelled"),
         }
     }
 }
 
 impl std::error::Error for FocusStackError {}
 
+impl From<FocusStackError> for crate::errors::CommandError {
+    fn from(err: FocusStackError) -> Self {
+        let code = match err {
+            FocusStackError::InvalidConfig(_) => "INVALID_CONFIG",
+            FocusStackError::InsufficientImages { .. }
+            | FocusStackError::DimensionMismatch { .. }
+            | FocusStackError::DataCorruption { .. } => "INVALID_FRAMES",
+            FocusStackError::AlignmentFailed(_) => "ALIGNMENT_FAILED",
+            FocusStackError::MergeFailed(_) => "MERGE_FAILED",
+            FocusStackError::Cancelled => crate::errors::CommandError::CANCELLED,
+        };
+        Self::new(code, err.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,93 @@
+//! Single-frame selection from a focus sequence
+//!
+//! For scenes with one focal plane a composite is unnecessary: the sharpest
+//! frame of the bracket is the answer, and picking it costs one Laplacian
+//! pass per frame instead of alignment plus pyramid blending.
+
+use crate::quality::BlurDetector;
+use crate::types::CameraFrame;
+
+/// Sharpest frame of a focus sequence and how it was chosen
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SharpestFrame {
+    /// The selected frame
+    pub frame: CameraFrame,
+
+    /// Position of the frame in the captured sequence
+    pub index: usize,
+
+    /// Laplacian variance of the frame (higher = sharper)
+    pub sharpness: f64,
+
+    /// Number of frames compared
+    pub num_sources: usize,
+}
+
+/// Pick the frame with the highest global sharpness (Laplacian variance)
+///
+/// Ties go to the earliest frame.
+///
+/// # Panics
+/// Panics if `frames` is empty.
+#[must_use]
+pub fn pick_sharpest(frames: &[CameraFrame]) -> (usize, &CameraFrame) {
+    let (index, _) = sharpest_index(frames).expect("pick_sharpest needs at least one frame");
+    (index, &frames[index])
+}
+
+/// Index and Laplacian variance of the sharpest frame, `None` if empty
+pub(crate) fn sharpest_index(frames: &[CameraFrame]) -> Option<(usize, f64)> {
+    frames
+        .iter()
+        .map(BlurDetector::laplacian_variance)
+        .enumerate()
+        .fold(None, |best, (index, sharpness)| match best {
+            Some((_, best_sharpness)) if best_sharpness >= sharpness => best,
+            _ => Some((index, sharpness)),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 32x32 checkerboard whose contrast sets its sharpness
+    fn checker_frame(contrast: u8) -> CameraFrame {
+        let mut data = Vec::with_capacity(32 * 32 * 3);
+        for y in 0..32 {
+            for x in 0..32 {
+                let v = if (x + y) % 2 == 0 {
+                    128 + contrast / 2
+                } else {
+                    128 - contrast / 2
+                };
+                data.extend([v; 3]);
+            }
+        }
+        CameraFrame::new(data, 32, 32, "pick-test".to_string())
+    }
+
+    #[test]
+    fn test_pick_sharpest_selects_highest_contrast() {
+        let frames = [
+            checker_frame(10),
+            checker_frame(60),
+            checker_frame(200),
+            checker_frame(90),
+            checker_frame(0),
+        ];
+        let (index, frame) = pick_sharpest(&frames);
+        assert_eq!(index, 2);
+        assert_eq!(frame.data, frames[2].data);
+
+        let (_, sharpness) = sharpest_index(&frames).expect("non-empty");
+        assert!(sharpness > BlurDetector::laplacian_variance(&frames[3]));
+    }
+
+    #[test]
+    fn test_pick_sharpest_prefers_earliest_tie_and_handles_empty() {
+        let frames = [checker_frame(0), checker_frame(50), checker_frame(50)];
+        assert_eq!(pick_sharpest(&frames).0, 1);
+        assert!(sharpest_index(&[]).is_none());
+    }
+}
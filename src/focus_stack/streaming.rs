@@ -0,0 +1,299 @@
+//! Incremental focus stacking
+//!
+//! [`StreamingStacker`] merges frames one at a time into a running
+//! sharpest-pixel composite plus a per-pixel best-sharpness map, so memory
+//! stays at roughly two frames (the composite and the frame being merged) no
+//! matter how many focus steps are captured. The result matches
+//! [`merge_frames`](super::merge::merge_frames) with `blend_levels == 0`; the
+//! pyramid-blended batch merge produces softer seams but needs every frame in
+//! memory at once.
+
+use std::time::Instant;
+
+use super::align::{align_frames_with, apply_alignment, AlignmentMethod};
+use super::capture::capture_focus_steps;
+use super::merge::compute_sharpness_map;
+use super::{FocusStackConfig, FocusStackError, FocusStackResult};
+use crate::types::{CameraFormat, CameraFrame};
+use tokio_util::sync::CancellationToken;
+
+/// Running focus-stack composite
+#[derive(Debug)]
+pub struct StreamingStacker {
+    composite: Option<CameraFrame>,
+    best_sharpness: Vec<f32>,
+    sharpness_threshold: f32,
+    enable_alignment: bool,
+    alignment_method: AlignmentMethod,
+    frames_merged: usize,
+    alignment_error_sum: f32,
+    alignment_residual_sum: f32,
+}
+
+impl StreamingStacker {
+    /// Create an empty stacker
+    ///
+    /// A pixel is only taken from a later frame when it is sharper than the
+    /// current best and at least `sharpness_threshold`. With
+    /// `enable_alignment`, each frame is aligned to the composite first.
+    pub fn new(sharpness_threshold: f32, enable_alignment: bool) -> Self {
+        Self {
+            composite: None,
+            best_sharpness: Vec::new(),
+            sharpness_threshold,
+            enable_alignment,
+            alignment_method: AlignmentMethod::default(),
+            frames_merged: 0,
+            alignment_error_sum: 0.0,
+            alignment_residual_sum: 0.0,
+        }
+    }
+
+    /// Use `method` when aligning frames (default
+    /// [`AlignmentMethod::TranslationOnly`])
+    #[must_use]
+    pub fn with_alignment_method(mut self, method: AlignmentMethod) -> Self {
+        self.alignment_method = method;
+        self
+    }
+
+    /// Merge one frame into the composite
+    ///
+    /// # Errors
+    /// Returns a [`FocusStackError::DimensionMismatch`] if the frame differs
+    /// in size from the first frame, a [`FocusStackError::DataCorruption`] if
+    /// its buffer does not hold RGB8 pixels for its dimensions, or propagates
+    /// an alignment failure.
+    pub fn push(&mut self, frame: CameraFrame) -> Result<(), FocusStackError> {
+        let expected_size = frame.width as usize * frame.height as usize * 3;
+        if frame.data.len() != expected_size {
+            return Err(FocusStackError::DataCorruption {
+                frame_size: frame.data.len(),
+                expected_size,
+            });
+        }
+
+        let Some(composite) = self.composite.take() else {
+            let threshold = self.sharpness_threshold;
+            self.best_sharpness = compute_sharpness_map(&frame)
+                .scores
+                .into_iter()
+                .map(|s| if s >= threshold { s } else { 0.0 })
+                .collect();
+            self.composite = Some(frame);
+            self.frames_merged = 1;
+            return Ok(());
+        };
+
+        if (frame.width, frame.height) != (composite.width, composite.height) {
+            let expected = (composite.width, composite.height);
+            self.composite = Some(composite);
+            return Err(FocusStackError::DimensionMismatch {
+                expected,
+                got: (frame.width, frame.height),
+            });
+        }
+
+        let (mut composite, frame) = if self.enable_alignment {
+            // Align against the composite so only two frames are ever held.
+            let pair = [composite, frame];
+            let aligned = align_frames_with(&pair, self.alignment_method).and_then(|alignments| {
+                let alignment = &alignments[1];
+                self.alignment_error_sum += alignment.error;
+                self.alignment_residual_sum += alignment.residual;
+                apply_alignment(&pair[1], alignment)
+            });
+            let [composite, _] = pair;
+            match aligned {
+                Ok(frame) => (composite, frame),
+                Err(e) => {
+                    self.composite = Some(composite);
+                    return Err(e);
+                }
+            }
+        } else {
+            (composite, frame)
+        };
+
+        let sharpness = compute_sharpness_map(&frame);
+        for (idx, (&score, best)) in sharpness
+            .scores
+            .iter()
+            .zip(self.best_sharpness.iter_mut())
+            .enumerate()
+        {
+            if score > *best && score >= self.sharpness_threshold {
+                *best = score;
+                let at = idx * 3;
+                composite.data[at..at + 3].copy_from_slice(&frame.data[at..at + 3]);
+            }
+        }
+
+        self.composite = Some(composite);
+        self.frames_merged += 1;
+        Ok(())
+    }
+
+    /// Number of frames merged so far
+    pub fn frames_merged(&self) -> usize {
+        self.frames_merged
+    }
+
+    /// Mean alignment error of the merged frames (0.0 without alignment)
+    pub fn alignment_error(&self) -> f32 {
+        self.per_aligned_frame(self.alignment_error_sum)
+    }
+
+    /// Mean luminance residual after alignment (0.0 without alignment)
+    pub fn alignment_residual(&self) -> f32 {
+        self.per_aligned_frame(self.alignment_residual_sum)
+    }
+
+    fn per_aligned_frame(&self, sum: f32) -> f32 {
+        if self.frames_merged < 2 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)] // frame counts are small
+        let aligned = (self.frames_merged - 1) as f32;
+        sum / aligned
+    }
+
+    /// Take the merged frame
+    ///
+    /// # Errors
+    /// Returns a [`FocusStackError::InsufficientImages`] if no frame was
+    /// pushed.
+    pub fn finish(self) -> Result<CameraFrame, FocusStackError> {
+        let composite = self.composite.ok_or(FocusStackError::InsufficientImages {
+            required: 1,
+            provided: 0,
+        })?;
+        Ok(CameraFrame::new(
+            composite.data,
+            composite.width,
+            composite.height,
+            composite.device_id,
+        )
+        .with_format(composite.format))
+    }
+}
+
+/// Capture a focus sequence and merge each frame as it arrives
+///
+/// Equivalent to capturing with
+/// [`capture_focus_sequence`](super::capture::capture_focus_sequence) and
+/// merging without pyramid blending, but holds only about two frames in
+/// memory, which makes 50+ step stacks practical at high resolutions.
+///
+/// # Errors
+/// Returns a [`FocusStackError::InvalidConfig`] for an invalid config, a
+/// [`FocusStackError::MergeFailed`] if a capture fails, a
+/// [`FocusStackError::Cancelled`] once `cancel` is cancelled, or any error
+/// from [`StreamingStacker::push`].
+pub async fn capture_focus_stack_streaming<F>(
+    device_id: String,
+    config: FocusStackConfig,
+    format: Option<CameraFormat>,
+    cancel: &CancellationToken,
+    mut on_frame: F,
+) -> Result<FocusStackResult, FocusStackError>
+where
+    F: FnMut(&CameraFrame),
+{
+    let start_time = Instant::now();
+    let mut stacker = StreamingStacker::new(config.sharpness_threshold, config.enable_alignment)
+        .with_alignment_method(config.alignment_method);
+
+    capture_focus_steps(device_id, &config, format, cancel, |frame| {
+        on_frame(&frame);
+        stacker.push(frame)
+    })
+    .await?;
+
+    let num_sources = stacker.frames_merged();
+    let alignment_error = stacker.alignment_error();
+    let alignment_residual = stacker.alignment_residual();
+    let merged_frame = stacker.finish()?;
+    let processing_time_ms = u64::try_from(start_time.elapsed().as_millis()).unwrap_or(u64::MAX);
+    log::info!("Streaming focus stack of {num_sources} frames complete in {processing_time_ms}ms");
+
+    Ok(FocusStackResult {
+        merged_frame,
+        num_sources,
+        alignment_error,
+        alignment_method: config.alignment_method,
+        alignment_residual,
+        processing_time_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::focus_stack::merge::merge_frames;
+
+    /// 32x32 frame whose left or right half carries a fine checker pattern
+    /// (sharp) while the other half is flat gray (blurry)
+    fn half_sharp_frame(sharp_left: bool, shade: u8) -> CameraFrame {
+        let mut data = Vec::with_capacity(32 * 32 * 3);
+        for y in 0..32 {
+            for x in 0..32 {
+                let sharp = (x < 16) == sharp_left;
+                let v = if sharp && (x + y) % 2 == 0 {
+                    255
+                } else if sharp {
+                    0
+                } else {
+                    shade
+                };
+                data.extend([v; 3]);
+            }
+        }
+        CameraFrame::new(data, 32, 32, "stack-test".to_string())
+    }
+
+    #[test]
+    fn test_streaming_matches_batch_merge() {
+        let frames = [
+            half_sharp_frame(true, 100),
+            half_sharp_frame(false, 140),
+            half_sharp_frame(true, 120),
+        ];
+        let batch = merge_frames(&frames, 0.1, 0).expect("batch merge");
+
+        let mut stacker = StreamingStacker::new(0.1, false);
+        for frame in frames.clone() {
+            stacker.push(frame).expect("push");
+        }
+        assert_eq!(stacker.frames_merged(), 3);
+        let streamed = stacker.finish().expect("finish");
+
+        assert_eq!((streamed.width, streamed.height), (32, 32));
+        let total_diff: u64 = streamed
+            .data
+            .iter()
+            .zip(&batch.data)
+            .map(|(&a, &b)| u64::from(a.abs_diff(b)))
+            .sum();
+        let mean_diff = total_diff / streamed.data.len() as u64;
+        assert!(mean_diff <= 1, "mean difference {mean_diff}");
+    }
+
+    #[test]
+    fn test_streaming_rejects_mismatched_frames() {
+        let mut stacker = StreamingStacker::new(0.1, false);
+        assert!(StreamingStacker::new(0.1, false).finish().is_err());
+
+        stacker.push(half_sharp_frame(true, 100)).expect("push");
+        let small = CameraFrame::new(vec![0; 8 * 8 * 3], 8, 8, "stack-test".to_string());
+        assert!(matches!(
+            stacker.push(small),
+            Err(FocusStackError::DimensionMismatch { .. })
+        ));
+        let torn = CameraFrame::new(vec![0; 10], 32, 32, "stack-test".to_string());
+        assert!(stacker.push(torn).is_err());
+
+        assert_eq!(stacker.frames_merged(), 1, "composite survives errors");
+        assert!(stacker.finish().is_ok());
+    }
+}
@@ -34,7 +34,7 @@ pub fn list_formats(device_id: &str) -> Result<Vec<FormatInfo>, HeadlessError> {
     let devices = list_devices()?;
     let device = devices
         .into_iter()
-        .find(|d| d.id == device_id)
+        .find(|d| d.matches_id(device_id))
         .ok_or_else(|| HeadlessError::not_found("device", device_id))?;
 
     Ok(device.supports_formats)
@@ -1,11 +1,15 @@
 #[cfg(feature = "audio")]
 use crate::audio::{AudioCapture, AudioFrame};
+use crate::config::{tune_current_thread, ThreadRole};
 use crate::headless::controls::{validate_control_value, ControlId, ControlValue};
 use crate::headless::errors::HeadlessError;
 use crate::headless::types::{AudioMode, AudioPacket, BufferPolicy, CaptureConfig, Frame};
 use crate::platform::PlatformCamera;
 use crate::timing::PTSClock;
-use crate::types::{CameraControls, CameraFrame, CameraInitParams};
+use crate::types::{
+    CameraControls, CameraFrame, CameraInitParams, OpenPolicy, PixelFormatPreference,
+    TimestampSource,
+};
 use std::collections::VecDeque;
 use std::sync::{Arc, Condvar, Mutex};
 use std::time::{Duration, Instant};
@@ -165,6 +169,10 @@ impl HeadlessSession {
             device_id: config.device_id.clone(),
             format: config.format.clone(),
             controls: CameraControls::default(),
+            open_policy: OpenPolicy::default(),
+            timestamp_source: TimestampSource::default(),
+            pixel_format: PixelFormatPreference::default(),
+            backend: None,
         };
 
         let camera = PlatformCamera::new(params).map_err(HeadlessError::backend)?;
@@ -682,6 +690,7 @@ impl Drop for SessionHandle {
 // `Arc<Inner>` must be owned: this function runs on a spawned thread via `move`.
 #[allow(clippy::needless_pass_by_value)]
 fn capture_loop(inner: Arc<Inner>) {
+    tune_current_thread(ThreadRole::Capture);
     let Some(mut camera) = inner.camera.lock().expect("lock poisoned").take() else {
         return;
     };
@@ -716,6 +725,7 @@ fn capture_loop(inner: Arc<Inner>) {
 #[allow(clippy::needless_pass_by_value)]
 #[cfg(feature = "audio")]
 fn audio_capture_loop(inner: Arc<Inner>) {
+    tune_current_thread(ThreadRole::Capture);
     let pts_clock = PTSClock::new();
     let Ok(mut audio_capture) =
         AudioCapture::new(inner.config.audio_device_id.as_deref(), 48000, 2, pts_clock)
@@ -732,6 +742,10 @@ fn audio_capture_loop(inner: Arc<Inner>) {
         if inner.stop_flag.load(std::sync::atomic::Ordering::Relaxed) {
             break;
         }
+        if audio_capture.poll_device().is_err() {
+            // Device unplugged; the loss is already logged, video continues
+            break;
+        }
 
         match audio_capture.recv_timeout(Duration::from_millis(100)) {
             Ok(frame) => {
@@ -66,6 +66,10 @@ pub mod errors;
 /// Automatic focus stacking.
 pub mod focus_stack;
 
+#[cfg(any(feature = "hwenc", feature = "screen-capture"))]
+/// Location of the ffmpeg executable.
+pub mod ffmpeg;
+
 #[cfg(feature = "headless")]
 /// Headless capture session management.
 pub mod headless;
@@ -73,6 +77,9 @@ pub mod headless;
 /// Invariant checks for PPT.
 pub mod invariant_ppt;
 
+/// Structured log events and pluggable log sinks.
+pub mod logging;
+
 /// Permission management.
 pub mod permissions;
 
@@ -85,7 +92,6 @@ pub mod registry;
 /// Image quality analysis.
 pub mod quality;
 
-#[cfg(any(feature = "headless", feature = "audio"))]
 /// Timing utilities.
 pub mod timing;
 /// Common data types and structures.
@@ -98,10 +104,18 @@ pub mod preview;
 /// Video recording and encoding.
 pub mod recording;
 
+#[cfg(feature = "rtsp")]
+/// Network streaming outputs (RTSP).
+pub mod streaming;
+
 #[cfg(feature = "audio")]
 /// Audio capture and processing.
 pub mod audio;
 
+#[cfg(feature = "bench")]
+/// Capture-to-mux pipeline benchmark.
+pub mod bench;
+
 // Tests module - available for external tests
 /// Integration tests and test utilities.
 pub mod tests;
@@ -111,10 +125,11 @@ pub mod tests;
 pub mod testing;
 
 // Re-exports for convenience
-pub use errors::CameraError;
+pub use errors::{CameraError, CommandError};
 pub use platform::{CameraSystem, PlatformCamera};
 pub use types::{
-    CameraDeviceInfo, CameraFormat, CameraFrame, CameraInitParams, FrameMetadata, Platform,
+    CameraDeviceInfo, CameraFormat, CameraFrame, CameraInitParams, DeviceTransport, FormatPreset,
+    FormatPresetInfo, FourCC, FrameMetadata, Platform, StreamRole, StreamSpec,
 };
 
 #[cfg(feature = "headless")]
@@ -123,7 +138,7 @@ pub use headless::{list_controls, list_devices, list_formats, HeadlessSession};
 #[cfg(feature = "tauri")]
 use tauri::{
     plugin::{Builder, TauriPlugin},
-    Runtime,
+    Emitter, Runtime,
 };
 
 /// Initialize the `CrabCamera` plugin with all commands
@@ -135,30 +150,64 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             // Initialization commands
             commands::init::initialize_camera_system,
             commands::init::get_available_cameras,
+            commands::init::refresh_cameras,
             commands::init::get_platform_info,
             commands::init::test_camera_system,
             commands::init::get_current_platform,
+            commands::init::get_enabled_features,
+            commands::init::require_feature,
             commands::init::check_camera_availability,
+            commands::init::check_camera_in_use,
             commands::init::get_camera_formats,
             commands::init::get_recommended_format,
+            commands::init::get_format_presets,
+            commands::init::get_format_tradeoff,
+            commands::init::get_max_resolution_format,
             commands::init::get_optimal_settings,
             commands::init::get_system_diagnostics,
             // Permission commands
             commands::permissions::request_camera_permission,
             commands::permissions::check_camera_permission_status,
             commands::permissions::get_permission_status_string,
+            commands::permissions::get_camera_permission_guidance,
+            commands::permissions::open_camera_privacy_settings,
+            commands::permissions::request_microphone_permission,
+            commands::permissions::check_microphone_permission_status,
             // Capture commands
             commands::capture::capture_single_photo,
             commands::capture::capture_photo_sequence,
+            commands::capture::capture_photo_sequence_streaming,
+            commands::capture::capture_gif,
+            commands::capture::arm_capture,
+            commands::capture::fire_capture,
+            commands::capture::disarm_capture,
+            commands::capture::cancel_operation,
             commands::capture::capture_with_quality_retry,
             commands::capture::capture,
             commands::capture::start_camera_preview,
+            commands::capture::start_camera_preview_with_format_index,
+            commands::capture::capture_single_photo_with_format_index,
+            commands::capture::capture_max_resolution_photo,
             commands::capture::stop_camera_preview,
+            commands::capture::set_camera_format,
+            commands::capture::set_frame_orientation,
+            commands::capture::capture_depth_frame,
             commands::capture::release_camera,
             commands::capture::get_capture_stats,
+            commands::capture::get_session_metrics,
+            commands::capture::pause_camera,
+            commands::capture::resume_camera,
+            commands::capture::reset_session_metrics,
             commands::capture::save_frame_to_disk,
             commands::capture::save_frame_compressed,
+            commands::capture::save_frame_auto,
+            commands::capture::cleanup_temp_captures,
+            commands::capture::start_timelapse,
+            commands::capture::stop_timelapse,
+            commands::capture::get_timelapse_status,
+            commands::capture::clear_timelapse_status,
             commands::capture::set_frame_callback,
+            commands::capture::get_queued_frames,
             // Advanced camera commands
             commands::advanced::set_camera_controls,
             commands::advanced::get_camera_controls,
@@ -166,11 +215,28 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             commands::advanced::apply_camera_settings,
             commands::advanced::set_manual_focus,
             commands::advanced::set_manual_exposure,
+            commands::advanced::set_exposure_mode,
+            commands::advanced::set_exposure,
+            commands::advanced::set_focus_mode,
+            commands::advanced::get_exposure_settings,
             commands::advanced::set_white_balance,
+            commands::advanced::set_white_balance_kelvin,
+            commands::advanced::set_white_balance_preset,
+            commands::advanced::preset_settings,
+            commands::advanced::apply_preset,
+            commands::advanced::trigger_autofocus,
+            commands::advanced::set_focus_point,
             commands::advanced::capture_hdr_sequence,
             commands::advanced::capture_focus_stack_legacy,
             commands::advanced::get_camera_performance,
             commands::advanced::test_camera_capabilities,
+            commands::advanced::get_camera_capabilities_cached,
+            commands::advanced::patch_camera_controls,
+            commands::advanced::begin_controls,
+            commands::advanced::stage_controls,
+            commands::advanced::commit_controls,
+            commands::advanced::discard_controls,
+            commands::advanced::capture_autoframe,
             // Quality validation commands
             commands::quality::validate_frame_quality,
             commands::quality::validate_provided_frame,
@@ -180,9 +246,14 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             commands::quality::get_quality_config,
             commands::quality::capture_best_quality_frame,
             commands::quality::auto_capture_with_quality,
+            commands::quality::auto_capture_with_enhancement,
+            commands::quality::capture_denoised,
             commands::quality::analyze_quality_trends,
             // Configuration commands
             commands::config::get_config,
+            commands::config::get_config_schema,
+            commands::config::validate_config,
+            commands::config::clear_device_config,
             commands::config::update_config,
             commands::config::reset_config,
             commands::config::get_camera_config,
@@ -193,6 +264,8 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             commands::config::update_full_quality_config,
             commands::config::update_storage_config,
             commands::config::update_advanced_config,
+            commands::config::save_camera_profile,
+            commands::config::load_camera_profile,
             // Device monitoring commands
             commands::device_monitor::start_device_monitoring,
             commands::device_monitor::stop_device_monitoring,
@@ -203,10 +276,83 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             commands::focus_stack::capture_focus_brackets_command,
             commands::focus_stack::get_default_focus_config,
             commands::focus_stack::validate_focus_config,
+            commands::focus_stack::capture_focus_stack_streaming,
+            commands::focus_stack::capture_and_pick_sharpest,
             // Preview stream commands
             commands::preview::start_preview_stream,
             commands::preview::stop_preview_stream,
+            // Recording commands; builds without a feature register
+            // stand-ins that fail with FEATURE_DISABLED
+            #[cfg(feature = "recording")]
+            commands::recording::start_recording,
+            #[cfg(feature = "recording")]
+            commands::recording::record_frame,
+            #[cfg(feature = "recording")]
+            commands::recording::stop_recording,
+            #[cfg(feature = "recording")]
+            commands::recording::get_recording_status,
+            #[cfg(feature = "recording")]
+            commands::recording::list_recording_sessions,
+            #[cfg(feature = "recording")]
+            commands::recording::start_camera_recording,
+            #[cfg(feature = "recording")]
+            commands::recording::stop_camera_recording,
+            #[cfg(not(feature = "recording"))]
+            commands::unavailable::start_recording,
+            #[cfg(not(feature = "recording"))]
+            commands::unavailable::record_frame,
+            #[cfg(not(feature = "recording"))]
+            commands::unavailable::stop_recording,
+            #[cfg(not(feature = "recording"))]
+            commands::unavailable::get_recording_status,
+            #[cfg(not(feature = "recording"))]
+            commands::unavailable::list_recording_sessions,
+            #[cfg(not(feature = "recording"))]
+            commands::unavailable::start_camera_recording,
+            #[cfg(not(feature = "recording"))]
+            commands::unavailable::stop_camera_recording,
+            // Stream server commands
+            #[cfg(feature = "http-stream")]
+            commands::stream::start_mjpeg_server,
+            #[cfg(feature = "http-stream")]
+            commands::stream::stop_mjpeg_server,
+            #[cfg(not(feature = "http-stream"))]
+            commands::unavailable::start_mjpeg_server,
+            #[cfg(not(feature = "http-stream"))]
+            commands::unavailable::stop_mjpeg_server,
+            #[cfg(feature = "rtsp")]
+            commands::stream::start_rtsp_server,
+            #[cfg(feature = "rtsp")]
+            commands::stream::stop_rtsp_server,
+            #[cfg(not(feature = "rtsp"))]
+            commands::unavailable::start_rtsp_server,
+            #[cfg(not(feature = "rtsp"))]
+            commands::unavailable::stop_rtsp_server,
+            // Audio device commands
+            #[cfg(feature = "audio")]
+            commands::audio::list_audio_devices,
+            #[cfg(feature = "audio")]
+            commands::audio::list_loopback_devices,
+            #[cfg(feature = "audio")]
+            commands::audio::get_default_audio_device,
+            #[cfg(not(feature = "audio"))]
+            commands::unavailable::list_audio_devices,
+            #[cfg(not(feature = "audio"))]
+            commands::unavailable::list_loopback_devices,
+            #[cfg(not(feature = "audio"))]
+            commands::unavailable::get_default_audio_device,
         ])
+        .setup(|app, _api| {
+            let handle = app.clone();
+            commands::config::set_config_listener(Box::new(move |event| {
+                let _ = handle.emit("crabcamera://config-changed", event);
+            }));
+            let handle = app.clone();
+            commands::capture::set_progress_listener(Box::new(move |progress| {
+                let _ = handle.emit("crabcamera://capture-progress", progress);
+            }));
+            Ok(())
+        })
         .build()
 }
 
@@ -221,6 +367,10 @@ pub fn current_platform_string() -> String {
 }
 
 /// Initialize logging for the camera system
+///
+/// Installs `env_logger` as the default backend. To additionally receive
+/// structured events (e.g. to forward them to a Tauri frontend), install a
+/// sink with [`logging::set_log_sink`].
 pub fn init_logging() {
     if std::env::var("RUST_LOG").is_err() {
         std::env::set_var("RUST_LOG", "crabcamera=info");
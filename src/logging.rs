@@ -0,0 +1,188 @@
+//! Structured camera log events
+//!
+//! By default CrabCamera logs through the `log` facade (see [`crate::init_logging`],
+//! which wires up `env_logger`). Applications that want to surface camera
+//! diagnostics in their own UI can additionally install a sink with
+//! [`set_log_sink`]; every structured [`LogEvent`] emitted by the crate (device
+//! open, format negotiation, dropped frames, errors) is then delivered to it.
+//!
+//! In a Tauri app the sink typically forwards events to the frontend:
+//! ```rust,ignore
+//! use tauri::Emitter;
+//!
+//! let handle = app.handle().clone();
+//! crabcamera::logging::set_log_sink(Box::new(move |event| {
+//!     let _ = handle.emit("crabcamera://log", event);
+//! }));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::sync::{mpsc, Arc, LazyLock, RwLock};
+
+/// Severity of a [`LogEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    /// Unrecoverable failure of an operation.
+    Error,
+    /// Recoverable problem (e.g. a dropped frame).
+    Warn,
+    /// Normal lifecycle information (e.g. a device was opened).
+    Info,
+    /// Verbose diagnostic detail.
+    Debug,
+}
+
+impl From<LogLevel> for log::Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => log::Level::Error,
+            LogLevel::Warn => log::Level::Warn,
+            LogLevel::Info => log::Level::Info,
+            LogLevel::Debug => log::Level::Debug,
+        }
+    }
+}
+
+/// A structured camera diagnostic event that can cross the IPC boundary.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEvent {
+    /// Event severity.
+    pub level: LogLevel,
+    /// Module that emitted the event (e.g. `crabcamera::platform::manager`).
+    pub module: String,
+    /// Device the event relates to, if any.
+    pub device_id: Option<String>,
+    /// Human-readable message.
+    pub message: String,
+}
+
+impl LogEvent {
+    /// Create a new log event.
+    pub fn new(level: LogLevel, module: &str, device_id: Option<&str>, message: String) -> Self {
+        Self {
+            level,
+            module: module.to_string(),
+            device_id: device_id.map(str::to_string),
+            message,
+        }
+    }
+}
+
+/// Callback receiving every structured [`LogEvent`].
+pub type LogSink = Box<dyn Fn(LogEvent) + Send + Sync + 'static>;
+
+// Shared so `emit` can call the sink without holding the lock, which lets a
+// sink replace or clear itself
+static LOG_SINK: LazyLock<RwLock<Option<Arc<dyn Fn(LogEvent) + Send + Sync>>>> =
+    LazyLock::new(|| RwLock::new(None));
+
+/// Install a sink that receives every structured [`LogEvent`].
+///
+/// Replaces any previously installed sink. Events are still written to the
+/// `log` facade, so the `env_logger` output is unaffected.
+pub fn set_log_sink(sink: LogSink) {
+    if let Ok(mut guard) = LOG_SINK.write() {
+        *guard = Some(Arc::from(sink));
+    }
+}
+
+/// Remove the installed sink, if any.
+pub fn clear_log_sink() {
+    if let Ok(mut guard) = LOG_SINK.write() {
+        *guard = None;
+    }
+}
+
+/// Install a sink that forwards events into a channel and return its receiver.
+///
+/// Convenient when events should be drained on another thread. Dropping the
+/// receiver simply discards subsequent events.
+pub fn subscribe() -> mpsc::Receiver<LogEvent> {
+    let (tx, rx) = mpsc::channel();
+    let tx = std::sync::Mutex::new(tx);
+    set_log_sink(Box::new(move |event| {
+        if let Ok(tx) = tx.lock() {
+            let _ = tx.send(event);
+        }
+    }));
+    rx
+}
+
+/// Emit a structured event to the `log` facade and the installed sink.
+pub fn emit(event: LogEvent) {
+    log::log!(
+        target: event.module.as_str(),
+        event.level.into(),
+        "{}{}",
+        event
+            .device_id
+            .as_deref()
+            .map(|id| format!("[{id}] "))
+            .unwrap_or_default(),
+        event.message
+    );
+
+    let sink = LOG_SINK.read().ok().and_then(|guard| guard.clone());
+    if let Some(sink) = sink {
+        sink(event);
+    }
+}
+
+/// Convenience wrapper around [`emit`] for the common call shape.
+pub fn log_event(level: LogLevel, module: &str, device_id: Option<&str>, message: String) {
+    emit(LogEvent::new(level, module, device_id, message));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_event_serde_roundtrip() {
+        let event = LogEvent::new(
+            LogLevel::Warn,
+            "crabcamera::platform",
+            Some("0"),
+            "frame dropped".to_string(),
+        );
+
+        let json = serde_json::to_string(&event).expect("LogEvent should serialize");
+        assert!(json.contains("\"level\":\"warn\""));
+
+        let decoded: LogEvent = serde_json::from_str(&json).expect("LogEvent should deserialize");
+        assert_eq!(decoded, event);
+    }
+
+    #[test]
+    fn test_subscribe_receives_emitted_events() {
+        let rx = subscribe();
+
+        log_event(
+            LogLevel::Info,
+            module_path!(),
+            Some("sink-test-device"),
+            "camera opened".to_string(),
+        );
+
+        // The sink is process-global, so other tests may interleave events.
+        let received = rx
+            .try_iter()
+            .find(|e| e.device_id.as_deref() == Some("sink-test-device"));
+
+        // A sink may remove itself without deadlocking
+        set_log_sink(Box::new(|_| clear_log_sink()));
+        log_event(LogLevel::Debug, module_path!(), None, "last".to_string());
+        assert!(LOG_SINK.read().expect("sink lock").is_none());
+
+        let received = received.expect("event should reach the sink");
+        assert_eq!(received.level, LogLevel::Info);
+        assert_eq!(received.message, "camera opened");
+    }
+
+    #[test]
+    fn test_level_maps_to_log_level() {
+        assert_eq!(log::Level::from(LogLevel::Error), log::Level::Error);
+        assert_eq!(log::Level::from(LogLevel::Debug), log::Level::Debug);
+    }
+}
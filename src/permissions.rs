@@ -1,3 +1,9 @@
+use crate::constants::{
+    LINUX_VIDEO_DEVICE_PREFIX, MACOS_CAMERA_PRIVACY_URI, WINDOWS_CAMERA_PRIVACY_URI,
+};
+use crate::errors::CameraError;
+use crate::types::Platform;
+
 /// Permission status enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum PermissionStatus {
@@ -55,6 +61,45 @@ pub fn check_permission_detailed() -> PermissionInfo {
     }
 }
 
+/// Check microphone permission status
+/// Returns permission status for the current platform
+pub fn check_microphone_permission() -> PermissionStatus {
+    check_microphone_permission_detailed().status
+}
+
+/// Check microphone permission status with detailed information
+pub fn check_microphone_permission_detailed() -> PermissionInfo {
+    #[cfg(target_os = "windows")]
+    {
+        check_microphone_permission_windows()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        check_av_authorization_macos(crate::constants::AV_MEDIA_TYPE_AUDIO, "Microphone")
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        // ALSA/PulseAudio/PipeWire have no OS-level consent prompt; device
+        // access problems surface when the capture stream is opened.
+        PermissionInfo {
+            status: PermissionStatus::Granted,
+            message: "Linux does not gate microphone access by permission".to_string(),
+            can_request: false,
+        }
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    {
+        PermissionInfo {
+            status: PermissionStatus::NotDetermined,
+            message: "Platform not supported".to_string(),
+            can_request: false,
+        }
+    }
+}
+
 /// Detailed permission information
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PermissionInfo {
@@ -66,6 +111,107 @@ pub struct PermissionInfo {
     pub can_request: bool,
 }
 
+/// Platform-specific hints for granting camera access after a denial
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PermissionGuidance {
+    /// Platform the guidance applies to.
+    pub platform: Platform,
+    /// Where the relevant setting lives, in the OS's own terminology.
+    pub settings_path: String,
+    /// Step-by-step instructions suitable for showing to the user.
+    pub instructions: String,
+    /// URI that opens the settings pane directly, if the OS provides one.
+    pub settings_uri: Option<String>,
+}
+
+/// Explain how to grant camera access on `platform`
+pub fn get_permission_guidance(platform: Platform) -> PermissionGuidance {
+    match platform {
+        Platform::MacOS => PermissionGuidance {
+            platform,
+            settings_path: "System Settings > Privacy & Security > Camera".to_string(),
+            instructions: "Open System Settings, choose Privacy & Security, select Camera and \
+                           turn on access for this app. Restart the app afterwards."
+                .to_string(),
+            settings_uri: Some(MACOS_CAMERA_PRIVACY_URI.to_string()),
+        },
+        Platform::Windows => PermissionGuidance {
+            platform,
+            settings_path: "Settings > Privacy & security > Camera".to_string(),
+            instructions: "Open Settings, choose Privacy & security, select Camera and turn on \
+                           both \"Camera access\" and \"Let desktop apps access your camera\"."
+                .to_string(),
+            settings_uri: Some(WINDOWS_CAMERA_PRIVACY_URI.to_string()),
+        },
+        Platform::Linux => PermissionGuidance {
+            platform,
+            settings_path: format!("Group membership for {LINUX_VIDEO_DEVICE_PREFIX}*"),
+            instructions: format!(
+                "Camera devices ({LINUX_VIDEO_DEVICE_PREFIX}*) are usually owned by the \
+                 'video' group. Run: sudo usermod -a -G video $USER, then log out and back in. \
+                 Sandboxed apps (Flatpak/Snap) also need the camera portal or device permission."
+            ),
+            settings_uri: None,
+        },
+        Platform::Unknown => PermissionGuidance {
+            platform,
+            settings_path: "Operating system privacy settings".to_string(),
+            instructions: "Check your operating system's privacy settings and make sure this \
+                           app is allowed to use the camera."
+                .to_string(),
+            settings_uri: None,
+        },
+    }
+}
+
+/// Open the OS settings pane that controls camera access
+///
+/// Deep-links to the camera privacy pane on macOS and Windows. Linux has no
+/// standard settings pane for device group membership, so use
+/// [`get_permission_guidance`] to show instructions instead.
+///
+/// # Errors
+/// Returns a [`CameraError::UnsupportedOperation`] on platforms without a
+/// settings deep link, or a [`CameraError::IoError`] if the settings app
+/// cannot be launched.
+pub fn open_privacy_settings() -> Result<(), CameraError> {
+    let guidance = get_permission_guidance(Platform::current());
+    let Some(uri) = guidance.settings_uri else {
+        return Err(CameraError::UnsupportedOperation(format!(
+            "No privacy settings deep link on this platform: {}",
+            guidance.instructions
+        )));
+    };
+
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = std::process::Command::new("open");
+        command.arg(&uri);
+        command
+    };
+
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        // `start` resolves ms-settings: URIs; the empty string is the window title
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", "", &uri]);
+        command
+    };
+
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    {
+        command.spawn().map_err(|e| {
+            CameraError::IoError(format!("Failed to open privacy settings ({uri}): {e}"))
+        })?;
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    Err(CameraError::UnsupportedOperation(format!(
+        "Cannot open {uri} on this platform"
+    )))
+}
+
 #[cfg(target_os = "windows")]
 fn check_permission_windows() -> PermissionInfo {
     // On Windows 10+, camera access is controlled by Privacy settings
@@ -91,8 +237,69 @@ fn check_permission_windows() -> PermissionInfo {
     }
 }
 
+/// Read the capability consent store: the global switch first, then the
+/// "Let desktop apps access your microphone" switch under `NonPackaged`.
+#[cfg(target_os = "windows")]
+fn check_microphone_permission_windows() -> PermissionInfo {
+    use crate::constants::WINDOWS_MICROPHONE_CONSENT_KEY;
+
+    let read_consent = |key: &str| -> Option<String> {
+        let output = std::process::Command::new("reg")
+            .args(["query", key, "/v", "Value"])
+            .output()
+            .ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        parse_consent_value(&stdout)
+    };
+
+    let global = read_consent(WINDOWS_MICROPHONE_CONSENT_KEY);
+    let desktop = read_consent(&format!(r"{WINDOWS_MICROPHONE_CONSENT_KEY}\NonPackaged"));
+
+    match (global.as_deref(), desktop.as_deref()) {
+        (Some("Deny"), _) => PermissionInfo {
+            status: PermissionStatus::Denied,
+            message: "Microphone access is turned off in Windows Settings > Privacy & security > Microphone".to_string(),
+            can_request: false,
+        },
+        (_, Some("Deny")) => PermissionInfo {
+            status: PermissionStatus::Denied,
+            message: "Desktop apps are not allowed to use the microphone - enable \"Let desktop apps access your microphone\" in Windows Settings".to_string(),
+            can_request: false,
+        },
+        (Some("Allow"), _) => PermissionInfo {
+            status: PermissionStatus::Granted,
+            message: "Microphone access granted via Windows Privacy settings".to_string(),
+            can_request: false,
+        },
+        _ => PermissionInfo {
+            status: PermissionStatus::NotDetermined,
+            message: "Microphone consent setting not found".to_string(),
+            can_request: false,
+        },
+    }
+}
+
+/// Extract the `Value` data (`Allow`/`Deny`) from `reg query` output.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn parse_consent_value(reg_output: &str) -> Option<String> {
+    reg_output.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("Value"), Some("REG_SZ"), Some(value)) => Some(value.to_string()),
+            _ => None,
+        }
+    })
+}
+
 #[cfg(target_os = "macos")]
 fn check_permission_macos() -> PermissionInfo {
+    check_av_authorization_macos(crate::constants::AV_MEDIA_TYPE_VIDEO, "Camera")
+}
+
+/// Query `AVCaptureDevice` authorization for a media type (`vide` or `soun`).
+/// `device` names both the device in messages and its Privacy settings pane.
+#[cfg(target_os = "macos")]
+fn check_av_authorization_macos(media_type_code: &str, device: &str) -> PermissionInfo {
     use objc::runtime::{Class, Object};
     use objc::{msg_send, sel, sel_impl};
     use std::ffi::CString;
@@ -107,7 +314,8 @@ fn check_permission_macos() -> PermissionInfo {
             };
         };
 
-        // Build an NSString for the video media type (AVMediaTypeVideo == @"vide").
+        // Build an NSString for the media type (AVMediaTypeVideo == @"vide",
+        // AVMediaTypeAudio == @"soun").
         // NOTE: AVCaptureDevice has no `mediaTypeForString:` selector; sending it
         // raises an unrecognized-selector NSException which aborts the process.
         let Some(ns_string_class) = Class::get("NSString") else {
@@ -117,7 +325,7 @@ fn check_permission_macos() -> PermissionInfo {
                 can_request: false,
             };
         };
-        let Ok(av_media_type_video) = CString::new(crate::constants::AV_MEDIA_TYPE_VIDEO) else {
+        let Ok(av_media_type) = CString::new(media_type_code) else {
             return PermissionInfo {
                 status: PermissionStatus::NotDetermined,
                 message: "Invalid media type string".to_string(),
@@ -125,7 +333,7 @@ fn check_permission_macos() -> PermissionInfo {
             };
         };
         let media_type: *mut Object =
-            msg_send![ns_string_class, stringWithUTF8String: av_media_type_video.as_ptr()];
+            msg_send![ns_string_class, stringWithUTF8String: av_media_type.as_ptr()];
 
         // Check authorization status
         let auth_status: i64 =
@@ -140,22 +348,22 @@ fn check_permission_macos() -> PermissionInfo {
         match auth_status {
             3 => PermissionInfo {
                 status: PermissionStatus::Granted,
-                message: "Camera access authorized".to_string(),
+                message: format!("{device} access authorized"),
                 can_request: false,
             },
             2 => PermissionInfo {
                 status: PermissionStatus::Denied,
-                message: "Camera access denied - enable in System Preferences > Security & Privacy > Camera".to_string(),
+                message: format!("{device} access denied - enable in System Preferences > Security & Privacy > {device}"),
                 can_request: false,
             },
             1 => PermissionInfo {
                 status: PermissionStatus::Restricted,
-                message: "Camera access restricted by system policy".to_string(),
+                message: format!("{device} access restricted by system policy"),
                 can_request: false,
             },
             _ => PermissionInfo {
                 status: PermissionStatus::NotDetermined,
-                message: "Camera permission not yet requested".to_string(),
+                message: format!("{device} permission not yet requested"),
                 can_request: true,
             },
         }
@@ -164,7 +372,6 @@ fn check_permission_macos() -> PermissionInfo {
 
 #[cfg(target_os = "linux")]
 fn check_permission_linux() -> PermissionInfo {
-    use crate::constants::LINUX_VIDEO_DEVICE_PREFIX;
     use std::fs;
     use std::path::Path;
 
@@ -230,7 +437,13 @@ fn check_linux_group_membership() -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{check_permission, check_permission_detailed, PermissionInfo, PermissionStatus};
+    use super::{
+        check_microphone_permission, check_microphone_permission_detailed, check_permission,
+        check_permission_detailed, get_permission_guidance, open_privacy_settings,
+        parse_consent_value, PermissionInfo, PermissionStatus,
+    };
+    use crate::errors::CameraError;
+    use crate::types::Platform;
 
     #[test]
     fn test_permission_status_display_values() {
@@ -283,4 +496,58 @@ mod tests {
         assert_eq!(decoded.message, "camera blocked");
         assert!(decoded.can_request);
     }
+
+    #[test]
+    fn test_permission_guidance_per_platform() {
+        let mac = get_permission_guidance(Platform::MacOS);
+        assert!(mac.settings_path.contains("Privacy & Security > Camera"));
+        assert!(mac
+            .settings_uri
+            .as_deref()
+            .is_some_and(|uri| uri.starts_with("x-apple.systempreferences:")));
+
+        let windows = get_permission_guidance(Platform::Windows);
+        assert_eq!(
+            windows.settings_uri.as_deref(),
+            Some("ms-settings:privacy-webcam")
+        );
+
+        let linux = get_permission_guidance(Platform::Linux);
+        assert!(linux.instructions.contains("video"));
+        assert!(linux.settings_path.contains("/dev/video"));
+        assert!(linux.settings_uri.is_none());
+
+        for platform in [
+            Platform::MacOS,
+            Platform::Windows,
+            Platform::Linux,
+            Platform::Unknown,
+        ] {
+            let guidance = get_permission_guidance(platform);
+            assert_eq!(guidance.platform, platform);
+            assert!(!guidance.instructions.is_empty());
+        }
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn test_open_privacy_settings_unsupported_without_deep_link() {
+        let err = open_privacy_settings().expect_err("no deep link on this platform");
+        assert!(matches!(err, CameraError::UnsupportedOperation(_)));
+    }
+
+    #[test]
+    fn test_check_microphone_permission_shape() {
+        let info = check_microphone_permission_detailed();
+        assert!(!info.message.is_empty());
+        assert_eq!(check_microphone_permission(), info.status);
+    }
+
+    #[test]
+    fn test_parse_consent_value() {
+        let output =
+            "\r\nHKEY_CURRENT_USER\\...\\microphone\r\n    Value    REG_SZ    Deny\r\n\r\n";
+        assert_eq!(parse_consent_value(output).as_deref(), Some("Deny"));
+        assert_eq!(parse_consent_value("ERROR: not found"), None);
+    }
 }
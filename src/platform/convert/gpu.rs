@@ -0,0 +1,409 @@
+//! GPU YUV to RGB8 conversion with wgpu compute shaders.
+//!
+//! [`GpuConverter`] keeps its device, pipeline and buffers alive between
+//! frames; buffers are only reallocated when the frame size changes. The
+//! shader uses the same BT.601 limited-range fixed-point math as the CPU
+//! path, so both produce identical output.
+
+use super::to_rgb8;
+use crate::errors::CameraError;
+use crate::types::FourCC;
+use std::sync::{LazyLock, Mutex};
+
+/// Pixels each shader invocation converts (12 output bytes = 3 words).
+const PIXELS_PER_INVOCATION: u32 = 4;
+
+/// Must match `@workgroup_size` in the shader.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Maximum workgroups per dispatch dimension guaranteed by WebGPU.
+const MAX_WORKGROUPS_PER_DIM: u32 = 65_535;
+
+const SHADER: &str = r"
+struct Params {
+    width: u32,
+    height: u32,
+    format: u32,
+    groups_per_row: u32,
+}
+
+@group(0) @binding(0) var<storage, read> src: array<u32>;
+@group(0) @binding(1) var<storage, read_write> dst: array<u32>;
+@group(0) @binding(2) var<uniform> params: Params;
+
+fn src_byte(i: u32) -> u32 {
+    return (src[i >> 2u] >> ((i & 3u) * 8u)) & 0xffu;
+}
+
+fn yuv_to_rgb(y: u32, u: u32, v: u32) -> vec3<u32> {
+    let c = 298 * (i32(y) - 16);
+    let d = i32(u) - 128;
+    let e = i32(v) - 128;
+    return vec3<u32>(
+        u32(clamp((c + 409 * e + 128) >> 8u, 0, 255)),
+        u32(clamp((c - 100 * d - 208 * e + 128) >> 8u, 0, 255)),
+        u32(clamp((c + 516 * d + 128) >> 8u, 0, 255)),
+    );
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) gid: vec3<u32>) {
+    let group = gid.x + gid.y * params.groups_per_row;
+    if (group >= params.width * params.height / 4u) {
+        return;
+    }
+
+    var px: array<vec3<u32>, 4>;
+    for (var k = 0u; k < 4u; k = k + 1u) {
+        let p = group * 4u + k;
+        let x = p % params.width;
+        let row = p / params.width;
+        var luma: u32;
+        var cb: u32;
+        var cr: u32;
+        if (params.format == 0u) {
+            // YUYV: Y0 U Y1 V per pixel pair
+            let base = (row * params.width + (x & ~1u)) * 2u;
+            luma = src_byte(base + (x & 1u) * 2u);
+            cb = src_byte(base + 1u);
+            cr = src_byte(base + 3u);
+        } else {
+            // NV12: full-res Y plane, then interleaved half-res UV
+            luma = src_byte(row * params.width + x);
+            let uv = params.width * params.height + (row / 2u) * params.width + (x & ~1u);
+            cb = src_byte(uv);
+            cr = src_byte(uv + 1u);
+        }
+        px[k] = yuv_to_rgb(luma, cb, cr);
+    }
+
+    let out = group * 3u;
+    dst[out] = px[0].x | (px[0].y << 8u) | (px[0].z << 16u) | (px[1].x << 24u);
+    dst[out + 1u] = px[1].y | (px[1].z << 8u) | (px[2].x << 16u) | (px[2].y << 24u);
+    dst[out + 2u] = px[2].z | (px[3].x << 8u) | (px[3].y << 16u) | (px[3].z << 24u);
+}
+";
+
+// Process-wide converter, created on first use; `None` without a GPU
+static SHARED: LazyLock<Option<Mutex<GpuConverter>>> =
+    LazyLock::new(|| match GpuConverter::new() {
+        Ok(converter) => Some(Mutex::new(converter)),
+        Err(e) => {
+            log::info!("GPU color conversion unavailable, using CPU: {e}");
+            None
+        }
+    });
+
+/// Buffers sized for one frame geometry
+struct FrameBuffers {
+    width: u32,
+    height: u32,
+    src_len: u64,
+    src: wgpu::Buffer,
+    dst: wgpu::Buffer,
+    readback: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+/// Reusable GPU converter for YUYV and NV12 frames.
+///
+/// Frames the shader cannot handle (other formats, or widths not divisible
+/// by 4) are converted on the CPU with [`to_rgb8`], so [`convert`] accepts
+/// everything the CPU path does.
+///
+/// [`convert`]: GpuConverter::convert
+pub struct GpuConverter {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    layout: wgpu::BindGroupLayout,
+    params: wgpu::Buffer,
+    buffers: Option<FrameBuffers>,
+}
+
+impl GpuConverter {
+    /// Create a converter on the default GPU adapter.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::InitializationError`] if no adapter is
+    /// available or the device cannot be created.
+    pub fn new() -> Result<Self, CameraError> {
+        let instance = wgpu::Instance::default();
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            force_fallback_adapter: false,
+            compatible_surface: None,
+        }))
+        .ok_or_else(|| CameraError::InitializationError("No GPU adapter available".to_string()))?;
+
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("crabcamera-convert"),
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::downlevel_defaults(),
+                memory_hints: wgpu::MemoryHints::Performance,
+            },
+            None,
+        ))
+        .map_err(|e| CameraError::InitializationError(format!("Failed to open GPU device: {e}")))?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("yuv-to-rgb8"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("yuv-to-rgb8"),
+            entries: &[
+                storage(0, true),
+                storage(1, false),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("yuv-to-rgb8"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("yuv-to-rgb8"),
+            layout: Some(&pipeline_layout),
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        });
+
+        let params = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("yuv-to-rgb8-params"),
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        log::info!("GPU color conversion using {}", adapter.get_info().name);
+        Ok(Self {
+            device,
+            queue,
+            pipeline,
+            layout,
+            params,
+            buffers: None,
+        })
+    }
+
+    /// The process-wide converter, or `None` if no GPU is available.
+    pub fn shared() -> Option<&'static Mutex<GpuConverter>> {
+        SHARED.as_ref()
+    }
+
+    /// Convert a raw camera buffer to packed RGB8.
+    ///
+    /// Same contract as [`to_rgb8`]; YUYV and NV12 frames whose width is a
+    /// multiple of 4 (and height even, for NV12) run on the GPU.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`to_rgb8`], plus a
+    /// [`CameraError::CaptureError`] if reading the result back from the GPU
+    /// fails.
+    pub fn convert(
+        &mut self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        src_format: &str,
+    ) -> Result<Vec<u8>, CameraError> {
+        let format_code = match src_format.parse::<FourCC>().as_ref().map(FourCC::as_str) {
+            Ok("YUYV") => 0u32,
+            Ok("NV12") if height.is_multiple_of(2) => 1u32,
+            _ => return to_rgb8(data, width, height, src_format),
+        };
+        if width == 0 || height == 0 || !width.is_multiple_of(PIXELS_PER_INVOCATION) {
+            return to_rgb8(data, width, height, src_format);
+        }
+
+        let pixels = u64::from(width) * u64::from(height);
+        let src_len = if format_code == 0 {
+            pixels * 2
+        } else {
+            pixels * 3 / 2
+        };
+        let src_bytes = usize::try_from(src_len).unwrap_or(usize::MAX);
+        if data.len() < src_bytes {
+            // Let the CPU path report the short buffer
+            return to_rgb8(data, width, height, src_format);
+        }
+
+        let invocations = u32::try_from(pixels / u64::from(PIXELS_PER_INVOCATION))
+            .map_err(|_| CameraError::CaptureError("Frame too large for GPU".to_string()))?;
+        let workgroups = invocations.div_ceil(WORKGROUP_SIZE);
+        let groups_x = workgroups.min(MAX_WORKGROUPS_PER_DIM);
+        let groups_y = workgroups.div_ceil(groups_x);
+
+        let mut params = Vec::with_capacity(16);
+        for word in [width, height, format_code, groups_x * WORKGROUP_SIZE] {
+            params.extend_from_slice(&word.to_le_bytes());
+        }
+        self.queue.write_buffer(&self.params, 0, &params);
+
+        self.ensure_buffers(width, height, src_len);
+        let buffers = self
+            .buffers
+            .as_ref()
+            .ok_or_else(|| CameraError::CaptureError("GPU buffers missing".to_string()))?;
+        self.queue.write_buffer(&buffers.src, 0, &data[..src_bytes]);
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("yuv-to-rgb8"),
+            });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("yuv-to-rgb8"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &buffers.bind_group, &[]);
+            pass.dispatch_workgroups(groups_x, groups_y, 1);
+        }
+        let out_len = pixels * 3;
+        encoder.copy_buffer_to_buffer(&buffers.dst, 0, &buffers.readback, 0, out_len);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = buffers.readback.slice(..out_len);
+        let (tx, rx) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        let _ = self.device.poll(wgpu::Maintain::Wait);
+        rx.recv()
+            .map_err(|e| CameraError::CaptureError(format!("GPU readback failed: {e}")))?
+            .map_err(|e| CameraError::CaptureError(format!("GPU readback failed: {e}")))?;
+
+        let rgb = slice.get_mapped_range().to_vec();
+        buffers.readback.unmap();
+        Ok(rgb)
+    }
+
+    /// Allocate buffers for the given geometry unless the current ones fit
+    fn ensure_buffers(&mut self, width: u32, height: u32, src_len: u64) {
+        let reusable = self
+            .buffers
+            .as_ref()
+            .is_some_and(|b| b.width == width && b.height == height && b.src_len == src_len);
+        if reusable {
+            return;
+        }
+        let out_len = u64::from(width) * u64::from(height) * 3;
+        let buffer = |label, size, usage| {
+            self.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+        let src = buffer(
+            "yuv-to-rgb8-src",
+            src_len,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
+        let dst = buffer(
+            "yuv-to-rgb8-dst",
+            out_len,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        );
+        let readback = buffer(
+            "yuv-to-rgb8-readback",
+            out_len,
+            wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        );
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("yuv-to-rgb8"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: src.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: dst.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.params.as_entire_binding(),
+                },
+            ],
+        });
+        self.buffers = Some(FrameBuffers {
+            width,
+            height,
+            src_len,
+            src,
+            dst,
+            readback,
+            bind_group,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient(len: usize) -> Vec<u8> {
+        (0..len)
+            .map(|i| u8::try_from(i % 251).unwrap_or(0))
+            .collect()
+    }
+
+    #[test]
+    fn test_gpu_matches_cpu_when_available() {
+        let Ok(mut gpu) = GpuConverter::new() else {
+            // No adapter on this machine; the CPU path is covered elsewhere
+            return;
+        };
+
+        let (width, height) = (64, 32);
+        let yuyv = gradient(64 * 32 * 2);
+        assert_eq!(
+            gpu.convert(&yuyv, width, height, "YUYV").expect("GPU YUYV"),
+            to_rgb8(&yuyv, width, height, "YUYV").expect("CPU YUYV")
+        );
+
+        let nv12 = gradient(64 * 32 * 3 / 2);
+        assert_eq!(
+            gpu.convert(&nv12, width, height, "NV12").expect("GPU NV12"),
+            to_rgb8(&nv12, width, height, "NV12").expect("CPU NV12")
+        );
+
+        // Odd geometry and other formats fall back to the CPU
+        let rgb = [1, 2, 3, 4, 5, 6];
+        assert_eq!(
+            gpu.convert(&rgb, 2, 1, "RGB24").expect("RGB24"),
+            rgb.to_vec()
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! 10-bit / HDR conversions.
+//!
+//! P010 frames are decoded to 16-bit RGB with BT.2020 limited-range
+//! coefficients, the matrix HDR10 capture cards use. [`tone_map_pq_to_rgb8`]
+//! turns PQ-encoded (SMPTE ST 2084) BT.2020 samples into SDR sRGB, compressing
+//! highlights above SDR white instead of clipping them.
+
+use crate::constants::{HDR_PEAK_NITS, HDR_SDR_WHITE_NITS};
+
+/// Entries in the PQ and sRGB lookup tables (12-bit precision)
+const LUT_SIZE: usize = 4096;
+
+/// Convert P010 (16-bit words, 10 significant high bits) to packed RGB16.
+///
+/// `data` must hold the full luma plane followed by the interleaved chroma
+/// plane; the caller checks the length.
+pub(super) fn p010_to_rgb16(data: &[u8], width: usize, height: usize) -> Vec<u16> {
+    let sample = |i: usize| u16::from_le_bytes([data[i * 2], data[i * 2 + 1]]) >> 6;
+    let chroma_stride = width.div_ceil(2) * 2;
+    let chroma_start = width * height;
+    let mut rgb = Vec::with_capacity(width * height * 3);
+
+    for y in 0..height {
+        for x in 0..width {
+            let uv = chroma_start + (y / 2) * chroma_stride + (x / 2) * 2;
+            rgb.extend_from_slice(&yuv10_to_rgb16(
+                sample(y * width + x),
+                sample(uv),
+                sample(uv + 1),
+            ));
+        }
+    }
+
+    rgb
+}
+
+/// BT.2020 non-constant-luminance, 10-bit limited-range YUV to RGB16.
+fn yuv10_to_rgb16(luma: u16, cb: u16, cr: u16) -> [u16; 3] {
+    let luma = (f32::from(luma) - 64.0) / 876.0;
+    let cb = (f32::from(cb) - 512.0) / 896.0;
+    let cr = (f32::from(cr) - 512.0) / 896.0;
+
+    [
+        unit_to_u16(luma + 1.4746 * cr),
+        unit_to_u16(luma - 0.164_553 * cb - 0.571_353 * cr),
+        unit_to_u16(luma + 1.8814 * cb),
+    ]
+}
+
+fn unit_to_u16(value: f32) -> u16 {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped to 0..=65535
+    let sample = (value.clamp(0.0, 1.0) * 65535.0).round() as u16;
+    sample
+}
+
+/// Round a 16-bit sample to 8 bits (65535 maps to 255).
+pub(super) fn rgb16_sample_to_u8(sample: u16) -> u8 {
+    u8::try_from((u32::from(sample) * 255 + 32767) / 65535).unwrap_or(u8::MAX)
+}
+
+/// Tone-map PQ-encoded BT.2020 RGB16 samples to SDR sRGB RGB8.
+///
+/// Luminance is decoded to nits, normalised so [`HDR_SDR_WHITE_NITS`] is
+/// 1.0, compressed with an extended Reinhard curve whose white point is
+/// [`HDR_PEAK_NITS`], converted to BT.709 primaries and sRGB-encoded. Hue is
+/// kept by scaling all three channels by the luminance ratio.
+pub fn tone_map_pq_to_rgb8(rgb16: &[u16]) -> Vec<u8> {
+    #[allow(clippy::cast_precision_loss)] // table indices are small
+    let pq: Vec<f32> = (0..LUT_SIZE)
+        .map(|i| pq_to_nits(i as f32 / (LUT_SIZE - 1) as f32) / HDR_SDR_WHITE_NITS)
+        .collect();
+    #[allow(clippy::cast_precision_loss)] // table indices are small
+    let srgb: Vec<u8> = (0..LUT_SIZE)
+        .map(|i| srgb_encode(i as f32 / (LUT_SIZE - 1) as f32))
+        .collect();
+    let linear = |sample: u16| pq[usize::from(sample >> 4)];
+    let encode = |value: f32| {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped to table
+        let index = (value.clamp(0.0, 1.0) * (LUT_SIZE - 1) as f32).round() as usize;
+        srgb[index]
+    };
+
+    let white = HDR_PEAK_NITS / HDR_SDR_WHITE_NITS;
+    rgb16
+        .chunks_exact(3)
+        .flat_map(|px| {
+            let (r, g, b) = (linear(px[0]), linear(px[1]), linear(px[2]));
+            // BT.2020 to BT.709 primaries (linear light)
+            let r709 = 1.6605 * r - 0.5876 * g - 0.0728 * b;
+            let g709 = -0.1246 * r + 1.1329 * g - 0.0083 * b;
+            let b709 = -0.0182 * r - 0.1006 * g + 1.1187 * b;
+
+            let luminance = (0.2126 * r709 + 0.7152 * g709 + 0.0722 * b709).max(0.0);
+            let mapped = luminance * (1.0 + luminance / (white * white)) / (1.0 + luminance);
+            let ratio = if luminance > f32::EPSILON {
+                mapped / luminance
+            } else {
+                0.0
+            };
+            [
+                encode(r709 * ratio),
+                encode(g709 * ratio),
+                encode(b709 * ratio),
+            ]
+        })
+        .collect()
+}
+
+/// SMPTE ST 2084 (PQ) EOTF: normalised code value to absolute nits.
+fn pq_to_nits(code: f32) -> f32 {
+    const M1: f32 = 2610.0 / 16384.0;
+    const M2: f32 = 2523.0 / 4096.0 * 128.0;
+    const C1: f32 = 3424.0 / 4096.0;
+    const C2: f32 = 2413.0 / 4096.0 * 32.0;
+    const C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+    let power = code.clamp(0.0, 1.0).powf(1.0 / M2);
+    let ratio = (power - C1).max(0.0) / (C2 - C3 * power);
+    ratio.powf(1.0 / M1) * 10_000.0
+}
+
+/// sRGB opto-electronic transfer function, linear 0-1 to an 8-bit code.
+fn srgb_encode(linear: f32) -> u8 {
+    let encoded = if linear <= 0.003_130_8 {
+        12.92 * linear
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    };
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped to 0..=255
+    let code = (encoded.clamp(0.0, 1.0) * 255.0).round() as u8;
+    code
+}
@@ -0,0 +1,580 @@
+//! Pixel format conversion shared by all platform backends.
+//!
+//! Every backend hands its raw buffer to [`to_rgb8`] together with the
+//! source fourcc, so frames always leave the platform layer as packed RGB8.
+//! Formats without an explicit conversion are rejected with
+//! [`CameraError::UnsupportedFormat`] rather than being passed through and
+//! mislabelled.
+//!
+//! With the `gpu` feature, [`to_rgb8_auto`] routes large YUYV/NV12 frames
+//! through a shared [`GpuConverter`] and falls back to [`to_rgb8`] when no
+//! GPU adapter is available.
+//!
+//! 10-bit P010 and 16-bit RGB16 frames keep their precision through
+//! [`to_rgb16`]; [`tone_map_pq_to_rgb8`] maps HDR10 content to SDR. Z16 depth
+//! frames convert to grayscale of their raw samples for display.
+//!
+//! Some virtual cameras declare one format and send another (e.g. RGB
+//! labels on NV12 buffers). [`to_rgb8_auto`] checks each buffer with
+//! [`sniff_format`] and converts from the detected format instead.
+
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex};
+
+use crate::errors::CameraError;
+use crate::types::FourCC;
+use nokhwa::utils::FrameFormat;
+
+#[cfg(feature = "gpu")]
+mod gpu;
+mod hdr;
+#[cfg(feature = "gpu")]
+pub use gpu::GpuConverter;
+pub use hdr::tone_map_pq_to_rgb8;
+
+/// Map a nokhwa source frame format to the fourcc understood by [`to_rgb8`].
+pub fn fourcc_of(format: FrameFormat) -> &'static str {
+    match format {
+        FrameFormat::MJPEG => "MJPEG",
+        FrameFormat::YUYV => "YUYV",
+        FrameFormat::NV12 => "NV12",
+        FrameFormat::GRAY => "GRAY",
+        FrameFormat::RAWRGB => "RGB24",
+        FrameFormat::RAWBGR => "BGR24",
+    }
+}
+
+/// Map a fourcc to the nokhwa source frame format, the inverse of [`fourcc_of`].
+pub fn frame_format_of(src_format: &str) -> Option<FrameFormat> {
+    let fourcc: FourCC = src_format.parse().ok()?;
+    match fourcc.as_str() {
+        "MJPEG" => Some(FrameFormat::MJPEG),
+        "YUYV" => Some(FrameFormat::YUYV),
+        "NV12" => Some(FrameFormat::NV12),
+        "GRAY" => Some(FrameFormat::GRAY),
+        "RGB8" => Some(FrameFormat::RAWRGB),
+        "BGR8" => Some(FrameFormat::RAWBGR),
+        _ => None,
+    }
+}
+
+// (declared, detected) pairs already warned about, so a mislabelled
+// stream logs once instead of every frame
+static FORMAT_MISMATCHES: LazyLock<Mutex<HashSet<(FourCC, FourCC)>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// 8-bit formats [`sniff_format`] can detect by buffer size, smallest first
+const SNIFF_CANDIDATES: [FourCC; 5] = [
+    FourCC::GRAY,
+    FourCC::NV12,
+    FourCC::YUYV,
+    FourCC::RGB8,
+    FourCC::RGBA8,
+];
+
+/// Best guess at the actual format of a buffer declared as `declared`
+///
+/// `declared` is trusted when the buffer is exactly its size, or when it is
+/// MJPEG and the data starts with a JPEG start-of-image marker. Otherwise:
+///
+/// - Data starting with a JPEG marker is MJPEG.
+/// - A buffer exactly the size of GRAY, NV12, 4:2:2, RGB8 or RGBA8 at
+///   `width` x `height` is that format. 4:2:2 data is YUYV or UYVY
+///   depending on which bytes stay near neutral chroma (128); 3 bytes per
+///   pixel is taken as RGB8, since BGR cannot be told apart by content.
+///
+/// Anything else, such as row padding or a truncated buffer, returns
+/// `declared` unchanged, so conversion reports the problem as before.
+pub fn sniff_format(data: &[u8], width: u32, height: u32, declared: &FourCC) -> FourCC {
+    let is_jpeg = data.starts_with(&[0xFF, 0xD8, 0xFF]);
+    if *declared == FourCC::MJPEG && is_jpeg {
+        return declared.clone();
+    }
+    let exact = expected_len(width, height, declared.as_str()) == Some(data.len());
+    if exact || width == 0 || height == 0 {
+        return declared.clone();
+    }
+    if is_jpeg {
+        return FourCC::MJPEG;
+    }
+
+    let by_size = SNIFF_CANDIDATES
+        .iter()
+        .find(|fourcc| expected_len(width, height, fourcc.as_str()) == Some(data.len()));
+    match by_size {
+        Some(fourcc) if *fourcc == FourCC::YUYV && !chroma_in_odd_bytes(data) => FourCC::UYVY,
+        Some(fourcc) => fourcc.clone(),
+        None => declared.clone(),
+    }
+}
+
+/// Whether the odd bytes of packed 4:2:2 data stay closer to neutral
+/// chroma (128) than the even ones, as in YUYV
+fn chroma_in_odd_bytes(data: &[u8]) -> bool {
+    let (mut even, mut odd) = (0u64, 0u64);
+    for pair in data.chunks_exact(2) {
+        even += u64::from(pair[0].abs_diff(128));
+        odd += u64::from(pair[1].abs_diff(128));
+    }
+    odd <= even
+}
+
+/// Convert a raw camera buffer to packed RGB8, on the GPU when worthwhile.
+///
+/// The buffer is converted from the format [`sniff_format`] detects, with a
+/// warning the first time a device's declared format turns out wrong.
+///
+/// With the `gpu` feature, YUYV and NV12 frames of at least
+/// [`GPU_CONVERT_MIN_PIXELS`] pixels go through the shared
+/// [`GpuConverter`]; everything else, and every frame when no adapter is
+/// available, uses [`to_rgb8`].
+///
+/// [`GPU_CONVERT_MIN_PIXELS`]: crate::constants::GPU_CONVERT_MIN_PIXELS
+///
+/// # Errors
+/// Same as [`to_rgb8`].
+pub fn to_rgb8_auto(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    src_format: &str,
+) -> Result<Vec<u8>, CameraError> {
+    let detected = src_format.parse::<FourCC>().ok().map(|declared| {
+        let detected = sniff_format(data, width, height, &declared);
+        if detected != declared {
+            warn_format_mismatch(declared, &detected);
+        }
+        detected
+    });
+    let src_format = detected.as_ref().map_or(src_format, FourCC::as_str);
+
+    #[cfg(feature = "gpu")]
+    {
+        let pixels = u64::from(width) * u64::from(height);
+        let gpu_format = src_format
+            .parse::<FourCC>()
+            .is_ok_and(|f| f == FourCC::YUYV || f == FourCC::NV12);
+        if gpu_format && pixels >= crate::constants::GPU_CONVERT_MIN_PIXELS {
+            if let Some(converter) = GpuConverter::shared() {
+                if let Ok(mut converter) = converter.lock() {
+                    return converter.convert(data, width, height, src_format);
+                }
+            }
+        }
+    }
+    to_rgb8(data, width, height, src_format)
+}
+
+/// Convert a raw camera buffer to packed RGB8.
+///
+/// Supported source formats (case-insensitive, aliases normalized through
+/// [`FourCC`]): `YUYV`/`YUY2`, `UYVY`, `NV12`, `MJPEG`/`MJPG`,
+/// `RGB24`/`RGB8`/`RAWRGB`, `BGR24`/`RAWBGR`, `GRAY`/`GREY`, and the high
+/// bit depth `P010`, `RGB16` and `Z16` (depth, as gray), whose samples are
+/// rounded to 8 bits (no tone mapping). 8-bit YUV sources are converted with BT.601 limited-range
+/// coefficients.
+///
+/// # Errors
+/// Returns [`CameraError::UnsupportedFormat`] for any other fourcc, and a
+/// [`CameraError::CaptureError`] if the buffer is too small for the given
+/// dimensions or an MJPEG frame cannot be decoded.
+pub fn to_rgb8(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    src_format: &str,
+) -> Result<Vec<u8>, CameraError> {
+    let pixels = width as usize * height as usize;
+    let fourcc: FourCC = src_format
+        .parse()
+        .map_err(|_| CameraError::UnsupportedFormat(src_format.to_string()))?;
+
+    match fourcc.as_str() {
+        "YUYV" => {
+            require_len(data, packed_422_len(width, height), src_format)?;
+            Ok(packed_422_to_rgb(data, width, height, [0, 1, 2, 3]))
+        }
+        "UYVY" => {
+            require_len(data, packed_422_len(width, height), src_format)?;
+            Ok(packed_422_to_rgb(data, width, height, [1, 0, 3, 2]))
+        }
+        "NV12" => {
+            let chroma_w = (width as usize).div_ceil(2);
+            let chroma_h = (height as usize).div_ceil(2);
+            require_len(data, pixels + chroma_w * chroma_h * 2, src_format)?;
+            Ok(nv12_to_rgb(data, width as usize, height as usize))
+        }
+        "MJPEG" => {
+            let img = image::load_from_memory(data)
+                .map_err(|e| CameraError::CaptureError(format!("Failed to decode MJPEG: {e}")))?
+                .to_rgb8();
+            if img.width() != width || img.height() != height {
+                return Err(CameraError::CaptureError(format!(
+                    "MJPEG frame is {}x{}, expected {width}x{height}",
+                    img.width(),
+                    img.height()
+                )));
+            }
+            Ok(img.into_raw())
+        }
+        "RGB8" => {
+            require_len(data, pixels * 3, src_format)?;
+            Ok(data[..pixels * 3].to_vec())
+        }
+        "RGBA8" => {
+            require_len(data, pixels * 4, src_format)?;
+            Ok(data[..pixels * 4]
+                .chunks_exact(4)
+                .flat_map(|rgba| [rgba[0], rgba[1], rgba[2]])
+                .collect())
+        }
+        "BGR8" => {
+            require_len(data, pixels * 3, src_format)?;
+            Ok(data[..pixels * 3]
+                .chunks_exact(3)
+                .flat_map(|bgr| [bgr[2], bgr[1], bgr[0]])
+                .collect())
+        }
+        "GRAY" => {
+            require_len(data, pixels, src_format)?;
+            Ok(data[..pixels].iter().flat_map(|&y| [y, y, y]).collect())
+        }
+        "P010" | "RGB16" | "Z16" => Ok(to_rgb16(data, width, height, src_format)?
+            .into_iter()
+            .map(hdr::rgb16_sample_to_u8)
+            .collect()),
+        _ => Err(CameraError::UnsupportedFormat(src_format.to_string())),
+    }
+}
+
+/// Convert a raw camera buffer to packed RGB with 16-bit samples.
+///
+/// `P010` is decoded with BT.2020 limited-range coefficients, `RGB16`
+/// (little-endian) is passed through and each raw `Z16` depth sample becomes
+/// a gray pixel, all at full precision. Every other
+/// format [`to_rgb8`] accepts is widened so 255 maps to 65535.
+///
+/// # Errors
+/// Same as [`to_rgb8`].
+pub fn to_rgb16(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    src_format: &str,
+) -> Result<Vec<u16>, CameraError> {
+    let pixels = width as usize * height as usize;
+    let fourcc: FourCC = src_format
+        .parse()
+        .map_err(|_| CameraError::UnsupportedFormat(src_format.to_string()))?;
+
+    match fourcc.as_str() {
+        "P010" => {
+            let chroma_w = (width as usize).div_ceil(2);
+            let chroma_h = (height as usize).div_ceil(2);
+            require_len(data, (pixels + chroma_w * chroma_h * 2) * 2, src_format)?;
+            Ok(hdr::p010_to_rgb16(data, width as usize, height as usize))
+        }
+        "RGB16" => {
+            require_len(data, pixels * 6, src_format)?;
+            Ok(data[..pixels * 6]
+                .chunks_exact(2)
+                .map(|sample| u16::from_le_bytes([sample[0], sample[1]]))
+                .collect())
+        }
+        "Z16" => {
+            require_len(data, pixels * 2, src_format)?;
+            Ok(data[..pixels * 2]
+                .chunks_exact(2)
+                .flat_map(|sample| [u16::from_le_bytes([sample[0], sample[1]]); 3])
+                .collect())
+        }
+        _ => Ok(to_rgb8(data, width, height, src_format)?
+            .into_iter()
+            .map(|v| u16::from(v) * 257)
+            .collect()),
+    }
+}
+
+/// Minimum buffer size for an uncompressed frame in `src_format`.
+///
+/// Returns `None` for compressed (MJPEG) and unrecognised formats, whose size
+/// cannot be derived from the dimensions.
+pub fn expected_len(width: u32, height: u32, src_format: &str) -> Option<usize> {
+    let pixels = width as usize * height as usize;
+    let fourcc: FourCC = src_format.parse().ok()?;
+    match fourcc.as_str() {
+        "YUYV" | "UYVY" => Some(packed_422_len(width, height)),
+        "NV12" => {
+            let chroma_w = (width as usize).div_ceil(2);
+            let chroma_h = (height as usize).div_ceil(2);
+            Some(pixels + chroma_w * chroma_h * 2)
+        }
+        "RGB8" | "BGR8" => Some(pixels * 3),
+        "RGBA8" => Some(pixels * 4),
+        "GRAY" => Some(pixels),
+        "P010" => {
+            let chroma_w = (width as usize).div_ceil(2);
+            let chroma_h = (height as usize).div_ceil(2);
+            Some((pixels + chroma_w * chroma_h * 2) * 2)
+        }
+        "RGB16" => Some(pixels * 6),
+        "Z16" => Some(pixels * 2),
+        _ => None,
+    }
+}
+
+fn warn_format_mismatch(declared: FourCC, detected: &FourCC) {
+    let mut warned = FORMAT_MISMATCHES
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if warned.insert((declared.clone(), detected.clone())) {
+        log::warn!("Frame declared as {declared} looks like {detected}; converting as {detected}");
+    }
+}
+
+fn require_len(data: &[u8], expected: usize, src_format: &str) -> Result<(), CameraError> {
+    if data.len() < expected {
+        return Err(CameraError::CaptureError(format!(
+            "{src_format} buffer too small: {} bytes, expected {expected}",
+            data.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Bytes in a packed 4:2:2 frame (two bytes per pixel, even row width).
+fn packed_422_len(width: u32, height: u32) -> usize {
+    (width as usize).div_ceil(2) * 4 * height as usize
+}
+
+/// Convert packed 4:2:2 data; `order` gives the byte offsets of Y0, U, Y1, V.
+fn packed_422_to_rgb(data: &[u8], width: u32, height: u32, order: [usize; 4]) -> Vec<u8> {
+    let width = width as usize;
+    let row_bytes = width.div_ceil(2) * 4;
+    let mut rgb = Vec::with_capacity(width * height as usize * 3);
+
+    for row in data.chunks_exact(row_bytes).take(height as usize) {
+        for (i, macro_px) in row.chunks_exact(4).enumerate() {
+            let (u, v) = (macro_px[order[1]], macro_px[order[3]]);
+            rgb.extend_from_slice(&yuv_to_rgb(macro_px[order[0]], u, v));
+            if i * 2 + 1 < width {
+                rgb.extend_from_slice(&yuv_to_rgb(macro_px[order[2]], u, v));
+            }
+        }
+    }
+
+    rgb
+}
+
+fn nv12_to_rgb(data: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let (y_plane, uv_plane) = data.split_at(width * height);
+    let chroma_stride = width.div_ceil(2) * 2;
+    let mut rgb = Vec::with_capacity(width * height * 3);
+
+    for y in 0..height {
+        for x in 0..width {
+            let uv = (y / 2) * chroma_stride + (x / 2) * 2;
+            rgb.extend_from_slice(&yuv_to_rgb(
+                y_plane[y * width + x],
+                uv_plane[uv],
+                uv_plane[uv + 1],
+            ));
+        }
+    }
+
+    rgb
+}
+
+/// BT.601 limited-range YUV to RGB using fixed-point arithmetic.
+fn yuv_to_rgb(luma: u8, cb: u8, cr: u8) -> [u8; 3] {
+    let luma = 298 * (i32::from(luma) - 16);
+    let cb = i32::from(cb) - 128;
+    let cr = i32::from(cr) - 128;
+
+    let clamp = |value: i32| u8::try_from(value.clamp(0, 255)).unwrap_or(u8::MAX);
+
+    [
+        clamp((luma + 409 * cr + 128) >> 8),
+        clamp((luma - 100 * cb - 208 * cr + 128) >> 8),
+        clamp((luma + 516 * cb + 128) >> 8),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BT.601 limited-range reference samples: (Y, U, V) -> RGB
+    const BLACK: (u8, u8, u8) = (16, 128, 128);
+    const WHITE: (u8, u8, u8) = (235, 128, 128);
+    const RED: (u8, u8, u8) = (81, 90, 240);
+
+    #[test]
+    fn test_yuyv_known_pixels() {
+        // Two macro-pixels: black/white share neutral chroma, then red/red.
+        let data = [
+            BLACK.0, 128, WHITE.0, 128, //
+            RED.0, RED.1, RED.0, RED.2,
+        ];
+        let rgb = to_rgb8(&data, 4, 1, "YUYV").expect("YUYV should convert");
+        assert_eq!(rgb, vec![0, 0, 0, 255, 255, 255, 255, 0, 0, 255, 0, 0]);
+
+        let alias = to_rgb8(&data, 4, 1, "yuy2").expect("YUY2 alias should convert");
+        assert_eq!(alias, rgb);
+    }
+
+    #[test]
+    fn test_uyvy_known_pixels() {
+        let data = [128, BLACK.0, 128, WHITE.0];
+        let rgb = to_rgb8(&data, 2, 1, "UYVY").expect("UYVY should convert");
+        assert_eq!(rgb, vec![0, 0, 0, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_nv12_known_pixels() {
+        // 2x2 red: four luma samples followed by a single interleaved UV pair.
+        let data = [RED.0, RED.0, RED.0, RED.0, RED.1, RED.2];
+        let rgb = to_rgb8(&data, 2, 2, "NV12").expect("NV12 should convert");
+        assert_eq!(rgb, [255, 0, 0].repeat(4));
+    }
+
+    #[test]
+    fn test_mjpeg_roundtrip() {
+        let img = image::RgbImage::from_pixel(8, 8, image::Rgb([255, 255, 255]));
+        let mut jpeg = Vec::new();
+        image::DynamicImage::ImageRgb8(img)
+            .write_to(
+                &mut std::io::Cursor::new(&mut jpeg),
+                image::ImageFormat::Jpeg,
+            )
+            .expect("JPEG encode should succeed");
+
+        let rgb = to_rgb8(&jpeg, 8, 8, "MJPG").expect("MJPEG should decode");
+        assert_eq!(rgb.len(), 8 * 8 * 3);
+        assert!(rgb.iter().all(|&b| b > 250));
+
+        let err = to_rgb8(&jpeg, 16, 16, "MJPEG").expect_err("size mismatch should fail");
+        assert!(matches!(err, CameraError::CaptureError(_)));
+    }
+
+    #[test]
+    fn test_rgb24_and_bgr24_passthrough() {
+        let data = [1, 2, 3, 4, 5, 6];
+        assert_eq!(to_rgb8(&data, 2, 1, "RGB24").expect("RGB24"), data.to_vec());
+        assert_eq!(
+            to_rgb8(&data, 2, 1, "BGR24").expect("BGR24"),
+            vec![3, 2, 1, 6, 5, 4]
+        );
+        assert_eq!(
+            to_rgb8(&[7, 9], 2, 1, "GRAY").expect("GRAY"),
+            vec![7, 7, 7, 9, 9, 9]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_format_is_explicit() {
+        let err = to_rgb8(&[0; 16], 2, 2, "H264").expect_err("H264 is not a pixel format");
+        assert!(matches!(err, CameraError::UnsupportedFormat(ref f) if f == "H264"));
+    }
+
+    #[test]
+    fn test_short_buffer_is_rejected() {
+        let err = to_rgb8(&[0; 4], 4, 4, "YUYV").expect_err("short buffer should fail");
+        assert!(matches!(err, CameraError::CaptureError(_)));
+    }
+
+    #[test]
+    fn test_expected_len_by_format() {
+        assert_eq!(expected_len(4, 2, "YUY2"), Some(16));
+        assert_eq!(expected_len(4, 2, "NV12"), Some(12));
+        assert_eq!(expected_len(4, 2, "RGB24"), Some(24));
+        assert_eq!(expected_len(4, 2, "MJPEG"), None);
+        assert_eq!(expected_len(4, 2, "P010"), Some(24));
+        assert_eq!(expected_len(4, 2, "RGB48"), Some(48));
+        assert_eq!(expected_len(4, 2, "Z16"), Some(16));
+    }
+
+    #[test]
+    fn test_p010_known_pixels_keep_ten_bits() {
+        // 2x2 frame: luma black, white, and two mid-grey levels one 10-bit
+        // code apart, sharing neutral chroma. Samples sit in the high bits.
+        let words: [u16; 6] = [64, 940, 500, 501, 512, 512];
+        let data: Vec<u8> = words.iter().flat_map(|w| (w << 6).to_le_bytes()).collect();
+
+        let rgb16 = to_rgb16(&data, 2, 2, "P010").expect("P010 should convert");
+        assert_eq!(&rgb16[..6], &[0, 0, 0, u16::MAX, u16::MAX, u16::MAX]);
+        assert!(rgb16[9] > rgb16[6], "adjacent 10-bit codes stay distinct");
+
+        let rgb8 = to_rgb8(&data, 2, 2, "P010").expect("P010 to RGB8");
+        assert_eq!(&rgb8[..6], &[0, 0, 0, 255, 255, 255]);
+        assert!(to_rgb16(&data[..10], 2, 2, "P010").is_err());
+    }
+
+    #[test]
+    fn test_pq_tone_map_compresses_highlights() {
+        // PQ code values for roughly 0, 100, 600, 1000 and 10000 nits
+        let codes = [0.0, 0.508, 0.706, 0.752, 1.0];
+        let rgb16: Vec<u16> = codes
+            .iter()
+            .flat_map(|&c: &f32| {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let v = (c * 65535.0) as u16;
+                [v; 3]
+            })
+            .collect();
+
+        let sdr = tone_map_pq_to_rgb8(&rgb16);
+        let grey: Vec<u8> = sdr.chunks_exact(3).map(|px| px[0]).collect();
+        assert_eq!(grey[0], 0);
+        assert!(grey[..4].windows(2).all(|w| w[0] < w[1]), "{grey:?}");
+        assert!(grey[2] < 255, "600 nits is below the peak: {grey:?}");
+        assert_eq!(grey[4], 255);
+        assert!(sdr
+            .chunks_exact(3)
+            .all(|px| px[0] == px[1] && px[1] == px[2]));
+    }
+
+    #[test]
+    fn test_sniff_format_corrects_mislabelled_buffers() {
+        let (w, h) = (4, 2);
+        let nv12 = vec![128u8; 12];
+        assert_eq!(sniff_format(&nv12, w, h, &FourCC::RGB8), FourCC::NV12);
+        assert_eq!(sniff_format(&nv12, w, h, &FourCC::NV12), FourCC::NV12);
+        assert_eq!(sniff_format(&[0u8; 24], w, h, &FourCC::NV12), FourCC::RGB8);
+        assert_eq!(sniff_format(&[0u8; 8], w, h, &FourCC::RGB8), FourCC::GRAY);
+
+        // 4:2:2 byte order follows the neutral chroma
+        let yuyv = [20, 128, 200, 130, 90, 126, 240, 128].repeat(2);
+        let uyvy = [128, 20, 130, 200, 126, 90, 128, 240].repeat(2);
+        assert_eq!(sniff_format(&yuyv, w, h, &FourCC::RGB8), FourCC::YUYV);
+        assert_eq!(sniff_format(&uyvy, w, h, &FourCC::RGB8), FourCC::UYVY);
+
+        let jpeg = [0xFF, 0xD8, 0xFF, 0xE0, 0, 0x10];
+        assert_eq!(sniff_format(&jpeg, w, h, &FourCC::YUYV), FourCC::MJPEG);
+        assert_eq!(sniff_format(&jpeg, w, h, &FourCC::MJPEG), FourCC::MJPEG);
+
+        // Sizes matching nothing (padding, truncation) keep the declared format
+        assert_eq!(sniff_format(&[0u8; 30], w, h, &FourCC::RGB8), FourCC::RGB8);
+    }
+
+    #[test]
+    fn test_to_rgb8_auto_converts_detected_format() {
+        // White NV12 declared as RGB8 would fail the size check
+        let mut nv12 = vec![WHITE.0; 4];
+        nv12.extend([128, 128]);
+        let rgb = to_rgb8_auto(&nv12, 2, 2, "RGB8").expect("NV12 should be detected");
+        assert_eq!(rgb, vec![255; 12]);
+    }
+
+    #[test]
+    fn test_fourcc_of_nokhwa_formats() {
+        assert_eq!(fourcc_of(FrameFormat::NV12), "NV12");
+        assert_eq!(fourcc_of(FrameFormat::RAWRGB), "RGB24");
+        assert_eq!(frame_format_of("MJPG"), Some(FrameFormat::MJPEG));
+        assert_eq!(
+            frame_format_of(fourcc_of(FrameFormat::RAWBGR)),
+            Some(FrameFormat::RAWBGR)
+        );
+        assert_eq!(frame_format_of("H264"), None);
+    }
+}
@@ -0,0 +1,216 @@
+//! Process-wide cache of enumerated devices and probed capabilities
+//!
+//! Listing cameras and probing their controls is slow (hundreds of
+//! milliseconds per device on V4L2 and Media Foundation), and settings
+//! screens ask for both every time they open. [`CameraSystem`] keeps the
+//! results here:
+//!
+//! - The device list is filled by the first
+//!   [`CameraSystem::list_cameras`] call and reused until invalidated.
+//! - Probed [`CameraCapabilities`] are stored per
+//!   [`stable_id`](CameraDeviceInfo::stable_id) the first time a device is
+//!   probed, so they survive renumbering when other cameras are replugged.
+//!
+//! Entries live until a [`DeviceMonitor`] reports the device connected,
+//! disconnected or modified, or until [`CameraSystem::refresh_devices`] is
+//! called. Without a running monitor, hot-plugged cameras therefore only
+//! appear after a refresh, and numeric ids may be stale; mapping between
+//! numeric and stable ids always enumerates again (see [`rescan`]).
+//!
+//! The cache is guarded by a mutex that is never held while enumerating or
+//! probing, so slow devices do not block readers. Every invalidation bumps
+//! a generation counter, and a result whose enumeration started before an
+//! invalidation is returned to its caller but not stored, so a scan racing
+//! an unplug cannot bring the removed device back.
+//!
+//! [`CameraSystem`]: super::CameraSystem
+//! [`CameraSystem::list_cameras`]: super::CameraSystem::list_cameras
+//! [`CameraSystem::refresh_devices`]: super::CameraSystem::refresh_devices
+//! [`DeviceMonitor`]: super::DeviceMonitor
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use crate::errors::CameraError;
+use crate::types::{CameraCapabilities, CameraDeviceInfo};
+
+#[derive(Default)]
+struct DeviceCache {
+    /// Bumped by every invalidation
+    generation: u64,
+    devices: Option<Vec<CameraDeviceInfo>>,
+    /// Probed capabilities by stable id
+    capabilities: HashMap<String, CameraCapabilities>,
+}
+
+// Shared by every CameraSystem call and device monitor in the process
+static DEVICE_CACHE: LazyLock<Mutex<DeviceCache>> =
+    LazyLock::new(|| Mutex::new(DeviceCache::default()));
+
+fn lock() -> std::sync::MutexGuard<'static, DeviceCache> {
+    // The cache holds no invariants a panic could break
+    DEVICE_CACHE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Cached device list, or the result of `scan` stored for next time
+///
+/// # Errors
+/// Returns the error of `scan`; failures are not cached.
+pub(crate) fn devices_or_scan<F>(scan: F) -> Result<Vec<CameraDeviceInfo>, CameraError>
+where
+    F: FnOnce() -> Result<Vec<CameraDeviceInfo>, CameraError>,
+{
+    let generation = {
+        let cache = lock();
+        if let Some(devices) = &cache.devices {
+            return Ok(devices.clone());
+        }
+        cache.generation
+    };
+
+    let devices = scan()?;
+    let mut cache = lock();
+    if cache.generation == generation {
+        cache.devices = Some(devices.clone());
+    }
+    Ok(devices)
+}
+
+/// Result of `scan`, which also replaces the cached device list
+///
+/// Never answered from the cache, for lookups where a stale list would
+/// pick the wrong device.
+///
+/// # Errors
+/// Returns the error of `scan`; failures leave the cache alone.
+pub(crate) fn rescan<F>(scan: F) -> Result<Vec<CameraDeviceInfo>, CameraError>
+where
+    F: FnOnce() -> Result<Vec<CameraDeviceInfo>, CameraError>,
+{
+    let generation = lock().generation;
+    let devices = scan()?;
+    let mut cache = lock();
+    if cache.generation == generation {
+        // Scans that started earlier must not replace this one
+        cache.generation += 1;
+        cache.devices = Some(devices.clone());
+    }
+    Ok(devices)
+}
+
+/// Cached capabilities of the device with `stable_id`, or the result of
+/// `probe` stored for next time
+///
+/// # Errors
+/// Returns the error of `probe`; failures are not cached.
+pub(crate) fn capabilities_or_probe<F>(
+    stable_id: &str,
+    probe: F,
+) -> Result<CameraCapabilities, CameraError>
+where
+    F: FnOnce() -> Result<CameraCapabilities, CameraError>,
+{
+    let generation = {
+        let cache = lock();
+        if let Some(capabilities) = cache.capabilities.get(stable_id) {
+            return Ok(capabilities.clone());
+        }
+        cache.generation
+    };
+
+    let capabilities = probe()?;
+    let mut cache = lock();
+    if cache.generation == generation {
+        cache
+            .capabilities
+            .insert(stable_id.to_string(), capabilities.clone());
+    }
+    Ok(capabilities)
+}
+
+/// Forget the device list and the capabilities of the device with
+/// `stable_id`, after it was connected, disconnected or changed
+pub(crate) fn invalidate_device(stable_id: &str) {
+    let mut cache = lock();
+    cache.generation += 1;
+    cache.devices = None;
+    cache.capabilities.remove(stable_id);
+}
+
+/// Forget everything
+pub(crate) fn clear() {
+    let mut cache = lock();
+    cache.generation += 1;
+    cache.devices = None;
+    cache.capabilities.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_capabilities_cached_until_invalidated() {
+        let probes = Cell::new(0);
+        let probe = || {
+            probes.set(probes.get() + 1);
+            Ok(CameraCapabilities::default())
+        };
+
+        let id = "usb-cache-test-1";
+        capabilities_or_probe(id, probe).unwrap();
+        capabilities_or_probe(id, probe).unwrap();
+        assert_eq!(probes.get(), 1);
+
+        // Other devices' events leave the entry alone
+        invalidate_device("usb-cache-test-other");
+        capabilities_or_probe(id, probe).unwrap();
+        assert_eq!(probes.get(), 1);
+
+        invalidate_device(id);
+        capabilities_or_probe(id, probe).unwrap();
+        assert_eq!(probes.get(), 2);
+
+        // Failures are not stored
+        assert!(capabilities_or_probe("usb-cache-test-2", || Err(
+            CameraError::InitializationError("gone".to_string())
+        ))
+        .is_err());
+        capabilities_or_probe("usb-cache-test-2", probe).unwrap();
+        assert_eq!(probes.get(), 3);
+    }
+
+    #[test]
+    fn test_rescan_ignores_cached_devices() {
+        devices_or_scan(|| Ok(Vec::new())).unwrap();
+
+        let scans = Cell::new(0);
+        rescan(|| {
+            scans.set(scans.get() + 1);
+            Ok(Vec::new())
+        })
+        .unwrap();
+        assert_eq!(scans.get(), 1);
+    }
+
+    #[test]
+    fn test_probe_racing_invalidation_is_not_stored() {
+        let id = "usb-cache-test-race";
+        capabilities_or_probe(id, || {
+            invalidate_device(id);
+            Ok(CameraCapabilities::default())
+        })
+        .unwrap();
+
+        let probes = Cell::new(0);
+        capabilities_or_probe(id, || {
+            probes.set(probes.get() + 1);
+            Ok(CameraCapabilities::default())
+        })
+        .unwrap();
+        assert_eq!(probes.get(), 1);
+    }
+}
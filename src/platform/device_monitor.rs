@@ -13,11 +13,11 @@ use tokio::sync::{mpsc, RwLock};
 /// Device event types
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DeviceEvent {
-    /// A new camera device was connected (Device ID).
+    /// A new camera device was connected (stable device ID).
     Connected(String),
-    /// A camera device was disconnected (Device ID).
+    /// A camera device was disconnected (stable device ID).
     Disconnected(String),
-    /// A camera device's settings or availability changed (Device ID).
+    /// A camera device's settings or availability changed (stable device ID).
     Modified(String),
 }
 
@@ -122,12 +122,13 @@ impl DeviceMonitor {
     async fn update_active_devices(&self, new_devices: Vec<CameraDeviceInfo>) {
         let mut active = self.active_devices.write().await;
         let old_ids: Vec<String> = active.keys().cloned().collect();
-        let new_ids: Vec<String> = new_devices.iter().map(|d| d.id.clone()).collect();
+        let new_ids: Vec<String> = new_devices.iter().map(|d| d.stable_id.clone()).collect();
 
         // Detect disconnections
         for old_id in &old_ids {
             if !new_ids.contains(old_id) {
                 log::info!("Device disconnected: {old_id}");
+                super::device_cache::invalidate_device(old_id);
                 let _ = self
                     .event_sender
                     .send(DeviceEvent::Disconnected(old_id.clone()));
@@ -136,13 +137,14 @@ impl DeviceMonitor {
 
         // Detect connections
         for device in new_devices {
-            if !old_ids.contains(&device.id) {
-                log::info!("Device connected: {}", device.id);
+            if !old_ids.contains(&device.stable_id) {
+                log::info!("Device connected: {}", device.stable_id);
+                super::device_cache::invalidate_device(&device.stable_id);
                 let _ = self
                     .event_sender
-                    .send(DeviceEvent::Connected(device.id.clone()));
+                    .send(DeviceEvent::Connected(device.stable_id.clone()));
             }
-            active.insert(device.id.clone(), device);
+            active.insert(device.stable_id.clone(), device);
         }
 
         // Remove disconnected devices
@@ -172,22 +174,26 @@ impl DeviceMonitor {
                 if let Ok(devices) = DeviceMonitor::scan_devices_windows() {
                     let mut active = active_devices.write().await;
                     let old_ids: Vec<String> = active.keys().cloned().collect();
-                    let new_ids: Vec<String> = devices.iter().map(|d| d.id.clone()).collect();
+                    let new_ids: Vec<String> =
+                        devices.iter().map(|d| d.stable_id.clone()).collect();
 
                     // Check for changes
                     for old_id in &old_ids {
                         if !new_ids.contains(old_id) {
                             log::info!("Device disconnected: {old_id}");
+                            super::device_cache::invalidate_device(old_id);
                             let _ = event_sender.send(DeviceEvent::Disconnected(old_id.clone()));
                         }
                     }
 
                     for device in devices {
-                        if !old_ids.contains(&device.id) {
-                            log::info!("Device connected: {}", device.id);
-                            let _ = event_sender.send(DeviceEvent::Connected(device.id.clone()));
+                        if !old_ids.contains(&device.stable_id) {
+                            log::info!("Device connected: {}", device.stable_id);
+                            super::device_cache::invalidate_device(&device.stable_id);
+                            let _ =
+                                event_sender.send(DeviceEvent::Connected(device.stable_id.clone()));
                         }
-                        active.insert(device.id.clone(), device);
+                        active.insert(device.stable_id.clone(), device);
                     }
 
                     active.retain(|id, _| new_ids.contains(id));
@@ -229,21 +235,25 @@ impl DeviceMonitor {
                 if let Ok(devices) = DeviceMonitor::scan_devices_macos() {
                     let mut active = active_devices.write().await;
                     let old_ids: Vec<String> = active.keys().cloned().collect();
-                    let new_ids: Vec<String> = devices.iter().map(|d| d.id.clone()).collect();
+                    let new_ids: Vec<String> =
+                        devices.iter().map(|d| d.stable_id.clone()).collect();
 
                     for old_id in &old_ids {
                         if !new_ids.contains(old_id) {
                             log::info!("Device disconnected: {old_id}");
+                            super::device_cache::invalidate_device(old_id);
                             let _ = event_sender.send(DeviceEvent::Disconnected(old_id.clone()));
                         }
                     }
 
                     for device in devices {
-                        if !old_ids.contains(&device.id) {
-                            log::info!("Device connected: {}", device.id);
-                            let _ = event_sender.send(DeviceEvent::Connected(device.id.clone()));
+                        if !old_ids.contains(&device.stable_id) {
+                            log::info!("Device connected: {}", device.stable_id);
+                            super::device_cache::invalidate_device(&device.stable_id);
+                            let _ =
+                                event_sender.send(DeviceEvent::Connected(device.stable_id.clone()));
                         }
-                        active.insert(device.id.clone(), device);
+                        active.insert(device.stable_id.clone(), device);
                     }
 
                     active.retain(|id, _| new_ids.contains(id));
@@ -283,21 +293,25 @@ impl DeviceMonitor {
                 if let Ok(devices) = DeviceMonitor::scan_devices_linux() {
                     let mut active = active_devices.write().await;
                     let old_ids: Vec<String> = active.keys().cloned().collect();
-                    let new_ids: Vec<String> = devices.iter().map(|d| d.id.clone()).collect();
+                    let new_ids: Vec<String> =
+                        devices.iter().map(|d| d.stable_id.clone()).collect();
 
                     for old_id in &old_ids {
                         if !new_ids.contains(old_id) {
                             log::info!("Device disconnected: {old_id}");
+                            super::device_cache::invalidate_device(old_id);
                             let _ = event_sender.send(DeviceEvent::Disconnected(old_id.clone()));
                         }
                     }
 
                     for device in devices {
-                        if !old_ids.contains(&device.id) {
-                            log::info!("Device connected: {}", device.id);
-                            let _ = event_sender.send(DeviceEvent::Connected(device.id.clone()));
+                        if !old_ids.contains(&device.stable_id) {
+                            log::info!("Device connected: {}", device.stable_id);
+                            super::device_cache::invalidate_device(&device.stable_id);
+                            let _ =
+                                event_sender.send(DeviceEvent::Connected(device.stable_id.clone()));
                         }
-                        active.insert(device.id.clone(), device);
+                        active.insert(device.stable_id.clone(), device);
                     }
 
                     active.retain(|id, _| new_ids.contains(id));
@@ -336,10 +350,15 @@ impl DeviceMonitor {
         Ok(cameras
             .into_iter()
             .map(|info| {
-                CameraDeviceInfo::new(
+                let device = CameraDeviceInfo::new(
                     format!("{}", info.index().as_index().unwrap_or(0)),
                     info.human_name().clone(),
-                )
+                );
+                if info.misc().is_empty() {
+                    device
+                } else {
+                    device.with_stable_id(info.misc())
+                }
             })
             .collect())
     }
@@ -363,10 +382,15 @@ impl DeviceMonitor {
         Ok(cameras
             .into_iter()
             .map(|info| {
-                CameraDeviceInfo::new(
+                let device = CameraDeviceInfo::new(
                     format!("{}", info.index().as_index().unwrap_or(0)),
                     info.human_name(),
-                )
+                );
+                if info.misc().is_empty() {
+                    device
+                } else {
+                    device.with_stable_id(info.misc())
+                }
             })
             .collect())
     }
@@ -390,10 +414,12 @@ impl DeviceMonitor {
         Ok(cameras
             .into_iter()
             .map(|info| {
-                CameraDeviceInfo::new(
-                    format!("{}", info.index().as_index().unwrap_or(0)),
-                    info.human_name(),
-                )
+                let index = info.index().as_index().unwrap_or(0);
+                let device = CameraDeviceInfo::new(format!("{index}"), info.human_name());
+                match super::linux::stable_device_id(index) {
+                    Some(stable_id) => device.with_stable_id(stable_id),
+                    None => device,
+                }
             })
             .collect())
     }
@@ -0,0 +1,140 @@
+//! Bounded frame buffer for consumers that poll
+//!
+//! A camera's frame callback pushes every frame into a [`FrameQueue`]; the
+//! consumer drains it at its own pace. When the consumer falls behind and the
+//! queue is full, the **oldest** frame is discarded and counted, so memory
+//! stays bounded at `capacity` frames and the consumer always catches up to
+//! recent frames rather than replaying a stale backlog.
+
+use std::collections::VecDeque;
+
+use crate::types::CameraFrame;
+
+/// Fixed-capacity FIFO of frames that drops the oldest frame on overflow
+#[derive(Debug)]
+pub struct FrameQueue {
+    frames: VecDeque<CameraFrame>,
+    capacity: usize,
+    dropped: u64,
+}
+
+impl FrameQueue {
+    /// Create an empty queue holding at most `capacity` frames (minimum 1)
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            frames: VecDeque::with_capacity(capacity),
+            capacity,
+            dropped: 0,
+        }
+    }
+
+    /// Append a frame, discarding the oldest one if the queue is full
+    ///
+    /// Returns `true` if a frame was dropped to make room.
+    pub fn push(&mut self, frame: CameraFrame) -> bool {
+        let dropped = self.frames.len() >= self.capacity;
+        if dropped {
+            self.frames.pop_front();
+            self.dropped += 1;
+        }
+        self.frames.push_back(frame);
+        dropped
+    }
+
+    /// Remove and return the oldest queued frame
+    pub fn pop(&mut self) -> Option<CameraFrame> {
+        self.frames.pop_front()
+    }
+
+    /// Remove up to `max` frames, oldest first
+    pub fn drain(&mut self, max: usize) -> Vec<CameraFrame> {
+        let count = max.min(self.frames.len());
+        self.frames.drain(..count).collect()
+    }
+
+    /// Number of frames waiting to be consumed
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether no frames are waiting
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Maximum number of frames held at once
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the capacity (minimum 1), dropping the oldest frames that no
+    /// longer fit
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.frames.len() > self.capacity {
+            self.frames.pop_front();
+            self.dropped += 1;
+        }
+    }
+
+    /// Total frames discarded because the queue was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(n: u8) -> CameraFrame {
+        CameraFrame::new(vec![n; 3], 1, 1, "queue-test".to_string())
+    }
+
+    #[test]
+    fn test_frame_queue_drops_oldest_when_full() {
+        let mut queue = FrameQueue::new(3);
+        for n in 0..5 {
+            queue.push(frame(n));
+        }
+
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.dropped(), 2);
+        let kept: Vec<u8> = queue.drain(usize::MAX).iter().map(|f| f.data[0]).collect();
+        assert_eq!(kept, [2, 3, 4]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_frame_queue_partial_drain_and_minimum_capacity() {
+        let mut queue = FrameQueue::new(4);
+        for n in 0..4 {
+            assert!(!queue.push(frame(n)));
+        }
+        assert_eq!(queue.drain(2).len(), 2);
+        assert_eq!(queue.pop().map(|f| f.data[0]), Some(2));
+        assert_eq!(queue.len(), 1);
+
+        let mut tiny = FrameQueue::new(0);
+        assert_eq!(tiny.capacity(), 1);
+        tiny.push(frame(0));
+        assert!(tiny.push(frame(1)));
+        assert_eq!(tiny.dropped(), 1);
+    }
+
+    #[test]
+    fn test_frame_queue_shrinking_drops_oldest() {
+        let mut queue = FrameQueue::new(4);
+        for n in 0..4 {
+            queue.push(frame(n));
+        }
+        queue.set_capacity(2);
+        assert_eq!(queue.dropped(), 2);
+        assert_eq!(queue.pop().map(|f| f.data[0]), Some(2));
+
+        queue.set_capacity(8);
+        assert_eq!(queue.capacity(), 8);
+        assert_eq!(queue.len(), 1);
+    }
+}
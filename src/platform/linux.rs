@@ -1,11 +1,16 @@
 use crate::constants::{
-    DEFAULT_FORMAT_TYPE, DEFAULT_FPS, DEFAULT_RESOLUTION_HEIGHT, DEFAULT_RESOLUTION_WIDTH,
-    FALLBACK_RESOLUTION_HEIGHT, FALLBACK_RESOLUTION_WIDTH, LINUX_VIDEO_DEVICE_PREFIX,
-    MIN_RESOLUTION_HEIGHT, MIN_RESOLUTION_WIDTH,
+    DEFAULT_DEPTH_UNITS, DEFAULT_FORMAT_TYPE, DEFAULT_FPS, DEFAULT_RESOLUTION_HEIGHT,
+    DEFAULT_RESOLUTION_WIDTH, DEVICE_BUSY_POLL_INTERVAL_MS, FALLBACK_RESOLUTION_HEIGHT,
+    FALLBACK_RESOLUTION_WIDTH, FORMAT_DEPTH16, FORMAT_RGB, LINUX_VIDEO_DEVICE_PREFIX,
+    MIN_RESOLUTION_HEIGHT, MIN_RESOLUTION_WIDTH, V4L2_EXPOSURE_UNIT_SECONDS,
 };
 use crate::errors::CameraError;
+use crate::platform::convert;
 use crate::platform::metrics::PerfTracker;
-use crate::types::{CameraDeviceInfo, CameraFormat, CameraFrame, CameraInitParams};
+use crate::types::{
+    CameraBackend, CameraDeviceInfo, CameraFormat, CameraFrame, CameraInitParams, OpenPolicy,
+    PixelFormatPreference, TimestampSource,
+};
 use nokhwa::{
     pixel_format::RgbFormat,
     query,
@@ -13,11 +18,16 @@ use nokhwa::{
     Camera,
 };
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 // Add proper imports for V4L2 format enumeration
+use v4l::io::traits::CaptureStream;
 use v4l::video::Capture;
 use v4l::Device;
 
+/// V4L2 fourcc of 16-bit depth nodes (note the trailing space).
+const V4L2_FOURCC_Z16: &[u8; 4] = b"Z16 ";
+
 /// Boxed frame callback invoked for each captured frame.
 type FrameCallback = Box<dyn Fn(CameraFrame) + Send + 'static>;
 
@@ -33,6 +43,7 @@ const V4L2_CID_FOCUS_AUTO: u32 = 0x009a_090c;
 const V4L2_CID_FOCUS_ABSOLUTE: u32 = 0x009a_090a;
 const V4L2_CID_EXPOSURE_AUTO: u32 = 0x009a_0901;
 const V4L2_CID_EXPOSURE_ABSOLUTE: u32 = 0x009a_0902;
+const V4L2_CID_CAMERA_SENSOR_ROTATION: u32 = 0x009a_0923;
 
 /// Convert a V4L2 discrete frame interval to frames-per-second.
 #[allow(clippy::cast_precision_loss)]
@@ -44,6 +55,91 @@ fn interval_to_fps(numerator: u32, denominator: u32) -> f32 {
     }
 }
 
+/// Persistent identifier for `/dev/video<index>`, read from sysfs
+///
+/// USB cameras are keyed by vendor and product id plus their serial number,
+/// or the USB port path when the device has no serial, so the key survives
+/// re-enumeration when cameras are plugged in a different order. Extra nodes
+/// of the same camera (e.g. UVC metadata) get their node index appended, as
+/// in the `/dev/v4l/by-id` links.
+pub(crate) fn stable_device_id(index: u32) -> Option<String> {
+    let node = format!("/sys/class/video4linux/video{index}");
+    let interface = std::fs::canonicalize(format!("{node}/device")).ok()?;
+    let usb_device = interface.parent()?;
+    let read = |name: &str| {
+        std::fs::read_to_string(usb_device.join(name))
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let vendor = read("idVendor")?;
+    let product = read("idProduct")?;
+    let instance =
+        read("serial").or_else(|| Some(usb_device.file_name()?.to_string_lossy().into_owned()))?;
+    let node_index = std::fs::read_to_string(format!("{node}/index"))
+        .ok()
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .unwrap_or(0);
+
+    if node_index == 0 {
+        Some(format!("usb-{vendor}:{product}-{instance}"))
+    } else {
+        Some(format!(
+            "usb-{vendor}:{product}-{instance}-index{node_index}"
+        ))
+    }
+}
+
+/// USB device directory in sysfs that `/dev/video<index>` belongs to
+fn usb_device_dir(index: u32) -> Option<std::path::PathBuf> {
+    let interface =
+        std::fs::canonicalize(format!("/sys/class/video4linux/video{index}/device")).ok()?;
+    interface.parent().map(std::path::Path::to_path_buf)
+}
+
+/// Indices of the `/dev/videoN` nodes belonging to the same physical device
+/// as `/dev/video<index>`, in ascending order and including `index` itself
+///
+/// Cameras with several sensors (e.g. color, depth and IR on an Intel
+/// RealSense) expose one node per stream, all under one USB device.
+pub fn sibling_nodes(index: u32) -> Vec<u32> {
+    let Some(device_dir) = usb_device_dir(index) else {
+        return vec![index];
+    };
+    let mut nodes: Vec<u32> = std::fs::read_dir("/sys/class/video4linux")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("video")?
+                .parse()
+                .ok()
+        })
+        .filter(|&node| usb_device_dir(node).as_ref() == Some(&device_dir))
+        .collect();
+    nodes.sort_unstable();
+    nodes
+}
+
+/// Pixel formats advertised by `/dev/video<index>`, empty if it cannot be opened
+pub fn node_formats(index: u32) -> Vec<crate::types::FourCC> {
+    let Ok(dev) = Device::with_path(format!("{LINUX_VIDEO_DEVICE_PREFIX}{index}")) else {
+        return Vec::new();
+    };
+    dev.enum_formats()
+        .map(|formats| {
+            formats
+                .iter()
+                .filter_map(|desc| std::str::from_utf8(&desc.fourcc.repr).ok()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// List available cameras on Linux using both nokhwa for device discovery and v4l for detailed format enumeration.
 ///
 /// # Errors
@@ -64,6 +160,9 @@ pub fn list_cameras() -> Result<Vec<CameraDeviceInfo>, CameraError> {
         let mut formats = Vec::new();
         let device_index = camera_info.index().as_index().unwrap_or(0);
         let path = format!("{LINUX_VIDEO_DEVICE_PREFIX}{device_index}");
+        if let Some(stable_id) = stable_device_id(device_index) {
+            device = device.with_stable_id(stable_id);
+        }
 
         if let Ok(dev) = Device::with_path(&path) {
             if let Ok(format_iter) = dev.enum_formats() {
@@ -96,6 +195,7 @@ pub fn list_cameras() -> Result<Vec<CameraDeviceInfo>, CameraError> {
                                             b"YUYV" => "YUYV",
                                             b"MJPG" => "MJPEG",
                                             b"RGB3" => "RGB",
+                                            V4L2_FOURCC_Z16 => FORMAT_DEPTH16,
                                             other => {
                                                 std::str::from_utf8(other).unwrap_or("UNKNOWN")
                                             }
@@ -145,38 +245,99 @@ pub fn list_cameras() -> Result<Vec<CameraDeviceInfo>, CameraError> {
 
 /// Initialize camera on Linux with V4L2 backend.
 ///
+/// If the device is held by another process (`EBUSY`), `params.open_policy`
+/// decides whether to fail immediately or poll until it frees up.
+///
 /// # Errors
 /// Returns [`CameraError::InitializationError`] if the device ID is invalid or the
-/// camera cannot be opened.
+/// camera cannot be opened, or [`CameraError::DeviceBusy`] if the device stays
+/// busy for longer than the open policy allows.
 pub fn initialize_camera(params: CameraInitParams) -> Result<LinuxCamera, CameraError> {
     let device_index = params
         .device_id
         .parse::<u32>()
         .map_err(|_| CameraError::InitializationError("Invalid device ID".to_string()))?;
 
-    // Simple format request for V4L2
-    let requested_format = RequestedFormat::new::<RgbFormat>(RequestedFormatType::None);
+    let mut camera = open_with_policy(device_index, params.open_policy, params.backend)?;
+    let backend = super::backend_of(&camera);
+    let format =
+        match super::renegotiate_format(&mut camera, &params.format, false, params.pixel_format) {
+            Ok(negotiated) => negotiated,
+            Err(e) => {
+                log::warn!(
+                    "Keeping the driver's default mode for {}: {e}",
+                    params.device_id
+                );
+                params.format
+            }
+        };
 
-    let camera = Camera::new(
-        nokhwa::utils::CameraIndex::Index(device_index),
-        requested_format,
-    )
-    .map_err(|e| CameraError::InitializationError(format!("Failed to initialize camera: {e}")))?;
+    crate::logging::log_event(
+        crate::logging::LogLevel::Info,
+        module_path!(),
+        Some(&params.device_id),
+        format!("Format negotiated: {}", camera.camera_format()),
+    );
+
+    let perf = PerfTracker::with_target_fps(format.fps);
 
     Ok(LinuxCamera {
         camera: Arc::new(Mutex::new(camera)),
         device_id: params.device_id,
-        format: params.format,
+        format,
+        pixel_format: params.pixel_format,
+        backend,
+        timestamp_source: params.timestamp_source,
         callback: Arc::new(Mutex::new(None)),
-        perf: Arc::new(Mutex::new(PerfTracker::new())),
+        perf: Arc::new(Mutex::new(perf)),
     })
 }
 
+/// Open the nokhwa camera, polling while the device is busy if the policy allows.
+fn open_with_policy(
+    device_index: u32,
+    policy: OpenPolicy,
+    backend: Option<CameraBackend>,
+) -> Result<Camera, CameraError> {
+    let deadline = match policy {
+        OpenPolicy::FailFast => None,
+        OpenPolicy::RetryFor(timeout) => Some(Instant::now() + timeout),
+    };
+
+    loop {
+        // Simple format request for V4L2
+        let requested_format = RequestedFormat::new::<RgbFormat>(RequestedFormatType::None);
+
+        match super::open_nokhwa(device_index, requested_format, backend) {
+            Ok(camera) => return Ok(camera),
+            Err(e) if utils::is_busy_error(&e.to_string()) => match deadline {
+                Some(deadline) if Instant::now() < deadline => {
+                    log::debug!("{LINUX_VIDEO_DEVICE_PREFIX}{device_index} is busy, retrying");
+                    std::thread::sleep(Duration::from_millis(DEVICE_BUSY_POLL_INTERVAL_MS));
+                }
+                _ => {
+                    return Err(CameraError::DeviceBusy(format!(
+                    "{LINUX_VIDEO_DEVICE_PREFIX}{device_index} is in use by another process: {e}"
+                )))
+                }
+            },
+            Err(e) => {
+                return Err(CameraError::InitializationError(format!(
+                    "Failed to initialize camera: {e}"
+                )))
+            }
+        }
+    }
+}
+
 /// Linux-specific camera wrapper
 pub struct LinuxCamera {
     camera: Arc<Mutex<Camera>>,
     device_id: String,
     format: CameraFormat,
+    pixel_format: PixelFormatPreference,
+    backend: CameraBackend,
+    timestamp_source: TimestampSource,
     callback: Arc<Mutex<Option<FrameCallback>>>,
     /// Real performance tracker, updated on every capture.
     perf: Arc<Mutex<PerfTracker>>,
@@ -207,17 +368,27 @@ impl LinuxCamera {
                 return Err(e);
             }
         };
+        let dequeued_at = self.dequeue_timestamp();
         let latency_ms = start.elapsed().as_secs_f32() * 1000.0;
 
         let process_start = std::time::Instant::now();
-        let camera_frame = CameraFrame::new(
-            frame.buffer_bytes().to_vec(),
-            frame.resolution().width_x,
-            frame.resolution().height_y,
-            self.device_id.clone(),
-        );
+        let width = frame.resolution().width_x;
+        let height = frame.resolution().height_y;
+        let source = convert::fourcc_of(frame.source_frame_format());
+        let rgb = match convert::to_rgb8_auto(frame.buffer(), width, height, source) {
+            Ok(rgb) => rgb,
+            Err(e) => {
+                if let Ok(mut perf) = self.perf.lock() {
+                    perf.record_drop();
+                }
+                return Err(e);
+            }
+        };
 
-        let camera_frame = camera_frame.with_format(format!("{:?}", self.format));
+        let mut camera_frame = CameraFrame::new(rgb, width, height, self.device_id.clone())
+            .with_format(FORMAT_RGB.to_string());
+        camera_frame.metadata.source_format = Some(source.to_string());
+        camera_frame.metadata.hardware_timestamp = dequeued_at;
 
         // Call callback if set
         if let Ok(guard) = self.callback.lock() {
@@ -232,10 +403,10 @@ impl LinuxCamera {
                 latency_ms,
                 processing_ms,
                 Some((
-                    frame.buffer_bytes().to_vec(),
+                    camera_frame.data.clone(),
                     camera_frame.width,
                     camera_frame.height,
-                    format!("{:?}", self.format),
+                    camera_frame.format.clone(),
                 )),
             );
         }
@@ -243,6 +414,63 @@ impl LinuxCamera {
         Ok(camera_frame)
     }
 
+    /// Capture a frame exactly as delivered by the driver, without converting
+    /// it to RGB. The frame's `format` carries the source fourcc (e.g. `MJPEG`),
+    /// so compressed frames can be recorded without re-encoding.
+    ///
+    /// # Errors
+    /// Returns [`CameraError::CaptureError`] if the camera mutex is poisoned or the
+    /// underlying capture fails.
+    pub fn capture_frame_raw(&self) -> Result<CameraFrame, CameraError> {
+        let mut camera = self
+            .camera
+            .lock()
+            .map_err(|_| CameraError::CaptureError("Failed to lock camera".to_string()))?;
+
+        let start = std::time::Instant::now();
+        let frame = match camera.frame() {
+            Ok(f) => f,
+            Err(e) => {
+                if let Ok(mut perf) = self.perf.lock() {
+                    perf.record_drop();
+                }
+                return Err(CameraError::CaptureError(format!(
+                    "Failed to capture frame: {e}"
+                )));
+            }
+        };
+        let dequeued_at = self.dequeue_timestamp();
+        let latency_ms = start.elapsed().as_secs_f32() * 1000.0;
+
+        let width = frame.resolution().width_x;
+        let height = frame.resolution().height_y;
+        let source = convert::fourcc_of(frame.source_frame_format());
+        let mut camera_frame = CameraFrame::new(
+            frame.buffer().to_vec(),
+            width,
+            height,
+            self.device_id.clone(),
+        )
+        .with_format(source.to_string());
+        camera_frame.metadata.hardware_timestamp = dequeued_at;
+
+        if let Ok(mut perf) = self.perf.lock() {
+            perf.record_capture(latency_ms, 0.0, None);
+        }
+
+        Ok(camera_frame)
+    }
+
+    /// Capture API the camera was opened with
+    pub fn backend(&self) -> CameraBackend {
+        self.backend
+    }
+
+    /// [`super::dequeue_timestamp`] if driver timestamps were requested
+    fn dequeue_timestamp(&self) -> Option<f64> {
+        (self.timestamp_source == TimestampSource::DriverHardware).then(super::dequeue_timestamp)
+    }
+
     /// Get current format
     pub fn get_format(&self) -> &CameraFormat {
         &self.format
@@ -276,6 +504,38 @@ impl LinuxCamera {
         Ok(())
     }
 
+    /// Switch the open device to the closest supported mode to `format`, or
+    /// to exactly `format` when `exact` is set. Returns the mode selected.
+    ///
+    /// # Errors
+    /// Returns [`CameraError::InitializationError`] if the camera mutex is poisoned,
+    /// the driver rejects the format, or the stream cannot be restarted.
+    pub fn reconfigure(
+        &mut self,
+        format: &CameraFormat,
+        exact: bool,
+    ) -> Result<CameraFormat, CameraError> {
+        let mut camera = self
+            .camera
+            .lock()
+            .map_err(|_| CameraError::InitializationError("Failed to lock camera".to_string()))?;
+
+        let negotiated = super::renegotiate_format(&mut camera, format, exact, self.pixel_format)?;
+        drop(camera);
+
+        crate::logging::log_event(
+            crate::logging::LogLevel::Info,
+            module_path!(),
+            Some(&self.device_id),
+            format!(
+                "Format renegotiated: {}x{} @ {}fps {}",
+                negotiated.width, negotiated.height, negotiated.fps, negotiated.format_type
+            ),
+        );
+        self.format = negotiated.clone();
+        Ok(negotiated)
+    }
+
     /// Stop camera stream.
     ///
     /// # Errors
@@ -334,6 +594,7 @@ impl LinuxCamera {
                                         b"YUYV" => "YUYV",
                                         b"MJPG" => "MJPEG",
                                         b"RGB3" => "RGB",
+                                        V4L2_FOURCC_Z16 => FORMAT_DEPTH16,
                                         other => std::str::from_utf8(other).unwrap_or("UNKNOWN"),
                                     }
                                     .to_string();
@@ -402,6 +663,18 @@ impl LinuxCamera {
         Ok(())
     }
 
+    /// Sensor mounting rotation in degrees counter-clockwise, from
+    /// `V4L2_CID_CAMERA_SENSOR_ROTATION`; `None` if the driver does not
+    /// expose it (most USB webcams).
+    pub fn sensor_rotation(&self) -> Option<u16> {
+        let device_index = self.device_id.parse::<usize>().unwrap_or(0);
+        let dev = Device::with_path(format!("/dev/video{device_index}")).ok()?;
+        match dev.control(V4L2_CID_CAMERA_SENSOR_ROTATION).ok()?.value {
+            v4l::control::Value::Integer(degrees) => u16::try_from(degrees).ok(),
+            _ => None,
+        }
+    }
+
     /// Get camera controls.
     ///
     /// # Errors
@@ -463,11 +736,18 @@ impl LinuxCamera {
             _ => None,
         }); // 1 is manual usually
 
+        // Absolute exposure is in 100 µs units, reported in seconds
+        #[allow(clippy::cast_precision_loss)]
+        let exposure_time = get_val(V4L2_CID_EXPOSURE_ABSOLUTE).and_then(|v| match v {
+            v4l::control::Value::Integer(units) => Some(units as f32 * V4L2_EXPOSURE_UNIT_SECONDS),
+            _ => None,
+        });
+
         Ok(crate::types::CameraControls {
             auto_focus,
             focus_distance: get_norm(V4L2_CID_FOCUS_ABSOLUTE),
             auto_exposure, // Boolean
-            exposure_time: get_norm(V4L2_CID_EXPOSURE_ABSOLUTE),
+            exposure_time,
             iso_sensitivity: None, // V4L2 ISO handling is complex/device specific
             white_balance: Some(crate::types::WhiteBalance::Auto), // Simplified
             aperture: None,
@@ -535,12 +815,6 @@ impl LinuxCamera {
             };
         }
 
-        try_norm!(controls.brightness, V4L2_CID_BRIGHTNESS, "brightness");
-        try_norm!(controls.contrast, V4L2_CID_CONTRAST, "contrast");
-        try_norm!(controls.saturation, V4L2_CID_SATURATION, "saturation");
-        try_norm!(controls.sharpness, V4L2_CID_SHARPNESS, "sharpness");
-        try_norm!(controls.zoom, V4L2_CID_ZOOM_ABSOLUTE, "zoom");
-
         if let Some(af) = controls.auto_focus {
             let ctrl = v4l::control::Control {
                 id: V4L2_CID_FOCUS_AUTO,
@@ -582,7 +856,29 @@ impl LinuxCamera {
 
         if let Some(et) = controls.exposure_time {
             if controls.auto_exposure != Some(true) {
-                if try_set_norm(V4L2_CID_EXPOSURE_ABSOLUTE, et) {
+                // Seconds to 100 µs units, within the driver's limits
+                let units = dev
+                    .query_controls()
+                    .ok()
+                    .and_then(|descs| {
+                        descs
+                            .into_iter()
+                            .find(|d| d.id == V4L2_CID_EXPOSURE_ABSOLUTE)
+                    })
+                    .map(|desc| {
+                        #[allow(clippy::cast_possible_truncation)]
+                        let units = (et / V4L2_EXPOSURE_UNIT_SECONDS).round() as i64;
+                        units.clamp(desc.minimum, desc.maximum)
+                    });
+                let set = units.is_some_and(|units| {
+                    dev.set_control(v4l::control::Control {
+                        id: V4L2_CID_EXPOSURE_ABSOLUTE,
+                        value: v4l::control::Value::Integer(units),
+                    })
+                    .map_err(|e| log::warn!("V4L2 set exposure_time failed: {e}"))
+                    .is_ok()
+                });
+                if set {
                     applied.push("exposure_time".to_string());
                 } else {
                     rejected.push("exposure_time".to_string());
@@ -590,6 +886,13 @@ impl LinuxCamera {
             }
         }
 
+        // Image adjustments last, like the other backends, once exposure is set
+        try_norm!(controls.brightness, V4L2_CID_BRIGHTNESS, "brightness");
+        try_norm!(controls.contrast, V4L2_CID_CONTRAST, "contrast");
+        try_norm!(controls.saturation, V4L2_CID_SATURATION, "saturation");
+        try_norm!(controls.sharpness, V4L2_CID_SHARPNESS, "sharpness");
+        try_norm!(controls.zoom, V4L2_CID_ZOOM_ABSOLUTE, "zoom");
+
         Ok(crate::types::ControlApplicationResult { applied, rejected })
     }
 
@@ -608,8 +911,18 @@ impl LinuxCamera {
         // Check controls for capabilities
         if let Ok(controls) = dev.query_controls() {
             caps.supports.manual_focus = controls.iter().any(|c| c.id == V4L2_CID_FOCUS_ABSOLUTE);
-            caps.supports.manual_exposure =
-                controls.iter().any(|c| c.id == V4L2_CID_EXPOSURE_ABSOLUTE);
+            if let Some(exposure) = controls.iter().find(|c| c.id == V4L2_CID_EXPOSURE_ABSOLUTE) {
+                caps.supports.manual_exposure = true;
+                #[allow(clippy::cast_precision_loss)]
+                let range = (
+                    exposure.minimum as f32 * V4L2_EXPOSURE_UNIT_SECONDS,
+                    exposure.maximum as f32 * V4L2_EXPOSURE_UNIT_SECONDS,
+                );
+                caps.exposure_range = Some(range);
+            } else {
+                caps.supports.manual_exposure = false;
+            }
+            caps.controls = control_ranges(&controls);
             caps.supports.zoom = controls.iter().any(|c| c.id == V4L2_CID_ZOOM_ABSOLUTE);
             caps.supports.auto_focus = controls.iter().any(|c| c.id == V4L2_CID_FOCUS_AUTO);
             caps.supports.auto_exposure = controls.iter().any(|c| c.id == V4L2_CID_EXPOSURE_AUTO);
@@ -664,6 +977,107 @@ impl LinuxCamera {
     }
 }
 
+/// Ranges of the controls `apply_controls` sets, from the V4L2 control
+/// descriptions: seconds for exposure, 0-1 for the normalized controls
+fn control_ranges(descriptions: &[v4l::control::Description]) -> Vec<crate::types::ControlRange> {
+    let normalized = [
+        (V4L2_CID_FOCUS_ABSOLUTE, "focus_distance"),
+        (V4L2_CID_ZOOM_ABSOLUTE, "zoom"),
+        (V4L2_CID_BRIGHTNESS, "brightness"),
+        (V4L2_CID_CONTRAST, "contrast"),
+        (V4L2_CID_SATURATION, "saturation"),
+        (V4L2_CID_SHARPNESS, "sharpness"),
+    ];
+    descriptions
+        .iter()
+        .filter_map(|desc| {
+            let raw_range = (desc.minimum, desc.maximum);
+            let step = i64::try_from(desc.step).unwrap_or(1);
+            if desc.id == V4L2_CID_EXPOSURE_ABSOLUTE {
+                #[allow(clippy::cast_precision_loss)]
+                let span = (
+                    desc.minimum as f32 * V4L2_EXPOSURE_UNIT_SECONDS,
+                    desc.maximum as f32 * V4L2_EXPOSURE_UNIT_SECONDS,
+                );
+                return Some(crate::types::ControlRange::linear(
+                    "exposure_time",
+                    span,
+                    raw_range,
+                    step,
+                    desc.default,
+                ));
+            }
+            let (_, control) = normalized.iter().find(|(id, _)| *id == desc.id)?;
+            Some(crate::types::ControlRange::linear(
+                control,
+                (0.0, 1.0),
+                raw_range,
+                step,
+                desc.default,
+            ))
+        })
+        .collect()
+}
+
+/// Capture one Z16 depth frame straight from a V4L2 depth node.
+///
+/// nokhwa has no depth formats, so this bypasses it and streams from the
+/// device with memory-mapped buffers. The driver may adjust `width` and
+/// `height`; the returned frame carries the negotiated size, format `Z16`,
+/// and `DEFAULT_DEPTH_UNITS` since V4L2 does not report a depth scale. Its
+/// `hardware_timestamp` is the driver's buffer timestamp.
+///
+/// # Errors
+/// Returns [`CameraError::InitializationError`] if the node cannot be opened
+/// or configured, [`CameraError::UnsupportedFormat`] if it does not deliver
+/// Z16, or [`CameraError::CaptureError`] if streaming fails.
+pub fn capture_depth_frame(
+    device_index: u32,
+    width: u32,
+    height: u32,
+) -> Result<CameraFrame, CameraError> {
+    let path = format!("{LINUX_VIDEO_DEVICE_PREFIX}{device_index}");
+    let dev = Device::with_path(&path)
+        .map_err(|e| CameraError::InitializationError(format!("Failed to open {path}: {e}")))?;
+
+    let mut fmt = dev
+        .format()
+        .map_err(|e| CameraError::InitializationError(format!("Failed to read format: {e}")))?;
+    fmt.width = width;
+    fmt.height = height;
+    fmt.fourcc = v4l::FourCC::new(V4L2_FOURCC_Z16);
+    let fmt = dev
+        .set_format(&fmt)
+        .map_err(|e| CameraError::InitializationError(format!("Failed to set Z16 format: {e}")))?;
+    if &fmt.fourcc.repr != V4L2_FOURCC_Z16 {
+        return Err(CameraError::UnsupportedFormat(format!(
+            "{path} does not deliver Z16 depth (got {})",
+            fmt.fourcc
+        )));
+    }
+
+    let mut stream = v4l::io::mmap::Stream::with_buffers(&dev, v4l::buffer::Type::VideoCapture, 4)
+        .map_err(|e| CameraError::CaptureError(format!("Failed to start stream: {e}")))?;
+    let (buffer, meta) = stream
+        .next()
+        .map_err(|e| CameraError::CaptureError(format!("Failed to capture depth frame: {e}")))?;
+    #[allow(clippy::cast_precision_loss)] // seconds since boot stay far below 2^52
+    let driver_time = meta.timestamp.sec as f64 + meta.timestamp.usec as f64 / 1_000_000.0;
+    let used = (meta.bytesused as usize).min(buffer.len());
+
+    let mut frame = CameraFrame::new(
+        buffer[..used].to_vec(),
+        fmt.width,
+        fmt.height,
+        device_index.to_string(),
+    )
+    .with_format(FORMAT_DEPTH16.to_string());
+    frame.metadata.depth_units = Some(DEFAULT_DEPTH_UNITS);
+    frame.metadata.hardware_timestamp = Some(driver_time);
+    frame.validate()?;
+    Ok(frame)
+}
+
 // Ensure the camera is properly cleaned up
 impl Drop for LinuxCamera {
     fn drop(&mut self) {
@@ -686,6 +1100,37 @@ pub mod utils {
         std::path::Path::new("/dev/video0").exists()
     }
 
+    /// Whether an open error indicates the device is held by another process (`EBUSY`).
+    pub fn is_busy_error(message: &str) -> bool {
+        message.contains("os error 16") || message.to_lowercase().contains("busy")
+    }
+
+    /// Check whether another process has the given device node open.
+    ///
+    /// Scans `/proc/<pid>/fd` the way `lsof` does. Processes whose descriptors
+    /// we are not permitted to read are skipped, so a `false` result is only
+    /// authoritative when running with sufficient privileges.
+    pub fn is_device_in_use(device_path: &str) -> bool {
+        let target = std::path::Path::new(device_path);
+        let own_pid = std::process::id().to_string();
+
+        let Ok(procs) = std::fs::read_dir("/proc") else {
+            return false;
+        };
+
+        procs
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| name != own_pid && name.bytes().all(|b| b.is_ascii_digit()))
+            })
+            .filter_map(|entry| std::fs::read_dir(entry.path().join("fd")).ok())
+            .flat_map(|fds| fds.flatten())
+            .any(|fd| std::fs::read_link(fd.path()).is_ok_and(|link| link == target))
+    }
+
     /// List all V4L2 devices in /dev/video*.
     ///
     /// # Errors
@@ -1,10 +1,14 @@
 use crate::constants::{
     DEFAULT_FPS, DEFAULT_RESOLUTION_HEIGHT, DEFAULT_RESOLUTION_WIDTH, FALLBACK_RESOLUTION_HEIGHT,
-    FALLBACK_RESOLUTION_WIDTH, MIN_RESOLUTION_HEIGHT, MIN_RESOLUTION_WIDTH,
+    FALLBACK_RESOLUTION_WIDTH, FORMAT_RGB, MIN_RESOLUTION_HEIGHT, MIN_RESOLUTION_WIDTH,
 };
 use crate::errors::CameraError;
+use crate::platform::convert;
 use crate::platform::metrics::PerfTracker;
-use crate::types::{CameraDeviceInfo, CameraFormat, CameraFrame, CameraInitParams};
+use crate::types::{
+    CameraBackend, CameraDeviceInfo, CameraFormat, CameraFrame, CameraInitParams,
+    PixelFormatPreference, TimestampSource,
+};
 use nokhwa::{
     pixel_format::RgbFormat,
     query,
@@ -53,6 +57,12 @@ pub fn list_cameras() -> Result<Vec<CameraDeviceInfo>, CameraError> {
 
         device = device.with_description(camera_info.description().to_string());
 
+        // The AVFoundation unique id, stable across reconnects
+        let misc = camera_info.misc();
+        if !misc.is_empty() {
+            device = device.with_stable_id(misc);
+        }
+
         // Add common macOS camera formats
         let formats = vec![
             CameraFormat::new(
@@ -101,18 +111,30 @@ pub fn initialize_camera(params: CameraInitParams) -> Result<MacOSCamera, Camera
             fps,
         ),
     ));
-    let camera = Camera::new(
-        nokhwa::utils::CameraIndex::Index(device_index),
-        requested_format,
-    )
-    .map_err(|e| CameraError::InitializationError(format!("Failed to initialize camera: {e}")))?;
+    let camera =
+        super::open_nokhwa(device_index, requested_format, params.backend).map_err(|e| {
+            CameraError::InitializationError(format!("Failed to initialize camera: {e}"))
+        })?;
+
+    crate::logging::log_event(
+        crate::logging::LogLevel::Info,
+        module_path!(),
+        Some(&params.device_id),
+        format!("Format negotiated: {}", camera.camera_format()),
+    );
+
+    let perf = PerfTracker::with_target_fps(params.format.fps);
+    let backend = super::backend_of(&camera);
 
     Ok(MacOSCamera {
         camera: Arc::new(Mutex::new(camera)),
         device_id: params.device_id,
         format: params.format,
+        pixel_format: params.pixel_format,
+        backend,
+        timestamp_source: params.timestamp_source,
         callback: Arc::new(Mutex::new(None)),
-        perf: Arc::new(Mutex::new(PerfTracker::new())),
+        perf: Arc::new(Mutex::new(perf)),
     })
 }
 
@@ -121,6 +143,9 @@ pub struct MacOSCamera {
     camera: Arc<Mutex<Camera>>,
     device_id: String,
     format: CameraFormat,
+    pixel_format: PixelFormatPreference,
+    backend: CameraBackend,
+    timestamp_source: TimestampSource,
     callback: Arc<Mutex<Option<FrameCallback>>>,
     /// Real performance tracker, updated on every capture.
     perf: Arc<Mutex<PerfTracker>>,
@@ -142,10 +167,19 @@ trait AVCaptureDeviceExt {
     fn set_focus_mode(&self, mode: i64) -> Result<(), CameraError>;
     fn set_exposure_mode(&self, mode: i64) -> Result<(), CameraError>;
     fn set_lens_position(&self, position: f32) -> Result<(), CameraError>;
+    fn set_focus_point_of_interest(&self, x: f32, y: f32) -> bool;
     // Exposure duration is complex due to CMTime struct passing via msg_send!
     // We omit it for this iteration to ensure stability.
 }
 
+// Normalized point passed by value to AVFoundation
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct CGPoint {
+    x: f64,
+    y: f64,
+}
+
 // Wrapper struct for raw pointer to impl methods
 struct AVDeviceWrapper(*mut Object);
 
@@ -231,6 +265,21 @@ impl AVCaptureDeviceExt for AVDeviceWrapper {
             Ok(())
         }
     }
+
+    fn set_focus_point_of_interest(&self, x: f32, y: f32) -> bool {
+        let device = self.0;
+        unsafe {
+            let supported: bool = msg_send![device, isFocusPointOfInterestSupported];
+            if supported {
+                let point = CGPoint {
+                    x: f64::from(x),
+                    y: f64::from(y),
+                };
+                let _: () = msg_send![device, setFocusPointOfInterest: point];
+            }
+            supported
+        }
+    }
 }
 
 impl MacOSCamera {
@@ -258,17 +307,27 @@ impl MacOSCamera {
                 return Err(e);
             }
         };
+        let dequeued_at = self.dequeue_timestamp();
         let latency_ms = start.elapsed().as_secs_f32() * 1000.0;
 
         let process_start = std::time::Instant::now();
-        let camera_frame = CameraFrame::new(
-            frame.buffer_bytes().to_vec(),
-            frame.resolution().width_x,
-            frame.resolution().height_y,
-            self.device_id.clone(),
-        );
+        let width = frame.resolution().width_x;
+        let height = frame.resolution().height_y;
+        let source = convert::fourcc_of(frame.source_frame_format());
+        let rgb = match convert::to_rgb8_auto(frame.buffer(), width, height, source) {
+            Ok(rgb) => rgb,
+            Err(e) => {
+                if let Ok(mut perf) = self.perf.lock() {
+                    perf.record_drop();
+                }
+                return Err(e);
+            }
+        };
 
-        let camera_frame = camera_frame.with_format(format!("{:?}", self.format));
+        let mut camera_frame = CameraFrame::new(rgb, width, height, self.device_id.clone())
+            .with_format(FORMAT_RGB.to_string());
+        camera_frame.metadata.source_format = Some(source.to_string());
+        camera_frame.metadata.hardware_timestamp = dequeued_at;
 
         // Call callback if set
         if let Ok(guard) = self.callback.lock() {
@@ -283,10 +342,10 @@ impl MacOSCamera {
                 latency_ms,
                 processing_ms,
                 Some((
-                    frame.buffer_bytes().to_vec(),
+                    camera_frame.data.clone(),
                     camera_frame.width,
                     camera_frame.height,
-                    format!("{:?}", self.format),
+                    camera_frame.format.clone(),
                 )),
             );
         }
@@ -294,6 +353,63 @@ impl MacOSCamera {
         Ok(camera_frame)
     }
 
+    /// Capture a frame exactly as delivered by the driver, without converting
+    /// it to RGB. The frame's `format` carries the source fourcc (e.g. `MJPEG`),
+    /// so compressed frames can be recorded without re-encoding.
+    ///
+    /// # Errors
+    /// Returns [`CameraError::CaptureError`] if the camera mutex is poisoned or the
+    /// underlying capture fails.
+    pub fn capture_frame_raw(&self) -> Result<CameraFrame, CameraError> {
+        let mut camera = self
+            .camera
+            .lock()
+            .map_err(|_| CameraError::CaptureError("Failed to lock camera".to_string()))?;
+
+        let start = std::time::Instant::now();
+        let frame = match camera.frame() {
+            Ok(f) => f,
+            Err(e) => {
+                if let Ok(mut perf) = self.perf.lock() {
+                    perf.record_drop();
+                }
+                return Err(CameraError::CaptureError(format!(
+                    "Failed to capture frame: {e}"
+                )));
+            }
+        };
+        let dequeued_at = self.dequeue_timestamp();
+        let latency_ms = start.elapsed().as_secs_f32() * 1000.0;
+
+        let width = frame.resolution().width_x;
+        let height = frame.resolution().height_y;
+        let source = convert::fourcc_of(frame.source_frame_format());
+        let mut camera_frame = CameraFrame::new(
+            frame.buffer().to_vec(),
+            width,
+            height,
+            self.device_id.clone(),
+        )
+        .with_format(source.to_string());
+        camera_frame.metadata.hardware_timestamp = dequeued_at;
+
+        if let Ok(mut perf) = self.perf.lock() {
+            perf.record_capture(latency_ms, 0.0, None);
+        }
+
+        Ok(camera_frame)
+    }
+
+    /// Capture API the camera was opened with
+    pub fn backend(&self) -> CameraBackend {
+        self.backend
+    }
+
+    /// [`super::dequeue_timestamp`] if driver timestamps were requested
+    fn dequeue_timestamp(&self) -> Option<f64> {
+        (self.timestamp_source == TimestampSource::DriverHardware).then(super::dequeue_timestamp)
+    }
+
     /// Get current format
     pub fn get_format(&self) -> &CameraFormat {
         &self.format
@@ -327,6 +443,38 @@ impl MacOSCamera {
         Ok(())
     }
 
+    /// Switch the open device to the closest supported mode to `format`, or
+    /// to exactly `format` when `exact` is set. Returns the mode selected.
+    ///
+    /// # Errors
+    /// Returns [`CameraError::InitializationError`] if the camera mutex is poisoned,
+    /// the driver rejects the format, or the stream cannot be restarted.
+    pub fn reconfigure(
+        &mut self,
+        format: &CameraFormat,
+        exact: bool,
+    ) -> Result<CameraFormat, CameraError> {
+        let mut camera = self
+            .camera
+            .lock()
+            .map_err(|_| CameraError::InitializationError("Failed to lock camera".to_string()))?;
+
+        let negotiated = super::renegotiate_format(&mut camera, format, exact, self.pixel_format)?;
+        drop(camera);
+
+        crate::logging::log_event(
+            crate::logging::LogLevel::Info,
+            module_path!(),
+            Some(&self.device_id),
+            format!(
+                "Format renegotiated: {}x{} @ {}fps {}",
+                negotiated.width, negotiated.height, negotiated.fps, negotiated.format_type
+            ),
+        );
+        self.format = negotiated.clone();
+        Ok(negotiated)
+    }
+
     /// Stop camera stream.
     ///
     /// # Errors
@@ -385,6 +533,30 @@ impl MacOSCamera {
         }
     }
 
+    /// Focus on a normalized point using `focusPointOfInterest`.
+    ///
+    /// Returns `false` if the device does not support a focus point.
+    ///
+    /// # Errors
+    /// Returns [`CameraError::InitializationError`] if the device cannot be found or
+    /// locked for configuration.
+    pub fn set_focus_point(&self, x: f32, y: f32) -> Result<bool, CameraError> {
+        let Some(wrapper) = AVDeviceWrapper::new(&self.device_id) else {
+            return Err(CameraError::InitializationError(
+                "Device not found".to_string(),
+            ));
+        };
+
+        wrapper.lock_for_configuration()?;
+        let supported = wrapper.set_focus_point_of_interest(x, y);
+        if supported {
+            // The point takes effect with the next focus operation
+            wrapper.set_focus_mode(AV_CAPTURE_FOCUS_MODE_AUTO)?;
+        }
+        wrapper.unlock_for_configuration();
+        Ok(supported)
+    }
+
     /// Apply camera controls.
     ///
     /// # Errors
@@ -1,11 +1,15 @@
 use crate::constants::{
     CAPTURE_RECONNECT_WARMUP_DELAY_MS, CAPTURE_RECONNECT_WARMUP_FRAMES, CAPTURE_WARMUP_DELAY_MS,
-    CAPTURE_WARMUP_FRAMES, CONNECTION_BACKOFF_INITIAL_MS, CONNECTION_BACKOFF_MAX_MS,
+    CAPTURE_WARMUP_FRAMES,
 };
 use crate::errors::CameraError;
-use crate::platform::PlatformCamera;
-use crate::types::{CameraFormat, CameraFrame, CameraInitParams};
-use std::collections::HashMap;
+use crate::logging::{log_event, LogLevel};
+use crate::platform::{CameraSystem, PlatformCamera};
+use crate::types::{
+    CameraFormat, CameraFrame, CameraInitParams, CameraPerformanceMetrics, OpenPolicy,
+    RetryAttempt, RetryPolicy, RetryReport,
+};
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, LazyLock, Mutex as SyncMutex};
 use tokio::sync::RwLock;
 
@@ -14,10 +18,156 @@ type CameraRegistry = LazyLock<Arc<RwLock<HashMap<String, Arc<SyncMutex<Platform
 
 static CAMERA_REGISTRY: CameraRegistry = LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
 
+// Last metrics observed for cameras that have since been released
+type MetricsCache = LazyLock<Arc<RwLock<HashMap<String, CameraPerformanceMetrics>>>>;
+
+static LAST_KNOWN_METRICS: MetricsCache = LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
+
+// Stable ids used to open cameras, mapped to the registry key they resolved to
+type AliasMap = LazyLock<Arc<RwLock<HashMap<String, String>>>>;
+
+static STABLE_ID_ALIASES: AliasMap = LazyLock::new(|| Arc::new(RwLock::new(HashMap::new())));
+
+// Per-device locks held while a camera is opened, so each device is opened
+// once without holding the registry lock through a slow or retrying open
+static OPENING: LazyLock<SyncMutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
+    LazyLock::new(|| SyncMutex::new(HashMap::new()));
+
+/// A held [`OPENING`] lock; the last task using it removes the map entry
+struct OpeningLock {
+    device_id: String,
+    guard: Option<tokio::sync::OwnedMutexGuard<()>>,
+}
+
+impl OpeningLock {
+    /// Wait until no other task is opening `device_id`
+    async fn acquire(device_id: &str) -> Self {
+        let lock = OPENING
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .entry(device_id.to_string())
+            .or_default()
+            .clone();
+        Self {
+            device_id: device_id.to_string(),
+            guard: Some(lock.lock_owned().await),
+        }
+    }
+}
+
+impl Drop for OpeningLock {
+    fn drop(&mut self) {
+        let Some(guard) = self.guard.take() else {
+            return;
+        };
+        let lock = Arc::clone(tokio::sync::OwnedMutexGuard::mutex(&guard));
+        drop(guard);
+        let mut opening = OPENING
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        // Only the map and `lock` are left, so no task is waiting on it
+        if Arc::strong_count(&lock) == 2
+            && opening
+                .get(&self.device_id)
+                .is_some_and(|entry| Arc::ptr_eq(entry, &lock))
+        {
+            opening.remove(&self.device_id);
+        }
+    }
+}
+
+// Registry keys of open cameras whose frame delivery is paused
+static PAUSED_CAMERAS: LazyLock<SyncMutex<HashSet<String>>> =
+    LazyLock::new(|| SyncMutex::new(HashSet::new()));
+
+/// Registry key for a numeric or stable device id
+///
+/// Cameras are registered under their numeric id, so opening one by either
+/// id shares a single handle. Stable ids of open cameras are answered from
+/// the alias map instead of re-enumerating devices.
+async fn registry_key(device_id: &str) -> String {
+    if let Some(key) = STABLE_ID_ALIASES.read().await.get(device_id) {
+        return key.clone();
+    }
+    CameraSystem::resolve_device_id(device_id)
+}
+
+/// Drop any stable id aliases pointing at `key`
+async fn forget_aliases(key: &str) {
+    STABLE_ID_ALIASES
+        .write()
+        .await
+        .retain(|_, target| target != key);
+}
+
 /// Get existing camera without creating if it doesn't exist
 pub async fn get_existing_camera(device_id: &str) -> Option<Arc<SyncMutex<PlatformCamera>>> {
+    let key = registry_key(device_id).await;
     let registry = CAMERA_REGISTRY.read().await;
-    registry.get(device_id).cloned()
+    registry.get(&key).cloned()
+}
+
+/// Get the last performance metrics recorded for a released camera
+pub async fn get_last_known_metrics(device_id: &str) -> Option<CameraPerformanceMetrics> {
+    let key = registry_key(device_id).await;
+    LAST_KNOWN_METRICS.read().await.get(&key).cloned()
+}
+
+/// Stop delivering frames from an open camera without closing it
+///
+/// The device keeps streaming, so exposure and white balance stay settled
+/// and [`resume_camera`] takes effect on the next capture. While paused,
+/// [`PlatformCamera::capture_frame`] fails with a
+/// [`CameraError::StreamError`]. Returns `false` if it was already paused.
+///
+/// # Errors
+/// Returns a [`CameraError::InitializationError`] if no camera is open for
+/// `device_id`.
+pub async fn pause_camera(device_id: &str) -> Result<bool, CameraError> {
+    let key = registry_key(device_id).await;
+    if !CAMERA_REGISTRY.read().await.contains_key(&key) {
+        return Err(CameraError::InitializationError(format!(
+            "No active camera found with ID: {device_id}"
+        )));
+    }
+    let paused = PAUSED_CAMERAS
+        .lock()
+        .map_err(|_| CameraError::SystemError("Pause state mutex poisoned".to_string()))?
+        .insert(key);
+    log::info!("Camera {device_id} paused");
+    Ok(paused)
+}
+
+/// Deliver frames again after [`pause_camera`]
+///
+/// Returns `false` if the camera was not paused.
+///
+/// # Errors
+/// Returns a [`CameraError::InitializationError`] if no camera is open for
+/// `device_id`.
+pub async fn resume_camera(device_id: &str) -> Result<bool, CameraError> {
+    let key = registry_key(device_id).await;
+    if !CAMERA_REGISTRY.read().await.contains_key(&key) {
+        return Err(CameraError::InitializationError(format!(
+            "No active camera found with ID: {device_id}"
+        )));
+    }
+    let resumed = PAUSED_CAMERAS
+        .lock()
+        .map_err(|_| CameraError::SystemError("Pause state mutex poisoned".to_string()))?
+        .remove(&key);
+    log::info!("Camera {device_id} resumed");
+    Ok(resumed)
+}
+
+/// Whether the camera registered as `key` is paused
+///
+/// `key` is the id the camera reports through
+/// [`PlatformCamera::get_device_id`]; numeric ids work as well.
+pub fn is_camera_paused(key: &str) -> bool {
+    PAUSED_CAMERAS
+        .lock()
+        .is_ok_and(|paused| paused.contains(key))
 }
 
 /// Release a camera (stop and remove from registry)
@@ -28,19 +178,31 @@ pub async fn get_existing_camera(device_id: &str) -> Option<Arc<SyncMutex<Platfo
 pub async fn release_camera(device_id: &str) -> Result<String, CameraError> {
     log::info!("Releasing camera: {device_id}");
 
+    let key = registry_key(device_id).await;
+    forget_aliases(&key).await;
+    if let Ok(mut paused) = PAUSED_CAMERAS.lock() {
+        paused.remove(&key);
+    }
     let mut registry = CAMERA_REGISTRY.write().await;
 
-    if let Some(camera) = registry.remove(device_id) {
+    if let Some(camera) = registry.remove(&key) {
         let camera_clone = camera.clone();
         let device_id_clone = device_id.to_string();
-        tokio::task::spawn_blocking(move || {
-            if let Ok(mut camera_guard) = camera_clone.lock() {
-                let _ = camera_guard.stop_stream(); // Ignore errors on cleanup
-                log::info!("Camera {device_id_clone} released");
-            }
+        let last_metrics = tokio::task::spawn_blocking(move || {
+            let mut camera_guard = camera_clone.lock().ok()?;
+            let metrics = camera_guard.get_performance_metrics().ok();
+            let _ = camera_guard.stop_stream(); // Ignore errors on cleanup
+            log::info!("Camera {device_id_clone} released");
+            metrics
         })
         .await
-        .ok();
+        .ok()
+        .flatten();
+
+        super::session_metrics::end_session(&key);
+        if let Some(metrics) = last_metrics {
+            LAST_KNOWN_METRICS.write().await.insert(key, metrics);
+        }
         Ok(format!("Camera {device_id} released"))
     } else {
         let msg = format!("No active camera found with ID: {device_id}");
@@ -51,6 +213,9 @@ pub async fn release_camera(device_id: &str) -> Result<String, CameraError> {
 
 /// Get existing camera or create new one
 ///
+/// A device held by another process fails at once; use
+/// [`get_or_create_camera_with_policy`] to wait for it instead.
+///
 /// # Errors
 /// Returns a [`CameraError`] if the platform camera cannot be created
 /// (e.g. an unsupported platform or an initialization failure).
@@ -58,6 +223,32 @@ pub async fn get_or_create_camera(
     device_id: String,
     format: CameraFormat,
 ) -> Result<Arc<SyncMutex<PlatformCamera>>, CameraError> {
+    get_or_create_camera_with_policy(device_id, format, None).await
+}
+
+/// [`get_or_create_camera`], opening a new camera under `open_policy`
+/// (`None` for [`OpenPolicy::default`])
+///
+/// The policy only applies when the camera is not open yet; concurrent
+/// callers for the same device wait for the first open to finish.
+///
+/// # Errors
+/// Returns a [`CameraError`] if the platform camera cannot be created, e.g.
+/// [`CameraError::DeviceBusy`] once the policy gives up on a busy device.
+pub async fn get_or_create_camera_with_policy(
+    device_id: String,
+    format: CameraFormat,
+    open_policy: Option<OpenPolicy>,
+) -> Result<Arc<SyncMutex<PlatformCamera>>, CameraError> {
+    let requested_id = device_id;
+    let device_id = registry_key(&requested_id).await;
+    if requested_id != device_id {
+        STABLE_ID_ALIASES
+            .write()
+            .await
+            .insert(requested_id, device_id.clone());
+    }
+
     // First, try to get existing camera with read lock
     {
         let registry = CAMERA_REGISTRY.read().await;
@@ -67,32 +258,85 @@ pub async fn get_or_create_camera(
         }
     }
 
-    // Need to create new camera, acquire write lock
-    let mut registry = CAMERA_REGISTRY.write().await;
+    // Need to create new camera; wait for any open of this device in flight
+    let _opening = OpeningLock::acquire(&device_id).await;
 
     // Double-check in case another task created it while we waited
-    if let Some(camera) = registry.get(&device_id) {
+    if let Some(camera) = CAMERA_REGISTRY.read().await.get(&device_id) {
         log::debug!("Using camera created by another task: {device_id}");
         return Ok(camera.clone());
     }
 
-    // Create new camera
+    // Create new camera; opening blocks, for long under `OpenPolicy::RetryFor`
     log::debug!("Creating new camera: {device_id}");
-    let params = CameraInitParams::new(device_id.clone()).with_format(format);
+    let params = CameraInitParams::new(device_id.clone())
+        .with_format(format)
+        .with_open_policy(open_policy.unwrap_or_default());
+    let use_mock = PlatformCamera::mock_requested();
+    let open_id = device_id.clone();
+    let opened = tokio::task::spawn_blocking(move || {
+        let mut camera = PlatformCamera::open(params, use_mock)?;
+        if crate::config::profiles::auto_apply_enabled() {
+            if let Err(e) = crate::config::profiles::apply_profile(&open_id, &mut camera) {
+                log::warn!("Failed to apply saved profile for device {open_id}: {e}");
+            }
+        }
+        Ok(camera)
+    })
+    .await
+    .unwrap_or_else(|e| {
+        Err(CameraError::InitializationError(format!(
+            "Camera open task failed: {e}"
+        )))
+    });
 
-    match PlatformCamera::new(params) {
+    match opened {
         Ok(camera) => {
             let camera_arc = Arc::new(SyncMutex::new(camera));
-            registry.insert(device_id.clone(), camera_arc.clone());
+            CAMERA_REGISTRY
+                .write()
+                .await
+                .insert(device_id.clone(), camera_arc.clone());
+            super::session_metrics::start_session(&device_id);
+            log_event(
+                LogLevel::Info,
+                module_path!(),
+                Some(&device_id),
+                "Camera opened".to_string(),
+            );
             Ok(camera_arc)
         }
         Err(e) => {
-            log::error!("Failed to create camera: {e}");
+            forget_aliases(&device_id).await;
+            log_event(
+                LogLevel::Error,
+                module_path!(),
+                Some(&device_id),
+                format!("Failed to create camera: {e}"),
+            );
             Err(e)
         }
     }
 }
 
+/// Stop a camera and drop it from the registry so the next open starts fresh
+async fn evict_camera(device_id: &str) {
+    // The device may come back under a different index
+    let key = registry_key(device_id).await;
+    forget_aliases(&key).await;
+    let mut registry = CAMERA_REGISTRY.write().await;
+    if let Some(old_camera) = registry.remove(&key) {
+        tokio::task::spawn_blocking(move || {
+            if let Ok(mut camera_guard) = old_camera.lock() {
+                let _ = camera_guard.stop_stream();
+                log::debug!("Removed old camera instance from registry");
+            }
+        })
+        .await
+        .ok();
+    }
+}
+
 /// Attempt to reconnect a camera with retries
 ///
 /// # Errors
@@ -104,23 +348,10 @@ pub async fn reconnect_camera(
 ) -> Result<Arc<SyncMutex<PlatformCamera>>, CameraError> {
     log::info!("Attempting to reconnect camera: {device_id} (max retries: {max_retries})");
 
-    // Remove old camera from registry
-    {
-        let mut registry = CAMERA_REGISTRY.write().await;
-        if let Some(old_camera) = registry.remove(&device_id) {
-            let old_camera_clone = old_camera.clone();
-            tokio::task::spawn_blocking(move || {
-                if let Ok(mut camera_guard) = old_camera_clone.lock() {
-                    let _ = camera_guard.stop_stream();
-                    log::debug!("Removed old camera instance from registry");
-                }
-            })
-            .await
-            .ok();
-        }
-    }
+    evict_camera(&device_id).await;
 
     // Retry connection with exponential backoff
+    let policy = RetryPolicy::from(max_retries);
     for attempt in 1..=max_retries {
         log::debug!("Reconnection attempt {attempt}/{max_retries} for camera: {device_id}");
 
@@ -132,9 +363,7 @@ pub async fn reconnect_camera(
             Err(e) => {
                 log::warn!("Reconnection attempt {attempt} failed: {e}");
                 if attempt < max_retries {
-                    let backoff_ms = (CONNECTION_BACKOFF_INITIAL_MS * 2_u64.pow(attempt - 1))
-                        .min(CONNECTION_BACKOFF_MAX_MS);
-                    tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)).await;
+                    tokio::time::sleep(policy.delay_for(attempt)).await;
                 }
             }
         }
@@ -145,98 +374,172 @@ pub async fn reconnect_camera(
     )))
 }
 
-/// Capture with automatic reconnection on failure
+/// Capture and drop `count` frames so auto-exposure and focus can settle
 ///
-/// # Errors
-/// Returns a [`CameraError::AccessError`] if the camera mutex is poisoned, a
-/// [`CameraError::CaptureError`] if the frame capture fails (even after
-/// reconnection), a [`CameraError::SystemError`] if the blocking task fails to
-/// join, or propagates any error from [`reconnect_camera`].
-pub async fn capture_with_reconnect(
-    device_id: String,
-    format: CameraFormat,
-    max_reconnect_attempts: u32,
-) -> Result<CameraFrame, CameraError> {
-    log::debug!("Attempting capture with reconnect for device: {device_id}");
-
-    let camera_result = get_or_create_camera(device_id.clone(), format.clone()).await;
-    let camera = match camera_result {
-        Ok(cam) => cam,
-        Err(e) => return Err(e),
-    };
+/// Failed captures are expected while a camera powers up and are only
+/// logged. Returns the number of frames actually delivered.
+pub(crate) fn discard_warmup_frames(camera: &mut PlatformCamera, count: u32, delay_ms: u64) -> u32 {
+    let mut delivered = 0;
+    for i in 0..count {
+        match camera.capture_frame() {
+            Ok(_) => delivered += 1,
+            Err(e) => log::debug!("Warmup frame {} failed (normal during startup): {e}", i + 1),
+        }
+        std::thread::sleep(std::time::Duration::from_millis(delay_ms));
+    }
+    delivered
+}
 
-    // Try normal capture first
-    let camera_clone = camera.clone();
-    let capture_result = tokio::task::spawn_blocking(move || {
-        let mut camera_guard = camera_clone
+/// Start the stream, discard `warmup_frames`, then capture one frame
+///
+/// Cameras need time to stabilize exposure/focus, especially USB cameras
+/// that power up on stream start.
+async fn capture_after_warmup(
+    camera: Arc<SyncMutex<PlatformCamera>>,
+    warmup_frames: u32,
+    warmup_delay_ms: u64,
+) -> Result<CameraFrame, CameraError> {
+    tokio::task::spawn_blocking(move || {
+        let mut camera_guard = camera
             .lock()
             .map_err(|_| CameraError::AccessError("Mutex poisoned".to_string()))?;
 
-        // Ensure stream is started
         if let Err(e) = camera_guard.start_stream() {
             log::warn!("Failed to start stream: {e}");
         }
 
-        // Discard warmup frames - cameras need time to stabilize exposure/focus
-        // This is especially important for USB cameras that power up on stream start
-        // Using 5 frames with 30ms delay for reasonable warmup without excessive latency
-        for i in 0..CAPTURE_WARMUP_FRAMES {
-            match camera_guard.capture_frame() {
-                Ok(_) => {
-                    log::debug!("Warmup frame {}Captured", i + 1);
-                }
-                Err(e) => {
-                    log::debug!(
-                        "Warmup frame {} failed (normal during startup): {}",
-                        i + 1,
-                        e
-                    );
-                }
-            }
-            // Small delay between warmup frames
-            std::thread::sleep(std::time::Duration::from_millis(CAPTURE_WARMUP_DELAY_MS));
-        }
+        discard_warmup_frames(&mut camera_guard, warmup_frames, warmup_delay_ms);
 
-        // Now capture the real frame
-        camera_guard.capture_frame().map_err(|e| {
-            CameraError::CaptureError(format!("Initial capture failed: {e}, attempting reconnect"))
-        })
+        camera_guard
+            .capture_frame()
+            .map_err(|e| CameraError::CaptureError(format!("Capture failed: {e}")))
     })
     .await
-    .map_err(|e| CameraError::SystemError(format!("Task join error: {e}")))?;
+    .map_err(|e| CameraError::SystemError(format!("Task join error: {e}")))?
+}
 
-    if let Ok(frame) = capture_result {
-        return Ok(frame);
-    }
+/// Capture with automatic reconnection on failure
+///
+/// `policy` controls how many reconnects are attempted and how long to back
+/// off between them; a plain `u32` is accepted as the attempt count with the
+/// default, jitter-free schedule.
+///
+/// # Errors
+/// Returns a [`CameraError::AccessError`] if the camera mutex is poisoned, a
+/// [`CameraError::CaptureError`] if the frame capture fails (even after
+/// reconnection), a [`CameraError::SystemError`] if the blocking task fails to
+/// join, or the error from reopening the camera if the last reconnect failed.
+pub async fn capture_with_reconnect(
+    device_id: String,
+    format: CameraFormat,
+    policy: impl Into<RetryPolicy>,
+) -> Result<CameraFrame, CameraError> {
+    capture_with_reconnect_report(device_id, format, policy)
+        .await
+        .0
+}
 
-    // Initial capture failed, try reconnecting
-    let camera_arc = reconnect_camera(device_id, format, max_reconnect_attempts).await?;
+/// Like [`capture_with_reconnect`], discarding `warmup_frames` frames before
+/// the real capture instead of the default [`CAPTURE_WARMUP_FRAMES`]
+///
+/// `0` disables warmup, including after a reconnect; otherwise a reopened
+/// camera discards at least [`CAPTURE_RECONNECT_WARMUP_FRAMES`].
+///
+/// # Errors
+/// Same as [`capture_with_reconnect`].
+pub async fn capture_with_warmup(
+    device_id: String,
+    format: CameraFormat,
+    policy: impl Into<RetryPolicy>,
+    warmup_frames: u32,
+) -> Result<CameraFrame, CameraError> {
+    capture_with_reconnect_inner(device_id, format, policy.into(), warmup_frames)
+        .await
+        .0
+}
 
-    let camera_clone = camera_arc.clone();
-    // Try capture after reconnect with warmup
-    tokio::task::spawn_blocking(move || {
-        let mut camera_guard = camera_clone
-            .lock()
-            .map_err(|_| CameraError::AccessError("Mutex poisoned".to_string()))?;
+/// Like [`capture_with_reconnect`], additionally returning a [`RetryReport`]
+/// with the error of every failed attempt.
+pub async fn capture_with_reconnect_report(
+    device_id: String,
+    format: CameraFormat,
+    policy: impl Into<RetryPolicy>,
+) -> (Result<CameraFrame, CameraError>, RetryReport) {
+    capture_with_reconnect_inner(device_id, format, policy.into(), CAPTURE_WARMUP_FRAMES).await
+}
 
-        if let Err(e) = camera_guard.start_stream() {
-            log::warn!("Failed to start stream after reconnect: {e}");
-        }
+async fn capture_with_reconnect_inner(
+    device_id: String,
+    format: CameraFormat,
+    policy: RetryPolicy,
+    warmup_frames: u32,
+) -> (Result<CameraFrame, CameraError>, RetryReport) {
+    let reconnect_warmup_frames = if warmup_frames == 0 {
+        0
+    } else {
+        warmup_frames.max(CAPTURE_RECONNECT_WARMUP_FRAMES)
+    };
+    let mut report = RetryReport::default();
+    log::debug!("Attempting capture with reconnect for device: {device_id} ({policy:?})");
 
-        // Warmup after reconnect too
-        for _ in 0..CAPTURE_RECONNECT_WARMUP_FRAMES {
-            let _ = camera_guard.capture_frame();
-            std::thread::sleep(std::time::Duration::from_millis(
-                CAPTURE_RECONNECT_WARMUP_DELAY_MS,
-            ));
+    let initial = match get_or_create_camera(device_id.clone(), format.clone()).await {
+        Ok(camera) => capture_after_warmup(camera, warmup_frames, CAPTURE_WARMUP_DELAY_MS).await,
+        Err(e) => Err(e),
+    };
+    let mut last_error = match initial {
+        Ok(frame) => {
+            report.succeeded = true;
+            return (Ok(frame), report);
+        }
+        Err(e) => e,
+    };
+    log::warn!("Initial capture failed for {device_id}: {last_error}, attempting reconnect");
+    report.failures.push(RetryAttempt {
+        attempt: 0,
+        delay_ms: 0,
+        error: last_error.to_string(),
+    });
+
+    for attempt in 1..=policy.max_attempts {
+        let delay = policy.delay_for(attempt);
+        tokio::time::sleep(delay).await;
+        log::debug!(
+            "Reconnect attempt {attempt}/{} for camera: {device_id}",
+            policy.max_attempts
+        );
+
+        evict_camera(&device_id).await;
+        let result = match get_or_create_camera(device_id.clone(), format.clone()).await {
+            Ok(camera) => {
+                capture_after_warmup(
+                    camera,
+                    reconnect_warmup_frames,
+                    CAPTURE_RECONNECT_WARMUP_DELAY_MS,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(frame) => {
+                log::info!("Camera {device_id} recovered on reconnect attempt {attempt}");
+                report.succeeded = true;
+                return (Ok(frame), report);
+            }
+            Err(e) => {
+                log::warn!("Reconnect attempt {attempt} failed: {e}");
+                report.failures.push(RetryAttempt {
+                    attempt,
+                    delay_ms: u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                    error: e.to_string(),
+                });
+                last_error = e;
+            }
         }
+    }
 
-        camera_guard.capture_frame().map_err(|e| {
-            CameraError::CaptureError(format!("Capture failed after reconnection: {e}"))
-        })
-    })
-    .await
-    .map_err(|e| CameraError::SystemError(format!("Task join error: {e}")))?
+    (Err(last_error), report)
 }
 
 #[cfg(test)]
@@ -271,6 +574,29 @@ mod tests {
         assert!(get_existing_camera(&device_id).await.is_none());
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_concurrent_opens_share_one_camera() {
+        let device_id = "mgr-dev-concurrent".to_string();
+        let format = CameraFormat::standard();
+
+        let (a, b) = tokio::join!(
+            get_or_create_camera(device_id.clone(), format.clone()),
+            get_or_create_camera(device_id.clone(), format)
+        );
+        assert!(Arc::ptr_eq(
+            &a.expect("camera should be created"),
+            &b.expect("camera should be shared")
+        ));
+        assert!(!OPENING
+            .lock()
+            .expect("opening map")
+            .contains_key(&device_id));
+
+        release_camera(&device_id)
+            .await
+            .expect("release should succeed");
+    }
+
     #[tokio::test]
     async fn test_release_missing_camera_is_ok() {
         let msg = release_camera("definitely-missing")
@@ -312,6 +638,24 @@ mod tests {
         assert!(frame.height > 0);
     }
 
+    #[tokio::test]
+    async fn test_capture_with_warmup_and_discard() {
+        let device_id = "mgr-cap-warmup".to_string();
+        set_mock_camera_mode(&device_id, MockCaptureMode::Success);
+
+        let frame = capture_with_warmup(device_id.clone(), CameraFormat::standard(), 1, 0)
+            .await
+            .expect("capture without warmup should succeed");
+        assert_eq!(frame.device_id, device_id);
+
+        let camera = get_existing_camera(&device_id)
+            .await
+            .expect("camera should be registered");
+        let mut guard = camera.lock().expect("camera lock");
+        assert_eq!(discard_warmup_frames(&mut guard, 3, 0), 3);
+        assert_eq!(discard_warmup_frames(&mut guard, 0, 0), 0);
+    }
+
     #[tokio::test]
     async fn test_capture_with_reconnect_failure_after_retries() {
         let device_id = "mgr-cap-fail".to_string();
@@ -323,4 +667,41 @@ mod tests {
 
         assert!(matches!(err, CameraError::CaptureError(_)));
     }
+
+    #[tokio::test]
+    async fn test_capture_with_reconnect_report_lists_each_failure() {
+        let device_id = "mgr-cap-report".to_string();
+        set_mock_camera_mode(&device_id, MockCaptureMode::Failure);
+
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            base_delay_ms: 1,
+            max_delay_ms: 2,
+            jitter: false,
+        };
+        let (result, report) =
+            capture_with_reconnect_report(device_id, CameraFormat::standard(), policy).await;
+
+        assert!(result.is_err());
+        assert!(!report.succeeded);
+        let attempts: Vec<u32> = report.failures.iter().map(|f| f.attempt).collect();
+        assert_eq!(attempts, vec![0, 1, 2]);
+        assert_eq!(report.failures[1].delay_ms, 1);
+        assert_eq!(report.failures[2].delay_ms, 2);
+        assert!(report.failures.iter().all(|f| !f.error.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_capture_with_reconnect_report_success_has_no_failures() {
+        let device_id = "mgr-cap-report-ok".to_string();
+        set_mock_camera_mode(&device_id, MockCaptureMode::Success);
+
+        let (result, report) =
+            capture_with_reconnect_report(device_id, CameraFormat::standard(), RetryPolicy::none())
+                .await;
+
+        assert!(result.is_ok());
+        assert!(report.succeeded);
+        assert_eq!(report.total_attempts(), 1);
+    }
 }
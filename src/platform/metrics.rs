@@ -4,14 +4,16 @@
 //! and `get_performance_metrics` reads it back together with a genuine OS-level
 //! process-memory reading. Nothing here is fabricated: latency and processing
 //! time are measured around the actual capture call, FPS is derived from the
-//! interval between captures, dropped frames are counted on capture failure, and
-//! memory usage is read from the operating system.
+//! deliveries observed over a sliding window on a [`PTSClock`], dropped frames
+//! are counted on capture failure, and memory usage is read from the operating
+//! system.
 
-use crate::constants::BLUR_VARIANCE_BLURRY;
+use crate::constants::{BLUR_VARIANCE_BLURRY, PERF_FPS_WINDOW_SECS};
 use crate::quality::blur::BlurDetector;
+use crate::timing::PTSClock;
 use crate::types::CameraFrame;
 use crate::types::CameraPerformanceMetrics;
-use std::time::Instant;
+use std::collections::VecDeque;
 
 /// Rolling performance tracker shared by all platform cameras.
 ///
@@ -23,9 +25,12 @@ pub struct PerfTracker {
     /// Time, in milliseconds, spent constructing the `CameraFrame` from the
     /// raw buffer (decode/clone/metadata).
     pub processing_time_ms: f32,
-    /// Frames per second actually delivered, derived from the interval between
-    /// consecutive successful captures.
+    /// Frames per second actually delivered over the last
+    /// [`PERF_FPS_WINDOW_SECS`] seconds.
     pub fps_actual: f32,
+    /// How far, in milliseconds, delivery has fallen behind the nominal frame
+    /// schedule because capture plus processing outran the frame interval.
+    pub processing_backlog_ms: f32,
     /// Total number of successful captures observed.
     pub frames_captured: u64,
     /// Number of capture attempts that failed (used to detect frame drops).
@@ -36,8 +41,12 @@ pub struct PerfTracker {
     /// Snapshot of the most recent frame, retained so a quality score can be
     /// derived on demand without re-capturing. `(buffer, width, height, format)`.
     last_frame: Option<(Vec<u8>, u32, u32, String)>,
-    /// Instant of the previous successful capture, for FPS accounting.
-    last_capture: Option<Instant>,
+    /// Timebase for delivery timestamps.
+    clock: PTSClock,
+    /// Delivery timestamps (PTS seconds) inside the sliding FPS window.
+    deliveries: VecDeque<f64>,
+    /// Nominal frame interval in seconds, if the negotiated rate is known.
+    target_interval: Option<f64>,
 }
 
 impl Default for PerfTracker {
@@ -53,14 +62,26 @@ impl PerfTracker {
             capture_latency_ms: 0.0,
             processing_time_ms: 0.0,
             fps_actual: 0.0,
+            processing_backlog_ms: 0.0,
             frames_captured: 0,
             dropped_frames: 0,
             buffer_overruns: 0,
             last_frame: None,
-            last_capture: None,
+            clock: PTSClock::new(),
+            deliveries: VecDeque::new(),
+            target_interval: None,
         }
     }
 
+    /// Create a tracker that measures backlog against the given nominal rate.
+    pub fn with_target_fps(fps: f32) -> Self {
+        let mut tracker = Self::new();
+        if fps > 0.0 {
+            tracker.target_interval = Some(1.0 / f64::from(fps));
+        }
+        tracker
+    }
+
     /// Record a successful capture.
     ///
     /// `latency_ms` is the time spent in the device `frame()` call,
@@ -72,6 +93,22 @@ impl PerfTracker {
         latency_ms: f32,
         processing_ms: f32,
         frame: Option<(Vec<u8>, u32, u32, String)>,
+    ) {
+        let now = self.clock.pts();
+        self.record_capture_at(now, latency_ms, processing_ms, frame);
+    }
+
+    /// Record a successful capture delivered at `pts` seconds on this
+    /// tracker's clock.
+    ///
+    /// Used directly by synthetic sources that supply their own timeline.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub fn record_capture_at(
+        &mut self,
+        pts: f64,
+        latency_ms: f32,
+        processing_ms: f32,
+        frame: Option<(Vec<u8>, u32, u32, String)>,
     ) {
         self.capture_latency_ms = latency_ms;
         self.processing_time_ms = processing_ms;
@@ -81,18 +118,43 @@ impl PerfTracker {
             self.last_frame = Some(f);
         }
 
-        let now = Instant::now();
-        if let Some(prev) = self.last_capture {
-            let elapsed = prev.elapsed().as_secs_f32();
-            if elapsed > 0.0 {
-                self.fps_actual = 1.0 / elapsed;
-            } else {
+        if let Some(&prev) = self.deliveries.back() {
+            if pts <= prev {
                 // Captured again before a measurable interval elapsed: the consumer
                 // is outrunning the device's delivery rate.
                 self.buffer_overruns += 1;
             }
         }
-        self.last_capture = Some(now);
+
+        self.deliveries.push_back(pts);
+        while self
+            .deliveries
+            .front()
+            .is_some_and(|&first| pts - first > PERF_FPS_WINDOW_SECS)
+        {
+            self.deliveries.pop_front();
+        }
+
+        if let (Some(&first), Some(&last)) = (self.deliveries.front(), self.deliveries.back()) {
+            let span = last - first;
+            if span > 0.0 {
+                // Window holds at most a few hundred entries; f32 is ample.
+                self.fps_actual = ((self.deliveries.len() - 1) as f64 / span) as f32;
+            }
+        }
+
+        if let Some(interval) = self.target_interval {
+            // Leaky bucket: time spent beyond the frame interval accumulates,
+            // spare time within an interval drains it.
+            let spent = f64::from(latency_ms + processing_ms) / 1000.0;
+            let backlog = f64::from(self.processing_backlog_ms) / 1000.0 + spent - interval;
+            self.processing_backlog_ms = (backlog.max(0.0) * 1000.0) as f32;
+        }
+    }
+
+    /// Clock used to timestamp deliveries.
+    pub fn clock(&self) -> &PTSClock {
+        &self.clock
     }
 
     /// Record a failed capture attempt as a dropped frame.
@@ -228,8 +290,69 @@ pub fn build_metrics(tracker: &PerfTracker, device_id: &str) -> CameraPerformanc
         processing_time_ms: tracker.processing_time_ms,
         memory_usage_mb: tracker.memory_usage_mb(),
         fps_actual: tracker.fps_actual,
+        processing_backlog_ms: tracker.processing_backlog_ms,
         dropped_frames: tracker.dropped_frames,
         buffer_overruns: tracker.buffer_overruns,
         quality_score,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fps_converges_for_synthetic_stream() {
+        let mut tracker = PerfTracker::with_target_fps(30.0);
+        let interval = 1.0 / 30.0;
+
+        for i in 0..120 {
+            tracker.record_capture_at(f64::from(i) * interval, 5.0, 2.0, None);
+        }
+
+        assert!((tracker.fps_actual - 30.0).abs() < 0.5);
+        assert!(tracker.processing_backlog_ms.abs() < f32::EPSILON);
+        assert_eq!(tracker.frames_captured, 120);
+        assert_eq!(tracker.buffer_overruns, 0);
+    }
+
+    #[test]
+    fn test_fps_tracks_rate_change_within_window() {
+        let mut tracker = PerfTracker::new();
+        let mut pts = 0.0;
+
+        for _ in 0..60 {
+            tracker.record_capture_at(pts, 0.0, 0.0, None);
+            pts += 1.0 / 60.0;
+        }
+        for _ in 0..60 {
+            tracker.record_capture_at(pts, 0.0, 0.0, None);
+            pts += 1.0 / 15.0;
+        }
+
+        assert!((tracker.fps_actual - 15.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_backlog_accumulates_when_processing_outruns_interval() {
+        let mut tracker = PerfTracker::with_target_fps(100.0);
+
+        for i in 0..10 {
+            // 15ms of work per 10ms frame interval
+            tracker.record_capture_at(f64::from(i) * 0.015, 10.0, 5.0, None);
+        }
+
+        assert!((tracker.processing_backlog_ms - 50.0).abs() < 0.1);
+
+        let metrics = build_metrics(&tracker, "synthetic");
+        assert!((metrics.processing_backlog_ms - tracker.processing_backlog_ms).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_repeated_timestamp_counts_overrun() {
+        let mut tracker = PerfTracker::new();
+        tracker.record_capture_at(1.0, 0.0, 0.0, None);
+        tracker.record_capture_at(1.0, 0.0, 0.0, None);
+        assert_eq!(tracker.buffer_overruns, 1);
+    }
+}
@@ -5,14 +5,15 @@
 //! optimizations and features.
 
 use crate::constants::{
-    DEFAULT_RESOLUTION_HEIGHT, DEFAULT_RESOLUTION_WIDTH, HIGH_FPS, MAX_ISO, MIN_ISO,
-    MOCK_CAPTURE_LATENCY_MS, MOCK_FPS, MOCK_MEMORY_USAGE_MB, MOCK_PROCESSING_TIME_MS,
-    MOCK_QUALITY_SCORE, MOCK_SLOW_CAPTURE_DELAY_MS,
+    DEFAULT_ISO, DEFAULT_RESOLUTION_HEIGHT, DEFAULT_RESOLUTION_WIDTH, FRAME_VALIDATION_RETRIES,
+    HIGH_FPS, MAX_ISO, MIN_ISO, MOCK_CAPTURE_LATENCY_MS, MOCK_FPS, MOCK_MEMORY_USAGE_MB,
+    MOCK_PROCESSING_TIME_MS, MOCK_QUALITY_SCORE, MOCK_SLOW_CAPTURE_DELAY_MS,
+    SYSTEM_TEST_CONCURRENCY, SYSTEM_TEST_DEVICE_TIMEOUT_MS,
 };
 use crate::errors::CameraError;
 use crate::types::{
     CameraDeviceInfo, CameraFormat, CameraFrame, CameraInitParams, ControlApplicationResult,
-    Platform,
+    Platform, TimestampSource,
 };
 
 // Type alias for frame callback to reduce complexity
@@ -34,16 +35,57 @@ pub mod linux;
 // Device monitoring module
 pub mod device_monitor;
 
+/// Cache of enumerated devices and probed capabilities.
+pub mod device_cache;
+
+/// Cumulative capture statistics per open camera.
+pub mod session_metrics;
+pub use session_metrics::SessionMetrics;
+
 // Shared real performance tracking
 pub mod metrics;
 
+/// Pixel format conversion shared by all backends.
+pub mod convert;
+
+/// Choice between compressed and uncompressed device modes.
+pub mod negotiation;
+pub use negotiation::{max_resolution_format, negotiate_format, FormatNegotiation};
+
+/// Async `Stream` adapter over frame capture.
+pub mod stream;
+pub use stream::FrameStream;
+
+/// Bounded drop-oldest frame buffer for polling consumers.
+pub mod frame_queue;
+pub use frame_queue::FrameQueue;
+
+/// Bounded resequencing of out-of-order frames by timestamp.
+pub mod reorder;
+pub use reorder::ReorderBuffer;
+
+/// Per-camera flip and rotation of captured frames.
+pub mod orientation;
+
+/// Displays captured through ffmpeg as video sources.
+#[cfg(feature = "screen-capture")]
+pub mod screen;
+#[cfg(feature = "screen-capture")]
+pub use screen::{list_screens, ScreenSource};
+
+/// Time-aligned frame sets from several streams of one device.
+pub mod multistream;
+pub use multistream::MultiStreamHandle;
+
 pub use device_monitor::{DeviceEvent, DeviceMonitor};
 
 /// Camera manager module for handling device lifecycle.
 pub mod manager;
 pub use manager::{
-    capture_with_reconnect, get_existing_camera, get_or_create_camera, reconnect_camera,
-    release_camera,
+    capture_with_reconnect, capture_with_reconnect_report, capture_with_warmup,
+    get_existing_camera, get_last_known_metrics, get_or_create_camera,
+    get_or_create_camera_with_policy, is_camera_paused, pause_camera, reconnect_camera,
+    release_camera, resume_camera,
 };
 
 use std::sync::{Arc, Mutex};
@@ -54,24 +96,118 @@ use std::sync::{Arc, Mutex};
 /// without physical hardware.
 pub struct MockCamera {
     device_id: String,
+    format: CameraFormat,
     controls: Arc<Mutex<crate::types::CameraControls>>,
     is_streaming: Arc<Mutex<bool>>,
     capture_mode: Arc<Mutex<crate::tests::MockCaptureMode>>,
     callback: Arc<Mutex<Option<FrameCallback>>>,
+    timestamp_source: TimestampSource,
+    opened_at: std::time::Instant,
+    perf: Arc<Mutex<metrics::PerfTracker>>,
+    sensor_rotation: Option<u16>,
+    captures: usize,
+    pacer: MockPacer,
+}
+
+/// Schedule of [`MockCaptureMode::Timed`](crate::tests::MockCaptureMode::Timed)
+/// frames
+#[derive(Default)]
+struct MockPacer {
+    /// When the previous frame was due, before jitter
+    nominal: Option<std::time::Instant>,
+    /// xorshift state for the jitter offsets
+    jitter_state: u64,
+}
+
+impl MockPacer {
+    /// Fixed seed so every run sees the same jitter
+    const SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    /// Sleep until the next frame at `fps` is due, offset by up to
+    /// `jitter_ms` either way
+    fn wait(&mut self, fps: f64, jitter_ms: u32) {
+        let now = std::time::Instant::now();
+        let Some(previous) = self.nominal else {
+            self.nominal = Some(now);
+            self.jitter_state = Self::SEED;
+            return;
+        };
+        let period = std::time::Duration::try_from_secs_f64(1.0 / fps).unwrap_or_default();
+        let nominal = previous + period;
+        self.nominal = Some(nominal);
+
+        let jitter = std::time::Duration::from_millis(u64::from(jitter_ms));
+        let offset = self.next_unit();
+        let due = if offset < 0.0 {
+            nominal
+                .checked_sub(jitter.mul_f64(-offset))
+                .unwrap_or(nominal)
+        } else {
+            nominal + jitter.mul_f64(offset)
+        };
+        if let Some(wait) = due.checked_duration_since(now) {
+            std::thread::sleep(wait);
+        }
+    }
+
+    /// Next pseudo-random value in `[-1, 1]`
+    // The top 53 bits convert to f64 exactly
+    #[allow(clippy::cast_precision_loss)]
+    fn next_unit(&mut self) -> f64 {
+        let mut x = self.jitter_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.jitter_state = x;
+        (x >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+    }
 }
 
 impl MockCamera {
     /// Create a new mock camera instance.
-    pub fn new(device_id: String, _format: CameraFormat) -> Self {
+    pub fn new(device_id: String, format: CameraFormat) -> Self {
+        let target_fps = format.fps;
         Self {
             device_id,
+            format,
             controls: Arc::new(Mutex::new(crate::types::CameraControls::default())),
             is_streaming: Arc::new(Mutex::new(false)),
             capture_mode: Arc::new(Mutex::new(crate::tests::MockCaptureMode::Success)),
             callback: Arc::new(Mutex::new(None)),
+            timestamp_source: TimestampSource::default(),
+            opened_at: std::time::Instant::now(),
+            perf: Arc::new(Mutex::new(metrics::PerfTracker::with_target_fps(
+                target_fps,
+            ))),
+            sensor_rotation: None,
+            captures: 0,
+            pacer: MockPacer::default(),
         }
     }
 
+    /// Report a sensor mounting rotation (degrees counter-clockwise), as a
+    /// rotated tablet camera would.
+    #[must_use]
+    pub fn with_sensor_rotation(mut self, degrees: u16) -> Self {
+        self.sensor_rotation = Some(degrees);
+        self
+    }
+
+    /// Sensor mounting rotation set with [`MockCamera::with_sensor_rotation`].
+    pub fn sensor_rotation(&self) -> Option<u16> {
+        self.sensor_rotation
+    }
+
+    /// Select the frame timestamp source.
+    ///
+    /// With [`TimestampSource::DriverHardware`] the mock reports seconds since
+    /// the device was opened, standing in for a driver's monotonic clock.
+    #[must_use]
+    pub fn with_timestamp_source(mut self, source: TimestampSource) -> Self {
+        self.timestamp_source = source;
+        self
+    }
+
     /// Set the behavior mode for this mock camera (e.g. simulate failure).
     pub fn set_capture_mode(&self, mode: crate::tests::MockCaptureMode) {
         if let Ok(mut capture_mode) = self.capture_mode.lock() {
@@ -86,10 +222,17 @@ impl MockCamera {
     /// failure simulation mode.
     pub fn capture_frame(&mut self) -> Result<CameraFrame, CameraError> {
         // Check global registry first, then fall back to local mode
-        let mode = crate::tests::get_mock_camera_mode(&self.device_id);
+        let mode = crate::tests::get_mock_camera_mode(&self.device_id).at_step(self.captures);
+        self.captures += 1;
+        if let crate::tests::MockCaptureMode::Timed { fps, jitter_ms } = mode {
+            self.pacer.wait(fps, jitter_ms);
+        }
+        let started = std::time::Instant::now();
 
-        let frame = match mode {
-            crate::tests::MockCaptureMode::Success => {
+        let mut frame = match mode {
+            crate::tests::MockCaptureMode::Success
+            | crate::tests::MockCaptureMode::Timed { .. }
+            | crate::tests::MockCaptureMode::Sequence(_) => {
                 Ok(crate::tests::create_mock_frame(&self.device_id))
             }
             crate::tests::MockCaptureMode::Failure => Err(CameraError::CaptureError(
@@ -101,6 +244,21 @@ impl MockCamera {
             }
         };
 
+        if let Ok(ref mut frame) = frame {
+            if self.timestamp_source == TimestampSource::DriverHardware {
+                frame.metadata.hardware_timestamp = Some(self.opened_at.elapsed().as_secs_f64());
+            }
+        }
+
+        if let Ok(mut perf) = self.perf.lock() {
+            if frame.is_ok() {
+                let latency_ms = started.elapsed().as_secs_f32() * 1000.0;
+                perf.record_capture(latency_ms, 0.0, None);
+            } else {
+                perf.record_drop();
+            }
+        }
+
         // Call callback if set and frame was successful
         if let Ok(ref frame) = frame {
             if let Ok(cb) = self.callback.lock() {
@@ -113,6 +271,31 @@ impl MockCamera {
         frame
     }
 
+    /// Capture a mock frame as compressed MJPEG, mirroring what a real
+    /// camera delivers from [`PlatformCamera::capture_frame_raw`].
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::CaptureError`] in failure simulation mode or
+    /// if the mock frame cannot be JPEG-encoded.
+    pub fn capture_frame_raw(&mut self) -> Result<CameraFrame, CameraError> {
+        let frame = self.capture_frame()?;
+        let image = image::RgbImage::from_raw(frame.width, frame.height, frame.data.clone())
+            .ok_or_else(|| CameraError::CaptureError("Invalid mock frame buffer".to_string()))?;
+
+        let mut jpeg = Vec::new();
+        image::DynamicImage::ImageRgb8(image)
+            .write_to(
+                &mut std::io::Cursor::new(&mut jpeg),
+                image::ImageFormat::Jpeg,
+            )
+            .map_err(|e| CameraError::CaptureError(format!("Failed to encode mock MJPEG: {e}")))?;
+
+        let mut raw = CameraFrame::new(jpeg, frame.width, frame.height, frame.device_id)
+            .with_format(crate::constants::FORMAT_MJPEG.to_string());
+        raw.metadata = frame.metadata;
+        Ok(raw)
+    }
+
     /// Start the stream.
     ///
     /// # Errors
@@ -159,6 +342,20 @@ impl MockCamera {
         &self.device_id
     }
 
+    /// Get the current format.
+    pub fn get_format(&self) -> &CameraFormat {
+        &self.format
+    }
+
+    /// Switch to `format`; the mock accepts any format exactly.
+    ///
+    /// # Errors
+    /// This function currently always returns `Ok` and never returns an `Err`.
+    pub fn reconfigure(&mut self, format: CameraFormat) -> Result<CameraFormat, CameraError> {
+        self.format = format.clone();
+        Ok(format)
+    }
+
     /// Apply camera controls.
     ///
     /// # Errors
@@ -168,7 +365,9 @@ impl MockCamera {
         controls: &crate::types::CameraControls,
     ) -> Result<ControlApplicationResult, CameraError> {
         if let Ok(mut current_controls) = self.controls.lock() {
-            *current_controls = controls.clone();
+            // Like real hardware, controls left as `None` keep their value
+            let changes = current_controls.diff(controls);
+            current_controls.apply_patch(&changes);
         }
         // Mock accepts every control requested
         let mut applied = Vec::new();
@@ -254,21 +453,65 @@ impl MockCamera {
             exposure_range: Some((0.001, 10.0)),
             iso_range: Some((MIN_ISO, MAX_ISO)),
             focus_range: Some((0.0, 1.0)),
+            provenance: crate::types::CapabilityProvenance::default(),
+            controls: Self::control_ranges(),
         })
     }
 
+    /// Ranges the mock accepts, matching `CameraControls` defaults
+    fn control_ranges() -> Vec<crate::types::ControlRange> {
+        let range = |control: &str, min: f32, max: f32, default: f32| crate::types::ControlRange {
+            control: control.to_string(),
+            min,
+            max,
+            step: None,
+            default: Some(default),
+        };
+        #[allow(clippy::cast_precision_loss)] // ISO values are exact in f32
+        let iso = crate::types::ControlRange {
+            step: Some(1.0),
+            ..range(
+                "iso_sensitivity",
+                MIN_ISO as f32,
+                MAX_ISO as f32,
+                DEFAULT_ISO as f32,
+            )
+        };
+        vec![
+            range("focus_distance", 0.0, 1.0, 0.0),
+            range("exposure_time", 0.001, 10.0, 1.0 / 30.0),
+            iso,
+            range("brightness", -1.0, 1.0, 0.0),
+            range("contrast", -1.0, 1.0, 0.0),
+            range("saturation", -1.0, 1.0, 0.0),
+            range("sharpness", -1.0, 1.0, 0.0),
+        ]
+    }
+
     /// Get mock performance metrics.
     ///
+    /// Once a delivery rate has been measured these are the live rolling
+    /// measurements; before that, fixed nominal values are reported.
+    ///
     /// # Errors
     /// This function currently always returns `Ok` and never returns an `Err`.
     pub fn get_performance_metrics(
         &self,
     ) -> Result<crate::types::CameraPerformanceMetrics, CameraError> {
+        if let Ok(perf) = self.perf.lock() {
+            if perf.fps_actual > 0.0 {
+                let mut live = metrics::build_metrics(&perf, &self.device_id);
+                live.quality_score = MOCK_QUALITY_SCORE;
+                return Ok(live);
+            }
+        }
+
         Ok(crate::types::CameraPerformanceMetrics {
             capture_latency_ms: MOCK_CAPTURE_LATENCY_MS,
             processing_time_ms: MOCK_PROCESSING_TIME_MS,
             memory_usage_mb: MOCK_MEMORY_USAGE_MB,
             fps_actual: MOCK_FPS,
+            processing_backlog_ms: 0.0,
             dropped_frames: 0,
             buffer_overruns: 0,
             quality_score: MOCK_QUALITY_SCORE,
@@ -293,6 +536,10 @@ pub enum PlatformCamera {
     /// Mock camera for testing.
     Mock(MockCamera),
 
+    /// Display captured through ffmpeg.
+    #[cfg(feature = "screen-capture")]
+    Screen(screen::ScreenSource),
+
     /// Fallback for unsupported platforms.
     #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
     Unsupported,
@@ -301,30 +548,64 @@ pub enum PlatformCamera {
 impl PlatformCamera {
     /// Create new platform camera from initialization parameters
     ///
+    /// `params.device_id` may be a numeric or a stable device id.
+    ///
     /// # Errors
-    /// Returns a [`CameraError::InitializationError`] if the current platform
-    /// is unsupported, or propagates any error from the platform-specific camera
-    /// creation.
+    /// Returns a [`CameraError::UnsupportedOperation`] if `params.backend`
+    /// is not available here, a [`CameraError::InitializationError`] if the
+    /// current platform is unsupported, or propagates any error from the
+    /// platform-specific camera creation.
     pub fn new(params: CameraInitParams) -> Result<Self, CameraError> {
+        Self::open(params, Self::mock_requested())
+    }
+
+    /// Whether [`PlatformCamera::new`] on this thread returns a mock camera
+    ///
+    /// Checked before handing the open to another thread, which would not
+    /// share the caller's thread name.
+    pub(crate) fn mock_requested() -> bool {
         // Only use mock camera when explicitly requested via environment variable
         // or when running in unit test threads (thread name contains "test")
         // Note: We no longer check CARGO_MANIFEST_DIR because that's set during
         // normal `cargo run` which should use real cameras
-        let use_mock = std::env::var("CRABCAMERA_USE_MOCK").is_ok()
+        std::env::var("CRABCAMERA_USE_MOCK").is_ok()
             || std::thread::current()
                 .name()
-                .is_some_and(|name| name.contains("test"));
+                .is_some_and(|name| name.contains("test"))
+    }
+
+    /// [`PlatformCamera::new`], with the mock decision made by the caller
+    pub(crate) fn open(mut params: CameraInitParams, use_mock: bool) -> Result<Self, CameraError> {
+        if let Some(backend) = params.backend {
+            backend.validate()?;
+        }
 
         if use_mock {
             log::info!("Using mock camera (CRABCAMERA_USE_MOCK set or in test thread)");
-            let mock_camera = MockCamera::new(params.device_id, params.format);
+            let mock_camera = MockCamera::new(params.device_id, params.format)
+                .with_timestamp_source(params.timestamp_source);
             return Ok(PlatformCamera::Mock(mock_camera));
         }
 
+        #[cfg(feature = "screen-capture")]
+        if screen::is_screen_id(&params.device_id) {
+            let source = screen::ScreenSource::new(params.device_id, params.format)?;
+            return Ok(PlatformCamera::Screen(source));
+        }
+
+        // Backends open by index; stable ids are resolved here
+        params.device_id = CameraSystem::resolve_device_id(&params.device_id);
+
         match Platform::current() {
             #[cfg(target_os = "windows")]
             Platform::Windows => {
-                let camera = windows::WindowsCamera::new(params.device_id, &params.format)?;
+                let camera = windows::WindowsCamera::with_backend(
+                    params.device_id,
+                    &params.format,
+                    params.backend,
+                )?
+                .with_pixel_format(params.pixel_format)
+                .with_timestamp_source(params.timestamp_source);
                 Ok(PlatformCamera::Windows(camera))
             }
 
@@ -348,21 +629,131 @@ impl PlatformCamera {
 
     /// Capture a single frame from the camera
     ///
+    /// Frames failing [`CameraFrame::validate`] (short or torn buffers) are
+    /// dropped and recaptured up to [`FRAME_VALIDATION_RETRIES`] times.
+    /// The returned frame's `metadata.content_hash` covers its final pixels.
+    /// Every call counts toward the device's [`SessionMetrics`]. Fails with
+    /// a [`CameraError::StreamError`] while the camera is paused (see
+    /// [`pause_camera`]).
+    ///
     /// # Errors
     /// Returns a [`CameraError::InitializationError`] on an unsupported platform,
-    /// or propagates any error from the underlying platform camera's capture.
+    /// a [`CameraError::CaptureError`] if no complete frame was delivered, or
+    /// propagates any error from the underlying platform camera's capture.
     pub fn capture_frame(&mut self) -> Result<CameraFrame, CameraError> {
+        let result = self.capture_recorded(false);
+
+        let result = result
+            .map(crate::quality::white_balance::correct_frame)
+            .map(orientation::orient_frame);
+
+        #[cfg(feature = "face-detect")]
+        let result = result.map(crate::quality::detect::annotate_frame);
+
+        result.map(with_content_hash)
+    }
+
+    /// Capture a single frame in the camera's native format, without RGB conversion
+    ///
+    /// The returned frame's `format` names the source pixel format (e.g. `MJPEG`,
+    /// `YUYV`), which lets compressed frames be recorded without re-encoding.
+    /// Incomplete frames are retried as in [`PlatformCamera::capture_frame`].
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::InitializationError`] on an unsupported platform,
+    /// a [`CameraError::CaptureError`] if no complete frame was delivered, or
+    /// propagates any error from the underlying platform camera's capture.
+    pub fn capture_frame_raw(&mut self) -> Result<CameraFrame, CameraError> {
+        self.capture_recorded(true).map(with_content_hash)
+    }
+
+    /// [`capture_validated`](Self::capture_validated), counted in the
+    /// device's session metrics
+    fn capture_recorded(&mut self, raw: bool) -> Result<CameraFrame, CameraError> {
+        if let Some(device_id) = self.get_device_id().filter(|id| is_camera_paused(id)) {
+            return Err(CameraError::StreamError(format!(
+                "Camera {device_id} is paused"
+            )));
+        }
+        let started = std::time::Instant::now();
+        let result = self.capture_validated(raw);
+        if let Some(device_id) = self.get_device_id() {
+            match &result {
+                Ok(frame) => {
+                    session_metrics::record_frame(device_id, frame.data.len(), started.elapsed());
+                }
+                Err(e) => session_metrics::record_error(device_id, e.error_code()),
+            }
+        }
+        result
+    }
+
+    /// Capture from the backend, retrying frames that fail validation
+    ///
+    /// Successful frames carry the settings read back from the device in
+    /// their metadata (see [`FrameMetadata::record_controls`]).
+    ///
+    /// [`FrameMetadata::record_controls`]: crate::types::FrameMetadata::record_controls
+    fn capture_validated(&mut self, raw: bool) -> Result<CameraFrame, CameraError> {
+        let mut attempt = 0;
+        loop {
+            let validated = self
+                .capture_from_backend(raw)
+                .map(|frame| (frame.validate(), frame));
+
+            match validated {
+                Ok((Ok(()), mut frame)) => {
+                    // Settings the backend can't read back stay `None`
+                    match self.get_controls() {
+                        Ok(controls) => frame.metadata.record_controls(&controls),
+                        Err(e) => log::debug!(
+                            "Capture settings unavailable for {}: {e}",
+                            self.get_device_id()
+                        ),
+                    }
+                    return Ok(frame);
+                }
+                Ok((Err(e), _)) if attempt < FRAME_VALIDATION_RETRIES => {
+                    attempt += 1;
+                    log::debug!(
+                        "Retrying capture on {} ({attempt}/{FRAME_VALIDATION_RETRIES}): {e}",
+                        self.get_device_id()
+                    );
+                }
+                Ok((Err(e), _)) | Err(e) => {
+                    crate::logging::log_event(
+                        crate::logging::LogLevel::Warn,
+                        module_path!(),
+                        self.get_device_id(),
+                        format!("Frame dropped: {e}"),
+                    );
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    fn capture_from_backend(&mut self, raw: bool) -> Result<CameraFrame, CameraError> {
         match self {
+            #[cfg(target_os = "windows")]
+            PlatformCamera::Windows(camera) if raw => camera.capture_frame_raw(),
             #[cfg(target_os = "windows")]
             PlatformCamera::Windows(camera) => camera.capture_frame(),
 
+            #[cfg(target_os = "macos")]
+            PlatformCamera::MacOS(camera) if raw => camera.capture_frame_raw(),
             #[cfg(target_os = "macos")]
             PlatformCamera::MacOS(camera) => camera.capture_frame(),
 
+            #[cfg(target_os = "linux")]
+            PlatformCamera::Linux(camera) if raw => camera.capture_frame_raw(),
             #[cfg(target_os = "linux")]
             PlatformCamera::Linux(camera) => camera.capture_frame(),
 
+            PlatformCamera::Mock(camera) if raw => camera.capture_frame_raw(),
             PlatformCamera::Mock(camera) => camera.capture_frame(),
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(source) => source.capture_frame(),
 
             #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
             PlatformCamera::Unsupported => Err(CameraError::InitializationError(
@@ -388,6 +779,8 @@ impl PlatformCamera {
             PlatformCamera::Linux(camera) => camera.start_stream(),
 
             PlatformCamera::Mock(camera) => camera.start_stream(),
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(source) => source.start_stream(),
 
             #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
             PlatformCamera::Unsupported => Err(CameraError::InitializationError(
@@ -413,6 +806,8 @@ impl PlatformCamera {
             PlatformCamera::Linux(camera) => camera.stop_stream(),
 
             PlatformCamera::Mock(camera) => camera.stop_stream(),
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(source) => source.stop_stream(),
 
             #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
             PlatformCamera::Unsupported => Err(CameraError::InitializationError(
@@ -434,6 +829,8 @@ impl PlatformCamera {
             PlatformCamera::Linux(camera) => camera.is_available(),
 
             PlatformCamera::Mock(camera) => camera.is_available(),
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(source) => source.is_available(),
 
             #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
             PlatformCamera::Unsupported => false,
@@ -461,6 +858,8 @@ impl PlatformCamera {
             PlatformCamera::Linux(camera) => camera.set_callback(callback),
 
             PlatformCamera::Mock(camera) => camera.frame_callback(callback),
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(source) => source.set_callback(callback),
 
             #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
             PlatformCamera::Unsupported => Err(CameraError::UnsupportedOperation(
@@ -482,12 +881,103 @@ impl PlatformCamera {
             PlatformCamera::Linux(camera) => Some(camera.get_device_id()),
 
             PlatformCamera::Mock(camera) => Some(camera.get_device_id()),
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(source) => Some(source.get_device_id()),
+
+            #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+            PlatformCamera::Unsupported => None,
+        }
+    }
+
+    /// Capture API the camera was opened with, `None` for mock cameras
+    /// and screen sources
+    pub fn backend(&self) -> Option<crate::types::CameraBackend> {
+        match self {
+            #[cfg(target_os = "windows")]
+            PlatformCamera::Windows(camera) => Some(camera.backend),
+
+            #[cfg(target_os = "macos")]
+            PlatformCamera::MacOS(camera) => Some(camera.backend()),
+
+            #[cfg(target_os = "linux")]
+            PlatformCamera::Linux(camera) => Some(camera.backend()),
+
+            PlatformCamera::Mock(_) => None,
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(_) => None,
 
             #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
             PlatformCamera::Unsupported => None,
         }
     }
 
+    /// Change the capture format on the open device without reinitializing
+    ///
+    /// Stops the stream if it is running, negotiates the closest mode the
+    /// driver supports and restarts it, keeping the registry entry and
+    /// device handle. Controls read before the switch are re-applied
+    /// afterwards since some drivers reset them on a format change; any the
+    /// new mode rejects are logged and skipped.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::InitializationError`] on an unsupported platform
+    /// or if the driver rejects the format or the stream cannot be restarted.
+    pub fn reconfigure(&mut self, format: CameraFormat) -> Result<(), CameraError> {
+        self.switch_format(format, false).map(|_| ())
+    }
+
+    /// Like [`PlatformCamera::reconfigure`], but use `format` verbatim
+    /// instead of the closest match, e.g. an entry of the device's
+    /// `supports_formats`. Returns the format now in use.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::InitializationError`] on an unsupported
+    /// platform, if the driver does not offer exactly `format`, or if the
+    /// stream cannot be restarted.
+    pub fn reconfigure_exact(&mut self, format: CameraFormat) -> Result<CameraFormat, CameraError> {
+        self.switch_format(format, true)
+    }
+
+    fn switch_format(
+        &mut self,
+        format: CameraFormat,
+        exact: bool,
+    ) -> Result<CameraFormat, CameraError> {
+        let controls = self.get_controls().ok();
+
+        let selected = match self {
+            #[cfg(target_os = "windows")]
+            PlatformCamera::Windows(camera) => camera.reconfigure(&format, exact),
+
+            #[cfg(target_os = "macos")]
+            PlatformCamera::MacOS(camera) => camera.reconfigure(&format, exact),
+
+            #[cfg(target_os = "linux")]
+            PlatformCamera::Linux(camera) => camera.reconfigure(&format, exact),
+
+            PlatformCamera::Mock(camera) => camera.reconfigure(format),
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(source) => source.reconfigure(&format),
+
+            #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+            PlatformCamera::Unsupported => Err(CameraError::InitializationError(
+                "Unsupported platform".to_string(),
+            )),
+        }?;
+
+        if let Some(controls) = controls {
+            match self.apply_controls(&controls) {
+                Ok(result) if !result.rejected.is_empty() => log::warn!(
+                    "Controls not restored after format change: {}",
+                    result.rejected.join(", ")
+                ),
+                Ok(_) => {}
+                Err(e) => log::warn!("Failed to restore controls after format change: {e}"),
+            }
+        }
+        Ok(selected)
+    }
+
     /// Apply camera controls
     ///
     /// # Errors
@@ -509,6 +999,8 @@ impl PlatformCamera {
             PlatformCamera::Linux(camera) => camera.apply_controls(controls),
 
             PlatformCamera::Mock(camera) => camera.apply_controls(controls),
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(source) => source.apply_controls(controls),
 
             #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
             PlatformCamera::Unsupported => Err(CameraError::InitializationError(
@@ -534,6 +1026,8 @@ impl PlatformCamera {
             PlatformCamera::Linux(camera) => camera.get_controls(),
 
             PlatformCamera::Mock(camera) => camera.get_controls(),
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(source) => source.get_controls(),
 
             #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
             PlatformCamera::Unsupported => Err(CameraError::InitializationError(
@@ -542,6 +1036,49 @@ impl PlatformCamera {
         }
     }
 
+    /// Focus on a point given in normalized coordinates (0.0-1.0, origin
+    /// top-left) using the driver's region autofocus
+    ///
+    /// Returns `false` when the backend has no region autofocus: only
+    /// `AVFoundation`'s focus point of interest is wired up so far.
+    ///
+    /// # Errors
+    /// Propagates any error from the platform camera while setting the point.
+    pub fn set_focus_point(&mut self, x: f32, y: f32) -> Result<bool, CameraError> {
+        let (x, y) = (x.clamp(0.0, 1.0), y.clamp(0.0, 1.0));
+        #[cfg(target_os = "macos")]
+        if let PlatformCamera::MacOS(camera) = self {
+            return camera.set_focus_point(x, y);
+        }
+        log::debug!("No region autofocus for point ({x:.2}, {y:.2}) on this backend");
+        Ok(false)
+    }
+
+    /// Physical rotation of the camera sensor, in degrees counter-clockwise
+    ///
+    /// Read from the driver where it is exposed (V4L2 sensor rotation on
+    /// Linux); `None` when it cannot be determined, which is the case for
+    /// Windows and macOS backends and most USB webcams.
+    pub fn sensor_rotation(&self) -> Option<u16> {
+        match self {
+            #[cfg(target_os = "windows")]
+            PlatformCamera::Windows(_) => None,
+
+            #[cfg(target_os = "macos")]
+            PlatformCamera::MacOS(_) => None,
+
+            #[cfg(target_os = "linux")]
+            PlatformCamera::Linux(camera) => camera.sensor_rotation(),
+
+            PlatformCamera::Mock(camera) => camera.sensor_rotation(),
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(_) => None,
+
+            #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+            PlatformCamera::Unsupported => None,
+        }
+    }
+
     /// Test camera capabilities
     ///
     /// # Errors
@@ -560,6 +1097,8 @@ impl PlatformCamera {
             PlatformCamera::Linux(camera) => camera.test_capabilities(),
 
             PlatformCamera::Mock(camera) => camera.test_capabilities(),
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(source) => source.test_capabilities(),
 
             #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
             PlatformCamera::Unsupported => Err(CameraError::InitializationError(
@@ -588,6 +1127,8 @@ impl PlatformCamera {
             PlatformCamera::Linux(camera) => camera.get_performance_metrics(),
 
             PlatformCamera::Mock(camera) => camera.get_performance_metrics(),
+            #[cfg(feature = "screen-capture")]
+            PlatformCamera::Screen(source) => source.get_performance_metrics(),
 
             #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
             PlatformCamera::Unsupported => Err(CameraError::InitializationError(
@@ -610,11 +1151,49 @@ pub struct CameraSystem;
 impl CameraSystem {
     /// List all available cameras on the current platform
     ///
+    /// The first call enumerates the devices; later calls return the
+    /// [cached](device_cache) list until a device monitor reports a change or
+    /// [`CameraSystem::refresh_devices`] is called.
+    ///
     /// # Errors
     /// Returns a [`CameraError::InitializationError`] if the current platform
     /// is unsupported, or propagates any error from the platform-specific camera
     /// enumeration.
     pub fn list_cameras() -> Result<Vec<CameraDeviceInfo>, CameraError> {
+        device_cache::devices_or_scan(Self::scan_cameras)
+    }
+
+    /// Drop all cached devices and capabilities and enumerate again
+    ///
+    /// Use this to pick up cameras plugged in while no device monitor is
+    /// running. Capabilities are probed again on their next query.
+    ///
+    /// # Errors
+    /// Returns the errors of [`CameraSystem::list_cameras`].
+    pub fn refresh_devices() -> Result<Vec<CameraDeviceInfo>, CameraError> {
+        device_cache::clear();
+        Self::list_cameras()
+    }
+
+    /// Probed capabilities of an open camera, cached by stable id
+    ///
+    /// The first query for a device calls
+    /// [`PlatformCamera::test_capabilities`]; later ones reuse the result
+    /// until the device is unplugged, changed or refreshed.
+    ///
+    /// # Errors
+    /// Returns the error of [`PlatformCamera::test_capabilities`].
+    pub fn capabilities(
+        device_id: &str,
+        camera: &PlatformCamera,
+    ) -> Result<crate::types::CameraCapabilities, CameraError> {
+        device_cache::capabilities_or_probe(&Self::stable_device_id(device_id), || {
+            camera.test_capabilities()
+        })
+    }
+
+    /// Enumerate cameras with the platform backend, bypassing the cache
+    fn scan_cameras() -> Result<Vec<CameraDeviceInfo>, CameraError> {
         match Platform::current() {
             #[cfg(target_os = "windows")]
             Platform::Windows => windows::list_cameras(),
@@ -631,6 +1210,179 @@ impl CameraSystem {
         }
     }
 
+    /// List cameras and, with `include_screens` and the `screen-capture`
+    /// feature, the displays that can be captured after them
+    ///
+    /// Screens carry [`DeviceTransport::Screen`] and open like cameras.
+    /// If they cannot be listed (e.g. no X11 session) a warning is logged
+    /// and only cameras are returned.
+    ///
+    /// [`DeviceTransport::Screen`]: crate::types::DeviceTransport::Screen
+    ///
+    /// # Errors
+    /// Returns the errors of [`CameraSystem::list_cameras`].
+    pub fn list_sources(include_screens: bool) -> Result<Vec<CameraDeviceInfo>, CameraError> {
+        #[cfg_attr(not(feature = "screen-capture"), allow(unused_mut))]
+        let mut devices = Self::list_cameras()?;
+
+        #[cfg(feature = "screen-capture")]
+        if include_screens {
+            match screen::list_screens() {
+                Ok(screens) => devices.extend(screens),
+                Err(e) => log::warn!("Screen sources unavailable: {e}"),
+            }
+        }
+        #[cfg(not(feature = "screen-capture"))]
+        let _ = include_screens;
+
+        Ok(devices)
+    }
+
+    /// Persistent key for a device, for storing per-camera settings
+    ///
+    /// Returns the device's [`stable_id`](CameraDeviceInfo::stable_id), or
+    /// `device_id` itself if no such device is currently connected. Devices
+    /// are enumerated again rather than read from the cached list, whose
+    /// numeric ids go stale when cameras are replugged without a running
+    /// [`DeviceMonitor`].
+    pub fn stable_device_id(device_id: &str) -> String {
+        device_cache::rescan(Self::scan_cameras)
+            .ok()
+            .and_then(|cameras| cameras.into_iter().find(|d| d.matches_id(device_id)))
+            .map_or_else(|| device_id.to_string(), |device| device.stable_id)
+    }
+
+    /// Resolve a numeric or stable device id to the current numeric id
+    ///
+    /// Numeric ids are returned unchanged without enumerating devices. Other
+    /// ids are looked up on a fresh enumeration, not the cached list, so a
+    /// camera renumbered while no [`DeviceMonitor`] runs is still found. Ids
+    /// that match no connected device are returned unchanged, so the
+    /// backend reports the usual "not found" error when opening them.
+    pub fn resolve_device_id(device_id: &str) -> String {
+        if device_id.parse::<u32>().is_ok() {
+            return device_id.to_string();
+        }
+
+        device_cache::rescan(Self::scan_cameras)
+            .ok()
+            .and_then(|cameras| cameras.into_iter().find(|d| d.stable_id == device_id))
+            .map_or_else(
+                || device_id.to_string(),
+                |device| {
+                    log::debug!("Resolved stable id {device_id} to device {}", device.id);
+                    device.id
+                },
+            )
+    }
+
+    /// Get capabilities for a device without opening it.
+    ///
+    /// Unlike [`PlatformCamera::test_capabilities`], this works while the
+    /// camera is in use by another process, at the cost of accuracy: only the
+    /// resolution and framerate limits come from the device's enumerated
+    /// formats, while control support and ranges are schema defaults. Check
+    /// [`CameraCapabilities::provenance`](crate::types::CameraCapabilities::provenance)
+    /// before relying on a field.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::InitializationError`] if enumeration fails or
+    /// no device with the given id exists.
+    pub fn get_capabilities_cached(
+        device_id: &str,
+    ) -> Result<crate::types::CameraCapabilities, CameraError> {
+        let device = Self::list_cameras()?
+            .into_iter()
+            .find(|d| d.matches_id(device_id))
+            .ok_or_else(|| {
+                CameraError::InitializationError(format!("Device not found: {device_id}"))
+            })?;
+
+        Ok(crate::types::CameraCapabilities::from_device_info(&device))
+    }
+
+    /// Check whether a camera is currently held by another process.
+    ///
+    /// On Linux this inspects open file descriptors under `/proc`, skipping
+    /// processes we are not permitted to inspect. Other platforms do not expose
+    /// this information and always report `false`.
+    pub fn is_device_in_use(device_id: &str) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            let path = if device_id.starts_with('/') {
+                device_id.to_string()
+            } else {
+                let device_id = Self::resolve_device_id(device_id);
+                format!("{}{device_id}", crate::constants::LINUX_VIDEO_DEVICE_PREFIX)
+            };
+            linux::utils::is_device_in_use(&path)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = device_id;
+            false
+        }
+    }
+
+    /// Capture one Z16 depth frame from a depth camera node
+    ///
+    /// Only V4L2 depth nodes (e.g. an Intel RealSense on Linux) are
+    /// supported; read distances with
+    /// [`CameraFrame::depth_at`](crate::types::CameraFrame::depth_at).
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::UnsupportedFormat`] on other platforms, or
+    /// propagates any error from the platform-specific depth capture.
+    pub fn capture_depth_frame(
+        device_id: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<CameraFrame, CameraError> {
+        #[cfg(target_os = "linux")]
+        {
+            let device_id = Self::resolve_device_id(device_id);
+            let index = device_id.parse::<u32>().map_err(|_| {
+                CameraError::InitializationError(format!("Device not found: {device_id}"))
+            })?;
+            linux::capture_depth_frame(index, width, height)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (device_id, width, height);
+            Err(CameraError::UnsupportedFormat(
+                "Depth capture is only supported on Linux (V4L2)".to_string(),
+            ))
+        }
+    }
+
+    /// Open several streams (e.g. color + depth) of one physical camera
+    ///
+    /// Each [`StreamSpec`](crate::types::StreamSpec) becomes one source; see
+    /// [`MultiStreamHandle`] for how nodes are picked and how frame sets are
+    /// captured.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::ConfigError`] for an empty or duplicated
+    /// stream list, or the error of the first stream that fails to open.
+    pub fn open_multistream(
+        device_id: &str,
+        streams: Vec<crate::types::StreamSpec>,
+    ) -> Result<MultiStreamHandle, CameraError> {
+        MultiStreamHandle::open(device_id, streams)
+    }
+
+    /// Open a camera and consume its frames as an async [`Stream`](futures_core::Stream)
+    ///
+    /// The stream owns its own device handle, independent of the shared camera
+    /// registry; dropping it stops the device. See [`FrameStream`] for
+    /// buffering and backpressure behavior.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError`] if the camera cannot be opened or started.
+    pub fn frame_stream(device_id: &str, format: CameraFormat) -> Result<FrameStream, CameraError> {
+        FrameStream::open(device_id, format)
+    }
+
     /// Initialize the camera system for the current platform
     ///
     /// # Errors
@@ -712,39 +1464,125 @@ impl CameraSystem {
 
     /// Test camera system functionality
     ///
+    /// Every available camera is opened and captures one frame, up to
+    /// [`SYSTEM_TEST_CONCURRENCY`] at a time and each within
+    /// [`SYSTEM_TEST_DEVICE_TIMEOUT_MS`]; see [`CameraSystem::test_devices`].
+    ///
     /// # Errors
-    /// This function always returns a test report and never returns an `Err`;
-    /// individual camera failures are captured in the report's `test_results`.
+    /// Returns an `Err` only if the cameras cannot be listed; individual
+    /// camera failures are captured in the report's `test_results`.
     pub fn test_system() -> Result<SystemTestResult, CameraError> {
-        let platform = Platform::current();
         let cameras = Self::list_cameras()?;
+        Ok(Self::test_devices(
+            &cameras,
+            std::time::Duration::from_millis(SYSTEM_TEST_DEVICE_TIMEOUT_MS),
+            SYSTEM_TEST_CONCURRENCY,
+        ))
+    }
 
-        let mut test_results = Vec::new();
-
-        // Test each camera
-        for camera_info in &cameras {
-            let test_result = if camera_info.is_available {
-                // Try to initialize camera
-                let params = CameraInitParams::new(camera_info.id.clone());
-                match PlatformCamera::new(params) {
-                    Ok(mut camera) => match camera.capture_frame() {
-                        Ok(_) => CameraTestResult::Success,
-                        Err(e) => CameraTestResult::CaptureError(e.to_string()),
-                    },
-                    Err(e) => CameraTestResult::InitError(e.to_string()),
+    /// Open each of `cameras` and capture a frame, `concurrency` at a time
+    ///
+    /// Each camera is tested on its own thread. One that has not finished
+    /// within `timeout` is reported as `CaptureError("timed out")` and left
+    /// behind: its thread runs until the driver returns, but no longer holds
+    /// a slot or delays the report. Results keep the order of `cameras`.
+    pub fn test_devices(
+        cameras: &[CameraDeviceInfo],
+        timeout: std::time::Duration,
+        concurrency: usize,
+    ) -> SystemTestResult {
+        use std::collections::{HashMap, VecDeque};
+        use std::sync::mpsc;
+        use std::time::Instant;
+
+        let started = Instant::now();
+        let mut results: Vec<Option<CameraTestResult>> = cameras
+            .iter()
+            .map(|camera| (!camera.is_available).then_some(CameraTestResult::NotAvailable))
+            .collect();
+        let mut queue: VecDeque<usize> = (0..cameras.len())
+            .filter(|&i| results[i].is_none())
+            .collect();
+        let mut running: HashMap<usize, Instant> = HashMap::new();
+        let (tx, rx) = mpsc::channel();
+        // Named after the caller, so test threads keep selecting the mock camera
+        let thread_name = format!(
+            "{}:camera-probe",
+            std::thread::current().name().unwrap_or("crabcamera")
+        );
+
+        loop {
+            while running.len() < concurrency.max(1) {
+                let Some(i) = queue.pop_front() else { break };
+                let device_id = cameras[i].id.clone();
+                let tx = tx.clone();
+                let spawned =
+                    std::thread::Builder::new()
+                        .name(thread_name.clone())
+                        .spawn(move || {
+                            // The receiver is gone once the test gave up on us
+                            let _ = tx.send((i, Self::test_device(device_id)));
+                        });
+                match spawned {
+                    Ok(_) => {
+                        running.insert(i, Instant::now() + timeout);
+                    }
+                    Err(e) => {
+                        results[i] = Some(CameraTestResult::InitError(format!(
+                            "Failed to start test thread: {e}"
+                        )));
+                    }
                 }
-            } else {
-                CameraTestResult::NotAvailable
+            }
+
+            let Some(&deadline) = running.values().min() else {
+                break;
             };
+            if let Ok((i, result)) =
+                rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            {
+                if running.remove(&i).is_some() {
+                    results[i] = Some(result);
+                }
+            }
 
-            test_results.push((camera_info.id.clone(), test_result));
+            let now = Instant::now();
+            running.retain(|&i, deadline| {
+                let expired = *deadline <= now;
+                if expired {
+                    log::warn!("Camera {} test timed out after {timeout:?}", cameras[i].id);
+                    results[i] = Some(CameraTestResult::CaptureError("timed out".to_string()));
+                }
+                !expired
+            });
         }
 
-        Ok(SystemTestResult {
-            platform,
+        SystemTestResult {
+            platform: Platform::current(),
             cameras_found: cameras.len(),
-            test_results,
-        })
+            test_results: cameras
+                .iter()
+                .zip(results)
+                .map(|(camera, result)| {
+                    (
+                        camera.id.clone(),
+                        result.unwrap_or(CameraTestResult::NotAvailable),
+                    )
+                })
+                .collect(),
+            elapsed_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+
+    /// Open `device_id` and capture one frame
+    fn test_device(device_id: String) -> CameraTestResult {
+        match PlatformCamera::new(CameraInitParams::new(device_id)) {
+            Ok(mut camera) => match camera.capture_frame() {
+                Ok(_) => CameraTestResult::Success,
+                Err(e) => CameraTestResult::CaptureError(e.to_string()),
+            },
+            Err(e) => CameraTestResult::InitError(e.to_string()),
+        }
     }
 }
 
@@ -768,6 +1606,9 @@ pub struct SystemTestResult {
     pub cameras_found: usize,
     /// Detailed results of camera tests.
     pub test_results: Vec<(String, CameraTestResult)>,
+    /// Wall-clock time the whole test took, in milliseconds.
+    #[serde(default)]
+    pub elapsed_ms: u64,
 }
 
 /// Individual camera test result
@@ -817,6 +1658,136 @@ pub mod optimizations {
     }
 }
 
+/// Renegotiate an open nokhwa camera to the best mode for `format`, or to
+/// exactly `format` when `exact` is set
+///
+/// The best mode is picked from the device's mode list by
+/// [`negotiate_format`] with `preference`; if the list is unavailable the
+/// driver's closest match is used. The stream is stopped for the switch and
+/// reopened if it was running. Returns the format the driver actually
+/// selected, whose `format_type` is the pixel format delivered.
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+pub(crate) fn renegotiate_format(
+    camera: &mut nokhwa::Camera,
+    format: &CameraFormat,
+    exact: bool,
+    preference: crate::types::PixelFormatPreference,
+) -> Result<CameraFormat, CameraError> {
+    use nokhwa::pixel_format::RgbFormat;
+    use nokhwa::utils::{RequestedFormat, RequestedFormatType, Resolution};
+
+    let negotiated = if exact {
+        None
+    } else {
+        let supported: Vec<CameraFormat> = camera
+            .compatible_camera_formats()
+            .map(|modes| modes.iter().map(format_of_mode).collect())
+            .unwrap_or_default();
+        negotiate_format(&supported, format, preference)
+    };
+    // A mode from the device's own list is requested as is
+    let exact = exact || negotiated.is_some();
+    let format = negotiated.map_or_else(|| format.clone(), |n| n.chosen);
+
+    let frame_format =
+        convert::frame_format_of(&format.format_type).unwrap_or(nokhwa::utils::FrameFormat::MJPEG);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // fps is small and positive
+    let fps = format.fps.round() as u32;
+    let mode = nokhwa::utils::CameraFormat::new(
+        Resolution::new(format.width, format.height),
+        frame_format,
+        fps,
+    );
+    let request = RequestedFormat::new::<RgbFormat>(if exact {
+        RequestedFormatType::Exact(mode)
+    } else {
+        RequestedFormatType::Closest(mode)
+    });
+
+    let was_streaming = camera.is_stream_open();
+    if was_streaming {
+        camera
+            .stop_stream()
+            .map_err(|e| CameraError::InitializationError(format!("Failed to stop stream: {e}")))?;
+    }
+
+    let negotiated = camera.set_camera_requset(request);
+
+    if was_streaming {
+        camera.open_stream().map_err(|e| {
+            CameraError::InitializationError(format!("Failed to restart stream: {e}"))
+        })?;
+    }
+
+    negotiated
+        .map(|mode| format_of_mode(&mode))
+        .map_err(|e| CameraError::InitializationError(format!("Failed to set format: {e}")))
+}
+
+/// Open a nokhwa camera with `backend`, or the platform default when `None`
+/// or `Auto`
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+pub(crate) fn open_nokhwa(
+    index: u32,
+    format: nokhwa::utils::RequestedFormat,
+    backend: Option<crate::types::CameraBackend>,
+) -> Result<nokhwa::Camera, nokhwa::NokhwaError> {
+    use crate::types::CameraBackend;
+    use nokhwa::utils::{ApiBackend, CameraIndex};
+
+    let api = match backend.unwrap_or_default() {
+        CameraBackend::Auto => return nokhwa::Camera::new(CameraIndex::Index(index), format),
+        CameraBackend::MediaFoundation => ApiBackend::MediaFoundation,
+        CameraBackend::DirectShow => {
+            return Err(nokhwa::NokhwaError::NotImplementedError(
+                "DirectShow backend".to_string(),
+            ))
+        }
+        CameraBackend::V4l2 => ApiBackend::Video4Linux,
+        CameraBackend::AvFoundation => ApiBackend::AVFoundation,
+    };
+    nokhwa::Camera::with_backend(CameraIndex::Index(index), format, api)
+}
+
+/// Backend an open nokhwa camera actually uses
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+pub(crate) fn backend_of(camera: &nokhwa::Camera) -> crate::types::CameraBackend {
+    use crate::types::CameraBackend;
+    use nokhwa::utils::ApiBackend;
+
+    match camera.backend() {
+        ApiBackend::MediaFoundation => CameraBackend::MediaFoundation,
+        ApiBackend::Video4Linux => CameraBackend::V4l2,
+        ApiBackend::AVFoundation => CameraBackend::AvFoundation,
+        _ => CameraBackend::Auto,
+    }
+}
+
+/// Capture time of a buffer a real backend just dequeued, in seconds
+///
+/// nokhwa drops the driver's per-buffer timestamp, so the backends read a
+/// monotonic clock as soon as `frame()` returns instead. Unlike
+/// [`CameraFrame::timestamp`] it never jumps with the wall clock; its epoch
+/// is the first call in the process.
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+pub(crate) fn dequeue_timestamp() -> f64 {
+    static CLOCK_ORIGIN: std::sync::LazyLock<std::time::Instant> =
+        std::sync::LazyLock::new(std::time::Instant::now);
+    CLOCK_ORIGIN.elapsed().as_secs_f64()
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+fn format_of_mode(mode: &nokhwa::utils::CameraFormat) -> CameraFormat {
+    #[allow(clippy::cast_precision_loss)] // frame rates are small integers
+    CameraFormat::new(mode.width(), mode.height(), mode.frame_rate() as f32)
+        .with_format_type(convert::fourcc_of(mode.format()).to_string())
+}
+
+fn with_content_hash(mut frame: CameraFrame) -> CameraFrame {
+    frame.record_content_hash();
+    frame
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -856,6 +1827,115 @@ mod tests {
         assert!(matches!(err, CameraError::CaptureError(_)));
     }
 
+    #[test]
+    fn test_mock_camera_live_fps_converges() {
+        let mut cam = MockCamera::new("mock-fps".to_string(), CameraFormat::new(640, 480, 50.0));
+
+        for _ in 0..30 {
+            cam.capture_frame().expect("capture should work");
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+
+        let metrics = cam.get_performance_metrics().expect("metrics should work");
+        // Sleep only guarantees a lower bound on the interval, so fps <= 50.
+        assert!(
+            metrics.fps_actual > 25.0 && metrics.fps_actual <= 51.0,
+            "fps {} should converge near 50",
+            metrics.fps_actual
+        );
+    }
+
+    #[test]
+    fn test_mock_camera_timestamp_source() {
+        let mut crate_clock =
+            MockCamera::new("mock-ts-crate".to_string(), CameraFormat::standard());
+        let frame = crate_clock.capture_frame().expect("capture should work");
+        assert!(frame.metadata.hardware_timestamp.is_none());
+
+        let mut driver = MockCamera::new("mock-ts-driver".to_string(), CameraFormat::standard())
+            .with_timestamp_source(TimestampSource::DriverHardware);
+        let first = driver
+            .capture_frame()
+            .expect("capture should work")
+            .metadata
+            .hardware_timestamp
+            .expect("driver timestamp should be populated");
+        let second = driver
+            .capture_frame()
+            .expect("capture should work")
+            .metadata
+            .hardware_timestamp
+            .expect("driver timestamp should be populated");
+        assert!(second >= first);
+    }
+
+    #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+    #[test]
+    fn test_dequeue_timestamp_is_monotonic() {
+        let first = dequeue_timestamp();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        assert!(dequeue_timestamp() > first);
+    }
+
+    #[test]
+    fn test_capture_records_camera_settings() {
+        let mut camera = PlatformCamera::Mock(MockCamera::new(
+            "mock-settings".to_string(),
+            CameraFormat::standard(),
+        ));
+        let controls = crate::types::CameraControls {
+            exposure_time: Some(0.02),
+            iso_sensitivity: Some(1600),
+            focus_distance: Some(0.4),
+            zoom: Some(1.5),
+            ..crate::types::CameraControls::default()
+        };
+        camera.apply_controls(&controls).expect("apply should work");
+
+        let metadata = camera
+            .capture_frame()
+            .expect("capture should work")
+            .metadata;
+        assert_eq!(metadata.exposure_time, Some(0.02));
+        assert_eq!(metadata.iso_sensitivity, Some(1600));
+        assert_eq!(metadata.focus_distance, Some(0.4));
+        assert_eq!(metadata.zoom, Some(1.5));
+        assert!(metadata.aperture.is_none());
+        assert!(metadata.capture_settings.is_some());
+    }
+
+    #[test]
+    fn test_mock_camera_raw_capture_is_mjpeg() {
+        let mut cam = MockCamera::new("mock-raw".to_string(), CameraFormat::standard());
+        let frame = cam.capture_frame_raw().expect("raw capture should work");
+
+        assert_eq!(frame.format, crate::constants::FORMAT_MJPEG);
+        assert!(frame.data.starts_with(&crate::constants::MJPEG_SIGNATURE));
+        let rgb = convert::to_rgb8(&frame.data, frame.width, frame.height, &frame.format)
+            .expect("raw frame should decode");
+        assert_eq!(rgb.len(), frame.width as usize * frame.height as usize * 3);
+    }
+
+    #[test]
+    fn test_platform_camera_validates_backend() {
+        use crate::types::CameraBackend;
+        std::env::set_var("CRABCAMERA_USE_MOCK", "1");
+
+        let params = CameraInitParams::new("pcam-backend".to_string());
+        let err = PlatformCamera::new(params.clone().with_backend(CameraBackend::DirectShow))
+            .err()
+            .expect("DirectShow is never available");
+        assert!(matches!(err, CameraError::UnsupportedOperation(_)));
+
+        let native = match Platform::current() {
+            Platform::Windows => CameraBackend::MediaFoundation,
+            Platform::MacOS => CameraBackend::AvFoundation,
+            _ => CameraBackend::V4l2,
+        };
+        let camera = PlatformCamera::new(params.with_backend(native)).expect("native backend");
+        assert_eq!(camera.backend(), None, "mock cameras have no backend");
+    }
+
     #[test]
     fn test_platform_camera_mock_end_to_end() {
         std::env::set_var("CRABCAMERA_USE_MOCK", "1");
@@ -886,6 +1966,11 @@ mod tests {
 
         let caps = camera.test_capabilities().expect("caps should work");
         assert!(caps.supports.auto_focus);
+        let exposure = caps
+            .control("exposure_time")
+            .expect("mock reports exposure");
+        assert_eq!(Some((exposure.min, exposure.max)), caps.exposure_range);
+        assert_eq!(caps.control("iso_sensitivity").unwrap().step, Some(1.0));
 
         let metrics = camera
             .get_performance_metrics()
@@ -933,6 +2018,96 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reconfigure_keeps_controls() {
+        let mut camera = PlatformCamera::Mock(MockCamera::new(
+            "mock-reconfigure".to_string(),
+            CameraFormat::standard(),
+        ));
+        let controls = crate::types::CameraControls {
+            brightness: Some(0.25),
+            ..Default::default()
+        };
+        camera.apply_controls(&controls).expect("apply controls");
+
+        camera
+            .reconfigure(CameraFormat::new(640, 480, 15.0))
+            .expect("reconfigure");
+
+        let PlatformCamera::Mock(ref mock) = camera else {
+            unreachable!("mock camera");
+        };
+        assert_eq!(mock.get_format().width, 640);
+        assert_eq!(mock.get_format().height, 480);
+        assert_eq!(
+            camera.get_controls().expect("controls").brightness,
+            Some(0.25)
+        );
+    }
+
+    #[test]
+    fn test_mock_camera_timed_mode_paces_frames() {
+        let device_id = "mock-timed";
+        crate::tests::set_mock_camera_mode(
+            device_id,
+            crate::tests::MockCaptureMode::Timed {
+                fps: 50.0,
+                jitter_ms: 0,
+            },
+        );
+        let mut cam = MockCamera::new(device_id.to_string(), CameraFormat::standard());
+
+        let start = std::time::Instant::now();
+        for _ in 0..5 {
+            cam.capture_frame().expect("timed mode delivers frames");
+        }
+        // Four 20 ms periods after the first, immediate frame
+        assert!(start.elapsed() >= std::time::Duration::from_millis(80));
+
+        crate::tests::set_mock_camera_mode(
+            device_id,
+            crate::tests::MockCaptureMode::Timed {
+                fps: 50.0,
+                jitter_ms: 5,
+            },
+        );
+        let mut cam = MockCamera::new(device_id.to_string(), CameraFormat::standard());
+        cam.capture_frame().expect("first frame");
+        let mut previous = std::time::Instant::now();
+        let mut total = std::time::Duration::ZERO;
+        for _ in 0..5 {
+            cam.capture_frame().expect("jittered frame");
+            let interval = previous.elapsed();
+            previous = std::time::Instant::now();
+            assert!(
+                interval >= std::time::Duration::from_millis(8),
+                "{interval:?}"
+            );
+            total += interval;
+        }
+        // Jitter stays around the nominal schedule instead of accumulating
+        assert!(total >= std::time::Duration::from_millis(90), "{total:?}");
+
+        crate::tests::set_mock_camera_mode(device_id, crate::tests::MockCaptureMode::Success);
+    }
+
+    #[test]
+    fn test_mock_camera_sequence_mode_scripts_failures() {
+        use crate::tests::MockCaptureMode::{Failure, Sequence, Success};
+
+        let device_id = "mock-sequence";
+        crate::tests::set_mock_camera_mode(
+            device_id,
+            Sequence(vec![Success, Success, Success, Failure]),
+        );
+        let mut cam = MockCamera::new(device_id.to_string(), CameraFormat::standard());
+
+        let results: Vec<bool> = (0..6).map(|_| cam.capture_frame().is_ok()).collect();
+        assert_eq!(results, vec![true, true, true, false, true, true]);
+
+        crate::tests::set_mock_camera_mode(device_id, Success);
+    }
+
     #[test]
     fn test_mock_camera_set_capture_mode_method() {
         let cam = MockCamera::new("mode-setter".to_string(), CameraFormat::standard());
@@ -940,4 +2115,38 @@ mod tests {
         // Behavior is sourced from global registry at capture time, so this asserts method call path only.
         assert_eq!(cam.get_device_id(), "mode-setter");
     }
+
+    #[test]
+    fn test_system_test_times_out_slow_devices() {
+        use crate::tests::{create_mock_device, set_mock_camera_mode, MockCaptureMode};
+
+        let mut cameras: Vec<CameraDeviceInfo> = ["systest-a", "systest-slow", "systest-b"]
+            .iter()
+            .map(|id| create_mock_device(id, id, Platform::current()))
+            .collect();
+        let mut unplugged = create_mock_device("systest-gone", "gone", Platform::current());
+        unplugged.is_available = false;
+        cameras.push(unplugged);
+        set_mock_camera_mode("systest-slow", MockCaptureMode::SlowCapture);
+
+        let report = CameraSystem::test_devices(&cameras, std::time::Duration::from_millis(60), 2);
+        set_mock_camera_mode("systest-slow", MockCaptureMode::Success);
+
+        let ids: Vec<&str> = report
+            .test_results
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .collect();
+        assert_eq!(
+            ids,
+            ["systest-a", "systest-slow", "systest-b", "systest-gone"]
+        );
+        let results: Vec<&CameraTestResult> = report.test_results.iter().map(|(_, r)| r).collect();
+        assert!(matches!(results[0], CameraTestResult::Success));
+        assert!(matches!(results[1], CameraTestResult::CaptureError(e) if e == "timed out"));
+        assert!(matches!(results[2], CameraTestResult::Success));
+        assert!(matches!(results[3], CameraTestResult::NotAvailable));
+        assert_eq!(report.cameras_found, 4);
+        assert!(report.elapsed_ms >= 60);
+    }
 }
@@ -0,0 +1,225 @@
+//! Several simultaneous streams from one physical camera
+//!
+//! Depth cameras and some webcams expose color, depth and infrared as
+//! separate streams. A [`MultiStreamHandle`] opens one source per
+//! [`StreamSpec`] and captures them together as a frame set, each frame
+//! tagged with its [`StreamRole`] in `metadata.stream_role`.
+//!
+//! On Linux every stream is usually its own `/dev/videoN` node. Specs
+//! without an explicit `device_id` are matched to the first sibling node of
+//! the requested device (same USB device in sysfs) that advertises the
+//! spec's pixel format, falling back to the requested device itself. Z16
+//! depth streams are read directly from V4L2 via
+//! [`CameraSystem::capture_depth_frame`]; every other stream is an ordinary
+//! [`PlatformCamera`].
+//!
+//! Frames of a set are captured concurrently, one thread per stream, so
+//! their timestamps differ by roughly one frame interval at most;
+//! [`MultiStreamHandle::last_skew_ms`] reports the actual spread.
+
+use crate::errors::CameraError;
+use crate::platform::{CameraSystem, PlatformCamera};
+use crate::types::{CameraFrame, CameraInitParams, StreamRole, StreamSpec};
+
+/// Where one stream's frames come from
+enum StreamSource {
+    Camera(Box<PlatformCamera>),
+    Depth {
+        device_id: String,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl StreamSource {
+    fn capture(&mut self) -> Result<CameraFrame, CameraError> {
+        match self {
+            Self::Camera(camera) => camera.capture_frame(),
+            Self::Depth {
+                device_id,
+                width,
+                height,
+            } => CameraSystem::capture_depth_frame(device_id, *width, *height),
+        }
+    }
+}
+
+/// Open streams of one physical camera, captured together as frame sets
+///
+/// Created by [`CameraSystem::open_multistream`]. Dropping the handle
+/// releases every stream.
+pub struct MultiStreamHandle {
+    streams: Vec<(StreamSpec, StreamSource)>,
+    last_skew_ms: Option<i64>,
+}
+
+impl MultiStreamHandle {
+    /// Open every stream in `specs` from the device `device_id`
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::ConfigError`] if `specs` is empty or lists a
+    /// role twice, or the error of the first stream that fails to open.
+    pub fn open(device_id: &str, specs: Vec<StreamSpec>) -> Result<Self, CameraError> {
+        if specs.is_empty() {
+            return Err(CameraError::ConfigError(
+                "At least one stream is required".to_string(),
+            ));
+        }
+        for (i, spec) in specs.iter().enumerate() {
+            if specs[..i].iter().any(|other| other.role == spec.role) {
+                return Err(CameraError::ConfigError(format!(
+                    "Stream role {:?} requested more than once",
+                    spec.role
+                )));
+            }
+        }
+
+        let mut streams = Vec::with_capacity(specs.len());
+        for spec in specs {
+            let node = spec
+                .device_id
+                .clone()
+                .unwrap_or_else(|| resolve_node(device_id, &spec));
+            log::info!("Opening {:?} stream on device {node}", spec.role);
+
+            let source = if spec.is_depth() {
+                StreamSource::Depth {
+                    device_id: node,
+                    width: spec.format.width,
+                    height: spec.format.height,
+                }
+            } else {
+                let params = CameraInitParams::new(node).with_format(spec.format.clone());
+                let mut camera = PlatformCamera::new(params)?;
+                camera.start_stream()?;
+                StreamSource::Camera(Box::new(camera))
+            };
+            streams.push((spec, source));
+        }
+
+        Ok(Self {
+            streams,
+            last_skew_ms: None,
+        })
+    }
+
+    /// Capture one frame from every stream, in the order the streams were
+    /// requested
+    ///
+    /// # Errors
+    /// Returns the first capture error; the frames of the other streams in
+    /// that set are discarded.
+    pub fn capture_frame_set(&mut self) -> Result<Vec<CameraFrame>, CameraError> {
+        let results: Vec<Result<CameraFrame, CameraError>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .streams
+                .iter_mut()
+                .map(|(spec, source)| {
+                    let role = spec.role;
+                    scope.spawn(move || {
+                        source.capture().map(|mut frame| {
+                            frame.metadata.stream_role = Some(role);
+                            frame
+                        })
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle.join().unwrap_or_else(|_| {
+                        Err(CameraError::CaptureError(
+                            "Stream capture thread panicked".to_string(),
+                        ))
+                    })
+                })
+                .collect()
+        });
+
+        let frames = results.into_iter().collect::<Result<Vec<_>, _>>()?;
+        let earliest = frames.iter().map(|f| f.timestamp).min();
+        let latest = frames.iter().map(|f| f.timestamp).max();
+        self.last_skew_ms = earliest
+            .zip(latest)
+            .map(|(earliest, latest)| (latest - earliest).num_milliseconds());
+        Ok(frames)
+    }
+
+    /// Roles of the open streams, in capture order
+    pub fn roles(&self) -> Vec<StreamRole> {
+        self.streams.iter().map(|(spec, _)| spec.role).collect()
+    }
+
+    /// Spread between the earliest and latest frame timestamps of the last
+    /// frame set, `None` before the first set
+    pub fn last_skew_ms(&self) -> Option<i64> {
+        self.last_skew_ms
+    }
+}
+
+impl std::fmt::Debug for MultiStreamHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiStreamHandle")
+            .field("roles", &self.roles())
+            .field("last_skew_ms", &self.last_skew_ms)
+            .finish()
+    }
+}
+
+/// Node of `device_id` that should carry `spec`
+#[cfg(target_os = "linux")]
+fn resolve_node(device_id: &str, spec: &StreamSpec) -> String {
+    let device_id = CameraSystem::resolve_device_id(device_id);
+    let (Ok(index), Some(fourcc)) = (device_id.parse::<u32>(), spec.format.fourcc()) else {
+        return device_id;
+    };
+    crate::platform::linux::sibling_nodes(index)
+        .into_iter()
+        .find(|&node| crate::platform::linux::node_formats(node).contains(&fourcc))
+        .map_or(device_id, |node| node.to_string())
+}
+
+/// Node of `device_id` that should carry `spec`
+#[cfg(not(target_os = "linux"))]
+fn resolve_node(device_id: &str, _spec: &StreamSpec) -> String {
+    device_id.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CameraFormat;
+
+    #[test]
+    fn test_frame_set_tags_each_stream() {
+        let mut handle = MultiStreamHandle::open(
+            "multi-test",
+            vec![
+                StreamSpec::new(StreamRole::Color, CameraFormat::new(320, 240, 30.0))
+                    .with_device_id("multi-color".to_string()),
+                StreamSpec::new(StreamRole::Infrared, CameraFormat::new(320, 240, 30.0))
+                    .with_device_id("multi-ir".to_string()),
+            ],
+        )
+        .expect("mock streams should open");
+        assert_eq!(handle.roles(), [StreamRole::Color, StreamRole::Infrared]);
+        assert!(handle.last_skew_ms().is_none());
+
+        let set = handle.capture_frame_set().expect("frame set");
+        assert_eq!(set.len(), 2);
+        assert_eq!(set[0].metadata.stream_role, Some(StreamRole::Color));
+        assert_eq!(set[0].device_id, "multi-color");
+        assert_eq!(set[1].metadata.stream_role, Some(StreamRole::Infrared));
+        assert!(handle.last_skew_ms().is_some_and(|skew| skew >= 0));
+    }
+
+    #[test]
+    fn test_open_rejects_empty_and_duplicate_roles() {
+        assert!(MultiStreamHandle::open("multi-test", Vec::new()).is_err());
+
+        let color = StreamSpec::new(StreamRole::Color, CameraFormat::new(320, 240, 30.0));
+        let err = MultiStreamHandle::open("multi-test", vec![color.clone(), color])
+            .expect_err("duplicate roles are rejected");
+        assert!(matches!(err, CameraError::ConfigError(_)));
+    }
+}
@@ -0,0 +1,256 @@
+//! Choosing a device mode for a requested format
+//!
+//! Webcams commonly offer 1920x1080 at 30 fps only as MJPEG, while raw YUYV
+//! at that size runs at 5 fps or less. Asking the driver for the "closest"
+//! mode can silently land on the slow one, so backends pick an entry of the
+//! device's own mode list with [`negotiate_format`] and request it exactly.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{FORMAT_FPS_TOLERANCE, MAX_RESOLUTION_MIN_FPS};
+use crate::types::{CameraFormat, FourCC, PixelFormatPreference};
+
+/// Mode chosen for a requested format, with the alternative it was weighed
+/// against
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormatNegotiation {
+    /// Mode to use; `format_type` is its pixel format.
+    pub chosen: CameraFormat,
+    /// Best compressed (MJPEG) mode at the chosen resolution, if any.
+    pub compressed: Option<CameraFormat>,
+    /// Best uncompressed mode at the chosen resolution, if any.
+    pub uncompressed: Option<CameraFormat>,
+}
+
+impl FormatNegotiation {
+    /// Whether the chosen mode runs slower than `requested_fps`
+    pub fn below_requested_fps(&self, requested_fps: f32) -> bool {
+        self.chosen.fps + FORMAT_FPS_TOLERANCE < requested_fps
+    }
+}
+
+/// Pick the entry of `supported` that best serves `requested`
+///
+/// The resolution closest to the requested one (by pixel count) is chosen
+/// first. Among its modes, the best of each kind is the slowest one meeting
+/// the requested frame rate, or the fastest if none does. Between the two
+/// kinds, `preference` decides; [`PixelFormatPreference::Auto`] takes
+/// uncompressed unless it falls short of the requested frame rate and the
+/// compressed mode is faster.
+///
+/// A `format_type` other than the default `RGB8` is a hard requirement:
+/// only modes of that pixel format are considered, when the device has any.
+/// Otherwise modes the backends cannot decode (e.g. depth) are skipped.
+///
+/// Returns `None` if `supported` is empty.
+pub fn negotiate_format(
+    supported: &[CameraFormat],
+    requested: &CameraFormat,
+    preference: PixelFormatPreference,
+) -> Option<FormatNegotiation> {
+    let explicit = fourcc(requested).filter(|f| *f != FourCC::RGB8);
+    let candidates: Vec<&CameraFormat> = match explicit {
+        Some(wanted)
+            if supported
+                .iter()
+                .any(|m| fourcc(m).as_ref() == Some(&wanted)) =>
+        {
+            supported
+                .iter()
+                .filter(|m| fourcc(m).as_ref() == Some(&wanted))
+                .collect()
+        }
+        _ => supported
+            .iter()
+            .filter(|m| super::convert::frame_format_of(&m.format_type).is_some())
+            .collect(),
+    };
+
+    let target = pixel_count(requested);
+    let (width, height) = candidates
+        .iter()
+        .map(|m| (m.width, m.height))
+        .min_by_key(|&(w, h)| (u64::from(w) * u64::from(h)).abs_diff(target))?;
+    let (compressed, uncompressed): (Vec<&CameraFormat>, Vec<&CameraFormat>) = candidates
+        .into_iter()
+        .filter(|m| m.width == width && m.height == height)
+        .partition(|m| fourcc(m).is_some_and(|f| f.is_compressed()));
+
+    let compressed = best_rate(&compressed, requested.fps);
+    let uncompressed = best_rate(&uncompressed, requested.fps);
+    let chosen = match preference {
+        PixelFormatPreference::Compressed => compressed.or(uncompressed),
+        PixelFormatPreference::Uncompressed => uncompressed.or(compressed),
+        PixelFormatPreference::Auto => match (uncompressed, compressed) {
+            (Some(raw), Some(mjpeg))
+                if raw.fps + FORMAT_FPS_TOLERANCE < requested.fps && mjpeg.fps > raw.fps =>
+            {
+                Some(mjpeg)
+            }
+            (Some(raw), _) => Some(raw),
+            (None, mjpeg) => mjpeg,
+        },
+    }?;
+
+    Some(FormatNegotiation {
+        chosen: chosen.clone(),
+        compressed: compressed.cloned(),
+        uncompressed: uncompressed.cloned(),
+    })
+}
+
+/// Largest mode of `supported` by pixel count
+///
+/// Modes reaching [`MAX_RESOLUTION_MIN_FPS`] are preferred, so a sensor's
+/// 1 fps full-resolution mode is only picked when nothing else reaches it.
+/// Among modes of the same size the fastest wins, then uncompressed ones.
+/// Modes the backends cannot decode (e.g. depth) are skipped.
+///
+/// Returns `None` if no mode can be decoded.
+pub fn max_resolution_format(supported: &[CameraFormat]) -> Option<CameraFormat> {
+    let decodable: Vec<&CameraFormat> = supported
+        .iter()
+        .filter(|m| super::convert::frame_format_of(&m.format_type).is_some())
+        .collect();
+    let fast_enough = |m: &&&CameraFormat| m.fps + FORMAT_FPS_TOLERANCE >= MAX_RESOLUTION_MIN_FPS;
+    let candidates: Vec<&CameraFormat> = if decodable.iter().any(|m| fast_enough(&m)) {
+        decodable.into_iter().filter(|m| fast_enough(&m)).collect()
+    } else {
+        decodable
+    };
+
+    candidates
+        .into_iter()
+        .max_by(|a, b| {
+            pixel_count(a)
+                .cmp(&pixel_count(b))
+                .then(a.fps.total_cmp(&b.fps))
+                .then_with(|| {
+                    let compressed =
+                        |m: &CameraFormat| fourcc(m).is_some_and(|f| f.is_compressed());
+                    compressed(b).cmp(&compressed(a))
+                })
+        })
+        .cloned()
+}
+
+fn fourcc(mode: &CameraFormat) -> Option<FourCC> {
+    mode.format_type.parse().ok()
+}
+
+fn pixel_count(format: &CameraFormat) -> u64 {
+    u64::from(format.width) * u64::from(format.height)
+}
+
+/// Slowest mode reaching `fps`, or the fastest mode if none does
+fn best_rate<'a>(modes: &[&'a CameraFormat], fps: f32) -> Option<&'a CameraFormat> {
+    let by_rate = |a: &&&CameraFormat, b: &&&CameraFormat| a.fps.total_cmp(&b.fps);
+    modes
+        .iter()
+        .filter(|m| m.fps + FORMAT_FPS_TOLERANCE >= fps)
+        .min_by(by_rate)
+        .or_else(|| modes.iter().max_by(by_rate))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode(width: u32, height: u32, fps: f32, format_type: &str) -> CameraFormat {
+        CameraFormat::new(width, height, fps).with_format_type(format_type.to_string())
+    }
+
+    /// Typical UVC webcam: YUYV is fast only at 640x480
+    fn webcam() -> Vec<CameraFormat> {
+        vec![
+            mode(640, 480, 30.0, "YUYV"),
+            mode(1920, 1080, 5.0, "YUYV"),
+            mode(640, 480, 30.0, "MJPEG"),
+            mode(1920, 1080, 15.0, "MJPEG"),
+            mode(1920, 1080, 30.0, "MJPEG"),
+            mode(1920, 1080, 30.0, "Z16"),
+        ]
+    }
+
+    #[test]
+    fn test_auto_prefers_mjpeg_when_yuyv_is_too_slow() {
+        let requested = CameraFormat::new(1920, 1080, 30.0);
+        let result = negotiate_format(&webcam(), &requested, PixelFormatPreference::Auto).unwrap();
+        assert_eq!(result.chosen, mode(1920, 1080, 30.0, "MJPEG"));
+        assert_eq!(result.uncompressed, Some(mode(1920, 1080, 5.0, "YUYV")));
+        assert!(!result.below_requested_fps(30.0));
+
+        // Uncompressed wins where it keeps up
+        let small = CameraFormat::new(640, 480, 30.0);
+        let result = negotiate_format(&webcam(), &small, PixelFormatPreference::Auto).unwrap();
+        assert_eq!(result.chosen.format_type, "YUYV");
+    }
+
+    #[test]
+    fn test_preference_overrides_auto() {
+        let requested = CameraFormat::new(1920, 1080, 30.0);
+        let raw =
+            negotiate_format(&webcam(), &requested, PixelFormatPreference::Uncompressed).unwrap();
+        assert_eq!(raw.chosen, mode(1920, 1080, 5.0, "YUYV"));
+        assert!(raw.below_requested_fps(30.0));
+
+        let small = CameraFormat::new(640, 480, 30.0);
+        let mjpeg = negotiate_format(&webcam(), &small, PixelFormatPreference::Compressed).unwrap();
+        assert_eq!(mjpeg.chosen.format_type, "MJPEG");
+    }
+
+    #[test]
+    fn test_explicit_format_type_is_required() {
+        let requested = mode(1920, 1080, 30.0, "YUY2");
+        let result = negotiate_format(&webcam(), &requested, PixelFormatPreference::Auto).unwrap();
+        assert_eq!(result.chosen, mode(1920, 1080, 5.0, "YUYV"));
+
+        // Formats the device lacks are ignored
+        let requested = mode(1920, 1080, 30.0, "NV12");
+        let result = negotiate_format(&webcam(), &requested, PixelFormatPreference::Auto).unwrap();
+        assert_eq!(result.chosen.format_type, "MJPEG");
+    }
+
+    #[test]
+    fn test_picks_closest_resolution_and_slowest_sufficient_rate() {
+        let requested = CameraFormat::new(1280, 720, 15.0);
+        let result = negotiate_format(&webcam(), &requested, PixelFormatPreference::Auto).unwrap();
+        // 640x480 is closer to 720p by pixel count than 1080p
+        assert_eq!(result.chosen, mode(640, 480, 30.0, "YUYV"));
+
+        let requested = CameraFormat::new(1920, 1080, 12.0);
+        let result =
+            negotiate_format(&webcam(), &requested, PixelFormatPreference::Compressed).unwrap();
+        assert!((result.chosen.fps - 15.0).abs() < f32::EPSILON);
+
+        assert!(negotiate_format(&[], &requested, PixelFormatPreference::Auto).is_none());
+    }
+
+    #[test]
+    fn test_max_resolution_prefers_usable_frame_rates() {
+        assert_eq!(
+            max_resolution_format(&webcam()),
+            Some(mode(1920, 1080, 30.0, "MJPEG"))
+        );
+
+        // A 1 fps sensor mode loses to a usable one unless it is all there is
+        let mut modes = webcam();
+        modes.push(mode(4000, 3000, 1.0, "YUYV"));
+        assert_eq!(max_resolution_format(&modes).map(|m| m.width), Some(1920));
+        let slow = [mode(4000, 3000, 1.0, "YUYV"), mode(640, 480, 2.0, "YUYV")];
+        assert_eq!(
+            max_resolution_format(&slow),
+            Some(mode(4000, 3000, 1.0, "YUYV"))
+        );
+
+        // Same size and rate: uncompressed wins
+        let tie = [
+            mode(1280, 720, 30.0, "MJPEG"),
+            mode(1280, 720, 30.0, "YUYV"),
+        ];
+        assert_eq!(max_resolution_format(&tie).unwrap().format_type, "YUYV");
+
+        assert!(max_resolution_format(&[mode(640, 480, 30.0, "Z16")]).is_none());
+    }
+}
@@ -0,0 +1,125 @@
+//! Per-camera frame orientation
+//!
+//! Front-facing cameras usually need mirroring for a natural preview, and
+//! some mounts need the image flipped or rotated. Once a [`FrameTransform`]
+//! is set for a device via [`set_orientation`], every frame returned by
+//! [`PlatformCamera::capture_frame`](crate::platform::PlatformCamera::capture_frame)
+//! and every frame buffered by the frame callback is transformed before it
+//! reaches the caller.
+//!
+//! Independently, [`auto_orient`] undoes the mounting rotation a device
+//! reports for its sensor (currently the V4L2 camera-sensor-rotation control
+//! on Linux); devices that report nothing are left untouched.
+
+use crate::types::{CameraFrame, FrameTransform};
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+static ORIENTATIONS: LazyLock<RwLock<HashMap<String, FrameTransform>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Set the transform for `device_id`; an identity transform clears it
+pub fn set_orientation(device_id: &str, transform: FrameTransform) {
+    if let Ok(mut orientations) = ORIENTATIONS.write() {
+        if transform.is_identity() {
+            orientations.remove(device_id);
+        } else {
+            orientations.insert(device_id.to_string(), transform);
+        }
+    }
+}
+
+/// Transform currently set for `device_id` (identity when none)
+pub fn get_orientation(device_id: &str) -> FrameTransform {
+    ORIENTATIONS
+        .read()
+        .ok()
+        .and_then(|orientations| orientations.get(device_id).copied())
+        .unwrap_or_default()
+}
+
+/// Clockwise rotation that turns a frame from a sensor mounted
+/// `sensor_rotation` degrees counter-clockwise upright
+///
+/// `None` unless the sensor rotation is a multiple of 90 degrees.
+pub fn upright_rotation(sensor_rotation: u16) -> Option<u16> {
+    let degrees = sensor_rotation % 360;
+    (degrees % 90 == 0).then(|| (360 - degrees) % 360)
+}
+
+/// Rotate a frame upright for a sensor mounted `sensor_rotation` degrees
+/// counter-clockwise, recording the correction in
+/// `metadata.applied_rotation`
+///
+/// A no-op when the rotation is unknown (`None`) or not a multiple of 90
+/// degrees, or when the frame format cannot be rotated.
+pub fn auto_orient(frame: &mut CameraFrame, sensor_rotation: Option<u16>) {
+    let Some(correction) = sensor_rotation.and_then(upright_rotation) else {
+        return;
+    };
+    match frame.rotate(correction) {
+        Ok(()) => frame.metadata.applied_rotation = Some(correction),
+        Err(e) => log::debug!("Frame from {} left unrotated: {e}", frame.device_id),
+    }
+}
+
+/// Apply the device's transform, if any, to a frame
+///
+/// Frames in formats that cannot be transformed (YUV, MJPEG) are passed
+/// through unchanged.
+pub(crate) fn orient_frame(mut frame: CameraFrame) -> CameraFrame {
+    let transform = get_orientation(&frame.device_id);
+    if let Err(e) = frame.apply_transform(transform) {
+        log::debug!("Frame from {} left unoriented: {e}", frame.device_id);
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FlipAxis;
+
+    #[test]
+    fn test_orient_frame_only_when_set() {
+        let frame = CameraFrame::new(vec![1, 2, 3, 4, 5, 6], 2, 1, "orient-dev".to_string());
+        assert_eq!(orient_frame(frame.clone()).data, frame.data);
+
+        let mirror = FrameTransform {
+            flip: Some(FlipAxis::Horizontal),
+            rotation_degrees: 0,
+        };
+        set_orientation("orient-dev", mirror);
+        assert_eq!(get_orientation("orient-dev"), mirror);
+        let mirrored = orient_frame(frame.clone());
+        assert_eq!(mirrored.data, [4, 5, 6, 1, 2, 3]);
+        assert_eq!(mirrored.metadata.orientation, Some(mirror));
+
+        let mjpeg = frame.clone().with_format("MJPEG".to_string());
+        assert_eq!(orient_frame(mjpeg.clone()).data, mjpeg.data);
+
+        set_orientation("orient-dev", FrameTransform::default());
+        assert!(get_orientation("orient-dev").is_identity());
+        assert_eq!(orient_frame(frame.clone()).data, frame.data);
+    }
+
+    #[test]
+    fn test_auto_orient_corrects_known_rotation_only() {
+        assert_eq!(upright_rotation(0), Some(0));
+        assert_eq!(upright_rotation(90), Some(270));
+        assert_eq!(upright_rotation(270), Some(90));
+        assert_eq!(upright_rotation(45), None);
+
+        let frame = CameraFrame::new(vec![7; 4 * 2 * 3], 4, 2, "auto-orient".to_string());
+
+        let mut unknown = frame.clone();
+        auto_orient(&mut unknown, None);
+        assert_eq!((unknown.width, unknown.height), (4, 2));
+        assert!(unknown.metadata.applied_rotation.is_none());
+
+        let mut rotated = frame;
+        auto_orient(&mut rotated, Some(90));
+        assert_eq!((rotated.width, rotated.height), (2, 4));
+        assert_eq!(rotated.metadata.applied_rotation, Some(270));
+    }
+}
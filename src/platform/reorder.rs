@@ -0,0 +1,173 @@
+//! Resequencing of frames that arrive out of timestamp order
+//!
+//! Under CPU load, callback threads can deliver frames slightly out of order
+//! relative to their capture timestamps, which muxers reject as timestamps
+//! going backwards. A [`ReorderBuffer`] holds up to `window` frames, sorted
+//! by hardware timestamp (falling back to [`CameraFrame::timestamp`] for
+//! cameras opened without [`TimestampSource::DriverHardware`]; see
+//! [`FrameMetadata::hardware_timestamp`]), and releases the oldest once the
+//! window is full.
+//!
+//! Released frames are guaranteed to be in non-decreasing timestamp order:
+//! a frame that arrives after a later-stamped frame was already released is
+//! too late to be placed and is dropped (counted in
+//! [`ReorderBuffer::late`]).
+//!
+//! ## Latency
+//!
+//! Every frame waits until `window` newer frames have arrived, so delivery
+//! is delayed by `window` frame intervals (e.g. 3 frames at 30 fps adds
+//! about 100 ms). A window of 0 adds no latency and only drops late frames.
+//!
+//! [`FrameMetadata::hardware_timestamp`]: crate::types::FrameMetadata::hardware_timestamp
+//! [`TimestampSource::DriverHardware`]: crate::types::TimestampSource::DriverHardware
+
+use std::collections::VecDeque;
+
+use crate::types::CameraFrame;
+
+/// Bounded buffer that releases frames in timestamp order
+#[derive(Debug)]
+pub struct ReorderBuffer {
+    pending: VecDeque<(f64, CameraFrame)>,
+    window: usize,
+    last_released: Option<f64>,
+    reordered: u64,
+    late: u64,
+}
+
+impl ReorderBuffer {
+    /// Create an empty buffer that holds back up to `window` frames
+    pub fn new(window: usize) -> Self {
+        Self {
+            pending: VecDeque::with_capacity(window + 1),
+            window,
+            last_released: None,
+            reordered: 0,
+            late: 0,
+        }
+    }
+
+    /// Add a frame and return the frames now ready for delivery, oldest first
+    pub fn push(&mut self, frame: CameraFrame) -> Vec<CameraFrame> {
+        let key = sort_key(&frame);
+        if self.last_released.is_some_and(|last| key < last) {
+            self.late += 1;
+            log::debug!(
+                "Dropping late frame {} from {} ({} dropped)",
+                frame.id,
+                frame.device_id,
+                self.late
+            );
+            return Vec::new();
+        }
+
+        let at = self.pending.partition_point(|(pending, _)| *pending <= key);
+        if at < self.pending.len() {
+            self.reordered += 1;
+        }
+        self.pending.insert(at, (key, frame));
+
+        let ready = self.pending.len().saturating_sub(self.window);
+        self.release(ready)
+    }
+
+    /// Release every held frame, oldest first (e.g. when capture stops)
+    pub fn flush(&mut self) -> Vec<CameraFrame> {
+        self.release(self.pending.len())
+    }
+
+    fn release(&mut self, count: usize) -> Vec<CameraFrame> {
+        let released: Vec<(f64, CameraFrame)> = self.pending.drain(..count).collect();
+        if let Some((key, _)) = released.last() {
+            self.last_released = Some(*key);
+        }
+        released.into_iter().map(|(_, frame)| frame).collect()
+    }
+
+    /// Number of frames currently held back
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether no frames are held back
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Maximum number of frames held back
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Frames that arrived ahead of an earlier-stamped frame and were moved
+    pub fn reordered(&self) -> u64 {
+        self.reordered
+    }
+
+    /// Frames dropped because a later-stamped frame had already been released
+    pub fn late(&self) -> u64 {
+        self.late
+    }
+}
+
+/// Ordering key in seconds: the hardware timestamp when present
+fn sort_key(frame: &CameraFrame) -> f64 {
+    frame.metadata.hardware_timestamp.unwrap_or_else(|| {
+        #[allow(clippy::cast_precision_loss)] // microsecond precision is plenty
+        let micros = frame.timestamp.timestamp_micros() as f64;
+        micros / 1_000_000.0
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(hardware_timestamp: f64) -> CameraFrame {
+        let mut frame = CameraFrame::new(vec![0; 3], 1, 1, "reorder-test".to_string());
+        frame.metadata.hardware_timestamp = Some(hardware_timestamp);
+        frame
+    }
+
+    fn stamps(frames: &[CameraFrame]) -> Vec<f64> {
+        frames
+            .iter()
+            .filter_map(|f| f.metadata.hardware_timestamp)
+            .collect()
+    }
+
+    #[test]
+    fn test_reorder_buffer_sorts_within_window() {
+        let mut buffer = ReorderBuffer::new(2);
+        let mut out = Vec::new();
+        for t in [1.0, 3.0, 2.0, 4.0, 6.0, 5.0] {
+            out.extend(buffer.push(frame(t)));
+        }
+        assert_eq!(buffer.len(), 2);
+        out.extend(buffer.flush());
+
+        assert_eq!(stamps(&out), [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(buffer.reordered(), 2);
+        assert_eq!(buffer.late(), 0);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_reorder_buffer_drops_frames_older_than_released() {
+        let mut buffer = ReorderBuffer::new(1);
+        let mut out = Vec::new();
+        for t in [1.0, 2.0, 3.0, 1.5, 4.0] {
+            out.extend(buffer.push(frame(t)));
+        }
+        out.extend(buffer.flush());
+
+        assert_eq!(stamps(&out), [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(buffer.late(), 1);
+
+        let mut passthrough = ReorderBuffer::new(0);
+        assert_eq!(passthrough.push(frame(2.0)).len(), 1);
+        assert!(passthrough.push(frame(1.0)).is_empty());
+        assert_eq!(passthrough.late(), 1);
+    }
+}
@@ -0,0 +1,604 @@
+//! Screen capture as a video source (`screen-capture` feature)
+//!
+//! Displays are grabbed by an `ffmpeg` child process using the platform's
+//! screen grabber (`x11grab` on Linux, `avfoundation` on macOS, `gdigrab`
+//! on Windows). ffmpeg is not bundled and never looked up on `PATH`; it runs
+//! from the path configured in [`crate::ffmpeg`], and without one no screens
+//! are listed. It scales them to the requested format and pipes them back
+//! as raw RGB. A reader thread keeps the newest frame, so a
+//! [`ScreenSource`] opened through [`PlatformCamera`](super::PlatformCamera)
+//! records, previews and streams like a camera.
+//!
+//! Screens have ids starting with `SCREEN_ID_PREFIX` (`screen:0`,
+//! `screen:1`, ...), as listed by [`list_screens`]. Individual windows are
+//! not enumerated or capturable; only whole displays are. On Windows the
+//! whole virtual desktop is a single screen.
+//!
+//! Permissions:
+//! - macOS: the host app needs Screen Recording access (System Settings >
+//!   Privacy & Security > Screen Recording). Without it, frames show only
+//!   the wallpaper and menu bar.
+//! - Linux: an X11 session is required. Under Wayland only XWayland
+//!   windows are visible.
+//! - Windows: nothing is required. Protected content and the secure
+//!   desktop (UAC prompts) capture as black.
+
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use super::metrics::PerfTracker;
+use super::FrameCallback;
+#[cfg(target_os = "linux")]
+use crate::constants::SCREEN_XRANDR;
+use crate::constants::{SCREEN_DEFAULT_FPS, SCREEN_FRAME_TIMEOUT_MS, SCREEN_ID_PREFIX};
+use crate::errors::CameraError;
+use crate::ffmpeg::ffmpeg_path;
+use crate::types::{
+    CameraCapabilities, CameraCapabilityFlags, CameraControls, CameraDeviceInfo, CameraFormat,
+    CameraFrame, CameraPerformanceMetrics, CapabilityProvenance, CapabilitySource,
+    ControlApplicationResult, DeviceTransport,
+};
+
+/// A display as the platform grabber addresses it
+#[derive(Debug, Clone, PartialEq)]
+struct Display {
+    name: String,
+    /// Native size, when the platform reports it
+    size: Option<(u32, u32)>,
+    /// ffmpeg input options placed before `-i`
+    input_args: Vec<String>,
+    /// ffmpeg `-i` argument selecting the display
+    input: String,
+}
+
+/// ffmpeg input format grabbing the screen on this platform
+fn grabber_format() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "avfoundation"
+    } else if cfg!(target_os = "windows") {
+        "gdigrab"
+    } else {
+        "x11grab"
+    }
+}
+
+/// Parse `xrandr --listmonitors` into displays of X server `x_display`
+///
+/// Lines look like ` 1: +HDMI-1 2560/597x1440/336+1920+0  HDMI-1`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_xrandr_monitors(listing: &str, x_display: &str) -> Vec<Display> {
+    listing
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            fields.next()?.strip_suffix(':')?.parse::<u32>().ok()?;
+            let name = fields.next()?.trim_start_matches(['+', '*']).to_string();
+            let (size, offset) = fields.next()?.split_once('+')?;
+            let (width, height) = size.split_once('x')?;
+            let width: u32 = width.split('/').next()?.parse().ok()?;
+            let height: u32 = height.split('/').next()?.parse().ok()?;
+            let (x, y) = offset.split_once('+')?;
+            let (x, y): (u32, u32) = (x.parse().ok()?, y.parse().ok()?);
+            Some(Display {
+                name,
+                size: Some((width, height)),
+                input_args: vec!["-video_size".to_string(), format!("{width}x{height}")],
+                input: format!("{x_display}+{x},{y}"),
+            })
+        })
+        .collect()
+}
+
+/// Parse the device listing of `ffmpeg -f avfoundation -list_devices true`
+/// into its screens
+///
+/// Lines look like `[AVFoundation indev @ 0x7f9c] [1] Capture screen 0`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_avfoundation_screens(listing: &str) -> Vec<Display> {
+    listing
+        .lines()
+        .take_while(|line| !line.contains("audio devices"))
+        .filter_map(|line| {
+            let (_, entry) = line.split_once("] [")?;
+            let (index, name) = entry.split_once("] ")?;
+            let index: u32 = index.parse().ok()?;
+            name.starts_with("Capture screen").then(|| Display {
+                name: name.trim().to_string(),
+                size: None,
+                input_args: vec!["-capture_cursor".to_string(), "1".to_string()],
+                input: format!("{index}:none"),
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn enumerate_displays() -> Result<Vec<Display>, CameraError> {
+    let x_display = std::env::var("DISPLAY").map_err(|_| {
+        CameraError::UnsupportedOperation(
+            "Screen capture needs an X11 session (DISPLAY is not set; Wayland is not supported)"
+                .to_string(),
+        )
+    })?;
+    let output = Command::new(SCREEN_XRANDR)
+        .arg("--listmonitors")
+        .output()
+        .map_err(|e| {
+            CameraError::InitializationError(format!("Failed to run {SCREEN_XRANDR}: {e}"))
+        })?;
+    Ok(parse_xrandr_monitors(
+        &String::from_utf8_lossy(&output.stdout),
+        &x_display,
+    ))
+}
+
+#[cfg(target_os = "macos")]
+fn enumerate_displays() -> Result<Vec<Display>, CameraError> {
+    let ffmpeg = ffmpeg_path()?;
+    let output = Command::new(&ffmpeg)
+        .args([
+            "-hide_banner",
+            "-f",
+            "avfoundation",
+            "-list_devices",
+            "true",
+        ])
+        .args(["-i", ""])
+        .output()
+        .map_err(|e| {
+            CameraError::InitializationError(format!("Failed to run {}: {e}", ffmpeg.display()))
+        })?;
+    // The listing goes to stderr, and ffmpeg exits with an error after it
+    Ok(parse_avfoundation_screens(&String::from_utf8_lossy(
+        &output.stderr,
+    )))
+}
+
+#[cfg(target_os = "windows")]
+#[allow(clippy::unnecessary_wraps)] // Fallible on the other platforms
+fn enumerate_displays() -> Result<Vec<Display>, CameraError> {
+    Ok(vec![Display {
+        name: "Desktop".to_string(),
+        size: None,
+        input_args: Vec::new(),
+        input: "desktop".to_string(),
+    }])
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn enumerate_displays() -> Result<Vec<Display>, CameraError> {
+    Err(CameraError::UnsupportedOperation(
+        "Screen capture is not supported on this platform".to_string(),
+    ))
+}
+
+/// Whether `device_id` names a screen rather than a camera
+pub fn is_screen_id(device_id: &str) -> bool {
+    device_id.starts_with(SCREEN_ID_PREFIX)
+}
+
+/// List the displays that can be captured, as devices with
+/// [`DeviceTransport::Screen`]
+///
+/// # Errors
+/// Returns a [`CameraError::UnsupportedOperation`] without a configured
+/// ffmpeg or a supported display server, or a
+/// [`CameraError::InitializationError`] if the listing tool (`xrandr` on
+/// Linux, `ffmpeg` on macOS) cannot run.
+pub fn list_screens() -> Result<Vec<CameraDeviceInfo>, CameraError> {
+    // Screens listed without ffmpeg could not be opened
+    ffmpeg_path()?;
+    Ok(enumerate_displays()?
+        .into_iter()
+        .enumerate()
+        .map(|(index, display)| {
+            let info = CameraDeviceInfo::new(format!("{SCREEN_ID_PREFIX}{index}"), display.name)
+                .with_description(format!("Screen capture ({})", grabber_format()))
+                .with_transport(DeviceTransport::Screen);
+            match display.size {
+                Some((width, height)) => {
+                    info.with_formats(vec![CameraFormat::new(width, height, SCREEN_DEFAULT_FPS)])
+                }
+                None => info,
+            }
+        })
+        .collect())
+}
+
+/// Newest frame from the grabber; the sequence number lets readers wait
+/// for the next one
+#[derive(Default)]
+struct Latest {
+    frame: Option<CameraFrame>,
+    sequence: u64,
+    ended: bool,
+}
+
+type SharedLatest = Arc<(Mutex<Latest>, Condvar)>;
+
+/// Running ffmpeg process and the thread reading its frames
+struct Grabber {
+    child: Child,
+    reader: Option<JoinHandle<()>>,
+    stopping: Arc<AtomicBool>,
+}
+
+impl Grabber {
+    fn spawn(
+        display: &Display,
+        format: &CameraFormat,
+        device_id: &str,
+        latest: SharedLatest,
+    ) -> Result<Self, CameraError> {
+        let (width, height) = (format.width, format.height);
+        let ffmpeg = ffmpeg_path()?;
+        let mut child = Command::new(&ffmpeg)
+            .args(["-hide_banner", "-loglevel", "error", "-nostdin"])
+            .args(["-f", grabber_format()])
+            .arg("-framerate")
+            .arg(format.fps.to_string())
+            .args(&display.input_args)
+            .arg("-i")
+            .arg(&display.input)
+            .arg("-vf")
+            .arg(format!("scale={width}:{height}"))
+            .args(["-pix_fmt", "rgb24", "-f", "rawvideo", "pipe:1"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| {
+                CameraError::InitializationError(format!(
+                    "Failed to start {}: {e}",
+                    ffmpeg.display()
+                ))
+            })?;
+        let mut stdout = child.stdout.take().ok_or_else(|| {
+            CameraError::InitializationError(format!("{} has no stdout", ffmpeg.display()))
+        })?;
+
+        let stopping = Arc::new(AtomicBool::new(false));
+        let stopping_clone = stopping.clone();
+        let frame_bytes = width as usize * height as usize * 3;
+        let device_id = device_id.to_string();
+        let reader = std::thread::spawn(move || {
+            let (lock, ready) = &*latest;
+            loop {
+                let mut buffer = vec![0u8; frame_bytes];
+                if stdout.read_exact(&mut buffer).is_err() {
+                    break;
+                }
+                let frame = CameraFrame::new(buffer, width, height, device_id.clone());
+                if let Ok(mut latest) = lock.lock() {
+                    latest.frame = Some(frame);
+                    latest.sequence += 1;
+                }
+                ready.notify_all();
+            }
+            if !stopping_clone.load(Ordering::SeqCst) {
+                log::warn!("Screen capture of {device_id} ended");
+            }
+            if let Ok(mut latest) = lock.lock() {
+                latest.ended = true;
+            }
+            ready.notify_all();
+        });
+
+        Ok(Self {
+            child,
+            reader: Some(reader),
+            stopping,
+        })
+    }
+}
+
+impl Drop for Grabber {
+    fn drop(&mut self) {
+        self.stopping.store(true, Ordering::SeqCst);
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+    }
+}
+
+/// A display captured as a video source
+pub struct ScreenSource {
+    device_id: String,
+    display: Display,
+    format: CameraFormat,
+    grabber: Option<Grabber>,
+    latest: SharedLatest,
+    /// Sequence number of the last frame returned
+    seen: u64,
+    callback: Arc<Mutex<Option<FrameCallback>>>,
+    perf: Arc<Mutex<PerfTracker>>,
+}
+
+impl ScreenSource {
+    /// Open the screen `device_id` (as listed by [`list_screens`]),
+    /// delivering frames scaled to `format`
+    ///
+    /// Grabbing starts with [`start_stream`](Self::start_stream) or the
+    /// first capture.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::InitializationError`] if the id is not a
+    /// listed screen, or the errors of [`list_screens`].
+    pub fn new(device_id: String, format: CameraFormat) -> Result<Self, CameraError> {
+        let not_found =
+            || CameraError::InitializationError(format!("Screen not found: {device_id}"));
+        let index = device_id
+            .strip_prefix(SCREEN_ID_PREFIX)
+            .and_then(|index| index.parse::<usize>().ok())
+            .ok_or_else(not_found)?;
+        let display = enumerate_displays()?
+            .into_iter()
+            .nth(index)
+            .ok_or_else(not_found)?;
+
+        Ok(Self {
+            perf: Arc::new(Mutex::new(PerfTracker::with_target_fps(format.fps))),
+            device_id,
+            display,
+            format,
+            grabber: None,
+            latest: Arc::default(),
+            seen: 0,
+            callback: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Start grabbing the display
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::UnsupportedOperation`] if no ffmpeg is
+    /// configured, or a [`CameraError::InitializationError`] if it cannot be
+    /// started.
+    pub fn start_stream(&mut self) -> Result<(), CameraError> {
+        if self.grabber.is_some() {
+            return Ok(());
+        }
+        if let Ok(mut latest) = self.latest.0.lock() {
+            latest.ended = false;
+        }
+        self.grabber = Some(Grabber::spawn(
+            &self.display,
+            &self.format,
+            &self.device_id,
+            self.latest.clone(),
+        )?);
+        Ok(())
+    }
+
+    /// Stop grabbing the display
+    ///
+    /// # Errors
+    /// Never fails; returns `Result` to match the camera backends.
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn stop_stream(&mut self) -> Result<(), CameraError> {
+        self.grabber = None;
+        Ok(())
+    }
+
+    /// Wait for the next frame of the display, starting the grabber if needed
+    ///
+    /// # Errors
+    /// Returns the errors of [`start_stream`](Self::start_stream), or a
+    /// [`CameraError::CaptureError`] if no new frame arrives in time or
+    /// ffmpeg exited (for example without screen recording permission).
+    pub fn capture_frame(&mut self) -> Result<CameraFrame, CameraError> {
+        self.start_stream()?;
+        let started = Instant::now();
+
+        let seen = self.seen;
+        let (lock, ready) = &*self.latest;
+        let guard = lock
+            .lock()
+            .map_err(|_| CameraError::CaptureError("Screen frame mutex poisoned".to_string()))?;
+        let (guard, _) = ready
+            .wait_timeout_while(
+                guard,
+                Duration::from_millis(SCREEN_FRAME_TIMEOUT_MS),
+                |latest| latest.sequence == seen && !latest.ended,
+            )
+            .map_err(|_| CameraError::CaptureError("Screen frame mutex poisoned".to_string()))?;
+        let (sequence, frame, ended) = (guard.sequence, guard.frame.clone(), guard.ended);
+        drop(guard);
+
+        let frame = match frame {
+            Some(frame) if sequence != seen => frame,
+            _ if ended => {
+                self.grabber = None;
+                return Err(CameraError::CaptureError(format!(
+                    "Screen capture of {} stopped; check the configured ffmpeg and that screen recording is permitted",
+                    self.device_id
+                )));
+            }
+            _ => {
+                return Err(CameraError::CaptureError(format!(
+                    "No frame from {} within {SCREEN_FRAME_TIMEOUT_MS}ms",
+                    self.device_id
+                )))
+            }
+        };
+        self.seen = sequence;
+
+        if let Ok(guard) = self.callback.lock() {
+            if let Some(ref cb) = *guard {
+                cb(frame.clone());
+            }
+        }
+        if let Ok(mut perf) = self.perf.lock() {
+            perf.record_capture(
+                started.elapsed().as_secs_f32() * 1000.0,
+                0.0,
+                Some((
+                    frame.data.clone(),
+                    frame.width,
+                    frame.height,
+                    frame.format.clone(),
+                )),
+            );
+        }
+        Ok(frame)
+    }
+
+    /// Whether the grabber is still delivering frames
+    pub fn is_available(&self) -> bool {
+        self.latest.0.lock().is_ok_and(|latest| !latest.ended)
+    }
+
+    /// Set frame callback, called with each captured frame
+    ///
+    /// # Errors
+    /// Returns [`CameraError::InitializationError`] if the callback mutex is poisoned.
+    pub fn set_callback<F>(&self, callback: F) -> Result<(), CameraError>
+    where
+        F: Fn(CameraFrame) + Send + 'static,
+    {
+        let mut guard = self
+            .callback
+            .lock()
+            .map_err(|_| CameraError::InitializationError("Callback mutex poisoned".to_string()))?;
+        *guard = Some(Box::new(callback));
+        Ok(())
+    }
+
+    /// Get the screen's device id
+    pub fn get_device_id(&self) -> &str {
+        &self.device_id
+    }
+
+    /// Scale to `format` instead, restarting the grabber if it is running
+    ///
+    /// # Errors
+    /// Returns the errors of [`start_stream`](Self::start_stream).
+    pub fn reconfigure(&mut self, format: &CameraFormat) -> Result<CameraFormat, CameraError> {
+        let running = self.grabber.take().is_some();
+        self.format = format.clone();
+        if running {
+            self.start_stream()?;
+        }
+        Ok(format.clone())
+    }
+
+    /// Screens have no camera controls
+    ///
+    /// # Errors
+    /// Always returns [`CameraError::UnsupportedOperation`].
+    #[allow(clippy::unused_self)] // Same signature as the camera backends
+    pub fn apply_controls(
+        &self,
+        _controls: &CameraControls,
+    ) -> Result<ControlApplicationResult, CameraError> {
+        Err(CameraError::UnsupportedOperation(
+            "Screen sources have no camera controls".to_string(),
+        ))
+    }
+
+    /// Screens have no camera controls
+    ///
+    /// # Errors
+    /// Always returns [`CameraError::UnsupportedOperation`].
+    #[allow(clippy::unused_self)] // Same signature as the camera backends
+    pub fn get_controls(&self) -> Result<CameraControls, CameraError> {
+        Err(CameraError::UnsupportedOperation(
+            "Screen sources have no camera controls".to_string(),
+        ))
+    }
+
+    /// Report the display size and requested rate, with no controls
+    ///
+    /// # Errors
+    /// Never fails; returns `Result` to match the camera backends.
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn test_capabilities(&self) -> Result<CameraCapabilities, CameraError> {
+        let (max_resolution, resolution_source) = match self.display.size {
+            Some(size) => (size, CapabilitySource::Reported),
+            None => (
+                (self.format.width, self.format.height),
+                CapabilitySource::Assumed,
+            ),
+        };
+        Ok(CameraCapabilities {
+            supports: CameraCapabilityFlags::default(),
+            max_resolution,
+            max_fps: self.format.fps,
+            exposure_range: None,
+            iso_range: None,
+            focus_range: None,
+            provenance: CapabilityProvenance {
+                supports: CapabilitySource::Reported,
+                max_resolution: resolution_source,
+                max_fps: CapabilitySource::Assumed,
+                ranges: CapabilitySource::Reported,
+            },
+            controls: Vec::new(),
+        })
+    }
+
+    /// Get real performance metrics for this capture session
+    ///
+    /// # Errors
+    /// Returns [`CameraError::CaptureError`] if the perf tracker mutex is
+    /// poisoned.
+    pub fn get_performance_metrics(&self) -> Result<CameraPerformanceMetrics, CameraError> {
+        let perf = self
+            .perf
+            .lock()
+            .map_err(|_| CameraError::CaptureError("Perf tracker mutex poisoned".to_string()))?;
+        Ok(super::metrics::build_metrics(&perf, &self.device_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_xrandr_monitors() {
+        let listing = "\
+Monitors: 2
+ 0: +*eDP-1 1920/344x1080/194+0+0  eDP-1
+ 1: +HDMI-1 2560/597x1440/336+1920+0  HDMI-1
+";
+        let displays = parse_xrandr_monitors(listing, ":1");
+        assert_eq!(displays.len(), 2);
+        assert_eq!(displays[0].name, "eDP-1");
+        assert_eq!(displays[0].size, Some((1920, 1080)));
+        assert_eq!(displays[0].input, ":1+0,0");
+        assert_eq!(displays[1].input, ":1+1920,0");
+        assert_eq!(displays[1].input_args, ["-video_size", "2560x1440"]);
+    }
+
+    #[test]
+    fn test_parse_avfoundation_screens() {
+        let listing = "\
+[AVFoundation indev @ 0x7f9c] AVFoundation video devices:
+[AVFoundation indev @ 0x7f9c] [0] FaceTime HD Camera
+[AVFoundation indev @ 0x7f9c] [1] Capture screen 0
+[AVFoundation indev @ 0x7f9c] [2] Capture screen 1
+[AVFoundation indev @ 0x7f9c] AVFoundation audio devices:
+[AVFoundation indev @ 0x7f9c] [0] MacBook Pro Microphone
+";
+        let displays = parse_avfoundation_screens(listing);
+        assert_eq!(displays.len(), 2);
+        assert_eq!(displays[0].name, "Capture screen 0");
+        assert_eq!(displays[0].input, "1:none");
+        assert_eq!(displays[1].input, "2:none");
+        assert!(displays.iter().all(|d| d.size.is_none()));
+    }
+
+    #[test]
+    fn test_screen_ids() {
+        assert!(is_screen_id("screen:0"));
+        assert!(!is_screen_id("0"));
+        assert!(ScreenSource::new("screen:x".to_string(), CameraFormat::hd()).is_err());
+    }
+}
@@ -0,0 +1,273 @@
+//! Cumulative capture statistics per open camera
+//!
+//! [`PerfTracker`](super::metrics::PerfTracker) describes the last couple of
+//! seconds; [`SessionMetrics`] adds up every capture since the camera was
+//! opened, so intermittent stalls and errors in a long-running session show
+//! up in the totals. [`PlatformCamera`](super::PlatformCamera) records each
+//! capture here, keyed by its device id. Opening a camera starts a new
+//! session for that id; reconnects continue the open one, so their errors
+//! stay in the totals.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::constants::SESSION_LATENCY_HISTOGRAM_MS;
+use crate::types::SessionMetricsReport;
+
+// Sessions by device id; kept after release so totals can still be read
+static SESSIONS: LazyLock<Mutex<HashMap<String, SessionMetrics>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Running totals of one camera's captures
+#[derive(Debug, Clone)]
+pub struct SessionMetrics {
+    started: Instant,
+    ended: Option<Instant>,
+    frames_captured: u64,
+    bytes_captured: u64,
+    latency_sum_ms: f64,
+    latency_max_ms: f32,
+    /// Captures per whole millisecond of latency, the last bucket holding
+    /// everything slower
+    latency_histogram: Vec<u64>,
+    errors: BTreeMap<String, u64>,
+}
+
+impl Default for SessionMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionMetrics {
+    /// Empty session starting now
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            ended: None,
+            frames_captured: 0,
+            bytes_captured: 0,
+            latency_sum_ms: 0.0,
+            latency_max_ms: 0.0,
+            latency_histogram: vec![0; SESSION_LATENCY_HISTOGRAM_MS + 1],
+            errors: BTreeMap::new(),
+        }
+    }
+
+    /// Count a delivered frame of `bytes` bytes that took `latency`
+    pub fn record_frame(&mut self, bytes: usize, latency: Duration) {
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        self.frames_captured += 1;
+        self.bytes_captured += u64::try_from(bytes).unwrap_or(u64::MAX);
+        self.latency_sum_ms += latency_ms;
+        #[allow(clippy::cast_possible_truncation)] // milliseconds fit an f32
+        let latency_ms_f32 = latency_ms as f32;
+        self.latency_max_ms = self.latency_max_ms.max(latency_ms_f32);
+
+        let bucket = usize::try_from(latency.as_millis())
+            .unwrap_or(usize::MAX)
+            .min(SESSION_LATENCY_HISTOGRAM_MS);
+        self.latency_histogram[bucket] += 1;
+    }
+
+    /// Count a failed capture under its error code
+    pub fn record_error(&mut self, code: &str) {
+        *self.errors.entry(code.to_string()).or_default() += 1;
+    }
+
+    /// Stop the uptime clock, when the camera is released
+    pub fn end(&mut self) {
+        self.ended.get_or_insert_with(Instant::now);
+    }
+
+    /// Totals so far
+    #[allow(clippy::cast_precision_loss)] // frame counts stay far below 2^52
+    pub fn report(&self, device_id: &str) -> SessionMetricsReport {
+        let latency_avg_ms = if self.frames_captured == 0 {
+            0.0
+        } else {
+            self.latency_sum_ms / self.frames_captured as f64
+        };
+        #[allow(clippy::cast_possible_truncation)] // milliseconds fit an f32
+        let latency_avg_ms = latency_avg_ms as f32;
+
+        SessionMetricsReport {
+            device_id: device_id.to_string(),
+            uptime_secs: self
+                .ended
+                .unwrap_or_else(Instant::now)
+                .duration_since(self.started)
+                .as_secs_f64(),
+            is_open: self.ended.is_none(),
+            frames_captured: self.frames_captured,
+            bytes_captured: self.bytes_captured,
+            latency_avg_ms,
+            latency_p50_ms: self.latency_percentile(0.50),
+            latency_p95_ms: self.latency_percentile(0.95),
+            latency_p99_ms: self.latency_percentile(0.99),
+            latency_max_ms: self.latency_max_ms,
+            error_count: self.errors.values().sum(),
+            errors: self.errors.clone(),
+        }
+    }
+
+    /// Upper edge of the bucket holding the `quantile` capture, capped at
+    /// the slowest capture seen
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn latency_percentile(&self, quantile: f64) -> f32 {
+        if self.frames_captured == 0 {
+            return 0.0;
+        }
+        // Ranks are at most the frame count; f64 keeps them exact
+        let rank = ((self.frames_captured as f64 * quantile).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, &count) in self.latency_histogram.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                if bucket == SESSION_LATENCY_HISTOGRAM_MS {
+                    break;
+                }
+                return ((bucket + 1) as f32).min(self.latency_max_ms);
+            }
+        }
+        self.latency_max_ms
+    }
+}
+
+fn with_sessions<T>(f: impl FnOnce(&mut HashMap<String, SessionMetrics>) -> T) -> T {
+    // Counters hold no invariants a panic could break
+    let mut sessions = SESSIONS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    f(&mut sessions)
+}
+
+/// Start a new session for `device_id` unless one is open, discarding a
+/// released camera's totals
+pub fn start_session(device_id: &str) {
+    with_sessions(|sessions| {
+        let open = sessions
+            .get(device_id)
+            .is_some_and(|session| session.ended.is_none());
+        if !open {
+            sessions.insert(device_id.to_string(), SessionMetrics::new());
+        }
+    });
+}
+
+/// Stop the uptime clock of `device_id`'s session
+pub fn end_session(device_id: &str) {
+    with_sessions(|sessions| {
+        if let Some(session) = sessions.get_mut(device_id) {
+            session.end();
+        }
+    });
+}
+
+/// Record a delivered frame for `device_id`
+pub fn record_frame(device_id: &str, bytes: usize, latency: Duration) {
+    with_sessions(|sessions| {
+        sessions
+            .entry(device_id.to_string())
+            .or_default()
+            .record_frame(bytes, latency);
+    });
+}
+
+/// Record a failed capture for `device_id`
+pub fn record_error(device_id: &str, code: &str) {
+    with_sessions(|sessions| {
+        sessions
+            .entry(device_id.to_string())
+            .or_default()
+            .record_error(code);
+    });
+}
+
+/// Totals of `device_id`'s session, if it has one
+pub fn session_report(device_id: &str) -> Option<SessionMetricsReport> {
+    with_sessions(|sessions| sessions.get(device_id).map(|s| s.report(device_id)))
+}
+
+/// Return `device_id`'s totals and start counting from zero
+///
+/// A released camera's session is removed instead of restarted.
+pub fn reset_session(device_id: &str) -> Option<SessionMetricsReport> {
+    with_sessions(|sessions| {
+        let report = sessions.get(device_id)?.report(device_id);
+        if report.is_open {
+            sessions.insert(device_id.to_string(), SessionMetrics::new());
+        } else {
+            sessions.remove(device_id);
+        }
+        Some(report)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totals_and_percentiles() {
+        let mut session = SessionMetrics::new();
+        for ms in 1..=100 {
+            session.record_frame(1000, Duration::from_micros(ms * 1000 - 500));
+        }
+        session.record_error("CAPTURE_FAILED");
+        session.record_error("CAPTURE_FAILED");
+        session.record_error("STREAM_FAILED");
+
+        let report = session.report("cam");
+        assert_eq!(report.frames_captured, 100);
+        assert_eq!(report.bytes_captured, 100_000);
+        assert!((report.latency_avg_ms - 50.0).abs() < 0.01);
+        assert!((report.latency_p50_ms - 50.0).abs() < f32::EPSILON);
+        assert!((report.latency_p95_ms - 95.0).abs() < f32::EPSILON);
+        assert!((report.latency_p99_ms - 99.0).abs() < f32::EPSILON);
+        assert!((report.latency_max_ms - 99.5).abs() < 0.01);
+        assert_eq!(report.error_count, 3);
+        assert_eq!(report.errors["CAPTURE_FAILED"], 2);
+        assert!(report.is_open);
+    }
+
+    #[test]
+    fn test_slow_captures_report_the_maximum() {
+        let mut session = SessionMetrics::new();
+        session.record_frame(0, Duration::from_millis(5));
+        session.record_frame(0, Duration::from_secs(3));
+        let report = session.report("cam");
+        assert!((report.latency_p99_ms - 3000.0).abs() < 0.01);
+        assert!(SessionMetrics::new().report("cam").latency_p50_ms.abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_reset_and_end() {
+        let id = "session-metrics-test";
+        start_session(id);
+        record_frame(id, 10, Duration::from_millis(2));
+        let before = reset_session(id).unwrap();
+        assert_eq!(before.frames_captured, 1);
+        assert_eq!(session_report(id).unwrap().frames_captured, 0);
+
+        // Reopening continues an open session
+        record_frame(id, 10, Duration::from_millis(2));
+        start_session(id);
+        assert_eq!(session_report(id).unwrap().frames_captured, 1);
+
+        end_session(id);
+        let ended = session_report(id).unwrap();
+        assert!(!ended.is_open);
+        start_session(id);
+        assert_eq!(session_report(id).unwrap().frames_captured, 0);
+
+        end_session(id);
+        assert!(reset_session(id).is_some());
+        assert!(session_report(id).is_none());
+    }
+}
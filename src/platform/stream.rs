@@ -0,0 +1,201 @@
+//! Async `Stream` adapter over frame capture
+//!
+//! [`FrameStream`] owns a dedicated capture thread that drives
+//! [`PlatformCamera::capture_frame`] and forwards frames delivered to the
+//! camera's frame callback into a bounded channel:
+//!
+//! ```rust,ignore
+//! use futures::StreamExt;
+//!
+//! let mut stream = CameraSystem::frame_stream("0", CameraFormat::hd())?;
+//! while let Some(frame) = stream.next().await {
+//!     let frame = frame?;
+//!     // ...
+//! }
+//! ```
+//!
+//! ## Buffering and backpressure
+//!
+//! The channel holds at most [`FRAME_STREAM_CHANNEL_CAPACITY`] items. The
+//! camera keeps running at its own rate regardless of how fast the consumer
+//! polls: when the buffer is full the newest frame is discarded (and counted
+//! in [`FrameStream::dropped_frames`]) rather than blocking capture, so a slow
+//! consumer always sees recent frames instead of an ever-growing backlog.
+//! Capture errors are delivered as `Err` items and do not end the stream.
+//!
+//! ## Cancellation
+//!
+//! Dropping the [`FrameStream`] signals the capture thread, which stops the
+//! camera stream and releases the device before exiting.
+
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures_core::Stream;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{tune_current_thread, ThreadRole};
+use crate::constants::FRAME_STREAM_CHANNEL_CAPACITY;
+use crate::errors::CameraError;
+use crate::platform::PlatformCamera;
+use crate::types::{CameraFormat, CameraFrame, CameraInitParams};
+
+type FrameResult = Result<CameraFrame, CameraError>;
+
+/// Asynchronous stream of frames from a single camera.
+///
+/// Created by [`crate::CameraSystem::frame_stream`]. See the
+/// [module documentation](self) for buffering and cancellation behavior.
+pub struct FrameStream {
+    rx: mpsc::Receiver<FrameResult>,
+    cancel: CancellationToken,
+    dropped: Arc<AtomicU64>,
+}
+
+impl FrameStream {
+    /// Open `device_id` with `format` and start capturing on a background thread.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError`] if the camera cannot be opened or its stream
+    /// cannot be started.
+    pub fn open(device_id: &str, format: CameraFormat) -> Result<Self, CameraError> {
+        let fps = format.fps;
+        let params = CameraInitParams::new(device_id.to_string()).with_format(format);
+        let mut camera = PlatformCamera::new(params)?;
+        camera.start_stream()?;
+
+        let (tx, rx) = mpsc::channel(FRAME_STREAM_CHANNEL_CAPACITY);
+        let cancel = CancellationToken::new();
+        let dropped = Arc::new(AtomicU64::new(0));
+
+        let frame_tx = tx.clone();
+        let frame_dropped = dropped.clone();
+        camera.frame_callback(move |frame| {
+            if frame_tx.try_send(Ok(frame)).is_err() {
+                frame_dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        })?;
+
+        let thread_cancel = cancel.clone();
+        std::thread::Builder::new()
+            .name(format!("crabcamera-stream-{device_id}"))
+            .spawn(move || run_capture_loop(camera, &tx, &thread_cancel, fps))
+            .map_err(|e| {
+                CameraError::InitializationError(format!("Failed to spawn capture thread: {e}"))
+            })?;
+
+        Ok(Self {
+            rx,
+            cancel,
+            dropped,
+        })
+    }
+
+    /// Number of frames discarded because the consumer fell behind.
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Stop capturing. Frames already buffered can still be drained.
+    pub fn stop(&self) {
+        self.cancel.cancel();
+    }
+}
+
+impl Stream for FrameStream {
+    type Item = FrameResult;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for FrameStream {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+/// Capture until cancelled or the receiver is gone, pacing to the target fps
+/// for backends whose `capture_frame` does not block on the device.
+fn run_capture_loop(
+    mut camera: PlatformCamera,
+    tx: &mpsc::Sender<FrameResult>,
+    cancel: &CancellationToken,
+    fps: f32,
+) {
+    tune_current_thread(ThreadRole::Capture);
+    let interval = if fps > 0.0 {
+        Duration::from_secs_f32(1.0 / fps)
+    } else {
+        Duration::ZERO
+    };
+
+    while !cancel.is_cancelled() && !tx.is_closed() {
+        let started = Instant::now();
+
+        // Successful frames reach the channel through the frame callback.
+        if let Err(e) = camera.capture_frame() {
+            let _ = tx.try_send(Err(e));
+        }
+
+        if let Some(remaining) = interval.checked_sub(started.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+    }
+
+    if let Err(e) = camera.stop_stream() {
+        log::warn!("Failed to stop camera stream: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::poll_fn;
+
+    async fn next(stream: &mut FrameStream) -> Option<FrameResult> {
+        poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+    }
+
+    #[tokio::test]
+    async fn test_frame_stream_yields_frames() {
+        let mut stream =
+            FrameStream::open("stream-test", CameraFormat::new(320, 240, 100.0)).expect("open");
+
+        for _ in 0..3 {
+            let frame = next(&mut stream)
+                .await
+                .expect("stream should not end")
+                .expect("mock capture should succeed");
+            assert_eq!(frame.device_id, "stream-test");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_frame_stream_ends_after_stop() {
+        let mut stream =
+            FrameStream::open("stream-stop", CameraFormat::new(320, 240, 100.0)).expect("open");
+        stream.stop();
+
+        // Buffered frames drain, then the capture thread drops its sender.
+        let drained = tokio::time::timeout(Duration::from_secs(5), async {
+            while next(&mut stream).await.is_some() {}
+        })
+        .await;
+        assert!(drained.is_ok(), "stream should terminate after stop");
+    }
+
+    #[tokio::test]
+    async fn test_slow_consumer_drops_instead_of_blocking() {
+        let stream =
+            FrameStream::open("stream-slow", CameraFormat::new(320, 240, 200.0)).expect("open");
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(stream.dropped_frames() > 0);
+    }
+}
@@ -1,10 +1,11 @@
 use crate::constants::{
     DEFAULT_FPS, DEFAULT_RESOLUTION_HEIGHT, DEFAULT_RESOLUTION_WIDTH, FALLBACK_RESOLUTION_HEIGHT,
-    FALLBACK_RESOLUTION_WIDTH, FORMAT_RGB, MIN_RESOLUTION_HEIGHT, MIN_RESOLUTION_WIDTH,
-    MJPEG_SIGNATURE, VALID_FRAME_NONZERO_PERCENT,
+    FALLBACK_RESOLUTION_WIDTH, FORMAT_MJPEG, FORMAT_RGB, MIN_RESOLUTION_HEIGHT,
+    MIN_RESOLUTION_WIDTH, MJPEG_SIGNATURE, VALID_FRAME_NONZERO_PERCENT,
 };
 use crate::errors::CameraError;
-use crate::types::{CameraDeviceInfo, CameraFormat, CameraFrame};
+use crate::platform::convert;
+use crate::types::{CameraBackend, CameraDeviceInfo, CameraFormat, CameraFrame};
 use nokhwa::{
     pixel_format::RgbFormat,
     query,
@@ -69,6 +70,12 @@ pub fn list_cameras() -> Result<Vec<CameraDeviceInfo>, CameraError> {
 
         device = device.with_description(camera_info.description().to_string());
 
+        // The Media Foundation symbolic link, stable across reconnects
+        let misc = camera_info.misc();
+        if !misc.is_empty() {
+            device = device.with_stable_id(misc);
+        }
+
         // Add common Windows camera formats
         let formats = vec![
             CameraFormat::new(
@@ -106,6 +113,20 @@ pub fn list_cameras() -> Result<Vec<CameraDeviceInfo>, CameraError> {
 /// Returns a [`CameraError::InitializationError`] if the `device_id`
 /// cannot be parsed, or if the `nokhwa` camera cannot be created.
 pub fn initialize_camera(device_id: &str, format: &CameraFormat) -> Result<Camera, CameraError> {
+    initialize_camera_with_backend(device_id, format, None)
+}
+
+/// Like [`initialize_camera`], but open the camera with `backend` instead
+/// of nokhwa's default when given
+///
+/// # Errors
+/// Same as [`initialize_camera`]; also fails if `backend` cannot open the
+/// device.
+pub fn initialize_camera_with_backend(
+    device_id: &str,
+    format: &CameraFormat,
+    backend: Option<CameraBackend>,
+) -> Result<Camera, CameraError> {
     log::debug!(
         "Requested format: {}x{} @ {}fps (note: nokhwa will use highest resolution)",
         format.width,
@@ -120,22 +141,29 @@ pub fn initialize_camera(device_id: &str, format: &CameraFormat) -> Result<Camer
     let requested_format =
         RequestedFormat::new::<RgbFormat>(RequestedFormatType::AbsoluteHighestResolution);
 
-    let camera = Camera::new(
-        nokhwa::utils::CameraIndex::Index(device_index),
-        requested_format,
-    )
-    .map_err(|e| CameraError::InitializationError(format!("Failed to initialize camera: {e}")))?;
+    let camera =
+        crate::platform::open_nokhwa(device_index, requested_format, backend).map_err(|e| {
+            CameraError::InitializationError(format!("Failed to initialize camera: {e}"))
+        })?;
+
+    crate::logging::log_event(
+        crate::logging::LogLevel::Info,
+        module_path!(),
+        Some(device_id),
+        format!("Format negotiated: {}", camera.camera_format()),
+    );
 
     Ok(camera)
 }
 
 /// Capture frame from Windows camera
 /// Note: nokhwa returns MJPEG data even when `RgbFormat` is requested,
-/// so we need to decode it manually to RGB
+/// so the JPEG signature takes precedence over the reported source format
 ///
 /// # Errors
 /// Returns a [`CameraError::CaptureError`] if the `nokhwa` frame
-/// cannot be obtained or, for MJPEG data, if it cannot be decoded.
+/// cannot be obtained or cannot be decoded, or a
+/// [`CameraError::UnsupportedFormat`] if its pixel format has no conversion.
 pub fn capture_frame(camera: &mut Camera, device_id: &str) -> Result<CameraFrame, CameraError> {
     let frame = camera
         .frame()
@@ -153,43 +181,70 @@ pub fn capture_frame(camera: &mut Camera, device_id: &str) -> Result<CameraFrame
         raw_bytes.get(0..3).unwrap_or(&[])
     );
 
-    // Check if the data is MJPEG
-    let rgb_data =
-        if raw_bytes.len() >= MJPEG_SIGNATURE.len() && raw_bytes.starts_with(&MJPEG_SIGNATURE) {
-            // Data is MJPEG - decode to RGB
-            log::debug!("Decoding MJPEG frame ({} bytes) to RGB", raw_bytes.len());
-
-            let img = image::load_from_memory(&raw_bytes)
-                .map_err(|e| CameraError::CaptureError(format!("Failed to decode MJPEG: {e}")))?;
-
-            img.to_rgb8().into_raw()
-        } else {
-            // Data is already RGB (or at least not MJPEG)
-            // Check if it's mostly zeros (invalid frame)
-            let non_zero_count = raw_bytes.iter().filter(|&&b| b != 0).count();
-            let total = raw_bytes.len();
-            #[allow(clippy::cast_precision_loss)]
-            // usize→f64: percent calculation; full u64 precision not needed for validation
-            let pct_nonzero = (non_zero_count as f64 / total as f64) * 100.0;
-            log::debug!("RGB frame: {pct_nonzero:.1}% non-zero pixels");
-
-            if pct_nonzero < VALID_FRAME_NONZERO_PERCENT {
-                log::warn!(
+    let source = if raw_bytes.starts_with(&MJPEG_SIGNATURE) {
+        FORMAT_MJPEG
+    } else {
+        convert::fourcc_of(frame.source_frame_format())
+    };
+    log::debug!(
+        "Converting {source} frame ({} bytes) to RGB",
+        raw_bytes.len()
+    );
+
+    let rgb_data = convert::to_rgb8_auto(&raw_bytes, width, height, source)?;
+
+    if source != FORMAT_MJPEG {
+        // Check if it's mostly zeros (invalid frame)
+        let non_zero_count = rgb_data.iter().filter(|&&b| b != 0).count();
+        let total = rgb_data.len().max(1);
+        #[allow(clippy::cast_precision_loss)]
+        // usize→f64: percent calculation; full u64 precision not needed for validation
+        let pct_nonzero = (non_zero_count as f64 / total as f64) * 100.0;
+        log::debug!("RGB frame: {pct_nonzero:.1}% non-zero pixels");
+
+        if pct_nonzero < VALID_FRAME_NONZERO_PERCENT {
+            log::warn!(
                 "Frame appears to be mostly zeros ({pct_nonzero:.1}%) - camera may not be ready"
             );
-            }
-
-            raw_bytes.to_vec()
-        };
+        }
+    }
 
-    let camera_frame = CameraFrame::new(rgb_data, width, height, device_id.to_string());
+    let mut camera_frame = CameraFrame::new(rgb_data, width, height, device_id.to_string());
+    camera_frame.metadata.source_format = Some(source.to_string());
 
-    // The frame is delivered as RGB8: MJPEG input is decoded above, and raw
-    // frames are treated as RGB per the Windows pipeline contract. The label
-    // must reflect the decoded output, not the camera's raw source format.
+    // The label must reflect the converted output, not the camera's raw
+    // source format.
     Ok(camera_frame.with_format(FORMAT_RGB.to_string()))
 }
 
+/// Capture a frame from a Windows camera without converting it to RGB.
+/// The frame's `format` is the detected source format (`MJPEG` when the
+/// buffer carries a JPEG signature).
+///
+/// # Errors
+/// Returns a [`CameraError::CaptureError`] if the `nokhwa` frame
+/// cannot be obtained.
+pub fn capture_frame_raw(camera: &mut Camera, device_id: &str) -> Result<CameraFrame, CameraError> {
+    let frame = camera
+        .frame()
+        .map_err(|e| CameraError::CaptureError(format!("Failed to capture frame: {e}")))?;
+
+    let raw_bytes = frame.buffer_bytes();
+    let width = frame.resolution().width_x;
+    let height = frame.resolution().height_y;
+
+    let source = if raw_bytes.starts_with(&MJPEG_SIGNATURE) {
+        FORMAT_MJPEG
+    } else {
+        convert::fourcc_of(frame.source_frame_format())
+    };
+
+    Ok(
+        CameraFrame::new(raw_bytes.to_vec(), width, height, device_id.to_string())
+            .with_format(source.to_string()),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,8 +2,8 @@
 use crate::constants::{HIGH_FPS, MAX_RESOLUTION_HEIGHT, MAX_RESOLUTION_WIDTH};
 use crate::errors::CameraError;
 use crate::types::{
-    CameraCapabilities, CameraCapabilityFlags, CameraControls, ControlApplicationResult,
-    WhiteBalance,
+    CameraCapabilities, CameraCapabilityFlags, CameraControls, CapabilityProvenance,
+    ControlApplicationResult, WhiteBalance,
 };
 use windows::core::Interface;
 use windows::Win32::Media::DirectShow::{
@@ -256,9 +256,8 @@ impl MediaFoundationControls {
                     controls.auto_exposure = Some(true);
                 } else if let Some(ref range) = self.exposure_range {
                     controls.auto_exposure = Some(false);
-                    // Convert from log base 2 back to seconds
-                    let log_exposure = device_to_normalized_range(value, range);
-                    controls.exposure_time = Some(2.0_f32.powf(log_exposure));
+                    controls.exposure_time =
+                        Some(exposure_seconds(value.clamp(range.min, range.max)));
                 }
             }
         }
@@ -330,6 +329,8 @@ impl MediaFoundationControls {
             exposure_range: None,
             iso_range: None,
             focus_range: None,
+            provenance: CapabilityProvenance::default(),
+            controls: self.control_ranges(),
         };
 
         // Test camera control capabilities
@@ -350,10 +351,8 @@ impl MediaFoundationControls {
                 capabilities.supports.auto_exposure = true;
                 capabilities.supports.manual_exposure = true;
                 if let Some(ref range) = self.exposure_range {
-                    // Convert device range to approximate seconds range
-                    let min_seconds = 2.0_f32.powf(device_to_normalized_range(range.min, range));
-                    let max_seconds = 2.0_f32.powf(device_to_normalized_range(range.max, range));
-                    capabilities.exposure_range = Some((min_seconds, max_seconds));
+                    capabilities.exposure_range =
+                        Some((exposure_seconds(range.min), exposure_seconds(range.max)));
                 }
             }
 
@@ -385,6 +384,41 @@ impl MediaFoundationControls {
         Ok(capabilities)
     }
 
+    /// Cached ranges in `CameraControls` units: -1 to 1 for the
+    /// normalized controls, seconds for exposure
+    fn control_ranges(&self) -> Vec<crate::types::ControlRange> {
+        let normalized = [
+            ("focus_distance", &self.focus_range),
+            ("brightness", &self.brightness_range),
+            ("contrast", &self.contrast_range),
+            ("saturation", &self.saturation_range),
+        ];
+        let mut ranges: Vec<crate::types::ControlRange> = normalized
+            .into_iter()
+            .filter_map(|(control, range)| {
+                let range = range.as_ref()?;
+                Some(crate::types::ControlRange::linear(
+                    control,
+                    (-1.0, 1.0),
+                    (i64::from(range.min), i64::from(range.max)),
+                    i64::from(range.step),
+                    i64::from(range.default),
+                ))
+            })
+            .collect();
+        if let Some(ref range) = self.exposure_range {
+            // Whole log2 steps are not evenly spaced in seconds
+            ranges.push(crate::types::ControlRange {
+                control: "exposure_time".to_string(),
+                min: exposure_seconds(range.min),
+                max: exposure_seconds(range.max),
+                step: None,
+                default: Some(exposure_seconds(range.default)),
+            });
+        }
+        ranges
+    }
+
     // Individual control implementation methods
 
     fn set_auto_focus(&mut self, enabled: bool) -> Result<(), CameraError> {
@@ -480,10 +514,10 @@ impl MediaFoundationControls {
     fn set_exposure_time(&mut self, time_seconds: f32) -> Result<(), CameraError> {
         if let Some(ref camera_control) = self.camera_control {
             if let Some(ref range) = self.exposure_range {
-                // Convert seconds to device-specific exposure units
-                // Note: MediaFoundation exposure is often in log base 2 seconds
+                // The exposure control is in whole log2 seconds
                 let log_exposure = time_seconds.log2();
-                let device_value = normalize_to_device_range(log_exposure, range);
+                #[allow(clippy::cast_possible_truncation)]
+                let device_value = (log_exposure.round() as i32).clamp(range.min, range.max);
 
                 unsafe {
                     camera_control
@@ -906,6 +940,12 @@ fn normalize_to_device_range(normalized: f32, range: &ControlRange) -> i32 {
     range.min + (zero_to_one * device_range as f32) as i32
 }
 
+/// Exposure time in seconds of a `CameraControl_Exposure` value, which is
+/// log2 seconds (-7 = 1/128 s)
+fn exposure_seconds(device_value: i32) -> f32 {
+    2.0_f32.powi(device_value)
+}
+
 /// Convert device-specific value to normalized range (-1.0 to 1.0)
 #[allow(clippy::cast_precision_loss)]
 // i32→f32: camera control values are small enough that f32 maintains exact integer representation
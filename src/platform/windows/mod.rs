@@ -13,7 +13,8 @@ use self::controls::MediaFoundationControls;
 use crate::errors::CameraError;
 use crate::platform::metrics::PerfTracker;
 use crate::types::{
-    CameraCapabilities, CameraControls, CameraFormat, CameraFrame, ControlApplicationResult,
+    CameraBackend, CameraCapabilities, CameraControls, CameraFormat, CameraFrame,
+    ControlApplicationResult, PixelFormatPreference, TimestampSource,
 };
 use nokhwa::Camera;
 use std::sync::Arc;
@@ -33,6 +34,12 @@ pub struct WindowsCamera {
     pub callback: std::sync::Mutex<Option<FrameCallback>>,
     /// Real performance tracker, updated on every capture.
     pub perf: Arc<std::sync::Mutex<PerfTracker>>,
+    /// Compressed vs uncompressed tradeoff used when reconfiguring
+    pub pixel_format: PixelFormatPreference,
+    /// Capture API the camera was opened with
+    pub backend: CameraBackend,
+    /// Whether frames carry a `hardware_timestamp`
+    pub timestamp_source: TimestampSource,
 }
 
 impl WindowsCamera {
@@ -43,10 +50,24 @@ impl WindowsCamera {
     /// cannot be parsed, or propagates any error from the `nokhwa` camera
     /// initialization or the `MediaFoundation` controls creation.
     pub fn new(device_id: String, format: &CameraFormat) -> Result<Self, CameraError> {
+        Self::with_backend(device_id, format, None)
+    }
+
+    /// Like [`WindowsCamera::new`], but capture through `backend` instead of
+    /// nokhwa's default when given
+    ///
+    /// # Errors
+    /// Same as [`WindowsCamera::new`].
+    pub fn with_backend(
+        device_id: String,
+        format: &CameraFormat,
+        backend: Option<CameraBackend>,
+    ) -> Result<Self, CameraError> {
         log::info!("Initializing Windows camera {device_id} with MediaFoundation controls");
 
         // Initialize nokhwa camera for capture
-        let nokhwa_camera = capture::initialize_camera(&device_id, format)?;
+        let nokhwa_camera = capture::initialize_camera_with_backend(&device_id, format, backend)?;
+        let backend = super::backend_of(&nokhwa_camera);
 
         // Initialize MediaFoundation controls
         let device_index = device_id
@@ -59,10 +80,34 @@ impl WindowsCamera {
             mf_controls,
             device_id,
             callback: std::sync::Mutex::new(None),
-            perf: Arc::new(std::sync::Mutex::new(PerfTracker::new())),
+            perf: Arc::new(std::sync::Mutex::new(PerfTracker::with_target_fps(
+                format.fps,
+            ))),
+            pixel_format: PixelFormatPreference::default(),
+            backend,
+            timestamp_source: TimestampSource::default(),
         })
     }
 
+    /// Set the compressed vs uncompressed tradeoff used when reconfiguring
+    #[must_use]
+    pub fn with_pixel_format(mut self, preference: PixelFormatPreference) -> Self {
+        self.pixel_format = preference;
+        self
+    }
+
+    /// Select whether frames carry a `hardware_timestamp`
+    #[must_use]
+    pub fn with_timestamp_source(mut self, source: TimestampSource) -> Self {
+        self.timestamp_source = source;
+        self
+    }
+
+    /// [`super::dequeue_timestamp`] if driver timestamps were requested
+    fn dequeue_timestamp(&self) -> Option<f64> {
+        (self.timestamp_source == TimestampSource::DriverHardware).then(super::dequeue_timestamp)
+    }
+
     /// Capture a frame using nokhwa
     ///
     /// # Errors
@@ -71,7 +116,7 @@ impl WindowsCamera {
     /// capture.
     pub fn capture_frame(&mut self) -> Result<CameraFrame, CameraError> {
         let start = std::time::Instant::now();
-        let frame = match capture::capture_frame(&mut self.nokhwa_camera, &self.device_id) {
+        let mut frame = match capture::capture_frame(&mut self.nokhwa_camera, &self.device_id) {
             Ok(f) => f,
             Err(e) => {
                 if let Ok(mut perf) = self.perf.lock() {
@@ -80,6 +125,7 @@ impl WindowsCamera {
                 return Err(e);
             }
         };
+        frame.metadata.hardware_timestamp = self.dequeue_timestamp();
         let latency_ms = start.elapsed().as_secs_f32() * 1000.0;
 
         let process_start = std::time::Instant::now();
@@ -109,6 +155,30 @@ impl WindowsCamera {
         Ok(frame)
     }
 
+    /// Capture a frame without RGB conversion (see [`capture::capture_frame_raw`])
+    ///
+    /// # Errors
+    /// Propagates any error from the underlying `nokhwa` capture.
+    pub fn capture_frame_raw(&mut self) -> Result<CameraFrame, CameraError> {
+        let start = std::time::Instant::now();
+        let result = capture::capture_frame_raw(&mut self.nokhwa_camera, &self.device_id).map(
+            |mut frame| {
+                frame.metadata.hardware_timestamp = self.dequeue_timestamp();
+                frame
+            },
+        );
+
+        if let Ok(mut perf) = self.perf.lock() {
+            if result.is_ok() {
+                perf.record_capture(start.elapsed().as_secs_f32() * 1000.0, 0.0, None);
+            } else {
+                perf.record_drop();
+            }
+        }
+
+        result
+    }
+
     /// Return real performance metrics for this camera session.
     ///
     /// # Errors
@@ -168,6 +238,31 @@ impl WindowsCamera {
             .map_err(|e| CameraError::StreamError(format!("Failed to open stream: {e}")))
     }
 
+    /// Switch the open device to the closest supported mode to `format`, or
+    /// to exactly `format` when `exact` is set. Returns the mode selected.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::InitializationError`] if the driver rejects
+    /// the format or the stream cannot be restarted.
+    pub fn reconfigure(
+        &mut self,
+        format: &CameraFormat,
+        exact: bool,
+    ) -> Result<CameraFormat, CameraError> {
+        let negotiated =
+            super::renegotiate_format(&mut self.nokhwa_camera, format, exact, self.pixel_format)?;
+        crate::logging::log_event(
+            crate::logging::LogLevel::Info,
+            module_path!(),
+            Some(&self.device_id),
+            format!(
+                "Format renegotiated: {}x{} @ {}fps {}",
+                negotiated.width, negotiated.height, negotiated.fps, negotiated.format_type
+            ),
+        );
+        Ok(negotiated)
+    }
+
     /// Stop camera stream
     ///
     /// # Errors
@@ -214,7 +309,7 @@ impl WindowsCamera {
 }
 
 // Re-export public interface functions for compatibility
-pub use capture::{capture_frame, initialize_camera, list_cameras};
+pub use capture::{capture_frame, capture_frame_raw, initialize_camera, list_cameras};
 
 #[cfg(test)]
 mod tests {
@@ -1,3 +1,4 @@
+use crate::constants::GIF_QUANTIZE_SPEED;
 use crate::types::CameraFrame;
 
 /// Encode a `CameraFrame` to JPEG in-memory.
@@ -18,6 +19,43 @@ pub fn encode_frame_jpeg(frame: &CameraFrame, quality: u8) -> Result<Vec<u8>, St
     Ok(buf)
 }
 
+/// Encode RGB frames as an animated GIF in-memory.
+///
+/// Each frame is quantized to its own 256-color palette and shown for
+/// `delay_ms` (rounded to the format's 10 ms resolution). With `looping`
+/// the animation repeats forever, otherwise it plays once.
+///
+/// # Errors
+/// Returns an `Err` if a frame's data is not tightly packed RGB or if GIF
+/// encoding fails.
+pub fn encode_gif(frames: &[CameraFrame], delay_ms: u32, looping: bool) -> Result<Vec<u8>, String> {
+    use image::codecs::gif::{GifEncoder, Repeat};
+
+    let mut buf = Vec::new();
+    {
+        let mut encoder = GifEncoder::new_with_speed(&mut buf, GIF_QUANTIZE_SPEED);
+        // Without a loop count the animation plays once
+        let repeat = if looping {
+            Repeat::Infinite
+        } else {
+            Repeat::Finite(0)
+        };
+        encoder
+            .set_repeat(repeat)
+            .map_err(|e| format!("GIF encode failed: {e}"))?;
+        let delay = image::Delay::from_numer_denom_ms(delay_ms, 1);
+        for frame in frames {
+            let rgb = image::RgbImage::from_vec(frame.width, frame.height, frame.data.clone())
+                .ok_or_else(|| "Failed to create image from frame data".to_string())?;
+            let rgba = image::DynamicImage::ImageRgb8(rgb).into_rgba8();
+            encoder
+                .encode_frame(image::Frame::from_parts(rgba, 0, 0, delay))
+                .map_err(|e| format!("GIF encode failed: {e}"))?;
+        }
+    }
+    Ok(buf)
+}
+
 /// Downscale a `CameraFrame` for preview using bilinear filtering.
 /// Returns a new `CameraFrame` at reduced resolution.
 ///
@@ -43,3 +81,90 @@ pub fn downsample_frame(frame: &CameraFrame, scale: f32) -> CameraFrame {
         image::imageops::resize(&img, new_w, new_h, image::imageops::FilterType::Triangle);
     CameraFrame::new(resized.into_raw(), new_w, new_h, frame.device_id.clone())
 }
+
+/// Largest size with the aspect ratio of `width`x`height` whose longest side
+/// is at most `max_dim`, never larger than the original
+pub fn fit_within(width: u32, height: u32, max_dim: u32) -> (u32, u32) {
+    let longest = width.max(height);
+    if longest <= max_dim {
+        return (width, height);
+    }
+    let scale = |side: u32| {
+        let scaled = u64::from(side) * u64::from(max_dim) / u64::from(longest);
+        u32::try_from(scaled).unwrap_or(max_dim).max(1)
+    };
+    (scale(width), scale(height))
+}
+
+/// Downscale an RGB frame so its longest side fits `max_dim`
+///
+/// The copy keeps the frame's id, timestamp and metadata. Returns `None`
+/// when the frame already fits or its data is not tightly packed RGB.
+pub fn downscale_to_max_dim(frame: &CameraFrame, max_dim: u32) -> Option<CameraFrame> {
+    let (width, height) = fit_within(frame.width, frame.height, max_dim);
+    if (width, height) == (frame.width, frame.height) {
+        return None;
+    }
+    let img = image::RgbImage::from_vec(frame.width, frame.height, frame.data.clone())?;
+    let resized =
+        image::imageops::resize(&img, width, height, image::imageops::FilterType::Triangle);
+    let mut scaled = frame.clone();
+    scaled.data = resized.into_raw();
+    scaled.size_bytes = scaled.data.len();
+    scaled.width = width;
+    scaled.height = height;
+    Some(scaled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_gif_keeps_every_frame() {
+        use image::AnimationDecoder;
+
+        let frames: Vec<CameraFrame> = [0u8, 128, 255]
+            .into_iter()
+            .map(|v| CameraFrame::new(vec![v; 16 * 8 * 3], 16, 8, "gif".to_string()))
+            .collect();
+        let gif = encode_gif(&frames, 100, true).expect("frames should encode");
+        assert!(gif.starts_with(b"GIF89a"));
+        assert!(gif.windows(11).any(|w| w == b"NETSCAPE2.0"), "loops");
+
+        let decoder = image::codecs::gif::GifDecoder::new(std::io::Cursor::new(gif)).unwrap();
+        let decoded = decoder.into_frames().collect_frames().unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[0].buffer().dimensions(), (16, 8));
+        assert_eq!(decoded[1].delay().numer_denom_ms(), (100, 1));
+        assert_eq!(decoded[2].buffer().get_pixel(3, 3).0, [255, 255, 255, 255]);
+
+        let bad = CameraFrame::new(vec![0; 5], 16, 8, "gif".to_string());
+        assert!(encode_gif(&[bad], 100, false).is_err());
+    }
+
+    #[test]
+    fn test_fit_within_keeps_aspect_and_never_upscales() {
+        assert_eq!(fit_within(1920, 1080, 640), (640, 360));
+        assert_eq!(fit_within(1080, 1920, 640), (360, 640));
+        assert_eq!(fit_within(320, 240, 640), (320, 240));
+        assert_eq!(fit_within(4000, 1, 100), (100, 1));
+    }
+
+    #[test]
+    fn test_downscale_to_max_dim_preserves_identity() {
+        let mut frame = CameraFrame::new(vec![200; 64 * 32 * 3], 64, 32, "scale".to_string());
+        frame.metadata.iso_sensitivity = Some(400);
+
+        let small = downscale_to_max_dim(&frame, 16).expect("frame should shrink");
+        assert_eq!((small.width, small.height), (16, 8));
+        assert_eq!(small.data.len(), 16 * 8 * 3);
+        assert_eq!(small.size_bytes, small.data.len());
+        assert_eq!(small.id, frame.id);
+        assert_eq!(small.timestamp, frame.timestamp);
+        assert_eq!(small.metadata.iso_sensitivity, Some(400));
+        assert!(small.data.iter().all(|&v| v == 200));
+
+        assert!(downscale_to_max_dim(&frame, 64).is_none(), "already fits");
+    }
+}
@@ -0,0 +1,334 @@
+//! Minimal MJPEG-over-HTTP server (`http-stream` feature)
+//!
+//! Serves the live camera as `multipart/x-mixed-replace` JPEG frames, which
+//! browsers, VLC and `ffplay` display natively — handy for checking a camera on
+//! a headless box without building a frontend. One capture task encodes each
+//! frame once and fans it out to every connected viewer through a broadcast
+//! channel; a viewer that falls behind skips frames instead of slowing the
+//! others down. Frames are only encoded while someone is watching.
+//!
+//! This is a debugging aid, not a hardened web server: there is no
+//! authentication or TLS, so bind to loopback unless the network is trusted.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use bytes::Bytes;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+
+use crate::constants::{
+    MJPEG_SERVER_BOUNDARY, MJPEG_SERVER_CHANNEL_CAPACITY, MJPEG_SERVER_JPEG_QUALITY,
+    MJPEG_SERVER_MAX_REQUEST_BYTES,
+};
+use crate::errors::CameraError;
+use crate::platform::PlatformCamera;
+use crate::preview::encode::encode_frame_jpeg;
+
+/// A running MJPEG server; stops when dropped or on [`MjpegServer::stop`]
+pub struct MjpegServer {
+    addr: SocketAddr,
+    cancel: CancellationToken,
+    frames: broadcast::Sender<Bytes>,
+}
+
+impl MjpegServer {
+    /// Bind `addr` and start streaming `camera` at up to `fps` frames per second
+    ///
+    /// Use port `0` to let the OS choose; [`MjpegServer::url`] reports the
+    /// actual address.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::ConnectionError`] if the address cannot be bound.
+    pub async fn start(
+        camera: Arc<StdMutex<PlatformCamera>>,
+        addr: SocketAddr,
+        fps: f32,
+    ) -> Result<Self, CameraError> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| CameraError::ConnectionError(format!("Failed to bind {addr}: {e}")))?;
+        let addr = listener.local_addr().map_err(|e| {
+            CameraError::ConnectionError(format!("Failed to read bound address: {e}"))
+        })?;
+
+        let (frames, _) = broadcast::channel(MJPEG_SERVER_CHANNEL_CAPACITY);
+        let cancel = CancellationToken::new();
+
+        tokio::spawn(run_capture(
+            camera,
+            frames.clone(),
+            cancel.clone(),
+            frame_interval(fps),
+        ));
+        tokio::spawn(run_accept(listener, frames.clone(), cancel.clone()));
+
+        log::info!("MJPEG server listening on http://{addr}/");
+        Ok(Self {
+            addr,
+            cancel,
+            frames,
+        })
+    }
+
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// URL of the stream, suitable for a browser or `<img src>`
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.addr)
+    }
+
+    /// Number of viewers currently connected
+    pub fn viewer_count(&self) -> usize {
+        self.frames.receiver_count()
+    }
+
+    /// Stop capturing, close all viewer connections and release the port
+    pub fn stop(&self) {
+        self.cancel.cancel();
+    }
+}
+
+impl Drop for MjpegServer {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+fn frame_interval(fps: f32) -> Duration {
+    if fps > 0.0 {
+        Duration::from_secs_f32(1.0 / fps)
+    } else {
+        Duration::from_millis(33)
+    }
+}
+
+/// Capture and encode frames while at least one viewer is connected
+async fn run_capture(
+    camera: Arc<StdMutex<PlatformCamera>>,
+    frames: broadcast::Sender<Bytes>,
+    cancel: CancellationToken,
+    interval: Duration,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            () = cancel.cancelled() => break,
+            _ = ticker.tick() => {}
+        }
+        if frames.receiver_count() == 0 {
+            continue;
+        }
+
+        let camera = camera.clone();
+        let encoded = tokio::task::spawn_blocking(move || {
+            let frame = camera
+                .lock()
+                .map_err(|_| "Mutex poisoned".to_string())?
+                .capture_frame()
+                .map_err(|e| e.to_string())?;
+            encode_frame_jpeg(&frame, MJPEG_SERVER_JPEG_QUALITY)
+        })
+        .await;
+
+        match encoded {
+            Ok(Ok(jpeg)) => {
+                let _ = frames.send(Bytes::from(jpeg));
+            }
+            Ok(Err(e)) => log::debug!("MJPEG server skipped a frame: {e}"),
+            Err(e) => log::warn!("MJPEG capture task failed: {e}"),
+        }
+    }
+}
+
+async fn run_accept(
+    listener: TcpListener,
+    frames: broadcast::Sender<Bytes>,
+    cancel: CancellationToken,
+) {
+    loop {
+        let (socket, peer) = tokio::select! {
+            () = cancel.cancelled() => break,
+            accepted = listener.accept() => match accepted {
+                Ok(conn) => conn,
+                Err(e) => {
+                    log::warn!("MJPEG server accept failed: {e}");
+                    continue;
+                }
+            },
+        };
+
+        let frames = frames.clone();
+        let cancel = cancel.clone();
+        tokio::spawn(async move {
+            if let Err(e) = serve_viewer(socket, frames, cancel).await {
+                log::debug!("MJPEG viewer {peer} disconnected: {e}");
+            }
+        });
+    }
+}
+
+async fn serve_viewer(
+    mut socket: TcpStream,
+    frames: broadcast::Sender<Bytes>,
+    cancel: CancellationToken,
+) -> std::io::Result<()> {
+    let path = read_request_path(&mut socket).await?;
+    if !matches!(path.as_deref(), Some("/" | "/stream")) {
+        socket
+            .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+            .await?;
+        return Ok(());
+    }
+
+    let header = format!(
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: multipart/x-mixed-replace; boundary={MJPEG_SERVER_BOUNDARY}\r\n\
+         Cache-Control: no-cache, no-store\r\n\
+         Pragma: no-cache\r\n\
+         Connection: close\r\n\r\n"
+    );
+    socket.write_all(header.as_bytes()).await?;
+
+    let mut frames = frames.subscribe();
+    loop {
+        let jpeg = tokio::select! {
+            () = cancel.cancelled() => return Ok(()),
+            received = frames.recv() => match received {
+                Ok(jpeg) => jpeg,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+        };
+
+        let part = format!(
+            "--{MJPEG_SERVER_BOUNDARY}\r\nContent-Type: image/jpeg\r\nContent-Length: {}\r\n\r\n",
+            jpeg.len()
+        );
+        socket.write_all(part.as_bytes()).await?;
+        socket.write_all(&jpeg).await?;
+        socket.write_all(b"\r\n").await?;
+    }
+}
+
+/// Read the request head and return the path of a `GET` request
+async fn read_request_path(socket: &mut TcpStream) -> std::io::Result<Option<String>> {
+    let mut head = Vec::with_capacity(512);
+    let mut buf = [0u8; 512];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        if head.len() >= MJPEG_SERVER_MAX_REQUEST_BYTES {
+            return Ok(None);
+        }
+        let n = socket.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+
+    Ok(parse_get_path(&head))
+}
+
+fn parse_get_path(head: &[u8]) -> Option<String> {
+    let request_line = head.split(|&b| b == b'\r').next()?;
+    let mut parts = std::str::from_utf8(request_line).ok()?.split(' ');
+    if parts.next()? != "GET" {
+        return None;
+    }
+    let target = parts.next()?;
+    Some(target.split('?').next().unwrap_or(target).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CameraFormat, CameraInitParams};
+
+    fn mock_camera(device_id: &str) -> Arc<StdMutex<PlatformCamera>> {
+        let params = CameraInitParams::new(device_id.to_string()).with_format(CameraFormat::low());
+        Arc::new(StdMutex::new(
+            PlatformCamera::new(params).expect("mock camera"),
+        ))
+    }
+
+    async fn get(addr: SocketAddr, path: &str, min_bytes: usize) -> Vec<u8> {
+        let mut socket = TcpStream::connect(addr).await.expect("connect");
+        socket
+            .write_all(format!("GET {path} HTTP/1.1\r\nHost: test\r\n\r\n").as_bytes())
+            .await
+            .expect("write request");
+
+        let mut received = Vec::new();
+        let mut buf = vec![0u8; 16 * 1024];
+        while received.len() < min_bytes {
+            let n = socket.read(&mut buf).await.expect("read");
+            if n == 0 {
+                break;
+            }
+            received.extend_from_slice(&buf[..n]);
+        }
+        received
+    }
+
+    #[test]
+    fn test_parse_get_path() {
+        assert_eq!(
+            parse_get_path(b"GET /stream?x=1 HTTP/1.1\r\nHost: a\r\n\r\n").as_deref(),
+            Some("/stream")
+        );
+        assert_eq!(parse_get_path(b"POST / HTTP/1.1\r\n\r\n"), None);
+        assert_eq!(parse_get_path(b"\xff\xfe\r\n\r\n"), None);
+    }
+
+    #[tokio::test]
+    async fn test_mjpeg_server_fans_out_to_concurrent_viewers() {
+        let server = MjpegServer::start(
+            mock_camera("mjpeg-fanout"),
+            "127.0.0.1:0".parse().expect("addr"),
+            30.0,
+        )
+        .await
+        .expect("server starts");
+        assert!(server.url().starts_with("http://127.0.0.1:"));
+
+        let addr = server.local_addr();
+        let (a, b) = tokio::time::timeout(Duration::from_secs(10), async {
+            tokio::join!(get(addr, "/", 64 * 1024), get(addr, "/stream", 64 * 1024))
+        })
+        .await
+        .expect("viewers receive frames");
+
+        for body in [a, b] {
+            let text = String::from_utf8_lossy(&body);
+            assert!(text.starts_with("HTTP/1.1 200 OK"));
+            assert!(text.contains("multipart/x-mixed-replace"));
+            assert!(text.contains("Content-Type: image/jpeg"));
+            // JPEG start-of-image marker
+            assert!(body.windows(2).any(|w| w == [0xFF, 0xD8]));
+        }
+
+        server.stop();
+    }
+
+    #[tokio::test]
+    async fn test_mjpeg_server_unknown_path_is_404() {
+        let server = MjpegServer::start(
+            mock_camera("mjpeg-404"),
+            "127.0.0.1:0".parse().expect("addr"),
+            30.0,
+        )
+        .await
+        .expect("server starts");
+
+        let body = get(server.local_addr(), "/favicon.ico", usize::MAX).await;
+        assert!(String::from_utf8_lossy(&body).starts_with("HTTP/1.1 404"));
+    }
+}
@@ -1,9 +1,14 @@
 /// JPEG encoding and downscaling helpers.
 pub mod encode;
+/// MJPEG-over-HTTP debug server.
+#[cfg(feature = "http-stream")]
+pub mod mjpeg_server;
 /// `PreviewStream` — push-based frame + metadata delivery.
 pub mod stream;
 /// Preview stream types (events and configuration).
 pub mod types;
 
+#[cfg(feature = "http-stream")]
+pub use mjpeg_server::MjpegServer;
 pub use stream::PreviewStream;
 pub use types::{PreviewConfig, PreviewFrameEvent};
@@ -3,7 +3,7 @@ use crate::constants::{
     DEFAULT_GRADIENT_THRESHOLD, DEFAULT_VARIANCE_THRESHOLD, QUALITY_SCORE_BLURRY,
     QUALITY_SCORE_GOOD, QUALITY_SCORE_MODERATE, QUALITY_SCORE_SHARP, QUALITY_SCORE_VERY_BLURRY,
 };
-use crate::types::CameraFrame;
+use crate::types::{CameraFrame, Rect};
 use serde::{Deserialize, Serialize};
 
 /// Blur detection levels
@@ -100,8 +100,8 @@ impl BlurDetector {
 
     /// Analyze frame for blur
     pub fn analyze_frame(&self, frame: &CameraFrame) -> BlurMetrics {
-        // Convert to grayscale for analysis
-        let grayscale = Self::rgb_to_grayscale(&frame.data, frame.width, frame.height);
+        // Convert to grayscale for analysis (alpha, if any, is ignored)
+        let grayscale = Self::rgb_to_grayscale(&frame.rgb_data(), frame.width, frame.height);
 
         // Calculate Laplacian variance (primary blur metric)
         let variance = Self::calculate_laplacian_variance(&grayscale, frame.width, frame.height);
@@ -126,6 +126,44 @@ impl BlurDetector {
         }
     }
 
+    /// Laplacian variance of a frame alone (higher = sharper)
+    ///
+    /// The primary metric of [`analyze_frame`](Self::analyze_frame), without
+    /// the gradient and edge passes, for ranking many frames cheaply.
+    #[must_use]
+    pub fn laplacian_variance(frame: &CameraFrame) -> f64 {
+        let grayscale = Self::rgb_to_grayscale(&frame.rgb_data(), frame.width, frame.height);
+        Self::calculate_laplacian_variance(&grayscale, frame.width, frame.height)
+    }
+
+    /// Laplacian variance of one region of a frame (higher = sharper)
+    ///
+    /// Used for contrast-detect focusing on a chosen point. The region is
+    /// clipped to the frame; an empty region scores 0.
+    #[must_use]
+    pub fn region_variance(frame: &CameraFrame, region: Rect) -> f64 {
+        let x0 = region.x.min(frame.width);
+        let y0 = region.y.min(frame.height);
+        let width = region.width.min(frame.width - x0);
+        let height = region.height.min(frame.height - y0);
+        if width == 0 || height == 0 {
+            return 0.0;
+        }
+
+        let grayscale = Self::rgb_to_grayscale(&frame.rgb_data(), frame.width, frame.height);
+        let stride = frame.width as usize;
+        let region: Vec<u8> = (y0..y0 + height)
+            .flat_map(|y| {
+                let start = y as usize * stride + x0 as usize;
+                grayscale
+                    .get(start..start + width as usize)
+                    .unwrap_or_default()
+            })
+            .copied()
+            .collect();
+        Self::calculate_laplacian_variance(&region, width, height)
+    }
+
     /// Convert RGB to grayscale
     fn rgb_to_grayscale(rgb_data: &[u8], width: u32, height: u32) -> Vec<u8> {
         let mut grayscale = Vec::with_capacity((width * height) as usize);
@@ -362,6 +400,32 @@ mod tests {
         assert!(grayscale[2] > 25 && grayscale[2] < 35); // Blue
     }
 
+    #[test]
+    fn test_region_variance_scores_only_the_region() {
+        // Checkerboard on the left half, flat gray on the right
+        let (width, height) = (40u32, 20u32);
+        let mut data = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let value = if x < width / 2 && (x + y) % 2 == 0 {
+                    255
+                } else {
+                    128
+                };
+                data.extend_from_slice(&[value, value, value]);
+            }
+        }
+        let frame = CameraFrame::new(data, width, height, "test".to_string());
+
+        let textured = BlurDetector::region_variance(&frame, Rect::new(0, 0, 20, 20));
+        let flat = BlurDetector::region_variance(&frame, Rect::new(22, 0, 18, 20));
+        assert!(textured > 1000.0);
+        assert!(flat.abs() < f64::EPSILON);
+        assert!(
+            BlurDetector::region_variance(&frame, Rect::new(50, 0, 10, 10)).abs() < f64::EPSILON
+        );
+    }
+
     #[test]
     fn test_frame_analysis() {
         let detector = BlurDetector::default();
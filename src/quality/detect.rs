@@ -0,0 +1,257 @@
+//! Best-effort face detection (`face-detect` feature)
+//!
+//! Uses the pure-Rust [`rustface`] port of the `SeetaFace` cascade detector, so
+//! no ML runtime is linked into the build. The detector needs the frontal
+//! model file (`seeta_fd_frontal_v1.0.bin`, ~1.2 MB), which is not bundled:
+//! point at it with [`set_face_model_path`] or the `CRABCAMERA_FACE_MODEL`
+//! environment variable.
+//!
+//! Results are intended for framing decisions (e.g. keeping a speaker
+//! centered), not identification. Small, profile, or poorly lit faces are
+//! routinely missed, and without a model every call simply finds nothing.
+
+use crate::constants::{
+    FACE_DETECT_MIN_FACE_SIZE, FACE_DETECT_PYRAMID_SCALE, FACE_DETECT_SCORE_THRESHOLD,
+    FACE_DETECT_WINDOW_STEP, FACE_MODEL_ENV_VAR,
+};
+use crate::errors::CameraError;
+use crate::types::{CameraFrame, Rect};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{LazyLock, RwLock};
+
+/// A detected face with the detector's confidence
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DetectedFace {
+    /// Bounding box in frame pixel coordinates.
+    pub bounds: Rect,
+    /// Raw detector score; higher is more confident. Not a probability.
+    pub confidence: f32,
+}
+
+static MODEL_PATH: LazyLock<RwLock<Option<PathBuf>>> = LazyLock::new(|| RwLock::new(None));
+
+static AUTO_DETECT_DEVICES: LazyLock<RwLock<HashSet<String>>> =
+    LazyLock::new(|| RwLock::new(HashSet::new()));
+
+thread_local! {
+    // `rustface` detectors are not `Send`, so each capture thread keeps its own,
+    // rebuilt only when the configured model path changes.
+    static DETECTOR: RefCell<Option<(PathBuf, Box<dyn rustface::Detector>)>> =
+        const { RefCell::new(None) };
+}
+
+/// Set the `SeetaFace` model file used by the detector
+///
+/// Takes precedence over the `CRABCAMERA_FACE_MODEL` environment variable.
+pub fn set_face_model_path(path: impl Into<PathBuf>) {
+    if let Ok(mut model) = MODEL_PATH.write() {
+        *model = Some(path.into());
+    }
+}
+
+fn model_path() -> Option<PathBuf> {
+    MODEL_PATH
+        .read()
+        .ok()
+        .and_then(|p| p.clone())
+        .or_else(|| std::env::var_os(FACE_MODEL_ENV_VAR).map(PathBuf::from))
+}
+
+/// Turn automatic detection on captured frames on or off for a device
+///
+/// While enabled, every frame returned by
+/// [`PlatformCamera::capture_frame`](crate::platform::PlatformCamera::capture_frame)
+/// for `device_id` gets [`FrameMetadata::faces`](crate::types::FrameMetadata::faces)
+/// populated. Detection runs on the capture thread, so expect lower frame rates.
+pub fn set_auto_detect(device_id: &str, enabled: bool) {
+    if let Ok(mut devices) = AUTO_DETECT_DEVICES.write() {
+        if enabled {
+            devices.insert(device_id.to_string());
+        } else {
+            devices.remove(device_id);
+        }
+    }
+}
+
+/// Whether automatic detection is enabled for `device_id`
+pub fn is_auto_detect_enabled(device_id: &str) -> bool {
+    AUTO_DETECT_DEVICES
+        .read()
+        .is_ok_and(|devices| devices.contains(device_id))
+}
+
+/// Detect faces in an RGB frame, with confidences
+///
+/// # Errors
+/// Returns a [`CameraError::UnsupportedOperation`] if no model file is
+/// configured, an [`CameraError::InitializationError`] if the model cannot be
+/// loaded, or a [`CameraError::UnsupportedFormat`] if the frame data is shorter
+/// than `width * height * 3`.
+pub fn detect_faces_with_confidence(frame: &CameraFrame) -> Result<Vec<DetectedFace>, CameraError> {
+    let path = model_path().ok_or_else(|| {
+        CameraError::UnsupportedOperation(format!(
+            "No face model configured; call set_face_model_path or set {FACE_MODEL_ENV_VAR}"
+        ))
+    })?;
+    let gray = rgb_to_gray(frame)?;
+
+    DETECTOR.with(|cell| {
+        let mut cached = cell.borrow_mut();
+        if cached.as_ref().is_none_or(|(loaded, _)| *loaded != path) {
+            let mut detector = rustface::create_detector(&path.to_string_lossy()).map_err(|e| {
+                CameraError::InitializationError(format!(
+                    "Failed to load face model {}: {e}",
+                    path.display()
+                ))
+            })?;
+            detector.set_min_face_size(FACE_DETECT_MIN_FACE_SIZE);
+            detector.set_score_thresh(FACE_DETECT_SCORE_THRESHOLD);
+            detector.set_pyramid_scale_factor(FACE_DETECT_PYRAMID_SCALE);
+            detector.set_slide_window_step(FACE_DETECT_WINDOW_STEP, FACE_DETECT_WINDOW_STEP);
+            *cached = Some((path, detector));
+        }
+        let Some((_, detector)) = cached.as_mut() else {
+            return Ok(Vec::new());
+        };
+
+        let image = rustface::ImageData::new(&gray, frame.width, frame.height);
+        Ok(detector
+            .detect(&image)
+            .iter()
+            .map(|face| {
+                let bbox = face.bbox();
+                DetectedFace {
+                    bounds: clamp_to_frame(
+                        bbox.x(),
+                        bbox.y(),
+                        bbox.width(),
+                        bbox.height(),
+                        frame,
+                    ),
+                    #[allow(clippy::cast_possible_truncation)] // scores are small (< 100)
+                    confidence: face.score() as f32,
+                }
+            })
+            .filter(|face| face.bounds.area() > 0)
+            .collect())
+    })
+}
+
+/// Detect faces in an RGB frame
+///
+/// Best-effort: any failure (no model configured, bad frame) is logged and
+/// reported as no faces.
+pub fn detect_faces(frame: &CameraFrame) -> Vec<Rect> {
+    match detect_faces_with_confidence(frame) {
+        Ok(faces) => faces.into_iter().map(|face| face.bounds).collect(),
+        Err(e) => {
+            log::debug!("Face detection skipped for {}: {e}", frame.device_id);
+            Vec::new()
+        }
+    }
+}
+
+/// Populate `metadata.faces` if automatic detection is enabled for the frame's device
+pub(crate) fn annotate_frame(mut frame: CameraFrame) -> CameraFrame {
+    if is_auto_detect_enabled(&frame.device_id) {
+        let faces = detect_faces(&frame);
+        frame.metadata.faces = Some(faces);
+    }
+    frame
+}
+
+fn rgb_to_gray(frame: &CameraFrame) -> Result<Vec<u8>, CameraError> {
+    let pixels = frame.width as usize * frame.height as usize;
+    let data = frame.rgb_data();
+    let rgb = data.get(..pixels * 3).ok_or_else(|| {
+        CameraError::UnsupportedFormat(format!(
+            "Face detection expects RGB data: {} bytes for {}x{}",
+            frame.data.len(),
+            frame.width,
+            frame.height
+        ))
+    })?;
+
+    // Integer BT.601 luma: (77 R + 150 G + 29 B) / 256
+    Ok(rgb
+        .chunks_exact(3)
+        .map(|px| {
+            let luma = 77 * u32::from(px[0]) + 150 * u32::from(px[1]) + 29 * u32::from(px[2]);
+            u8::try_from(luma >> 8).unwrap_or(u8::MAX)
+        })
+        .collect())
+}
+
+/// Clip a detector box (which may extend past the edges) to the frame
+fn clamp_to_frame(x: i32, y: i32, width: u32, height: u32, frame: &CameraFrame) -> Rect {
+    let left = u32::try_from(x).unwrap_or(0).min(frame.width);
+    let top = u32::try_from(y).unwrap_or(0).min(frame.height);
+    let right = (i64::from(x) + i64::from(width)).clamp(0, i64::from(frame.width));
+    let bottom = (i64::from(y) + i64::from(height)).clamp(0, i64::from(frame.height));
+    let right = u32::try_from(right).unwrap_or(frame.width);
+    let bottom = u32::try_from(bottom).unwrap_or(frame.height);
+    Rect::new(
+        left,
+        top,
+        right.saturating_sub(left),
+        bottom.saturating_sub(top),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(width: u32, height: u32) -> CameraFrame {
+        CameraFrame::new(
+            vec![128; (width * height * 3) as usize],
+            width,
+            height,
+            "detect-test".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_clamp_to_frame() {
+        let f = frame(100, 80);
+        assert_eq!(
+            clamp_to_frame(10, 10, 20, 20, &f),
+            Rect::new(10, 10, 20, 20)
+        );
+        assert_eq!(clamp_to_frame(-5, -5, 20, 20, &f), Rect::new(0, 0, 15, 15));
+        assert_eq!(
+            clamp_to_frame(90, 70, 20, 20, &f),
+            Rect::new(90, 70, 10, 10)
+        );
+        assert_eq!(clamp_to_frame(-50, 0, 20, 20, &f).area(), 0);
+    }
+
+    #[test]
+    fn test_rgb_to_gray_rejects_short_data() {
+        let mut f = frame(4, 4);
+        assert_eq!(rgb_to_gray(&f).expect("gray").len(), 16);
+        f.data.truncate(10);
+        assert!(matches!(
+            rgb_to_gray(&f),
+            Err(CameraError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_auto_detect_toggle_and_annotate() {
+        let f = frame(8, 8);
+        assert!(annotate_frame(f.clone()).metadata.faces.is_none());
+
+        set_auto_detect("detect-test", true);
+        assert!(is_auto_detect_enabled("detect-test"));
+        // Best-effort: without a loadable model the detector finds nothing
+        set_face_model_path("/nonexistent/seeta_fd_frontal_v1.0.bin");
+        assert_eq!(annotate_frame(f).metadata.faces, Some(Vec::new()));
+
+        set_auto_detect("detect-test", false);
+        assert!(!is_auto_detect_enabled("detect-test"));
+    }
+}
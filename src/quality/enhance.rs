@@ -0,0 +1,240 @@
+//! Low-light auto-enhancement
+//!
+//! [`auto_enhance`] brightens dark RGB frames: an adaptive gamma curve lifts
+//! the overall level, then CLAHE-style (contrast-limited, tile-based)
+//! histogram equalization restores local contrast. Both work on luminance and
+//! each pixel's RGB is scaled by the resulting gain, so hues are preserved.
+//! Frames the [`ExposureAnalyzer`] does not rate as dark are left untouched.
+//!
+//! This is a perceptual filter, not a physically accurate exposure
+//! correction: output brightness is not proportional to scene light and
+//! should not be used for measurement or comparing frames.
+
+use crate::constants::{
+    ENHANCE_CLIP_LIMIT, ENHANCE_MAX_GAIN, ENHANCE_MIN_GAMMA, ENHANCE_TARGET_BRIGHTNESS,
+    ENHANCE_TILE_GRID, FORMAT_RGB,
+};
+use crate::quality::{ExposureAnalyzer, ExposureLevel};
+use crate::types::CameraFrame;
+
+/// Brighten an under-exposed RGB frame
+///
+/// `strength` (clamped to 0.0-1.0) blends from the original (0.0) to the
+/// fully enhanced luminance (1.0). Per-pixel gain is capped at
+/// [`ENHANCE_MAX_GAIN`] so sensor noise in near-black areas is not blown up.
+/// Non-RGB frames and frames that are not dark are returned unchanged.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped to 0..=255
+pub fn auto_enhance(mut frame: CameraFrame, strength: f32) -> CameraFrame {
+    let strength = strength.clamp(0.0, 1.0);
+    let pixels = frame.width as usize * frame.height as usize;
+    if frame.format != FORMAT_RGB || strength <= 0.0 || pixels == 0 || frame.data.len() < pixels * 3
+    {
+        return frame;
+    }
+
+    let metrics = ExposureAnalyzer::default().analyze_frame(&frame);
+    if !needs_enhancement(metrics.exposure_level) {
+        return frame;
+    }
+
+    let luma: Vec<u8> = frame.data[..pixels * 3]
+        .chunks_exact(3)
+        .map(luminance)
+        .collect();
+    let curve = gamma_lut(adaptive_gamma(metrics.mean_brightness));
+    let brightened: Vec<u8> = luma.iter().map(|&y| curve[usize::from(y)]).collect();
+    let equalized = clahe(
+        &brightened,
+        frame.width as usize,
+        frame.height as usize,
+        ENHANCE_CLIP_LIMIT,
+    );
+
+    for ((px, &y), &target) in frame.data.chunks_exact_mut(3).zip(&luma).zip(&equalized) {
+        let y = f32::from(y);
+        let enhanced = y + (f32::from(target) - y) * strength;
+        let gain = ((enhanced + 1.0) / (y + 1.0)).clamp(1.0, ENHANCE_MAX_GAIN);
+        for c in px {
+            *c = (f32::from(*c) * gain).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+
+    frame
+}
+
+/// Whether an exposure level is dark enough to enhance
+pub fn needs_enhancement(level: ExposureLevel) -> bool {
+    matches!(
+        level,
+        ExposureLevel::Underexposed | ExposureLevel::SlightlyDark
+    )
+}
+
+/// Gamma that maps the mean brightness (0.0-1.0) onto the target brightness
+fn adaptive_gamma(mean_brightness: f32) -> f32 {
+    if mean_brightness <= 0.0 || mean_brightness >= 1.0 {
+        return ENHANCE_MIN_GAMMA;
+    }
+    (ENHANCE_TARGET_BRIGHTNESS.ln() / mean_brightness.ln()).clamp(ENHANCE_MIN_GAMMA, 1.0)
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)] // clamped to 0..=255
+fn gamma_lut(gamma: f32) -> [u8; 256] {
+    let mut lut = [0u8; 256];
+    for (i, v) in lut.iter_mut().enumerate() {
+        let x = i as f32 / 255.0;
+        *v = (x.powf(gamma) * 255.0).round().clamp(0.0, 255.0) as u8;
+    }
+    lut
+}
+
+/// ITU-R BT.709 luminance of one RGB pixel
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped to 0..=255
+fn luminance(px: &[u8]) -> u8 {
+    (0.2126 * f32::from(px[0]) + 0.7152 * f32::from(px[1]) + 0.0722 * f32::from(px[2]))
+        .round()
+        .clamp(0.0, 255.0) as u8
+}
+
+/// Contrast-limited adaptive histogram equalization of a luminance plane
+///
+/// Each tile of a grid of up to [`ENHANCE_TILE_GRID`]² tiles gets its own
+/// equalization curve, with histogram bins clipped at `clip_limit` times the
+/// uniform bin height; the clipped excess is spread over all bins. Pixels
+/// interpolate bilinearly between the curves of the four nearest tiles.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)] // tile indices and counts are small; output clamped to 0..=255
+fn clahe(luma: &[u8], width: usize, height: usize, clip_limit: f32) -> Vec<u8> {
+    let tile_w = width.div_ceil(ENHANCE_TILE_GRID.min(width));
+    let tile_h = height.div_ceil(ENHANCE_TILE_GRID.min(height));
+    let tiles_x = width.div_ceil(tile_w);
+    let tiles_y = height.div_ceil(tile_h);
+
+    let mut luts = Vec::with_capacity(tiles_x * tiles_y);
+    for ty in 0..tiles_y {
+        for tx in 0..tiles_x {
+            let mut hist = [0u32; 256];
+            let rows = ty * tile_h..((ty + 1) * tile_h).min(height);
+            let cols = tx * tile_w..((tx + 1) * tile_w).min(width);
+            for y in rows {
+                for &v in &luma[y * width + cols.start..y * width + cols.end] {
+                    hist[usize::from(v)] += 1;
+                }
+            }
+            luts.push(clipped_equalization(&hist, clip_limit));
+        }
+    }
+
+    let axis = |pos: usize, tile: usize, tiles: usize| {
+        let f = (pos as f32 + 0.5) / tile as f32 - 0.5;
+        let t0 = (f.floor().max(0.0) as usize).min(tiles - 1);
+        let t1 = (t0 + 1).min(tiles - 1);
+        (t0, t1, (f - t0 as f32).clamp(0.0, 1.0))
+    };
+
+    let mut out = Vec::with_capacity(luma.len());
+    for y in 0..height {
+        let (ty0, ty1, ay) = axis(y, tile_h, tiles_y);
+        for x in 0..width {
+            let (tx0, tx1, ax) = axis(x, tile_w, tiles_x);
+            let v = usize::from(luma[y * width + x]);
+            let at = |ty: usize, tx: usize| f32::from(luts[ty * tiles_x + tx][v]);
+            let top = at(ty0, tx0) + (at(ty0, tx1) - at(ty0, tx0)) * ax;
+            let bottom = at(ty1, tx0) + (at(ty1, tx1) - at(ty1, tx0)) * ax;
+            out.push((top + (bottom - top) * ay).round().clamp(0.0, 255.0) as u8);
+        }
+    }
+    out
+}
+
+/// Equalization curve for one tile's histogram with contrast limiting
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)] // output clamped to 0..=255
+fn clipped_equalization(hist: &[u32; 256], clip_limit: f32) -> [u8; 256] {
+    let total: u32 = hist.iter().sum();
+    let mut lut = [0u8; 256];
+    if total == 0 {
+        return lut;
+    }
+
+    let limit = (clip_limit * total as f32 / 256.0).max(1.0);
+    let mut excess = 0.0;
+    let mut clipped = [0f32; 256];
+    for (c, &h) in clipped.iter_mut().zip(hist) {
+        let h = h as f32;
+        *c = h.min(limit);
+        excess += h - *c;
+    }
+    let bonus = excess / 256.0;
+
+    let mut cdf = 0.0;
+    for (v, c) in lut.iter_mut().zip(clipped) {
+        cdf += c + bonus;
+        *v = (cdf / total as f32 * 255.0).round().clamp(0.0, 255.0) as u8;
+    }
+    lut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Horizontal gradient scaled by `scale` to simulate a dim scene
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn gradient_frame(width: u32, height: u32, scale: f32) -> CameraFrame {
+        let mut data = Vec::with_capacity((width * height * 3) as usize);
+        for _ in 0..height {
+            for x in 0..width {
+                let v = (x * 255 / (width - 1)) as f32 * scale;
+                data.extend([v as u8, (v * 0.9) as u8, (v * 0.8) as u8]);
+            }
+        }
+        CameraFrame::new(data, width, height, "enhance-test".to_string())
+    }
+
+    #[test]
+    fn test_auto_enhance_brightens_dark_frame() {
+        let dark = gradient_frame(64, 48, 0.15);
+        let analyzer = ExposureAnalyzer::default();
+        let before = analyzer.analyze_frame(&dark).mean_brightness;
+
+        let enhanced = auto_enhance(dark.clone(), 1.0);
+        let after = analyzer.analyze_frame(&enhanced).mean_brightness;
+
+        assert!(after > before, "mean luminance {before} -> {after}");
+        assert!(
+            enhanced
+                .data
+                .iter()
+                .zip(&dark.data)
+                .all(|(e, d)| { f32::from(*e) <= (f32::from(*d) + 1.0) * ENHANCE_MAX_GAIN }),
+            "gain is capped"
+        );
+    }
+
+    #[test]
+    fn test_auto_enhance_leaves_good_frames_alone() {
+        let bright = gradient_frame(64, 48, 1.0);
+        assert_eq!(auto_enhance(bright.clone(), 1.0).data, bright.data);
+
+        let dark = gradient_frame(64, 48, 0.15);
+        assert_eq!(auto_enhance(dark.clone(), 0.0).data, dark.data);
+
+        let mjpeg = dark.clone().with_format("MJPEG".to_string());
+        assert_eq!(auto_enhance(mjpeg, 1.0).data, dark.data);
+    }
+}
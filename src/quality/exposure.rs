@@ -100,8 +100,8 @@ impl ExposureAnalyzer {
 
     /// Analyze frame exposure
     pub fn analyze_frame(&self, frame: &CameraFrame) -> ExposureMetrics {
-        // Convert to grayscale for luminance analysis
-        let grayscale = Self::rgb_to_luminance(&frame.data, frame.width, frame.height);
+        // Convert to grayscale for luminance analysis (alpha, if any, is ignored)
+        let grayscale = Self::rgb_to_luminance(&frame.rgb_data(), frame.width, frame.height);
 
         // Calculate histogram
         let histogram = Self::calculate_histogram(&grayscale);
@@ -0,0 +1,270 @@
+//! Auto-framing: crop to keep a subject centered
+//!
+//! [`AutoFramer`] turns a subject bounding box (typically the largest detected
+//! face) into a crop window at a fixed aspect ratio. Across successive frames
+//! the window follows the subject with exponential smoothing instead of
+//! jumping, and the cropped region is always scaled to the same output size so
+//! it can feed a video pipeline directly. Without a subject the window eases
+//! back to a center crop.
+
+use crate::constants::{AUTOFRAME_MIN_CROP_FRACTION, AUTOFRAME_SMOOTHING, FORMAT_RGB};
+use crate::errors::CameraError;
+use crate::types::{CameraFrame, Rect};
+use image::imageops::{self, FilterType};
+
+/// Crop window in sub-pixel coordinates, kept between frames for smoothing
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Window {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Window {
+    fn lerp(self, target: Window, alpha: f64) -> Window {
+        Window {
+            x: self.x + (target.x - self.x) * alpha,
+            y: self.y + (target.y - self.y) * alpha,
+            width: self.width + (target.width - self.width) * alpha,
+            height: self.height + (target.height - self.height) * alpha,
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped to frame bounds
+    fn to_rect(self, frame_width: u32, frame_height: u32) -> Rect {
+        let x = self.x.round().clamp(0.0, f64::from(frame_width - 1)) as u32;
+        let y = self.y.round().clamp(0.0, f64::from(frame_height - 1)) as u32;
+        let width = (self.width.round() as u32).clamp(1, frame_width - x);
+        let height = (self.height.round() as u32).clamp(1, frame_height - y);
+        Rect::new(x, y, width, height)
+    }
+}
+
+/// Stateful crop-to-subject framing for a single camera
+#[derive(Debug, Clone)]
+pub struct AutoFramer {
+    target_aspect: f64,
+    padding: f64,
+    smoothing: f64,
+    window: Option<Window>,
+}
+
+impl AutoFramer {
+    /// Create a framer for `target_aspect` (width / height)
+    ///
+    /// `padding` is the margin added around the subject on each side, as a
+    /// fraction of the subject's size (e.g. `0.5` leaves half a face of room).
+    pub fn new(target_aspect: f32, padding: f32) -> Self {
+        Self {
+            target_aspect: f64::from(target_aspect),
+            padding: f64::from(padding.max(0.0)),
+            smoothing: AUTOFRAME_SMOOTHING,
+            window: None,
+        }
+    }
+
+    /// Set the smoothing factor (0.0-1.0)
+    ///
+    /// This is the fraction of the distance to the new target covered each
+    /// frame; `1.0` disables smoothing.
+    #[must_use]
+    pub fn with_smoothing(mut self, smoothing: f64) -> Self {
+        self.smoothing = smoothing.clamp(0.01, 1.0);
+        self
+    }
+
+    /// Whether the framer parameters match, so its smoothing state can be reused
+    pub fn is_configured_for(&self, target_aspect: f32, padding: f32) -> bool {
+        (self.target_aspect - f64::from(target_aspect)).abs() < 1e-3
+            && (self.padding - f64::from(padding.max(0.0))).abs() < 1e-3
+    }
+
+    /// Forget the previous crop so the next frame snaps to its target
+    pub fn reset(&mut self) {
+        self.window = None;
+    }
+
+    /// Compute the next crop rectangle for a frame of the given size
+    pub fn next_crop(
+        &mut self,
+        frame_width: u32,
+        frame_height: u32,
+        subject: Option<Rect>,
+    ) -> Rect {
+        let target = self.target_window(frame_width, frame_height, subject);
+        let window = match self.window {
+            // Resolution changed: smoothing between incompatible windows is meaningless
+            Some(prev)
+                if prev.width <= f64::from(frame_width)
+                    && prev.height <= f64::from(frame_height) =>
+            {
+                prev.lerp(target, self.smoothing)
+            }
+            _ => target,
+        };
+        self.window = Some(window);
+        window.to_rect(frame_width, frame_height)
+    }
+
+    /// Size of the largest window at the target aspect that fits in the frame
+    fn max_window(&self, frame_width: u32, frame_height: u32) -> (f64, f64) {
+        let (fw, fh) = (f64::from(frame_width), f64::from(frame_height));
+        if fw / fh > self.target_aspect {
+            (fh * self.target_aspect, fh)
+        } else {
+            (fw, fw / self.target_aspect)
+        }
+    }
+
+    fn target_window(&self, frame_width: u32, frame_height: u32, subject: Option<Rect>) -> Window {
+        let (fw, fh) = (f64::from(frame_width), f64::from(frame_height));
+        let (max_w, max_h) = self.max_window(frame_width, frame_height);
+
+        let Some(subject) = subject.filter(|s| s.area() > 0) else {
+            return Window {
+                x: (fw - max_w) / 2.0,
+                y: (fh - max_h) / 2.0,
+                width: max_w,
+                height: max_h,
+            };
+        };
+
+        // Pad the subject, then grow the short side to reach the target aspect
+        let padded_w = f64::from(subject.width) * (1.0 + 2.0 * self.padding);
+        let padded_h = f64::from(subject.height) * (1.0 + 2.0 * self.padding);
+        let height = padded_h
+            .max(padded_w / self.target_aspect)
+            .clamp(max_h * AUTOFRAME_MIN_CROP_FRACTION, max_h);
+        let width = height * self.target_aspect;
+
+        let (cx, cy) = subject.center();
+        Window {
+            x: (cx - width / 2.0).clamp(0.0, (fw - width).max(0.0)),
+            y: (cy - height / 2.0).clamp(0.0, (fh - height).max(0.0)),
+            width,
+            height,
+        }
+    }
+
+    /// Output size every cropped frame is scaled to
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // bounded by frame size
+    pub fn output_size(&self, frame_width: u32, frame_height: u32) -> (u32, u32) {
+        let (w, h) = self.max_window(frame_width, frame_height);
+        ((w.round() as u32).max(1), (h.round() as u32).max(1))
+    }
+
+    /// Crop an RGB frame to the next window and scale it to [`Self::output_size`]
+    ///
+    /// The subject is `frame.metadata.faces` (largest face) when present. The
+    /// chosen crop is recorded in the returned frame's `metadata.crop`.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::UnsupportedFormat`] if the frame is not RGB or
+    /// its data does not match its dimensions.
+    pub fn frame(&mut self, frame: &CameraFrame) -> Result<CameraFrame, CameraError> {
+        if frame.format != FORMAT_RGB {
+            return Err(CameraError::UnsupportedFormat(format!(
+                "Auto-framing requires RGB frames, got {}",
+                frame.format
+            )));
+        }
+        let image = image::RgbImage::from_raw(frame.width, frame.height, frame.data.clone())
+            .filter(|_| frame.width > 0 && frame.height > 0)
+            .ok_or_else(|| {
+                CameraError::UnsupportedFormat(format!(
+                    "Frame data does not match {}x{} RGB",
+                    frame.width, frame.height
+                ))
+            })?;
+
+        let subject = frame
+            .metadata
+            .faces
+            .as_ref()
+            .and_then(|faces| faces.iter().copied().max_by_key(Rect::area));
+        let crop = self.next_crop(frame.width, frame.height, subject);
+        let (out_w, out_h) = self.output_size(frame.width, frame.height);
+
+        let cropped =
+            imageops::crop_imm(&image, crop.x, crop.y, crop.width, crop.height).to_image();
+        let scaled = imageops::resize(&cropped, out_w, out_h, FilterType::Triangle);
+
+        let mut output = CameraFrame::new(scaled.into_raw(), out_w, out_h, frame.device_id.clone());
+        output.timestamp = frame.timestamp;
+        output.metadata = frame.metadata.clone();
+        output.metadata.crop = Some(crop);
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_center_crop_without_subject() {
+        let mut framer = AutoFramer::new(1.0, 0.5);
+        assert_eq!(framer.next_crop(640, 480, None), Rect::new(80, 0, 480, 480));
+        assert_eq!(framer.output_size(640, 480), (480, 480));
+    }
+
+    #[test]
+    fn test_subject_crop_is_padded_centered_and_in_bounds() {
+        let mut framer = AutoFramer::new(16.0 / 9.0, 0.5);
+        let face = Rect::new(500, 100, 100, 100);
+        let crop = framer.next_crop(1280, 720, Some(face));
+
+        // 100px face + 50% padding per side = 200px tall window
+        assert_eq!(crop.height, 200);
+        assert_eq!(crop.width, 356);
+        let (cx, cy) = crop.center();
+        assert!((cx - 550.0).abs() <= 1.0 && (cy - 150.0).abs() <= 1.0);
+
+        // Subject at the edge: window is pushed back inside the frame
+        framer.reset();
+        let crop = framer.next_crop(1280, 720, Some(Rect::new(1250, 690, 30, 30)));
+        assert!(crop.x + crop.width <= 1280 && crop.y + crop.height <= 720);
+    }
+
+    #[test]
+    fn test_crop_moves_smoothly_between_frames() {
+        let mut framer = AutoFramer::new(1.0, 0.0).with_smoothing(0.5);
+        let left = framer.next_crop(1000, 500, Some(Rect::new(100, 200, 200, 200)));
+        let step = framer.next_crop(1000, 500, Some(Rect::new(700, 200, 200, 200)));
+        assert_eq!(left.x, 100);
+        assert_eq!(step.x, 400, "half-way towards the new target");
+
+        let mut x = step.x;
+        for _ in 0..20 {
+            x = framer
+                .next_crop(1000, 500, Some(Rect::new(700, 200, 200, 200)))
+                .x;
+        }
+        assert_eq!(x, 700);
+    }
+
+    #[test]
+    fn test_frame_crops_scales_and_records_crop() {
+        let mut frame = CameraFrame::new(vec![90; 320 * 240 * 3], 320, 240, "af".to_string());
+        frame.metadata.faces = Some(vec![Rect::new(10, 10, 20, 20), Rect::new(150, 100, 60, 60)]);
+
+        let mut framer = AutoFramer::new(4.0 / 3.0, 0.25);
+        let out = framer.frame(&frame).expect("framing should succeed");
+
+        assert_eq!((out.width, out.height), (320, 240));
+        assert_eq!(out.data.len(), 320 * 240 * 3);
+        let crop = out.metadata.crop.expect("crop recorded");
+        let (cx, cy) = crop.center();
+        assert!(
+            (cx - 180.0).abs() <= 1.0 && (cy - 130.0).abs() <= 1.0,
+            "tracks the largest face"
+        );
+
+        let mjpeg = frame.with_format("MJPEG".to_string());
+        assert!(matches!(
+            framer.frame(&mjpeg),
+            Err(CameraError::UnsupportedFormat(_))
+        ));
+    }
+}
@@ -3,13 +3,30 @@
 /// Provides automated quality assessment for captured frames including
 /// blur detection, exposure analysis, and overall image quality scoring.
 pub mod blur;
+/// Multi-frame temporal noise reduction.
+pub mod denoise;
+/// Best-effort face detection.
+#[cfg(feature = "face-detect")]
+pub mod detect;
+/// Low-light auto-enhancement.
+pub mod enhance;
 /// Exposure analysis and correction recommendations.
 pub mod exposure;
+/// Crop-to-subject auto-framing.
+pub mod framing;
+/// Text and shape burn-in overlays.
+pub mod overlay;
 /// Quality validation summary and reporting.
 pub mod validator;
+/// Software white-balance correction.
+pub mod white_balance;
 
 pub use blur::{BlurDetector, BlurLevel, BlurMetrics};
+pub use denoise::temporal_denoise;
+pub use enhance::auto_enhance;
 pub use exposure::{ExposureAnalyzer, ExposureLevel, ExposureMetrics};
+pub use framing::AutoFramer;
+pub use overlay::{draw_overlay, timestamp_overlay, DrawOp};
 pub use validator::{QualityReport, QualityScore, QualityValidator, ValidationConfig};
 
 /// Smart capture triggering based on quality metrics.
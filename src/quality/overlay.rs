@@ -0,0 +1,387 @@
+//! Burn-in overlays for captured frames
+//!
+//! [`draw_overlay`] renders text, rectangles and lines straight into an RGB8
+//! (or RGBA8) frame buffer, e.g. for security-camera timestamps before a
+//! frame is saved or streamed. Text uses a built-in 5×7 bitmap font covering
+//! digits, letters (lowercase is drawn as uppercase) and common punctuation;
+//! unknown characters render as `?`. Shapes are clipped to the frame.
+
+use crate::constants::{
+    TIMESTAMP_OVERLAY_FORMAT, TIMESTAMP_OVERLAY_LINES_PER_SCALE, TIMESTAMP_OVERLAY_MARGIN,
+};
+use crate::types::{CameraFrame, FourCC};
+use serde::{Deserialize, Serialize};
+
+/// Glyph cell width of the bitmap font (pixels at scale 1)
+const GLYPH_WIDTH: u32 = 5;
+/// Glyph cell height of the bitmap font (pixels at scale 1)
+const GLYPH_HEIGHT: u32 = 7;
+
+/// A single overlay drawing operation
+///
+/// Positions are in frame pixels from the top-left corner; colors are RGB.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum DrawOp {
+    /// Text with its top-left corner at `pos`; `\n` starts a new line
+    Text {
+        /// Top-left corner of the first glyph
+        pos: (u32, u32),
+        /// Text to render
+        content: String,
+        /// Text color
+        color: [u8; 3],
+        /// Integer magnification of the 5×7 font (0 is treated as 1)
+        scale: u32,
+    },
+    /// Axis-aligned rectangle
+    Rect {
+        /// Top-left corner
+        pos: (u32, u32),
+        /// Width and height
+        size: (u32, u32),
+        /// Fill or outline color
+        color: [u8; 3],
+        /// Fill the rectangle instead of drawing a 1-pixel outline
+        filled: bool,
+    },
+    /// One-pixel line between two points (inclusive)
+    Line {
+        /// Start point
+        from: (u32, u32),
+        /// End point
+        to: (u32, u32),
+        /// Line color
+        color: [u8; 3],
+    },
+}
+
+/// Draw `ops` onto the frame in order
+///
+/// Frames that are not RGB8 or RGBA8, or whose buffer is smaller than their
+/// dimensions, are returned unchanged. Alpha is left as-is on RGBA8 frames.
+pub fn draw_overlay(mut frame: CameraFrame, ops: &[DrawOp]) -> CameraFrame {
+    let Some(mut canvas) = Canvas::new(&mut frame) else {
+        return frame;
+    };
+    for op in ops {
+        match op {
+            DrawOp::Text {
+                pos,
+                content,
+                color,
+                scale,
+            } => canvas.text(*pos, content, *color, (*scale).max(1)),
+            DrawOp::Rect {
+                pos,
+                size,
+                color,
+                filled,
+            } => canvas.rect(*pos, *size, *color, *filled),
+            DrawOp::Line { from, to, color } => canvas.line(*from, *to, *color),
+        }
+    }
+    frame
+}
+
+/// Burn the frame's capture time into its top-left corner
+///
+/// White text on a black box, formatted with [`TIMESTAMP_OVERLAY_FORMAT`] and
+/// scaled with the frame height (one step per
+/// [`TIMESTAMP_OVERLAY_LINES_PER_SCALE`] lines).
+pub fn timestamp_overlay(frame: CameraFrame) -> CameraFrame {
+    let content = frame.timestamp.format(TIMESTAMP_OVERLAY_FORMAT).to_string();
+    let scale = (frame.height / TIMESTAMP_OVERLAY_LINES_PER_SCALE).max(1);
+    let (text_w, text_h) = text_size(&content, scale);
+    let margin = TIMESTAMP_OVERLAY_MARGIN;
+    let pad = 2 * scale;
+
+    let ops = [
+        DrawOp::Rect {
+            pos: (margin, margin),
+            size: (text_w + 2 * pad, text_h + 2 * pad),
+            color: [0, 0, 0],
+            filled: true,
+        },
+        DrawOp::Text {
+            pos: (margin + pad, margin + pad),
+            content,
+            color: [255, 255, 255],
+            scale,
+        },
+    ];
+    draw_overlay(frame, &ops)
+}
+
+/// Pixel size of `content` rendered at `scale`
+pub fn text_size(content: &str, scale: u32) -> (u32, u32) {
+    let scale = scale.max(1);
+    let lines = content.split('\n');
+    let (count, widest) = lines.fold((0u32, 0u32), |(count, widest), line| {
+        let chars = u32::try_from(line.chars().count()).unwrap_or(u32::MAX);
+        (count + 1, widest.max(chars))
+    });
+    let width = widest
+        .saturating_mul(GLYPH_WIDTH + 1)
+        .saturating_sub(1)
+        .saturating_mul(scale);
+    let height = count
+        .saturating_mul(GLYPH_HEIGHT + 1)
+        .saturating_sub(1)
+        .saturating_mul(scale);
+    (width, height)
+}
+
+/// Mutable view of a frame's pixels
+struct Canvas<'a> {
+    data: &'a mut [u8],
+    width: u32,
+    height: u32,
+    stride: usize,
+}
+
+impl<'a> Canvas<'a> {
+    fn new(frame: &'a mut CameraFrame) -> Option<Self> {
+        let stride = match frame.format.parse::<FourCC>().ok()? {
+            f if f == FourCC::RGB8 => 3,
+            f if f == FourCC::RGBA8 => 4,
+            _ => return None,
+        };
+        let len = frame.width as usize * frame.height as usize * stride;
+        if frame.data.len() < len {
+            return None;
+        }
+        Some(Self {
+            data: &mut frame.data[..len],
+            width: frame.width,
+            height: frame.height,
+            stride,
+        })
+    }
+
+    fn put(&mut self, x: i64, y: i64, color: [u8; 3]) {
+        let (Ok(x), Ok(y)) = (u32::try_from(x), u32::try_from(y)) else {
+            return;
+        };
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let at = (y as usize * self.width as usize + x as usize) * self.stride;
+        self.data[at..at + 3].copy_from_slice(&color);
+    }
+
+    fn fill(&mut self, pos: (u32, u32), size: (u32, u32), color: [u8; 3]) {
+        let x_end = pos.0.saturating_add(size.0).min(self.width);
+        let y_end = pos.1.saturating_add(size.1).min(self.height);
+        for y in pos.1..y_end {
+            for x in pos.0..x_end {
+                self.put(i64::from(x), i64::from(y), color);
+            }
+        }
+    }
+
+    fn rect(&mut self, pos: (u32, u32), size: (u32, u32), color: [u8; 3], filled: bool) {
+        if size.0 == 0 || size.1 == 0 {
+            return;
+        }
+        if filled {
+            self.fill(pos, size, color);
+            return;
+        }
+        let right = pos.0.saturating_add(size.0 - 1);
+        let bottom = pos.1.saturating_add(size.1 - 1);
+        self.fill(pos, (size.0, 1), color);
+        self.fill((pos.0, bottom), (size.0, 1), color);
+        self.fill(pos, (1, size.1), color);
+        self.fill((right, pos.1), (1, size.1), color);
+    }
+
+    /// Bresenham line, inclusive of both end points
+    fn line(&mut self, from: (u32, u32), to: (u32, u32), color: [u8; 3]) {
+        let (mut x, mut y) = (i64::from(from.0), i64::from(from.1));
+        let (x1, y1) = (i64::from(to.0), i64::from(to.1));
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            self.put(x, y, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    fn text(&mut self, pos: (u32, u32), content: &str, color: [u8; 3], scale: u32) {
+        let mut y = pos.1;
+        for line in content.split('\n') {
+            let mut x = pos.0;
+            for c in line.chars() {
+                for (row, bits) in (0u32..).zip(glyph(c)) {
+                    for col in 0..GLYPH_WIDTH {
+                        if bits & (1 << (GLYPH_WIDTH - 1 - col)) != 0 {
+                            let px = x.saturating_add(col.saturating_mul(scale));
+                            let py = y.saturating_add(row.saturating_mul(scale));
+                            self.fill((px, py), (scale, scale), color);
+                        }
+                    }
+                }
+                x = x.saturating_add((GLYPH_WIDTH + 1).saturating_mul(scale));
+            }
+            y = y.saturating_add((GLYPH_HEIGHT + 1).saturating_mul(scale));
+        }
+    }
+}
+
+/// 5×7 bitmap for `c`, one byte per row with the leftmost pixel in bit 4
+fn glyph(c: char) -> [u8; 7] {
+    match c.to_ascii_uppercase() {
+        ' ' => [0x00; 7],
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        'A' => [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        '+' => [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00],
+        '/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        ',' => [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08],
+        '_' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F],
+        '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+        ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+        '#' => [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A],
+        '%' => [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03],
+        '!' => [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04],
+        _ => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gray_frame(width: u32, height: u32) -> CameraFrame {
+        let len = (width * height * 3) as usize;
+        CameraFrame::new(vec![128; len], width, height, "overlay-test".to_string())
+    }
+
+    fn pixel(frame: &CameraFrame, x: u32, y: u32) -> [u8; 3] {
+        let at = ((y * frame.width + x) * 3) as usize;
+        [frame.data[at], frame.data[at + 1], frame.data[at + 2]]
+    }
+
+    #[test]
+    fn test_draw_text_sets_glyph_pixels() {
+        let text = DrawOp::Text {
+            pos: (2, 3),
+            content: "1".to_string(),
+            color: [255, 0, 0],
+            scale: 2,
+        };
+        let frame = draw_overlay(gray_frame(32, 32), &[text]);
+
+        // Top row of "1" is a single pixel in column 2 of the glyph
+        assert_eq!(pixel(&frame, 2 + 2 * 2, 3), [255, 0, 0]);
+        assert_eq!(pixel(&frame, 2 + 2 * 2 + 1, 3 + 1), [255, 0, 0]);
+        assert_eq!(pixel(&frame, 2, 3), [128; 3]);
+        // Bottom row spans columns 1-3
+        assert_eq!(pixel(&frame, 2 + 2, 3 + 6 * 2), [255, 0, 0]);
+        assert_eq!(pixel(&frame, 2 + 3 * 2, 3 + 6 * 2), [255, 0, 0]);
+    }
+
+    #[test]
+    fn test_draw_rect_and_line() {
+        let ops = [
+            DrawOp::Rect {
+                pos: (4, 4),
+                size: (6, 5),
+                color: [0, 255, 0],
+                filled: false,
+            },
+            DrawOp::Line {
+                from: (0, 31),
+                to: (31, 0),
+                color: [0, 0, 255],
+            },
+        ];
+        let frame = draw_overlay(gray_frame(32, 32), &ops);
+
+        assert_eq!(pixel(&frame, 4, 4), [0, 255, 0]);
+        assert_eq!(pixel(&frame, 9, 8), [0, 255, 0]);
+        assert_eq!(pixel(&frame, 6, 6), [128; 3], "outline only");
+        assert_eq!(pixel(&frame, 0, 31), [0, 0, 255]);
+        assert_eq!(pixel(&frame, 16, 15), [0, 0, 255]);
+        assert_eq!(pixel(&frame, 31, 0), [0, 0, 255]);
+    }
+
+    #[test]
+    fn test_overlay_clips_and_skips_unsupported_frames() {
+        let rect = DrawOp::Rect {
+            pos: (30, 30),
+            size: (100, 100),
+            color: [255; 3],
+            filled: true,
+        };
+        let frame = draw_overlay(gray_frame(32, 32), std::slice::from_ref(&rect));
+        assert_eq!(pixel(&frame, 31, 31), [255; 3]);
+        assert_eq!(pixel(&frame, 29, 29), [128; 3]);
+
+        let mjpeg = gray_frame(32, 32).with_format("MJPEG".to_string());
+        assert_eq!(draw_overlay(mjpeg.clone(), &[rect]).data, mjpeg.data);
+    }
+
+    #[test]
+    fn test_timestamp_overlay_marks_corner() {
+        let frame = timestamp_overlay(gray_frame(320, 240));
+        let margin = TIMESTAMP_OVERLAY_MARGIN;
+        let (w, _) = text_size("2026-01-01 00:00:00 UTC", 1);
+
+        assert_eq!(pixel(&frame, margin, margin), [0; 3], "background box");
+        let mut text_row = (margin..margin + w + 4).map(|x| pixel(&frame, x, margin + 2));
+        assert!(text_row.any(|px| px == [255; 3]), "text drawn");
+        assert_eq!(pixel(&frame, 319, 239), [128; 3]);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::constants::{MIN_RESOLUTION_HEIGHT, MIN_RESOLUTION_WIDTH};
+use crate::constants::{FORMAT_RGB, MIN_RESOLUTION_HEIGHT, MIN_RESOLUTION_WIDTH};
 use crate::quality::{BlurDetector, BlurMetrics, ExposureAnalyzer, ExposureMetrics};
 use crate::types::CameraFrame;
 use serde::{Deserialize, Serialize};
@@ -321,6 +321,19 @@ impl QualityValidator {
 
     /// Validate frame quality comprehensively
     pub fn validate_frame(&self, frame: &CameraFrame) -> QualityReport {
+        // The analyzers read 3-byte pixels; drop the alpha channel of RGBA frames.
+        let rgb_frame;
+        let frame = if frame.is_rgba() {
+            rgb_frame = CameraFrame {
+                data: frame.rgb_data().into_owned(),
+                format: FORMAT_RGB.to_string(),
+                ..frame.clone()
+            };
+            &rgb_frame
+        } else {
+            frame
+        };
+
         // Fast-preview profiles downscale large frames before analysis.
         let analyzed = match self.profile.max_analysis_dimension() {
             Some(max_dim) => Self::downscale_frame(frame, max_dim),
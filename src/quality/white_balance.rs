@@ -0,0 +1,149 @@
+//! Software white-balance correction
+//!
+//! Fallback for cameras that do not expose a color-temperature control: the
+//! scene illuminant's color is estimated from its Kelvin temperature and each
+//! RGB channel is scaled by the inverse, normalized so green is unchanged.
+//! Once enabled for a device via [`set_software_white_balance`], every RGB
+//! frame returned by [`PlatformCamera::capture_frame`](crate::platform::PlatformCamera::capture_frame)
+//! is corrected.
+
+use crate::constants::{FORMAT_RGB, WHITE_BALANCE_MAX_KELVIN, WHITE_BALANCE_MIN_KELVIN};
+use crate::types::CameraFrame;
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+static SOFTWARE_GAINS: LazyLock<RwLock<HashMap<String, [f32; 3]>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Clamp a color temperature to the supported range
+pub fn clamp_kelvin(kelvin: u32) -> u32 {
+    kelvin.clamp(WHITE_BALANCE_MIN_KELVIN, WHITE_BALANCE_MAX_KELVIN)
+}
+
+/// Approximate RGB color of a black-body illuminant (Tanner Helland's fit)
+fn illuminant_rgb(kelvin: u32) -> [f64; 3] {
+    let t = f64::from(clamp_kelvin(kelvin)) / 100.0;
+
+    let r = if t <= 66.0 {
+        255.0
+    } else {
+        329.698_727_446 * (t - 60.0).powf(-0.133_204_759_2)
+    };
+    let g = if t <= 66.0 {
+        99.470_802_586_1 * t.ln() - 161.119_568_166_1
+    } else {
+        288.122_169_528_3 * (t - 60.0).powf(-0.075_514_849_2)
+    };
+    let b = if t >= 66.0 {
+        255.0
+    } else {
+        138.517_731_223_1 * (t - 10.0).ln() - 305.044_792_730_7
+    };
+
+    [r, g, b].map(|c| c.clamp(1.0, 255.0))
+}
+
+/// Per-channel gains that neutralize an illuminant of the given temperature
+#[allow(clippy::cast_possible_truncation)] // gains are small positive ratios
+pub fn kelvin_to_gains(kelvin: u32) -> [f32; 3] {
+    let [r, g, b] = illuminant_rgb(kelvin);
+    [(g / r) as f32, 1.0, (g / b) as f32]
+}
+
+/// Enable software correction for `device_id` at `kelvin`, or disable it with `None`
+pub fn set_software_white_balance(device_id: &str, kelvin: Option<u32>) {
+    if let Ok(mut gains) = SOFTWARE_GAINS.write() {
+        match kelvin {
+            Some(k) => {
+                gains.insert(device_id.to_string(), kelvin_to_gains(k));
+            }
+            None => {
+                gains.remove(device_id);
+            }
+        }
+    }
+}
+
+/// Whether software correction is active for `device_id`
+pub fn is_software_white_balance_active(device_id: &str) -> bool {
+    SOFTWARE_GAINS
+        .read()
+        .is_ok_and(|gains| gains.contains_key(device_id))
+}
+
+/// Scale the channels of an RGB frame in place
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped to 0..=255
+pub fn apply_gains(frame: &mut CameraFrame, gains: [f32; 3]) {
+    for px in frame.data.chunks_exact_mut(3) {
+        for (value, gain) in px.iter_mut().zip(gains) {
+            *value = (f32::from(*value) * gain).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+}
+
+/// Apply the device's software correction, if any, to an RGB frame
+pub(crate) fn correct_frame(mut frame: CameraFrame) -> CameraFrame {
+    if frame.format != FORMAT_RGB {
+        return frame;
+    }
+    let gains = SOFTWARE_GAINS
+        .read()
+        .ok()
+        .and_then(|gains| gains.get(&frame.device_id).copied());
+    if let Some(gains) = gains {
+        apply_gains(&mut frame, gains);
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gains_warm_and_cool_light() {
+        let tungsten = kelvin_to_gains(3200);
+        assert!(
+            tungsten[0] < 1.0,
+            "tungsten light is red-heavy: {tungsten:?}"
+        );
+        assert!(tungsten[2] > 1.0, "tungsten light lacks blue: {tungsten:?}");
+
+        let shade = kelvin_to_gains(7500);
+        assert!(
+            shade[0] > 1.0 && shade[2] < 1.0,
+            "shade is blue-heavy: {shade:?}"
+        );
+
+        let daylight = kelvin_to_gains(6500);
+        assert!(daylight.iter().all(|g| (g - 1.0).abs() < 0.05));
+    }
+
+    #[test]
+    fn test_clamp_kelvin() {
+        assert_eq!(clamp_kelvin(100), WHITE_BALANCE_MIN_KELVIN);
+        assert_eq!(clamp_kelvin(50_000), WHITE_BALANCE_MAX_KELVIN);
+        assert_eq!(clamp_kelvin(5600), 5600);
+    }
+
+    #[test]
+    fn test_correct_frame_only_when_enabled() {
+        let frame = CameraFrame::new(vec![100; 4 * 3], 2, 2, "wb-soft".to_string());
+        assert_eq!(correct_frame(frame.clone()).data, frame.data);
+
+        set_software_white_balance("wb-soft", Some(3200));
+        assert!(is_software_white_balance_active("wb-soft"));
+        let corrected = correct_frame(frame.clone());
+        assert!(corrected.data[0] < 100 && corrected.data[1] == 100 && corrected.data[2] > 100);
+
+        let mjpeg = frame.clone().with_format("MJPEG".to_string());
+        assert_eq!(
+            correct_frame(mjpeg).data,
+            frame.data,
+            "compressed data untouched"
+        );
+
+        set_software_white_balance("wb-soft", None);
+        assert_eq!(correct_frame(frame.clone()).data, frame.data);
+    }
+}
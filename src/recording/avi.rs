@@ -0,0 +1,300 @@
+//! Minimal Motion-JPEG AVI writer
+//!
+//! Used by the MJPEG passthrough mode of [`super::Recorder`]: compressed
+//! frames coming straight from the camera are stored as `00dc` chunks
+//! without being decoded or re-encoded. The header is written up front with
+//! placeholder sizes and patched in [`AviMjpegWriter::finish`], which also
+//! appends the `idx1` index so players can seek.
+
+use std::io::{Seek, SeekFrom, Write};
+
+use crate::errors::CameraError;
+
+/// `AVIF_HASINDEX` main header flag.
+const AVIF_HASINDEX: u32 = 0x10;
+/// `AVIIF_KEYFRAME` index flag; every MJPEG frame is independently decodable.
+const AVIIF_KEYFRAME: u32 = 0x10;
+/// Byte offset of the RIFF size field.
+const RIFF_SIZE_OFFSET: u64 = 4;
+/// Byte offset of `avih.dwTotalFrames`.
+const AVIH_TOTAL_FRAMES_OFFSET: u64 = 48;
+/// Byte offset of `avih.dwSuggestedBufferSize`.
+const AVIH_BUFFER_SIZE_OFFSET: u64 = 60;
+/// Byte offset of `strh.dwLength`.
+const STRH_LENGTH_OFFSET: u64 = 140;
+/// Byte offset of `strh.dwSuggestedBufferSize`.
+const STRH_BUFFER_SIZE_OFFSET: u64 = 144;
+/// Byte offset of the `movi` list size field.
+const MOVI_SIZE_OFFSET: u64 = 216;
+/// Total header length up to and including the `movi` fourcc.
+const HEADER_LEN: u64 = 224;
+
+/// Totals reported when an AVI file is finalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct AviStats {
+    /// Number of video frames written.
+    pub frames: u64,
+    /// Total file size in bytes.
+    pub bytes_written: u64,
+}
+
+/// Writes already-compressed JPEG frames into an AVI container.
+pub(crate) struct AviMjpegWriter<W: Write + Seek> {
+    writer: W,
+    /// (offset relative to the `movi` fourcc, chunk size) per frame
+    index: Vec<(u32, u32)>,
+    /// Bytes written after the `movi` fourcc
+    movi_len: u64,
+    max_frame_len: u32,
+}
+
+impl<W: Write + Seek> AviMjpegWriter<W> {
+    /// Write the AVI header for a single MJPEG video stream.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::MuxingError`] if the header cannot be written.
+    pub fn new(mut writer: W, width: u32, height: u32, fps: f64) -> Result<Self, CameraError> {
+        let header = build_header(width, height, fps);
+        debug_assert_eq!(header.len() as u64, HEADER_LEN);
+        writer
+            .write_all(&header)
+            .map_err(|e| CameraError::MuxingError(format!("Failed to write AVI header: {e}")))?;
+
+        Ok(Self {
+            writer,
+            index: Vec::new(),
+            movi_len: 0,
+            max_frame_len: 0,
+        })
+    }
+
+    /// Append one JPEG frame as a `00dc` chunk.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::MuxingError`] if the frame is too large for
+    /// an AVI chunk or cannot be written.
+    pub fn write_frame(&mut self, jpeg: &[u8]) -> Result<(), CameraError> {
+        let len = u32::try_from(jpeg.len())
+            .map_err(|_| CameraError::MuxingError("MJPEG frame exceeds 4 GiB".to_string()))?;
+        // Offsets in idx1 are relative to the `movi` fourcc, which is 4 bytes
+        // before the first chunk.
+        let offset = u32::try_from(self.movi_len + 4)
+            .map_err(|_| CameraError::MuxingError("AVI file exceeds 4 GiB".to_string()))?;
+
+        let padded = jpeg.len() % 2 == 1;
+        self.write(b"00dc")?;
+        self.write(&len.to_le_bytes())?;
+        self.write(jpeg)?;
+        if padded {
+            self.write(&[0])?;
+        }
+
+        self.index.push((offset, len));
+        self.movi_len += 8 + u64::from(len) + u64::from(padded);
+        self.max_frame_len = self.max_frame_len.max(len);
+        Ok(())
+    }
+
+    /// Number of frames written so far.
+    pub fn frame_count(&self) -> u64 {
+        self.index.len() as u64
+    }
+
+    /// Append the index, patch header sizes and flush.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::MuxingError`] if the file cannot be finalized.
+    pub fn finish(mut self) -> Result<AviStats, CameraError> {
+        let mut idx1 = Vec::with_capacity(8 + self.index.len() * 16);
+        idx1.extend_from_slice(b"idx1");
+        push_u32(&mut idx1, to_u32(self.index.len() as u64 * 16)?);
+        for &(offset, len) in &self.index {
+            idx1.extend_from_slice(b"00dc");
+            push_u32(&mut idx1, AVIIF_KEYFRAME);
+            push_u32(&mut idx1, offset);
+            push_u32(&mut idx1, len);
+        }
+        self.write(&idx1)?;
+
+        let total = HEADER_LEN + self.movi_len + idx1.len() as u64;
+        let frames = to_u32(self.frame_count())?;
+        self.patch(RIFF_SIZE_OFFSET, to_u32(total - 8)?)?;
+        self.patch(AVIH_TOTAL_FRAMES_OFFSET, frames)?;
+        self.patch(AVIH_BUFFER_SIZE_OFFSET, self.max_frame_len)?;
+        self.patch(STRH_LENGTH_OFFSET, frames)?;
+        self.patch(STRH_BUFFER_SIZE_OFFSET, self.max_frame_len)?;
+        // The movi list size covers its own fourcc plus all chunks.
+        self.patch(MOVI_SIZE_OFFSET, to_u32(self.movi_len + 4)?)?;
+
+        self.writer
+            .seek(SeekFrom::End(0))
+            .and_then(|_| self.writer.flush())
+            .map_err(|e| CameraError::MuxingError(format!("Failed to finalize AVI: {e}")))?;
+
+        Ok(AviStats {
+            frames: self.frame_count(),
+            bytes_written: total,
+        })
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), CameraError> {
+        self.writer
+            .write_all(bytes)
+            .map_err(|e| CameraError::MuxingError(format!("Failed to write AVI data: {e}")))
+    }
+
+    fn patch(&mut self, offset: u64, value: u32) -> Result<(), CameraError> {
+        self.writer
+            .seek(SeekFrom::Start(offset))
+            .and_then(|_| self.writer.write_all(&value.to_le_bytes()))
+            .map_err(|e| CameraError::MuxingError(format!("Failed to patch AVI header: {e}")))
+    }
+}
+
+fn to_u32(value: u64) -> Result<u32, CameraError> {
+    u32::try_from(value).map_err(|_| CameraError::MuxingError("AVI file exceeds 4 GiB".to_string()))
+}
+
+fn push_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn push_u16(buf: &mut Vec<u8>, value: u16) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Build `RIFF/AVI` + `hdrl` (avih, strl(strh, strf)) + the `movi` list
+/// header. Frame counts, buffer sizes and list sizes are zero placeholders.
+fn build_header(width: u32, height: u32, fps: f64) -> Vec<u8> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    // Frame rates are small positive values; rounding to whole units is intended
+    let (usec_per_frame, rate) = if fps > 0.0 {
+        (
+            (1_000_000.0 / fps).round() as u32,
+            (fps * 1000.0).round() as u32,
+        )
+    } else {
+        (0, 0)
+    };
+    let rect_w = u16::try_from(width).unwrap_or(u16::MAX);
+    let rect_h = u16::try_from(height).unwrap_or(u16::MAX);
+
+    let mut h = Vec::new();
+    h.extend_from_slice(b"RIFF");
+    push_u32(&mut h, 0);
+    h.extend_from_slice(b"AVI ");
+
+    h.extend_from_slice(b"LIST");
+    push_u32(&mut h, 192);
+    h.extend_from_slice(b"hdrl");
+
+    h.extend_from_slice(b"avih");
+    push_u32(&mut h, 56);
+    push_u32(&mut h, usec_per_frame);
+    push_u32(&mut h, 0); // dwMaxBytesPerSec
+    push_u32(&mut h, 0); // dwPaddingGranularity
+    push_u32(&mut h, AVIF_HASINDEX);
+    push_u32(&mut h, 0); // dwTotalFrames (patched)
+    push_u32(&mut h, 0); // dwInitialFrames
+    push_u32(&mut h, 1); // dwStreams
+    push_u32(&mut h, 0); // dwSuggestedBufferSize (patched)
+    push_u32(&mut h, width);
+    push_u32(&mut h, height);
+    h.extend_from_slice(&[0; 16]); // dwReserved
+
+    h.extend_from_slice(b"LIST");
+    push_u32(&mut h, 116);
+    h.extend_from_slice(b"strl");
+
+    h.extend_from_slice(b"strh");
+    push_u32(&mut h, 56);
+    h.extend_from_slice(b"vids");
+    h.extend_from_slice(b"MJPG");
+    push_u32(&mut h, 0); // dwFlags
+    push_u16(&mut h, 0); // wPriority
+    push_u16(&mut h, 0); // wLanguage
+    push_u32(&mut h, 0); // dwInitialFrames
+    push_u32(&mut h, 1000); // dwScale
+    push_u32(&mut h, rate); // dwRate (fps = rate / scale)
+    push_u32(&mut h, 0); // dwStart
+    push_u32(&mut h, 0); // dwLength (patched)
+    push_u32(&mut h, 0); // dwSuggestedBufferSize (patched)
+    push_u32(&mut h, u32::MAX); // dwQuality (driver default)
+    push_u32(&mut h, 0); // dwSampleSize
+    for v in [0, 0, rect_w, rect_h] {
+        push_u16(&mut h, v); // rcFrame
+    }
+
+    h.extend_from_slice(b"strf");
+    push_u32(&mut h, 40);
+    push_u32(&mut h, 40); // biSize
+    push_u32(&mut h, width);
+    push_u32(&mut h, height);
+    push_u16(&mut h, 1); // biPlanes
+    push_u16(&mut h, 24); // biBitCount
+    h.extend_from_slice(b"MJPG");
+    push_u32(&mut h, width.saturating_mul(height).saturating_mul(3));
+    h.extend_from_slice(&[0; 16]); // resolution and palette fields
+
+    h.extend_from_slice(b"LIST");
+    push_u32(&mut h, 0); // movi size (patched)
+    h.extend_from_slice(b"movi");
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn read_u32(buf: &[u8], offset: u64) -> u32 {
+        let at = usize::try_from(offset).expect("offset fits usize");
+        u32::from_le_bytes(buf[at..at + 4].try_into().expect("4 bytes"))
+    }
+
+    #[test]
+    fn test_header_layout() {
+        let header = build_header(640, 480, 30.0);
+        assert_eq!(header.len() as u64, HEADER_LEN);
+        assert_eq!(&header[0..4], b"RIFF");
+        assert_eq!(&header[8..12], b"AVI ");
+        assert_eq!(&header[24..28], b"avih");
+        assert_eq!(&header[100..104], b"strh");
+        assert_eq!(&header[108..112], b"vids");
+        assert_eq!(&header[220..224], b"movi");
+        assert_eq!(read_u32(&header, 32), 33_333);
+    }
+
+    #[test]
+    fn test_frames_are_stored_verbatim_and_indexed() {
+        let frame_a = [0xFF, 0xD8, 0xFF, 0x01, 0xD9];
+        let frame_b = [0xFF, 0xD8, 0xFF, 0x02];
+
+        let mut out = Vec::new();
+        {
+            let mut w = AviMjpegWriter::new(Cursor::new(&mut out), 2, 2, 15.0).expect("header");
+            w.write_frame(&frame_a).expect("frame a");
+            w.write_frame(&frame_b).expect("frame b");
+            assert_eq!(w.frame_count(), 2);
+            let stats = w.finish().expect("finish");
+            assert_eq!(stats.frames, 2);
+            assert_eq!(stats.bytes_written, out.len() as u64);
+        }
+
+        assert_eq!(read_u32(&out, RIFF_SIZE_OFFSET) as usize, out.len() - 8);
+        assert_eq!(read_u32(&out, AVIH_TOTAL_FRAMES_OFFSET), 2);
+        assert_eq!(read_u32(&out, STRH_LENGTH_OFFSET), 2);
+
+        // First chunk follows the header; odd-sized frame is padded.
+        let first = usize::try_from(HEADER_LEN).expect("fits");
+        assert_eq!(&out[first..first + 4], b"00dc");
+        assert_eq!(&out[first + 8..first + 13], &frame_a);
+        let second = first + 8 + 6;
+        assert_eq!(&out[second + 8..second + 12], &frame_b);
+
+        let idx = second + 12;
+        assert_eq!(&out[idx..idx + 4], b"idx1");
+        assert_eq!(read_u32(&out, idx as u64 + 4), 32);
+        assert_eq!(read_u32(&out, idx as u64 + 16), 4);
+    }
+}
@@ -0,0 +1,190 @@
+//! Constant frame rate conversion
+//!
+//! Cameras deliver frames when the sensor finishes them, so intervals vary
+//! with exposure time and load. Many editors assume a constant frame rate
+//! and play such files at the wrong speed. With
+//! [`RecordingConfig::constant_framerate`](super::RecordingConfig::constant_framerate)
+//! the recorder places every frame on a fixed grid of `1 / fps` slots by its
+//! timestamp: a frame landing in a slot that is already filled is dropped,
+//! and slots skipped over are filled by repeating the previous frame.
+//!
+//! At most [`CFR_MAX_GAP_SECS`] of slots are repeated. A longer gap (a
+//! stalled camera, a paused recording) is cut to that length and the grid
+//! restarts at the frame after it, so the file does not fill with copies of
+//! one frame and later frames keep their spacing.
+
+use chrono::{DateTime, Utc};
+
+use crate::constants::CFR_MAX_GAP_SECS;
+use crate::types::CameraFrame;
+
+/// Timestamps of a recording's first frame, the origin of later offsets
+pub(super) type FrameOrigin = (DateTime<Utc>, Option<f64>);
+
+/// Seconds from `origin` to `frame`: the hardware timestamp difference when
+/// both carry one, else the [`CameraFrame::timestamp`] difference (see
+/// [`FrameMetadata::hardware_timestamp`])
+///
+/// [`FrameMetadata::hardware_timestamp`]: crate::types::FrameMetadata::hardware_timestamp
+pub(super) fn offset_secs(origin: FrameOrigin, frame: &CameraFrame) -> f64 {
+    let (first_time, first_hardware) = origin;
+    match (first_hardware, frame.metadata.hardware_timestamp) {
+        (Some(first), Some(current)) => current - first,
+        _ => {
+            let micros = (frame.timestamp - first_time)
+                .num_microseconds()
+                .unwrap_or(i64::MAX);
+            #[allow(clippy::cast_precision_loss)] // microsecond offsets
+            let secs = micros as f64 / 1_000_000.0;
+            secs
+        }
+    }
+}
+
+/// Where a frame goes on the output grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct CfrSlots {
+    /// Slots to fill with the previous frame first
+    pub repeat_previous: std::ops::Range<u64>,
+    /// Slot of this frame
+    pub current: u64,
+}
+
+/// Maps frame timestamps to slots of a constant frame rate grid
+#[derive(Debug)]
+pub(super) struct CfrConverter {
+    fps: f64,
+    origin: Option<FrameOrigin>,
+    /// Slot of the frame at `origin`; moves when a gap is cut
+    origin_slot: u64,
+    /// Most slots repeated for one gap
+    max_repeat: u64,
+    next_slot: u64,
+    duplicated: u64,
+    dropped: u64,
+}
+
+impl CfrConverter {
+    /// Converter for a grid of `fps` frames per second
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // frame rates are small and positive
+    pub(super) fn new(fps: f64) -> Self {
+        Self {
+            fps,
+            origin: None,
+            origin_slot: 0,
+            max_repeat: (CFR_MAX_GAP_SECS * fps).ceil().max(1.0) as u64,
+            next_slot: 0,
+            duplicated: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Slots for `frame`, or `None` if its slot is already filled and it
+    /// should be dropped
+    ///
+    /// The first frame is slot 0; frames must arrive in timestamp order.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(super) fn place(&mut self, frame: &CameraFrame) -> Option<CfrSlots> {
+        let origin = *self
+            .origin
+            .get_or_insert((frame.timestamp, frame.metadata.hardware_timestamp));
+        // Negative and non-finite offsets saturate to the origin's slot
+        let offset = (offset_secs(origin, frame) * self.fps).round().max(0.0) as u64;
+        let mut slot = self.origin_slot.saturating_add(offset);
+        if slot < self.next_slot {
+            self.dropped += 1;
+            return None;
+        }
+        if slot - self.next_slot > self.max_repeat {
+            // Cut the gap and measure later frames from this one
+            slot = self.next_slot + self.max_repeat;
+            self.origin = Some((frame.timestamp, frame.metadata.hardware_timestamp));
+            self.origin_slot = slot;
+        }
+
+        let repeat_previous = self.next_slot..slot;
+        self.duplicated += slot - self.next_slot;
+        self.next_slot = slot + 1;
+        Some(CfrSlots {
+            repeat_previous,
+            current: slot,
+        })
+    }
+
+    /// Presentation time of `slot` in seconds
+    #[allow(clippy::cast_precision_loss)] // slot counts stay far below 2^52
+    pub(super) fn pts(&self, slot: u64) -> f64 {
+        slot as f64 / self.fps
+    }
+
+    /// Repeated frames written so far
+    pub(super) fn duplicated(&self) -> u64 {
+        self.duplicated
+    }
+
+    /// Frames dropped so far for landing in a filled slot
+    pub(super) fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame_at(hardware_secs: f64) -> CameraFrame {
+        let mut frame = CameraFrame::new(vec![0; 3], 1, 1, "cfr".to_string());
+        frame.metadata.hardware_timestamp = Some(hardware_secs);
+        frame
+    }
+
+    #[test]
+    fn test_drops_and_duplicates_onto_grid() {
+        let mut cfr = CfrConverter::new(10.0);
+        let slots: Vec<Option<CfrSlots>> = [5.0, 5.1, 5.12, 5.38, 5.4]
+            .iter()
+            .map(|&t| cfr.place(&frame_at(t)))
+            .collect();
+
+        assert_eq!(slots[0].as_ref().unwrap().current, 0);
+        assert_eq!(slots[1].as_ref().unwrap().current, 1);
+        // 5.12 rounds into slot 1, which is taken
+        assert!(slots[2].is_none());
+        // 5.38 skips slot 2 and 3, repeating the frame of slot 1
+        let late = slots[3].as_ref().unwrap();
+        assert_eq!((late.repeat_previous.clone(), late.current), (2..4, 4));
+        // 5.4 lands in slot 4 again
+        assert!(slots[4].is_none());
+
+        assert_eq!(cfr.duplicated(), 2);
+        assert_eq!(cfr.dropped(), 2);
+        assert!((cfr.pts(4) - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_long_gap_is_cut_and_grid_restarts() {
+        let mut cfr = CfrConverter::new(10.0);
+        assert_eq!(cfr.place(&frame_at(0.0)).unwrap().current, 0);
+
+        // A 60 s stall repeats one second of slots, not six hundred
+        let resumed = cfr.place(&frame_at(60.0)).unwrap();
+        assert_eq!((resumed.repeat_previous, resumed.current), (1..11, 11));
+        assert_eq!(cfr.duplicated(), 10);
+
+        // Later frames are spaced from the resumed one
+        assert_eq!(cfr.place(&frame_at(60.1)).unwrap().current, 12);
+        assert!(cfr.place(&frame_at(60.12)).is_none());
+    }
+
+    #[test]
+    fn test_wall_clock_timestamps_without_hardware_time() {
+        let mut cfr = CfrConverter::new(30.0);
+        let first = CameraFrame::new(vec![0; 3], 1, 1, "cfr".to_string());
+        let mut second = first.clone();
+        second.timestamp = first.timestamp + chrono::Duration::milliseconds(100);
+
+        assert_eq!(cfr.place(&first).unwrap().current, 0);
+        assert_eq!(cfr.place(&second).unwrap().current, 3);
+        assert_eq!(cfr.duplicated(), 2);
+    }
+}
@@ -1,5 +1,8 @@
 //! Recording configuration types
 
+use super::encoder::{H264Level, H264Profile};
+#[cfg(feature = "audio")]
+use crate::audio::DeviceKind;
 use crate::constants::{AUDIO_BITRATE, AUDIO_CHANNELS, AUDIO_SAMPLE_RATE, VIDEO_BITRATE_HD};
 use serde::{Deserialize, Serialize};
 
@@ -10,12 +13,19 @@ use serde::{Deserialize, Serialize};
 pub struct AudioConfig {
     /// Audio device ID (None = default device)
     pub device_id: Option<String>,
+    /// Record a microphone or system audio (loopback)
+    #[serde(default)]
+    pub device_kind: DeviceKind,
     /// Sample rate (must be 48000 for Opus)
     pub sample_rate: u32,
     /// Number of channels (1 or 2)
     pub channels: u16,
     /// Opus bitrate in bits per second
     pub bitrate: u32,
+    /// Continue on the system default input if the device is unplugged
+    /// mid-recording, instead of ending the audio track
+    #[serde(default)]
+    pub auto_fallback: bool,
 }
 
 #[cfg(feature = "audio")]
@@ -23,9 +33,11 @@ impl Default for AudioConfig {
     fn default() -> Self {
         Self {
             device_id: None,
+            device_kind: DeviceKind::Input,
             sample_rate: AUDIO_SAMPLE_RATE, // Opus requirement
             channels: AUDIO_CHANNELS,
             bitrate: AUDIO_BITRATE,
+            auto_fallback: false,
         }
     }
 }
@@ -41,6 +53,17 @@ impl AudioConfig {
         }
     }
 
+    /// Create audio config recording system audio from a loopback device
+    /// (`None` = the default output)
+    #[must_use]
+    pub fn loopback(device_id: Option<String>) -> Self {
+        Self {
+            device_id,
+            device_kind: DeviceKind::Loopback,
+            ..Default::default()
+        }
+    }
+
     /// Set mono audio
     #[must_use]
     pub fn mono(mut self) -> Self {
@@ -61,6 +84,13 @@ impl AudioConfig {
         self.bitrate = bitrate;
         self
     }
+
+    /// Switch to the default input if the device is lost mid-recording
+    #[must_use]
+    pub fn with_auto_fallback(mut self, enabled: bool) -> Self {
+        self.auto_fallback = enabled;
+        self
+    }
 }
 
 /// Quality presets for video recording
@@ -106,6 +136,53 @@ impl RecordingQuality {
     }
 }
 
+/// Video codec used by the recorder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RecordingCodec {
+    /// Encode RGB frames to H.264 and mux them into MP4
+    #[default]
+    H264,
+    /// Store camera-compressed MJPEG frames in an AVI container as delivered,
+    /// without decoding or re-encoding. Intended for low-power event recording.
+    MjpegPassthrough,
+}
+
+/// Which H.264 encoder the recorder should use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EncoderPreference {
+    /// Hardware when the `hwenc` feature finds one, otherwise openh264
+    ///
+    /// Fails with [`CameraError::EncoderUnavailable`](crate::errors::CameraError::EncoderUnavailable)
+    /// only if neither works.
+    #[default]
+    Auto,
+    /// Hardware only; creating the recorder fails if none is available
+    Hardware,
+    /// openh264, or hardware (`hwenc` feature) if openh264 cannot load
+    Software,
+}
+
+/// H.264 encoder that produced a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EncoderBackend {
+    /// openh264 software encoder
+    #[default]
+    OpenH264,
+    /// Apple VideoToolbox (macOS)
+    VideoToolbox,
+    /// Media Foundation hardware MFT (Windows)
+    MediaFoundation,
+    /// VA-API (Linux, Intel/AMD GPUs)
+    VaApi,
+}
+
+impl EncoderBackend {
+    /// Whether the backend encodes on dedicated hardware
+    pub fn is_hardware(self) -> bool {
+        self != Self::OpenH264
+    }
+}
+
 /// Configuration for video recording
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecordingConfig {
@@ -123,6 +200,37 @@ pub struct RecordingConfig {
     pub fast_start: bool,
     /// Optional title metadata
     pub title: Option<String>,
+    /// Video codec (H.264 encode or MJPEG passthrough)
+    #[serde(default)]
+    pub codec: RecordingCodec,
+    /// Frames held back so `write_frame` writes them in hardware timestamp
+    /// order (0 = off)
+    #[serde(default)]
+    pub reorder_window: usize,
+    /// H.264 frames per GOP: an IDR frame is inserted every this many frames
+    /// (0 = default, only the first frame is an IDR). Ignored for MJPEG
+    /// passthrough, where every frame is a keyframe.
+    #[serde(default)]
+    pub keyframe_interval_frames: u32,
+    /// H.264 profile (Baseline by default)
+    #[serde(default)]
+    pub profile: H264Profile,
+    /// H.264 level (chosen by the encoder by default)
+    #[serde(default)]
+    pub level: H264Level,
+    /// Write a fragmented MP4, flushing a self-contained fragment at the
+    /// first keyframe after every this many seconds, so a crashed recording
+    /// stays playable up to its last fragment (`None` = regular MP4)
+    #[serde(default)]
+    pub fragment_interval_secs: Option<f64>,
+    /// Hardware or software H.264 encoding (ignored for MJPEG passthrough)
+    #[serde(default)]
+    pub encoder_preference: EncoderPreference,
+    /// Place frames on an evenly spaced `fps` grid by their timestamps,
+    /// dropping frames that land in a filled slot and repeating the previous
+    /// frame for skipped ones (replaces arrival-time rate limiting)
+    #[serde(default)]
+    pub constant_framerate: bool,
     /// Audio configuration (None = video only)
     /// Per #`RecorderIntegrateAudio`: ! `supports_audio_optional`
     #[cfg(feature = "audio")]
@@ -140,6 +248,14 @@ impl RecordingConfig {
             quality: RecordingQuality::Custom,
             fast_start: true,
             title: None,
+            codec: RecordingCodec::H264,
+            reorder_window: 0,
+            keyframe_interval_frames: 0,
+            profile: H264Profile::Baseline,
+            level: H264Level::Auto,
+            fragment_interval_secs: None,
+            encoder_preference: EncoderPreference::Auto,
+            constant_framerate: false,
             #[cfg(feature = "audio")]
             audio: None,
         }
@@ -156,6 +272,14 @@ impl RecordingConfig {
             quality,
             fast_start: true,
             title: None,
+            codec: RecordingCodec::H264,
+            reorder_window: 0,
+            keyframe_interval_frames: 0,
+            profile: H264Profile::Baseline,
+            level: H264Level::Auto,
+            fragment_interval_secs: None,
+            encoder_preference: EncoderPreference::Auto,
+            constant_framerate: false,
             #[cfg(feature = "audio")]
             audio: None,
         }
@@ -172,6 +296,14 @@ impl RecordingConfig {
             quality,
             fast_start: true,
             title: None,
+            codec: RecordingCodec::H264,
+            reorder_window: 0,
+            keyframe_interval_frames: 0,
+            profile: H264Profile::Baseline,
+            level: H264Level::Auto,
+            fragment_interval_secs: None,
+            encoder_preference: EncoderPreference::Auto,
+            constant_framerate: false,
             #[cfg(feature = "audio")]
             audio: None,
         }
@@ -198,6 +330,80 @@ impl RecordingConfig {
         self
     }
 
+    /// Set the video codec
+    #[must_use]
+    pub fn with_codec(mut self, codec: RecordingCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Hold back up to `window` frames so they are written in hardware
+    /// timestamp order, adding `window` frame intervals of latency
+    #[must_use]
+    pub fn with_reorder_window(mut self, window: usize) -> Self {
+        self.reorder_window = window;
+        self
+    }
+
+    /// Insert an H.264 IDR frame every `frames` frames (0 = first frame only)
+    #[must_use]
+    pub fn with_keyframe_interval(mut self, frames: u32) -> Self {
+        self.keyframe_interval_frames = frames;
+        self
+    }
+
+    /// Insert an H.264 IDR frame every `seconds` at the configured fps,
+    /// e.g. 2.0 for typical HLS/DASH segment boundaries
+    #[must_use]
+    pub fn with_keyframe_interval_secs(self, seconds: f64) -> Self {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // clamped to >= 1
+        let frames = (seconds * self.fps).round().max(1.0) as u32;
+        self.with_keyframe_interval(frames)
+    }
+
+    /// Set the H.264 profile
+    #[must_use]
+    pub fn with_profile(mut self, profile: H264Profile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Set the H.264 level
+    #[must_use]
+    pub fn with_level(mut self, level: H264Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Record a fragmented MP4 flushed every `seconds` (see
+    /// [`fragment_interval_secs`](Self::fragment_interval_secs))
+    #[must_use]
+    pub fn with_fragment_interval(mut self, seconds: f64) -> Self {
+        self.fragment_interval_secs = Some(seconds);
+        self
+    }
+
+    /// Choose between hardware and software H.264 encoding
+    #[must_use]
+    pub fn with_encoder_preference(mut self, preference: EncoderPreference) -> Self {
+        self.encoder_preference = preference;
+        self
+    }
+
+    /// Write a constant frame rate recording from frames arriving at a
+    /// variable rate
+    #[must_use]
+    pub fn with_constant_framerate(mut self, enabled: bool) -> Self {
+        self.constant_framerate = enabled;
+        self
+    }
+
+    /// Record camera MJPEG frames into an AVI file without re-encoding
+    #[must_use]
+    pub fn mjpeg_passthrough(self) -> Self {
+        self.with_codec(RecordingCodec::MjpegPassthrough)
+    }
+
     /// Enable audio recording with the given configuration
     /// Per #`RecorderIntegrateAudio`: ! `supports_audio_optional`
     #[cfg(feature = "audio")]
@@ -239,6 +445,53 @@ pub struct RecordingStats {
     pub dropped_frames: u64,
     /// Output file path
     pub output_path: String,
+    /// Whether frames were re-encoded (`false` for MJPEG passthrough)
+    pub transcoded: bool,
+    /// Frames written out of arrival order to keep timestamps monotonic
+    #[serde(default)]
+    pub reordered_frames: u64,
+    /// Frames discarded because they arrived after a later-stamped frame
+    /// was already written
+    #[serde(default)]
+    pub late_frames: u64,
+    /// Encoder that produced the video track (`OpenH264` for MJPEG
+    /// passthrough, which encodes nothing)
+    #[serde(default)]
+    pub encoder: EncoderBackend,
+    /// Times audio capture switched to another input after its device was
+    /// lost
+    #[serde(default)]
+    pub audio_device_switches: u32,
+    /// Total seconds of audio missing around those switches
+    #[serde(default)]
+    pub audio_gap_secs: f64,
+    /// Seconds from the first video frame offered to the first captured
+    /// audio (negative when audio came first); both tracks are trimmed to
+    /// start together, and this is 0 without audio
+    #[serde(default)]
+    pub av_start_offset_secs: f64,
+    /// Repeated frames written to fill a constant frame rate grid
+    #[serde(default)]
+    pub cfr_duplicated_frames: u64,
+    /// Frames left out of a constant frame rate recording because another
+    /// frame already filled their slot (not in `dropped_frames`)
+    #[serde(default)]
+    pub cfr_dropped_frames: u64,
+}
+
+/// Result of [`Recorder::recover`](super::Recorder::recover)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecoveryReport {
+    /// Complete fragments kept
+    pub fragments: u32,
+    /// Video frames in the kept fragments
+    pub video_frames: u64,
+    /// Playable duration in seconds
+    pub duration_secs: f64,
+    /// File size after recovery
+    pub bytes_kept: u64,
+    /// Bytes of the incomplete trailing fragment that were removed
+    pub bytes_discarded: u64,
 }
 
 impl RecordingStats {
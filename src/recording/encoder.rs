@@ -1,8 +1,204 @@
 //! H.264 encoder wrapper using openh264
+//!
+//! ## Profiles, levels and B-frames
+//!
+//! openh264 is a real-time encoder with a narrow feature set, so
+//! [`H264EncoderConfig`] only offers what it can produce:
+//!
+//! - **Baseline** (default): Constrained Baseline, CAVLC, decodable everywhere.
+//! - **Main** / **High**: CABAC entropy coding for roughly 10% smaller
+//!   output at the same quality; still I and P slices only.
+//! - **B-frames** are not implemented by openh264. A non-zero
+//!   `b_frames` is logged as a warning and encoded as 0.
+//! - **Levels** cap the frame size; a level too small for the configured
+//!   resolution is logged and replaced by [`H264Level::Auto`], which lets
+//!   openh264 derive the level from the stream.
+//!
+//! ## Availability
+//!
+//! openh264 can fail to initialize on some machines (e.g. an unsupported
+//! architecture). Creating an encoder then fails with
+//! [`CameraError::EncoderUnavailable`] naming the cause, and
+//! [`H264Encoder::is_available`] tells callers in advance. A configuration
+//! openh264 rejects on a machine where it works fails with
+//! [`CameraError::EncodingError`] instead.
+
+use std::sync::OnceLock;
 
 use crate::errors::CameraError;
-use openh264::encoder::{Encoder, FrameType};
+use openh264::encoder::{
+    BitRate, Encoder, EncoderConfig, FrameRate, FrameType, Level, Profile, RateControlMode,
+};
 use openh264::formats::YUVBuffer;
+use openh264::OpenH264API;
+use serde::{Deserialize, Serialize};
+
+/// H.264 profile written to the SPS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum H264Profile {
+    /// Constrained Baseline: CAVLC, widest decoder support
+    #[default]
+    Baseline,
+    /// Main: CABAC entropy coding
+    Main,
+    /// High: CABAC plus 8x8 transforms
+    High,
+}
+
+impl H264Profile {
+    fn to_openh264(self) -> Profile {
+        match self {
+            Self::Baseline => Profile::Baseline,
+            Self::Main => Profile::Main,
+            Self::High => Profile::High,
+        }
+    }
+}
+
+/// H.264 level, which bounds frame size and macroblock throughput
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum H264Level {
+    /// Let openh264 choose the level from the resolution
+    #[default]
+    Auto,
+    /// Level 3.0 (up to 720x576)
+    Level3_0,
+    /// Level 3.1 (up to 1280x720)
+    Level3_1,
+    /// Level 3.2 (up to 1280x1024)
+    Level3_2,
+    /// Level 4.0 (up to 2048x1024, e.g. 1080p30)
+    Level4_0,
+    /// Level 4.1 (as 4.0, higher bitrate)
+    Level4_1,
+    /// Level 4.2 (1080p60)
+    Level4_2,
+    /// Level 5.0 (up to 2560x1920)
+    Level5_0,
+    /// Level 5.1 (up to 4096x2304, e.g. 4K30)
+    Level5_1,
+    /// Level 5.2 (4K60)
+    Level5_2,
+}
+
+impl H264Level {
+    /// Largest frame the level allows, in 16x16 macroblocks (`None` for Auto)
+    pub fn max_frame_macroblocks(self) -> Option<u32> {
+        match self {
+            Self::Auto => None,
+            Self::Level3_0 => Some(1_620),
+            Self::Level3_1 => Some(3_600),
+            Self::Level3_2 => Some(5_120),
+            Self::Level4_0 | Self::Level4_1 | Self::Level4_2 => Some(8_192),
+            Self::Level5_0 => Some(22_080),
+            Self::Level5_1 | Self::Level5_2 => Some(36_864),
+        }
+    }
+
+    fn to_openh264(self) -> Option<Level> {
+        match self {
+            Self::Auto => None,
+            Self::Level3_0 => Some(Level::Level_3_0),
+            Self::Level3_1 => Some(Level::Level_3_1),
+            Self::Level3_2 => Some(Level::Level_3_2),
+            Self::Level4_0 => Some(Level::Level_4_0),
+            Self::Level4_1 => Some(Level::Level_4_1),
+            Self::Level4_2 => Some(Level::Level_4_2),
+            Self::Level5_0 => Some(Level::Level_5_0),
+            Self::Level5_1 => Some(Level::Level_5_1),
+            Self::Level5_2 => Some(Level::Level_5_2),
+        }
+    }
+}
+
+/// Full set of options for [`H264Encoder::with_config`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct H264EncoderConfig {
+    /// Frame width in pixels
+    pub width: u32,
+    /// Frame height in pixels
+    pub height: u32,
+    /// Target frame rate, used by rate control
+    pub fps: f64,
+    /// Target bitrate in bits per second
+    pub bitrate: u32,
+    /// H.264 profile
+    pub profile: H264Profile,
+    /// H.264 level
+    pub level: H264Level,
+    /// Consecutive B-frames (unsupported by openh264, always encoded as 0)
+    pub b_frames: u8,
+    /// Frames between automatic IDR frames (0 = first frame only)
+    pub keyframe_interval: u32,
+}
+
+impl H264EncoderConfig {
+    /// Baseline profile, automatic level, no B-frames
+    pub fn new(width: u32, height: u32, fps: f64, bitrate: u32) -> Self {
+        Self {
+            width,
+            height,
+            fps,
+            bitrate,
+            profile: H264Profile::Baseline,
+            level: H264Level::Auto,
+            b_frames: 0,
+            keyframe_interval: 0,
+        }
+    }
+
+    /// Set the profile
+    #[must_use]
+    pub fn with_profile(mut self, profile: H264Profile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Set the level
+    #[must_use]
+    pub fn with_level(mut self, level: H264Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Request consecutive B-frames (see the module docs)
+    #[must_use]
+    pub fn with_b_frames(mut self, b_frames: u8) -> Self {
+        self.b_frames = b_frames;
+        self
+    }
+
+    /// Insert an IDR frame every `frames` frames
+    #[must_use]
+    pub fn with_keyframe_interval(mut self, frames: u32) -> Self {
+        self.keyframe_interval = frames;
+        self
+    }
+
+    /// Replace settings openh264 cannot honour, logging each fallback
+    fn supported(mut self) -> Self {
+        if self.b_frames > 0 {
+            log::warn!(
+                "openh264 does not support B-frames; encoding {} requested B-frames as P-frames",
+                self.b_frames
+            );
+            self.b_frames = 0;
+        }
+        let macroblocks = self.width.div_ceil(16) * self.height.div_ceil(16);
+        if let Some(max) = self.level.max_frame_macroblocks() {
+            if macroblocks > max {
+                log::warn!(
+                    "{}x{} exceeds H.264 {:?} ({max} macroblocks); letting the encoder pick the level",
+                    self.width,
+                    self.height,
+                    self.level
+                );
+                self.level = H264Level::Auto;
+            }
+        }
+        self
+    }
+}
 
 /// H.264 encoder using openh264
 pub struct H264Encoder {
@@ -11,31 +207,121 @@ pub struct H264Encoder {
     height: u32,
     frame_count: u64,
     last_frame_was_keyframe: bool,
+    config: H264EncoderConfig,
 }
 
 impl H264Encoder {
-    /// Create a new H.264 encoder with the specified parameters
+    /// Create a new Baseline H.264 encoder with the specified parameters
     ///
-    /// Note: openh264 0.6.x API determines dimensions from the `YUVSource` at encode time.
-    /// The fps and bitrate are hints for the encoder's rate control.
+    /// Shorthand for [`with_config`](Self::with_config) with
+    /// [`H264EncoderConfig::new`].
     ///
     /// # Errors
     /// Returns `CameraError` if the openh264 encoder fails to initialize.
-    pub fn new(width: u32, height: u32, _fps: f64, _bitrate: u32) -> Result<Self, CameraError> {
-        // openh264 0.6.x: Encoder::new() creates with default config
-        // Dimensions are inferred from the YUVSource at encode time
-        let encoder = Encoder::new()
-            .map_err(|e| CameraError::EncodingError(format!("Failed to create encoder: {e}")))?;
+    pub fn new(width: u32, height: u32, fps: f64, bitrate: u32) -> Result<Self, CameraError> {
+        Self::with_config(H264EncoderConfig::new(width, height, fps, bitrate))
+    }
+
+    /// Create an H.264 encoder from a full configuration
+    ///
+    /// Unsupported settings fall back as described in the module docs; the
+    /// effective configuration is available from [`config`](Self::config).
+    /// Dimensions are inferred from the `YUVSource` at encode time, and a
+    /// non-positive fps or zero bitrate leaves openh264's defaults in place.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::EncoderUnavailable`] if openh264 does not work
+    /// on this machine, or a [`CameraError::EncodingError`] if it rejects
+    /// `config`.
+    pub fn with_config(config: H264EncoderConfig) -> Result<Self, CameraError> {
+        let config = config.supported();
+
+        let mut encoder_config = EncoderConfig::new().profile(config.profile.to_openh264());
+        if let Some(level) = config.level.to_openh264() {
+            encoder_config = encoder_config.level(level);
+        }
+        if config.bitrate > 0 {
+            encoder_config = encoder_config
+                .bitrate(BitRate::from_bps(config.bitrate))
+                .rate_control_mode(RateControlMode::Bitrate);
+        }
+        if config.fps.is_finite() && config.fps > 0.0 {
+            #[allow(clippy::cast_possible_truncation)] // frame rates fit f32
+            let fps = config.fps as f32;
+            encoder_config = encoder_config.max_frame_rate(FrameRate::from_hz(fps));
+        }
+
+        let encoder = Encoder::with_api_config(OpenH264API::from_source(), encoder_config)
+            .map_err(|e| {
+                // An encoder with default settings tells a rejected
+                // configuration apart from openh264 not working at all
+                if Encoder::with_api_config(OpenH264API::from_source(), EncoderConfig::new())
+                    .is_ok()
+                {
+                    log::error!("openh264 rejected the encoder configuration: {e}");
+                    CameraError::EncodingError(format!(
+                        "openh264 rejected the encoder configuration: {e}"
+                    ))
+                } else {
+                    log::error!("Failed to create openh264 encoder: {e}");
+                    CameraError::EncoderUnavailable(format!("openh264 ({e})"))
+                }
+            })?;
 
         Ok(Self {
             encoder,
-            width,
-            height,
+            width: config.width,
+            height: config.height,
             frame_count: 0,
             last_frame_was_keyframe: false,
+            config,
         })
     }
 
+    /// Whether openh264 works on this machine
+    ///
+    /// The first call creates an encoder and encodes one small frame; the
+    /// answer is cached for the process.
+    pub fn is_available() -> bool {
+        Self::unavailable_reason().is_none()
+    }
+
+    /// Why openh264 does not work on this machine, if it does not
+    pub fn unavailable_reason() -> Option<&'static str> {
+        static PROBE: OnceLock<Option<String>> = OnceLock::new();
+        PROBE
+            .get_or_init(|| {
+                let probe = Self::new(16, 16, 30.0, 0)
+                    .and_then(|mut encoder| encoder.encode_rgb(&[0; 16 * 16 * 3]));
+                match probe {
+                    Ok(_) => None,
+                    Err(e) => {
+                        log::warn!("openh264 is unavailable: {e}");
+                        Some(e.to_string())
+                    }
+                }
+            })
+            .as_deref()
+    }
+
+    /// Effective configuration, after unsupported settings fell back
+    pub fn config(&self) -> &H264EncoderConfig {
+        &self.config
+    }
+
+    /// Insert an IDR frame every `frames` frames, counted from the first
+    /// frame (0 = only the first frame and forced keyframes)
+    #[must_use]
+    pub fn with_keyframe_interval(mut self, frames: u32) -> Self {
+        self.config.keyframe_interval = frames;
+        self
+    }
+
+    /// Frames between automatic IDR frames (0 = none after the first)
+    pub fn keyframe_interval(&self) -> u32 {
+        self.config.keyframe_interval
+    }
+
     /// Encode an RGB frame to H.264
     /// Returns the encoded NAL units as a single buffer (Annex B format)
     ///
@@ -67,6 +353,12 @@ impl H264Encoder {
         let yuv_buffer =
             YUVBuffer::from_vec(yuv_data.to_vec(), self.width as usize, self.height as usize);
 
+        // Start each GOP on schedule so segments can be cut at fixed positions
+        let interval = u64::from(self.config.keyframe_interval);
+        if interval > 0 && self.frame_count > 0 && self.frame_count % interval == 0 {
+            self.encoder.force_intra_frame();
+        }
+
         let bitstream = self
             .encoder
             .encode(&yuv_buffer)
@@ -174,6 +466,12 @@ mod tests {
         assert!(result.is_ok(), "Encoder should be created successfully");
     }
 
+    #[test]
+    fn test_openh264_probe() {
+        assert!(H264Encoder::is_available());
+        assert!(H264Encoder::unavailable_reason().is_none());
+    }
+
     #[test]
     fn test_encode_frame() {
         let mut encoder =
@@ -204,4 +502,81 @@ mod tests {
             "First frame should be a keyframe"
         );
     }
+
+    /// NAL unit types and the byte after each NAL header, from Annex B data
+    fn nal_units(data: &[u8]) -> Vec<(u8, u8)> {
+        let mut units = Vec::new();
+        let mut i = 0;
+        while i + 3 < data.len() {
+            if data[i..i + 3] == [0, 0, 1] {
+                let header = data[i + 3];
+                units.push((header & 0x1F, data.get(i + 4).copied().unwrap_or(0)));
+                i += 3;
+            } else {
+                i += 1;
+            }
+        }
+        units
+    }
+
+    #[test]
+    fn test_config_streams_are_decodable() {
+        let rgb = vec![128u8; 320 * 240 * 3];
+        for profile in [H264Profile::Baseline, H264Profile::Main, H264Profile::High] {
+            let config = H264EncoderConfig::new(320, 240, 30.0, 500_000)
+                .with_profile(profile)
+                .with_level(H264Level::Level3_0);
+            let mut encoder = H264Encoder::with_config(config).expect("encoder");
+
+            // An IDR access unit carries SPS (7), PPS (8) and an IDR slice (5)
+            let first = nal_units(&encoder.encode_rgb(&rgb).expect("encode").data);
+            let types: Vec<u8> = first.iter().map(|&(t, _)| t).collect();
+            for expected in [7, 8, 5] {
+                assert!(
+                    types.contains(&expected),
+                    "{profile:?}: NAL types {types:?}"
+                );
+            }
+            if profile == H264Profile::Baseline {
+                let sps = first.iter().find(|&&(t, _)| t == 7).expect("SPS");
+                assert_eq!(sps.1, 66, "profile_idc should be Baseline");
+            }
+
+            // Later frames are non-IDR slices (1)
+            let second = nal_units(&encoder.encode_rgb(&rgb).expect("encode").data);
+            assert!(
+                second.iter().all(|&(t, _)| t != 5),
+                "{profile:?}: {second:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unsupported_settings_fall_back() {
+        let config = H264EncoderConfig::new(1920, 1080, 30.0, 5_000_000)
+            .with_b_frames(2)
+            .with_level(H264Level::Level3_0);
+        let encoder = H264Encoder::with_config(config).expect("encoder");
+        assert_eq!(encoder.config().b_frames, 0);
+        assert_eq!(encoder.config().level, H264Level::Auto);
+
+        let config =
+            H264EncoderConfig::new(1280, 720, 30.0, 2_000_000).with_level(H264Level::Level3_1);
+        let encoder = H264Encoder::with_config(config).expect("encoder");
+        assert_eq!(encoder.config().level, H264Level::Level3_1);
+    }
+
+    #[test]
+    fn test_keyframe_interval_inserts_idr_frames() {
+        let mut encoder = H264Encoder::new(320, 240, 30.0, 500_000)
+            .expect("Encoder creation failed")
+            .with_keyframe_interval(5);
+        assert_eq!(encoder.keyframe_interval(), 5);
+
+        let rgb = vec![96u8; 320 * 240 * 3];
+        for i in 0..12 {
+            encoder.encode_rgb(&rgb).expect("encoding should succeed");
+            assert_eq!(encoder.last_was_keyframe(), i % 5 == 0, "frame {i}");
+        }
+    }
 }
@@ -0,0 +1,652 @@
+//! Minimal fragmented MP4 (fMP4) writer for H.264
+//!
+//! Used by [`super::Recorder`] when [`super::RecordingConfig::fragment_interval_secs`]
+//! is set. The `ftyp` and `moov` boxes are written as soon as the first
+//! keyframe supplies the SPS/PPS, and samples are then emitted as
+//! self-describing `moof` + `mdat` fragments, each starting at a keyframe.
+//! Every fragment is flushed to the writer when it is complete, so a file cut
+//! short by a crash plays up to its last complete fragment; [`recover`]
+//! trims an incomplete trailing fragment.
+
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use super::config::RecoveryReport;
+use super::encoder::EncodedFrame;
+use crate::errors::CameraError;
+
+/// Media timescale (ticks per second) of the video track
+const TIMESCALE: u32 = 90_000;
+/// Track ID of the single video track
+const TRACK_ID: u32 = 1;
+/// `trun` sample flags of a sync sample
+const SAMPLE_FLAGS_SYNC: u32 = 0x0200_0000;
+/// `trun` sample flags of a sample that depends on others
+const SAMPLE_FLAGS_NON_SYNC: u32 = 0x0101_0000;
+/// `trun` flags: data offset, sample duration, size and flags present
+const TRUN_FLAGS: u32 = 0x0000_0701;
+/// `tfhd` flags: base data offset is the start of the `moof`
+const TFHD_DEFAULT_BASE_IS_MOOF: u32 = 0x0002_0000;
+
+/// NAL unit types
+const NAL_SPS: u8 = 7;
+const NAL_PPS: u8 = 8;
+const NAL_AUD: u8 = 9;
+
+/// Totals reported when a fragmented file is finalized
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Fmp4Stats {
+    /// Number of video frames written
+    pub frames: u64,
+    /// Media duration in seconds
+    pub duration_secs: f64,
+    /// Total file size in bytes
+    pub bytes_written: u64,
+}
+
+/// One sample waiting for its fragment
+struct Sample {
+    /// Length-prefixed NAL units
+    data: Vec<u8>,
+    /// Decode time in [`TIMESCALE`] ticks from the first sample
+    ticks: u64,
+    keyframe: bool,
+}
+
+/// Writes H.264 access units into a fragmented MP4 stream
+pub(crate) struct Fmp4Writer<W: Write> {
+    writer: W,
+    width: u32,
+    height: u32,
+    fragment_ticks: u64,
+    default_duration: u32,
+    first_pts: Option<f64>,
+    header_written: bool,
+    pending: Vec<Sample>,
+    sequence: u32,
+    frames: u64,
+    bytes_written: u64,
+    last_duration: u32,
+}
+
+impl<W: Write> Fmp4Writer<W> {
+    /// Prepare a writer cutting fragments every `fragment_interval_secs`
+    /// (at the next keyframe)
+    pub fn new(writer: W, width: u32, height: u32, fps: f64, fragment_interval_secs: f64) -> Self {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        // positive, validated intervals and frame rates
+        let (fragment_ticks, default_duration) = (
+            (fragment_interval_secs * f64::from(TIMESCALE)).round() as u64,
+            if fps > 0.0 {
+                (f64::from(TIMESCALE) / fps).round() as u32
+            } else {
+                TIMESCALE / 30
+            },
+        );
+        Self {
+            writer,
+            width,
+            height,
+            fragment_ticks,
+            default_duration,
+            first_pts: None,
+            header_written: false,
+            pending: Vec::new(),
+            sequence: 0,
+            frames: 0,
+            bytes_written: 0,
+            last_duration: default_duration,
+        }
+    }
+
+    /// Queue one encoded frame at `pts` seconds, writing the previous
+    /// fragment first if this keyframe closes it
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::MuxingError`] if the first frame is not a
+    /// keyframe carrying SPS/PPS, or the output cannot be written.
+    pub fn write_sample(&mut self, pts: f64, frame: &EncodedFrame) -> Result<(), CameraError> {
+        let nals = annex_b_nals(&frame.data);
+        if !self.header_written {
+            let sps = nals
+                .iter()
+                .find(|nal| nal_type(nal) == NAL_SPS && nal.len() >= 4);
+            let pps = nals.iter().find(|nal| nal_type(nal) == NAL_PPS);
+            let (Some(sps), Some(pps), true) = (sps, pps, frame.is_keyframe) else {
+                return Err(CameraError::MuxingError(
+                    "Fragmented MP4 must start with a keyframe carrying SPS and PPS".to_string(),
+                ));
+            };
+            let header = [ftyp(), moov(self.width, self.height, sps, pps)].concat();
+            self.write(&header)?;
+            self.header_written = true;
+        }
+
+        let first_pts = *self.first_pts.get_or_insert(pts);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        // clamped to >= 0
+        let ticks = ((pts - first_pts).max(0.0) * f64::from(TIMESCALE)).round() as u64;
+
+        let fragment_start = self.pending.first().map(|sample| sample.ticks);
+        if frame.is_keyframe
+            && fragment_start
+                .is_some_and(|start| ticks.saturating_sub(start) >= self.fragment_ticks)
+        {
+            self.write_fragment(Some(ticks))?;
+        }
+
+        // Parameter sets live in the avcC; delimiters are not stored in MP4
+        let mut data = Vec::with_capacity(frame.data.len());
+        for nal in nals
+            .iter()
+            .filter(|nal| !matches!(nal_type(nal), NAL_SPS | NAL_PPS | NAL_AUD))
+        {
+            data.extend_from_slice(&to_u32(nal.len() as u64)?.to_be_bytes());
+            data.extend_from_slice(nal);
+        }
+        self.pending.push(Sample {
+            data,
+            ticks,
+            keyframe: frame.is_keyframe,
+        });
+        Ok(())
+    }
+
+    /// Write the last fragment and flush
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::MuxingError`] if the output cannot be written.
+    pub fn finish(mut self) -> Result<Fmp4Stats, CameraError> {
+        let end_ticks = self.pending.last().map(|sample| sample.ticks);
+        self.write_fragment(None)?;
+        let total_ticks = end_ticks.map_or(0, |ticks| ticks + u64::from(self.last_duration));
+        #[allow(clippy::cast_precision_loss)]
+        let duration_secs = total_ticks as f64 / f64::from(TIMESCALE);
+        Ok(Fmp4Stats {
+            frames: self.frames,
+            duration_secs,
+            bytes_written: self.bytes_written,
+        })
+    }
+
+    /// Emit the pending samples as one `moof` + `mdat`; `next_ticks` is the
+    /// decode time of the sample after them, if known
+    fn write_fragment(&mut self, next_ticks: Option<u64>) -> Result<(), CameraError> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let samples = std::mem::take(&mut self.pending);
+        self.sequence += 1;
+
+        let mut durations = Vec::with_capacity(samples.len());
+        for (i, sample) in samples.iter().enumerate() {
+            let next = samples.get(i + 1).map(|s| s.ticks).or(next_ticks);
+            let duration = match next {
+                Some(next) => u32::try_from(next.saturating_sub(sample.ticks))
+                    .unwrap_or(self.default_duration),
+                None => durations
+                    .last()
+                    .copied()
+                    .filter(|&d| d > 0)
+                    .unwrap_or(self.last_duration),
+            };
+            durations.push(duration);
+        }
+        if let Some(&last) = durations.iter().rev().find(|&&d| d > 0) {
+            self.last_duration = last;
+        }
+
+        let mdat_len: u64 = samples.iter().map(|s| s.data.len() as u64).sum();
+        let moof = moof(self.sequence, samples[0].ticks, &samples, &durations)?;
+        let mut fragment = moof;
+        fragment.extend_from_slice(&to_u32(mdat_len + 8)?.to_be_bytes());
+        fragment.extend_from_slice(b"mdat");
+        for sample in &samples {
+            fragment.extend_from_slice(&sample.data);
+        }
+        self.write(&fragment)?;
+        self.writer
+            .flush()
+            .map_err(|e| CameraError::MuxingError(format!("Failed to flush fragment: {e}")))?;
+        self.frames += samples.len() as u64;
+        Ok(())
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), CameraError> {
+        self.bytes_written += bytes.len() as u64;
+        self.writer
+            .write_all(bytes)
+            .map_err(|e| CameraError::MuxingError(format!("Failed to write fMP4 data: {e}")))
+    }
+}
+
+/// Trim a fragmented MP4 left behind by an interrupted recording so it ends
+/// at its last complete fragment
+///
+/// # Errors
+/// Returns a [`CameraError::IoError`] if the file cannot be read or
+/// truncated, or a [`CameraError::MuxingError`] if it is not a fragmented
+/// MP4 with its header intact.
+pub(crate) fn recover(path: &Path) -> Result<RecoveryReport, CameraError> {
+    let io_err =
+        |e: std::io::Error| CameraError::IoError(format!("Failed to recover recording: {e}"));
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(io_err)?;
+    let file_len = file.metadata().map_err(io_err)?.len();
+
+    let mut offset = 0u64;
+    let mut kept = 0u64;
+    let mut has_moov = false;
+    let mut report = RecoveryReport::default();
+    let mut fragment: Option<(u32, u64)> = None;
+    let mut total_ticks = 0u64;
+
+    while offset + 8 <= file_len {
+        let mut header = [0u8; 16];
+        file.seek(SeekFrom::Start(offset)).map_err(io_err)?;
+        let available = usize::try_from((file_len - offset).min(16)).unwrap_or(16);
+        file.read_exact(&mut header[..available]).map_err(io_err)?;
+
+        let size32 = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+        let kind = [header[4], header[5], header[6], header[7]];
+        let size = match size32 {
+            0 => file_len - offset,
+            1 if available >= 16 => u64::from_be_bytes(header[8..16].try_into().unwrap_or([0; 8])),
+            1 => break,
+            size => u64::from(size),
+        };
+        if size < 8 || offset + size > file_len {
+            break;
+        }
+
+        match &kind {
+            b"moov" => {
+                has_moov = true;
+                kept = offset + size;
+            }
+            b"moof" => {
+                let len = usize::try_from(size).map_err(|_| {
+                    CameraError::MuxingError("Fragment header too large".to_string())
+                })?;
+                let mut moof = vec![0u8; len];
+                file.seek(SeekFrom::Start(offset)).map_err(io_err)?;
+                file.read_exact(&mut moof).map_err(io_err)?;
+                fragment = trun_totals(&moof);
+            }
+            b"mdat" => {
+                if let Some((samples, ticks)) = fragment.take() {
+                    report.fragments += 1;
+                    report.video_frames += u64::from(samples);
+                    total_ticks += ticks;
+                    kept = offset + size;
+                }
+            }
+            b"ftyp" | b"free" | b"styp" | b"sidx" => {
+                if !has_moov {
+                    kept = offset + size;
+                }
+            }
+            _ => {}
+        }
+        offset += size;
+    }
+
+    if !has_moov {
+        return Err(CameraError::MuxingError(
+            "Not a fragmented MP4 with a complete moov box".to_string(),
+        ));
+    }
+
+    if kept < file_len {
+        file.set_len(kept).map_err(io_err)?;
+        file.sync_all().map_err(io_err)?;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    {
+        report.duration_secs = total_ticks as f64 / f64::from(TIMESCALE);
+    }
+    report.bytes_kept = kept;
+    report.bytes_discarded = file_len - kept;
+    Ok(report)
+}
+
+/// Sample count and summed sample durations of the `trun` in a `moof`
+fn trun_totals(moof: &[u8]) -> Option<(u32, u64)> {
+    let traf = child_box(&moof[8..], b"traf")?;
+    let trun = child_box(traf, b"trun")?;
+    let flags = u32::from_be_bytes([0, trun[1], trun[2], trun[3]]);
+    let count = u32::from_be_bytes(trun.get(4..8)?.try_into().ok()?);
+
+    let mut pos = 8;
+    if flags & 0x1 != 0 {
+        pos += 4;
+    }
+    if flags & 0x4 != 0 {
+        pos += 4;
+    }
+    let fields = [0x100, 0x200, 0x400, 0x800]
+        .iter()
+        .filter(|&&bit| flags & bit != 0)
+        .count();
+    let mut ticks = 0u64;
+    if flags & 0x100 != 0 {
+        for i in 0..count as usize {
+            let at = pos + i * fields * 4;
+            ticks += u64::from(u32::from_be_bytes(trun.get(at..at + 4)?.try_into().ok()?));
+        }
+    }
+    Some((count, ticks))
+}
+
+/// Payload of the first child box of type `kind` (after its 8-byte header)
+fn child_box<'a>(mut data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    while data.len() >= 8 {
+        let size = u32::from_be_bytes(data[0..4].try_into().ok()?) as usize;
+        if size < 8 || size > data.len() {
+            return None;
+        }
+        if &data[4..8] == kind {
+            return Some(&data[8..size]);
+        }
+        data = &data[size..];
+    }
+    None
+}
+
+/// Split an Annex B buffer into NAL units (without start codes)
+fn annex_b_nals(data: &[u8]) -> Vec<&[u8]> {
+    let mut starts = Vec::new();
+    let mut i = 0;
+    while i + 3 <= data.len() {
+        if data[i..i + 3] == [0, 0, 1] {
+            starts.push(i + 3);
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| {
+            let end = starts.get(n + 1).map_or(data.len(), |&next| next - 3);
+            // Trailing zeros belong to the next 4-byte start code
+            let mut end = end;
+            while end > start && data[end - 1] == 0 {
+                end -= 1;
+            }
+            &data[start..end]
+        })
+        .filter(|nal| !nal.is_empty())
+        .collect()
+}
+
+fn nal_type(nal: &[u8]) -> u8 {
+    nal[0] & 0x1F
+}
+
+fn to_u32(value: u64) -> Result<u32, CameraError> {
+    u32::try_from(value).map_err(|_| CameraError::MuxingError("fMP4 box exceeds 4 GiB".to_string()))
+}
+
+fn mp4_box(kind: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(payload.len() + 8);
+    // Boxes built here hold headers or a single fragment's samples
+    let size = u32::try_from(payload.len() + 8).unwrap_or(u32::MAX);
+    out.extend_from_slice(&size.to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(payload);
+    out
+}
+
+fn full_box(kind: &[u8; 4], version: u8, flags: u32, payload: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(payload.len() + 4);
+    body.push(version);
+    body.extend_from_slice(&flags.to_be_bytes()[1..]);
+    body.extend_from_slice(payload);
+    mp4_box(kind, &body)
+}
+
+fn push_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn push_u16(buf: &mut Vec<u8>, value: u16) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+/// Unity transformation matrix used by `mvhd` and `tkhd`
+fn push_matrix(buf: &mut Vec<u8>) {
+    for value in [0x0001_0000, 0, 0, 0, 0x0001_0000, 0, 0, 0, 0x4000_0000] {
+        push_u32(buf, value);
+    }
+}
+
+fn ftyp() -> Vec<u8> {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(b"iso6");
+    push_u32(&mut payload, 0);
+    for brand in [b"iso6", b"isom", b"avc1", b"mp41"] {
+        payload.extend_from_slice(brand);
+    }
+    mp4_box(b"ftyp", &payload)
+}
+
+fn moov(width: u32, height: u32, sps: &[u8], pps: &[u8]) -> Vec<u8> {
+    let mut mvhd = Vec::new();
+    push_u32(&mut mvhd, 0); // creation_time
+    push_u32(&mut mvhd, 0); // modification_time
+    push_u32(&mut mvhd, 1000); // timescale
+    push_u32(&mut mvhd, 0); // duration (fragments carry it)
+    push_u32(&mut mvhd, 0x0001_0000); // rate
+    push_u16(&mut mvhd, 0x0100); // volume
+    mvhd.extend_from_slice(&[0; 10]);
+    push_matrix(&mut mvhd);
+    mvhd.extend_from_slice(&[0; 24]); // pre_defined
+    push_u32(&mut mvhd, TRACK_ID + 1); // next_track_ID
+
+    let mut tkhd = Vec::new();
+    push_u32(&mut tkhd, 0); // creation_time
+    push_u32(&mut tkhd, 0); // modification_time
+    push_u32(&mut tkhd, TRACK_ID);
+    push_u32(&mut tkhd, 0); // reserved
+    push_u32(&mut tkhd, 0); // duration
+    tkhd.extend_from_slice(&[0; 8]);
+    push_u16(&mut tkhd, 0); // layer
+    push_u16(&mut tkhd, 0); // alternate_group
+    push_u16(&mut tkhd, 0); // volume
+    push_u16(&mut tkhd, 0); // reserved
+    push_matrix(&mut tkhd);
+    push_u32(&mut tkhd, width << 16);
+    push_u32(&mut tkhd, height << 16);
+
+    let mut mdhd = Vec::new();
+    push_u32(&mut mdhd, 0); // creation_time
+    push_u32(&mut mdhd, 0); // modification_time
+    push_u32(&mut mdhd, TIMESCALE);
+    push_u32(&mut mdhd, 0); // duration
+    push_u16(&mut mdhd, 0x55C4); // language "und"
+    push_u16(&mut mdhd, 0);
+
+    let mut hdlr = Vec::new();
+    push_u32(&mut hdlr, 0); // pre_defined
+    hdlr.extend_from_slice(b"vide");
+    hdlr.extend_from_slice(&[0; 12]);
+    hdlr.extend_from_slice(b"VideoHandler\0");
+
+    let vmhd = full_box(b"vmhd", 0, 1, &[0; 8]);
+    let mut dref = Vec::new();
+    push_u32(&mut dref, 1);
+    dref.extend_from_slice(&full_box(b"url ", 0, 1, &[]));
+    let dinf = mp4_box(b"dinf", &full_box(b"dref", 0, 0, &dref));
+
+    let stbl = mp4_box(
+        b"stbl",
+        &[
+            stsd(width, height, sps, pps),
+            full_box(b"stts", 0, 0, &[0; 4]),
+            full_box(b"stsc", 0, 0, &[0; 4]),
+            full_box(b"stsz", 0, 0, &[0; 8]),
+            full_box(b"stco", 0, 0, &[0; 4]),
+        ]
+        .concat(),
+    );
+    let minf = mp4_box(b"minf", &[vmhd, dinf, stbl].concat());
+    let mdia = mp4_box(
+        b"mdia",
+        &[
+            full_box(b"mdhd", 0, 0, &mdhd),
+            full_box(b"hdlr", 0, 0, &hdlr),
+            minf,
+        ]
+        .concat(),
+    );
+    let trak = mp4_box(b"trak", &[full_box(b"tkhd", 0, 3, &tkhd), mdia].concat());
+
+    let mut trex = Vec::new();
+    push_u32(&mut trex, TRACK_ID);
+    push_u32(&mut trex, 1); // default_sample_description_index
+    push_u32(&mut trex, 0); // default_sample_duration
+    push_u32(&mut trex, 0); // default_sample_size
+    push_u32(&mut trex, 0); // default_sample_flags
+    let mvex = mp4_box(b"mvex", &full_box(b"trex", 0, 0, &trex));
+
+    mp4_box(
+        b"moov",
+        &[full_box(b"mvhd", 0, 0, &mvhd), trak, mvex].concat(),
+    )
+}
+
+fn stsd(width: u32, height: u32, sps: &[u8], pps: &[u8]) -> Vec<u8> {
+    let mut avcc = vec![1, sps[1], sps[2], sps[3], 0xFF, 0xE1];
+    push_u16(&mut avcc, u16::try_from(sps.len()).unwrap_or(u16::MAX));
+    avcc.extend_from_slice(sps);
+    avcc.push(1);
+    push_u16(&mut avcc, u16::try_from(pps.len()).unwrap_or(u16::MAX));
+    avcc.extend_from_slice(pps);
+
+    let mut avc1 = vec![0; 6];
+    push_u16(&mut avc1, 1); // data_reference_index
+    avc1.extend_from_slice(&[0; 16]); // pre_defined / reserved
+    push_u16(&mut avc1, u16::try_from(width).unwrap_or(u16::MAX));
+    push_u16(&mut avc1, u16::try_from(height).unwrap_or(u16::MAX));
+    push_u32(&mut avc1, 0x0048_0000); // horizresolution
+    push_u32(&mut avc1, 0x0048_0000); // vertresolution
+    push_u32(&mut avc1, 0);
+    push_u16(&mut avc1, 1); // frame_count
+    avc1.extend_from_slice(&[0; 32]); // compressorname
+    push_u16(&mut avc1, 0x0018); // depth
+    push_u16(&mut avc1, 0xFFFF); // pre_defined
+    avc1.extend_from_slice(&mp4_box(b"avcC", &avcc));
+
+    let mut stsd = Vec::new();
+    push_u32(&mut stsd, 1);
+    stsd.extend_from_slice(&mp4_box(b"avc1", &avc1));
+    full_box(b"stsd", 0, 0, &stsd)
+}
+
+fn moof(
+    sequence: u32,
+    base_ticks: u64,
+    samples: &[Sample],
+    durations: &[u32],
+) -> Result<Vec<u8>, CameraError> {
+    let mut mfhd = Vec::new();
+    push_u32(&mut mfhd, sequence);
+
+    let mut tfhd = Vec::new();
+    push_u32(&mut tfhd, TRACK_ID);
+
+    let mut trun = Vec::new();
+    push_u32(&mut trun, to_u32(samples.len() as u64)?);
+    push_u32(&mut trun, 0); // data_offset, patched below
+    for (sample, &duration) in samples.iter().zip(durations) {
+        push_u32(&mut trun, duration);
+        push_u32(&mut trun, to_u32(sample.data.len() as u64)?);
+        push_u32(
+            &mut trun,
+            if sample.keyframe {
+                SAMPLE_FLAGS_SYNC
+            } else {
+                SAMPLE_FLAGS_NON_SYNC
+            },
+        );
+    }
+
+    let traf = mp4_box(
+        b"traf",
+        &[
+            full_box(b"tfhd", 0, TFHD_DEFAULT_BASE_IS_MOOF, &tfhd),
+            full_box(b"tfdt", 1, 0, &base_ticks.to_be_bytes()),
+            full_box(b"trun", 0, TRUN_FLAGS, &trun),
+        ]
+        .concat(),
+    );
+    let mut moof = mp4_box(b"moof", &[full_box(b"mfhd", 0, 0, &mfhd), traf].concat());
+
+    // Sample data starts right after the mdat header that follows the moof;
+    // the data_offset field is the last 4 bytes before the per-sample entries
+    let data_offset = to_u32(moof.len() as u64 + 8)?;
+    let entries_len = samples.len() * 12;
+    let at = moof.len() - entries_len - 4;
+    moof[at..at + 4].copy_from_slice(&data_offset.to_be_bytes());
+    Ok(moof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(keyframe: bool) -> EncodedFrame {
+        let mut data = Vec::new();
+        if keyframe {
+            data.extend_from_slice(&[0, 0, 0, 1, 0x67, 66, 0, 30, 0xAB]);
+            data.extend_from_slice(&[0, 0, 0, 1, 0x68, 0xCE, 0x38]);
+            data.extend_from_slice(&[0, 0, 0, 1, 0x65, 0x88, 0x84]);
+        } else {
+            data.extend_from_slice(&[0, 0, 0, 1, 0x41, 0x9A, 0x02]);
+        }
+        EncodedFrame {
+            data,
+            is_keyframe: keyframe,
+        }
+    }
+
+    #[test]
+    fn test_fragments_start_at_keyframes_after_interval() {
+        let mut writer = Fmp4Writer::new(Vec::new(), 64, 48, 10.0, 0.5);
+        for i in 0..20u32 {
+            let pts = f64::from(i) / 10.0;
+            writer
+                .write_sample(pts, &frame(i % 5 == 0))
+                .expect("sample");
+        }
+        let bytes = writer.writer.clone();
+        let stats = writer.finish().expect("finish");
+        assert_eq!(stats.frames, 20);
+        assert!((stats.duration_secs - 2.0).abs() < 1e-6);
+
+        // ftyp + moov, then one fragment per keyframe group
+        assert_eq!(&bytes[4..8], b"ftyp");
+        let moofs = bytes.windows(4).filter(|w| *w == b"moof").count();
+        assert_eq!(moofs, 3, "the last group is written by finish");
+    }
+
+    #[test]
+    fn test_first_sample_must_be_keyframe() {
+        let mut writer = Fmp4Writer::new(Vec::new(), 64, 48, 10.0, 1.0);
+        assert!(writer.write_sample(0.0, &frame(false)).is_err());
+    }
+
+    #[test]
+    fn test_annex_b_nals_strip_start_codes() {
+        let nals = annex_b_nals(&frame(true).data);
+        let types: Vec<u8> = nals.iter().map(|nal| nal_type(nal)).collect();
+        assert_eq!(types, [NAL_SPS, NAL_PPS, 5]);
+        assert_eq!(nals[1], [0x68, 0xCE, 0x38]);
+    }
+}
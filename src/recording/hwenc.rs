@@ -0,0 +1,408 @@
+//! Hardware H.264 encoding through an external ffmpeg
+//!
+//! The platform encoders (VideoToolbox on macOS, the Media Foundation
+//! hardware MFT on Windows, VA-API on Linux) are driven by an `ffmpeg`
+//! child process, run from the path configured in [`crate::ffmpeg`] and
+//! never from `PATH`: raw RGB frames go in on stdin and an Annex B stream with
+//! an access unit delimiter before every frame comes back on stdout, which
+//! a reader thread splits into one packet per frame.
+//!
+//! [`probe_hardware_encoder`] test-encodes one frame with the platform's
+//! encoder the first time it is called for a configured ffmpeg and caches
+//! the answer, so an unconfigured or missing ffmpeg, a build without the
+//! encoder, or a GPU without H.264 support all count as "no hardware" and
+//! recordings fall back to openh264.
+//!
+//! Hardware encoders buffer a few frames, so packets come back after the
+//! frames that produced them; each packet is muxed at the PTS of its input
+//! frame. B-frames are disabled so output order matches input order, and
+//! per-frame keyframe requests are not supported (use a keyframe interval).
+
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+
+use super::config::EncoderBackend;
+use super::encoder::{EncodedFrame, H264EncoderConfig, H264Profile};
+use crate::constants::HWENC_VAAPI_DEVICE;
+use crate::errors::CameraError;
+use crate::ffmpeg::ffmpeg_path;
+
+/// NAL unit type of an access unit delimiter
+const NAL_AUD: u8 = 9;
+/// NAL unit type of an IDR slice
+const NAL_IDR: u8 = 5;
+
+/// Hardware encoder this platform would use, before probing
+fn platform_backend() -> Option<EncoderBackend> {
+    if cfg!(target_os = "macos") {
+        Some(EncoderBackend::VideoToolbox)
+    } else if cfg!(target_os = "windows") {
+        Some(EncoderBackend::MediaFoundation)
+    } else if cfg!(target_os = "linux") {
+        Some(EncoderBackend::VaApi)
+    } else {
+        None
+    }
+}
+
+/// ffmpeg arguments selecting `backend`, placed before the input
+fn device_args(backend: EncoderBackend) -> Vec<String> {
+    match backend {
+        EncoderBackend::VaApi => vec!["-vaapi_device".to_string(), HWENC_VAAPI_DEVICE.to_string()],
+        _ => Vec::new(),
+    }
+}
+
+/// ffmpeg arguments selecting `backend`, placed after the input
+fn encoder_args(backend: EncoderBackend) -> Vec<String> {
+    let args: &[&str] = match backend {
+        EncoderBackend::VideoToolbox => &["-c:v", "h264_videotoolbox", "-realtime", "1"],
+        EncoderBackend::MediaFoundation => &["-c:v", "h264_mf", "-hw_encoding", "1"],
+        EncoderBackend::VaApi => &["-vf", "format=nv12,hwupload", "-c:v", "h264_vaapi"],
+        EncoderBackend::OpenH264 => &[],
+    };
+    args.iter().map(ToString::to_string).collect()
+}
+
+/// Hardware encoder available on this machine, probed once per configured
+/// ffmpeg
+///
+/// Returns `None` when no ffmpeg is configured (see [`crate::ffmpeg`]), or
+/// it is missing or cannot encode with the platform's hardware encoder.
+/// Blocks while the probe runs; call it off the async runtime.
+pub fn probe_hardware_encoder() -> Option<EncoderBackend> {
+    static PROBE: Mutex<Option<(PathBuf, Option<EncoderBackend>)>> = Mutex::new(None);
+
+    let ffmpeg = match ffmpeg_path() {
+        Ok(ffmpeg) => ffmpeg,
+        Err(e) => {
+            log::info!("Hardware H.264 encoding unavailable: {e}");
+            return None;
+        }
+    };
+    let mut probe = PROBE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some((probed, backend)) = probe.as_ref() {
+        if *probed == ffmpeg {
+            return *backend;
+        }
+    }
+    let backend = probe_with(&ffmpeg);
+    *probe = Some((ffmpeg, backend));
+    backend
+}
+
+/// Test-encode one frame with `ffmpeg` on the platform's hardware encoder
+fn probe_with(ffmpeg: &Path) -> Option<EncoderBackend> {
+    let backend = platform_backend()?;
+    let status = Command::new(ffmpeg)
+        .args(["-hide_banner", "-loglevel", "error"])
+        .args(device_args(backend))
+        .args(["-f", "lavfi", "-i", "color=c=black:s=256x256:d=0.1"])
+        .args(encoder_args(backend))
+        .args(["-frames:v", "1", "-f", "null", "-"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    match status {
+        Ok(status) if status.success() => {
+            log::info!("Hardware H.264 encoder available: {backend:?}");
+            Some(backend)
+        }
+        Ok(status) => {
+            log::info!("Hardware H.264 encoder {backend:?} unusable ({status})");
+            None
+        }
+        Err(e) => {
+            log::info!(
+                "Hardware H.264 encoding unavailable, cannot run {}: {e}",
+                ffmpeg.display()
+            );
+            None
+        }
+    }
+}
+
+/// H.264 encoder running on the platform's hardware encoder
+pub(crate) struct HardwareEncoder {
+    backend: EncoderBackend,
+    child: Child,
+    stdin: Option<ChildStdin>,
+    packets: Receiver<Vec<u8>>,
+    reader: Option<JoinHandle<()>>,
+    /// PTS of frames sent to ffmpeg whose packets have not come back yet
+    pending_pts: VecDeque<f64>,
+    frame_size: usize,
+    packets_out: u64,
+    last_was_keyframe: bool,
+}
+
+impl HardwareEncoder {
+    /// Start an ffmpeg process encoding with `backend`
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::UnsupportedOperation`] if no ffmpeg is
+    /// configured, or a [`CameraError::EncodingError`] if it cannot be started.
+    pub(crate) fn new(
+        backend: EncoderBackend,
+        config: &H264EncoderConfig,
+    ) -> Result<Self, CameraError> {
+        let profile = match (config.profile, backend) {
+            (H264Profile::Baseline, EncoderBackend::VaApi) => "constrained_baseline",
+            (H264Profile::Baseline, _) => "baseline",
+            (H264Profile::Main, _) => "main",
+            (H264Profile::High, _) => "high",
+        };
+
+        let ffmpeg = ffmpeg_path()?;
+        let mut command = Command::new(&ffmpeg);
+        command
+            .args(["-hide_banner", "-loglevel", "error"])
+            .args(device_args(backend))
+            .args(["-f", "rawvideo", "-pix_fmt", "rgb24"])
+            .arg("-s")
+            .arg(format!("{}x{}", config.width, config.height))
+            .arg("-r")
+            .arg(config.fps.to_string())
+            .args(["-i", "pipe:0"])
+            .args(encoder_args(backend))
+            .args(["-profile:v", profile, "-bf", "0"])
+            .arg("-b:v")
+            .arg(config.bitrate.to_string());
+        if config.keyframe_interval > 0 {
+            command.arg("-g").arg(config.keyframe_interval.to_string());
+        }
+        let mut child = command
+            .args(["-bsf:v", "h264_metadata=aud=insert", "-f", "h264", "pipe:1"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| {
+                CameraError::EncodingError(format!("Failed to start {}: {e}", ffmpeg.display()))
+            })?;
+
+        let stdin = child.stdin.take();
+        let mut stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| CameraError::EncodingError("ffmpeg stdout unavailable".to_string()))?;
+
+        let (sender, packets) = mpsc::channel();
+        let reader = std::thread::Builder::new()
+            .name("crabcamera-hwenc".to_string())
+            .spawn(move || {
+                let mut buffer = Vec::new();
+                let mut chunk = [0u8; 64 * 1024];
+                loop {
+                    match stdout.read(&mut chunk) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => {
+                            buffer.extend_from_slice(&chunk[..n]);
+                            for unit in split_access_units(&mut buffer) {
+                                if sender.send(unit).is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                    }
+                }
+                if !buffer.is_empty() {
+                    let _ = sender.send(buffer);
+                }
+            })
+            .map_err(|e| CameraError::EncodingError(format!("Failed to spawn reader: {e}")))?;
+
+        log::info!(
+            "Hardware H.264 encoding with {backend:?} at {}x{}",
+            config.width,
+            config.height
+        );
+        Ok(Self {
+            backend,
+            child,
+            stdin,
+            packets,
+            reader: Some(reader),
+            pending_pts: VecDeque::new(),
+            frame_size: (config.width as usize) * (config.height as usize) * 3,
+            packets_out: 0,
+            last_was_keyframe: false,
+        })
+    }
+
+    /// Send one RGB frame and return the packets that are ready, each with
+    /// the PTS of the frame it encodes
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::EncodingError`] if the frame size is wrong
+    /// or ffmpeg has exited.
+    pub(crate) fn encode_rgb(
+        &mut self,
+        rgb_data: &[u8],
+        pts: f64,
+    ) -> Result<Vec<(f64, EncodedFrame)>, CameraError> {
+        if rgb_data.len() != self.frame_size {
+            return Err(CameraError::EncodingError(format!(
+                "Invalid frame size: expected {} bytes, got {}",
+                self.frame_size,
+                rgb_data.len()
+            )));
+        }
+        let stdin = self.stdin.as_mut().ok_or_else(|| {
+            CameraError::EncodingError("Hardware encoder already flushed".to_string())
+        })?;
+        stdin
+            .write_all(rgb_data)
+            .map_err(|e| CameraError::EncodingError(format!("Hardware encoder exited: {e}")))?;
+        self.pending_pts.push_back(pts);
+
+        let ready: Vec<Vec<u8>> = self.packets.try_iter().collect();
+        Ok(self.pair_with_pts(ready))
+    }
+
+    /// Close the input, wait for ffmpeg to drain and return the remaining
+    /// packets
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::EncodingError`] if ffmpeg exits with an
+    /// error.
+    pub(crate) fn flush(&mut self) -> Result<Vec<(f64, EncodedFrame)>, CameraError> {
+        drop(self.stdin.take());
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+        let status = self
+            .child
+            .wait()
+            .map_err(|e| CameraError::EncodingError(format!("Hardware encoder failed: {e}")))?;
+        if !status.success() {
+            return Err(CameraError::EncodingError(format!(
+                "Hardware encoder {:?} exited with {status}",
+                self.backend
+            )));
+        }
+        let rest: Vec<Vec<u8>> = self.packets.try_iter().collect();
+        let packets = self.pair_with_pts(rest);
+        if !self.pending_pts.is_empty() {
+            log::warn!(
+                "Hardware encoder returned {} fewer packets than frames",
+                self.pending_pts.len()
+            );
+        }
+        Ok(packets)
+    }
+
+    fn pair_with_pts(&mut self, units: Vec<Vec<u8>>) -> Vec<(f64, EncodedFrame)> {
+        let mut packets = Vec::with_capacity(units.len());
+        for data in units {
+            let Some(pts) = self.pending_pts.pop_front() else {
+                log::warn!("Hardware encoder produced more packets than frames");
+                break;
+            };
+            let is_keyframe = nal_types(&data).contains(&NAL_IDR);
+            self.last_was_keyframe = is_keyframe;
+            self.packets_out += 1;
+            packets.push((pts, EncodedFrame { data, is_keyframe }));
+        }
+        packets
+    }
+
+    /// Encoder backend in use
+    pub(crate) fn backend(&self) -> EncoderBackend {
+        self.backend
+    }
+
+    /// Number of packets returned so far
+    pub(crate) fn packets_out(&self) -> u64 {
+        self.packets_out
+    }
+
+    /// Whether the most recently returned packet was an IDR frame
+    pub(crate) fn last_was_keyframe(&self) -> bool {
+        self.last_was_keyframe
+    }
+}
+
+impl Drop for HardwareEncoder {
+    fn drop(&mut self) {
+        if self.reader.is_some() {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+/// Offsets of the start codes (including a leading zero of a 4-byte start
+/// code) of every access unit delimiter in `data`
+fn delimiter_offsets(data: &[u8]) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    let mut i = 0;
+    while i + 3 < data.len() {
+        if data[i..i + 3] == [0, 0, 1] && data[i + 3] & 0x1F == NAL_AUD {
+            offsets.push(if i > 0 && data[i - 1] == 0 { i - 1 } else { i });
+            i += 4;
+        } else {
+            i += 1;
+        }
+    }
+    offsets
+}
+
+/// Remove every complete access unit from the front of `buffer`, leaving
+/// the unit still being received
+fn split_access_units(buffer: &mut Vec<u8>) -> Vec<Vec<u8>> {
+    // A unit is complete once the delimiter of the next one has arrived
+    let mut units = Vec::new();
+    let mut start = 0;
+    for offset in delimiter_offsets(buffer) {
+        if offset > start {
+            units.push(buffer[start..offset].to_vec());
+            start = offset;
+        }
+    }
+    buffer.drain(..start);
+    units
+}
+
+/// NAL unit types in an Annex B buffer
+fn nal_types(data: &[u8]) -> Vec<u8> {
+    data.windows(4)
+        .filter(|w| w[..3] == [0, 0, 1])
+        .map(|w| w[3] & 0x1F)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(types: &[u8]) -> Vec<u8> {
+        let mut data = vec![0, 0, 0, 1, NAL_AUD, 0xF0];
+        for &nal in types {
+            data.extend_from_slice(&[0, 0, 0, 1, 0x60 | nal, 0xAA, 0xBB]);
+        }
+        data
+    }
+
+    #[test]
+    fn test_split_access_units_keeps_partial_tail() {
+        let first = unit(&[7, 8, NAL_IDR]);
+        let second = unit(&[1]);
+        let mut buffer = [first.clone(), second.clone(), unit(&[1])[..5].to_vec()].concat();
+
+        let units = split_access_units(&mut buffer);
+        assert_eq!(units, [first.clone(), second]);
+        assert_eq!(buffer, unit(&[1])[..5]);
+
+        assert!(nal_types(&first).contains(&NAL_IDR));
+        assert!(split_access_units(&mut Vec::new()).is_empty());
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides video recording capabilities using:
 //! - openh264 for H.264 encoding
 //! - muxide for MP4 muxing
+//! - a built-in AVI writer for MJPEG passthrough (no re-encoding)
 //!
 //! # Example
 //! ```rust,ignore
@@ -16,16 +17,29 @@
 //!
 //! // When done:
 //! let stats = recorder.finish()?;
+//!
+//! // Or mux pre-captured frames, timed by their timestamps:
+//! let stats = Recorder::from_sources("timelapse.mp4", frames, config)?;
 //! ```
 
+mod avi;
+mod cfr;
 mod config;
 mod encoder;
+mod fmp4;
+#[cfg(feature = "hwenc")]
+mod hwenc;
 mod recorder;
 
 #[cfg(feature = "audio")]
 pub use config::AudioConfig;
-pub use config::{RecordingConfig, RecordingQuality, RecordingStats};
-pub use encoder::{EncodedFrame, H264Encoder};
+pub use config::{
+    EncoderBackend, EncoderPreference, RecordingCodec, RecordingConfig, RecordingQuality,
+    RecordingStats, RecoveryReport,
+};
+pub use encoder::{EncodedFrame, H264Encoder, H264EncoderConfig, H264Level, H264Profile};
+#[cfg(feature = "hwenc")]
+pub use hwenc::probe_hardware_encoder;
 pub use recorder::Recorder;
 
 #[cfg(test)]
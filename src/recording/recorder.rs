@@ -9,11 +9,45 @@
 //! - Audio support is optional
 //! - Configures muxer audio track when enabled
 //! - Continues video if audio fails (graceful degradation)
-//! - Never blocks video on audio initialization
+//! - Never blocks video on audio initialization beyond the bounded start
+//!   barrier
+//!
+//! [`Recorder::start`] pre-rolls audio capture and waits for its first
+//! frame; the first video frame after that is t=0 of both tracks, and
+//! audio captured earlier is trimmed. The measured startup offset is
+//! reported in [`RecordingStats::av_start_offset_secs`].
+//!
+//! With [`RecordingCodec::MjpegPassthrough`] the encoder is bypassed entirely:
+//! MJPEG frames from [`PlatformCamera::capture_frame_raw`] are written to an
+//! AVI file exactly as the camera delivered them. Audio is not available in
+//! this mode.
+//!
+//! With [`RecordingConfig::reorder_window`] set, accepted frames pass through
+//! a [`ReorderBuffer`] and are encoded in hardware timestamp order, delayed by
+//! that many frames; frames arriving after a later-stamped frame was written
+//! are discarded so the file never sees timestamps go backwards.
+//!
+//! With [`RecordingConfig::fragment_interval_secs`] set, the H.264 stream is
+//! written as fragmented MP4 instead, so an interrupted recording stays
+//! playable up to its last flushed fragment ([`Recorder::recover`] trims the
+//! torn tail). Fragmented recordings are video only.
+//!
+//! With [`RecordingConfig::constant_framerate`] set, frames are placed on an
+//! evenly spaced grid by timestamp instead of being rate limited by arrival
+//! time (see the `cfr` module); repeated and dropped frames are reported in
+//! [`RecordingStats::cfr_duplicated_frames`] and
+//! [`RecordingStats::cfr_dropped_frames`].
+//!
+//! [`RecordingConfig::encoder_preference`] chooses between openh264 and,
+//! with the `hwenc` feature, the platform's hardware encoder; the one used
+//! is reported in [`RecordingStats::encoder`].
+//!
+//! [`PlatformCamera::capture_frame_raw`]: crate::platform::PlatformCamera::capture_frame_raw
 
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{BufWriter, Cursor, Seek, SeekFrom, Write};
 use std::path::Path;
+use std::sync::{Arc, Mutex as StdMutex};
 use std::time::Instant;
 
 use muxide::api::{Metadata, MuxerBuilder, VideoCodec};
@@ -21,25 +55,300 @@ use muxide::api::{Metadata, MuxerBuilder, VideoCodec};
 #[cfg(feature = "audio")]
 use muxide::api::AudioCodec;
 
-use super::config::{RecordingConfig, RecordingStats};
-use super::encoder::H264Encoder;
+use super::avi::AviMjpegWriter;
+use super::cfr::{self, CfrConverter};
+use super::config::{
+    EncoderBackend, EncoderPreference, RecordingCodec, RecordingConfig, RecordingStats,
+    RecoveryReport,
+};
+use super::encoder::{EncodedFrame, H264Encoder, H264EncoderConfig};
+use super::fmp4::Fmp4Writer;
+#[cfg(feature = "hwenc")]
+use super::hwenc::HardwareEncoder;
 use crate::constants::{
-    RECORDING_AUDIO_CHANNEL_CAPACITY, RECORDING_AUDIO_SLEEP_MS, RECORDING_DROP_LOG_INTERVAL,
-    RECORDING_JITTER_TOLERANCE,
+    RECORDING_AUDIO_CHANNEL_CAPACITY, RECORDING_AUDIO_PREROLL_FRAMES, RECORDING_AUDIO_SLEEP_MS,
+    RECORDING_AV_START_TIMEOUT_MS, RECORDING_DROP_LOG_INTERVAL, RECORDING_JITTER_TOLERANCE,
 };
 use crate::errors::CameraError;
-use crate::types::CameraFrame;
+use crate::platform::ReorderBuffer;
+use crate::types::{CameraFrame, FourCC};
 
 #[cfg(feature = "audio")]
-use crate::audio::{EncodedAudio, OpusEncoder, PTSClock};
+use super::config::AudioConfig;
+#[cfg(feature = "audio")]
+use crate::audio::{AudioDeviceSwitch, AudioFrame, EncodedAudio, OpusEncoder, PTSClock};
+#[cfg(feature = "audio")]
+use crate::config::{tune_current_thread, ThreadRole};
+#[cfg(feature = "audio")]
+use std::collections::VecDeque;
+#[cfg(feature = "audio")]
+use std::sync::atomic::{AtomicU64, Ordering};
 #[cfg(feature = "audio")]
 use std::thread::JoinHandle;
 
+/// Encode the held audio frames once the first video frame has fixed t=0,
+/// trimmed and rebased so the audio track starts with the video
+#[cfg(feature = "audio")]
+fn encode_pending(
+    pending: &mut VecDeque<AudioFrame>,
+    origin: &AtomicU64,
+    encoder: &mut OpusEncoder,
+    sender: &crossbeam_channel::Sender<EncodedAudio>,
+) {
+    let origin = f64::from_bits(origin.load(Ordering::SeqCst));
+    if origin.is_nan() {
+        return;
+    }
+    for frame in pending.drain(..) {
+        let Some(frame) = rebase_audio_frame(frame, origin) else {
+            continue;
+        };
+        if let Ok(packets) = encoder.encode(&frame) {
+            for packet in packets {
+                if sender.try_send(packet).is_err() {
+                    // Channel full, drop packet (not a fatal error)
+                    log::debug!("Audio channel full, dropping packet");
+                }
+            }
+        }
+    }
+}
+
+/// Shift `frame` to be relative to `origin`, dropping the samples before
+/// it; `None` if the whole frame precedes it
+#[cfg(feature = "audio")]
+fn rebase_audio_frame(mut frame: AudioFrame, origin: f64) -> Option<AudioFrame> {
+    let channels = usize::from(frame.channels.max(1));
+    let frames = frame.samples.len() / channels;
+    let early_secs = origin - frame.timestamp;
+    if early_secs > 0.0 {
+        // Positive and bounded by the frame length below
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let skip = ((early_secs * f64::from(frame.sample_rate)).round() as usize).min(frames);
+        if skip == frames {
+            return None;
+        }
+        frame.samples.drain(..skip * channels);
+        frame.timestamp = origin;
+    }
+    frame.timestamp -= origin;
+    Some(frame)
+}
+
+/// H.264 encoder selected by [`RecordingConfig::encoder_preference`]
+enum VideoEncoder {
+    Software(H264Encoder),
+    #[cfg(feature = "hwenc")]
+    Hardware(HardwareEncoder),
+}
+
+impl VideoEncoder {
+    /// Pick the encoder for `config`
+    fn select(config: &RecordingConfig) -> Result<Self, CameraError> {
+        let encoder_config =
+            H264EncoderConfig::new(config.width, config.height, config.fps, config.bitrate)
+                .with_profile(config.profile)
+                .with_level(config.level)
+                .with_keyframe_interval(config.keyframe_interval_frames);
+
+        #[cfg(feature = "hwenc")]
+        if config.encoder_preference != EncoderPreference::Software {
+            match super::hwenc::probe_hardware_encoder() {
+                Some(backend) => match HardwareEncoder::new(backend, &encoder_config) {
+                    Ok(encoder) => return Ok(Self::Hardware(encoder)),
+                    Err(e) if config.encoder_preference == EncoderPreference::Auto => {
+                        log::warn!("Hardware encoder failed, using openh264: {e}");
+                    }
+                    Err(e) => return Err(e),
+                },
+                None if config.encoder_preference == EncoderPreference::Hardware => {
+                    return Err(CameraError::EncodingError(
+                        "No hardware H.264 encoder available".to_string(),
+                    ));
+                }
+                None => {}
+            }
+        }
+
+        #[cfg(not(feature = "hwenc"))]
+        if config.encoder_preference == EncoderPreference::Hardware {
+            return Err(CameraError::EncodingError(
+                "Hardware encoding requires the `hwenc` feature".to_string(),
+            ));
+        }
+
+        match H264Encoder::with_config(encoder_config.clone()) {
+            Ok(encoder) => Ok(Self::Software(encoder)),
+            #[cfg(feature = "hwenc")]
+            Err(e @ CameraError::EncoderUnavailable(_)) => {
+                let backend = super::hwenc::probe_hardware_encoder().ok_or(e)?;
+                log::warn!("openh264 is unavailable, recording with {backend:?}");
+                HardwareEncoder::new(backend, &encoder_config).map(Self::Hardware)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Encode one RGB frame, returning the packets now ready with their PTS
+    ///
+    /// openh264 returns the frame's own packet (or nothing); hardware
+    /// encoders return packets of earlier frames as they complete.
+    fn encode(&mut self, rgb: &[u8], pts: f64) -> Result<Vec<(f64, EncodedFrame)>, CameraError> {
+        match self {
+            Self::Software(encoder) => {
+                let encoded = encoder.encode_rgb(rgb)?;
+                Ok(if encoded.data.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![(pts, encoded)]
+                })
+            }
+            #[cfg(feature = "hwenc")]
+            Self::Hardware(encoder) => encoder.encode_rgb(rgb, pts),
+        }
+    }
+
+    /// Packets still inside the encoder
+    #[cfg_attr(not(feature = "hwenc"), allow(clippy::unnecessary_wraps))]
+    fn flush(&mut self) -> Result<Vec<(f64, EncodedFrame)>, CameraError> {
+        match self {
+            Self::Software(_) => Ok(Vec::new()),
+            #[cfg(feature = "hwenc")]
+            Self::Hardware(encoder) => encoder.flush(),
+        }
+    }
+
+    /// Whether an empty [`encode`](Self::encode) result means the frame is
+    /// still in flight rather than dropped
+    fn is_delayed(&self) -> bool {
+        !matches!(self, Self::Software(_))
+    }
+
+    /// Whether a packet has been produced yet (audio must follow video)
+    fn has_output(&self) -> bool {
+        match self {
+            Self::Software(encoder) => encoder.frame_count() > 0,
+            #[cfg(feature = "hwenc")]
+            Self::Hardware(encoder) => encoder.packets_out() > 0,
+        }
+    }
+
+    fn backend(&self) -> EncoderBackend {
+        match self {
+            Self::Software(_) => EncoderBackend::OpenH264,
+            #[cfg(feature = "hwenc")]
+            Self::Hardware(encoder) => encoder.backend(),
+        }
+    }
+
+    fn force_keyframe(&mut self) {
+        match self {
+            Self::Software(encoder) => encoder.force_keyframe(),
+            #[cfg(feature = "hwenc")]
+            Self::Hardware(_) => {
+                log::debug!("Keyframe requests are not supported by hardware encoders");
+            }
+        }
+    }
+
+    fn last_was_keyframe(&self) -> bool {
+        match self {
+            Self::Software(encoder) => encoder.last_was_keyframe(),
+            #[cfg(feature = "hwenc")]
+            Self::Hardware(encoder) => encoder.last_was_keyframe(),
+        }
+    }
+}
+
+/// Output pipeline selected by [`RecordingConfig::codec`]
+// Only one sink exists per recorder, so the size difference is irrelevant
+#[allow(clippy::large_enum_variant)]
+enum VideoSink {
+    /// RGB frames encoded to H.264 and muxed to MP4
+    H264 {
+        encoder: VideoEncoder,
+        muxer: muxide::api::Muxer<Box<dyn Write + Send>>,
+    },
+    /// RGB frames encoded to H.264 and written as fragmented MP4
+    FragmentedMp4 {
+        encoder: VideoEncoder,
+        fmp4: Fmp4Writer<Box<dyn Write + Send>>,
+    },
+    /// Compressed MJPEG frames stored verbatim in an AVI container
+    MjpegAvi(AviMjpegWriter<Box<dyn SeekWrite + Send>>),
+}
+
+/// Output that can also be seeked, as the AVI writer requires
+trait SeekWrite: Write + Seek {}
+
+impl<T: Write + Seek> SeekWrite for T {}
+
+/// Where a recorder's container bytes go
+enum Output {
+    /// Files and memory buffers: every codec
+    Seekable(Box<dyn SeekWrite + Send>),
+    /// Caller-supplied streams: MP4 only
+    Stream(Box<dyn Write + Send>),
+}
+
+/// Growable in-memory output shared between the muxer and the recorder
+#[derive(Clone, Default)]
+struct MemoryBuffer(Arc<StdMutex<Cursor<Vec<u8>>>>);
+
+impl MemoryBuffer {
+    fn lock(&self) -> std::io::Result<std::sync::MutexGuard<'_, Cursor<Vec<u8>>>> {
+        self.0
+            .lock()
+            .map_err(|_| std::io::Error::other("recording buffer mutex poisoned"))
+    }
+
+    fn take(&self) -> Result<Vec<u8>, CameraError> {
+        let mut cursor = self
+            .lock()
+            .map_err(|e| CameraError::EncodingError(e.to_string()))?;
+        Ok(std::mem::take(cursor.get_mut()))
+    }
+}
+
+impl Write for MemoryBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.lock()?.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for MemoryBuffer {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.lock()?.seek(pos)
+    }
+}
+
+/// Mux one encoded H.264 packet
+fn write_video_packet(
+    muxer: &mut muxide::api::Muxer<Box<dyn Write + Send>>,
+    pts: f64,
+    encoded: &EncodedFrame,
+) -> Result<(), CameraError> {
+    muxer
+        .write_video(pts, &encoded.data, encoded.is_keyframe)
+        .map_err(|e| CameraError::MuxingError(format!("Failed to write frame: {e}")))
+}
+
+/// Check whether a frame format label denotes camera-compressed MJPEG
+fn is_mjpeg_format(format: &str) -> bool {
+    format
+        .parse::<FourCC>()
+        .is_ok_and(|fourcc| fourcc == FourCC::MJPEG)
+}
+
 /// Video recorder that captures frames, encodes to H.264, and muxes to MP4
+/// (or, in MJPEG passthrough mode, stores compressed frames in an AVI file)
 /// Per #`RecorderIntegrateAudio`: ! `supports_audio_optional`
 pub struct Recorder {
-    encoder: H264Encoder,
-    muxer: muxide::api::Muxer<BufWriter<File>>,
+    sink: VideoSink,
     config: RecordingConfig,
     output_path: String,
     frame_count: u64,
@@ -47,6 +356,16 @@ pub struct Recorder {
     start_time: Option<Instant>,
     last_frame_time: Option<Instant>,
     frame_duration_secs: f64,
+    /// Timestamp resequencing, when `reorder_window` is set
+    reorder: Option<ReorderBuffer>,
+    /// Constant frame rate grid, when `constant_framerate` is set
+    cfr: Option<CfrConverter>,
+    /// Data of the last frame written, repeated for skipped grid slots
+    cfr_previous: Option<Vec<u8>>,
+    /// Output buffer of [`Recorder::new_in_memory`]
+    memory: Option<MemoryBuffer>,
+    /// Whether [`Recorder::start`] has run
+    started: bool,
     /// Shared PTS clock for audio/video sync
     #[cfg(feature = "audio")]
     pts_clock: Option<PTSClock>,
@@ -63,6 +382,22 @@ pub struct Recorder {
     /// Per #`AudioErrorRecovery`: ! `error_logged`, ! `session_status_reflects_audio_state`
     #[cfg(feature = "audio")]
     audio_error_flag: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    /// Input device switches made by the audio thread after a device loss
+    #[cfg(feature = "audio")]
+    audio_switches: Arc<StdMutex<Vec<AudioDeviceSwitch>>>,
+    /// Common t=0 on `pts_clock`; both tracks are written relative to it
+    #[cfg(feature = "audio")]
+    av_origin: f64,
+    /// `av_origin` for the audio thread as f64 bits, NaN until the first
+    /// video frame is accepted
+    #[cfg(feature = "audio")]
+    av_origin_shared: Arc<AtomicU64>,
+    /// Timestamp of the first captured audio frame
+    #[cfg(feature = "audio")]
+    first_audio_pts: Option<f64>,
+    /// Clock time the first video frame was offered
+    #[cfg(feature = "audio")]
+    first_video_pts: Option<f64>,
     /// Whether audio is enabled for this recording
     #[cfg(feature = "audio")]
     audio_enabled: bool,
@@ -77,11 +412,13 @@ impl Recorder {
     /// Per #`RecorderIntegrateAudio`: ! `configures_muxer_audio_track_when_enabled`
     ///
     /// # Errors
-    /// Returns `CameraError` if file creation, encoding initialization, or muxer setup fails.
+    /// Returns `CameraError` if file creation, encoding initialization, or muxer setup fails,
+    /// or if audio is requested together with MJPEG passthrough.
     pub fn new<P: AsRef<Path>>(
         output_path: P,
         config: RecordingConfig,
     ) -> Result<Self, CameraError> {
+        Self::check_config(&config)?;
         let output_path_str = output_path.as_ref().to_string_lossy().to_string();
 
         // Create the output file
@@ -89,8 +426,119 @@ impl Recorder {
             .map_err(|e| CameraError::IoError(format!("Failed to create output file: {e}")))?;
         let writer = BufWriter::new(file);
 
+        Self::with_output(Output::Seekable(Box::new(writer)), config, output_path_str)
+    }
+
+    /// Create a recorder that writes the muxed MP4 to any [`Write`]
+    /// (a socket, an upload body, a pipe) instead of a file
+    ///
+    /// Bytes reach `writer` as the muxer emits them. With
+    /// [`RecordingConfig::fast_start`] the muxer has to hold the media data
+    /// until [`finish`](Self::finish) to put the index first, so disable it
+    /// for long recordings that should not be buffered in memory.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::EncodingError`] for MJPEG passthrough, whose
+    /// AVI container needs a seekable output, or any error of
+    /// [`new`](Self::new) other than file creation.
+    pub fn with_writer<W: Write + Send + 'static>(
+        writer: W,
+        config: RecordingConfig,
+    ) -> Result<Self, CameraError> {
+        Self::check_config(&config)?;
+        Self::with_output(Output::Stream(Box::new(writer)), config, String::new())
+    }
+
+    /// Create a recorder that keeps the whole file in memory; retrieve it
+    /// with [`finish_to_vec`](Self::finish_to_vec)
+    ///
+    /// # Errors
+    /// Same as [`new`](Self::new), other than file creation.
+    pub fn new_in_memory(config: RecordingConfig) -> Result<Self, CameraError> {
+        Self::check_config(&config)?;
+        let buffer = MemoryBuffer::default();
+        let mut recorder = Self::with_output(
+            Output::Seekable(Box::new(buffer.clone())),
+            config,
+            String::new(),
+        )?;
+        recorder.memory = Some(buffer);
+        Ok(recorder)
+    }
+
+    /// Reject configurations no output can record
+    fn check_config(config: &RecordingConfig) -> Result<(), CameraError> {
+        #[cfg(feature = "audio")]
+        if config.codec == RecordingCodec::MjpegPassthrough && config.audio.is_some() {
+            return Err(CameraError::EncodingError(
+                "Audio is not supported with MJPEG passthrough recording".to_string(),
+            ));
+        }
+        if let Some(interval) = config.fragment_interval_secs {
+            if !interval.is_finite() || interval <= 0.0 {
+                return Err(CameraError::EncodingError(format!(
+                    "Fragment interval must be a positive number of seconds, got {interval}"
+                )));
+            }
+            if config.codec != RecordingCodec::H264 {
+                return Err(CameraError::EncodingError(
+                    "Fragmented recording requires the H.264 codec".to_string(),
+                ));
+            }
+            #[cfg(feature = "audio")]
+            if config.audio.is_some() {
+                return Err(CameraError::EncodingError(
+                    "Audio is not supported with fragmented recording".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn with_output(
+        output: Output,
+        mut config: RecordingConfig,
+        output_path_str: String,
+    ) -> Result<Self, CameraError> {
+        let frame_duration_secs = 1.0 / config.fps;
+
+        if config.codec == RecordingCodec::MjpegPassthrough {
+            let Output::Seekable(writer) = output else {
+                return Err(CameraError::EncodingError(
+                    "MJPEG passthrough needs a seekable output for its AVI index".to_string(),
+                ));
+            };
+            let avi = AviMjpegWriter::new(writer, config.width, config.height, config.fps)?;
+            return Ok(Self::with_sink(
+                VideoSink::MjpegAvi(avi),
+                config,
+                output_path_str,
+                frame_duration_secs,
+            ));
+        }
+
+        let writer: Box<dyn Write + Send> = match output {
+            Output::Seekable(writer) => Box::new(writer),
+            Output::Stream(writer) => writer,
+        };
+
+        if let Some(interval) = config.fragment_interval_secs {
+            // Fragments begin at keyframes, so keep one due at each boundary
+            if config.keyframe_interval_frames == 0 {
+                config = config.with_keyframe_interval_secs(interval);
+            }
+            let encoder = VideoEncoder::select(&config)?;
+            let fmp4 = Fmp4Writer::new(writer, config.width, config.height, config.fps, interval);
+            return Ok(Self::with_sink(
+                VideoSink::FragmentedMp4 { encoder, fmp4 },
+                config,
+                output_path_str,
+                frame_duration_secs,
+            ));
+        }
+
         // Create the H.264 encoder
-        let encoder = H264Encoder::new(config.width, config.height, config.fps, config.bitrate)?;
+        let encoder = VideoEncoder::select(&config)?;
 
         // Build the muxer with optional metadata
         let mut builder = MuxerBuilder::new(writer)
@@ -118,23 +566,46 @@ impl Recorder {
             .build()
             .map_err(|e| CameraError::MuxingError(format!("Failed to create muxer: {e}")))?;
 
-        let frame_duration_secs = 1.0 / config.fps;
+        Ok(Self::with_sink(
+            VideoSink::H264 { encoder, muxer },
+            config,
+            output_path_str,
+            frame_duration_secs,
+        ))
+    }
 
-        // Audio subsystem is started lazily on first video frame
-        // to ensure video starts first (muxide requirement)
+    fn with_sink(
+        sink: VideoSink,
+        config: RecordingConfig,
+        output_path: String,
+        frame_duration_secs: f64,
+    ) -> Self {
+        // Audio subsystem is started by `start` (or the first video frame);
+        // its packets wait for the first video packet (muxide requirement)
+        #[cfg(feature = "audio")]
+        let audio_config = config.audio.clone();
         #[cfg(feature = "audio")]
         let pts_clock = audio_config.as_ref().map(|_| PTSClock::new());
-
-        Ok(Self {
-            encoder,
-            muxer,
+        let reorder =
+            (config.reorder_window > 0).then(|| ReorderBuffer::new(config.reorder_window));
+        let cfr = config
+            .constant_framerate
+            .then(|| CfrConverter::new(config.fps));
+
+        Self {
+            sink,
             config,
-            output_path: output_path_str,
+            output_path,
             frame_count: 0,
             dropped_frames: 0,
             start_time: None,
             last_frame_time: None,
             frame_duration_secs,
+            reorder,
+            cfr,
+            cfr_previous: None,
+            memory: None,
+            started: false,
             #[cfg(feature = "audio")]
             pts_clock,
             #[cfg(feature = "audio")]
@@ -146,34 +617,155 @@ impl Recorder {
             #[cfg(feature = "audio")]
             audio_error_flag: None,
             #[cfg(feature = "audio")]
+            audio_switches: Arc::default(),
+            #[cfg(feature = "audio")]
+            av_origin: 0.0,
+            #[cfg(feature = "audio")]
+            av_origin_shared: Arc::new(AtomicU64::new(f64::NAN.to_bits())),
+            #[cfg(feature = "audio")]
+            first_audio_pts: None,
+            #[cfg(feature = "audio")]
+            first_video_pts: None,
+            #[cfg(feature = "audio")]
             audio_enabled: audio_config.is_some(),
             #[cfg(feature = "audio")]
             audio_failed: false,
-        })
+        }
+    }
+
+    /// Calculate the PTS for the next video frame
+    /// Per #`AVSyncPolicy`: ! `shared_baseline`, - `dual_clock_sources`
+    /// When audio is enabled, use `PTSClock` for both A/V to ensure sync,
+    /// measured from the common start.
+    /// When video-only, use frame-count based PTS (no sync needed).
+    fn next_video_pts(&self) -> f64 {
+        #[cfg(feature = "audio")]
+        if let Some(ref clock) = self.pts_clock {
+            return clock.pts() - self.av_origin; // Real elapsed time from shared clock
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            self.frame_count as f64 * self.frame_duration_secs
+        }
+    }
+
+    /// Encode (or pass through) one frame and hand it to the container.
+    /// Returns `false` if the encoder produced no data for this frame.
+    fn write_video(&mut self, data: &[u8]) -> Result<bool, CameraError> {
+        self.write_video_at(self.next_video_pts(), data)
+    }
+
+    /// [`write_video`](Self::write_video) at an explicit PTS in seconds
+    /// (ignored by the AVI container, which plays at a constant rate)
+    fn write_video_at(&mut self, pts: f64, data: &[u8]) -> Result<bool, CameraError> {
+        match self.sink {
+            VideoSink::H264 {
+                ref mut encoder,
+                ref mut muxer,
+            } => {
+                let packets = encoder.encode(data, pts)?;
+
+                // Skip empty frames (encoder may return no data for some frames)
+                if packets.is_empty() && !encoder.is_delayed() {
+                    return Ok(false);
+                }
+
+                // Write to muxer (use the keyframe info from the encoder)
+                for (pts, encoded) in packets {
+                    write_video_packet(muxer, pts, &encoded)?;
+                }
+            }
+            VideoSink::FragmentedMp4 {
+                ref mut encoder,
+                ref mut fmp4,
+            } => {
+                let packets = encoder.encode(data, pts)?;
+                if packets.is_empty() && !encoder.is_delayed() {
+                    return Ok(false);
+                }
+                for (pts, encoded) in packets {
+                    fmp4.write_sample(pts, &encoded)?;
+                }
+            }
+            VideoSink::MjpegAvi(ref mut avi) => avi.write_frame(data)?,
+        }
+        Ok(true)
+    }
+
+    /// Start the recording, pre-rolling audio capture before any video is
+    /// accepted
+    ///
+    /// With audio enabled this starts capture and blocks until the first
+    /// audio frame arrives (at most `RECORDING_AV_START_TIMEOUT_MS`). The
+    /// next frame written becomes t=0 of both tracks: audio captured before
+    /// it is trimmed, so neither track starts ahead of the other. Call this
+    /// once the camera is delivering frames; otherwise the first write runs
+    /// it and that frame, stale by the time audio is up, is discarded as
+    /// pre-roll. Does nothing without audio or when already started.
+    pub fn start(&mut self) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+
+        #[cfg(feature = "audio")]
+        if let Some(ready) = self.start_audio_capture() {
+            match ready.recv_timeout(std::time::Duration::from_millis(
+                RECORDING_AV_START_TIMEOUT_MS,
+            )) {
+                Ok(pts) => self.first_audio_pts = Some(pts),
+                Err(crossbeam_channel::RecvTimeoutError::Timeout) => log::warn!(
+                    "No audio within {RECORDING_AV_START_TIMEOUT_MS}ms, starting video without it"
+                ),
+                // Capture failed to start and the thread already reported it
+                Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {}
+            }
+        }
     }
 
-    /// Start audio capture thread (call after first video frame)
+    /// Run the start barrier for the first frame and fix the common t=0.
+    /// Returns `false` if the frame predates the barrier and is discarded.
+    fn sync_start(&mut self) -> bool {
+        #[cfg(feature = "audio")]
+        if let Some(offered) = self.pts_clock.as_ref().map(PTSClock::pts) {
+            self.first_video_pts.get_or_insert(offered);
+        }
+        let implicit = !self.started;
+        self.start();
+
+        #[cfg(feature = "audio")]
+        if let Some(origin) = self.pts_clock.as_ref().map(PTSClock::pts) {
+            // The frame waited for audio to come up, so it is stale
+            if implicit && self.first_audio_pts.is_some() {
+                return false;
+            }
+            self.av_origin = origin;
+            self.av_origin_shared
+                .store(origin.to_bits(), Ordering::SeqCst);
+        }
+        #[cfg(not(feature = "audio"))]
+        let _ = implicit;
+        true
+    }
+
+    /// Start audio capture thread (called by [`Recorder::start`])
     /// Per #`RecorderIntegrateAudio`: ! `continues_video_if_audio_fails`
     /// Per #`AudioErrorRecovery`: ! `error_logged`, - panic, - `silent_data_loss`
-    /// Audio runs in its own thread to avoid Send issues with `cpal::Stream`
+    /// Audio runs in its own thread to avoid Send issues with `cpal::Stream`.
+    /// Returns a channel that receives the first audio timestamp.
     #[cfg(feature = "audio")]
-    fn start_audio_capture(&mut self) {
+    fn start_audio_capture(&mut self) -> Option<crossbeam_channel::Receiver<f64>> {
         use crate::audio::AudioCapture;
-        use std::sync::atomic::{AtomicBool, Ordering};
-        use std::sync::Arc;
+        use std::sync::atomic::AtomicBool;
 
         // Already started or not enabled
         if self.audio_thread.is_some() || !self.audio_enabled {
-            return;
+            return None;
         }
 
-        let Some(ref audio_cfg) = self.config.audio else {
-            return;
-        };
-
-        let Some(ref clock) = self.pts_clock else {
-            return;
-        };
+        let audio_cfg = self.config.audio.as_ref()?;
+        let clock = self.pts_clock.as_ref()?;
 
         // Channel for encoded audio packets
         let (sender, receiver) =
@@ -181,18 +773,25 @@ impl Recorder {
         let stop_flag = Arc::new(AtomicBool::new(false));
         // Per #`AudioErrorRecovery`: ! `session_status_reflects_audio_state`
         let error_flag = Arc::new(AtomicBool::new(false));
+        let (ready_sender, ready_receiver) = crossbeam_channel::bounded::<f64>(1);
 
         let device_id = audio_cfg.device_id.clone();
+        let device_kind = audio_cfg.device_kind;
         let sample_rate = audio_cfg.sample_rate;
         let channels = audio_cfg.channels;
         let bitrate = audio_cfg.bitrate;
+        let auto_fallback = audio_cfg.auto_fallback;
         let clock_clone = clock.clone();
         let stop_clone = stop_flag.clone();
         let error_clone = error_flag.clone();
+        let switches = self.audio_switches.clone();
+        let origin = self.av_origin_shared.clone();
 
         // Spawn audio thread
         // Per #`AudioErrorRecovery`: ! `video_continues_on_audio_failure` (thread errors don't affect video)
         let handle = std::thread::spawn(move || {
+            tune_current_thread(ThreadRole::Encode);
+
             // Helper to set error flag and log
             let report_error = |msg: &str| {
                 log::error!("Audio thread error: {msg}");
@@ -200,14 +799,19 @@ impl Recorder {
             };
 
             // Create capture and encoder in this thread (they stay here)
-            let mut capture =
-                match AudioCapture::new(device_id.as_deref(), sample_rate, channels, clock_clone) {
-                    Ok(c) => c,
-                    Err(e) => {
-                        report_error(&format!("Audio capture init failed: {e}"));
-                        return;
-                    }
-                };
+            let mut capture = match AudioCapture::with_kind(
+                device_id.as_deref(),
+                device_kind,
+                sample_rate,
+                channels,
+                clock_clone,
+            ) {
+                Ok(c) => c.with_auto_fallback(auto_fallback),
+                Err(e) => {
+                    report_error(&format!("Audio capture init failed: {e}"));
+                    return;
+                }
+            };
 
             let mut encoder = match OpusEncoder::new(sample_rate, channels, bitrate) {
                 Ok(e) => e,
@@ -222,21 +826,43 @@ impl Recorder {
                 return;
             }
 
+            // Frames are held until the first video frame fixes t=0
+            let mut pending = VecDeque::new();
+            let mut ready_sender = Some(ready_sender);
+
             // Process audio until stop signal
             while !stop_clone.load(Ordering::Relaxed) {
-                if let Some(frame) = capture.try_read() {
-                    if let Ok(packets) = encoder.encode(&frame) {
-                        for packet in packets {
-                            if sender.try_send(packet).is_err() {
-                                // Channel full, drop packet (not a fatal error)
-                                log::debug!("Audio channel full, dropping packet");
-                            }
+                // A lost device ends the audio track unless it falls back;
+                // video keeps recording either way
+                match capture.poll_device() {
+                    Ok(Some(switch)) => {
+                        if let Ok(mut switches) = switches.lock() {
+                            switches.push(switch);
                         }
                     }
+                    Ok(None) => {}
+                    Err(e) => {
+                        report_error(&e.to_string());
+                        // Encode what was captured before the loss
+                        pending.extend(capture.drain());
+                        encode_pending(&mut pending, &origin, &mut encoder, &sender);
+                        break;
+                    }
+                }
+
+                if let Some(frame) = capture.try_read() {
+                    if let Some(ready) = ready_sender.take() {
+                        let _ = ready.send(frame.timestamp);
+                    }
+                    pending.push_back(frame);
+                    if pending.len() > RECORDING_AUDIO_PREROLL_FRAMES {
+                        pending.pop_front();
+                    }
                 } else {
                     // No audio available, brief sleep to avoid busy-wait
                     std::thread::sleep(std::time::Duration::from_millis(RECORDING_AUDIO_SLEEP_MS));
                 }
+                encode_pending(&mut pending, &origin, &mut encoder, &sender);
             }
 
             // Flush remaining
@@ -254,6 +880,7 @@ impl Recorder {
         self.audio_thread = Some(handle);
         self.audio_error_flag = Some(error_flag);
         self.audio_stop = Some(stop_flag);
+        Some(ready_receiver)
     }
 
     /// Write a camera frame to the recording
@@ -266,20 +893,30 @@ impl Recorder {
     ///
     /// # Errors
     /// Returns `CameraError` if the frame dimensions don't match or encoding/muxing fails.
+    /// In MJPEG passthrough mode, frames whose `format` is not MJPEG are rejected with
+    /// [`CameraError::UnsupportedFormat`].
     pub fn write_frame(&mut self, frame: &CameraFrame) -> Result<(), CameraError> {
+        if matches!(self.sink, VideoSink::MjpegAvi(_)) && !is_mjpeg_format(&frame.format) {
+            return Err(CameraError::UnsupportedFormat(format!(
+                "{} (MJPEG passthrough recording requires MJPEG frames)",
+                frame.format
+            )));
+        }
+
         let now = Instant::now();
 
-        // Initialize start time on first frame and start audio
-        let is_first_frame = self.start_time.is_none();
-        if is_first_frame {
-            self.start_time = Some(now);
-            #[cfg(feature = "audio")]
-            self.start_audio_capture();
+        // Synchronize the start on the first frame
+        if self.start_time.is_none() {
+            if !self.sync_start() {
+                return Ok(());
+            }
+            self.start_time = Some(Instant::now());
         }
 
         // Check if we should drop this frame (frame rate limiting)
         // The 0.8 factor allows some jitter tolerance (frames up to 20% early are accepted)
-        if let Some(last_time) = self.last_frame_time {
+        // A constant frame rate grid drops by timestamp instead
+        if let Some(last_time) = self.last_frame_time.filter(|_| self.cfr.is_none()) {
             let elapsed = now.duration_since(last_time).as_secs_f64();
             if elapsed < self.frame_duration_secs * RECORDING_JITTER_TOLERANCE {
                 // Frame came too fast, skip it
@@ -305,38 +942,18 @@ impl Recorder {
             )));
         }
 
-        // Encode the frame to H.264
-        let encoded = self.encoder.encode_rgb(&frame.data)?;
-
-        // Skip empty frames (encoder may return no data for some frames)
-        if encoded.data.is_empty() {
-            self.dropped_frames += 1;
-            return Ok(());
-        }
-
-        // Calculate PTS
-        // Per #`AVSyncPolicy`: ! `shared_baseline`, - `dual_clock_sources`
-        // When audio is enabled, use PTSClock for both A/V to ensure sync.
-        // When video-only, use frame-count based PTS (no sync needed).
-        #[cfg(feature = "audio")]
-        let pts = if let Some(ref clock) = self.pts_clock {
-            clock.pts() // Real elapsed time from shared clock
-        } else {
-            #[allow(clippy::cast_precision_loss)]
-            {
-                self.frame_count as f64 * self.frame_duration_secs
+        let written = match self.reorder.as_mut().map(|b| b.push(frame.clone())) {
+            Some(ready) => {
+                for frame in &ready {
+                    self.encode_frame(frame)?;
+                }
+                true
             }
+            None => self.encode_frame(frame)?,
         };
-        #[cfg(not(feature = "audio"))]
-        #[allow(clippy::cast_precision_loss)]
-        let pts = self.frame_count as f64 * self.frame_duration_secs;
-
-        // Write to muxer (use the keyframe info from the encoder)
-        self.muxer
-            .write_video(pts, &encoded.data, encoded.is_keyframe)
-            .map_err(|e| CameraError::MuxingError(format!("Failed to write frame: {e}")))?;
-
-        self.frame_count += 1;
+        if !written {
+            return Ok(());
+        }
         self.last_frame_time = Some(now);
 
         // Drain and write audio (non-blocking with bounded buffer)
@@ -346,6 +963,60 @@ impl Recorder {
         Ok(())
     }
 
+    /// Encode the frame to H.264 (or store it as-is in passthrough mode);
+    /// the encoder is 8-bit, so RGBA and high bit depth frames go in as RGB8.
+    /// Returns `false` if the encoder produced no data and the frame was
+    /// counted as dropped, or the constant frame rate grid left it out.
+    fn encode_frame(&mut self, frame: &CameraFrame) -> Result<bool, CameraError> {
+        if self.cfr.is_some() {
+            return self.encode_frame_cfr(frame);
+        }
+        if !self.write_video(&frame.rgb_data())? {
+            self.dropped_frames += 1;
+            return Ok(false);
+        }
+        self.frame_count += 1;
+        Ok(true)
+    }
+
+    /// [`encode_frame`](Self::encode_frame) on the constant frame rate grid:
+    /// the previous frame is repeated for every slot skipped since it, then
+    /// the frame is written at its own slot's PTS
+    fn encode_frame_cfr(&mut self, frame: &CameraFrame) -> Result<bool, CameraError> {
+        let Some(cfr) = self.cfr.as_mut() else {
+            return Ok(false);
+        };
+        let Some(slots) = cfr.place(frame) else {
+            return Ok(false);
+        };
+        let repeat_pts: Vec<f64> = slots.repeat_previous.map(|slot| cfr.pts(slot)).collect();
+        let pts = cfr.pts(slots.current);
+
+        if let Some(previous) = self.cfr_previous.take() {
+            let repeated = repeat_pts.into_iter().try_for_each(|at| {
+                if self.write_video_at(at, &previous)? {
+                    self.frame_count += 1;
+                } else {
+                    self.dropped_frames += 1;
+                }
+                Ok::<(), CameraError>(())
+            });
+            // Kept even if a write failed, for the next frame to repeat
+            self.cfr_previous = Some(previous);
+            repeated?;
+        }
+
+        let data = frame.rgb_data().into_owned();
+        let written = self.write_video_at(pts, &data)?;
+        self.cfr_previous = Some(data);
+        if written {
+            self.frame_count += 1;
+        } else {
+            self.dropped_frames += 1;
+        }
+        Ok(written)
+    }
+
     /// Drain available audio frames and write to muxer (non-blocking)
     /// Per #`RecorderIntegrateAudio`: ! `drains_audio_non_blocking`
     /// Bounded drain: processes at most `MAX_AUDIO_DRAIN_PER_FRAME` packets
@@ -363,13 +1034,26 @@ impl Recorder {
             return;
         };
 
+        // Audio is only available with the H.264/MP4 pipeline, and must
+        // follow the first video packet
+        let VideoSink::H264 {
+            ref mut muxer,
+            ref encoder,
+        } = self.sink
+        else {
+            return;
+        };
+        if !encoder.has_output() {
+            return;
+        }
+
         // Non-blocking drain with bounded iteration
         let mut drained = 0;
         while drained < MAX_AUDIO_DRAIN_PER_FRAME {
             match receiver.try_recv() {
                 Ok(packet) => {
                     // Write to muxer with PTS from audio frame
-                    if let Err(e) = self.muxer.write_audio(packet.timestamp, &packet.data) {
+                    if let Err(e) = muxer.write_audio(packet.timestamp, &packet.data) {
                         log::warn!("Audio write failed (video continues): {e}");
                         self.audio_failed = true;
                         return;
@@ -383,9 +1067,13 @@ impl Recorder {
 
     /// Write raw RGB data as a frame
     ///
+    /// The data carries no timestamp, so it bypasses the
+    /// [`RecordingConfig::constant_framerate`] grid.
+    ///
     /// # Errors
     /// Returns a [`CameraError::EncodingError`] if the supplied frame
-    /// dimensions do not match the recording config, or a
+    /// dimensions do not match the recording config, a
+    /// [`CameraError::UnsupportedFormat`] in MJPEG passthrough mode, or a
     /// [`CameraError::MuxingError`] if encoding or writing the frame fails.
     pub fn write_rgb_frame(
         &mut self,
@@ -393,6 +1081,12 @@ impl Recorder {
         width: u32,
         height: u32,
     ) -> Result<(), CameraError> {
+        if matches!(self.sink, VideoSink::MjpegAvi(_)) {
+            return Err(CameraError::UnsupportedFormat(
+                "RGB (MJPEG passthrough recording requires MJPEG frames)".to_string(),
+            ));
+        }
+
         // Validate dimensions
         if width != self.config.width || height != self.config.height {
             return Err(CameraError::EncodingError(format!(
@@ -403,41 +1097,19 @@ impl Recorder {
 
         let now = Instant::now();
 
-        let is_first_frame = self.start_time.is_none();
-        if is_first_frame {
-            self.start_time = Some(now);
-            #[cfg(feature = "audio")]
-            self.start_audio_capture();
+        if self.start_time.is_none() {
+            if !self.sync_start() {
+                return Ok(());
+            }
+            self.start_time = Some(Instant::now());
         }
 
         // Encode the frame
-        let encoded = self.encoder.encode_rgb(rgb_data)?;
-
-        // Skip empty frames (encoder may return no data for some frames)
-        if encoded.data.is_empty() {
+        if !self.write_video(rgb_data)? {
             self.dropped_frames += 1;
             return Ok(());
         }
 
-        // Calculate PTS - same logic as write_frame
-        // Per #AVSyncPolicy: ! shared_baseline
-        #[cfg(feature = "audio")]
-        let pts = if let Some(ref clock) = self.pts_clock {
-            clock.pts()
-        } else {
-            #[allow(clippy::cast_precision_loss)]
-            {
-                self.frame_count as f64 * self.frame_duration_secs
-            }
-        };
-        #[cfg(not(feature = "audio"))]
-        #[allow(clippy::cast_precision_loss)]
-        let pts = self.frame_count as f64 * self.frame_duration_secs;
-
-        self.muxer
-            .write_video(pts, &encoded.data, encoded.is_keyframe)
-            .map_err(|e| CameraError::MuxingError(format!("Failed to write frame: {e}")))?;
-
         self.frame_count += 1;
         self.last_frame_time = Some(now);
 
@@ -448,26 +1120,272 @@ impl Recorder {
         Ok(())
     }
 
+    /// Mux pre-captured frames into `output_path`, timed by their own
+    /// timestamps instead of their arrival
+    ///
+    /// The first frame is placed at 0 s and every later frame at its offset
+    /// from the first: the hardware timestamp difference when both frames
+    /// carry one, else the [`CameraFrame::timestamp`] difference. No frames
+    /// are rate-limited or reordered. To assemble a time-lapse from stills,
+    /// stamp them at the intended playback spacing. MJPEG passthrough output
+    /// plays at `config.fps` regardless of the timestamps.
+    ///
+    /// # Errors
+    /// Returns the errors of [`Recorder::new`], a
+    /// [`CameraError::EncodingError`] if `video` is empty, a frame's size
+    /// differs from the config or its timestamp does not advance, and any
+    /// encoding or muxing error.
+    pub fn from_sources<P, V>(
+        output_path: P,
+        video: V,
+        config: RecordingConfig,
+    ) -> Result<RecordingStats, CameraError>
+    where
+        P: AsRef<Path>,
+        V: IntoIterator<Item = CameraFrame>,
+    {
+        let mut recorder = Self::new(output_path, config)?;
+        recorder.mux_sources(video, |_, _| Ok(()))?;
+        recorder.finish_offline()
+    }
+
+    /// [`from_sources`](Self::from_sources) with an optional audio track
+    /// muxed from pre-captured PCM frames
+    ///
+    /// Audio timestamps share the video timeline (0 s = first video frame).
+    /// The track takes its sample rate and channel count from the first
+    /// audio frame and its bitrate from `config.audio` (default 128 kbps);
+    /// packets are interleaved with the video frames they precede.
+    ///
+    /// # Errors
+    /// Returns the errors of [`from_sources`](Self::from_sources), a
+    /// [`CameraError::AudioError`] if an audio frame's format changes or
+    /// Opus encoding fails, or a [`CameraError::MuxingError`] if a packet
+    /// cannot be written.
+    #[cfg(feature = "audio")]
+    pub fn from_sources_with_audio<P, V, A>(
+        output_path: P,
+        video: V,
+        audio: Option<A>,
+        mut config: RecordingConfig,
+    ) -> Result<RecordingStats, CameraError>
+    where
+        P: AsRef<Path>,
+        V: IntoIterator<Item = CameraFrame>,
+        A: IntoIterator<Item = AudioFrame>,
+    {
+        let mut audio = audio.map(|a| a.into_iter().peekable());
+        let Some((audio, (sample_rate, channels))) = audio.as_mut().and_then(|frames| {
+            let format = frames.peek().map(|f| (f.sample_rate, f.channels))?;
+            Some((frames, format))
+        }) else {
+            config.audio = None;
+            return Self::from_sources(output_path, video, config);
+        };
+
+        let audio_cfg = config.audio.get_or_insert_with(AudioConfig::default);
+        audio_cfg.sample_rate = sample_rate;
+        audio_cfg.channels = channels;
+        let mut encoder = OpusEncoder::new(sample_rate, channels, audio_cfg.bitrate)?;
+
+        let mut recorder = Self::new(output_path, config)?;
+        // Packets come from the supplied frames, never a live microphone
+        recorder.audio_enabled = false;
+
+        let mut pending = std::collections::VecDeque::new();
+        recorder.mux_sources(video, |recorder, pts| {
+            while let Some(frame) = audio.next_if(|frame| frame.timestamp <= pts) {
+                pending.extend(encoder.encode(&frame)?);
+            }
+            while pending
+                .front()
+                .is_some_and(|packet: &EncodedAudio| packet.timestamp <= pts)
+            {
+                if let Some(packet) = pending.pop_front() {
+                    recorder.write_audio_packet(&packet)?;
+                }
+            }
+            Ok(())
+        })?;
+
+        for frame in audio {
+            pending.extend(encoder.encode(&frame)?);
+        }
+        pending.extend(encoder.flush()?);
+        for packet in pending {
+            recorder.write_audio_packet(&packet)?;
+        }
+        recorder.finish_offline()
+    }
+
+    /// Encode every frame of `video` at its timestamp offset, calling
+    /// `after_frame` with each frame's PTS
+    fn mux_sources<V, F>(&mut self, video: V, mut after_frame: F) -> Result<(), CameraError>
+    where
+        V: IntoIterator<Item = CameraFrame>,
+        F: FnMut(&mut Self, f64) -> Result<(), CameraError>,
+    {
+        let mut origin = None;
+        let mut last_pts: Option<f64> = None;
+        for (index, frame) in video.into_iter().enumerate() {
+            if matches!(self.sink, VideoSink::MjpegAvi(_)) && !is_mjpeg_format(&frame.format) {
+                return Err(CameraError::UnsupportedFormat(format!(
+                    "{} (MJPEG passthrough recording requires MJPEG frames)",
+                    frame.format
+                )));
+            }
+            if frame.width != self.config.width || frame.height != self.config.height {
+                return Err(CameraError::EncodingError(format!(
+                    "Frame {index} is {}x{}, recording config is {}x{}",
+                    frame.width, frame.height, self.config.width, self.config.height
+                )));
+            }
+
+            let first = *origin.get_or_insert((frame.timestamp, frame.metadata.hardware_timestamp));
+            let pts = cfr::offset_secs(first, &frame);
+            if let Some(last) = last_pts.filter(|&last| pts <= last) {
+                return Err(CameraError::EncodingError(format!(
+                    "Frame {index} timestamp {pts:.3}s does not advance past {last:.3}s"
+                )));
+            }
+            last_pts = Some(pts);
+
+            if self.start_time.is_none() {
+                self.start_time = Some(Instant::now());
+            }
+            if self.cfr.is_some() {
+                self.encode_frame_cfr(&frame)?;
+            } else if self.write_video_at(pts, &frame.rgb_data())? {
+                self.frame_count += 1;
+            } else {
+                self.dropped_frames += 1;
+            }
+            after_frame(self, pts)?;
+        }
+
+        if last_pts.is_none() {
+            return Err(CameraError::EncodingError(
+                "No video frames to mux".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Write one encoded audio packet to the MP4 audio track
+    #[cfg(feature = "audio")]
+    fn write_audio_packet(&mut self, packet: &EncodedAudio) -> Result<(), CameraError> {
+        let VideoSink::H264 { ref mut muxer, .. } = self.sink else {
+            return Err(CameraError::MuxingError(
+                "Audio requires the H.264/MP4 pipeline".to_string(),
+            ));
+        };
+        muxer
+            .write_audio(packet.timestamp, &packet.data)
+            .map_err(|e| CameraError::MuxingError(format!("Failed to write audio: {e}")))
+    }
+
+    /// [`finish`](Self::finish) with the achieved frame rate taken from the
+    /// muxed duration rather than wall-clock time
+    fn finish_offline(self) -> Result<RecordingStats, CameraError> {
+        let mut stats = self.finish()?;
+        stats.actual_fps = if stats.duration_secs > 0.0 {
+            #[allow(clippy::cast_precision_loss)]
+            {
+                stats.video_frames as f64 / stats.duration_secs
+            }
+        } else {
+            0.0
+        };
+        Ok(stats)
+    }
+
     /// Finish the recording and return statistics
     ///
     /// # Errors
     /// Returns a [`CameraError::MuxingError`] if the underlying muxer
-    /// cannot be finalized.
-    #[allow(unused_mut)]
+    /// cannot be finalized, or the encoding error of a frame still held
+    /// back for reordering.
     pub fn finish(mut self) -> Result<RecordingStats, CameraError> {
+        // Write frames still held back for reordering
+        let (reordered_frames, late_frames) = match self.reorder.take() {
+            Some(mut buffer) => {
+                for frame in buffer.flush() {
+                    self.encode_frame(&frame)?;
+                }
+                (buffer.reordered(), buffer.late())
+            }
+            None => (0, 0),
+        };
+
+        // Write packets still inside a hardware encoder
+        let encoder_backend = match self.sink {
+            VideoSink::H264 {
+                ref mut encoder,
+                ref mut muxer,
+            } => {
+                for (pts, encoded) in encoder.flush()? {
+                    write_video_packet(muxer, pts, &encoded)?;
+                }
+                encoder.backend()
+            }
+            VideoSink::FragmentedMp4 {
+                ref mut encoder,
+                ref mut fmp4,
+            } => {
+                for (pts, encoded) in encoder.flush()? {
+                    fmp4.write_sample(pts, &encoded)?;
+                }
+                encoder.backend()
+            }
+            VideoSink::MjpegAvi(_) => EncoderBackend::OpenH264,
+        };
+
         // Stop audio capture and flush remaining audio
         #[cfg(feature = "audio")]
         self.finish_audio();
 
-        // Use finish_with_stats() which returns Result<MuxerStats, MuxerError>
-        let muxer_stats = self
-            .muxer
-            .finish_with_stats()
-            .map_err(|e| CameraError::MuxingError(format!("Failed to finalize recording: {e}")))?;
+        let (video_frames, audio_frames, duration_secs, bytes_written, transcoded) = match self.sink
+        {
+            VideoSink::H264 { muxer, .. } => {
+                // Use finish_with_stats() which returns Result<MuxerStats, MuxerError>
+                let muxer_stats = muxer.finish_with_stats().map_err(|e| {
+                    CameraError::MuxingError(format!("Failed to finalize recording: {e}"))
+                })?;
+                (
+                    muxer_stats.video_frames,
+                    muxer_stats.audio_frames,
+                    muxer_stats.duration_secs,
+                    muxer_stats.bytes_written,
+                    true,
+                )
+            }
+            VideoSink::FragmentedMp4 { fmp4, .. } => {
+                let fmp4_stats = fmp4.finish()?;
+                (
+                    fmp4_stats.frames,
+                    0,
+                    fmp4_stats.duration_secs,
+                    fmp4_stats.bytes_written,
+                    true,
+                )
+            }
+            VideoSink::MjpegAvi(avi) => {
+                let avi_stats = avi.finish()?;
+                #[allow(clippy::cast_precision_loss)]
+                let duration_secs = avi_stats.frames as f64 * self.frame_duration_secs;
+                (
+                    avi_stats.frames,
+                    0,
+                    duration_secs,
+                    avi_stats.bytes_written,
+                    false,
+                )
+            }
+        };
 
-        let actual_duration = self.start_time.map_or(muxer_stats.duration_secs, |start| {
-            start.elapsed().as_secs_f64()
-        });
+        let actual_duration = self
+            .start_time
+            .map_or(duration_secs, |start| start.elapsed().as_secs_f64());
 
         let actual_fps = if actual_duration > 0.0 {
             #[allow(clippy::cast_precision_loss)]
@@ -478,22 +1396,89 @@ impl Recorder {
             0.0
         };
 
+        #[cfg(feature = "audio")]
+        let (audio_device_switches, audio_gap_secs) = {
+            let switches = self
+                .audio_switches
+                .lock()
+                .map(|switches| switches.clone())
+                .unwrap_or_default();
+            (
+                u32::try_from(switches.len()).unwrap_or(u32::MAX),
+                switches.iter().map(|s| s.gap_secs).sum(),
+            )
+        };
+        #[cfg(not(feature = "audio"))]
+        let (audio_device_switches, audio_gap_secs) = (0, 0.0);
+
+        #[cfg(feature = "audio")]
+        let av_start_offset_secs = match (self.first_audio_pts, self.first_video_pts) {
+            (Some(audio), Some(video)) => audio - video,
+            _ => 0.0,
+        };
+        #[cfg(not(feature = "audio"))]
+        let av_start_offset_secs = 0.0;
+
+        let (cfr_duplicated_frames, cfr_dropped_frames) = self
+            .cfr
+            .as_ref()
+            .map_or((0, 0), |cfr| (cfr.duplicated(), cfr.dropped()));
+
         Ok(RecordingStats {
-            video_frames: muxer_stats.video_frames,
-            audio_frames: muxer_stats.audio_frames,
-            duration_secs: muxer_stats.duration_secs,
-            bytes_written: muxer_stats.bytes_written,
+            video_frames,
+            audio_frames,
+            duration_secs,
+            bytes_written,
             actual_fps,
             dropped_frames: self.dropped_frames,
             output_path: self.output_path,
+            transcoded,
+            reordered_frames,
+            late_frames,
+            encoder: encoder_backend,
+            audio_device_switches,
+            audio_gap_secs,
+            av_start_offset_secs,
+            cfr_duplicated_frames,
+            cfr_dropped_frames,
         })
     }
 
+    /// Trim a fragmented recording left behind by a crash or power loss so
+    /// it ends at its last complete fragment, and report what was kept
+    ///
+    /// Only applies to files recorded with
+    /// [`RecordingConfig::fragment_interval_secs`]; a regular MP4 without
+    /// its `moov` box cannot be recovered.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::IoError`] if the file cannot be read or
+    /// truncated, or a [`CameraError::MuxingError`] if it is not a
+    /// fragmented MP4 with an intact header.
+    pub fn recover<P: AsRef<Path>>(path: P) -> Result<RecoveryReport, CameraError> {
+        super::fmp4::recover(path.as_ref())
+    }
+
+    /// Finish an in-memory recording and return the file bytes with the
+    /// statistics
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::EncodingError`] if the recorder was not
+    /// created by [`new_in_memory`](Self::new_in_memory), or any error of
+    /// [`finish`](Self::finish).
+    pub fn finish_to_vec(mut self) -> Result<(Vec<u8>, RecordingStats), CameraError> {
+        let memory = self.memory.take().ok_or_else(|| {
+            CameraError::EncodingError(
+                "finish_to_vec requires a recorder from Recorder::new_in_memory".to_string(),
+            )
+        })?;
+        let stats = self.finish()?;
+        Ok((memory.take()?, stats))
+    }
+
     /// Stop audio capture thread and flush remaining audio
     #[cfg(feature = "audio")]
     fn finish_audio(&mut self) {
-        use std::sync::atomic::Ordering;
-
         // Signal audio thread to stop
         if let Some(ref stop) = self.audio_stop {
             stop.store(true, Ordering::Relaxed);
@@ -513,9 +1498,11 @@ impl Recorder {
         }
 
         // Drain any remaining packets from the channel
-        if let Some(ref receiver) = self.audio_receiver {
+        if let (Some(receiver), VideoSink::H264 { muxer, .. }) =
+            (&self.audio_receiver, &mut self.sink)
+        {
             while let Ok(packet) = receiver.try_recv() {
-                if let Err(e) = self.muxer.write_audio(packet.timestamp, &packet.data) {
+                if let Err(e) = muxer.write_audio(packet.timestamp, &packet.data) {
                     log::warn!("Failed to write remaining audio packet in finish: {e}");
                 }
             }
@@ -544,8 +1531,30 @@ impl Recorder {
     }
 
     /// Force the next frame to be a keyframe
+    ///
+    /// No-op in MJPEG passthrough mode, where every frame is a keyframe.
     pub fn force_keyframe(&mut self) {
-        self.encoder.force_keyframe();
+        match self.sink {
+            VideoSink::H264 {
+                ref mut encoder, ..
+            }
+            | VideoSink::FragmentedMp4 {
+                ref mut encoder, ..
+            } => encoder.force_keyframe(),
+            VideoSink::MjpegAvi(_) => {}
+        }
+    }
+
+    /// Check if the last written frame was a keyframe
+    ///
+    /// Always `true` in MJPEG passthrough mode.
+    pub fn last_was_keyframe(&self) -> bool {
+        match self.sink {
+            VideoSink::H264 { ref encoder, .. } | VideoSink::FragmentedMp4 { ref encoder, .. } => {
+                encoder.last_was_keyframe()
+            }
+            VideoSink::MjpegAvi(_) => true,
+        }
     }
 
     /// Check if audio capture has failed
@@ -554,7 +1563,6 @@ impl Recorder {
     pub fn audio_failed(&self) -> bool {
         // Check the shared error flag from audio thread
         if let Some(ref flag) = self.audio_error_flag {
-            use std::sync::atomic::Ordering;
             flag.load(Ordering::SeqCst)
         } else {
             // No flag = audio not started or not enabled
@@ -567,6 +1575,16 @@ impl Recorder {
     pub fn audio_enabled(&self) -> bool {
         self.audio_enabled
     }
+
+    /// Input devices the audio track switched between after a device was
+    /// unplugged (requires [`AudioConfig::auto_fallback`])
+    #[cfg(feature = "audio")]
+    pub fn audio_device_switches(&self) -> Vec<AudioDeviceSwitch> {
+        self.audio_switches
+            .lock()
+            .map(|switches| switches.clone())
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -586,6 +1604,47 @@ mod tests {
         let _ = std::fs::remove_file(&output);
     }
 
+    #[test]
+    fn test_start_is_idempotent_without_audio() {
+        let config = RecordingConfig::new(64, 48, 30.0);
+        let mut recorder = Recorder::new_in_memory(config).expect("Recorder creation failed");
+        recorder.start();
+        recorder.start();
+
+        // An explicit start means the first frame is kept
+        recorder
+            .write_rgb_frame(&vec![128; 64 * 48 * 3], 64, 48)
+            .expect("Frame write should succeed");
+        assert_eq!(recorder.frame_count(), 1);
+
+        let (_, stats) = recorder.finish_to_vec().expect("Finish should succeed");
+        assert!(stats.av_start_offset_secs.abs() < f64::EPSILON);
+    }
+
+    #[cfg(feature = "audio")]
+    #[test]
+    fn test_rebase_audio_frame_trims_to_origin() {
+        let frame = |timestamp| AudioFrame {
+            samples: vec![0.5; 480 * 2],
+            sample_rate: 48_000,
+            channels: 2,
+            timestamp,
+        };
+
+        // Entirely before t=0
+        assert!(rebase_audio_frame(frame(1.0), 1.5).is_none());
+
+        // Straddling t=0: the first 5 ms are cut
+        let trimmed = rebase_audio_frame(frame(1.0), 1.005).expect("frame kept");
+        assert_eq!(trimmed.samples.len(), 240 * 2);
+        assert!(trimmed.timestamp.abs() < 1e-9);
+
+        // After t=0: shifted only
+        let shifted = rebase_audio_frame(frame(2.0), 1.5).expect("frame kept");
+        assert_eq!(shifted.samples.len(), 480 * 2);
+        assert!((shifted.timestamp - 0.5).abs() < 1e-9);
+    }
+
     #[test]
     fn test_record_frames() {
         let output = temp_dir().join("test_frames_recording.mp4");
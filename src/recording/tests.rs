@@ -2,7 +2,12 @@
 
 #[cfg(test)]
 mod recording_tests {
-    use crate::recording::{Recorder, RecordingConfig, RecordingQuality};
+    use crate::errors::CameraError;
+    use crate::recording::{
+        EncoderBackend, EncoderPreference, Recorder, RecordingCodec, RecordingConfig,
+        RecordingQuality,
+    };
+    use crate::types::CameraFrame;
     use std::env::temp_dir;
 
     #[test]
@@ -124,4 +129,385 @@ mod recording_tests {
 
         let _ = std::fs::remove_file(&output);
     }
+
+    fn mjpeg_frame(width: u32, height: u32) -> CameraFrame {
+        let image = image::RgbImage::from_pixel(width, height, image::Rgb([40, 80, 120]));
+        let mut jpeg = Vec::new();
+        image::DynamicImage::ImageRgb8(image)
+            .write_to(
+                &mut std::io::Cursor::new(&mut jpeg),
+                image::ImageFormat::Jpeg,
+            )
+            .expect("JPEG encode should succeed");
+        CameraFrame::new(jpeg, width, height, "passthrough".to_string())
+            .with_format("MJPEG".to_string())
+    }
+
+    #[test]
+    fn test_mjpeg_passthrough_stores_frames_verbatim() {
+        let output = temp_dir().join("test_passthrough.avi");
+        let config = RecordingConfig::new(64, 48, 100.0).mjpeg_passthrough();
+        assert_eq!(config.codec, RecordingCodec::MjpegPassthrough);
+
+        let mut recorder = Recorder::new(&output, config).expect("Failed to create recorder");
+        let frame = mjpeg_frame(64, 48);
+        for _ in 0..5 {
+            recorder.write_frame(&frame).expect("Failed to write frame");
+            // Stay above the frame-rate limiter's minimum interval
+            std::thread::sleep(std::time::Duration::from_millis(12));
+        }
+
+        let stats = recorder.finish().expect("Failed to finish");
+        assert_eq!(stats.video_frames, 5);
+        assert!(!stats.transcoded, "passthrough must not transcode");
+
+        let file = std::fs::read(&output).expect("AVI should exist");
+        assert_eq!(stats.bytes_written, file.len() as u64);
+        assert_eq!(&file[0..4], b"RIFF");
+        assert_eq!(&file[8..12], b"AVI ");
+        // The first chunk carries the original JPEG bytes untouched
+        assert_eq!(&file[232..232 + frame.data.len()], frame.data.as_slice());
+
+        let _ = std::fs::remove_file(&output);
+    }
+
+    #[test]
+    fn test_fragmented_recording_recovers_after_truncation() {
+        let output = temp_dir().join("test_fragmented.mp4");
+        let config = RecordingConfig::new(320, 240, 15.0).with_fragment_interval(0.5);
+        let mut recorder = Recorder::new(&output, config).expect("Failed to create recorder");
+        for i in 0..30u8 {
+            let rgb = vec![i * 8; 320 * 240 * 3];
+            recorder
+                .write_rgb_frame(&rgb, 320, 240)
+                .expect("Failed to write frame");
+        }
+        let stats = recorder.finish().expect("Failed to finish");
+        assert_eq!(stats.video_frames, 30);
+
+        // Keyframes every 8 frames (0.5 s at 15 fps) give four fragments
+        let bytes = std::fs::read(&output).expect("fMP4 should exist");
+        assert_eq!(bytes.windows(4).filter(|w| *w == b"moof").count(), 4);
+
+        // Simulate a crash partway through writing the last fragment
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .open(&output)
+            .expect("open");
+        file.set_len(bytes.len() as u64 - 10).expect("truncate");
+        drop(file);
+
+        let report = Recorder::recover(&output).expect("Failed to recover");
+        assert_eq!(report.fragments, 3);
+        assert_eq!(report.video_frames, 24);
+        assert!((report.duration_secs - 1.6).abs() < 0.01);
+        assert!(report.bytes_discarded > 0);
+        let recovered_len = std::fs::metadata(&output).expect("metadata").len();
+        assert_eq!(recovered_len, report.bytes_kept);
+
+        // Recovering an intact file changes nothing
+        let again = Recorder::recover(&output).expect("Failed to recover");
+        assert_eq!(again.bytes_discarded, 0);
+        assert_eq!(again.fragments, 3);
+
+        let _ = std::fs::remove_file(&output);
+    }
+
+    #[test]
+    fn test_fragment_interval_is_validated() {
+        let output = temp_dir().join("test_fragment_invalid.mp4");
+        let config = RecordingConfig::new(320, 240, 15.0).with_fragment_interval(0.0);
+        assert!(Recorder::new(&output, config).is_err());
+        let config = RecordingConfig::new(64, 48, 15.0)
+            .mjpeg_passthrough()
+            .with_fragment_interval(1.0);
+        assert!(Recorder::new(&output, config).is_err());
+        let _ = std::fs::remove_file(&output);
+    }
+
+    #[test]
+    fn test_in_memory_recording_produces_mp4() {
+        let config = RecordingConfig::new(320, 240, 15.0);
+        let mut recorder = Recorder::new_in_memory(config).expect("Failed to create recorder");
+        for _ in 0..10 {
+            let rgb = vec![100u8; 320 * 240 * 3];
+            recorder
+                .write_rgb_frame(&rgb, 320, 240)
+                .expect("Failed to write frame");
+        }
+
+        let (bytes, stats) = recorder.finish_to_vec().expect("Failed to finish");
+        assert_eq!(stats.video_frames, 10);
+        assert!(stats.output_path.is_empty());
+
+        // An MP4 starts with the `ftyp` box; fast start puts `moov` before `mdat`
+        assert_eq!(&bytes[4..8], b"ftyp");
+        let find = |tag: &[u8]| bytes.windows(4).position(|w| w == tag);
+        let (moov, mdat) = (find(b"moov"), find(b"mdat"));
+        assert!(moov.is_some() && mdat.is_some());
+        assert!(moov < mdat, "moov should precede mdat");
+
+        let output = temp_dir().join("test_not_in_memory.mp4");
+        let file_recorder = Recorder::new(&output, RecordingConfig::new(320, 240, 15.0))
+            .expect("Failed to create recorder");
+        assert!(file_recorder.finish_to_vec().is_err());
+        let _ = std::fs::remove_file(&output);
+    }
+
+    #[test]
+    fn test_recording_to_streaming_writer() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        struct Counting(Arc<AtomicUsize>);
+        impl std::io::Write for Counting {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.fetch_add(buf.len(), Ordering::SeqCst);
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let written = Arc::new(AtomicUsize::new(0));
+        let config = RecordingConfig::new(320, 240, 15.0).with_fast_start(false);
+        let mut recorder = Recorder::with_writer(Counting(Arc::clone(&written)), config)
+            .expect("Failed to create recorder");
+        for _ in 0..5 {
+            let rgb = vec![100u8; 320 * 240 * 3];
+            recorder
+                .write_rgb_frame(&rgb, 320, 240)
+                .expect("Failed to write frame");
+        }
+        let stats = recorder.finish().expect("Failed to finish");
+        assert_eq!(stats.video_frames, 5);
+        assert!(written.load(Ordering::SeqCst) > 0);
+
+        // AVI needs to seek back to patch its header
+        let config = RecordingConfig::new(64, 48, 30.0).mjpeg_passthrough();
+        assert!(matches!(
+            Recorder::with_writer(Counting(Arc::clone(&written)), config),
+            Err(CameraError::EncodingError(_))
+        ));
+    }
+
+    #[test]
+    fn test_encoder_preference_selects_backend() {
+        let output = temp_dir().join("test_software_encoder.mp4");
+        let config = RecordingConfig::new(320, 240, 15.0)
+            .with_encoder_preference(EncoderPreference::Software);
+        let mut recorder = Recorder::new(&output, config).expect("Failed to create recorder");
+        let rgb = vec![100u8; 320 * 240 * 3];
+        recorder
+            .write_rgb_frame(&rgb, 320, 240)
+            .expect("Failed to write frame");
+        let stats = recorder.finish().expect("Failed to finish");
+        assert_eq!(stats.encoder, EncoderBackend::OpenH264);
+        assert!(!stats.encoder.is_hardware());
+        let _ = std::fs::remove_file(&output);
+
+        // Without the feature there is no hardware encoder to require
+        #[cfg(not(feature = "hwenc"))]
+        {
+            let config = RecordingConfig::new(320, 240, 15.0)
+                .with_encoder_preference(EncoderPreference::Hardware);
+            assert!(matches!(
+                Recorder::new(&output, config),
+                Err(CameraError::EncodingError(_))
+            ));
+            let _ = std::fs::remove_file(&output);
+        }
+    }
+
+    #[test]
+    fn test_keyframe_interval_sets_gop_size() {
+        let output = temp_dir().join("test_gop.mp4");
+        let config = RecordingConfig::new(320, 240, 15.0).with_keyframe_interval(4);
+        assert_eq!(config.keyframe_interval_frames, 4);
+        let mut recorder = Recorder::new(&output, config).expect("Failed to create recorder");
+
+        let rgb = vec![100u8; 320 * 240 * 3];
+        let mut keyframes = Vec::new();
+        for i in 0..10 {
+            recorder
+                .write_rgb_frame(&rgb, 320, 240)
+                .expect("Failed to write frame");
+            if recorder.last_was_keyframe() {
+                keyframes.push(i);
+            }
+        }
+        assert_eq!(keyframes, [0, 4, 8]);
+
+        recorder.finish().expect("Failed to finish");
+        let _ = std::fs::remove_file(&output);
+
+        let config = RecordingConfig::new(320, 240, 30.0).with_keyframe_interval_secs(2.0);
+        assert_eq!(config.keyframe_interval_frames, 60);
+    }
+
+    #[test]
+    fn test_reorder_window_writes_frames_in_timestamp_order() {
+        let output = temp_dir().join("test_reorder.avi");
+        let config = RecordingConfig::new(64, 48, 100.0)
+            .mjpeg_passthrough()
+            .with_reorder_window(1);
+        let mut recorder = Recorder::new(&output, config).expect("Failed to create recorder");
+
+        // Payload byte n marks the frame stamped n seconds; 0.5 arrives too late
+        for (payload, stamp) in [(1u8, 1.0), (3, 3.0), (2, 2.0), (0, 0.5)] {
+            let mut frame = CameraFrame::new(vec![payload; 4], 64, 48, "reorder".to_string())
+                .with_format("MJPEG".to_string());
+            frame.metadata.hardware_timestamp = Some(stamp);
+            recorder.write_frame(&frame).expect("Failed to write frame");
+            std::thread::sleep(std::time::Duration::from_millis(12));
+        }
+
+        let stats = recorder.finish().expect("Failed to finish");
+        assert_eq!(stats.video_frames, 3);
+        assert_eq!(stats.reordered_frames, 1);
+        assert_eq!(stats.late_frames, 1);
+
+        // Each chunk is an 8-byte header followed by the 4-byte payload
+        let file = std::fs::read(&output).expect("AVI should exist");
+        let payloads: Vec<u8> = (0..3).map(|i| file[232 + i * 12]).collect();
+        assert_eq!(payloads, [1, 2, 3]);
+
+        let _ = std::fs::remove_file(&output);
+    }
+
+    #[test]
+    fn test_constant_framerate_drops_and_duplicates_by_timestamp() {
+        let output = temp_dir().join("test_constant_framerate.avi");
+        let config = RecordingConfig::new(64, 48, 10.0)
+            .mjpeg_passthrough()
+            .with_constant_framerate(true);
+        let mut recorder = Recorder::new(&output, config).expect("Failed to create recorder");
+
+        // Written back to back: arrival-time rate limiting would drop most
+        for (payload, stamp) in [(1u8, 0.0), (2, 0.1), (3, 0.12), (4, 0.38), (5, 0.4)] {
+            let mut frame = CameraFrame::new(vec![payload; 4], 64, 48, "cfr".to_string())
+                .with_format("MJPEG".to_string());
+            frame.metadata.hardware_timestamp = Some(stamp);
+            recorder.write_frame(&frame).expect("Failed to write frame");
+        }
+
+        let stats = recorder.finish().expect("Failed to finish");
+        assert_eq!(stats.video_frames, 5);
+        assert_eq!(stats.cfr_duplicated_frames, 2);
+        assert_eq!(stats.cfr_dropped_frames, 2);
+        assert_eq!(stats.dropped_frames, 0);
+
+        // Frame 2 fills the slots at 0.2s and 0.3s that no frame landed in
+        let file = std::fs::read(&output).expect("AVI should exist");
+        let payloads: Vec<u8> = (0..5).map(|i| file[232 + i * 12]).collect();
+        assert_eq!(payloads, [1, 2, 2, 2, 4]);
+
+        let _ = std::fs::remove_file(&output);
+    }
+
+    #[test]
+    fn test_mjpeg_passthrough_rejects_other_formats() {
+        let output = temp_dir().join("test_passthrough_reject.avi");
+        let config = RecordingConfig::new(64, 48, 30.0).mjpeg_passthrough();
+        let mut recorder = Recorder::new(&output, config).expect("Failed to create recorder");
+
+        let rgb = CameraFrame::new(vec![0u8; 64 * 48 * 3], 64, 48, "passthrough".to_string())
+            .with_format("RGB8".to_string());
+        let err = recorder
+            .write_frame(&rgb)
+            .expect_err("RGB frame should be rejected");
+        assert!(matches!(err, CameraError::UnsupportedFormat(_)));
+
+        let err = recorder
+            .write_rgb_frame(&rgb.data, 64, 48)
+            .expect_err("RGB data should be rejected");
+        assert!(matches!(err, CameraError::UnsupportedFormat(_)));
+
+        let _ = std::fs::remove_file(&output);
+    }
+
+    /// `count` gradient frames stamped `1 / fps` apart
+    fn timed_frames(count: u32, fps: u32) -> Vec<CameraFrame> {
+        let start = chrono::Utc::now();
+        (0..count)
+            .map(|i| {
+                let mut frame = crate::testing::gradient_frame(320, 240);
+                frame.timestamp =
+                    start + chrono::Duration::microseconds(i64::from(i * 1_000_000 / fps));
+                frame
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_from_sources_muxes_frames_by_timestamp() {
+        let output = temp_dir().join("test_from_sources.mp4");
+        let config = RecordingConfig::new(320, 240, 15.0);
+
+        let stats = Recorder::from_sources(&output, timed_frames(15, 15), config)
+            .expect("offline mux should succeed");
+        assert_eq!(stats.video_frames, 15);
+        assert!(
+            (0.9..=1.1).contains(&stats.duration_secs),
+            "15 frames at 15 fps last about a second, got {}",
+            stats.duration_secs
+        );
+        assert!((stats.actual_fps - 15.0).abs() < 2.0);
+        assert!(std::fs::metadata(&output).is_ok_and(|m| m.len() > 0));
+
+        let _ = std::fs::remove_file(&output);
+    }
+
+    #[test]
+    fn test_from_sources_rejects_empty_and_backwards_video() {
+        let output = temp_dir().join("test_from_sources_invalid.mp4");
+        let config = RecordingConfig::new(320, 240, 15.0);
+
+        let err = Recorder::from_sources(&output, Vec::new(), config.clone())
+            .expect_err("no frames to mux");
+        assert!(matches!(err, CameraError::EncodingError(_)));
+
+        let mut frames = timed_frames(3, 15);
+        frames.swap(1, 2);
+        let err =
+            Recorder::from_sources(&output, frames, config).expect_err("timestamps go backwards");
+        assert!(err.to_string().contains("does not advance"));
+
+        let _ = std::fs::remove_file(&output);
+    }
+
+    #[cfg(feature = "audio")]
+    #[test]
+    fn test_from_sources_with_audio_muxes_both_tracks() {
+        let output = temp_dir().join("test_from_sources_av.mp4");
+        let config = RecordingConfig::new(320, 240, 15.0);
+        // 1 s of 48 kHz stereo in 20 ms frames
+        let audio = (0..50).map(|n| crate::testing::synthetic_audio_frame(n, 960));
+
+        let stats =
+            Recorder::from_sources_with_audio(&output, timed_frames(15, 15), Some(audio), config)
+                .expect("offline A/V mux should succeed");
+        assert_eq!(stats.video_frames, 15);
+        assert!(stats.audio_frames > 0);
+
+        let _ = std::fs::remove_file(&output);
+    }
+
+    #[test]
+    fn test_h264_recording_reports_transcoding() {
+        let output = temp_dir().join("test_transcoded.mp4");
+        let config = RecordingConfig::new(320, 240, 15.0);
+        assert_eq!(config.codec, RecordingCodec::H264);
+
+        let mut recorder = Recorder::new(&output, config).expect("Failed to create recorder");
+        let rgb = vec![100u8; 320 * 240 * 3];
+        recorder
+            .write_rgb_frame(&rgb, 320, 240)
+            .expect("Failed to write frame");
+        let stats = recorder.finish().expect("Failed to finish");
+        assert!(stats.transcoded);
+
+        let _ = std::fs::remove_file(&output);
+    }
 }
@@ -0,0 +1,8 @@
+//! Network streaming outputs
+//!
+//! Servers that publish a live camera to standard clients (NVRs, media
+//! players) rather than to the Tauri frontend.
+
+pub mod rtsp;
+
+pub use rtsp::RtspServer;
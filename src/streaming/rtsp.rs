@@ -0,0 +1,994 @@
+//! Minimal RTSP server publishing H.264 over RTP (`rtsp` feature)
+//!
+//! Serves a single live stream at `rtsp://<host>:<port>/<path>` for NVRs,
+//! Home Assistant, VLC or `ffplay`. Frames are captured once, encoded with the
+//! recording pipeline's [`H264Encoder`], and fanned out to every playing
+//! client; each client gets its own RTP sequence numbers and SSRC.
+//!
+//! Supported: `OPTIONS`, `DESCRIBE` (SDP with `sprop-parameter-sets` once the
+//! encoder has produced SPS/PPS), `SETUP` over UDP unicast or TCP interleaved
+//! (`RTP/AVP/TCP`), `PLAY`, `GET_PARAMETER` keep-alives and `TEARDOWN`. H.264
+//! is packetized per RFC 6184 (single NAL units and FU-A fragments). There is
+//! no RTCP, authentication or encryption, so only expose the port on trusted
+//! networks.
+
+use std::fmt::Write as _;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+use tokio::sync::{broadcast, Mutex as AsyncMutex};
+use tokio_util::sync::CancellationToken;
+
+use crate::constants::{
+    RTP_MAX_PAYLOAD, RTP_PAYLOAD_TYPE_H264, RTSP_CHANNEL_CAPACITY, RTSP_MAX_BODY_BYTES,
+    RTSP_MAX_LINE_BYTES, RTSP_SESSION_TIMEOUT_SECS, VIDEO_BITRATE_SD,
+};
+use crate::errors::CameraError;
+use crate::platform::PlatformCamera;
+use crate::recording::H264Encoder;
+
+/// One encoded picture: its NAL units (without start codes) and RTP timestamp
+#[derive(Debug, Clone)]
+struct AccessUnit {
+    nals: Vec<Bytes>,
+    timestamp: u32,
+}
+
+/// Stream state shared between the encoder task and client sessions
+struct Shared {
+    path: String,
+    fps: f32,
+    /// Latest SPS and PPS, advertised in the SDP
+    parameter_sets: StdMutex<Option<(Bytes, Bytes)>>,
+    keyframe_requested: AtomicBool,
+}
+
+/// A running RTSP server; stops when dropped or on [`RtspServer::stop`]
+pub struct RtspServer {
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+    cancel: CancellationToken,
+    units: broadcast::Sender<Arc<AccessUnit>>,
+}
+
+impl RtspServer {
+    /// Bind `addr` and serve `camera` as H.264 at `path` (e.g. `"live"`)
+    ///
+    /// Use port `0` to let the OS choose; [`RtspServer::url`] reports the
+    /// actual address.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::ConnectionError`] if the address cannot be bound.
+    pub async fn start(
+        camera: Arc<StdMutex<PlatformCamera>>,
+        addr: SocketAddr,
+        path: &str,
+        fps: f32,
+    ) -> Result<Self, CameraError> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| CameraError::ConnectionError(format!("Failed to bind {addr}: {e}")))?;
+        let addr = listener.local_addr().map_err(|e| {
+            CameraError::ConnectionError(format!("Failed to read bound address: {e}"))
+        })?;
+
+        let shared = Arc::new(Shared {
+            path: format!("/{}", path.trim_matches('/')),
+            fps: if fps > 0.0 { fps } else { 30.0 },
+            parameter_sets: StdMutex::new(None),
+            keyframe_requested: AtomicBool::new(false),
+        });
+        let (units, _) = broadcast::channel(RTSP_CHANNEL_CAPACITY);
+        let cancel = CancellationToken::new();
+
+        tokio::spawn(run_encoder(
+            camera,
+            shared.clone(),
+            units.clone(),
+            cancel.clone(),
+        ));
+        tokio::spawn(run_accept(
+            listener,
+            shared.clone(),
+            units.clone(),
+            cancel.clone(),
+        ));
+
+        let server = Self {
+            addr,
+            shared,
+            cancel,
+            units,
+        };
+        log::info!("RTSP server listening on {}", server.url());
+        Ok(server)
+    }
+
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// `rtsp://` URL of the stream
+    ///
+    /// A wildcard bind address is reported as `127.0.0.1`; clients on other
+    /// machines should substitute this host's network address.
+    pub fn url(&self) -> String {
+        let host = if self.addr.ip().is_unspecified() {
+            IpAddr::from([127, 0, 0, 1])
+        } else {
+            self.addr.ip()
+        };
+        format!(
+            "rtsp://{}{}",
+            SocketAddr::new(host, self.addr.port()),
+            self.shared.path
+        )
+    }
+
+    /// Number of clients currently playing
+    pub fn client_count(&self) -> usize {
+        self.units.receiver_count()
+    }
+
+    /// Stop encoding, end all sessions and release the port
+    pub fn stop(&self) {
+        self.cancel.cancel();
+    }
+}
+
+impl Drop for RtspServer {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
+
+/// Capture and encode while at least one client is playing
+async fn run_encoder(
+    camera: Arc<StdMutex<PlatformCamera>>,
+    shared: Arc<Shared>,
+    units: broadcast::Sender<Arc<AccessUnit>>,
+    cancel: CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(Duration::from_secs_f32(1.0 / shared.fps));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let started = Instant::now();
+    let mut encoder: Option<(H264Encoder, (u32, u32))> = None;
+
+    loop {
+        tokio::select! {
+            () = cancel.cancelled() => break,
+            _ = ticker.tick() => {}
+        }
+        if units.receiver_count() == 0 {
+            continue;
+        }
+
+        let camera = camera.clone();
+        let fps = shared.fps;
+        let force_keyframe = shared.keyframe_requested.swap(false, Ordering::Relaxed);
+        let mut current = encoder.take();
+        let joined = tokio::task::spawn_blocking(move || {
+            let result = encode_next(&camera, &mut current, fps, force_keyframe);
+            (current, result)
+        })
+        .await;
+
+        let outcome = match joined {
+            Ok((enc, result)) => {
+                encoder = enc;
+                result
+            }
+            Err(e) => Err(format!("encoder task failed: {e}")),
+        };
+        let data = match outcome {
+            Ok(data) => data,
+            Err(e) => {
+                log::debug!("RTSP server skipped a frame: {e}");
+                continue;
+            }
+        };
+
+        let nals: Vec<Bytes> = split_annex_b(&data)
+            .into_iter()
+            .map(Bytes::copy_from_slice)
+            .collect();
+        remember_parameter_sets(&shared, &nals);
+
+        let _ = units.send(Arc::new(AccessUnit {
+            nals,
+            timestamp: rtp_timestamp(started.elapsed()),
+        }));
+    }
+}
+
+/// Capture one frame and encode it, (re)creating the encoder on size changes
+fn encode_next(
+    camera: &StdMutex<PlatformCamera>,
+    encoder: &mut Option<(H264Encoder, (u32, u32))>,
+    fps: f32,
+    force_keyframe: bool,
+) -> Result<Vec<u8>, String> {
+    let frame = camera
+        .lock()
+        .map_err(|_| "Mutex poisoned".to_string())?
+        .capture_frame()
+        .map_err(|e| e.to_string())?;
+
+    let size = (frame.width, frame.height);
+    let encoder = match encoder {
+        Some((enc, encoded_size)) if *encoded_size == size => enc,
+        slot => {
+            let enc = H264Encoder::new(frame.width, frame.height, f64::from(fps), VIDEO_BITRATE_SD)
+                .map_err(|e| e.to_string())?;
+            &mut slot.insert((enc, size)).0
+        }
+    };
+    if force_keyframe {
+        encoder.force_keyframe();
+    }
+    encoder
+        .encode_rgb(&frame.data)
+        .map(|encoded| encoded.data)
+        .map_err(|e| e.to_string())
+}
+
+fn remember_parameter_sets(shared: &Shared, nals: &[Bytes]) {
+    let find = |nal_type: u8| {
+        nals.iter()
+            .find(|n| n.first().map(|b| b & 0x1F) == Some(nal_type))
+    };
+    if let (Some(sps), Some(pps)) = (find(7), find(8)) {
+        if let Ok(mut sets) = shared.parameter_sets.lock() {
+            *sets = Some((sps.clone(), pps.clone()));
+        }
+    }
+}
+
+/// 90 kHz RTP clock; wraps around every ~13 hours as RTP expects
+#[allow(clippy::cast_possible_truncation)] // wrapping is the RTP timestamp semantics
+fn rtp_timestamp(elapsed: Duration) -> u32 {
+    (elapsed.as_micros() * 9 / 100) as u32
+}
+
+async fn run_accept(
+    listener: TcpListener,
+    shared: Arc<Shared>,
+    units: broadcast::Sender<Arc<AccessUnit>>,
+    cancel: CancellationToken,
+) {
+    loop {
+        let (socket, peer) = tokio::select! {
+            () = cancel.cancelled() => break,
+            accepted = listener.accept() => match accepted {
+                Ok(conn) => conn,
+                Err(e) => {
+                    log::warn!("RTSP accept failed: {e}");
+                    continue;
+                }
+            },
+        };
+
+        let session = Session::new(
+            socket,
+            peer,
+            shared.clone(),
+            units.clone(),
+            cancel.child_token(),
+        );
+        tokio::spawn(async move {
+            if let Err(e) = session.run().await {
+                log::debug!("RTSP client {peer} disconnected: {e}");
+            }
+        });
+    }
+}
+
+/// Where a session's RTP packets go
+enum Transport {
+    Interleaved {
+        channel: u8,
+    },
+    Udp {
+        socket: Arc<UdpSocket>,
+        dest: SocketAddr,
+    },
+}
+
+/// One RTSP client connection
+struct Session {
+    reader: BufReader<OwnedReadHalf>,
+    writer: Arc<AsyncMutex<OwnedWriteHalf>>,
+    peer: SocketAddr,
+    local: SocketAddr,
+    id: String,
+    shared: Arc<Shared>,
+    units: broadcast::Sender<Arc<AccessUnit>>,
+    transport: Option<Arc<Transport>>,
+    cancel: CancellationToken,
+}
+
+/// A parsed RTSP request
+#[derive(Debug, Default)]
+struct Request {
+    method: String,
+    uri: String,
+    cseq: String,
+    transport: Option<String>,
+    /// `Content-Length` over [`RTSP_MAX_BODY_BYTES`]; the body was not read
+    body_too_large: bool,
+}
+
+impl Session {
+    fn new(
+        socket: TcpStream,
+        peer: SocketAddr,
+        shared: Arc<Shared>,
+        units: broadcast::Sender<Arc<AccessUnit>>,
+        cancel: CancellationToken,
+    ) -> Self {
+        let local = socket
+            .local_addr()
+            .unwrap_or_else(|_| SocketAddr::from(([0, 0, 0, 0], 0)));
+        let (read, write) = socket.into_split();
+        Self {
+            reader: BufReader::new(read),
+            writer: Arc::new(AsyncMutex::new(write)),
+            peer,
+            local,
+            id: format!("{:016X}", random_u64()),
+            shared,
+            units,
+            transport: None,
+            cancel,
+        }
+    }
+
+    async fn run(mut self) -> std::io::Result<()> {
+        let result = self.serve().await;
+        // Ends the RTP task of this session, if playing
+        self.cancel.cancel();
+        result
+    }
+
+    async fn serve(&mut self) -> std::io::Result<()> {
+        loop {
+            let request = tokio::select! {
+                () = self.cancel.cancelled() => return Ok(()),
+                request = read_request(&mut self.reader) => request?,
+            };
+            let Some(request) = request else {
+                return Ok(());
+            };
+
+            let teardown = request.method == "TEARDOWN";
+            let response = if request.body_too_large {
+                response(&request.cseq, "413 Request Entity Too Large", "", "")
+            } else {
+                self.handle(&request).await
+            };
+            self.writer
+                .lock()
+                .await
+                .write_all(response.as_bytes())
+                .await?;
+            // The unread body would be parsed as the next request
+            if teardown || request.body_too_large {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn handle(&mut self, request: &Request) -> String {
+        let path_ok = uri_path(&request.uri).starts_with(&self.shared.path);
+        match request.method.as_str() {
+            "OPTIONS" => response(
+                &request.cseq,
+                "200 OK",
+                "Public: OPTIONS, DESCRIBE, SETUP, PLAY, GET_PARAMETER, TEARDOWN\r\n",
+                "",
+            ),
+            "DESCRIBE" if path_ok => {
+                let sdp = self.sdp();
+                let base = request.uri.trim_end_matches('/');
+                response(
+                    &request.cseq,
+                    "200 OK",
+                    &format!("Content-Base: {base}/\r\nContent-Type: application/sdp\r\n"),
+                    &sdp,
+                )
+            }
+            "SETUP" if path_ok => self.setup(request).await,
+            "PLAY" if self.transport.is_some() => {
+                self.play();
+                response(
+                    &request.cseq,
+                    "200 OK",
+                    &format!("Session: {}\r\nRange: npt=0.000-\r\n", self.id),
+                    "",
+                )
+            }
+            "PLAY" => response(&request.cseq, "455 Method Not Valid in This State", "", ""),
+            "GET_PARAMETER" | "TEARDOWN" => response(
+                &request.cseq,
+                "200 OK",
+                &format!("Session: {}\r\n", self.id),
+                "",
+            ),
+            "DESCRIBE" | "SETUP" => response(&request.cseq, "404 Not Found", "", ""),
+            _ => response(&request.cseq, "501 Not Implemented", "", ""),
+        }
+    }
+
+    async fn setup(&mut self, request: &Request) -> String {
+        let spec = request.transport.as_deref().unwrap_or_default();
+        let session = format!(
+            "Session: {};timeout={RTSP_SESSION_TIMEOUT_SECS}\r\n",
+            self.id
+        );
+
+        if spec.contains("RTP/AVP/TCP") {
+            let channel = transport_param(spec, "interleaved")
+                .and_then(|(first, _)| u8::try_from(first).ok())
+                .unwrap_or(0);
+            self.transport = Some(Arc::new(Transport::Interleaved { channel }));
+            let header = format!(
+                "Transport: RTP/AVP/TCP;unicast;interleaved={channel}-{}\r\n{session}",
+                channel.saturating_add(1)
+            );
+            return response(&request.cseq, "200 OK", &header, "");
+        }
+
+        let Some((rtp_port, control_port)) = transport_param(spec, "client_port") else {
+            return response(&request.cseq, "461 Unsupported Transport", "", "");
+        };
+        let socket = match UdpSocket::bind(SocketAddr::new(self.local.ip(), 0)).await {
+            Ok(socket) => socket,
+            Err(e) => {
+                log::warn!("RTSP UDP bind failed: {e}");
+                return response(&request.cseq, "500 Internal Server Error", "", "");
+            }
+        };
+        let server_port = socket.local_addr().map_or(0, |a| a.port());
+        let dest = SocketAddr::new(self.peer.ip(), u16::try_from(rtp_port).unwrap_or(0));
+        self.transport = Some(Arc::new(Transport::Udp {
+            socket: Arc::new(socket),
+            dest,
+        }));
+
+        let header = format!(
+            "Transport: RTP/AVP;unicast;client_port={rtp_port}-{control_port};server_port={server_port}-{}\r\n{session}",
+            server_port.saturating_add(1)
+        );
+        response(&request.cseq, "200 OK", &header, "")
+    }
+
+    /// Start sending RTP for this session
+    fn play(&self) {
+        let Some(transport) = self.transport.clone() else {
+            return;
+        };
+        let mut units = self.units.subscribe();
+        // New viewers need SPS/PPS and an IDR frame to start decoding
+        self.shared
+            .keyframe_requested
+            .store(true, Ordering::Relaxed);
+
+        let writer = self.writer.clone();
+        let cancel = self.cancel.clone();
+        let mut packetizer = RtpPacketizer::new(random_u64());
+        tokio::spawn(async move {
+            loop {
+                let unit = tokio::select! {
+                    () = cancel.cancelled() => break,
+                    received = units.recv() => match received {
+                        Ok(unit) => unit,
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                };
+
+                for packet in packetizer.packetize(&unit) {
+                    let sent = match transport.as_ref() {
+                        Transport::Interleaved { channel } => {
+                            let mut frame = Vec::with_capacity(packet.len() + 4);
+                            frame.push(b'$');
+                            frame.push(*channel);
+                            frame.extend_from_slice(
+                                &u16::try_from(packet.len())
+                                    .unwrap_or(u16::MAX)
+                                    .to_be_bytes(),
+                            );
+                            frame.extend_from_slice(&packet);
+                            writer.lock().await.write_all(&frame).await
+                        }
+                        Transport::Udp { socket, dest } => {
+                            socket.send_to(&packet, dest).await.map(|_| ())
+                        }
+                    };
+                    if sent.is_err() {
+                        cancel.cancel();
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    fn sdp(&self) -> String {
+        let fmtp = match self
+            .shared
+            .parameter_sets
+            .lock()
+            .ok()
+            .and_then(|s| s.clone())
+        {
+            Some((sps, pps)) => {
+                let profile = sps.get(1..4).map_or_else(|| "42e01f".to_string(), hex);
+                format!(
+                    "packetization-mode=1;profile-level-id={profile};sprop-parameter-sets={},{}",
+                    base64(&sps),
+                    base64(&pps)
+                )
+            }
+            None => "packetization-mode=1;profile-level-id=42e01f".to_string(),
+        };
+
+        format!(
+            "v=0\r\n\
+             o=- {id} 1 IN IP4 {ip}\r\n\
+             s=CrabCamera\r\n\
+             c=IN IP4 0.0.0.0\r\n\
+             t=0 0\r\n\
+             a=control:*\r\n\
+             a=range:npt=0-\r\n\
+             m=video 0 RTP/AVP {pt}\r\n\
+             a=rtpmap:{pt} H264/90000\r\n\
+             a=fmtp:{pt} {fmtp}\r\n\
+             a=framerate:{fps}\r\n\
+             a=control:trackID=0\r\n",
+            id = u64::from_str_radix(&self.id, 16).unwrap_or(0),
+            ip = self.local.ip(),
+            pt = RTP_PAYLOAD_TYPE_H264,
+            fps = self.shared.fps,
+        )
+    }
+}
+
+fn response(cseq: &str, status: &str, headers: &str, body: &str) -> String {
+    let mut out = format!("RTSP/1.0 {status}\r\nCSeq: {cseq}\r\nServer: CrabCamera\r\n{headers}");
+    if !body.is_empty() {
+        let _ = write!(out, "Content-Length: {}\r\n", body.len());
+    }
+    out.push_str("\r\n");
+    out.push_str(body);
+    out
+}
+
+/// Read the next RTSP request, skipping interleaved RTCP from the client
+async fn read_request(reader: &mut BufReader<OwnedReadHalf>) -> std::io::Result<Option<Request>> {
+    loop {
+        let buffered = reader.fill_buf().await?;
+        let Some(&first) = buffered.first() else {
+            return Ok(None);
+        };
+        if first != b'$' {
+            break;
+        }
+        let mut header = [0u8; 4];
+        reader.read_exact(&mut header).await?;
+        let len = u16::from_be_bytes([header[2], header[3]]);
+        let mut skip = vec![0u8; usize::from(len)];
+        reader.read_exact(&mut skip).await?;
+    }
+
+    let mut request = Request::default();
+    let mut content_length = 0usize;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let limit = RTSP_MAX_LINE_BYTES as u64;
+        if (&mut *reader).take(limit).read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        if !line.ends_with('\n') && line.len() as u64 >= limit {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("RTSP request line longer than {RTSP_MAX_LINE_BYTES} bytes"),
+            ));
+        }
+        let trimmed = line.trim_end();
+        if trimmed.is_empty() {
+            if request.method.is_empty() {
+                continue;
+            }
+            break;
+        }
+        if request.method.is_empty() {
+            let mut parts = trimmed.split_whitespace();
+            request.method = parts.next().unwrap_or_default().to_string();
+            request.uri = parts.next().unwrap_or_default().to_string();
+            continue;
+        }
+        if let Some((name, value)) = trimmed.split_once(':') {
+            let value = value.trim();
+            match name.trim().to_ascii_lowercase().as_str() {
+                "cseq" => request.cseq = value.to_string(),
+                "transport" => request.transport = Some(value.to_string()),
+                "content-length" => content_length = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+    }
+
+    if content_length > RTSP_MAX_BODY_BYTES {
+        request.body_too_large = true;
+    } else if content_length > 0 {
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).await?;
+    }
+    Ok(Some(request))
+}
+
+/// Path component of an `rtsp://host:port/path` URI (or a bare path)
+fn uri_path(uri: &str) -> &str {
+    let without_scheme = uri.split_once("://").map_or(uri, |(_, rest)| rest);
+    match without_scheme.find('/') {
+        Some(i) if uri.contains("://") => &without_scheme[i..],
+        _ if uri.starts_with('/') => uri,
+        _ => "/",
+    }
+}
+
+/// Parse a `name=a-b` parameter from an RTSP `Transport` header
+fn transport_param(spec: &str, name: &str) -> Option<(u32, u32)> {
+    spec.split(';').find_map(|part| {
+        let (key, value) = part.trim().split_once('=')?;
+        if key != name {
+            return None;
+        }
+        let (first, second) = value.split_once('-').unwrap_or((value, ""));
+        let first: u32 = first.parse().ok()?;
+        Some((first, second.parse().unwrap_or(first + 1)))
+    })
+}
+
+/// Split an Annex B byte stream into NAL units without start codes
+fn split_annex_b(data: &[u8]) -> Vec<&[u8]> {
+    let mut nals = Vec::new();
+    let mut start = None;
+    let mut i = 0;
+    while i + 2 < data.len() {
+        if data[i] == 0 && data[i + 1] == 0 && data[i + 2] == 1 {
+            if let Some(s) = start {
+                // A 4-byte start code leaves a trailing zero on the previous NAL
+                let mut end = i;
+                while end > s && data[end - 1] == 0 {
+                    end -= 1;
+                }
+                nals.push(&data[s..end]);
+            }
+            i += 3;
+            start = Some(i);
+        } else {
+            i += 1;
+        }
+    }
+    if let Some(s) = start {
+        if s < data.len() {
+            nals.push(&data[s..]);
+        }
+    }
+    nals.retain(|nal| !nal.is_empty());
+    nals
+}
+
+/// RFC 6184 packetizer keeping per-session sequence state
+struct RtpPacketizer {
+    ssrc: u32,
+    sequence: u16,
+}
+
+impl RtpPacketizer {
+    #[allow(clippy::cast_possible_truncation)] // any 32/16 bits of randomness will do
+    fn new(seed: u64) -> Self {
+        Self {
+            ssrc: seed as u32,
+            sequence: (seed >> 32) as u16,
+        }
+    }
+
+    fn packetize(&mut self, unit: &AccessUnit) -> Vec<Vec<u8>> {
+        let mut packets = Vec::new();
+        for (index, nal) in unit.nals.iter().enumerate() {
+            let last_nal = index + 1 == unit.nals.len();
+            if nal.len() <= RTP_MAX_PAYLOAD {
+                packets.push(self.packet(unit.timestamp, last_nal, nal));
+                continue;
+            }
+
+            // FU-A: indicator keeps F/NRI with type 28, header carries the NAL type
+            let indicator = (nal[0] & 0xE0) | 0x1C;
+            let nal_type = nal[0] & 0x1F;
+            let chunks: Vec<&[u8]> = nal[1..].chunks(RTP_MAX_PAYLOAD - 2).collect();
+            for (i, chunk) in chunks.iter().enumerate() {
+                let first = i == 0;
+                let last = i + 1 == chunks.len();
+                let header = nal_type | if first { 0x80 } else { 0 } | if last { 0x40 } else { 0 };
+                let mut payload = Vec::with_capacity(chunk.len() + 2);
+                payload.push(indicator);
+                payload.push(header);
+                payload.extend_from_slice(chunk);
+                packets.push(self.packet(unit.timestamp, last_nal && last, &payload));
+            }
+        }
+        packets
+    }
+
+    fn packet(&mut self, timestamp: u32, marker: bool, payload: &[u8]) -> Vec<u8> {
+        let mut packet = Vec::with_capacity(payload.len() + 12);
+        packet.push(0x80); // version 2, no padding/extension/CSRC
+        packet.push(RTP_PAYLOAD_TYPE_H264 | if marker { 0x80 } else { 0 });
+        packet.extend_from_slice(&self.sequence.to_be_bytes());
+        packet.extend_from_slice(&timestamp.to_be_bytes());
+        packet.extend_from_slice(&self.ssrc.to_be_bytes());
+        packet.extend_from_slice(payload);
+        self.sequence = self.sequence.wrapping_add(1);
+        packet
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, b| {
+        let _ = write!(out, "{b:02x}");
+        out
+    })
+}
+
+fn random_u64() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+    std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish()
+}
+
+/// Standard base64 with padding, for `sprop-parameter-sets`
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for (i, shift) in [18, 12, 6, 0].into_iter().enumerate() {
+            if i <= chunk.len() {
+                out.push(char::from(ALPHABET[((n >> shift) & 0x3F) as usize]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CameraFormat, CameraInitParams};
+
+    #[test]
+    fn test_split_annex_b_handles_3_and_4_byte_start_codes() {
+        let data = [
+            0, 0, 0, 1, 0x67, 1, 2, 0, 0, 1, 0x68, 3, 0, 0, 0, 1, 0x65, 4, 5,
+        ];
+        let nals = split_annex_b(&data);
+        assert_eq!(
+            nals,
+            vec![&[0x67, 1, 2][..], &[0x68, 3][..], &[0x65, 4, 5][..]]
+        );
+        assert!(split_annex_b(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_packetizer_single_nal_and_fu_a() {
+        let small = Bytes::from_static(&[0x67, 1, 2, 3]);
+        let mut large = vec![0x65];
+        large.extend(std::iter::repeat_n(0xAB, RTP_MAX_PAYLOAD * 2));
+        let unit = AccessUnit {
+            nals: vec![small, Bytes::from(large)],
+            timestamp: 9000,
+        };
+
+        let mut packetizer = RtpPacketizer::new(1 << 32);
+        let packets = packetizer.packetize(&unit);
+        assert_eq!(packets.len(), 4, "1 single NAL + 3 FU-A fragments");
+
+        // Single NAL: no marker, payload is the NAL itself
+        assert_eq!(packets[0][0], 0x80);
+        assert_eq!(packets[0][1], RTP_PAYLOAD_TYPE_H264);
+        assert_eq!(&packets[0][12..], &[0x67, 1, 2, 3]);
+        assert_eq!(&packets[0][4..8], &9000u32.to_be_bytes());
+
+        // FU-A start / middle / end, marker only on the last packet
+        assert_eq!(packets[1][12] & 0x1F, 28);
+        assert_eq!(packets[1][13], 0x80 | 0x05);
+        assert_eq!(packets[2][13], 0x05);
+        assert_eq!(packets[3][13], 0x40 | 0x05);
+        assert_eq!(packets[3][1], 0x80 | RTP_PAYLOAD_TYPE_H264);
+
+        let seqs: Vec<u16> = packets
+            .iter()
+            .map(|p| u16::from_be_bytes([p[2], p[3]]))
+            .collect();
+        assert_eq!(seqs, vec![1, 2, 3, 4]);
+        assert!(packets.iter().all(|p| p.len() <= RTP_MAX_PAYLOAD + 12));
+    }
+
+    #[test]
+    fn test_base64_and_transport_parsing() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(&[0x67, 0x42, 0xE0, 0x1F]), "Z0LgHw==");
+
+        assert_eq!(
+            transport_param("RTP/AVP;unicast;client_port=5000-5001", "client_port"),
+            Some((5000, 5001))
+        );
+        assert_eq!(
+            transport_param("RTP/AVP/TCP;unicast;interleaved=0-1", "interleaved"),
+            Some((0, 1))
+        );
+        assert_eq!(transport_param("RTP/AVP;multicast", "client_port"), None);
+
+        assert_eq!(
+            uri_path("rtsp://127.0.0.1:8554/live/trackID=0"),
+            "/live/trackID=0"
+        );
+        assert_eq!(uri_path("rtsp://host"), "/");
+        assert_eq!(uri_path("*"), "/");
+    }
+
+    async fn request(stream: &mut TcpStream, text: &str) -> String {
+        stream.write_all(text.as_bytes()).await.expect("write");
+        let mut reader = BufReader::new(stream);
+        let mut head = String::new();
+        let mut line = String::new();
+        let mut content_length = 0;
+        loop {
+            line.clear();
+            reader.read_line(&mut line).await.expect("read");
+            if line == "\r\n" || line.is_empty() {
+                break;
+            }
+            if let Some(v) = line.strip_prefix("Content-Length: ") {
+                content_length = v.trim().parse().expect("length");
+            }
+            head.push_str(&line);
+        }
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).await.expect("body");
+        head + &String::from_utf8_lossy(&body)
+    }
+
+    #[tokio::test]
+    async fn test_rtsp_session_over_tcp_delivers_rtp() {
+        let params =
+            CameraInitParams::new("rtsp-test".to_string()).with_format(CameraFormat::low());
+        let camera = Arc::new(StdMutex::new(PlatformCamera::new(params).expect("mock")));
+        let server = RtspServer::start(camera, "127.0.0.1:0".parse().expect("addr"), "live", 30.0)
+            .await
+            .expect("server starts");
+        let url = server.url();
+        assert!(url.starts_with("rtsp://127.0.0.1:") && url.ends_with("/live"));
+
+        let mut stream = TcpStream::connect(server.local_addr())
+            .await
+            .expect("connect");
+
+        let options = request(
+            &mut stream,
+            &format!("OPTIONS {url} RTSP/1.0\r\nCSeq: 1\r\n\r\n"),
+        )
+        .await;
+        assert!(options.starts_with("RTSP/1.0 200 OK") && options.contains("CSeq: 1"));
+
+        let describe = request(
+            &mut stream,
+            &format!("DESCRIBE {url} RTSP/1.0\r\nCSeq: 2\r\n\r\n"),
+        )
+        .await;
+        assert!(describe.contains("application/sdp"));
+        assert!(describe.contains("a=rtpmap:96 H264/90000"));
+        assert!(describe.contains("packetization-mode=1"));
+
+        let missing = request(
+            &mut stream,
+            &format!(
+                "DESCRIBE {}/other RTSP/1.0\r\nCSeq: 3\r\n\r\n",
+                url.trim_end_matches("/live")
+            ),
+        )
+        .await;
+        assert!(missing.starts_with("RTSP/1.0 404"));
+
+        let setup = request(
+            &mut stream,
+            &format!(
+                "SETUP {url}/trackID=0 RTSP/1.0\r\nCSeq: 4\r\nTransport: RTP/AVP/TCP;unicast;interleaved=0-1\r\n\r\n"
+            ),
+        )
+        .await;
+        assert!(setup.contains("interleaved=0-1"));
+        let session = setup
+            .lines()
+            .find_map(|l| l.strip_prefix("Session: "))
+            .and_then(|s| s.split(';').next())
+            .expect("session id")
+            .to_string();
+
+        let play = request(
+            &mut stream,
+            &format!("PLAY {url} RTSP/1.0\r\nCSeq: 5\r\nSession: {session}\r\n\r\n"),
+        )
+        .await;
+        assert!(play.starts_with("RTSP/1.0 200 OK"));
+
+        let mut header = [0u8; 4];
+        tokio::time::timeout(Duration::from_secs(10), stream.read_exact(&mut header))
+            .await
+            .expect("RTP arrives")
+            .expect("read");
+        assert_eq!(header[0], b'$');
+        assert_eq!(header[1], 0);
+        let mut rtp = vec![0u8; usize::from(u16::from_be_bytes([header[2], header[3]]))];
+        stream.read_exact(&mut rtp).await.expect("packet");
+        assert_eq!(rtp[0] >> 6, 2, "RTP version 2");
+        assert_eq!(rtp[1] & 0x7F, RTP_PAYLOAD_TYPE_H264);
+
+        server.stop();
+    }
+
+    #[tokio::test]
+    async fn test_rtsp_rejects_oversized_body() {
+        let params = CameraInitParams::new("rtsp-413".to_string()).with_format(CameraFormat::low());
+        let camera = Arc::new(StdMutex::new(PlatformCamera::new(params).expect("mock")));
+        let server = RtspServer::start(camera, "127.0.0.1:0".parse().expect("addr"), "live", 30.0)
+            .await
+            .expect("server starts");
+        let url = server.url();
+
+        let mut stream = TcpStream::connect(server.local_addr())
+            .await
+            .expect("connect");
+        let reply = request(
+            &mut stream,
+            &format!(
+                "GET_PARAMETER {url} RTSP/1.0\r\nCSeq: 1\r\nContent-Length: {}\r\n\r\n",
+                RTSP_MAX_BODY_BYTES + 1
+            ),
+        )
+        .await;
+        assert!(reply.starts_with("RTSP/1.0 413") && reply.contains("CSeq: 1"));
+
+        // The connection is closed rather than parsing the body as requests
+        let mut rest = Vec::new();
+        tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut rest))
+            .await
+            .expect("connection closes")
+            .expect("read");
+        assert!(rest.is_empty());
+
+        server.stop();
+    }
+}
@@ -1,11 +1,20 @@
 //! Testing utilities for CrabCamera
 //!
 //! Provides synthetic test data based on real hardware captures
-//! from OBSBOT Tiny 4K camera and microphone.
+//! from OBSBOT Tiny 4K camera and microphone, and broadcast-style test
+//! patterns for calibrating displays and encoders.
 
+pub mod patterns;
 pub mod synthetic_data;
 
-pub use synthetic_data::{synthetic_video_frame, ObsbotCharacteristics};
+pub use patterns::{focus_chart, resolution_wedge, smpte_bars};
+
+pub use synthetic_data::{
+    color_bars_frame, gradient_frame, synthetic_video_frame, synthetic_video_frame_seeded,
+    ObsbotCharacteristics, COLOR_BARS,
+};
 
 #[cfg(feature = "audio")]
-pub use synthetic_data::synthetic_audio_frame;
+pub use synthetic_data::{
+    sine_sweep_audio_frame, synthetic_audio_frame, synthetic_audio_frame_seeded,
+};
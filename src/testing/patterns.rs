@@ -0,0 +1,255 @@
+//! Broadcast-style test patterns
+//!
+//! Calibration frames at any resolution, for checking that displays,
+//! encoders and streams reproduce levels, colors and detail end to end.
+//! Every pattern is RGB8 and depends only on the requested size.
+
+use crate::types::CameraFrame;
+
+/// Studio-range (16-235) RGB values used by [`smpte_bars`]
+pub mod smpte {
+    /// 75% bars of the top section, left to right: gray, yellow, cyan,
+    /// green, magenta, red, blue
+    pub const BARS: [[u8; 3]; 7] = [
+        [180, 180, 180],
+        [180, 180, 16],
+        [16, 180, 180],
+        [16, 180, 16],
+        [180, 16, 180],
+        [180, 16, 16],
+        [16, 16, 180],
+    ];
+    /// Reverse castellation strip below the bars
+    pub const CASTELLATIONS: [[u8; 3]; 7] = [
+        [16, 16, 180],
+        BLACK,
+        [180, 16, 180],
+        BLACK,
+        [16, 180, 180],
+        BLACK,
+        [180, 180, 180],
+    ];
+    /// -I chroma reference
+    pub const MINUS_I: [u8; 3] = [16, 70, 106];
+    /// 100% white
+    pub const WHITE: [u8; 3] = [235, 235, 235];
+    /// +Q chroma reference
+    pub const PLUS_Q: [u8; 3] = [72, 16, 118];
+    /// Reference black
+    pub const BLACK: [u8; 3] = [16, 16, 16];
+    /// PLUGE strip 4% below black
+    pub const SUB_BLACK: [u8; 3] = [7, 7, 7];
+    /// PLUGE strip 4% above black
+    pub const SUPER_BLACK: [u8; 3] = [25, 25, 25];
+}
+
+/// Spokes of each Siemens star in [`focus_chart`]
+pub const FOCUS_CHART_SPOKES: u32 = 36;
+
+/// Period in pixels of the coarsest lines of [`resolution_wedge`]
+pub const WEDGE_START_PERIOD: f64 = 32.0;
+
+/// SMPTE color bars (SMPTE EG 1 layout)
+///
+/// The top two thirds hold seven 75% bars, then a thin strip of reverse
+/// castellations, and the bottom quarter -I, 100% white, +Q, black and the
+/// PLUGE strips (4% below black, black, 4% above black). Values are studio
+/// range, so a correct display shows black at 16 and white at 235; see
+/// [`smpte`] for the exact colors.
+#[must_use]
+pub fn smpte_bars(width: u32, height: u32) -> CameraFrame {
+    let w = u64::from(width.max(1));
+    let h = u64::from(height.max(1));
+    render(width, height, "synthetic_smpte_bars", |x, y| {
+        let (x, y) = (u64::from(x), u64::from(y));
+        let bar = usize::try_from(x * 7 / w).unwrap_or(6);
+        if y * 3 < h * 2 {
+            smpte::BARS[bar]
+        } else if y * 4 < h * 3 {
+            smpte::CASTELLATIONS[bar]
+        } else {
+            // Twelfths of a bar: four 5/4-bar patches, three 1/3-bar PLUGE
+            // strips, then one bar of black
+            match x * 84 / w {
+                0..=14 => smpte::MINUS_I,
+                15..=29 => smpte::WHITE,
+                30..=44 => smpte::PLUS_Q,
+                60..=63 => smpte::SUB_BLACK,
+                68..=71 => smpte::SUPER_BLACK,
+                _ => smpte::BLACK,
+            }
+        }
+    })
+}
+
+/// Focus chart of Siemens stars on mid gray
+///
+/// A large star fills the center and a small one sits in each corner, so
+/// focus can be judged across the field. Each star has
+/// [`FOCUS_CHART_SPOKES`] black and white spokes whose detail increases
+/// toward its center.
+#[must_use]
+pub fn focus_chart(width: u32, height: u32) -> CameraFrame {
+    let (w, h) = (f64::from(width), f64::from(height));
+    let short = w.min(h);
+    let big = short * 0.45;
+    let small = short / 8.0;
+    let stars = [
+        (w / 2.0, h / 2.0, big),
+        (small * 1.25, small * 1.25, small),
+        (w - small * 1.25, small * 1.25, small),
+        (small * 1.25, h - small * 1.25, small),
+        (w - small * 1.25, h - small * 1.25, small),
+    ];
+
+    render(width, height, "synthetic_focus_chart", |x, y| {
+        let (px, py) = (f64::from(x) + 0.5, f64::from(y) + 0.5);
+        for &(cx, cy, radius) in &stars {
+            let (dx, dy) = (px - cx, py - cy);
+            if dx.hypot(dy) <= radius {
+                let turn = (dy.atan2(dx) / std::f64::consts::TAU).rem_euclid(1.0);
+                let spoke = (turn * f64::from(FOCUS_CHART_SPOKES * 2)).floor();
+                return if spoke.rem_euclid(2.0) < 1.0 {
+                    [255; 3]
+                } else {
+                    [0; 3]
+                };
+            }
+        }
+        [128; 3]
+    })
+}
+
+/// Resolution wedge of black and white lines that get finer across the
+/// frame
+///
+/// The top half has vertical lines, testing horizontal resolution; their
+/// period shrinks from [`WEDGE_START_PERIOD`] pixels at the left edge to 2
+/// pixels (the Nyquist limit) at the right. The bottom half has horizontal
+/// lines, testing vertical resolution, shrinking the same way from the
+/// bottom edge up to the middle. Where a pipeline loses detail, the lines
+/// blur into gray or alias into moiré.
+#[must_use]
+pub fn resolution_wedge(width: u32, height: u32) -> CameraFrame {
+    let half = height / 2;
+    render(width, height, "synthetic_resolution_wedge", |x, y| {
+        let lit = if y < half {
+            chirp_lit(x, width)
+        } else {
+            chirp_lit(height - 1 - y, height - half)
+        };
+        if lit {
+            [255; 3]
+        } else {
+            [0; 3]
+        }
+    })
+}
+
+/// Whether position `at` of a linear chirp over `span` pixels is white
+///
+/// The frequency rises linearly from 1 / [`WEDGE_START_PERIOD`] to 0.5
+/// cycles per pixel.
+fn chirp_lit(at: u32, span: u32) -> bool {
+    let start = 1.0 / WEDGE_START_PERIOD;
+    let end = 0.5;
+    let t = f64::from(at);
+    let phase = start.mul_add(t, (end - start) * t * t / (2.0 * f64::from(span.max(1))));
+    phase.fract() < 0.5
+}
+
+/// Frame whose pixel at `(x, y)` is `pixel(x, y)`
+fn render<F>(width: u32, height: u32, device_id: &str, pixel: F) -> CameraFrame
+where
+    F: Fn(u32, u32) -> [u8; 3],
+{
+    let data = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .flat_map(|(x, y)| pixel(x, y))
+        .collect();
+    CameraFrame::new(data, width, height, device_id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(frame: &CameraFrame, x: u32, y: u32) -> [u8; 3] {
+        let at = ((y * frame.width + x) * 3) as usize;
+        [frame.data[at], frame.data[at + 1], frame.data[at + 2]]
+    }
+
+    #[test]
+    fn test_patterns_are_rgb8_at_requested_size() {
+        for frame in [
+            smpte_bars(320, 180),
+            focus_chart(320, 180),
+            resolution_wedge(320, 180),
+        ] {
+            assert_eq!((frame.width, frame.height), (320, 180));
+            assert_eq!(frame.data.len(), 320 * 180 * 3);
+            assert_eq!(frame.format, "RGB8");
+        }
+        assert!(smpte_bars(0, 0).data.is_empty());
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn test_smpte_bars_layout() {
+        let bars = smpte_bars(840, 480);
+        for (i, color) in smpte::BARS.iter().enumerate() {
+            assert_eq!(pixel(&bars, i as u32 * 120 + 60, 100), *color);
+        }
+        assert_eq!(pixel(&bars, 60, 340), smpte::CASTELLATIONS[0]);
+        assert_eq!(pixel(&bars, 180, 340), smpte::BLACK);
+        assert_eq!(pixel(&bars, 10, 470), smpte::MINUS_I);
+        assert_eq!(pixel(&bars, 200, 470), smpte::WHITE);
+        assert_eq!(pixel(&bars, 350, 470), smpte::PLUS_Q);
+        assert_eq!(pixel(&bars, 610, 470), smpte::SUB_BLACK);
+        assert_eq!(pixel(&bars, 650, 470), smpte::BLACK);
+        assert_eq!(pixel(&bars, 690, 470), smpte::SUPER_BLACK);
+        assert_eq!(pixel(&bars, 800, 470), smpte::BLACK);
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn test_focus_chart_has_spokes_and_gray_field() {
+        let chart = focus_chart(400, 300);
+        assert_eq!(pixel(&chart, 200, 1), [128; 3], "between the stars");
+
+        // Walking around the center star crosses every spoke
+        let mut transitions = 0;
+        let mut previous = None;
+        for step in 0..720 {
+            let angle = f64::from(step) / 720.0 * std::f64::consts::TAU;
+            let x = (200.0 + 100.0 * angle.cos()) as u32;
+            let y = (150.0 + 100.0 * angle.sin()) as u32;
+            let value = pixel(&chart, x, y)[0];
+            assert!(value == 0 || value == 255);
+            if previous.is_some_and(|p| p != value) {
+                transitions += 1;
+            }
+            previous = Some(value);
+        }
+        assert!(transitions >= FOCUS_CHART_SPOKES as usize * 2 - 2);
+    }
+
+    #[test]
+    fn test_resolution_wedge_gets_finer() {
+        let wedge = resolution_wedge(512, 200);
+        let runs = |range: std::ops::Range<u32>| {
+            range
+                .clone()
+                .zip(range.skip(1))
+                .filter(|&(a, b)| pixel(&wedge, a, 10) != pixel(&wedge, b, 10))
+                .count()
+        };
+        // Coarse at the left, close to one line per pixel at the right
+        assert!(runs(0..64) <= 8);
+        assert!(runs(448..511) >= 40);
+
+        // The bottom half repeats the chirp vertically from the bottom edge
+        assert_eq!(pixel(&wedge, 5, 199), [255; 3]);
+        assert_eq!(pixel(&wedge, 5, 186), [0; 3]);
+    }
+}
@@ -70,6 +70,153 @@ pub fn synthetic_audio_frame(frame_number: u64, samples_per_frame: usize) -> Aud
     }
 }
 
+/// Sample rate of every synthetic audio frame (Hz)
+#[cfg(feature = "audio")]
+const SYNTHETIC_SAMPLE_RATE: u32 = 48000;
+
+/// `SplitMix64` generator: tiny, fast and fully determined by its seed
+///
+/// Streams are part of the seeded generators' contract, so the algorithm
+/// must not change.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Create a frame of pseudo-random RGB noise determined by `seed`
+///
+/// The same `(width, height, seed)` always yields the same bytes, on every
+/// platform, so property tests can fuzz the pipeline reproducibly.
+#[must_use]
+pub fn synthetic_video_frame_seeded(width: u32, height: u32, seed: u64) -> CameraFrame {
+    let mut rng = SplitMix64(seed);
+    let mut data = vec![0u8; width as usize * height as usize * 3];
+    for chunk in data.chunks_mut(8) {
+        let bytes = rng.next_u64().to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+
+    CameraFrame::new(data, width, height, "synthetic_seeded".to_string())
+}
+
+/// Create a stereo 48kHz audio frame of pseudo-random noise determined by `seed`
+///
+/// Samples are uniform in `[-0.3, 0.3)`, independent per channel, with a
+/// timestamp of `0.0`. The same `(samples_per_frame, seed)` always yields
+/// the same samples.
+#[cfg(feature = "audio")]
+#[must_use]
+pub fn synthetic_audio_frame_seeded(samples_per_frame: usize, seed: u64) -> AudioFrame {
+    let mut rng = SplitMix64(seed);
+    let samples = (0..samples_per_frame * 2)
+        .map(|_| {
+            // Top 24 bits give an exactly representable f32 in [0, 1)
+            #[allow(clippy::cast_precision_loss)] // 24 bits fit the f32 mantissa
+            let unit = (rng.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+            unit.mul_add(0.6, -0.3)
+        })
+        .collect();
+
+    AudioFrame {
+        samples,
+        sample_rate: SYNTHETIC_SAMPLE_RATE,
+        channels: 2,
+        timestamp: 0.0,
+    }
+}
+
+/// RGB values of the bars drawn by [`color_bars_frame`], left to right
+pub const COLOR_BARS: [[u8; 3]; 8] = [
+    [255, 255, 255], // white
+    [255, 255, 0],   // yellow
+    [0, 255, 255],   // cyan
+    [0, 255, 0],     // green
+    [255, 0, 255],   // magenta
+    [255, 0, 0],     // red
+    [0, 0, 255],     // blue
+    [0, 0, 0],       // black
+];
+
+/// Create a frame of eight vertical 100% color bars
+///
+/// The pixel at column `x` is `COLOR_BARS[x * 8 / width]` on every row.
+#[must_use]
+pub fn color_bars_frame(width: u32, height: u32) -> CameraFrame {
+    let row: Vec<u8> = (0..u64::from(width))
+        .flat_map(|x| {
+            let bar = usize::try_from(x * 8 / u64::from(width)).unwrap_or(7);
+            COLOR_BARS[bar]
+        })
+        .collect();
+
+    CameraFrame::new(
+        row.repeat(height as usize),
+        width,
+        height,
+        "synthetic_color_bars".to_string(),
+    )
+}
+
+/// Create a frame with a horizontal gray ramp from black to white
+///
+/// The pixel at column `x` has R = G = B = `x * 255 / (width - 1)` (integer
+/// division), so the first column is 0 and the last is 255. A one pixel
+/// wide frame is black.
+#[must_use]
+pub fn gradient_frame(width: u32, height: u32) -> CameraFrame {
+    let span = u64::from(width.saturating_sub(1)).max(1);
+    let row: Vec<u8> = (0..u64::from(width))
+        .flat_map(|x| [u8::try_from(x * 255 / span).unwrap_or(u8::MAX); 3])
+        .collect();
+
+    CameraFrame::new(
+        row.repeat(height as usize),
+        width,
+        height,
+        "synthetic_gradient".to_string(),
+    )
+}
+
+/// Create a stereo 48kHz linear sine sweep from `start_hz` to `end_hz`
+///
+/// Sample `i` (both channels) is `0.5 * sin(2π (f0·t + (f1 - f0)·t² / 2T))`
+/// with `t = i / 48000` and `T` the frame duration, so the sweep starts at
+/// exactly `0.0` and reaches `end_hz` at the end of the frame. The
+/// timestamp is `0.0`.
+#[cfg(feature = "audio")]
+#[must_use]
+pub fn sine_sweep_audio_frame(samples_per_frame: usize, start_hz: f64, end_hz: f64) -> AudioFrame {
+    let sample_rate = f64::from(SYNTHETIC_SAMPLE_RATE);
+    #[allow(clippy::cast_precision_loss)] // frame lengths are far below 2^52
+    let duration = samples_per_frame.max(1) as f64 / sample_rate;
+    let rate = (end_hz - start_hz) / duration;
+
+    let samples = (0..samples_per_frame)
+        .flat_map(|i| {
+            #[allow(clippy::cast_precision_loss)]
+            let t = i as f64 / sample_rate;
+            let phase = 2.0 * std::f64::consts::PI * start_hz.mul_add(t, rate * t * t / 2.0);
+            #[allow(clippy::cast_possible_truncation)] // amplitude fits f32
+            let value = (0.5 * phase.sin()) as f32;
+            [value, value]
+        })
+        .collect();
+
+    AudioFrame {
+        samples,
+        sample_rate: SYNTHETIC_SAMPLE_RATE,
+        channels: 2,
+        timestamp: 0.0,
+    }
+}
+
 /// Hardware characteristics learned from OBSBOT Tiny 4K
 pub struct ObsbotCharacteristics {
     /// Native video resolution (camera returns this even when lower requested)
@@ -151,6 +298,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_seeded_video_frame_is_reproducible() {
+        let a = synthetic_video_frame_seeded(33, 7, 42);
+        let b = synthetic_video_frame_seeded(33, 7, 42);
+        let c = synthetic_video_frame_seeded(33, 7, 43);
+        assert_eq!(a.data.len(), 33 * 7 * 3);
+        assert_eq!(a.data, b.data);
+        assert_ne!(a.data, c.data);
+        // Pin the stream so the generator cannot change unnoticed
+        assert_eq!(
+            &a.data[..8],
+            &SplitMix64(42).next_u64().to_le_bytes(),
+            "first bytes come straight from SplitMix64"
+        );
+        assert_eq!(SplitMix64(0).next_u64(), 0xE220_A839_7B1D_CDAF);
+    }
+
+    #[test]
+    fn test_color_bars_and_gradient_values() {
+        let bars = color_bars_frame(16, 2);
+        let pixel = |frame: &CameraFrame, x: usize, y: usize| {
+            let at = (y * frame.width as usize + x) * 3;
+            [frame.data[at], frame.data[at + 1], frame.data[at + 2]]
+        };
+        assert_eq!(pixel(&bars, 0, 0), COLOR_BARS[0]);
+        assert_eq!(pixel(&bars, 3, 1), COLOR_BARS[1]);
+        assert_eq!(pixel(&bars, 15, 1), COLOR_BARS[7]);
+
+        let ramp = gradient_frame(256, 2);
+        assert_eq!(pixel(&ramp, 0, 0), [0; 3]);
+        assert_eq!(pixel(&ramp, 128, 1), [128; 3]);
+        assert_eq!(pixel(&ramp, 255, 1), [255; 3]);
+        assert_eq!(gradient_frame(1, 1).data, [0; 3]);
+    }
+
+    #[cfg(feature = "audio")]
+    #[test]
+    fn test_seeded_audio_and_sine_sweep() {
+        let a = synthetic_audio_frame_seeded(480, 7);
+        assert_eq!(a.samples, synthetic_audio_frame_seeded(480, 7).samples);
+        assert_ne!(a.samples, synthetic_audio_frame_seeded(480, 8).samples);
+        assert_eq!(a.samples.len(), 960);
+        assert!(a.samples.iter().all(|s| (-0.3..0.3).contains(s)));
+
+        let sweep = sine_sweep_audio_frame(4800, 100.0, 1000.0);
+        assert_eq!(sweep.samples.len(), 9600);
+        assert!(sweep.samples[0].abs() < f32::EPSILON);
+        assert_eq!(
+            sweep.samples[2].to_bits(),
+            sweep.samples[3].to_bits(),
+            "channels match"
+        );
+        let peak = sweep.samples.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!(peak > 0.49 && peak <= 0.5, "peak {peak}");
+    }
+
     #[test]
     fn test_obsbot_characteristics() {
         let chars = ObsbotCharacteristics::default();
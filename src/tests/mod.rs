@@ -2,7 +2,7 @@
 // Focus on core functionality that actually works
 
 use crate::errors::CameraError;
-use crate::types::{CameraDeviceInfo, CameraFormat, CameraFrame, Platform};
+use crate::types::{CameraDeviceInfo, CameraFormat, CameraFrame, DeviceTransport, Platform};
 use chrono::Utc;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
@@ -16,6 +16,17 @@ pub struct MockCameraSystem {
 }
 
 /// Detailed mock capture behavior.
+///
+/// `Timed` and `Sequence` make timing and failures scriptable, for testing
+/// streaming stats, frame skipping and reordering without hardware:
+///
+/// - `Timed { fps: 30.0, jitter_ms: 0 }` delivers frames exactly 1/30 s
+///   apart, measured from the first capture.
+/// - `Timed { fps: 30.0, jitter_ms: 5 }` shifts each frame by up to 5 ms
+///   either way. The offsets are pseudo-random but identical on every run,
+///   so tests stay deterministic.
+/// - `Sequence(vec![Success, Success, Success, Failure])` succeeds three
+///   times, fails once, and repeats. Nested sequences are flattened.
 #[derive(Debug, Clone)]
 pub enum MockCaptureMode {
     /// Return a valid frame.
@@ -24,6 +35,42 @@ pub enum MockCaptureMode {
     Failure,
     /// Delay before returning a frame.
     SlowCapture,
+    /// Return valid frames paced at `fps`, each offset by up to `jitter_ms`.
+    Timed {
+        /// Frames per second.
+        fps: f64,
+        /// Largest deviation of a frame from its nominal time, in ms.
+        jitter_ms: u32,
+    },
+    /// Apply the listed modes in turn, one per capture, then start over.
+    Sequence(Vec<MockCaptureMode>),
+}
+
+impl MockCaptureMode {
+    /// Mode applied to the zero-based `step`th capture
+    ///
+    /// Resolves [`MockCaptureMode::Sequence`] to one of its entries; an empty
+    /// sequence behaves like [`MockCaptureMode::Success`].
+    #[must_use]
+    pub fn at_step(&self, step: usize) -> Self {
+        let mut modes = Vec::new();
+        self.flatten_into(&mut modes);
+        if modes.is_empty() {
+            return Self::Success;
+        }
+        modes[step % modes.len()].clone()
+    }
+
+    fn flatten_into<'a>(&'a self, modes: &mut Vec<&'a Self>) {
+        match self {
+            Self::Sequence(steps) => {
+                for mode in steps {
+                    mode.flatten_into(modes);
+                }
+            }
+            mode => modes.push(mode),
+        }
+    }
 }
 
 impl MockCameraSystem {
@@ -114,6 +161,8 @@ pub fn create_mock_device(id: &str, name: &str, platform: Platform) -> CameraDev
         platform,
         is_available: true,
         supports_formats: get_test_formats(),
+        stable_id: format!("mock-{id}"),
+        transport: DeviceTransport::Camera,
     }
 }
 
@@ -264,6 +313,23 @@ mod mock_tests {
         assert!(matches!(get_mock_camera_mode(id), MockCaptureMode::Failure));
     }
 
+    #[test]
+    fn test_sequence_mode_cycles_flattened_steps() {
+        use MockCaptureMode::{Failure, Sequence, SlowCapture, Success};
+
+        let mode = Sequence(vec![Sequence(vec![Success, Success, Success]), Failure]);
+        let steps: Vec<bool> = (0..8)
+            .map(|step| matches!(mode.at_step(step), Success))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![true, true, true, false, true, true, true, false]
+        );
+
+        assert!(matches!(SlowCapture.at_step(5), SlowCapture));
+        assert!(matches!(Sequence(Vec::new()).at_step(0), Success));
+    }
+
     #[test]
     fn test_init_test_env_is_idempotent() {
         init_test_env();
@@ -1,9 +1,16 @@
 use crate::constants::{
-    DEFAULT_FPS, DEFAULT_RESOLUTION_HEIGHT, DEFAULT_RESOLUTION_WIDTH, FALLBACK_RESOLUTION_HEIGHT,
-    FALLBACK_RESOLUTION_WIDTH, FORMAT_RGB, MIN_RESOLUTION_HEIGHT, MIN_RESOLUTION_WIDTH,
+    CONNECTION_BACKOFF_INITIAL_MS, CONNECTION_BACKOFF_MAX_MS, CONNECTION_RETRY_DEFAULT,
+    DEFAULT_BIT_DEPTH, DEFAULT_DEPTH_UNITS, DEFAULT_FPS, DEFAULT_RESOLUTION_HEIGHT,
+    DEFAULT_RESOLUTION_WIDTH, FALLBACK_RESOLUTION_HEIGHT, FALLBACK_RESOLUTION_WIDTH, FORMAT_RGB,
+    FRAME_DUPLICATE_SAMPLE_STRIDE, ISO_AT_UNITY_GAIN, JPEG_EOI, JPEG_SOI, MIN_EXPOSURE_SECONDS,
+    MIN_RESOLUTION_HEIGHT, MIN_RESOLUTION_WIDTH,
 };
+use crate::errors::CameraError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
 use uuid::Uuid;
 
 /// Platform enumeration
@@ -59,21 +66,63 @@ pub struct CameraDeviceInfo {
     pub supports_formats: Vec<CameraFormat>,
     /// The platform this camera belongs to.
     pub platform: Platform,
+    /// Identifier that survives reconnects and reboots, unlike the
+    /// index-based `id`: USB vendor/product id plus serial (or port) from
+    /// sysfs on Linux, the symbolic link on Windows, the `uniqueID` on macOS.
+    /// Falls back to `id` when the backend exposes nothing persistent.
+    /// Accepted anywhere a device id is.
+    #[serde(default)]
+    pub stable_id: String,
+    /// Whether this is a camera or, with the `screen-capture` feature, a
+    /// display.
+    #[serde(default)]
+    pub transport: DeviceTransport,
+}
+
+/// Where a device's frames come from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeviceTransport {
+    /// A camera opened through the platform camera backend.
+    #[default]
+    Camera,
+    /// A display captured through ffmpeg (`screen-capture` feature).
+    Screen,
 }
 
 impl CameraDeviceInfo {
     /// Create new camera device info
     pub fn new(id: String, name: String) -> Self {
         Self {
+            stable_id: id.clone(),
             id,
             name,
             description: None,
             is_available: true,
             supports_formats: Vec::new(),
             platform: Platform::current(),
+            transport: DeviceTransport::Camera,
         }
     }
 
+    /// Set the persistent device identifier
+    #[must_use]
+    pub fn with_stable_id(mut self, stable_id: String) -> Self {
+        self.stable_id = stable_id;
+        self
+    }
+
+    /// Set where the device's frames come from
+    #[must_use]
+    pub fn with_transport(mut self, transport: DeviceTransport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Whether `device_id` names this device, by numeric or stable id
+    pub fn matches_id(&self, device_id: &str) -> bool {
+        self.id == device_id || self.stable_id == device_id
+    }
+
     /// Set description
     #[must_use]
     pub fn with_description(mut self, description: String) -> Self {
@@ -96,6 +145,122 @@ impl CameraDeviceInfo {
     }
 }
 
+/// Canonical pixel/compression format code.
+///
+/// Parsing normalizes case and the common aliases used by different drivers
+/// and backends, so `"MJPG"` and `"mjpeg"` both become [`FourCC::MJPEG`] and
+/// `"YUY2"` becomes [`FourCC::YUYV`]. Codes without a known alias are kept
+/// (uppercased) rather than rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FourCC(Cow<'static, str>);
+
+impl FourCC {
+    /// Motion JPEG (aliases: `MJPG`, `JPEG`).
+    pub const MJPEG: FourCC = FourCC(Cow::Borrowed("MJPEG"));
+    /// Packed YUV 4:2:2, Y0 U Y1 V order (alias: `YUY2`).
+    pub const YUYV: FourCC = FourCC(Cow::Borrowed("YUYV"));
+    /// Packed YUV 4:2:2, U Y0 V Y1 order.
+    pub const UYVY: FourCC = FourCC(Cow::Borrowed("UYVY"));
+    /// Semi-planar YUV 4:2:0.
+    pub const NV12: FourCC = FourCC(Cow::Borrowed("NV12"));
+    /// Packed 8-bit RGB (aliases: `RGB24`, `RGB`, `RAWRGB`).
+    pub const RGB8: FourCC = FourCC(Cow::Borrowed("RGB8"));
+    /// Packed 8-bit RGBA (aliases: `RGBA`, `RGBA32`).
+    pub const RGBA8: FourCC = FourCC(Cow::Borrowed("RGBA8"));
+    /// Packed 8-bit BGR (aliases: `BGR24`, `BGR`, `RAWBGR`).
+    pub const BGR8: FourCC = FourCC(Cow::Borrowed("BGR8"));
+    /// 8-bit grayscale (aliases: `GREY`, `Y8`).
+    pub const GRAY: FourCC = FourCC(Cow::Borrowed("GRAY"));
+    /// Semi-planar YUV 4:2:0 with 10-bit samples in the high bits of
+    /// little-endian 16-bit words (HDR10 capture cards).
+    pub const P010: FourCC = FourCC(Cow::Borrowed("P010"));
+    /// Packed RGB with 16-bit little-endian samples; 10-bit sources are
+    /// stored in the high bits (aliases: `RGB48`, `RGB10`).
+    pub const RGB16: FourCC = FourCC(Cow::Borrowed("RGB16"));
+    /// 16-bit little-endian depth samples, 0 meaning no reading (V4L2
+    /// `Z16 `; alias: `DEPTH16`).
+    pub const Z16: FourCC = FourCC(Cow::Borrowed("Z16"));
+
+    /// Canonical code string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Significant bits per sample (8 for everything but P010/RGB16/Z16)
+    pub fn bit_depth(&self) -> u8 {
+        match self.as_str() {
+            "P010" => 10,
+            "RGB16" | "Z16" => 16,
+            _ => DEFAULT_BIT_DEPTH,
+        }
+    }
+
+    /// Bytes per pixel for packed formats where every pixel is stored
+    /// whole, `None` for subsampled (YUV) or compressed formats
+    pub fn packed_bytes_per_pixel(&self) -> Option<usize> {
+        match self.as_str() {
+            "GRAY" => Some(1),
+            "Z16" => Some(2),
+            "RGB8" | "BGR8" => Some(3),
+            "RGBA8" => Some(4),
+            "RGB16" => Some(6),
+            _ => None,
+        }
+    }
+
+    /// Whether frames are compressed (MJPEG, H264) rather than raw samples
+    pub fn is_compressed(&self) -> bool {
+        matches!(self.as_str(), "MJPEG" | "H264")
+    }
+}
+
+impl FromStr for FourCC {
+    type Err = CameraError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s.trim().to_ascii_uppercase();
+        if code.is_empty() || !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(CameraError::UnsupportedFormat(s.to_string()));
+        }
+
+        Ok(match code.as_str() {
+            "MJPEG" | "MJPG" | "JPEG" => Self::MJPEG,
+            "YUYV" | "YUY2" => Self::YUYV,
+            "UYVY" => Self::UYVY,
+            "NV12" => Self::NV12,
+            "RGB8" | "RGB24" | "RGB" | "RAWRGB" => Self::RGB8,
+            "RGBA8" | "RGBA" | "RGBA32" => Self::RGBA8,
+            "BGR8" | "BGR24" | "BGR" | "RAWBGR" => Self::BGR8,
+            "GRAY" | "GREY" | "Y8" => Self::GRAY,
+            "P010" => Self::P010,
+            "RGB16" | "RGB48" | "RGB10" => Self::RGB16,
+            "Z16" | "DEPTH16" => Self::Z16,
+            _ => FourCC(Cow::Owned(code)),
+        })
+    }
+}
+
+impl TryFrom<String> for FourCC {
+    type Error = CameraError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<FourCC> for String {
+    fn from(fourcc: FourCC) -> Self {
+        fourcc.0.into_owned()
+    }
+}
+
+impl fmt::Display for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 /// Camera format specification
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CameraFormat {
@@ -107,6 +272,13 @@ pub struct CameraFormat {
     pub fps: f32,
     /// Format identifier (e.g. "MJPEG").
     pub format_type: String,
+    /// Significant bits per color sample (10 for P010, 8 for most formats).
+    #[serde(default = "default_bit_depth")]
+    pub bit_depth: u8,
+}
+
+fn default_bit_depth() -> u8 {
+    DEFAULT_BIT_DEPTH
 }
 
 impl CameraFormat {
@@ -117,10 +289,11 @@ impl CameraFormat {
             height,
             fps,
             format_type: FORMAT_RGB.to_string(),
+            bit_depth: DEFAULT_BIT_DEPTH,
         }
     }
 
-    /// Create high resolution format
+    /// Create high resolution format (1920x1080, same as [`fhd`](Self::fhd))
     pub fn hd() -> Self {
         Self::new(
             DEFAULT_RESOLUTION_WIDTH,
@@ -143,12 +316,97 @@ impl CameraFormat {
         Self::new(MIN_RESOLUTION_WIDTH, MIN_RESOLUTION_HEIGHT, DEFAULT_FPS)
     }
 
-    /// Set format type
+    /// Format of a standard [`FormatPreset`] at its default frame rate
+    pub fn preset(preset: FormatPreset) -> Self {
+        let (width, height) = preset.resolution();
+        Self::new(width, height, preset.default_fps())
+    }
+
+    /// QVGA, 320x240 at 30 fps
+    pub fn qvga() -> Self {
+        Self::preset(FormatPreset::Qvga)
+    }
+
+    /// VGA, 640x480 at 30 fps
+    pub fn vga() -> Self {
+        Self::preset(FormatPreset::Vga)
+    }
+
+    /// PAL standard definition, 720x576 at 25 fps
+    pub fn sd_pal() -> Self {
+        Self::preset(FormatPreset::SdPal)
+    }
+
+    /// HD 720p, 1280x720 at 30 fps
+    pub fn hd_720() -> Self {
+        Self::preset(FormatPreset::Hd720)
+    }
+
+    /// Full HD 1080p, 1920x1080 at 30 fps
+    pub fn fhd() -> Self {
+        Self::preset(FormatPreset::Fhd1080)
+    }
+
+    /// 4K UHD, 3840x2160 at 30 fps
+    pub fn uhd_4k() -> Self {
+        Self::preset(FormatPreset::Uhd4k)
+    }
+
+    /// Set the frame rate
+    #[must_use]
+    pub fn with_fps(mut self, fps: f32) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    /// Set the resolution
+    #[must_use]
+    pub fn with_resolution(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Create a format with an explicit, canonical format code
+    pub fn from_fourcc(width: u32, height: u32, fps: f32, fourcc: FourCC) -> Self {
+        Self::new(width, height, fps).with_format_type(fourcc.into())
+    }
+
+    /// Set format type, deriving `bit_depth` from it
     #[must_use]
     pub fn with_format_type(mut self, format_type: String) -> Self {
+        self.bit_depth = format_type
+            .parse::<FourCC>()
+            .map_or(DEFAULT_BIT_DEPTH, |f| f.bit_depth());
         self.format_type = format_type;
         self
     }
+
+    /// Override the bit depth (e.g. a 10-bit source delivered as RGB16)
+    #[must_use]
+    pub fn with_bit_depth(mut self, bit_depth: u8) -> Self {
+        self.bit_depth = bit_depth;
+        self
+    }
+
+    /// Canonical format code, or `None` if `format_type` is empty or malformed
+    pub fn fourcc(&self) -> Option<FourCC> {
+        self.format_type.parse().ok()
+    }
+
+    /// Compare resolution, frame rate and format code, treating format
+    /// aliases (e.g. `MJPG`/`MJPEG`, `YUY2`/`YUYV`) as equal
+    pub fn matches(&self, other: &CameraFormat) -> bool {
+        let same_format = match (self.fourcc(), other.fourcc()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.format_type.eq_ignore_ascii_case(&other.format_type),
+        };
+
+        self.width == other.width
+            && self.height == other.height
+            && (self.fps - other.fps).abs() < 0.01
+            && same_format
+    }
 }
 
 impl Default for CameraFormat {
@@ -157,6 +415,89 @@ impl Default for CameraFormat {
     }
 }
 
+/// Standard resolutions for [`CameraFormat::preset`], for UIs listing choices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FormatPreset {
+    /// 320x240
+    Qvga,
+    /// 640x480
+    Vga,
+    /// 720x576, 25 fps
+    SdPal,
+    /// 1280x720
+    Hd720,
+    /// 1920x1080
+    Fhd1080,
+    /// 3840x2160
+    Uhd4k,
+}
+
+impl FormatPreset {
+    /// Every preset, smallest first
+    pub const ALL: [FormatPreset; 6] = [
+        FormatPreset::Qvga,
+        FormatPreset::Vga,
+        FormatPreset::SdPal,
+        FormatPreset::Hd720,
+        FormatPreset::Fhd1080,
+        FormatPreset::Uhd4k,
+    ];
+
+    /// Width and height in pixels
+    pub fn resolution(self) -> (u32, u32) {
+        match self {
+            FormatPreset::Qvga => (320, 240),
+            FormatPreset::Vga => (640, 480),
+            FormatPreset::SdPal => (720, 576),
+            FormatPreset::Hd720 => (1280, 720),
+            FormatPreset::Fhd1080 => (1920, 1080),
+            FormatPreset::Uhd4k => (3840, 2160),
+        }
+    }
+
+    /// Frame rate the preset's format uses
+    pub fn default_fps(self) -> f32 {
+        match self {
+            FormatPreset::SdPal => 25.0,
+            _ => DEFAULT_FPS,
+        }
+    }
+
+    /// Human-readable name for display
+    pub fn label(self) -> &'static str {
+        match self {
+            FormatPreset::Qvga => "QVGA (320×240)",
+            FormatPreset::Vga => "VGA (640×480)",
+            FormatPreset::SdPal => "SD PAL (720×576)",
+            FormatPreset::Hd720 => "HD 720p (1280×720)",
+            FormatPreset::Fhd1080 => "Full HD 1080p (1920×1080)",
+            FormatPreset::Uhd4k => "4K UHD (3840×2160)",
+        }
+    }
+}
+
+/// A [`FormatPreset`] with its label and format, as listed to frontends
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormatPresetInfo {
+    /// The preset.
+    pub preset: FormatPreset,
+    /// Human-readable name.
+    pub label: String,
+    /// Format the preset selects.
+    pub format: CameraFormat,
+}
+
+impl From<FormatPreset> for FormatPresetInfo {
+    fn from(preset: FormatPreset) -> Self {
+        Self {
+            preset,
+            label: preset.label().to_string(),
+            format: CameraFormat::preset(preset),
+        }
+    }
+}
+
 /// Camera frame data with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CameraFrame {
@@ -217,6 +558,512 @@ impl CameraFrame {
     pub fn is_valid(&self) -> bool {
         !self.data.is_empty() && self.width > 0 && self.height > 0
     }
+
+    /// Check that the buffer holds a complete frame
+    ///
+    /// Uncompressed formats must carry at least as many bytes as their
+    /// dimensions require (extra row padding is allowed). MJPEG frames must
+    /// start with a JPEG SOI marker and end with an EOI marker, ignoring
+    /// trailing zero padding some drivers append.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::CaptureError`] describing the first problem
+    /// found, e.g. `incomplete frame: got N bytes, expected M`.
+    pub fn validate(&self) -> Result<(), CameraError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(CameraError::CaptureError(format!(
+                "incomplete frame: invalid dimensions {}x{}",
+                self.width, self.height
+            )));
+        }
+
+        if self
+            .format
+            .parse::<FourCC>()
+            .is_ok_and(|f| f == FourCC::MJPEG)
+        {
+            let end = self.data.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+            if !self.data.starts_with(&JPEG_SOI) {
+                return Err(CameraError::CaptureError(
+                    "incomplete frame: missing JPEG start-of-image marker".to_string(),
+                ));
+            }
+            if !self.data[..end].ends_with(&JPEG_EOI) {
+                return Err(CameraError::CaptureError(format!(
+                    "incomplete frame: missing JPEG end-of-image marker ({} bytes)",
+                    self.data.len()
+                )));
+            }
+            return Ok(());
+        }
+
+        if let Some(expected) =
+            crate::platform::convert::expected_len(self.width, self.height, &self.format)
+        {
+            if self.data.len() < expected {
+                return Err(CameraError::CaptureError(format!(
+                    "incomplete frame: got {} bytes, expected {expected}",
+                    self.data.len()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Convert the frame's pixels to packed RGB8
+    ///
+    /// Uses the GPU converter for large YUV frames when the `gpu` feature is
+    /// enabled (see [`crate::platform::convert::to_rgb8_auto`]).
+    ///
+    /// # Errors
+    /// Returns a [`CameraError`] if the format is unsupported or the buffer
+    /// does not match the frame dimensions.
+    pub fn to_rgb8(&self) -> Result<Vec<u8>, CameraError> {
+        crate::platform::convert::to_rgb8_auto(&self.data, self.width, self.height, &self.format)
+    }
+
+    /// Convert the frame's pixels to packed RGB with 16-bit samples
+    ///
+    /// P010 and RGB16 frames keep their full precision; 8-bit formats are
+    /// widened so 255 maps to 65535.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError`] if the format is unsupported or the buffer
+    /// does not match the frame dimensions.
+    pub fn to_rgb16(&self) -> Result<Vec<u16>, CameraError> {
+        crate::platform::convert::to_rgb16(&self.data, self.width, self.height, &self.format)
+    }
+
+    /// Tone-map an HDR10 (PQ-encoded, BT.2020) frame to SDR RGB8
+    ///
+    /// Unlike [`to_rgb8`](Self::to_rgb8), which only drops the low bits,
+    /// this compresses highlights up to the mastering peak instead of
+    /// clipping them. 8-bit frames are returned as by `to_rgb8`.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError`] if the format is unsupported or the buffer
+    /// does not match the frame dimensions.
+    pub fn tone_map_to_rgb8(&self) -> Result<Vec<u8>, CameraError> {
+        if !self.is_high_bit_depth() {
+            return self.to_rgb8();
+        }
+        Ok(crate::platform::convert::tone_map_pq_to_rgb8(
+            &self.to_rgb16()?,
+        ))
+    }
+
+    /// Significant bits per color sample of the frame's format
+    pub fn bit_depth(&self) -> u8 {
+        self.format
+            .parse::<FourCC>()
+            .map_or(DEFAULT_BIT_DEPTH, |f| f.bit_depth())
+    }
+
+    /// Whether the frame carries more than 8 bits per sample (P010, RGB16)
+    pub fn is_high_bit_depth(&self) -> bool {
+        self.bit_depth() > DEFAULT_BIT_DEPTH
+    }
+
+    /// Convert the frame's pixels to packed RGBA8
+    ///
+    /// RGBA8 frames are returned as-is; every other format is converted to
+    /// RGB8 first and given a fully opaque alpha channel.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError`] if the format is unsupported or the buffer
+    /// does not match the frame dimensions.
+    pub fn to_rgba8(&self) -> Result<Vec<u8>, CameraError> {
+        if self.is_rgba() {
+            let len = self.width as usize * self.height as usize * 4;
+            if self.data.len() < len {
+                return Err(CameraError::CaptureError(format!(
+                    "RGBA8 buffer too small: {} bytes, expected {len}",
+                    self.data.len()
+                )));
+            }
+            return Ok(self.data[..len].to_vec());
+        }
+        Ok(self
+            .to_rgb8()?
+            .chunks_exact(3)
+            .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], u8::MAX])
+            .collect())
+    }
+
+    /// Whether the frame carries depth samples (Z16) rather than color
+    pub fn is_depth(&self) -> bool {
+        self.format
+            .parse::<FourCC>()
+            .is_ok_and(|f| f == FourCC::Z16)
+    }
+
+    /// Distance in meters at pixel (`x`, `y`) of a depth frame
+    ///
+    /// Raw samples are scaled by `metadata.depth_units`, or
+    /// `DEFAULT_DEPTH_UNITS` (1 mm) when unset. `None` for color frames,
+    /// out-of-bounds coordinates, and pixels without a reading (raw 0).
+    pub fn depth_at(&self, x: u32, y: u32) -> Option<f32> {
+        if !self.is_depth() || x >= self.width || y >= self.height {
+            return None;
+        }
+        let at = (y as usize * self.width as usize + x as usize) * 2;
+        let raw = u16::from_le_bytes([*self.data.get(at)?, *self.data.get(at + 1)?]);
+        if raw == 0 {
+            return None;
+        }
+        let units = self.metadata.depth_units.unwrap_or(DEFAULT_DEPTH_UNITS);
+        Some(f32::from(raw) * units)
+    }
+
+    /// Whether the frame carries an alpha channel (RGBA8)
+    pub fn is_rgba(&self) -> bool {
+        self.format
+            .parse::<FourCC>()
+            .is_ok_and(|f| f == FourCC::RGBA8)
+    }
+
+    /// Mirror the frame in place across `axis`
+    ///
+    /// Works on packed formats (RGB8, BGR8, RGBA8, GRAY, RGB16).
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::UnsupportedFormat`] for YUV or compressed
+    /// frames, or a [`CameraError::CaptureError`] if a dimension is zero or
+    /// the buffer is smaller than the dimensions require.
+    pub fn flip(&mut self, axis: FlipAxis) -> Result<(), CameraError> {
+        let bpp = self.packed_pixel_size()?;
+        let row_len = self.width as usize * bpp;
+        let pixels = &mut self.data[..row_len * self.height as usize];
+
+        if matches!(axis, FlipAxis::Vertical | FlipAxis::Both) {
+            let rows = self.height as usize;
+            for y in 0..rows / 2 {
+                let (top, bottom) = pixels.split_at_mut((rows - 1 - y) * row_len);
+                top[y * row_len..(y + 1) * row_len].swap_with_slice(&mut bottom[..row_len]);
+            }
+        }
+        if matches!(axis, FlipAxis::Horizontal | FlipAxis::Both) {
+            let cols = self.width as usize;
+            for row in pixels.chunks_exact_mut(row_len) {
+                for x in 0..cols / 2 {
+                    let (left, right) = row.split_at_mut((cols - 1 - x) * bpp);
+                    left[x * bpp..(x + 1) * bpp].swap_with_slice(&mut right[..bpp]);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rotate the frame clockwise by `degrees` (0, 90, 180 or 270)
+    ///
+    /// 180 degrees is done in place; 90 and 270 need a new buffer and swap
+    /// `width` and `height`. Supports the same formats as [`flip`](Self::flip).
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::ConfigError`] for any other angle, or the
+    /// errors of [`flip`](Self::flip).
+    pub fn rotate(&mut self, degrees: u16) -> Result<(), CameraError> {
+        match degrees {
+            0 => Ok(()),
+            180 => self.flip(FlipAxis::Both),
+            90 | 270 => {
+                let bpp = self.packed_pixel_size()?;
+                let (w, h) = (self.width as usize, self.height as usize);
+                let mut rotated = vec![0u8; w * h * bpp];
+                for (y, row) in self.data.chunks_exact(w * bpp).take(h).enumerate() {
+                    for (x, pixel) in row.chunks_exact(bpp).enumerate() {
+                        // Destination is h pixels wide
+                        let (dx, dy) = if degrees == 90 {
+                            (h - 1 - y, x)
+                        } else {
+                            (y, w - 1 - x)
+                        };
+                        let at = (dy * h + dx) * bpp;
+                        rotated[at..at + bpp].copy_from_slice(pixel);
+                    }
+                }
+                self.size_bytes = rotated.len();
+                self.data = rotated;
+                std::mem::swap(&mut self.width, &mut self.height);
+                Ok(())
+            }
+            _ => Err(CameraError::ConfigError(format!(
+                "Rotation must be 0, 90, 180 or 270 degrees, got {degrees}"
+            ))),
+        }
+    }
+
+    /// Flip, then rotate, as described by `transform`, recording it in
+    /// `metadata.orientation`
+    ///
+    /// # Errors
+    /// Returns the errors of [`flip`](Self::flip) and [`rotate`](Self::rotate).
+    pub fn apply_transform(&mut self, transform: FrameTransform) -> Result<(), CameraError> {
+        if transform.is_identity() {
+            return Ok(());
+        }
+        transform.validate()?;
+        if let Some(axis) = transform.flip {
+            self.flip(axis)?;
+        }
+        self.rotate(transform.rotation_degrees)?;
+        self.metadata.orientation = Some(transform);
+        Ok(())
+    }
+
+    /// Bytes per pixel of a packed frame whose buffer covers its dimensions
+    fn packed_pixel_size(&self) -> Result<usize, CameraError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(CameraError::CaptureError(format!(
+                "incomplete frame: invalid dimensions {}x{}",
+                self.width, self.height
+            )));
+        }
+        let bpp = self
+            .format
+            .parse::<FourCC>()
+            .ok()
+            .and_then(|f| f.packed_bytes_per_pixel())
+            .ok_or_else(|| CameraError::UnsupportedFormat(self.format.clone()))?;
+        let expected = self.width as usize * self.height as usize * bpp;
+        if self.data.len() < expected {
+            return Err(CameraError::CaptureError(format!(
+                "incomplete frame: got {} bytes, expected {expected}",
+                self.data.len()
+            )));
+        }
+        Ok(bpp)
+    }
+
+    /// RGB8 view of the frame for analysis code that reads 3-byte pixels
+    ///
+    /// RGBA8 frames have their alpha channel stripped and high bit depth
+    /// frames are reduced to 8 bits; any other frame is borrowed unchanged.
+    pub fn rgb_data(&self) -> Cow<'_, [u8]> {
+        if self.is_rgba() {
+            Cow::Owned(
+                self.data
+                    .chunks_exact(4)
+                    .flat_map(|rgba| [rgba[0], rgba[1], rgba[2]])
+                    .collect(),
+            )
+        } else if self.is_high_bit_depth() {
+            self.to_rgb8().map_or(Cow::Borrowed(&self.data), Cow::Owned)
+        } else {
+            Cow::Borrowed(&self.data)
+        }
+    }
+
+    /// Mean squared error per RGB8 sample between two frames
+    ///
+    /// Both frames are compared through [`rgb_data`](Self::rgb_data), so
+    /// RGBA8 and high bit depth frames work; `0.0` means identical pixels.
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::CaptureError`] if the dimensions differ or
+    /// either buffer does not hold RGB pixels for its dimensions (e.g. an
+    /// MJPEG frame).
+    pub fn mse(&self, other: &CameraFrame) -> Result<f64, CameraError> {
+        let (sum, count) = self.squared_error(other, 1)?;
+        #[allow(clippy::cast_precision_loss)] // sums stay far below 2^52
+        Ok(sum as f64 / count.max(1) as f64)
+    }
+
+    /// Peak signal-to-noise ratio in dB between two frames
+    ///
+    /// `f64::INFINITY` for identical frames; higher means more similar.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`mse`](Self::mse).
+    pub fn psnr(&self, other: &CameraFrame) -> Result<f64, CameraError> {
+        let mse = self.mse(other)?;
+        if mse <= 0.0 {
+            return Ok(f64::INFINITY);
+        }
+        Ok(10.0 * (255.0 * 255.0 / mse).log10())
+    }
+
+    /// Fast non-cryptographic hash (XXH3) of the frame content
+    ///
+    /// Covers the pixel data, dimensions and format, so identical captures
+    /// hash equal while any changed byte almost certainly changes the
+    /// hash. Suited to deduplication and spotting corruption, not to
+    /// defending against deliberate tampering (see `sha256_hex`).
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+        hasher.update(&self.data);
+        hasher.update(&self.width.to_le_bytes());
+        hasher.update(&self.height.to_le_bytes());
+        hasher.update(self.format.as_bytes());
+        hasher.digest()
+    }
+
+    /// Store [`CameraFrame::content_hash`] in the frame's metadata
+    ///
+    /// Call again after changing the pixel data to keep it current.
+    pub fn record_content_hash(&mut self) {
+        self.metadata.content_hash = Some(self.content_hash());
+    }
+
+    /// Whether the frame still matches the hash in its metadata
+    ///
+    /// `None` if no hash was recorded.
+    pub fn verify_content_hash(&self) -> Option<bool> {
+        self.metadata
+            .content_hash
+            .map(|hash| hash == self.content_hash())
+    }
+
+    /// SHA-256 of the pixel data as lowercase hex, for integrity checks
+    /// that must resist tampering
+    #[cfg(feature = "sha256")]
+    pub fn sha256_hex(&self) -> String {
+        use sha2::{Digest, Sha256};
+        Sha256::digest(&self.data)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    /// Whether `other` is visually identical to this frame
+    ///
+    /// A cheap check for skipping repeated frames: the MSE is estimated from
+    /// every `FRAME_DUPLICATE_SAMPLE_STRIDE`th pixel and compared against
+    /// `threshold` (`0.0` for an exact match on the sampled pixels). Frames
+    /// that cannot be compared are never duplicates.
+    pub fn is_duplicate(&self, other: &CameraFrame, threshold: f64) -> bool {
+        self.squared_error(other, FRAME_DUPLICATE_SAMPLE_STRIDE)
+            .is_ok_and(|(sum, count)| {
+                #[allow(clippy::cast_precision_loss)]
+                let mse = sum as f64 / count.max(1) as f64;
+                mse <= threshold
+            })
+    }
+
+    /// Sum of squared sample differences over every `stride`th pixel, and
+    /// the number of samples compared
+    fn squared_error(&self, other: &CameraFrame, stride: usize) -> Result<(u64, u64), CameraError> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return Err(CameraError::CaptureError(format!(
+                "cannot compare {}x{} frame with {}x{} frame",
+                self.width, self.height, other.width, other.height
+            )));
+        }
+
+        let expected = self.width as usize * self.height as usize * 3;
+        let (a, b) = (self.rgb_data(), other.rgb_data());
+        if a.len() != expected || b.len() != expected {
+            return Err(CameraError::CaptureError(format!(
+                "cannot compare frames: expected {expected} RGB bytes, got {} and {}",
+                a.len(),
+                b.len()
+            )));
+        }
+
+        let (mut sum, mut count) = (0u64, 0u64);
+        for (pa, pb) in a
+            .chunks_exact(3)
+            .zip(b.chunks_exact(3))
+            .step_by(stride.max(1))
+        {
+            for (&x, &y) in pa.iter().zip(pb) {
+                let diff = u64::from(x.abs_diff(y));
+                sum += diff * diff;
+            }
+            count += 3;
+        }
+        Ok((sum, count))
+    }
+}
+
+/// Axis to mirror a frame across
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlipAxis {
+    /// Mirror left-right (selfie view).
+    Horizontal,
+    /// Mirror top-bottom.
+    Vertical,
+    /// Mirror both ways (same pixels as a 180 degree rotation).
+    Both,
+}
+
+/// Flip and rotation applied to a camera's frames
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameTransform {
+    /// Mirror applied first, `None` for no flip.
+    pub flip: Option<FlipAxis>,
+    /// Clockwise rotation applied after the flip: 0, 90, 180 or 270.
+    pub rotation_degrees: u16,
+}
+
+impl FrameTransform {
+    /// Whether the transform leaves frames unchanged
+    pub fn is_identity(&self) -> bool {
+        self.flip.is_none() && self.rotation_degrees == 0
+    }
+
+    /// Check that the rotation is a supported angle
+    ///
+    /// # Errors
+    /// Returns a [`CameraError::ConfigError`] unless `rotation_degrees` is
+    /// 0, 90, 180 or 270.
+    pub fn validate(&self) -> Result<(), CameraError> {
+        if matches!(self.rotation_degrees, 0 | 90 | 180 | 270) {
+            Ok(())
+        } else {
+            Err(CameraError::ConfigError(format!(
+                "Rotation must be 0, 90, 180 or 270 degrees, got {}",
+                self.rotation_degrees
+            )))
+        }
+    }
+}
+
+/// What a stream of a multi-stream device carries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StreamRole {
+    /// Visible-light color image.
+    Color,
+    /// Depth map (usually Z16).
+    Depth,
+    /// Infrared intensity image.
+    Infrared,
+}
+
+/// One stream to open from a multi-stream device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamSpec {
+    /// Role the stream's frames are tagged with.
+    pub role: StreamRole,
+    /// Requested resolution, rate and pixel format.
+    pub format: CameraFormat,
+    /// Node to open; `None` picks the device's first node advertising
+    /// `format`'s pixel format.
+    #[serde(default)]
+    pub device_id: Option<String>,
+}
+
+impl StreamSpec {
+    /// Stream of `role` in `format`, on a node picked automatically
+    pub fn new(role: StreamRole, format: CameraFormat) -> Self {
+        Self {
+            role,
+            format,
+            device_id: None,
+        }
+    }
+
+    /// Open the stream on a specific node
+    #[must_use]
+    pub fn with_device_id(mut self, device_id: String) -> Self {
+        self.device_id = Some(device_id);
+        self
+    }
+
+    /// Whether the stream delivers Z16 depth frames
+    pub fn is_depth(&self) -> bool {
+        self.format.fourcc() == Some(FourCC::Z16)
+    }
 }
 
 /// Reports which controls were accepted vs. rejected by hardware after a `set_camera_controls` call.
@@ -248,9 +1095,10 @@ pub struct CameraControls {
     pub focus_distance: Option<f32>,
     /// Enable auto-exposure.
     pub auto_exposure: Option<bool>,
-    /// Exposure time in seconds.
+    /// Exposure time in seconds, never a normalized position; see
+    /// [`ExposureValue`] for other units.
     pub exposure_time: Option<f32>,
-    /// ISO sensitivity value.
+    /// ISO sensitivity; see [`GainValue`] for other units.
     pub iso_sensitivity: Option<u32>,
     /// White balance setting.
     pub white_balance: Option<WhiteBalance>,
@@ -272,6 +1120,106 @@ pub struct CameraControls {
     pub image_stabilization: Option<bool>,
 }
 
+/// Partial update to [`CameraControls`].
+///
+/// Only `Some` fields are applied; `None` leaves the corresponding control
+/// untouched on the device (unlike sending a full [`CameraControls`], which
+/// is typically built from defaults).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CameraControlsPatch {
+    /// Enable auto-focus.
+    pub auto_focus: Option<bool>,
+    /// Focus distance (0.0 = infinity, 1.0 = closest).
+    pub focus_distance: Option<f32>,
+    /// Enable auto-exposure.
+    pub auto_exposure: Option<bool>,
+    /// Exposure time in seconds, never a normalized position; see
+    /// [`ExposureValue`] for other units.
+    pub exposure_time: Option<f32>,
+    /// ISO sensitivity; see [`GainValue`] for other units.
+    pub iso_sensitivity: Option<u32>,
+    /// White balance setting.
+    pub white_balance: Option<WhiteBalance>,
+    /// Aperture f-stop value.
+    pub aperture: Option<f32>,
+    /// Digital zoom factor.
+    pub zoom: Option<f32>,
+    /// Brightness adjustment (-1.0 to 1.0).
+    pub brightness: Option<f32>,
+    /// Contrast adjustment (-1.0 to 1.0).
+    pub contrast: Option<f32>,
+    /// Saturation adjustment (-1.0 to 1.0).
+    pub saturation: Option<f32>,
+    /// Sharpness adjustment (-1.0 to 1.0).
+    pub sharpness: Option<f32>,
+    /// Enable noise reduction.
+    pub noise_reduction: Option<bool>,
+    /// Enable image stabilization.
+    pub image_stabilization: Option<bool>,
+}
+
+/// Invoke `$m!` with the full list of control field names.
+macro_rules! with_control_fields {
+    ($m:ident) => {
+        $m!(
+            auto_focus,
+            focus_distance,
+            auto_exposure,
+            exposure_time,
+            iso_sensitivity,
+            white_balance,
+            aperture,
+            zoom,
+            brightness,
+            contrast,
+            saturation,
+            sharpness,
+            noise_reduction,
+            image_stabilization
+        )
+    };
+}
+
+impl CameraControlsPatch {
+    /// Whether the patch changes nothing.
+    pub fn is_empty(&self) -> bool {
+        macro_rules! all_none {
+            ($($field:ident),*) => {
+                $(self.$field.is_none())&&*
+            };
+        }
+        with_control_fields!(all_none)
+    }
+
+    /// Take every control set in `later`, keeping this patch's value for
+    /// the rest.
+    pub fn merge(&mut self, later: CameraControlsPatch) {
+        macro_rules! merge_fields {
+            ($($field:ident),*) => {
+                $(
+                    if later.$field.is_some() {
+                        self.$field = later.$field;
+                    }
+                )*
+            };
+        }
+        with_control_fields!(merge_fields);
+    }
+}
+
+impl From<CameraControlsPatch> for CameraControls {
+    fn from(patch: CameraControlsPatch) -> Self {
+        macro_rules! move_fields {
+            ($($field:ident),*) => {
+                CameraControls {
+                    $($field: patch.$field),*
+                }
+            };
+        }
+        with_control_fields!(move_fields)
+    }
+}
+
 /// White balance presets.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum WhiteBalance {
@@ -293,6 +1241,537 @@ pub enum WhiteBalance {
     Custom(u32),
 }
 
+/// Named color-temperature presets for [`WhiteBalance`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WhiteBalancePreset {
+    /// Incandescent/tungsten light, 3200K.
+    Tungsten,
+    /// Fluorescent light, 4000K.
+    Fluorescent,
+    /// Direct sunlight, 5600K.
+    Daylight,
+    /// Overcast sky, 6500K.
+    Cloudy,
+    /// Open shade, 7500K.
+    Shade,
+}
+
+impl WhiteBalancePreset {
+    /// Color temperature of the preset in Kelvin.
+    pub fn kelvin(self) -> u32 {
+        match self {
+            WhiteBalancePreset::Tungsten => 3200,
+            WhiteBalancePreset::Fluorescent => 4000,
+            WhiteBalancePreset::Daylight => 5600,
+            WhiteBalancePreset::Cloudy => 6500,
+            WhiteBalancePreset::Shade => 7500,
+        }
+    }
+}
+
+/// Outcome of waiting for auto-focus to settle
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FocusLock {
+    /// Whether focus settled before the timeout.
+    pub locked: bool,
+    /// Focus distance the driver reports afterwards (0.0 = infinity,
+    /// 1.0 = closest), if it reports one.
+    pub focus_distance: Option<f32>,
+    /// Laplacian variance of the last frame (higher = sharper).
+    pub sharpness: f64,
+    /// Frames examined while waiting.
+    pub frames: u32,
+    /// Time spent waiting in milliseconds.
+    pub elapsed_ms: u64,
+}
+
+/// How a focus point request was carried out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FocusMethod {
+    /// The camera's region autofocus focused on the point.
+    Hardware,
+    /// crabcamera swept the lens and kept the sharpest setting for the region.
+    Software,
+}
+
+/// Outcome of focusing on a point
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FocusPointResult {
+    /// Normalized horizontal position focused on (0.0 = left).
+    pub x: f32,
+    /// Normalized vertical position focused on (0.0 = top).
+    pub y: f32,
+    /// Whether hardware or software autofocus was used.
+    pub method: FocusMethod,
+    /// Focus distance chosen by the software sweep (0.0 = infinity,
+    /// 1.0 = closest); `None` for hardware autofocus.
+    pub focus_distance: Option<f32>,
+    /// Laplacian variance of the region at that distance; `None` for
+    /// hardware autofocus.
+    pub sharpness: Option<f64>,
+}
+
+/// Focus behaviour, mapped onto the raw focus controls
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FocusMode {
+    /// Focus once on the scene, then hold that distance.
+    Auto,
+    /// Keep refocusing as the scene changes.
+    Continuous,
+    /// Fixed focus distance with autofocus off.
+    Manual {
+        /// Focus distance (0.0 = infinity, 1.0 = closest).
+        distance: f32,
+    },
+    /// Fixed at infinity with autofocus off, for landscapes and the night
+    /// sky.
+    Infinity,
+    /// Fixed at the closest distance with autofocus off.
+    Macro,
+}
+
+impl FocusMode {
+    /// Fixed focus distance of this mode, `None` for the autofocus modes
+    pub fn distance(self) -> Option<f32> {
+        match self {
+            FocusMode::Auto | FocusMode::Continuous => None,
+            FocusMode::Manual { distance } => Some(distance),
+            FocusMode::Infinity => Some(0.0),
+            FocusMode::Macro => Some(1.0),
+        }
+    }
+
+    /// Whether this mode holds the lens at a fixed distance
+    pub fn fixes_distance(self) -> bool {
+        self.distance().is_some()
+    }
+
+    /// Focus controls that select this mode
+    ///
+    /// Both autofocus modes switch autofocus on; holding focus after
+    /// [`FocusMode::Auto`] settles is done separately.
+    pub fn to_patch(self) -> CameraControlsPatch {
+        CameraControlsPatch {
+            auto_focus: Some(!self.fixes_distance()),
+            focus_distance: self.distance(),
+            ..CameraControlsPatch::default()
+        }
+    }
+}
+
+/// How a [`FocusMode`] was carried out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FocusModeSupport {
+    /// The camera's own controls provide the mode.
+    Hardware,
+    /// crabcamera emulates the mode on top of the camera's controls.
+    Emulated,
+    /// The camera rejected the controls the mode needs.
+    Unsupported,
+}
+
+/// Outcome of setting a [`FocusMode`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusModeResult {
+    /// Mode that was requested.
+    pub mode: FocusMode,
+    /// Whether the mode is in effect in hardware, emulated, or unsupported.
+    pub support: FocusModeSupport,
+    /// Distance the lens is held at (0.0 = infinity, 1.0 = closest);
+    /// `None` while autofocus runs or if the driver does not report it.
+    pub focus_distance: Option<f32>,
+    /// Per-control outcome reported by the camera.
+    pub controls: ControlApplicationResult,
+}
+
+impl FocusModeResult {
+    /// Derive whether the camera honored `mode` from `controls`
+    pub fn from_application(mode: FocusMode, controls: ControlApplicationResult) -> Self {
+        let applied = |name: &str| controls.applied.iter().any(|c| c == name);
+        let honored = if mode.fixes_distance() {
+            applied("focus_distance")
+        } else {
+            applied("auto_focus")
+        };
+        Self {
+            mode,
+            support: if honored {
+                FocusModeSupport::Hardware
+            } else {
+                FocusModeSupport::Unsupported
+            },
+            focus_distance: mode.distance().filter(|_| honored),
+            controls,
+        }
+    }
+}
+
+/// Scene-oriented bundle of camera controls, for users who think in
+/// "document mode" rather than individual sliders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapturePreset {
+    /// People at arm's length: continuous focus, natural color, softer detail.
+    Portrait,
+    /// Paper and whiteboards: fixed close focus, high contrast and sharpness.
+    Document,
+    /// Dim scenes: longer exposure, higher ISO, noise reduction.
+    LowLight,
+    /// Moving subjects: short shutter to freeze motion, suited to bursts.
+    Action,
+}
+
+impl CapturePreset {
+    /// Every preset, in display order.
+    pub const ALL: [CapturePreset; 4] = [
+        CapturePreset::Portrait,
+        CapturePreset::Document,
+        CapturePreset::LowLight,
+        CapturePreset::Action,
+    ];
+
+    /// Controls the preset sets; `None` fields are left as they are.
+    pub fn controls(self) -> CameraControls {
+        let none = CameraControls::from(CameraControlsPatch::default());
+        match self {
+            CapturePreset::Portrait => CameraControls {
+                auto_focus: Some(true),
+                auto_exposure: Some(true),
+                white_balance: Some(WhiteBalance::Auto),
+                contrast: Some(-0.1),
+                saturation: Some(0.1),
+                sharpness: Some(-0.2),
+                noise_reduction: Some(true),
+                ..none
+            },
+            CapturePreset::Document => CameraControls {
+                auto_focus: Some(false),
+                focus_distance: Some(0.8),
+                auto_exposure: Some(true),
+                brightness: Some(0.1),
+                contrast: Some(0.6),
+                saturation: Some(-0.5),
+                sharpness: Some(0.8),
+                ..none
+            },
+            CapturePreset::LowLight => CameraControls {
+                auto_focus: Some(true),
+                auto_exposure: Some(false),
+                exposure_time: Some(1.0 / 15.0),
+                iso_sensitivity: Some(1600),
+                brightness: Some(0.2),
+                noise_reduction: Some(true),
+                image_stabilization: Some(true),
+                ..none
+            },
+            CapturePreset::Action => CameraControls {
+                auto_focus: Some(true),
+                auto_exposure: Some(false),
+                exposure_time: Some(1.0 / 1000.0),
+                iso_sensitivity: Some(800),
+                sharpness: Some(0.3),
+                noise_reduction: Some(false),
+                image_stabilization: Some(true),
+                ..none
+            },
+        }
+    }
+}
+
+/// Outcome of applying a [`CapturePreset`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturePresetResult {
+    /// Preset that was applied.
+    pub preset: CapturePreset,
+    /// Controls the camera accepted and rejected.
+    pub controls: ControlApplicationResult,
+    /// Automatic modes switched back on because the manual value the preset
+    /// needed was rejected (e.g. `auto_exposure` when `exposure_time` is
+    /// unsupported), so the camera is not left in a half-manual state.
+    pub restored_auto: Vec<String>,
+}
+
+/// How a white-balance request was carried out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WhiteBalanceCorrection {
+    /// The camera's color-temperature control was set.
+    Hardware,
+    /// The camera rejected the control; captured RGB frames are gain-corrected.
+    Software,
+}
+
+/// Outcome of setting a Kelvin white balance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WhiteBalanceResult {
+    /// Temperature that was requested.
+    pub requested_kelvin: u32,
+    /// Temperature actually applied, after clamping to the supported range.
+    pub kelvin: u32,
+    /// Whether hardware or software correction is in effect.
+    pub correction: WhiteBalanceCorrection,
+}
+
+/// Camera-app style exposure mode, mapped onto the raw exposure controls
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ExposureMode {
+    /// Camera picks shutter and ISO.
+    Auto,
+    /// Fixed shutter time in seconds; ISO/gain is left to the camera.
+    ShutterPriority {
+        /// Exposure time in seconds.
+        shutter: f32,
+    },
+    /// Fixed shutter time and ISO.
+    Manual {
+        /// Exposure time in seconds.
+        shutter: f32,
+        /// ISO sensitivity.
+        iso: u32,
+    },
+}
+
+impl ExposureMode {
+    /// Exposure controls that select this mode
+    ///
+    /// ISO is only touched in [`ExposureMode::Manual`]. In shutter priority
+    /// it is left as is, so cameras with independent auto gain keep
+    /// adjusting it; cameras that tie gain to auto-exposure hold the current
+    /// gain instead.
+    pub fn to_patch(self) -> CameraControlsPatch {
+        match self {
+            ExposureMode::Auto => CameraControlsPatch {
+                auto_exposure: Some(true),
+                ..CameraControlsPatch::default()
+            },
+            ExposureMode::ShutterPriority { shutter } => CameraControlsPatch {
+                auto_exposure: Some(false),
+                exposure_time: Some(shutter),
+                ..CameraControlsPatch::default()
+            },
+            ExposureMode::Manual { shutter, iso } => CameraControlsPatch {
+                auto_exposure: Some(false),
+                exposure_time: Some(shutter),
+                iso_sensitivity: Some(iso),
+                ..CameraControlsPatch::default()
+            },
+        }
+    }
+}
+
+/// Outcome of setting an [`ExposureMode`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExposureModeResult {
+    /// Mode that was requested.
+    pub mode: ExposureMode,
+    /// Whether the camera accepted switching auto-exposure on or off.
+    pub auto_exposure_honored: bool,
+    /// Whether the shutter time was applied, `None` in `Auto`.
+    pub shutter_honored: Option<bool>,
+    /// Whether the ISO was applied, `None` unless `Manual`.
+    pub iso_honored: Option<bool>,
+    /// Per-control outcome reported by the camera.
+    pub controls: ControlApplicationResult,
+}
+
+impl ExposureModeResult {
+    /// Derive which aspects of `mode` the camera honored from `controls`
+    pub fn from_application(mode: ExposureMode, controls: ControlApplicationResult) -> Self {
+        let applied = |name: &str| controls.applied.iter().any(|c| c == name);
+        let auto_exposure_honored = applied("auto_exposure");
+        let (shutter_honored, iso_honored) = match mode {
+            ExposureMode::Auto => (None, None),
+            ExposureMode::ShutterPriority { .. } => (Some(applied("exposure_time")), None),
+            ExposureMode::Manual { .. } => (
+                Some(applied("exposure_time")),
+                Some(applied("iso_sensitivity")),
+            ),
+        };
+        Self {
+            mode,
+            auto_exposure_honored,
+            shutter_honored,
+            iso_honored,
+            controls,
+        }
+    }
+
+    /// Whether every aspect of the mode was honored
+    pub fn fully_honored(&self) -> bool {
+        self.auto_exposure_honored
+            && self.shutter_honored != Some(false)
+            && self.iso_honored != Some(false)
+    }
+}
+
+/// Exposure time in the unit the caller works in
+///
+/// [`CameraControls::exposure_time`] is always in seconds; convert with
+/// [`ExposureValue::to_seconds`]. Serialized as e.g. `{"seconds": 0.004}`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExposureValue {
+    /// Exposure time in seconds (1/250 s = 0.004).
+    Seconds(f32),
+    /// Log2 of the exposure time in seconds, one stop per step (-8 =
+    /// 1/256 s); the unit of UVC and `DirectShow` exposure controls.
+    Ev(f32),
+    /// Position in the camera's exposure range: 0.0 = shortest, 1.0 =
+    /// longest, spaced evenly in stops. Clamped to 0-1.
+    Normalized(f32),
+}
+
+impl ExposureValue {
+    /// Exposure time in seconds
+    ///
+    /// `range` is the camera's `(min, max)` exposure time in seconds, only
+    /// used by [`ExposureValue::Normalized`].
+    pub fn to_seconds(self, range: (f32, f32)) -> f32 {
+        match self {
+            ExposureValue::Seconds(seconds) => seconds,
+            ExposureValue::Ev(ev) => ev.exp2(),
+            ExposureValue::Normalized(position) => {
+                let (low, high) = log2_exposure_range(range);
+                position.clamp(0.0, 1.0).mul_add(high - low, low).exp2()
+            }
+        }
+    }
+}
+
+/// Current exposure time in every unit, with the camera's limits
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExposureReading {
+    /// Exposure time in seconds.
+    pub seconds: f32,
+    /// Log2 of the exposure time in seconds (see [`ExposureValue::Ev`]).
+    pub ev: f32,
+    /// Position in the range (see [`ExposureValue::Normalized`]).
+    pub normalized: f32,
+    /// Shortest exposure time the camera supports, in seconds.
+    pub min_seconds: f32,
+    /// Longest exposure time the camera supports, in seconds.
+    pub max_seconds: f32,
+}
+
+impl ExposureReading {
+    /// Describe `seconds` within the camera's `(min, max)` range
+    pub fn new(seconds: f32, range: (f32, f32)) -> Self {
+        let (low, high) = log2_exposure_range(range);
+        let ev = seconds.max(MIN_EXPOSURE_SECONDS).log2();
+        let normalized = if high > low {
+            ((ev - low) / (high - low)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        Self {
+            seconds,
+            ev,
+            normalized,
+            min_seconds: range.0,
+            max_seconds: range.1,
+        }
+    }
+}
+
+/// Log2 bounds of an exposure range, with a zero minimum replaced by
+/// `MIN_EXPOSURE_SECONDS`
+fn log2_exposure_range((min, max): (f32, f32)) -> (f32, f32) {
+    let low = min.max(MIN_EXPOSURE_SECONDS).log2();
+    (low, max.max(MIN_EXPOSURE_SECONDS).log2().max(low))
+}
+
+/// Sensor gain in the unit the caller works in
+///
+/// [`CameraControls::iso_sensitivity`] is always an ISO value; convert with
+/// [`GainValue::to_iso`]. ISO 100 is taken as 0 dB, so each doubling of ISO
+/// adds about 6 dB. Serialized as e.g. `{"iso": 800}`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GainValue {
+    /// ISO sensitivity.
+    Iso(u32),
+    /// Gain in decibels relative to ISO 100.
+    Db(f32),
+    /// Position in the camera's ISO range: 0.0 = lowest, 1.0 = highest,
+    /// spaced evenly in stops. Clamped to 0-1.
+    Normalized(f32),
+}
+
+impl GainValue {
+    /// ISO sensitivity, rounded to the nearest whole value
+    ///
+    /// `range` is the camera's `(min, max)` ISO, only used by
+    /// [`GainValue::Normalized`].
+    pub fn to_iso(self, range: (u32, u32)) -> u32 {
+        let iso = match self {
+            GainValue::Iso(iso) => return iso,
+            GainValue::Db(db) => f64::from(ISO_AT_UNITY_GAIN) * 10f64.powf(f64::from(db) / 20.0),
+            GainValue::Normalized(position) => {
+                let (low, high) = log2_iso_range(range);
+                f64::from(position.clamp(0.0, 1.0))
+                    .mul_add(high - low, low)
+                    .exp2()
+            }
+        };
+        // Clamped to the u32 range first
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let rounded = iso.round().clamp(0.0, f64::from(u32::MAX)) as u32;
+        rounded
+    }
+}
+
+/// Current gain in every unit, with the camera's limits
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GainReading {
+    /// ISO sensitivity.
+    pub iso: u32,
+    /// Gain in decibels relative to ISO 100.
+    pub db: f32,
+    /// Position in the range (see [`GainValue::Normalized`]).
+    pub normalized: f32,
+    /// Lowest ISO the camera supports.
+    pub min_iso: u32,
+    /// Highest ISO the camera supports.
+    pub max_iso: u32,
+}
+
+impl GainReading {
+    /// Describe `iso` within the camera's `(min, max)` range
+    // Gains and positions are small, f32 precision is plenty
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(iso: u32, range: (u32, u32)) -> Self {
+        let (low, high) = log2_iso_range(range);
+        let stops = f64::from(iso.max(1)).log2();
+        let normalized = if high > low {
+            ((stops - low) / (high - low)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        Self {
+            iso,
+            db: (20.0 * (f64::from(iso.max(1)) / f64::from(ISO_AT_UNITY_GAIN)).log10()) as f32,
+            normalized: normalized as f32,
+            min_iso: range.0,
+            max_iso: range.1,
+        }
+    }
+}
+
+fn log2_iso_range((min, max): (u32, u32)) -> (f64, f64) {
+    let low = f64::from(min.max(1)).log2();
+    (low, f64::from(max.max(1)).log2().max(low))
+}
+
+/// Exposure state of a camera in unambiguous units
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExposureSettings {
+    /// Whether auto-exposure is on, if the camera reports it.
+    pub auto_exposure: Option<bool>,
+    /// Current exposure time, if the camera reports it.
+    pub exposure: Option<ExposureReading>,
+    /// Current gain, if the camera reports it.
+    pub gain: Option<GainReading>,
+}
+
 impl Default for CameraControls {
     fn default() -> Self {
         Self {
@@ -334,6 +1813,38 @@ impl CameraControls {
             image_stabilization: Some(true),
         }
     }
+
+    /// Overwrite the controls set in `patch`, leaving all others unchanged.
+    pub fn apply_patch(&mut self, patch: &CameraControlsPatch) {
+        macro_rules! apply_fields {
+            ($($field:ident),*) => {
+                $(
+                    if let Some(ref value) = patch.$field {
+                        self.$field = Some(value.clone());
+                    }
+                )*
+            };
+        }
+        with_control_fields!(apply_fields);
+    }
+
+    /// Patch containing the controls that are set in `target` and differ from `self`.
+    ///
+    /// Applying the result to `self` yields `target` for every control `target` sets.
+    pub fn diff(&self, target: &CameraControls) -> CameraControlsPatch {
+        let mut patch = CameraControlsPatch::default();
+        macro_rules! diff_fields {
+            ($($field:ident),*) => {
+                $(
+                    if target.$field.is_some() && target.$field != self.$field {
+                        patch.$field.clone_from(&target.$field);
+                    }
+                )*
+            };
+        }
+        with_control_fields!(diff_fields);
+        patch
+    }
 }
 
 /// Burst capture configuration
@@ -407,6 +1918,32 @@ pub struct CameraCapabilityFlags {
     pub hdr: bool,
 }
 
+/// How a capability value was obtained.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CapabilitySource {
+    /// Queried from the opened device.
+    #[default]
+    Probed,
+    /// Derived from enumeration metadata without opening the device.
+    Reported,
+    /// Not known for this device; a schema default is used instead.
+    Assumed,
+}
+
+/// Per-field provenance for [`CameraCapabilities`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityProvenance {
+    /// Source of [`CameraCapabilities::supports`].
+    pub supports: CapabilitySource,
+    /// Source of [`CameraCapabilities::max_resolution`].
+    pub max_resolution: CapabilitySource,
+    /// Source of [`CameraCapabilities::max_fps`].
+    pub max_fps: CapabilitySource,
+    /// Source of the exposure, ISO and focus ranges.
+    pub ranges: CapabilitySource,
+}
+
 /// Camera hardware capabilities
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CameraCapabilities {
@@ -422,6 +1959,67 @@ pub struct CameraCapabilities {
     pub iso_range: Option<(u32, u32)>,
     /// Range of supported focus distances (min, max).
     pub focus_range: Option<(f32, f32)>,
+    /// Where each of the above values came from.
+    #[serde(default)]
+    pub provenance: CapabilityProvenance,
+    /// Adjustable controls with their probed ranges; empty where the
+    /// backend cannot probe them (macOS, screen capture, or without
+    /// opening the device).
+    #[serde(default)]
+    pub controls: Vec<ControlRange>,
+}
+
+/// Range of one adjustable control, probed from the device
+///
+/// Values are in the units [`CameraControls`] uses for the same field on
+/// the current backend (seconds for `exposure_time`, ISO for
+/// `iso_sensitivity`, the backend's normalized scale otherwise), so a
+/// slider bounded by `min` and `max` can send its value back unchanged.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlRange {
+    /// [`CameraControls`] field, e.g. `"brightness"` or `"exposure_time"`.
+    pub control: String,
+    /// Lowest accepted value.
+    pub min: f32,
+    /// Highest accepted value.
+    pub max: f32,
+    /// Smallest change the device distinguishes, `None` where its steps
+    /// are not evenly spaced or unknown.
+    pub step: Option<f32>,
+    /// Factory default, if the device reports one.
+    pub default: Option<f32>,
+}
+
+impl ControlRange {
+    /// Map a raw device range linearly onto `span` (the `(min, max)` in
+    /// [`CameraControls`] units), scaling the step and default with it
+    // Raw control values are small enough for f32
+    #[allow(clippy::cast_precision_loss)]
+    pub fn linear(
+        control: &str,
+        span: (f32, f32),
+        raw_range: (i64, i64),
+        raw_step: i64,
+        raw_default: i64,
+    ) -> Self {
+        let (raw_min, raw_max) = raw_range;
+        let raw_span = (raw_max - raw_min) as f32;
+        let scale = |raw: i64| {
+            if raw_span > 0.0 {
+                span.0 + (raw - raw_min) as f32 / raw_span * (span.1 - span.0)
+            } else {
+                span.0
+            }
+        };
+        Self {
+            control: control.to_string(),
+            min: span.0,
+            max: span.1,
+            step: (raw_step > 0 && raw_span > 0.0)
+                .then(|| raw_step as f32 / raw_span * (span.1 - span.0)),
+            default: Some(scale(raw_default.clamp(raw_min, raw_max))),
+        }
+    }
 }
 
 impl Default for CameraCapabilities {
@@ -443,7 +2041,57 @@ impl Default for CameraCapabilities {
             exposure_range: None,
             iso_range: None,
             focus_range: None,
+            provenance: CapabilityProvenance::default(),
+            controls: Vec::new(),
+        }
+    }
+}
+
+impl CameraCapabilities {
+    /// Probed range of a [`CameraControls`] field, if the camera has it
+    pub fn control(&self, control: &str) -> Option<&ControlRange> {
+        self.controls.iter().find(|range| range.control == control)
+    }
+
+    /// Derive capabilities from enumeration metadata without opening the device.
+    ///
+    /// Resolution and framerate limits are taken from the formats the platform
+    /// reported during enumeration and marked [`CapabilitySource::Reported`].
+    /// Control support and ranges cannot be known without opening the device,
+    /// so they fall back to the schema defaults and are marked
+    /// [`CapabilitySource::Assumed`]. If enumeration reported no formats, the
+    /// default limits are used and also marked as assumed.
+    pub fn from_device_info(info: &CameraDeviceInfo) -> Self {
+        let mut caps = Self {
+            provenance: CapabilityProvenance {
+                supports: CapabilitySource::Assumed,
+                max_resolution: CapabilitySource::Assumed,
+                max_fps: CapabilitySource::Assumed,
+                ranges: CapabilitySource::Assumed,
+            },
+            ..Self::default()
+        };
+
+        if let Some(largest) = info
+            .supports_formats
+            .iter()
+            .max_by_key(|f| u64::from(f.width) * u64::from(f.height))
+        {
+            caps.max_resolution = (largest.width, largest.height);
+            caps.provenance.max_resolution = CapabilitySource::Reported;
+        }
+
+        if let Some(fps) = info
+            .supports_formats
+            .iter()
+            .map(|f| f.fps)
+            .max_by(f32::total_cmp)
+        {
+            caps.max_fps = fps;
+            caps.provenance.max_fps = CapabilitySource::Reported;
         }
+
+        caps
     }
 }
 
@@ -460,12 +2108,143 @@ pub struct FrameMetadata {
     pub focus_distance: Option<f32>,
     /// Aperture f-stop.
     pub aperture: Option<f32>,
+    /// Digital zoom factor.
+    #[serde(default)]
+    pub zoom: Option<f32>,
     /// Whether flash fired.
     pub flash_fired: Option<bool>,
     /// Scene mode description.
     pub scene_mode: Option<String>,
     /// Full capture settings snapshot.
     pub capture_settings: Option<CameraControls>,
+    /// Capture timestamp in seconds, when requested via
+    /// [`TimestampSource::DriverHardware`].
+    ///
+    /// The V4L2, Media Foundation and AVFoundation backends capture through
+    /// nokhwa, which discards the driver's per-buffer timestamp, so they read
+    /// a monotonic clock the moment each buffer is dequeued. Depth frames
+    /// from a V4L2 depth node carry the driver's buffer timestamp. Unlike
+    /// [`CameraFrame::timestamp`] neither jumps when the wall clock is
+    /// adjusted. The value has no common epoch with
+    /// [`CameraFrame::timestamp`] or with `PTSClock`, so only differences
+    /// between frames of the same device are meaningful; consumers such as
+    /// [`ReorderBuffer`](crate::platform::ReorderBuffer) fall back to
+    /// [`CameraFrame::timestamp`] without it.
+    #[serde(default)]
+    pub hardware_timestamp: Option<f64>,
+    /// Faces detected in the frame, in pixel coordinates.
+    ///
+    /// Only populated when the `face-detect` feature is enabled and automatic
+    /// detection was turned on for the device. Detection is best-effort:
+    /// `Some(vec![])` means the detector ran and found nothing.
+    #[serde(default)]
+    pub faces: Option<Vec<Rect>>,
+    /// Region of the source frame this frame was cropped from, for frames
+    /// produced by auto-framing.
+    #[serde(default)]
+    pub crop: Option<Rect>,
+    /// Flip and rotation applied to the frame after capture.
+    #[serde(default)]
+    pub orientation: Option<FrameTransform>,
+    /// Clockwise rotation applied by auto-orientation to turn the frame
+    /// upright; `None` when auto-orientation was off or the device's
+    /// orientation is unknown.
+    #[serde(default)]
+    pub applied_rotation: Option<u16>,
+    /// Meters per raw sample of a depth (Z16) frame.
+    #[serde(default)]
+    pub depth_units: Option<f32>,
+    /// Stream the frame came from when captured as part of a multi-stream
+    /// frame set.
+    #[serde(default)]
+    pub stream_role: Option<StreamRole>,
+    /// Pixel format the driver delivered the frame in (e.g. `"MJPEG"`,
+    /// `"YUYV"`), before conversion to [`CameraFrame::format`].
+    #[serde(default)]
+    pub source_format: Option<String>,
+    /// [`CameraFrame::content_hash`] of the frame as captured; serialized
+    /// as 16 hex digits since JavaScript numbers cannot hold every `u64`.
+    #[serde(default, with = "hex_u64")]
+    pub content_hash: Option<u64>,
+}
+
+/// Optional `u64` as a zero-padded hex string
+mod hex_u64 {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[allow(clippy::ref_option)] // signature required by `serde(with)`
+    pub fn serialize<S: Serializer>(value: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_str(&format!("{value:016x}")),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u64>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|hex| u64::from_str_radix(&hex, 16).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+impl FrameMetadata {
+    /// Record the camera settings in effect when the frame was captured
+    ///
+    /// `controls` should be read back from the device rather than the values
+    /// that were requested. Fields the device did not report (`None`) keep
+    /// their current value; the full snapshot goes to `capture_settings`.
+    pub fn record_controls(&mut self, controls: &CameraControls) {
+        self.exposure_time = controls.exposure_time.or(self.exposure_time);
+        self.iso_sensitivity = controls.iso_sensitivity.or(self.iso_sensitivity);
+        self.white_balance = controls
+            .white_balance
+            .clone()
+            .or_else(|| self.white_balance.take());
+        self.focus_distance = controls.focus_distance.or(self.focus_distance);
+        self.aperture = controls.aperture.or(self.aperture);
+        self.zoom = controls.zoom.or(self.zoom);
+        self.capture_settings = Some(controls.clone());
+    }
+}
+
+/// Axis-aligned rectangle in pixel coordinates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rect {
+    /// Left edge.
+    pub x: u32,
+    /// Top edge.
+    pub y: u32,
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+}
+
+impl Rect {
+    /// Create a new rectangle
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Center point as `(x, y)`.
+    pub fn center(&self) -> (f64, f64) {
+        (
+            f64::from(self.x) + f64::from(self.width) / 2.0,
+            f64::from(self.y) + f64::from(self.height) / 2.0,
+        )
+    }
+
+    /// Area in square pixels.
+    pub fn area(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
 }
 
 /// Performance metrics for camera operations
@@ -477,8 +2256,11 @@ pub struct CameraPerformanceMetrics {
     pub processing_time_ms: f32,
     /// Memory usage in megabytes.
     pub memory_usage_mb: f32,
-    /// Actual frames per second delivered.
+    /// Actual frames per second delivered over a sliding window.
     pub fps_actual: f32,
+    /// Milliseconds by which delivery lags the nominal frame schedule.
+    #[serde(default)]
+    pub processing_backlog_ms: f32,
     /// Number of dropped frames.
     pub dropped_frames: u32,
     /// Number of buffer overruns.
@@ -494,6 +2276,7 @@ impl Default for CameraPerformanceMetrics {
             processing_time_ms: 0.0,
             memory_usage_mb: 0.0,
             fps_actual: 0.0,
+            processing_backlog_ms: 0.0,
             dropped_frames: 0,
             buffer_overruns: 0,
             quality_score: 0.0,
@@ -501,6 +2284,253 @@ impl Default for CameraPerformanceMetrics {
     }
 }
 
+/// Cumulative capture statistics of one camera since it was opened
+///
+/// Latency percentiles have 1 ms resolution; captures slower than
+/// [`SESSION_LATENCY_HISTOGRAM_MS`](crate::constants::SESSION_LATENCY_HISTOGRAM_MS)
+/// count at `latency_max_ms`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionMetricsReport {
+    /// Device the statistics belong to.
+    pub device_id: String,
+    /// Seconds since the camera was opened or its metrics were reset,
+    /// frozen once the camera is released.
+    pub uptime_secs: f64,
+    /// Whether the camera is still open.
+    pub is_open: bool,
+    /// Frames delivered successfully.
+    pub frames_captured: u64,
+    /// Total size of the delivered frames' pixel data.
+    pub bytes_captured: u64,
+    /// Mean capture latency in milliseconds.
+    pub latency_avg_ms: f32,
+    /// Median capture latency in milliseconds.
+    pub latency_p50_ms: f32,
+    /// 95th percentile capture latency in milliseconds.
+    pub latency_p95_ms: f32,
+    /// 99th percentile capture latency in milliseconds.
+    pub latency_p99_ms: f32,
+    /// Slowest capture in milliseconds.
+    pub latency_max_ms: f32,
+    /// Failed captures.
+    pub error_count: u64,
+    /// Failed captures by error code (e.g. `CAPTURE_FAILED`).
+    pub errors: std::collections::BTreeMap<String, u64>,
+}
+
+/// What to do when the device is held by another process at open time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OpenPolicy {
+    /// Return [`CameraError::DeviceBusy`](crate::errors::CameraError::DeviceBusy) immediately.
+    #[default]
+    FailFast,
+    /// Poll until the device frees up, giving up after the given duration.
+    ///
+    /// Only the Linux backend can tell a busy device from other open
+    /// failures; elsewhere this behaves like [`OpenPolicy::FailFast`].
+    RetryFor(std::time::Duration),
+}
+
+/// Which clock stamps captured frames
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimestampSource {
+    /// Only the crate's wall-clock [`CameraFrame::timestamp`] is set.
+    #[default]
+    CrateClock,
+    /// Additionally record a monotonic capture time in
+    /// [`FrameMetadata::hardware_timestamp`].
+    DriverHardware,
+}
+
+/// Capture API used to open a camera
+///
+/// The default picks the platform's native API. Forcing another can help
+/// with devices only one API sees, such as some virtual cameras.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CameraBackend {
+    /// The platform's native API.
+    #[default]
+    Auto,
+    /// Windows Media Foundation.
+    MediaFoundation,
+    /// Windows `DirectShow`.
+    DirectShow,
+    /// Linux Video4Linux2.
+    V4l2,
+    /// macOS `AVFoundation`.
+    AvFoundation,
+}
+
+impl CameraBackend {
+    /// Display name, e.g. `"MediaFoundation"`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CameraBackend::Auto => "Auto",
+            CameraBackend::MediaFoundation => "MediaFoundation",
+            CameraBackend::DirectShow => "DirectShow",
+            CameraBackend::V4l2 => "V4L2",
+            CameraBackend::AvFoundation => "AVFoundation",
+        }
+    }
+
+    /// Check that this backend can open cameras on the current platform
+    ///
+    /// # Errors
+    /// Returns [`CameraError::UnsupportedOperation`] for a backend of another
+    /// platform, and for `DirectShow`, which the capture library does not
+    /// implement.
+    pub fn validate(self) -> Result<(), CameraError> {
+        let available = match self {
+            CameraBackend::Auto => true,
+            CameraBackend::MediaFoundation => cfg!(target_os = "windows"),
+            CameraBackend::DirectShow => {
+                return Err(CameraError::UnsupportedOperation(
+                    "The DirectShow backend is not available; use MediaFoundation or Auto"
+                        .to_string(),
+                ))
+            }
+            CameraBackend::V4l2 => cfg!(target_os = "linux"),
+            CameraBackend::AvFoundation => cfg!(target_os = "macos"),
+        };
+        if available {
+            Ok(())
+        } else {
+            Err(CameraError::UnsupportedOperation(format!(
+                "The {} backend is not available on {}",
+                self.as_str(),
+                Platform::current().as_str()
+            )))
+        }
+    }
+}
+
+/// Which kind of pixel format to favor when a resolution is offered in
+/// several
+///
+/// Many webcams deliver high resolutions at full frame rate only as MJPEG,
+/// while raw YUYV at the same size drops to a few frames per second. See
+/// [`negotiate_format`](crate::platform::negotiate_format) for how the
+/// choice is made.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PixelFormatPreference {
+    /// Uncompressed when it reaches the requested frame rate, otherwise
+    /// the faster compressed mode.
+    #[default]
+    Auto,
+    /// Compressed (MJPEG) whenever offered: full frame rate, some artifacts.
+    Compressed,
+    /// Uncompressed (YUYV, NV12, ...) whenever offered, even if slower.
+    Uncompressed,
+}
+
+/// Backoff schedule for reconnecting to a camera after a failed capture
+///
+/// The delay before retry `n` (1-based) is `base_delay_ms * 2^(n-1)`, capped
+/// at `max_delay_ms`. With `jitter` enabled the delay is drawn uniformly from
+/// the upper half of that window so several callers don't retry in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Maximum number of reconnect attempts after the initial capture fails.
+    pub max_attempts: u32,
+    /// Delay before the first retry, in milliseconds.
+    pub base_delay_ms: u64,
+    /// Upper bound for any single delay, in milliseconds.
+    pub max_delay_ms: u64,
+    /// Randomize each delay to spread out concurrent retries.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: CONNECTION_RETRY_DEFAULT,
+            base_delay_ms: CONNECTION_BACKOFF_INITIAL_MS,
+            max_delay_ms: CONNECTION_BACKOFF_MAX_MS,
+            jitter: true,
+        }
+    }
+}
+
+impl From<u32> for RetryPolicy {
+    /// Default backoff without jitter, matching the original retry-count API.
+    fn from(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            jitter: false,
+            ..Self::default()
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// No retries at all: fail on the first error.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Delay to wait before retry `attempt` (1-based).
+    pub fn delay_for(&self, attempt: u32) -> std::time::Duration {
+        let exponent = attempt.saturating_sub(1).min(63);
+        let ceiling = self
+            .base_delay_ms
+            .saturating_mul(1_u64 << exponent)
+            .min(self.max_delay_ms);
+
+        let delay_ms = if self.jitter && ceiling > 1 {
+            let half = ceiling / 2;
+            half + jitter_seed() % (ceiling - half + 1)
+        } else {
+            ceiling
+        };
+        std::time::Duration::from_millis(delay_ms)
+    }
+}
+
+/// Per-call random value for retry jitter, without pulling in an RNG crate.
+fn jitter_seed() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+    std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish()
+}
+
+/// One failed attempt recorded in a [`RetryReport`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryAttempt {
+    /// Attempt number; `0` is the initial capture before any reconnect.
+    pub attempt: u32,
+    /// Milliseconds waited before this attempt.
+    pub delay_ms: u64,
+    /// Error the attempt failed with.
+    pub error: String,
+}
+
+/// Outcome of a capture with reconnect retries
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RetryReport {
+    /// Every failed attempt, in order.
+    pub failures: Vec<RetryAttempt>,
+    /// Whether a frame was eventually captured.
+    pub succeeded: bool,
+}
+
+impl RetryReport {
+    /// Total attempts made, including the initial capture and a final success.
+    pub fn total_attempts(&self) -> usize {
+        self.failures.len() + usize::from(self.succeeded)
+    }
+
+    /// Total time spent waiting between attempts.
+    pub fn total_delay(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.failures.iter().map(|f| f.delay_ms).sum())
+    }
+}
+
 /// Camera initialization parameters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CameraInitParams {
@@ -510,6 +2540,18 @@ pub struct CameraInitParams {
     pub format: CameraFormat,
     /// Initial camera controls.
     pub controls: CameraControls,
+    /// Behaviour when the device is busy (currently honoured on Linux).
+    #[serde(default)]
+    pub open_policy: OpenPolicy,
+    /// Clock used to stamp captured frames.
+    #[serde(default)]
+    pub timestamp_source: TimestampSource,
+    /// Compressed vs uncompressed tradeoff when negotiating the format.
+    #[serde(default)]
+    pub pixel_format: PixelFormatPreference,
+    /// Capture API to open the camera with (`None` = platform default).
+    #[serde(default)]
+    pub backend: Option<CameraBackend>,
 }
 
 impl Default for CameraInitParams {
@@ -525,6 +2567,10 @@ impl CameraInitParams {
             device_id,
             format: CameraFormat::standard(),
             controls: CameraControls::default(),
+            open_policy: OpenPolicy::default(),
+            timestamp_source: TimestampSource::default(),
+            pixel_format: PixelFormatPreference::default(),
+            backend: None,
         }
     }
 
@@ -542,6 +2588,34 @@ impl CameraInitParams {
         self
     }
 
+    /// Set the policy for opening a device held by another process
+    #[must_use]
+    pub fn with_open_policy(mut self, policy: OpenPolicy) -> Self {
+        self.open_policy = policy;
+        self
+    }
+
+    /// Set the clock used to stamp captured frames
+    #[must_use]
+    pub fn with_timestamp_source(mut self, source: TimestampSource) -> Self {
+        self.timestamp_source = source;
+        self
+    }
+
+    /// Set which kind of pixel format to favor when negotiating the format
+    #[must_use]
+    pub fn with_pixel_format(mut self, preference: PixelFormatPreference) -> Self {
+        self.pixel_format = preference;
+        self
+    }
+
+    /// Force the capture API used to open the camera
+    #[must_use]
+    pub fn with_backend(mut self, backend: CameraBackend) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
     /// Enable/disable auto focus
     #[must_use]
     pub fn with_auto_focus(mut self, enabled: bool) -> Self {
@@ -562,6 +2636,10 @@ impl CameraInitParams {
             device_id,
             format: CameraFormat::new(2592, 1944, 15.0), // 5MP high quality
             controls: CameraControls::professional(),
+            open_policy: OpenPolicy::default(),
+            timestamp_source: TimestampSource::default(),
+            pixel_format: PixelFormatPreference::default(),
+            backend: None,
         }
     }
 }
@@ -586,10 +2664,16 @@ mod tests {
             .with_availability(false);
 
         assert_eq!(device.id, "0");
+        assert_eq!(device.stable_id, "0");
         assert_eq!(device.name, "Cam");
         assert_eq!(device.description.as_deref(), Some("Front camera"));
         assert_eq!(device.supports_formats.len(), formats.len());
         assert!(!device.is_available);
+
+        let device = device.with_stable_id("usb-046d:0825-ABC123".to_string());
+        assert!(device.matches_id("0"));
+        assert!(device.matches_id("usb-046d:0825-ABC123"));
+        assert!(!device.matches_id("1"));
     }
 
     #[test]
@@ -607,6 +2691,220 @@ mod tests {
         assert_eq!(mjpeg.height, 600);
         assert!((mjpeg.fps - 24.0).abs() < 1e-6);
         assert_eq!(mjpeg.format_type, "MJPEG");
+        assert_eq!(mjpeg.bit_depth, 8);
+
+        let p010 = CameraFormat::new(3840, 2160, 30.0).with_format_type("P010".to_string());
+        assert_eq!(p010.bit_depth, 10);
+        assert_eq!(p010.clone().with_bit_depth(12).bit_depth, 12);
+        let legacy: CameraFormat =
+            serde_json::from_str(r#"{"width":640,"height":480,"fps":30.0,"format_type":"YUYV"}"#)
+                .expect("format without bit_depth");
+        assert_eq!(legacy.bit_depth, 8);
+    }
+
+    #[test]
+    fn test_format_preset_constructors() {
+        assert_eq!(CameraFormat::fhd(), CameraFormat::hd());
+        assert_eq!(
+            (CameraFormat::qvga().width, CameraFormat::qvga().height),
+            (320, 240)
+        );
+        assert_eq!(CameraFormat::hd_720(), CameraFormat::standard());
+        assert_eq!(CameraFormat::vga(), CameraFormat::low());
+        let pal = CameraFormat::sd_pal();
+        assert_eq!((pal.width, pal.height), (720, 576));
+        assert!((pal.fps - 25.0).abs() < 1e-6);
+
+        let widths: Vec<u32> = FormatPreset::ALL.iter().map(|p| p.resolution().0).collect();
+        assert!(widths.windows(2).all(|w| w[0] < w[1]));
+
+        let custom = CameraFormat::uhd_4k()
+            .with_fps(60.0)
+            .with_resolution(2560, 1440);
+        assert_eq!((custom.width, custom.height), (2560, 1440));
+        assert!((custom.fps - 60.0).abs() < 1e-6);
+
+        let info = FormatPresetInfo::from(FormatPreset::Hd720);
+        assert_eq!(info.label, "HD 720p (1280×720)");
+        assert_eq!(
+            serde_json::to_string(&info.preset).expect("serialize"),
+            "\"hd720\""
+        );
+    }
+
+    #[test]
+    fn test_fourcc_aliases_normalize() {
+        let pairs = [
+            ("MJPG", FourCC::MJPEG),
+            ("mjpeg", FourCC::MJPEG),
+            ("JPEG", FourCC::MJPEG),
+            ("YUY2", FourCC::YUYV),
+            ("yuyv", FourCC::YUYV),
+            ("RGB24", FourCC::RGB8),
+            ("RAWRGB", FourCC::RGB8),
+            ("RGB8", FourCC::RGB8),
+            ("RGBA", FourCC::RGBA8),
+            ("RGBA32", FourCC::RGBA8),
+            ("BGR24", FourCC::BGR8),
+            ("RAWBGR", FourCC::BGR8),
+            ("GREY", FourCC::GRAY),
+            ("nv12", FourCC::NV12),
+            ("p010", FourCC::P010),
+            ("RGB48", FourCC::RGB16),
+        ];
+        for (alias, canonical) in pairs {
+            let parsed: FourCC = alias.parse().expect("alias should parse");
+            assert_eq!(parsed, canonical, "{alias} should normalize");
+        }
+
+        let unknown: FourCC = "h264".parse().expect("unknown codes are kept");
+        assert_eq!(unknown.as_str(), "H264");
+        assert!(unknown.is_compressed());
+        assert!(FourCC::MJPEG.is_compressed());
+        assert!(!FourCC::YUYV.is_compressed());
+        assert!("".parse::<FourCC>().is_err());
+        assert!("RGB 8".parse::<FourCC>().is_err());
+    }
+
+    #[test]
+    fn test_fourcc_serde_uses_canonical_string() {
+        let json = serde_json::to_string(&FourCC::MJPEG).expect("serialize");
+        assert_eq!(json, "\"MJPEG\"");
+        let parsed: FourCC = serde_json::from_str("\"MJPG\"").expect("deserialize");
+        assert_eq!(parsed, FourCC::MJPEG);
+    }
+
+    #[test]
+    fn test_camera_format_matches_normalizes_codes() {
+        let canonical = CameraFormat::from_fourcc(1280, 720, 30.0, FourCC::MJPEG);
+        assert_eq!(canonical.format_type, "MJPEG");
+        assert_eq!(canonical.fourcc(), Some(FourCC::MJPEG));
+
+        let alias = CameraFormat::new(1280, 720, 30.0).with_format_type("MJPG".to_string());
+        assert!(canonical.matches(&alias));
+        assert_ne!(canonical, alias, "PartialEq stays a literal comparison");
+
+        let alias_422 = CameraFormat::new(640, 480, 30.0).with_format_type("YUY2".to_string());
+        let canonical_422 = CameraFormat::from_fourcc(640, 480, 30.0, FourCC::YUYV);
+        assert!(alias_422.matches(&canonical_422));
+
+        assert!(!canonical.matches(&canonical_422));
+        assert!(!canonical.matches(&CameraFormat::from_fourcc(1280, 720, 15.0, FourCC::MJPEG)));
+    }
+
+    #[test]
+    fn test_white_balance_preset_kelvin() {
+        let kelvins: Vec<u32> = [
+            WhiteBalancePreset::Tungsten,
+            WhiteBalancePreset::Fluorescent,
+            WhiteBalancePreset::Daylight,
+            WhiteBalancePreset::Cloudy,
+            WhiteBalancePreset::Shade,
+        ]
+        .into_iter()
+        .map(WhiteBalancePreset::kelvin)
+        .collect();
+        assert_eq!(kelvins, vec![3200, 4000, 5600, 6500, 7500]);
+    }
+
+    #[test]
+    fn test_retry_policy_exponential_backoff_is_capped() {
+        let policy = RetryPolicy {
+            max_attempts: 6,
+            base_delay_ms: 100,
+            max_delay_ms: 500,
+            jitter: false,
+        };
+        let delays: Vec<u128> = (1..=5).map(|n| policy.delay_for(n).as_millis()).collect();
+        assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+        assert_eq!(policy.delay_for(u32::MAX).as_millis(), 500);
+    }
+
+    #[test]
+    fn test_retry_policy_jitter_stays_in_upper_half() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 400,
+            max_delay_ms: 10_000,
+            jitter: true,
+        };
+        for _ in 0..50 {
+            let ms = policy.delay_for(2).as_millis();
+            assert!(
+                (400..=800).contains(&ms),
+                "jittered delay {ms} out of range"
+            );
+        }
+    }
+
+    #[test]
+    fn test_retry_policy_from_count_keeps_legacy_schedule() {
+        let policy = RetryPolicy::from(4);
+        assert_eq!(policy.max_attempts, 4);
+        assert!(!policy.jitter);
+        assert_eq!(
+            policy.delay_for(1).as_millis(),
+            u128::from(CONNECTION_BACKOFF_INITIAL_MS)
+        );
+    }
+
+    #[test]
+    fn test_controls_patch_only_touches_set_fields() {
+        let mut controls = CameraControls::default();
+        let patch = CameraControlsPatch {
+            focus_distance: Some(0.7),
+            brightness: Some(0.2),
+            ..Default::default()
+        };
+        assert!(!patch.is_empty());
+        assert!(CameraControlsPatch::default().is_empty());
+
+        controls.apply_patch(&patch);
+        assert_eq!(controls.focus_distance, Some(0.7));
+        assert_eq!(controls.brightness, Some(0.2));
+        assert_eq!(controls.auto_exposure, Some(true), "untouched by patch");
+        assert_eq!(controls.white_balance, Some(WhiteBalance::Auto));
+
+        let sparse = CameraControls::from(patch);
+        assert_eq!(sparse.focus_distance, Some(0.7));
+        assert!(sparse.auto_exposure.is_none());
+    }
+
+    #[test]
+    fn test_controls_patch_merge_prefers_later_values() {
+        let mut patch = CameraControlsPatch {
+            focus_distance: Some(0.7),
+            brightness: Some(0.2),
+            ..Default::default()
+        };
+        patch.merge(CameraControlsPatch {
+            focus_distance: Some(0.3),
+            white_balance: Some(WhiteBalance::Daylight),
+            ..Default::default()
+        });
+        assert_eq!(patch.focus_distance, Some(0.3));
+        assert_eq!(patch.brightness, Some(0.2), "kept when not set later");
+        assert_eq!(patch.white_balance, Some(WhiteBalance::Daylight));
+    }
+
+    #[test]
+    fn test_controls_diff_roundtrip() {
+        let current = CameraControls::default();
+        let mut target = current.clone();
+        target.zoom = Some(2.0);
+        target.white_balance = Some(WhiteBalance::Custom(4500));
+        target.aperture = None;
+
+        let patch = current.diff(&target);
+        assert_eq!(patch.zoom, Some(2.0));
+        assert_eq!(patch.white_balance, Some(WhiteBalance::Custom(4500)));
+        assert!(patch.brightness.is_none(), "unchanged fields are omitted");
+        assert!(patch.aperture.is_none(), "None in target is not a change");
+
+        let mut applied = current.clone();
+        applied.apply_patch(&patch);
+        assert_eq!(applied, target);
+        assert!(target.diff(&target).is_empty());
     }
 
     #[test]
@@ -626,10 +2924,99 @@ mod tests {
         let yuyv = frame.clone().with_format("YUYV".to_string());
         assert_eq!(yuyv.format, "YUYV");
 
+        assert_eq!(frame.to_rgb8().expect("rgb passthrough"), data);
+        let unknown = frame.clone().with_format("H264".to_string());
+        assert!(unknown.to_rgb8().is_err());
+
         let invalid = CameraFrame::new(Vec::new(), 640, 480, "dev-1".to_string());
         assert!(!invalid.is_valid());
     }
 
+    #[test]
+    fn test_camera_frame_validate() {
+        let complete = CameraFrame::new(vec![0; 2 * 2 * 3], 2, 2, "dev-0".to_string());
+        assert!(complete.validate().is_ok());
+
+        let torn = CameraFrame::new(vec![0; 5], 2, 2, "dev-0".to_string());
+        let msg = torn.validate().expect_err("short buffer").to_string();
+        assert!(msg.contains("incomplete frame: got 5 bytes, expected 12"));
+
+        let jpeg = |data: Vec<u8>| {
+            CameraFrame::new(data, 2, 2, "dev-0".to_string()).with_format("MJPG".to_string())
+        };
+        assert!(jpeg(vec![0xFF, 0xD8, 0x00, 0xFF, 0xD9]).validate().is_ok());
+        assert!(jpeg(vec![0xFF, 0xD8, 0x00, 0xFF, 0xD9, 0, 0])
+            .validate()
+            .is_ok());
+        assert!(jpeg(vec![0xFF, 0xD8, 0x00, 0x12]).validate().is_err());
+        assert!(jpeg(vec![0x00, 0x00, 0xFF, 0xD9]).validate().is_err());
+    }
+
+    #[test]
+    fn test_rgba_roundtrip_defaults_to_opaque() {
+        let rgb = vec![10, 20, 30, 40, 50, 60, 70, 80, 90, 100, 110, 120];
+        let frame = CameraFrame::new(rgb.clone(), 2, 2, "dev-0".to_string());
+        assert!(!frame.is_rgba());
+
+        let rgba = frame.to_rgba8().expect("RGB to RGBA");
+        assert_eq!(rgba.len(), 2 * 2 * 4);
+        assert!(rgba.chunks_exact(4).all(|px| px[3] == u8::MAX));
+
+        let rgba_frame = CameraFrame::new(rgba.clone(), 2, 2, "dev-0".to_string())
+            .with_format(crate::constants::FORMAT_RGBA.to_string());
+        assert!(rgba_frame.is_rgba());
+        assert!(rgba_frame.validate().is_ok());
+        assert_eq!(rgba_frame.to_rgba8().expect("RGBA passthrough"), rgba);
+        assert_eq!(rgba_frame.to_rgb8().expect("RGBA to RGB"), rgb);
+        assert_eq!(rgba_frame.rgb_data().as_ref(), rgb.as_slice());
+    }
+
+    #[test]
+    fn test_rgb16_frame_keeps_full_depth() {
+        let samples: [u16; 6] = [0, 1023 << 6, u16::MAX, 0x1234, 0x8000, 0x00FF];
+        let data = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let frame = CameraFrame::new(data, 2, 1, "dev-0".to_string())
+            .with_format(crate::constants::FORMAT_RGB16.to_string());
+
+        assert_eq!(frame.bit_depth(), 16);
+        assert!(frame.validate().is_ok());
+        assert_eq!(frame.to_rgb16().expect("RGB16 passthrough"), samples);
+        assert_eq!(
+            frame.rgb_data().as_ref(),
+            &[0, 255, 255, 0x12, 0x80, 0x01],
+            "rounded to 8 bits for analysis"
+        );
+        assert_eq!(frame.tone_map_to_rgb8().expect("tone map").len(), 6);
+
+        let rgb8 = CameraFrame::new(vec![0, 128, 255], 1, 1, "dev-0".to_string());
+        assert_eq!(rgb8.to_rgb16().expect("widen"), [0, 128 * 257, u16::MAX]);
+    }
+
+    #[test]
+    fn test_depth_frame_reads_meters() {
+        let raw: [u16; 4] = [0, 1000, 2500, u16::MAX];
+        let data = raw.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let mut frame =
+            CameraFrame::new(data, 2, 2, "depth-0".to_string()).with_format("Z16 ".to_string());
+
+        assert!(frame.is_depth());
+        assert!(frame.validate().is_ok());
+        assert_eq!(frame.depth_at(0, 0), None, "0 means no reading");
+        assert_eq!(frame.depth_at(2, 0), None);
+        let one_meter = frame.depth_at(1, 0).expect("depth");
+        assert!((one_meter - 1.0).abs() < 1e-6);
+
+        frame.metadata.depth_units = Some(0.0001);
+        let quarter = frame.depth_at(0, 1).expect("depth");
+        assert!((quarter - 0.25).abs() < 1e-6);
+
+        assert_eq!(frame.to_rgb16().expect("gray")[3..6], [1000; 3]);
+        assert!(frame.rotate(90).is_ok());
+        assert!(CameraFrame::new(vec![0; 3], 1, 1, "c".to_string())
+            .depth_at(0, 0)
+            .is_none());
+    }
+
     #[test]
     fn test_control_application_result_fully_applied() {
         let ok = ControlApplicationResult {
@@ -682,6 +3069,52 @@ mod tests {
         assert!(caps.supports.auto_exposure);
         assert_eq!(caps.max_resolution, (1920, 1080));
         assert!((caps.max_fps - 30.0).abs() < 1e-6);
+        assert_eq!(caps.provenance.max_resolution, CapabilitySource::Probed);
+    }
+
+    #[test]
+    fn test_control_range_linear_scaling() {
+        // Typical UVC brightness: -64..64 in steps of 1, default 0
+        let brightness = ControlRange::linear("brightness", (0.0, 1.0), (-64, 64), 1, 0);
+        assert!((brightness.default.unwrap() - 0.5).abs() < 1e-6);
+        assert!((brightness.step.unwrap() - 1.0 / 128.0).abs() < 1e-6);
+        assert!((brightness.max - 1.0).abs() < f32::EPSILON);
+
+        // Exposure in 100 us units maps onto seconds
+        let exposure = ControlRange::linear("exposure_time", (0.0003, 0.2047), (3, 2047), 1, 156);
+        assert!((exposure.default.unwrap() - 0.0156).abs() < 1e-6);
+        assert!((exposure.step.unwrap() - 0.0001).abs() < 1e-7);
+
+        let fixed = ControlRange::linear("zoom", (0.0, 1.0), (5, 5), 1, 5);
+        assert_eq!(fixed.step, None);
+        assert_eq!(fixed.default, Some(0.0));
+
+        let caps = CameraCapabilities {
+            controls: vec![brightness],
+            ..CameraCapabilities::default()
+        };
+        assert!(caps.control("brightness").is_some());
+        assert!(caps.control("zoom").is_none());
+    }
+
+    #[test]
+    fn test_capabilities_from_device_info() {
+        let info = CameraDeviceInfo::new("0".to_string(), "Cam".to_string()).with_formats(vec![
+            CameraFormat::new(640, 480, 60.0),
+            CameraFormat::new(1920, 1080, 30.0),
+        ]);
+
+        let caps = CameraCapabilities::from_device_info(&info);
+        assert_eq!(caps.max_resolution, (1920, 1080));
+        assert!((caps.max_fps - 60.0).abs() < 1e-6);
+        assert_eq!(caps.provenance.max_resolution, CapabilitySource::Reported);
+        assert_eq!(caps.provenance.max_fps, CapabilitySource::Reported);
+        assert_eq!(caps.provenance.supports, CapabilitySource::Assumed);
+
+        let empty = CameraDeviceInfo::new("1".to_string(), "Bare".to_string());
+        let caps = CameraCapabilities::from_device_info(&empty);
+        assert_eq!(caps.max_resolution, (1920, 1080));
+        assert_eq!(caps.provenance.max_resolution, CapabilitySource::Assumed);
     }
 
     #[test]
@@ -690,6 +3123,7 @@ mod tests {
         assert!(meta.exposure_time.is_none());
         assert!(meta.iso_sensitivity.is_none());
         assert!(meta.capture_settings.is_none());
+        assert!(meta.zoom.is_none());
 
         let perf = CameraPerformanceMetrics::default();
         assert!(perf.capture_latency_ms.abs() < 1e-6);
@@ -699,6 +3133,291 @@ mod tests {
         assert!(perf.quality_score.abs() < 1e-6);
     }
 
+    /// 3x2 GRAY frame with pixel values 0..6 in row-major order
+    fn numbered_frame() -> CameraFrame {
+        CameraFrame::new((0..6).collect(), 3, 2, "orient".to_string()).with_format("GRAY".into())
+    }
+
+    #[test]
+    fn test_frame_flip_axes() {
+        let mut frame = numbered_frame();
+        frame.flip(FlipAxis::Horizontal).unwrap();
+        assert_eq!(frame.data, [2, 1, 0, 5, 4, 3]);
+
+        let mut frame = numbered_frame();
+        frame.flip(FlipAxis::Vertical).unwrap();
+        assert_eq!(frame.data, [3, 4, 5, 0, 1, 2]);
+
+        let mut frame = numbered_frame();
+        frame.flip(FlipAxis::Both).unwrap();
+        assert_eq!(frame.data, [5, 4, 3, 2, 1, 0]);
+
+        let mut rgb = CameraFrame::new(vec![1, 2, 3, 4, 5, 6], 2, 1, "orient".to_string());
+        rgb.flip(FlipAxis::Horizontal).unwrap();
+        assert_eq!(rgb.data, [4, 5, 6, 1, 2, 3], "pixels move whole");
+
+        let mut yuv = numbered_frame().with_format("YUYV".into());
+        assert!(matches!(
+            yuv.flip(FlipAxis::Horizontal),
+            Err(CameraError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_frame_rotate_and_transform() {
+        // 0 1 2      90:  3 0     270:  2 5
+        // 3 4 5           4 1           1 4
+        //                 5 2           0 3
+        let mut frame = numbered_frame();
+        frame.rotate(90).unwrap();
+        assert_eq!((frame.width, frame.height), (2, 3));
+        assert_eq!(frame.data, [3, 0, 4, 1, 5, 2]);
+
+        let mut frame = numbered_frame();
+        frame.rotate(270).unwrap();
+        assert_eq!(frame.data, [2, 5, 1, 4, 0, 3]);
+
+        let mut frame = numbered_frame();
+        frame.rotate(180).unwrap();
+        assert_eq!(frame.data, [5, 4, 3, 2, 1, 0]);
+        assert!(frame.rotate(45).is_err());
+
+        let transform = FrameTransform {
+            flip: Some(FlipAxis::Horizontal),
+            rotation_degrees: 90,
+        };
+        let mut frame = numbered_frame();
+        frame.apply_transform(transform).unwrap();
+        assert_eq!(frame.data, [5, 2, 4, 1, 3, 0]);
+        assert_eq!(frame.metadata.orientation, Some(transform));
+
+        let mut untouched = numbered_frame();
+        untouched
+            .apply_transform(FrameTransform::default())
+            .unwrap();
+        assert!(untouched.metadata.orientation.is_none());
+    }
+
+    #[test]
+    fn test_frame_mse_psnr_and_duplicates() {
+        let frame = CameraFrame::new(vec![100; 4 * 4 * 3], 4, 4, "diff".to_string());
+        let same = CameraFrame::new(vec![100; 4 * 4 * 3], 4, 4, "diff".to_string());
+        assert!(frame.mse(&same).unwrap().abs() < f64::EPSILON);
+        assert!(frame.psnr(&same).unwrap().is_infinite());
+        assert!(frame.is_duplicate(&same, 0.0));
+
+        // Every sample off by 5: MSE 25, PSNR = 10 log10(255^2 / 25)
+        let offset = CameraFrame::new(vec![105; 4 * 4 * 3], 4, 4, "diff".to_string());
+        assert!((frame.mse(&offset).unwrap() - 25.0).abs() < 1e-9);
+        let expected_psnr = 10.0 * (255.0_f64 * 255.0 / 25.0).log10();
+        assert!((frame.psnr(&offset).unwrap() - expected_psnr).abs() < 1e-9);
+        assert!(!frame.is_duplicate(&offset, 10.0));
+        assert!(frame.is_duplicate(&offset, 25.0));
+
+        let small = CameraFrame::new(vec![100; 2 * 2 * 3], 2, 2, "diff".to_string());
+        assert!(frame.mse(&small).is_err());
+        assert!(frame.psnr(&small).is_err());
+        assert!(!frame.is_duplicate(&small, f64::MAX));
+    }
+
+    #[test]
+    fn test_content_hash_dedups_and_detects_changes() {
+        let data: Vec<u8> = (0..=191).collect();
+        let frame = CameraFrame::new(data.clone(), 8, 8, "hash".to_string());
+        let same = CameraFrame::new(data.clone(), 8, 8, "other-device".to_string());
+        assert_eq!(frame.content_hash(), same.content_hash());
+
+        let mut changed = data.clone();
+        changed[100] ^= 1;
+        let changed = CameraFrame::new(changed, 8, 8, "hash".to_string());
+        assert_ne!(frame.content_hash(), changed.content_hash());
+        // Same bytes at another shape are a different frame
+        let reshaped = CameraFrame::new(data, 16, 4, "hash".to_string());
+        assert_ne!(frame.content_hash(), reshaped.content_hash());
+
+        let mut stored = frame.clone();
+        assert_eq!(stored.verify_content_hash(), None);
+        stored.record_content_hash();
+        assert_eq!(stored.verify_content_hash(), Some(true));
+        let json = serde_json::to_value(&stored.metadata).unwrap();
+        assert_eq!(
+            json["content_hash"],
+            format!("{:016x}", frame.content_hash())
+        );
+        let back: FrameMetadata = serde_json::from_value(json).unwrap();
+        assert_eq!(back.content_hash, Some(frame.content_hash()));
+        stored.data[0] ^= 0xff;
+        assert_eq!(stored.verify_content_hash(), Some(false));
+    }
+
+    #[test]
+    fn test_exposure_value_conversions() {
+        let range = (0.0001, 10.0);
+        let close = |a: f32, b: f32| (a - b).abs() <= b.abs() * 1e-4;
+        assert!(close(
+            ExposureValue::Seconds(0.004).to_seconds(range),
+            0.004
+        ));
+        assert!(close(
+            ExposureValue::Ev(-8.0).to_seconds(range),
+            1.0 / 256.0
+        ));
+        assert!(close(
+            ExposureValue::Normalized(0.0).to_seconds(range),
+            0.0001
+        ));
+        assert!(close(
+            ExposureValue::Normalized(1.0).to_seconds(range),
+            10.0
+        ));
+        // Evenly spaced in stops: the midpoint is the geometric mean
+        assert!(close(
+            ExposureValue::Normalized(0.5).to_seconds(range),
+            0.031_623
+        ));
+        assert!(close(
+            ExposureValue::Normalized(7.0).to_seconds(range),
+            10.0
+        ));
+
+        let reading = ExposureReading::new(1.0 / 256.0, range);
+        assert!(close(reading.ev, -8.0));
+        assert!(close(
+            ExposureValue::Normalized(reading.normalized).to_seconds(range),
+            1.0 / 256.0
+        ));
+        assert!(close(reading.max_seconds, 10.0));
+
+        // A zero minimum does not break the log scale
+        let reading = ExposureReading::new(0.0, (0.0, 1.0));
+        assert!(reading.normalized.abs() < f32::EPSILON);
+        let json = serde_json::to_string(&ExposureValue::Ev(-8.0)).unwrap();
+        assert_eq!(json, r#"{"ev":-8.0}"#);
+    }
+
+    #[test]
+    fn test_gain_value_conversions() {
+        let range = (100, 6400);
+        assert_eq!(GainValue::Iso(800).to_iso(range), 800);
+        assert_eq!(GainValue::Db(0.0).to_iso(range), 100);
+        assert_eq!(GainValue::Db(20.0).to_iso(range), 1000);
+        assert_eq!(GainValue::Db(-6.0206).to_iso(range), 50);
+        assert_eq!(GainValue::Normalized(0.0).to_iso(range), 100);
+        assert_eq!(GainValue::Normalized(0.5).to_iso(range), 800);
+        assert_eq!(GainValue::Normalized(1.0).to_iso(range), 6400);
+
+        let reading = GainReading::new(400, range);
+        assert!((reading.db - 12.0412).abs() < 1e-3);
+        assert!((reading.normalized - 1.0 / 3.0).abs() < 1e-6);
+        assert_eq!(GainValue::Db(reading.db).to_iso(range), 400);
+        assert_eq!(
+            serde_json::from_str::<GainValue>(r#"{"db":6.0}"#).unwrap(),
+            GainValue::Db(6.0)
+        );
+    }
+
+    #[test]
+    fn test_focus_mode_patch_and_result() {
+        let infinity = FocusMode::Infinity.to_patch();
+        assert_eq!(infinity.auto_focus, Some(false));
+        assert_eq!(infinity.focus_distance, Some(0.0));
+        assert_eq!(FocusMode::Macro.distance(), Some(1.0));
+        let continuous = FocusMode::Continuous.to_patch();
+        assert_eq!(continuous.auto_focus, Some(true));
+        assert_eq!(continuous.focus_distance, None);
+
+        let fixed_focus_camera = ControlApplicationResult {
+            applied: vec!["auto_focus".to_string()],
+            rejected: vec!["focus_distance".to_string()],
+        };
+        let result = FocusModeResult::from_application(FocusMode::Infinity, fixed_focus_camera);
+        assert_eq!(result.support, FocusModeSupport::Unsupported);
+        assert_eq!(result.focus_distance, None);
+
+        let accepted = ControlApplicationResult {
+            applied: vec!["auto_focus".to_string(), "focus_distance".to_string()],
+            rejected: vec![],
+        };
+        let result = FocusModeResult::from_application(FocusMode::Macro, accepted);
+        assert_eq!(result.support, FocusModeSupport::Hardware);
+        assert_eq!(result.focus_distance, Some(1.0));
+
+        let json = serde_json::to_string(&FocusMode::Manual { distance: 0.5 }).unwrap();
+        assert_eq!(json, r#"{"Manual":{"distance":0.5}}"#);
+    }
+
+    #[test]
+    fn test_exposure_mode_patch_and_result() {
+        let patch = ExposureMode::ShutterPriority { shutter: 0.01 }.to_patch();
+        assert_eq!(patch.auto_exposure, Some(false));
+        assert_eq!(patch.exposure_time, Some(0.01));
+        assert!(patch.iso_sensitivity.is_none(), "ISO stays automatic");
+        assert_eq!(ExposureMode::Auto.to_patch().auto_exposure, Some(true));
+
+        let mode = ExposureMode::Manual {
+            shutter: 0.01,
+            iso: 800,
+        };
+        let result = ExposureModeResult::from_application(
+            mode,
+            ControlApplicationResult {
+                applied: vec!["auto_exposure".to_string(), "exposure_time".to_string()],
+                rejected: vec![],
+            },
+        );
+        assert_eq!(result.shutter_honored, Some(true));
+        assert_eq!(result.iso_honored, Some(false), "ISO silently ignored");
+        assert!(!result.fully_honored());
+    }
+
+    #[test]
+    fn test_frame_metadata_record_controls() {
+        let mut meta = FrameMetadata {
+            aperture: Some(2.8),
+            ..FrameMetadata::default()
+        };
+        let controls = CameraControls {
+            exposure_time: Some(0.01),
+            iso_sensitivity: Some(800),
+            focus_distance: Some(0.25),
+            zoom: Some(2.0),
+            aperture: None,
+            ..CameraControls::default()
+        };
+
+        meta.record_controls(&controls);
+        assert_eq!(meta.exposure_time, Some(0.01));
+        assert_eq!(meta.iso_sensitivity, Some(800));
+        assert_eq!(meta.focus_distance, Some(0.25));
+        assert_eq!(meta.white_balance, Some(WhiteBalance::Auto));
+        assert_eq!(meta.zoom, Some(2.0));
+        assert_eq!(meta.aperture, Some(2.8), "unreported fields are kept");
+        assert_eq!(meta.capture_settings, Some(controls));
+    }
+
+    #[test]
+    fn test_camera_backend_availability() {
+        assert!(CameraBackend::Auto.validate().is_ok());
+        assert!(CameraBackend::DirectShow.validate().is_err());
+        assert_eq!(
+            CameraBackend::V4l2.validate().is_ok(),
+            cfg!(target_os = "linux")
+        );
+        assert_eq!(
+            CameraBackend::MediaFoundation.validate().is_ok(),
+            cfg!(target_os = "windows")
+        );
+        let params: CameraInitParams = serde_json::from_value(serde_json::json!({
+            "device_id": "0",
+            "format": CameraFormat::standard(),
+            "controls": CameraControls::default(),
+            "backend": "av_foundation",
+        }))
+        .unwrap();
+        assert_eq!(params.backend, Some(CameraBackend::AvFoundation));
+        assert_eq!(CameraInitParams::default().backend, None);
+    }
+
     #[test]
     fn test_camera_init_params_builders_and_professional() {
         let default_params = CameraInitParams::default();
@@ -717,6 +3436,14 @@ mod tests {
         assert_eq!(built.format, custom_format);
         assert_eq!(built.controls.auto_focus, Some(false));
         assert_eq!(built.controls.auto_exposure, Some(false));
+        assert_eq!(built.open_policy, OpenPolicy::FailFast);
+
+        let patient = CameraInitParams::new("3".to_string())
+            .with_open_policy(OpenPolicy::RetryFor(std::time::Duration::from_secs(2)));
+        assert_eq!(
+            patient.open_policy,
+            OpenPolicy::RetryFor(std::time::Duration::from_secs(2))
+        );
 
         let pro = CameraInitParams::professional("9".to_string());
         assert_eq!(pro.device_id, "9");
@@ -224,27 +224,30 @@ fn test_capture_format_handling() {
     let formats = vec![
         (48000, 1), // Standard mono
         (48000, 2), // Standard stereo
-        (44100, 2), // CD quality (should be resampled to 48kHz)
+        (44100, 2), // CD quality (device audio is resampled to it)
     ];
 
     for (requested_rate, channels) in formats {
         match AudioCapture::new(None, requested_rate, channels, clock.clone()) {
             Ok(capture) => {
-                // The capture might adjust the format to what's actually supported
+                // Frames are resampled to the requested rate whatever the device runs at
                 let actual_rate = capture.sample_rate();
                 let actual_channels = capture.channels();
 
                 println!(
-                    "Requested {}Hz/{}ch, got {}Hz/{}ch",
-                    requested_rate, channels, actual_rate, actual_channels
+                    "Requested {}Hz/{}ch, got {}Hz/{}ch (device {}Hz)",
+                    requested_rate,
+                    channels,
+                    actual_rate,
+                    actual_channels,
+                    capture.actual_device_rate()
                 );
 
-                // Should be reasonable values
-                assert!(
-                    actual_rate == 44100 || actual_rate == 48000,
-                    "Sample rate should be standard: {}",
-                    actual_rate
+                assert_eq!(
+                    actual_rate, requested_rate,
+                    "Delivered rate should match the request"
                 );
+                assert!(capture.actual_device_rate() > 0);
                 assert!(
                     (1..=2).contains(&actual_channels),
                     "Channels should be 1 or 2: {}",
@@ -163,9 +163,11 @@ fn test_av_recording_config_with_audio() {
     // Create config with audio enabled
     let config = RecordingConfig::new(320, 240, 30.0).with_audio(AudioConfig {
         device_id: None, // Default device
+        device_kind: crabcamera::audio::DeviceKind::Input,
         sample_rate: 48000,
         channels: 2,
         bitrate: 128_000,
+        auto_fallback: false,
     });
 
     // Try to create recorder - this tests audio track configuration
@@ -260,9 +262,11 @@ fn test_full_av_recording_produces_valid_file() {
     // Create config with audio
     let config = RecordingConfig::new(320, 240, 30.0).with_audio(AudioConfig {
         device_id: None,
+        device_kind: crabcamera::audio::DeviceKind::Input,
         sample_rate: 48000,
         channels: 2,
         bitrate: 128_000,
+        auto_fallback: false,
     });
 
     let mut recorder = Recorder::new(&output, config).expect("Recorder should create");
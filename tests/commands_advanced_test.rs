@@ -12,12 +12,12 @@
 //! - Performance testing for advanced operations
 
 use crabcamera::commands::advanced::{
-    apply_camera_settings, capture_burst_sequence, capture_focus_stack_legacy,
-    capture_hdr_sequence, get_camera_controls, get_camera_performance, set_camera_controls,
-    set_manual_exposure, set_manual_focus, set_white_balance,
+    apply_camera_settings, capture_autoframe, capture_burst_sequence, capture_focus_stack_legacy,
+    capture_hdr_sequence, get_camera_controls, get_camera_performance, patch_camera_controls,
+    set_camera_controls, set_manual_exposure, set_manual_focus, set_white_balance,
     test_camera_capabilities as test_capabilities, CameraSettingsInput,
 };
-use crabcamera::types::{BurstConfig, CameraControls, WhiteBalance};
+use crabcamera::types::{BurstConfig, CameraControls, CameraControlsPatch, WhiteBalance};
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex as AsyncMutex;
 
@@ -103,6 +103,60 @@ async fn test_set_get_camera_controls() {
     let _ = set_camera_controls(device_id, default_controls).await;
 }
 
+/// Test that patching controls leaves unspecified controls untouched
+#[tokio::test]
+async fn test_patch_camera_controls_partial_update() {
+    let _lock = TEST_LOCK.lock().await;
+    let device_id = TEST_DEVICE_ID.to_string();
+
+    if let Err(e) = set_camera_controls(device_id.clone(), create_test_controls()).await {
+        println!(
+            "Warning: Camera control test failed (expected in CI): {}",
+            e
+        );
+        return;
+    }
+
+    let patch = CameraControlsPatch {
+        zoom: Some(2.0),
+        ..Default::default()
+    };
+    match patch_camera_controls(device_id.clone(), patch).await {
+        Ok(effective) => {
+            assert_eq!(effective.zoom, Some(2.0));
+            assert_eq!(
+                effective.iso_sensitivity,
+                Some(400),
+                "ISO must be untouched"
+            );
+            assert_eq!(effective.aperture, Some(5.6), "aperture must be untouched");
+        }
+        Err(e) => println!(
+            "Warning: Patch controls test failed (expected in CI): {}",
+            e
+        ),
+    }
+
+    let _ = set_camera_controls(device_id, CameraControls::default()).await;
+}
+
+/// Test that auto-framing returns a frame at the requested aspect ratio
+#[tokio::test]
+async fn test_capture_autoframe_aspect_and_crop() {
+    match capture_autoframe("test_camera_autoframe".to_string(), 1.0, 0.5).await {
+        Ok(frame) => {
+            assert_eq!(frame.width, frame.height, "1:1 output");
+            let crop = frame.metadata.crop.expect("crop should be recorded");
+            assert!(crop.width > 0 && crop.height > 0);
+        }
+        Err(e) => println!("Warning: Auto-frame test failed (expected in CI): {}", e),
+    }
+
+    assert!(capture_autoframe(TEST_DEVICE_ID.to_string(), 0.0, 0.5)
+        .await
+        .is_err());
+}
+
 /// Test manual focus control with parameter validation
 #[tokio::test]
 async fn test_manual_focus_control() {
@@ -117,13 +171,13 @@ async fn test_manual_focus_control() {
             Ok(_) => {
                 // Success is good
             }
-            Err(e) if e.contains("mutex") || e.contains("camera") => {
+            Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
                 // Expected in CI without camera hardware
                 println!("Warning: Focus test failed (expected in CI): {}", e);
             }
             Err(e) => {
                 // Should not be a parameter validation error
-                assert!(!e.contains("Focus distance must be"));
+                assert!(!e.message.contains("Focus distance must be"));
             }
         }
     }
@@ -135,7 +189,7 @@ async fn test_manual_focus_control() {
         let result = set_manual_focus(device_id.clone(), *distance).await;
         assert!(result.is_err());
         if let Err(e) = result {
-            assert!(e.contains("Focus distance must be between 0.0"));
+            assert!(e.message.contains("Focus distance must be between 0.0"));
         }
     }
 }
@@ -159,14 +213,14 @@ async fn test_manual_exposure_settings() {
             Ok(_) => {
                 // Success is good
             }
-            Err(e) if e.contains("mutex") || e.contains("camera") => {
+            Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
                 // Expected in CI
                 println!("Warning: Exposure test failed (expected in CI): {}", e);
             }
             Err(e) => {
                 // Should not be parameter validation errors
-                assert!(!e.contains("Exposure time must be"));
-                assert!(!e.contains("ISO sensitivity must be"));
+                assert!(!e.message.contains("Exposure time must be"));
+                assert!(!e.message.contains("ISO sensitivity must be"));
             }
         }
     }
@@ -177,7 +231,7 @@ async fn test_manual_exposure_settings() {
         let result = set_manual_exposure(device_id.clone(), *exposure, 400).await;
         assert!(result.is_err());
         if let Err(e) = result {
-            assert!(e.contains("Exposure time must be"));
+            assert!(e.message.contains("Exposure time must be"));
         }
     }
 
@@ -187,7 +241,7 @@ async fn test_manual_exposure_settings() {
         let result = set_manual_exposure(device_id.clone(), 1.0 / 125.0, *iso).await;
         assert!(result.is_err());
         if let Err(e) = result {
-            assert!(e.contains("ISO sensitivity must be"));
+            assert!(e.message.contains("ISO sensitivity must be"));
         }
     }
 }
@@ -214,7 +268,7 @@ async fn test_white_balance_modes() {
             Ok(_) => {
                 // Success is good
             }
-            Err(e) if e.contains("mutex") || e.contains("camera") => {
+            Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
                 // Expected in CI
                 println!("Warning: WB test failed (expected in CI): {}", e);
             }
@@ -250,7 +304,7 @@ async fn test_burst_sequence_capture() {
                 assert!(!frame.data.is_empty());
             }
         }
-        Err(e) if e.contains("mutex") || e.contains("camera") => {
+        Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
             // Expected in CI
             println!("Warning: Burst test failed (expected in CI): {}", e);
         }
@@ -296,7 +350,7 @@ async fn test_exposure_bracketing() {
                 println!("Bracketed frame {}: {} bytes", i + 1, frame.size_bytes);
             }
         }
-        Err(e) if e.contains("mutex") || e.contains("camera") => {
+        Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
             println!("Warning: Bracketing test failed (expected in CI): {}", e);
         }
         Err(e) => {
@@ -322,12 +376,12 @@ async fn test_focus_stacking_legacy() {
                     assert!(frame.is_valid());
                 }
             }
-            Err(e) if e.contains("mutex") || e.contains("camera") => {
+            Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
                 println!("Warning: Focus stack test failed (expected in CI): {}", e);
             }
             Err(e) => {
                 // Should not be validation error for valid counts
-                assert!(!e.contains("Focus stack count must be"));
+                assert!(!e.message.contains("Focus stack count must be"));
                 println!("Unexpected focus stack error: {}", e);
             }
         }
@@ -340,7 +394,9 @@ async fn test_focus_stacking_legacy() {
         let result = capture_focus_stack_legacy(device_id.clone(), *count).await;
         assert!(result.is_err());
         if let Err(e) = result {
-            assert!(e.contains("Focus stack count must be between 3 and 20"));
+            assert!(e
+                .message
+                .contains("Focus stack count must be between 3 and 20"));
         }
     }
 }
@@ -350,7 +406,7 @@ async fn test_focus_stacking_legacy() {
 async fn test_hdr_capture() {
     let device_id = TEST_DEVICE_ID.to_string();
 
-    let result = capture_hdr_sequence(device_id).await;
+    let result = capture_hdr_sequence(device_id, None).await;
     match result {
         Ok(frames) => {
             // HDR should capture multiple frames (typically 3-5)
@@ -362,7 +418,7 @@ async fn test_hdr_capture() {
                 assert!(frame.size_bytes > 0);
             }
         }
-        Err(e) if e.contains("mutex") || e.contains("camera") => {
+        Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
             println!("Warning: HDR test failed (expected in CI): {}", e);
         }
         Err(e) => {
@@ -396,7 +452,7 @@ async fn test_camera_capabilities() {
             );
             println!("  Manual focus: {}", capabilities.supports.manual_focus);
         }
-        Err(e) if e.contains("mutex") || e.contains("camera") => {
+        Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
             println!("Warning: Capabilities test failed (expected in CI): {}", e);
         }
         Err(e) => {
@@ -424,7 +480,7 @@ async fn test_performance_metrics() {
             println!("  Processing time: {:.2}ms", metrics.processing_time_ms);
             println!("  Frame drops: {}", metrics.dropped_frames);
         }
-        Err(e) if e.contains("mutex") || e.contains("camera") => {
+        Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
             println!("Warning: Performance test failed (expected in CI): {}", e);
         }
         Err(e) => {
@@ -451,7 +507,7 @@ async fn test_burst_config_validation() {
     let result = capture_burst_sequence(device_id.clone(), invalid_config_zero).await;
     assert!(result.is_err());
     if let Err(e) = result {
-        assert!(e.contains("Invalid burst count"));
+        assert!(e.message.contains("Invalid burst count"));
     }
 
     // Test invalid count (too high)
@@ -467,7 +523,7 @@ async fn test_burst_config_validation() {
     let result = capture_burst_sequence(device_id, invalid_config_high).await;
     assert!(result.is_err());
     if let Err(e) = result {
-        assert!(e.contains("Invalid burst count"));
+        assert!(e.message.contains("Invalid burst count"));
     }
 }
 
@@ -488,7 +544,7 @@ async fn test_advanced_operations_performance() {
             // Should be reasonably fast (under 1 second)
             assert!(controls_time < Duration::from_secs(1));
         }
-        Err(e) if e.contains("mutex") || e.contains("camera") => {
+        Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
             println!("Warning: Performance test skipped (no camera): {}", e);
             return;
         }
@@ -524,7 +580,7 @@ async fn test_advanced_operations_performance() {
             // Should maintain reasonable performance
             assert!(fps >= 1.0); // At least 1 FPS
         }
-        Err(e) if e.contains("mutex") || e.contains("camera") => {
+        Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
             println!("Warning: Burst performance test skipped (no camera): {}", e);
         }
         Err(e) => {
@@ -553,7 +609,7 @@ async fn test_advanced_edge_cases() {
         Ok(frames) => {
             assert_eq!(frames.len(), 2);
         }
-        Err(e) if e.contains("mutex") || e.contains("camera") => {
+        Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
             println!("Warning: Fast interval test skipped: {}", e);
         }
         Err(e) => {
@@ -573,7 +629,7 @@ async fn test_advanced_edge_cases() {
         Ok(_) => {
             // Should handle extreme values
         }
-        Err(e) if e.contains("mutex") || e.contains("camera") => {
+        Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
             println!("Warning: Extreme values test skipped: {}", e);
         }
         Err(e) => {
@@ -609,7 +665,7 @@ async fn test_concurrent_advanced_operations() {
     for result in results {
         match result {
             Ok(Ok(_)) => success_count += 1,
-            Ok(Err(e)) if e.contains("mutex") || e.contains("camera") => {
+            Ok(Err(e)) if e.message.contains("mutex") || e.message.contains("camera") => {
                 expected_failures += 1;
             }
             Ok(Err(e)) => {
@@ -662,7 +718,7 @@ async fn test_resource_management() {
                     assert!(frame.size_bytes > 0);
                 }
             }
-            Err(e) if e.contains("mutex") || e.contains("camera") => {
+            Err(e) if e.message.contains("mutex") || e.message.contains("camera") => {
                 println!("Warning: Resource test {} skipped: {}", i + 1, e);
             }
             Err(e) => {
@@ -688,5 +744,6 @@ async fn test_apply_camera_settings_rejects_invalid_focus() {
     assert!(result.is_err());
     assert!(result
         .unwrap_err()
+        .message
         .contains("Focus distance must be between 0.0"));
 }
@@ -22,7 +22,7 @@ mod commands_capture_tests {
     async fn test_capture_single_photo_success() {
         set_mock_camera_mode("0", MockCaptureMode::Success);
 
-        let result = capture_single_photo(None, None).await;
+        let result = capture_single_photo(None, None, None).await;
         assert!(result.is_ok(), "Single photo capture should succeed");
 
         let frame = result.unwrap();
@@ -36,7 +36,7 @@ mod commands_capture_tests {
     async fn test_capture_single_photo_with_device_id() {
         set_mock_camera_mode("test_camera_1", MockCaptureMode::Success);
 
-        let result = capture_single_photo(Some("test_camera_1".to_string()), None).await;
+        let result = capture_single_photo(Some("test_camera_1".to_string()), None, None).await;
         assert!(
             result.is_ok(),
             "Single photo capture with device ID should succeed"
@@ -55,7 +55,7 @@ mod commands_capture_tests {
 
         let format = CameraFormat::new(1920, 1080, 60.0);
         let result =
-            capture_single_photo(Some("test_camera_format".to_string()), Some(format)).await;
+            capture_single_photo(Some("test_camera_format".to_string()), Some(format), None).await;
 
         assert!(
             result.is_ok(),
@@ -67,7 +67,7 @@ mod commands_capture_tests {
     async fn test_capture_single_photo_failure() {
         set_mock_camera_mode("fail_camera", MockCaptureMode::Failure);
 
-        let result = capture_single_photo(Some("fail_camera".to_string()), None).await;
+        let result = capture_single_photo(Some("fail_camera".to_string()), None, None).await;
         assert!(
             result.is_err(),
             "Single photo capture should fail with Failure mode"
@@ -75,7 +75,7 @@ mod commands_capture_tests {
 
         let error = result.unwrap_err();
         assert!(
-            error.contains("Failed to capture frame"),
+            error.message.contains("Failed to capture frame"),
             "Error should mention capture failure"
         );
     }
@@ -84,7 +84,7 @@ mod commands_capture_tests {
     async fn test_capture_photo_sequence_success() {
         set_mock_camera_mode("seq_camera", MockCaptureMode::Success);
 
-        let result = capture_photo_sequence("seq_camera".to_string(), 3, 50, None).await;
+        let result = capture_photo_sequence("seq_camera".to_string(), 3, 50, None, None).await;
         assert!(result.is_ok(), "Photo sequence capture should succeed");
 
         let frames = result.unwrap();
@@ -106,20 +106,20 @@ mod commands_capture_tests {
 
     #[tokio::test]
     async fn test_capture_photo_sequence_invalid_count() {
-        let result = capture_photo_sequence("test".to_string(), 0, 50, None).await;
+        let result = capture_photo_sequence("test".to_string(), 0, 50, None, None).await;
         assert!(result.is_err(), "Should fail with count 0");
-        assert!(result.unwrap_err().contains("Invalid photo count"));
+        assert!(result.unwrap_err().message.contains("Invalid photo count"));
 
-        let result = capture_photo_sequence("test".to_string(), 25, 50, None).await;
+        let result = capture_photo_sequence("test".to_string(), 25, 50, None, None).await;
         assert!(result.is_err(), "Should fail with count > 20");
-        assert!(result.unwrap_err().contains("Invalid photo count"));
+        assert!(result.unwrap_err().message.contains("Invalid photo count"));
     }
 
     #[tokio::test]
     async fn test_capture_photo_sequence_with_failure() {
         set_mock_camera_mode("seq_fail", MockCaptureMode::Failure);
 
-        let result = capture_photo_sequence("seq_fail".to_string(), 2, 50, None).await;
+        let result = capture_photo_sequence("seq_fail".to_string(), 2, 50, None, None).await;
         assert!(
             result.is_err(),
             "Photo sequence should fail if capture fails"
@@ -127,7 +127,7 @@ mod commands_capture_tests {
 
         let error = result.unwrap_err();
         assert!(
-            error.contains("Failed to capture frame"),
+            error.message.contains("Failed to capture frame"),
             "Error should mention capture failure"
         );
     }
@@ -137,7 +137,7 @@ mod commands_capture_tests {
         set_mock_camera_mode("seq_timing", MockCaptureMode::Success);
 
         let start = std::time::Instant::now();
-        let result = capture_photo_sequence("seq_timing".to_string(), 3, 100, None).await;
+        let result = capture_photo_sequence("seq_timing".to_string(), 3, 100, None, None).await;
         let duration = start.elapsed();
 
         assert!(result.is_ok(), "Sequence capture should succeed");
@@ -152,7 +152,7 @@ mod commands_capture_tests {
     async fn test_start_camera_preview() {
         set_mock_camera_mode("preview_start", MockCaptureMode::Success);
 
-        let result = start_camera_preview("preview_start".to_string(), None).await;
+        let result = start_camera_preview("preview_start".to_string(), None, None).await;
         assert!(result.is_ok(), "Starting preview should succeed");
 
         let message = result.unwrap();
@@ -171,7 +171,7 @@ mod commands_capture_tests {
         set_mock_camera_mode("preview_format", MockCaptureMode::Success);
 
         let format = CameraFormat::new(1280, 720, 30.0);
-        let result = start_camera_preview("preview_format".to_string(), Some(format)).await;
+        let result = start_camera_preview("preview_format".to_string(), Some(format), None).await;
 
         assert!(
             result.is_ok(),
@@ -183,7 +183,7 @@ mod commands_capture_tests {
     async fn test_stop_camera_preview_success() {
         // First start a preview
         set_mock_camera_mode("preview_stop", MockCaptureMode::Success);
-        let _ = start_camera_preview("preview_stop".to_string(), None).await;
+        let _ = start_camera_preview("preview_stop".to_string(), None, None).await;
 
         // Then stop it
         let result = stop_camera_preview("preview_stop".to_string()).await;
@@ -204,7 +204,7 @@ mod commands_capture_tests {
 
         let error = result.unwrap_err();
         assert!(
-            error.contains("No active camera found"),
+            error.message.contains("No active camera found"),
             "Should mention camera not found"
         );
     }
@@ -213,7 +213,7 @@ mod commands_capture_tests {
     async fn test_release_camera_success() {
         // First create a camera by starting preview
         set_mock_camera_mode("release_test", MockCaptureMode::Success);
-        let _ = start_camera_preview("release_test".to_string(), None).await;
+        let _ = start_camera_preview("release_test".to_string(), None, None).await;
 
         // Then release it
         let result = release_camera("release_test".to_string()).await;
@@ -246,7 +246,7 @@ mod commands_capture_tests {
     async fn test_get_capture_stats_active_camera() {
         // First create an active camera
         set_mock_camera_mode("stats_test", MockCaptureMode::Success);
-        let _ = start_camera_preview("stats_test".to_string(), None).await;
+        let _ = start_camera_preview("stats_test".to_string(), None, None).await;
 
         let result = get_capture_stats("stats_test".to_string()).await;
         assert!(result.is_ok(), "Getting stats should succeed");
@@ -299,6 +299,44 @@ mod commands_capture_tests {
         let _ = tokio::fs::remove_file(temp_file).await;
     }
 
+    #[tokio::test]
+    async fn test_save_high_bit_depth_frame_as_16_bit_png() {
+        let samples: Vec<u16> = (0..4 * 2 * 3).map(|i| i * 2731).collect();
+        let data = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let frame = CameraFrame::new(data, 4, 2, "test_device".to_string())
+            .with_format("RGB16".to_string());
+        let temp_file = std::env::temp_dir().join("test_frame_save_rgb16.png");
+
+        save_frame_to_disk(frame, temp_file.to_string_lossy().to_string())
+            .await
+            .expect("16-bit PNG save should succeed");
+
+        let saved = image::open(&temp_file).expect("saved PNG should load");
+        assert_eq!(saved.color(), image::ColorType::Rgb16);
+        assert_eq!(saved.into_rgb16().into_raw(), samples);
+
+        let _ = tokio::fs::remove_file(temp_file).await;
+    }
+
+    #[tokio::test]
+    async fn test_save_depth_frame_as_16_bit_gray_png() {
+        let samples: Vec<u16> = vec![0, 450, 1000, 12_000];
+        let data = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let frame =
+            CameraFrame::new(data, 2, 2, "test_device".to_string()).with_format("Z16".to_string());
+        let temp_file = std::env::temp_dir().join("test_frame_save_z16.png");
+
+        save_frame_to_disk(frame, temp_file.to_string_lossy().to_string())
+            .await
+            .expect("depth PNG save should succeed");
+
+        let saved = image::open(&temp_file).expect("saved PNG should load");
+        assert_eq!(saved.color(), image::ColorType::L16);
+        assert_eq!(saved.into_luma16().into_raw(), samples);
+
+        let _ = tokio::fs::remove_file(temp_file).await;
+    }
+
     #[tokio::test]
     async fn test_save_frame_to_disk_invalid_path() {
         let frame = create_test_frame();
@@ -313,7 +351,7 @@ mod commands_capture_tests {
 
         let error = result.unwrap_err();
         assert!(
-            error.contains("Failed to save frame"),
+            error.message.contains("Failed to save frame"),
             "Should mention save failure"
         );
     }
@@ -388,6 +426,13 @@ mod commands_capture_tests {
             device_id: "test_device".to_string(),
             is_active: true,
             device_info: Some("Test Camera Info".to_string()),
+            paused: false,
+            queued_frames: 2,
+            dropped_frames: 0,
+            capture_resolution: Some((1920, 1080)),
+            preview_resolution: Some((640, 360)),
+            reordered_frames: 0,
+            late_frames: 0,
         };
 
         // Test serialization
@@ -417,8 +462,8 @@ mod commands_capture_tests {
             set_mock_camera_mode(&device_id, MockCaptureMode::Success);
 
             let handle = tokio::spawn(async move {
-                let _ = capture_single_photo(Some(device_id.clone()), None).await;
-                let _ = start_camera_preview(device_id.clone(), None).await;
+                let _ = capture_single_photo(Some(device_id.clone()), None, None).await;
+                let _ = start_camera_preview(device_id.clone(), None, None).await;
                 let _ = get_capture_stats(device_id.clone()).await;
                 let _ = release_camera(device_id).await;
                 i // Return for verification
@@ -443,14 +488,14 @@ mod commands_capture_tests {
         set_mock_camera_mode("error_recovery", MockCaptureMode::Failure);
 
         // First operation should fail
-        let result1 = capture_single_photo(Some("error_recovery".to_string()), None).await;
+        let result1 = capture_single_photo(Some("error_recovery".to_string()), None, None).await;
         assert!(result1.is_err(), "Should fail in failure mode");
 
         // Switch to success mode
         set_mock_camera_mode("error_recovery", MockCaptureMode::Success);
 
         // Subsequent operation should succeed
-        let result2 = capture_single_photo(Some("error_recovery".to_string()), None).await;
+        let result2 = capture_single_photo(Some("error_recovery".to_string()), None, None).await;
         assert!(result2.is_ok(), "Should succeed in success mode");
     }
 
@@ -460,11 +505,11 @@ mod commands_capture_tests {
         set_mock_camera_mode(&device_id, MockCaptureMode::Success);
 
         // 1. Start preview
-        let result = start_camera_preview(device_id.clone(), None).await;
+        let result = start_camera_preview(device_id.clone(), None, None).await;
         assert!(result.is_ok(), "Should start preview");
 
         // 2. Capture some photos
-        let result = capture_single_photo(Some(device_id.clone()), None).await;
+        let result = capture_single_photo(Some(device_id.clone()), None, None).await;
         assert!(result.is_ok(), "Should capture photo");
 
         // 3. Get stats
@@ -545,10 +590,10 @@ mod commands_capture_tests {
         let error = result.unwrap_err();
         println!("Actual error: {}", error);
         assert!(
-            error.contains("Failed to capture")
-                || error.contains("quality")
-                || error.contains("attempt")
-                || error.contains("Capture error"),
+            error.message.contains("Failed to capture")
+                || error.message.contains("quality")
+                || error.message.contains("attempt")
+                || error.message.contains("Capture error"),
             "Error should be descriptive: {}",
             error
         );
@@ -649,7 +694,8 @@ mod commands_capture_tests {
         );
 
         // But captures should fail with this camera
-        let capture_result = capture_single_photo(Some("reconnect_test".to_string()), None).await;
+        let capture_result =
+            capture_single_photo(Some("reconnect_test".to_string()), None, None).await;
         assert!(
             capture_result.is_err(),
             "Captures should fail with failure mode"
@@ -663,7 +709,7 @@ mod commands_capture_tests {
         let start = Instant::now();
         let result = timeout(
             Duration::from_secs(5), // Generous timeout
-            capture_single_photo(Some("timeout_test".to_string()), None),
+            capture_single_photo(Some("timeout_test".to_string()), None, None),
         )
         .await;
 
@@ -703,7 +749,7 @@ mod commands_capture_tests {
             for cap_id in 0..captures_per_camera {
                 let device_id = format!("{}_cam_{}", device_base, cam_id);
                 let handle = tokio::spawn(async move {
-                    let result = capture_single_photo(Some(device_id.clone()), None).await;
+                    let result = capture_single_photo(Some(device_id.clone()), None, None).await;
                     (cam_id, cap_id, device_id, result)
                 });
                 handles.push(handle);
@@ -739,25 +785,25 @@ mod commands_capture_tests {
         set_mock_camera_mode(&device_id, MockCaptureMode::Success);
 
         // Start preview
-        let preview_result = start_camera_preview(device_id.clone(), None).await;
+        let preview_result = start_camera_preview(device_id.clone(), None, None).await;
         assert!(preview_result.is_ok(), "Preview should start");
 
         // Capture should work
-        let capture_result = capture_single_photo(Some(device_id.clone()), None).await;
+        let capture_result = capture_single_photo(Some(device_id.clone()), None, None).await;
         assert!(capture_result.is_ok(), "Initial capture should work");
 
         // Simulate hot unplug by switching to failure mode
         set_mock_camera_mode(&device_id, MockCaptureMode::Failure);
 
         // Captures should start failing
-        let capture_result = capture_single_photo(Some(device_id.clone()), None).await;
+        let capture_result = capture_single_photo(Some(device_id.clone()), None, None).await;
         assert!(capture_result.is_err(), "Capture should fail after unplug");
 
         // Simulate hot plug by switching back to success
         set_mock_camera_mode(&device_id, MockCaptureMode::Success);
 
         // Should be able to capture again
-        let capture_result = capture_single_photo(Some(device_id.clone()), None).await;
+        let capture_result = capture_single_photo(Some(device_id.clone()), None, None).await;
         assert!(capture_result.is_ok(), "Capture should work after replug");
 
         // Cleanup
@@ -782,7 +828,8 @@ mod commands_capture_tests {
             set_mock_camera_mode(&test_device_id, MockCaptureMode::Success);
 
             let result =
-                capture_single_photo(Some(test_device_id.clone()), Some(format.clone())).await;
+                capture_single_photo(Some(test_device_id.clone()), Some(format.clone()), None)
+                    .await;
 
             // Should handle edge case formats gracefully
             match result {
@@ -806,7 +853,7 @@ mod commands_capture_tests {
             set_mock_camera_mode(&device_id, MockCaptureMode::Success);
 
             // Start preview
-            let preview_result = start_camera_preview(device_id.clone(), None).await;
+            let preview_result = start_camera_preview(device_id.clone(), None, None).await;
             assert!(
                 preview_result.is_ok(),
                 "Preview should start for iteration {}",
@@ -895,6 +942,7 @@ mod commands_capture_tests {
                 10,  // 10 photos
                 100, // 100ms interval = ~1 second total
                 None,
+                None,
             )
             .await
         });
@@ -931,17 +979,17 @@ mod commands_capture_tests {
         // Start operations on all cameras
         let camera_ids_clone1 = camera_ids.clone();
         let handle1 = tokio::spawn(async move {
-            capture_single_photo(Some(camera_ids_clone1[0].clone()), None).await
+            capture_single_photo(Some(camera_ids_clone1[0].clone()), None, None).await
         });
 
         let camera_ids_clone2 = camera_ids.clone();
         let handle2 = tokio::spawn(async move {
-            capture_single_photo(Some(camera_ids_clone2[1].clone()), None).await
+            capture_single_photo(Some(camera_ids_clone2[1].clone()), None, None).await
         });
 
         let camera_ids_clone3 = camera_ids.clone();
         let handle3 = tokio::spawn(async move {
-            capture_single_photo(Some(camera_ids_clone3[2].clone()), None).await
+            capture_single_photo(Some(camera_ids_clone3[2].clone()), None, None).await
         });
 
         // Collect results
@@ -959,29 +1007,32 @@ mod commands_capture_tests {
 
         // Test failing camera
         set_mock_camera_mode("error_msg_test", MockCaptureMode::Failure);
-        let result = capture_single_photo(Some("error_msg_test".to_string()), None).await;
+        let result = capture_single_photo(Some("error_msg_test".to_string()), None, None).await;
         assert!(result.is_err(), "Should fail for failing camera");
         let error = result.unwrap_err();
-        assert!(!error.is_empty(), "Error message should not be empty");
         assert!(
-            error.contains("Failed to capture frame"),
+            !error.message.is_empty(),
+            "Error message should not be empty"
+        );
+        assert!(
+            error.message.contains("Failed to capture frame"),
             "Error should be descriptive"
         );
 
         // Test invalid sequence parameters
-        let result = capture_photo_sequence("any".to_string(), 0, 100, None).await;
+        let result = capture_photo_sequence("any".to_string(), 0, 100, None, None).await;
         assert!(result.is_err(), "Should fail for invalid count");
         let error = result.unwrap_err();
         assert!(
-            error.contains("Invalid photo count"),
+            error.message.contains("Invalid photo count"),
             "Error should mention invalid count"
         );
 
-        let result = capture_photo_sequence("any".to_string(), 25, 100, None).await;
+        let result = capture_photo_sequence("any".to_string(), 25, 100, None, None).await;
         assert!(result.is_err(), "Should fail for too many photos");
         let error = result.unwrap_err();
         assert!(
-            error.contains("Invalid photo count"),
+            error.message.contains("Invalid photo count"),
             "Error should mention invalid count"
         );
     }
@@ -993,15 +1044,15 @@ mod commands_capture_tests {
         set_mock_camera_mode(&device_id, MockCaptureMode::Success);
 
         // 1. Single capture
-        let result = capture_single_photo(Some(device_id.clone()), None).await;
+        let result = capture_single_photo(Some(device_id.clone()), None, None).await;
         assert!(result.is_ok(), "Single capture should work");
 
         // 2. Start preview
-        let result = start_camera_preview(device_id.clone(), None).await;
+        let result = start_camera_preview(device_id.clone(), None, None).await;
         assert!(result.is_ok(), "Preview should start");
 
         // 3. Sequence capture while preview is running
-        let result = capture_photo_sequence(device_id.clone(), 3, 10, None).await;
+        let result = capture_photo_sequence(device_id.clone(), 3, 10, None, None).await;
         assert!(result.is_ok(), "Sequence should work with preview running");
 
         // 4. Get stats
@@ -1009,7 +1060,7 @@ mod commands_capture_tests {
         assert!(result.is_ok(), "Stats should be available");
 
         // 5. Another single capture
-        let result = capture_single_photo(Some(device_id.clone()), None).await;
+        let result = capture_single_photo(Some(device_id.clone()), None, None).await;
         assert!(result.is_ok(), "Another single capture should work");
 
         // 6. Stop preview
@@ -1017,7 +1068,7 @@ mod commands_capture_tests {
         assert!(result.is_ok(), "Should stop preview");
 
         // 7. Final capture
-        let result = capture_single_photo(Some(device_id.clone()), None).await;
+        let result = capture_single_photo(Some(device_id.clone()), None, None).await;
         assert!(result.is_ok(), "Final capture should work");
 
         // 8. Release
@@ -1033,6 +1084,11 @@ mod commands_capture_tests {
             device_id: None,
             format: None,
             mode: CaptureMode::Single,
+            warmup_frames: None,
+            timestamp_overlay: false,
+            auto_orient: false,
+            autofocus: false,
+            operation_id: None,
         })
         .await;
         assert!(result.is_ok(), "Consolidated single capture should succeed");
@@ -1053,6 +1109,11 @@ mod commands_capture_tests {
                 count: 5,
                 interval_ms: 0,
             },
+            warmup_frames: None,
+            timestamp_overlay: false,
+            auto_orient: false,
+            autofocus: false,
+            operation_id: None,
         })
         .await;
         assert!(
@@ -1073,6 +1134,11 @@ mod commands_capture_tests {
                 count: 0,
                 interval_ms: 0,
             },
+            warmup_frames: None,
+            timestamp_overlay: false,
+            auto_orient: false,
+            autofocus: false,
+            operation_id: None,
         })
         .await;
         assert!(result.is_err(), "Zero-count sequence should be rejected");
@@ -19,9 +19,12 @@ mod commands_init_tests {
                 assert!(message.len() > 5, "Success message should be descriptive");
             }
             Err(error) => {
-                assert!(!error.is_empty(), "Error message should not be empty");
                 assert!(
-                    error.contains("Failed to initialize"),
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
+                assert!(
+                    error.message.contains("Failed to initialize"),
                     "Error should mention initialization failure"
                 );
             }
@@ -44,9 +47,12 @@ mod commands_init_tests {
                 // Log should have been written (we can't test log content directly in unit tests)
             }
             Err(error) => {
-                assert!(!error.is_empty(), "Error message should not be empty");
                 assert!(
-                    error.contains("Failed to list cameras"),
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
+                assert!(
+                    error.message.contains("Failed to list cameras"),
                     "Error should mention camera listing failure"
                 );
             }
@@ -78,9 +84,12 @@ mod commands_init_tests {
                 );
             }
             Err(error) => {
-                assert!(!error.is_empty(), "Error message should not be empty");
                 assert!(
-                    error.contains("Failed to get platform info"),
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
+                assert!(
+                    error.message.contains("Failed to get platform info"),
                     "Error should mention platform info failure"
                 );
             }
@@ -127,9 +136,12 @@ mod commands_init_tests {
                 }
             }
             Err(error) => {
-                assert!(!error.is_empty(), "Error message should not be empty");
                 assert!(
-                    error.contains("Camera system test failed"),
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
+                assert!(
+                    error.message.contains("Camera system test failed"),
                     "Error should mention test failure"
                 );
             }
@@ -146,9 +158,14 @@ mod commands_init_tests {
                 assert!(!is_available, "Non-existent camera should not be available");
             }
             Err(error) => {
-                assert!(!error.is_empty(), "Error message should not be empty");
                 assert!(
-                    error.contains("Failed to check camera availability"),
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
+                assert!(
+                    error
+                        .message
+                        .contains("Failed to check camera availability"),
                     "Error should mention availability check failure"
                 );
             }
@@ -165,7 +182,10 @@ mod commands_init_tests {
                 assert!(!is_available, "Empty camera ID should not be available");
             }
             Err(error) => {
-                assert!(!error.is_empty(), "Error message should not be empty");
+                assert!(
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
             }
         }
     }
@@ -179,9 +199,13 @@ mod commands_init_tests {
                 panic!("Should not find formats for non-existent camera");
             }
             Err(error) => {
-                assert!(!error.is_empty(), "Error message should not be empty");
                 assert!(
-                    error.contains("not found") || error.contains("Failed to get camera formats"),
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
+                assert!(
+                    error.message.contains("not found")
+                        || error.message.contains("Failed to get camera formats"),
                     "Error should mention camera not found, got: {}",
                     error
                 );
@@ -279,16 +303,28 @@ mod commands_init_tests {
         match availability_result {
             Ok(_) => {} // OK to return false for invalid ID
             Err(error) => {
-                assert!(!error.is_empty(), "Error message should not be empty");
-                assert!(!error.contains("panic"), "Error should not mention panic");
+                assert!(
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
+                assert!(
+                    !error.message.contains("panic"),
+                    "Error should not mention panic"
+                );
             }
         }
 
         match formats_result {
             Ok(_) => panic!("Should not find formats for invalid device"),
             Err(error) => {
-                assert!(!error.is_empty(), "Error message should not be empty");
-                assert!(!error.contains("panic"), "Error should not mention panic");
+                assert!(
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
+                assert!(
+                    !error.message.contains("panic"),
+                    "Error should not mention panic"
+                );
             }
         }
     }
@@ -380,7 +416,7 @@ mod commands_init_tests {
         let init_result = result.unwrap();
         match init_result {
             Ok(msg) => assert!(!msg.is_empty(), "Success message should not be empty"),
-            Err(err) => assert!(!err.is_empty(), "Error message should not be empty"),
+            Err(err) => assert!(!err.message.is_empty(), "Error message should not be empty"),
         }
     }
 
@@ -411,7 +447,7 @@ mod commands_init_tests {
                 }
                 Err(error) => {
                     assert!(
-                        !error.is_empty(),
+                        !error.message.is_empty(),
                         "Error message should not be empty for ID: {}",
                         device_id
                     );
@@ -437,9 +473,13 @@ mod commands_init_tests {
             if should_fail {
                 assert!(result.is_err(), "Should fail for device ID: {}", device_id);
                 let error = result.unwrap_err();
-                assert!(!error.is_empty(), "Error message should not be empty");
                 assert!(
-                    error.contains("not found") || error.contains("Failed to get camera formats"),
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
+                assert!(
+                    error.message.contains("not found")
+                        || error.message.contains("Failed to get camera formats"),
                     "Error should be descriptive for ID: {}",
                     device_id
                 );
@@ -527,7 +567,7 @@ mod commands_init_tests {
                     call_id
                 ),
                 Err(err) => assert!(
-                    !err.is_empty(),
+                    !err.message.is_empty(),
                     "Error message should not be empty for call {}",
                     call_id
                 ),
@@ -578,9 +618,12 @@ mod commands_init_tests {
                 }
             }
             Err(error) => {
-                assert!(!error.is_empty(), "Error message should not be empty");
                 assert!(
-                    error.contains("Camera system test failed"),
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
+                assert!(
+                    error.message.contains("Camera system test failed"),
                     "Error should mention test failure"
                 );
             }
@@ -610,7 +653,7 @@ mod commands_init_tests {
                 }
                 Err(error) => {
                     assert!(
-                        !error.is_empty(),
+                        !error.message.is_empty(),
                         "Error should not be empty for device: {}",
                         device_id
                     );
@@ -640,7 +683,10 @@ mod commands_init_tests {
                 assert!(!info.backend.is_empty(), "Backend should not be empty");
             }
             Err(err) => {
-                assert!(!err.is_empty(), "Platform error should not be empty");
+                assert!(
+                    !err.message.is_empty(),
+                    "Platform error should not be empty"
+                );
             }
         }
 
@@ -651,7 +697,7 @@ mod commands_init_tests {
                 }
             }
             Err(err) => {
-                assert!(!err.is_empty(), "Camera error should not be empty");
+                assert!(!err.message.is_empty(), "Camera error should not be empty");
             }
         }
 
@@ -674,12 +720,12 @@ mod commands_init_tests {
                 }
                 Err(err) => {
                     assert!(
-                        !err.is_empty(),
+                        !err.message.is_empty(),
                         "Error message should not be empty on attempt {}",
                         attempt
                     );
                     assert!(
-                        err.contains("Failed to initialize"),
+                        err.message.contains("Failed to initialize"),
                         "Error should be descriptive on attempt {}",
                         attempt
                     );
@@ -332,8 +332,14 @@ mod commands_permissions_tests {
                 );
             }
             Err(error) => {
-                assert!(!error.is_empty(), "Error message should not be empty");
-                assert!(!error.contains("panic"), "Error should not mention panic");
+                assert!(
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
+                assert!(
+                    !error.message.contains("panic"),
+                    "Error should not mention panic"
+                );
             }
         }
 
@@ -346,8 +352,14 @@ mod commands_permissions_tests {
                 );
             }
             Err(error) => {
-                assert!(!error.is_empty(), "Error message should not be empty");
-                assert!(!error.contains("panic"), "Error should not mention panic");
+                assert!(
+                    !error.message.is_empty(),
+                    "Error message should not be empty"
+                );
+                assert!(
+                    !error.message.contains("panic"),
+                    "Error should not mention panic"
+                );
             }
         }
     }
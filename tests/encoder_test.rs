@@ -605,9 +605,11 @@ fn test_integrated_av_encoding_pipeline() {
     // Create recorder with A/V configuration
     let config = RecordingConfig::new(640, 480, 30.0).with_audio(AudioConfig {
         device_id: None,
+        device_kind: crabcamera::audio::DeviceKind::Input,
         sample_rate: 48000,
         channels: 2,
         bitrate: 128_000,
+        auto_fallback: false,
     });
 
     let mut recorder = Recorder::new(&output, config).expect("Recorder creation should succeed");
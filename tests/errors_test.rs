@@ -52,10 +52,12 @@ mod error_tests {
         let mut errors = vec![
             CameraError::InitializationError("Init error".to_string()),
             CameraError::PermissionDenied("Permission error".to_string()),
+            CameraError::DeviceBusy("Busy error".to_string()),
             CameraError::CaptureError("Capture error".to_string()),
             CameraError::ControlError("Control error".to_string()),
             CameraError::StreamError("Stream error".to_string()),
             CameraError::UnsupportedOperation("Unsupported error".to_string()),
+            CameraError::UnsupportedFormat("XXXX".to_string()),
         ];
 
         // Add conditional feature errors if compiled with those features
@@ -523,10 +525,12 @@ mod error_tests {
             match error {
                 CameraError::InitializationError(msg) => format!("Init: {}", msg),
                 CameraError::PermissionDenied(msg) => format!("Permission: {}", msg),
+                CameraError::DeviceBusy(msg) => format!("Busy: {}", msg),
                 CameraError::CaptureError(msg) => format!("Capture: {}", msg),
                 CameraError::ControlError(msg) => format!("Control: {}", msg),
                 CameraError::StreamError(msg) => format!("Stream: {}", msg),
                 CameraError::UnsupportedOperation(msg) => format!("Unsupported: {}", msg),
+                CameraError::UnsupportedFormat(fourcc) => format!("Format: {}", fourcc),
 
                 #[cfg(feature = "recording")]
                 CameraError::EncodingError(msg) => format!("Encoding: {}", msg),
@@ -13,7 +13,7 @@ use crabcamera::focus_stack::{
     align::{align_frames, apply_alignment},
     capture::{capture_focus_brackets, capture_focus_sequence},
     merge::merge_frames,
-    FocusStackConfig, FocusStackError,
+    AlignmentMethod, FocusStackConfig, FocusStackError,
 };
 use crabcamera::types::{CameraFormat, CameraFrame};
 use std::time::Instant;
@@ -151,6 +151,10 @@ fn test_focus_stack_config_validation() {
     assert_eq!(default_config.focus_start, 0.0);
     assert_eq!(default_config.focus_end, 1.0);
     assert!(default_config.enable_alignment);
+    assert_eq!(
+        default_config.alignment_method,
+        AlignmentMethod::TranslationOnly
+    );
     assert_eq!(default_config.sharpness_threshold, 0.5);
     assert_eq!(default_config.blend_levels, 5);
 
@@ -175,6 +179,7 @@ async fn test_focus_sequence_capture() {
         focus_start: 0.0,
         focus_end: 1.0,
         enable_alignment: true,
+        alignment_method: AlignmentMethod::default(),
         sharpness_threshold: 0.5,
         blend_levels: 3,
     };
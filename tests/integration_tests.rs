@@ -26,7 +26,7 @@ mod integration_tests {
         match init_result {
             Ok(msg) => assert!(!msg.is_empty(), "Init message should not be empty"),
             Err(err) => assert!(
-                err.contains("Failed to initialize"),
+                err.message.contains("Failed to initialize"),
                 "Error should be descriptive"
             ),
         }
@@ -50,7 +50,7 @@ mod integration_tests {
         let _ = availability; // Consume result, either Ok or Err is acceptable
 
         // 4. Start camera preview
-        let preview_result = start_camera_preview(device_id.clone(), None).await;
+        let preview_result = start_camera_preview(device_id.clone(), None, None).await;
         assert!(
             preview_result.is_ok(),
             "Starting preview should succeed with mock camera"
@@ -67,7 +67,7 @@ mod integration_tests {
         );
 
         // 6. Capture single photo
-        let single_result = capture_single_photo(Some(device_id.clone()), None).await;
+        let single_result = capture_single_photo(Some(device_id.clone()), None, None).await;
         assert!(single_result.is_ok(), "Single photo capture should succeed");
         let frame = single_result.unwrap();
         assert!(
@@ -76,7 +76,7 @@ mod integration_tests {
         );
 
         // 7. Capture photo sequence
-        let sequence_result = capture_photo_sequence(device_id.clone(), 3, 50, None).await;
+        let sequence_result = capture_photo_sequence(device_id.clone(), 3, 50, None, None).await;
         assert!(sequence_result.is_ok(), "Photo sequence should succeed");
         let frames = sequence_result.unwrap();
         assert_eq!(frames.len(), 3, "Should capture 3 frames");
@@ -104,16 +104,16 @@ mod integration_tests {
 
         // Start with success to establish camera
         set_mock_camera_mode(&device_id, MockCaptureMode::Success);
-        let _ = start_camera_preview(device_id.clone(), None).await;
+        let _ = start_camera_preview(device_id.clone(), None, None).await;
 
         // Switch to failure mode
         set_mock_camera_mode(&device_id, MockCaptureMode::Failure);
 
         // Test capture failures
-        let single_result = capture_single_photo(Some(device_id.clone()), None).await;
+        let single_result = capture_single_photo(Some(device_id.clone()), None, None).await;
         assert!(single_result.is_err(), "Should fail with failure mode");
 
-        let sequence_result = capture_photo_sequence(device_id.clone(), 2, 50, None).await;
+        let sequence_result = capture_photo_sequence(device_id.clone(), 2, 50, None, None).await;
         assert!(
             sequence_result.is_err(),
             "Sequence should fail with failure mode"
@@ -122,13 +122,13 @@ mod integration_tests {
         // Error messages should be descriptive
         let error = single_result.unwrap_err();
         assert!(
-            error.contains("Failed to capture frame"),
+            error.message.contains("Failed to capture frame"),
             "Error should mention capture failure"
         );
 
         // Switch back to success mode - operations should recover
         set_mock_camera_mode(&device_id, MockCaptureMode::Success);
-        let recovery_result = capture_single_photo(Some(device_id.clone()), None).await;
+        let recovery_result = capture_single_photo(Some(device_id.clone()), None, None).await;
         assert!(
             recovery_result.is_ok(),
             "Should recover after switching to success mode"
@@ -155,7 +155,7 @@ mod integration_tests {
         // Start previews for all cameras
         let mut preview_results = Vec::new();
         for camera_id in &camera_ids {
-            let result = start_camera_preview(camera_id.clone(), None).await;
+            let result = start_camera_preview(camera_id.clone(), None, None).await;
             preview_results.push((camera_id.clone(), result));
         }
 
@@ -170,7 +170,7 @@ mod integration_tests {
 
         // Capture from all cameras
         for camera_id in &camera_ids {
-            let capture_result = capture_single_photo(Some(camera_id.clone()), None).await;
+            let capture_result = capture_single_photo(Some(camera_id.clone()), None, None).await;
             assert!(
                 capture_result.is_ok(),
                 "Capture should succeed for camera {}",
@@ -228,7 +228,7 @@ mod integration_tests {
         set_mock_camera_mode(&device_id, MockCaptureMode::Success);
 
         // Start preview first
-        let preview_result = start_camera_preview(device_id.clone(), None).await;
+        let preview_result = start_camera_preview(device_id.clone(), None, None).await;
         assert!(preview_result.is_ok(), "Preview should start successfully");
 
         // Launch multiple concurrent operations
@@ -238,7 +238,7 @@ mod integration_tests {
         for i in 0..5 {
             let device_id_clone = device_id.clone();
             let handle = tokio::spawn(async move {
-                let result = capture_single_photo(Some(device_id_clone), None).await;
+                let result = capture_single_photo(Some(device_id_clone), None, None).await;
                 (i, result)
             });
             handles.push(handle);
@@ -290,6 +290,7 @@ mod integration_tests {
             let _capture_result = capture_single_photo(
                 Some(format!("{}/_format_{}", device_id, i)),
                 Some(format.clone()),
+                None,
             )
             .await;
 
@@ -299,9 +300,12 @@ mod integration_tests {
                 MockCaptureMode::Success,
             );
 
-            let capture_result =
-                capture_single_photo(Some(format!("{}_format_{}", device_id, i)), Some(format))
-                    .await;
+            let capture_result = capture_single_photo(
+                Some(format!("{}_format_{}", device_id, i)),
+                Some(format),
+                None,
+            )
+            .await;
             assert!(
                 capture_result.is_ok(),
                 "Capture with format {} should succeed",
@@ -335,7 +339,7 @@ mod integration_tests {
             }
             Err(error) => {
                 assert!(
-                    error.contains("test failed"),
+                    error.message.contains("test failed"),
                     "Error should mention test failure"
                 );
             }
@@ -353,7 +357,7 @@ mod integration_tests {
             }
             Err(error) => {
                 assert!(
-                    error.contains("Failed to list cameras"),
+                    error.message.contains("Failed to list cameras"),
                     "Error should be descriptive"
                 );
             }
@@ -368,34 +372,35 @@ mod integration_tests {
         // Test various edge cases
 
         // Empty device ID
-        let _empty_result = capture_single_photo(Some("".to_string()), None).await;
+        let _empty_result = capture_single_photo(Some("".to_string()), None, None).await;
         // Should either succeed with empty string or fail gracefully
 
         // Very long device ID
         let long_id = "a".repeat(1000);
         set_mock_camera_mode(&long_id, MockCaptureMode::Success);
-        let long_result = capture_single_photo(Some(long_id.clone()), None).await;
+        let long_result = capture_single_photo(Some(long_id.clone()), None, None).await;
         assert!(long_result.is_ok(), "Should handle long device IDs");
 
         // Special characters in device ID
         let special_id = "test-cam_123.device@domain:8080/path?query=value#fragment".to_string();
         set_mock_camera_mode(&special_id, MockCaptureMode::Success);
-        let special_result = capture_single_photo(Some(special_id), None).await;
+        let special_result = capture_single_photo(Some(special_id), None, None).await;
         assert!(
             special_result.is_ok(),
             "Should handle special characters in device ID"
         );
 
         // Invalid sequence parameters
-        let invalid_count = capture_photo_sequence("test".to_string(), 0, 100, None).await;
+        let invalid_count = capture_photo_sequence("test".to_string(), 0, 100, None, None).await;
         assert!(invalid_count.is_err(), "Should reject invalid count");
 
-        let too_many = capture_photo_sequence("test".to_string(), 100, 100, None).await;
+        let too_many = capture_photo_sequence("test".to_string(), 100, 100, None, None).await;
         assert!(too_many.is_err(), "Should reject too many photos");
 
         // Very short interval
         set_mock_camera_mode("short_interval", MockCaptureMode::Success);
-        let short_interval = capture_photo_sequence("short_interval".to_string(), 2, 1, None).await;
+        let short_interval =
+            capture_photo_sequence("short_interval".to_string(), 2, 1, None, None).await;
         assert!(short_interval.is_ok(), "Should handle short intervals");
     }
 
@@ -411,7 +416,7 @@ mod integration_tests {
             set_mock_camera_mode(&test_id, MockCaptureMode::Success);
 
             // Start preview
-            let preview_result = start_camera_preview(test_id.clone(), None).await;
+            let preview_result = start_camera_preview(test_id.clone(), None, None).await;
             assert!(
                 preview_result.is_ok(),
                 "Preview should start for camera {}",
@@ -419,7 +424,7 @@ mod integration_tests {
             );
 
             // Capture a frame
-            let capture_result = capture_single_photo(Some(test_id.clone()), None).await;
+            let capture_result = capture_single_photo(Some(test_id.clone()), None, None).await;
             assert!(
                 capture_result.is_ok(),
                 "Capture should succeed for camera {}",
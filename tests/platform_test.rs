@@ -445,6 +445,11 @@ mod platform_tests {
                 for camera in cameras {
                     assert!(!camera.id.is_empty(), "Camera ID should not be empty");
                     assert!(!camera.name.is_empty(), "Camera name should not be empty");
+                    assert!(!camera.stable_id.is_empty(), "Stable ID should be set");
+                    assert_eq!(
+                        CameraSystem::resolve_device_id(&camera.stable_id),
+                        camera.id
+                    );
                     // is_available can be true or false - both are valid
                 }
             }
@@ -453,6 +458,9 @@ mod platform_tests {
             }
         }
 
+        // Numeric ids resolve to themselves without enumeration
+        assert_eq!(CameraSystem::resolve_device_id("0"), "0");
+
         // Test system testing
         let test_result = CameraSystem::test_system();
         match test_result {
@@ -574,6 +582,7 @@ mod platform_tests {
                 ),
                 ("camera3".to_string(), CameraTestResult::NotAvailable),
             ],
+            elapsed_ms: 12,
         };
 
         // Test serialization
@@ -28,9 +28,11 @@ fn test_synthetic_av_recording() {
     // Configure with audio
     let config = RecordingConfig::new(width, height, fps).with_audio(AudioConfig {
         device_id: None, // Will use synthetic data
+        device_kind: crabcamera::audio::DeviceKind::Input,
         sample_rate: 48000,
         channels: 2,
         bitrate: 128_000,
+        auto_fallback: false,
     });
 
     let mut recorder = Recorder::new(&output, config).expect("Create recorder");
@@ -5,7 +5,7 @@
 use crabcamera::types::{
     BurstConfig, CameraCapabilities, CameraControls, CameraDeviceInfo, CameraFormat, CameraFrame,
     CameraInitParams, CameraPerformanceMetrics, ControlApplicationResult, ExposureBracketing,
-    FrameMetadata, Platform, WhiteBalance,
+    FrameMetadata, Platform, Rect, WhiteBalance,
 };
 
 #[cfg(test)]
@@ -318,6 +318,7 @@ mod camera_performance_tests {
             processing_time_ms: 5.5,
             memory_usage_mb: 128.5,
             fps_actual: 59.94,
+            processing_backlog_ms: 4.0,
             dropped_frames: 3,
             buffer_overruns: 1,
             quality_score: 0.95,
@@ -465,6 +466,7 @@ mod frame_metadata_tests {
         assert!(metadata.flash_fired.is_none());
         assert!(metadata.scene_mode.is_none());
         assert!(metadata.capture_settings.is_none());
+        assert!(metadata.zoom.is_none());
     }
 
     #[test]
@@ -475,9 +477,19 @@ mod frame_metadata_tests {
             white_balance: Some(WhiteBalance::Daylight),
             focus_distance: Some(0.5),
             aperture: Some(5.6),
+            zoom: None,
             flash_fired: Some(true),
             scene_mode: Some("Portrait".to_string()),
             capture_settings: Some(CameraControls::professional()),
+            hardware_timestamp: None,
+            faces: None,
+            crop: None,
+            orientation: None,
+            applied_rotation: None,
+            depth_units: None,
+            stream_role: None,
+            source_format: None,
+            content_hash: None,
         };
 
         assert!(metadata.exposure_time.is_some());
@@ -498,9 +510,19 @@ mod frame_metadata_tests {
             white_balance: Some(WhiteBalance::Custom(5200)),
             focus_distance: Some(0.75),
             aperture: Some(2.8),
+            zoom: Some(2.0),
             flash_fired: Some(false),
             scene_mode: Some("Night".to_string()),
             capture_settings: Some(CameraControls::default()),
+            hardware_timestamp: Some(12.5),
+            faces: Some(vec![Rect::new(10, 20, 64, 64)]),
+            crop: Some(Rect::new(0, 0, 320, 240)),
+            orientation: None,
+            applied_rotation: None,
+            depth_units: None,
+            stream_role: None,
+            source_format: None,
+            content_hash: None,
         };
 
         let json = serde_json::to_string(&metadata).unwrap();
@@ -511,8 +533,20 @@ mod frame_metadata_tests {
         assert_eq!(deserialized.white_balance, metadata.white_balance);
         assert_eq!(deserialized.focus_distance, metadata.focus_distance);
         assert_eq!(deserialized.aperture, metadata.aperture);
+        assert_eq!(deserialized.zoom, metadata.zoom);
         assert_eq!(deserialized.flash_fired, metadata.flash_fired);
         assert_eq!(deserialized.scene_mode, metadata.scene_mode);
+        assert_eq!(deserialized.hardware_timestamp, metadata.hardware_timestamp);
+        assert_eq!(deserialized.faces, metadata.faces);
+        assert_eq!(deserialized.crop, metadata.crop);
+    }
+
+    #[test]
+    fn test_rect_center_and_area() {
+        let rect = Rect::new(10, 20, 64, 32);
+        assert_eq!(rect.center(), (42.0, 36.0));
+        assert_eq!(rect.area(), 2048);
+        assert!(FrameMetadata::default().faces.is_none());
     }
 
     #[test]
@@ -523,9 +557,19 @@ mod frame_metadata_tests {
             white_balance: Some(WhiteBalance::Auto),
             focus_distance: None,
             aperture: None,
+            zoom: None,
             flash_fired: Some(false),
             scene_mode: Some("Auto".to_string()),
             capture_settings: None,
+            hardware_timestamp: None,
+            faces: None,
+            crop: None,
+            orientation: None,
+            applied_rotation: None,
+            depth_units: None,
+            stream_role: None,
+            source_format: None,
+            content_hash: None,
         };
 
         let cloned = metadata.clone();